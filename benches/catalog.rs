@@ -0,0 +1,32 @@
+//! Catalog construction and connector lookup.
+//!
+//! Lookups rebuild the catalog on every call, so these numbers bound the cost of
+//! anything that resolves a `connector.class` per connector or per field.
+
+use connect_util::types::ConnectorDefinition;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+fn catalog(c: &mut Criterion) {
+    let names: Vec<String> = ConnectorDefinition::get_all_connectors()
+        .into_iter()
+        .map(|connector| connector.name)
+        .collect();
+
+    c.bench_function("get_all_connectors", |b| {
+        b.iter(ConnectorDefinition::get_all_connectors)
+    });
+    c.bench_function("get_connector_by_name/every connector", |b| {
+        b.iter(|| {
+            for name in &names {
+                black_box(ConnectorDefinition::get_connector_by_name(black_box(name)));
+            }
+        })
+    });
+    c.bench_function("get_connector_by_name/unknown", |b| {
+        b.iter(|| ConnectorDefinition::get_connector_by_name(black_box("NoSuchConnector")))
+    });
+}
+
+criterion_group!(benches, catalog);
+criterion_main!(benches);
@@ -0,0 +1,50 @@
+//! Terraform generation for every connector in the catalog.
+
+use connect_util::terraform::TerraformGenerator;
+use connect_util::types::{CompanionResources, ConnectorDefinition, TerraformConfigOptions};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+fn options(connector: &ConnectorDefinition) -> TerraformConfigOptions {
+    TerraformConfigOptions {
+        connector_name: format!("{}-bench", connector.name.to_lowercase()),
+        connector: connector.clone(),
+        topics: vec!["orders".to_string()],
+        ..Default::default()
+    }
+}
+
+fn generate(c: &mut Criterion) {
+    let connectors = ConnectorDefinition::get_all_connectors();
+    let generator = TerraformGenerator;
+
+    c.bench_function("generate_connector_config/every connector", |b| {
+        b.iter(|| {
+            for connector in &connectors {
+                black_box(
+                    generator
+                        .generate_connector_config(options(connector))
+                        .unwrap(),
+                );
+            }
+        })
+    });
+    c.bench_function("generate project files/every connector", |b| {
+        b.iter(|| {
+            for connector in &connectors {
+                let options = options(connector);
+                let name = options.connector_name.clone();
+                black_box(generator.generate_connector_config(options).unwrap());
+                black_box(
+                    generator
+                        .generate_variables_config(&CompanionResources::default(), &[])
+                        .unwrap(),
+                );
+                black_box(generator.generate_outputs_config(&name).unwrap());
+            }
+        })
+    });
+}
+
+criterion_group!(benches, generate);
+criterion_main!(benches);
@@ -0,0 +1,137 @@
+//! Memory and time of linting generated mono-files of increasing size.
+//!
+//! Run with `cargo bench --bench large_file`. Each file is read and parsed once per
+//! pass and modules are processed one at a time, so peak heap should grow with the
+//! largest file rather than with the number of checks or modules. Without
+//! `--bench` (as under `cargo test --benches`) it runs one small case as a smoke test.
+
+use connect_util::app::ConnectUtilApp;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// The system allocator, tracking live and peak heap bytes
+struct Counting;
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let live = LIVE.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(live, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Peak heap above the starting point and the time taken by `f`
+fn measure<T>(f: impl FnOnce() -> T) -> (T, usize, Duration) {
+    let baseline = LIVE.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+    let started = Instant::now();
+    let result = f();
+    let elapsed = started.elapsed();
+    (result, PEAK.load(Ordering::Relaxed) - baseline, elapsed)
+}
+
+/// A module file with `count` Datagen connectors sharing a `locals` block
+fn mono_file(count: usize) -> String {
+    let mut content = String::from(
+        r#"locals {
+  common_config = {
+    "kafka.auth.mode"          = "SERVICE_ACCOUNT"
+    "kafka.service.account.id" = var.service_account_id
+    "output.data.format"       = "JSON"
+    "tasks.max"                = "1"
+  }
+}
+
+variable "environment_id" {}
+variable "kafka_cluster_id" {}
+variable "service_account_id" {}
+"#,
+    );
+    for i in 0..count {
+        content.push_str(&format!(
+            r#"
+resource "confluent_connector" "orders_{i}" {{
+  environment {{
+    id = var.environment_id
+  }}
+  kafka_cluster {{
+    id = var.kafka_cluster_id
+  }}
+  config_sensitive = {{}}
+  config_nonsensitive = merge(local.common_config, {{
+    "connector.class" = "DatagenSource"
+    "name"            = "orders-{i}"
+    "kafka.topic"     = "orders-{i}"
+    "quickstart"      = "ORDERS"
+  }})
+}}
+"#
+        ));
+    }
+    content
+}
+
+fn mib(bytes: usize) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+/// Writes `modules` copies of a `connectors`-connector file and reports one
+/// `lint_directory` pass and one `parse_terraform_configs` call over it
+fn run(app: &ConnectUtilApp, modules: usize, connectors: usize) {
+    let dir = tempfile::tempdir().unwrap();
+    let content = mono_file(connectors);
+    for module in 0..modules {
+        let module_dir = dir.path().join(format!("module_{}", module));
+        std::fs::create_dir(&module_dir).unwrap();
+        std::fs::write(module_dir.join("main.tf"), &content).unwrap();
+    }
+    let root = dir.path().to_string_lossy();
+
+    let (issues, lint_peak, lint_time) = measure(|| app.lint_directory(&root).unwrap());
+    let (configs, parse_peak, parse_time) =
+        measure(|| app.parse_terraform_configs(&content).unwrap());
+    assert_eq!(configs.len(), connectors);
+
+    println!(
+        "{:>2} module(s) x {:>5} connectors  file {:>7.2} MiB  lint: peak {:>8.2} MiB ({:>5.1}x file) {:>8.1?} ({} issue(s))  parse: peak {:>8.2} MiB ({:>5.1}x file) {:>8.1?}",
+        modules,
+        connectors,
+        mib(content.len()),
+        mib(lint_peak),
+        lint_peak as f64 / content.len() as f64,
+        lint_time,
+        issues.len(),
+        mib(parse_peak),
+        parse_peak as f64 / content.len() as f64,
+        parse_time,
+    );
+}
+
+fn main() {
+    let app = futures::executor::block_on(ConnectUtilApp::new()).unwrap();
+    if !std::env::args().any(|arg| arg == "--bench") {
+        run(&app, 1, 50);
+        return;
+    }
+    // Peak per file size should stay flat as files grow, and adding modules
+    // should not raise it since each is dropped before the next is loaded
+    for connectors in [1_000, 4_000, 16_000] {
+        run(&app, 1, connectors);
+    }
+    run(&app, 8, 1_000);
+}
@@ -0,0 +1,83 @@
+//! Parsing of representative Terraform files: one generated file per connector,
+//! and a module of every connector behind a shared `locals` block.
+
+use connect_util::app::ConnectUtilApp;
+use connect_util::parser::parse_body;
+use connect_util::terraform::TerraformGenerator;
+use connect_util::types::{ConnectorDefinition, TerraformConfigOptions};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::hint::black_box;
+
+/// The generated file of every connector in the catalog
+fn generated_files() -> Vec<String> {
+    ConnectorDefinition::get_all_connectors()
+        .into_iter()
+        .map(|connector| {
+            let options = TerraformConfigOptions {
+                connector_name: format!("{}-bench", connector.name.to_lowercase()),
+                connector,
+                topics: vec!["orders".to_string()],
+                ..Default::default()
+            };
+            TerraformGenerator
+                .generate_connector_config(options)
+                .unwrap()
+        })
+        .collect()
+}
+
+/// A module file of every connector, each merging a shared `locals` block
+fn module_file(files: &[String]) -> String {
+    let mut content = String::from(
+        r#"locals {
+  common_config = {
+    "kafka.auth.mode" = "SERVICE_ACCOUNT"
+    "tasks.max"       = "1"
+  }
+}
+"#,
+    );
+    for file in files {
+        let start = file.find("config_nonsensitive = {").unwrap();
+        let end = start + file[start..].find("\n  }\n").unwrap();
+        content.push('\n');
+        content.push_str(&file[..start]);
+        content.push_str("config_nonsensitive = merge(local.common_config, {");
+        content.push_str(&file[start + "config_nonsensitive = {".len()..end]);
+        content.push_str("\n  })\n");
+        content.push_str(&file[end + "\n  }\n".len()..]);
+    }
+    content
+}
+
+fn parse(c: &mut Criterion) {
+    let app = futures::executor::block_on(ConnectUtilApp::new()).unwrap();
+    let files = generated_files();
+    let module = module_file(&files);
+    let total: usize = files.iter().map(String::len).sum();
+
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Bytes(total as u64));
+    group.bench_function("parse_body/every connector file", |b| {
+        b.iter(|| {
+            for file in &files {
+                black_box(parse_body(black_box(file)).unwrap());
+            }
+        })
+    });
+    group.bench_function("parse_terraform_configs/every connector file", |b| {
+        b.iter(|| {
+            for file in &files {
+                black_box(app.parse_terraform_configs(black_box(file)).unwrap());
+            }
+        })
+    });
+    group.throughput(Throughput::Bytes(module.len() as u64));
+    group.bench_function("parse_terraform_configs/module with locals", |b| {
+        b.iter(|| app.parse_terraform_configs(black_box(&module)).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
@@ -0,0 +1,18 @@
+//! Feeds arbitrary text to the connector parser, which must return configs or an
+//! error for any input and never panic
+#![no_main]
+
+use connect_util::app::ConnectUtilApp;
+use libfuzzer_sys::fuzz_target;
+use std::sync::OnceLock;
+
+/// Built once, since loading the catalog for every input would dominate the run
+static APP: OnceLock<ConnectUtilApp> = OnceLock::new();
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(content) = std::str::from_utf8(data) else {
+        return;
+    };
+    let app = APP.get_or_init(|| futures::executor::block_on(ConnectUtilApp::new()).unwrap());
+    let _ = app.parse_terraform_configs(content);
+});
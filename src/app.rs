@@ -1,143 +1,212 @@
+use crate::audit::{
+    connector_data_sources, is_connector_module, other_blocks, render_inventory, InventoryEntry,
+    InventoryFormat,
+};
+use crate::catalog::{diff_catalogs, fingerprint, CatalogBundle, CatalogStats};
+use crate::cloud::{
+    CloudEnvironment, ConnectApi, ConnectorOffsets, KafkaCluster, LiveConnector, OffsetReset,
+    OrgApi,
+};
+use crate::cluster::ClusterType;
+use crate::conflicts::{find_conflicts, ModuleConnector};
+use crate::connect::ConnectRestApi;
+use crate::convert::{
+    connect_rest_payload, convert_json, convert_properties, self_managed_connector,
+    ConvertedConnector,
+};
+use crate::coverage::{coverage, ConnectorCoverage, CoverageStatus};
+use crate::debezium::{self, Dialect};
+use crate::definitions::config_schema;
+use crate::edit::TerraformFile;
+use crate::enrich::{enrich, CatalogMetadata, EnrichReport};
 use crate::error::ConnectUtilError;
+use crate::health::{config_drift, ConnectorHealth, HealthFormat, HealthReport};
+use crate::i18n;
+use crate::instances::{instances, Instances};
+use crate::ksql::{generate_streams, StreamScaffold};
+use crate::lifecycle::{connector_ignore_changes, unset_ignored_keys};
+use crate::lint::{
+    base_label, block_line, ruleset_json, to_markdown, to_tflint_json, LintIssue, LintRule,
+};
+use crate::locals::{collect_locals, module_locals, resolve, Locals};
+use crate::merge::merge_connector;
+use crate::metadata::{annotate, parse_metadata};
+use crate::notify::{Notifier, NotifyFormat, ValidationSummary};
+use crate::order::{
+    declared_dependencies, plan_order, render_order, topic_resources, ModuleOrder, OrderFormat,
+};
+use crate::parser::{parse_body, template_text, ModuleSources, SourceFile};
+use crate::plan::{parse_plan, PlannedConnector};
+use crate::policy::{NamingRules, Policy};
+use crate::profile::ValidationProfile;
+use crate::progress::Progress;
+use crate::provenance::{self, GenerationReport};
+use crate::record_schema::{self, RecordSchema};
+use crate::remote::{schema_findings, SchemaCache};
+use crate::rename::{rename_prefix, rename_resource};
+use crate::reorganize::{merge_files, split_connectors};
+use crate::review::{
+    actions, editor_command, insert_suppression, rename_connector_name, suggested_fix,
+    ReviewAction, Reviewer, SuggestedFix,
+};
+use crate::rotation::{render_checklist, rotate_references, rotation_checklist};
+use crate::say;
+use crate::scaffold::{scaffold, CiProvider};
+use crate::scan::{collect_terraform_files, ReportFormat, ScanReport, SecretScanner};
+use crate::schema_registry;
+use crate::snapshot;
+use crate::sops;
+use crate::stamp::{restamp, stamp, stamps};
+use crate::state::ManagedConnectors;
+use crate::stats::{FileTiming, ValidationStats};
+use crate::stdio;
+use crate::storage::{self, Partitioner};
+use crate::suppress::Suppressions;
+use crate::tables::{
+    self, parse_table_patterns, table_warnings, TABLE_EXCLUDE_LIST, TABLE_INCLUDE_LIST,
+};
 use crate::terraform::TerraformGenerator;
 use crate::types::{
-    ConnectorConfig, ConnectorDefinition, ConnectorOptions, ConnectorType, TerraformConfigOptions,
+    is_dynamic_value, CompanionResources, ConfigField, ConnectorConfig, ConnectorDefinition,
+    ConnectorFamily, ConnectorOptions, ConnectorType, DataFormat, DebeziumOptions, Environment,
+    EnvironmentLayout, GenerateTarget, GeneratedFile, Newline, OutputLayout, SecretsBackend,
+    StorageLayoutOptions, TerraformConfigOptions, TerraformProvider, TopicStrategy, WritePlan,
 };
-use dialoguer::{theme::ColorfulTheme, FuzzySelect, Input, Select};
+use crate::variables::{group_by_module, ModuleVariables, VariableFinding};
+use crate::where_used::{find_usages, render_usages, UsageFormat, UsageQuery, UsageReport};
+use dialoguer::{theme::ColorfulTheme, Confirm, FuzzySelect, Input, Select};
+use futures::StreamExt;
 use hcl::{Body, Expression};
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Findings for a file or directory, with every connector and file checked
+struct ValidationFindings {
+    issues: Vec<LintIssue>,
+    entries: Vec<InventoryEntry>,
+    timings: Vec<FileTiming>,
+}
+
+impl ValidationFindings {
+    /// `(file, label)` of every connector checked
+    fn connectors(&self) -> Vec<(PathBuf, String)> {
+        self.entries
+            .iter()
+            .map(|entry| (entry.file.clone(), entry.resource.clone()))
+            .collect()
+    }
+
+    fn stats(&self) -> ValidationStats {
+        ValidationStats::collect(&self.timings, &self.entries, &self.issues)
+    }
+}
 
 type TerraformParseResults = Result<Vec<ConnectorConfig>, ConnectUtilError>;
 
+fn parse_error(error: &hcl::Error) -> ConnectUtilError {
+    ConnectUtilError::Config(format!("Failed to parse Terraform file: {}", error))
+}
+
+/// An entry in the first step of the interactive connector picker
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ConnectorMenuEntry {
+    /// A recently used connector, by index into the available connectors
+    Connector(usize),
+    Family(ConnectorFamily),
+}
+
 /// Main application struct for the Connect Utility
-pub struct ConnectUtilApp;
+pub struct ConnectUtilApp {
+    naming: NamingRules,
+    /// `validate --profile` severities applied under every policy file
+    profile: Option<ValidationProfile>,
+    /// File that standard input stands in for, from `--stdin-filename`
+    stdin_filename: Option<PathBuf>,
+    /// Where failed remote checks snapshot the local and live configs, from `--debug-dir`
+    debug_dir: Option<PathBuf>,
+}
 
 impl ConnectUtilApp {
     /// Creates a new instance of ConnectUtilApp
     pub async fn new() -> Result<Self, ConnectUtilError> {
-        Ok(Self)
+        Ok(Self {
+            naming: NamingRules::default(),
+            profile: None,
+            stdin_filename: None,
+            debug_dir: None,
+        })
     }
 
-    /// Non-interactive version for testing and programmatic use
-    /// Generates Terraform configuration without user prompts
-    pub fn generate_terraform_non_interactive(
-        &self,
-        options: ConnectorOptions,
-    ) -> Result<String, ConnectUtilError> {
-        // Validate required options
-        let connector_name = options.name.ok_or_else(|| {
-            ConnectUtilError::Config(
-                "Connector name is required for non-interactive mode".to_string(),
-            )
-        })?;
-
-        // Get connector type from options or default to Source
-        let connector_type = ConnectorType::Source; // Default for non-interactive
-        let available_connectors = ConnectorDefinition::get_connectors_by_type(&connector_type);
-        let selected_connector = available_connectors
-            .first()
-            .ok_or_else(|| ConnectUtilError::Config("No connectors available".to_string()))?;
-
-        // Get topics - empty for non-interactive mode
-        let topics = vec![];
+    /// Enforces the given naming conventions during generation and validation
+    pub fn with_naming_rules(mut self, naming: NamingRules) -> Self {
+        self.naming = naming;
+        self
+    }
 
-        // Generate Terraform configuration
-        let terraform_options = TerraformConfigOptions {
-            connector_name,
-            connector: selected_connector.clone(),
-            topics,
-            input_data_format: None,
-            output_data_format: None,
-        };
+    /// Validates with a profile's rule severities, under the policy file's own
+    pub fn with_validation_profile(mut self, profile: Option<ValidationProfile>) -> Self {
+        self.profile = profile;
+        self
+    }
 
-        let generator = TerraformGenerator;
-        generator.generate_connector_config(terraform_options)
+    /// Checks `-` input as if it were this file: findings name it, policy overrides
+    /// match it, and its directory's other files form its module
+    pub fn with_stdin_filename(mut self, path: Option<PathBuf>) -> Self {
+        self.stdin_filename = path;
+        self
     }
 
-    #[cfg(not(tarpaulin_include))]
-    pub async fn generate_terraform_interactive(
-        &mut self,
-        options: ConnectorOptions,
-    ) -> Result<(), ConnectUtilError> {
-        println!("🚀 Welcome to the Kafka Connect Terraform Generator!");
-        println!();
+    /// Writes the local and live configs of each connector that fails a remote
+    /// check into `dir`, for diffing and bug reports
+    pub fn with_debug_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.debug_dir = dir;
+        self
+    }
 
-        // Step 1: Get connector name
-        let connector_name = if let Some(name) = options.name {
-            name
-        } else {
-            Input::with_theme(&ColorfulTheme::default())
-                .with_prompt("Enter connector name")
-                .interact()
-                .map_err(|e| {
-                    ConnectUtilError::Config(format!("Failed to get connector name: {}", e))
-                })?
+    /// Snapshots a connector that failed a remote check into the debug directory,
+    /// when there is one. A failed write is logged rather than failing the check
+    fn snapshot_failure(
+        &self,
+        local: &ModuleConnector,
+        live: Option<&LiveConnector>,
+        findings: &[String],
+    ) {
+        let Some(dir) = &self.debug_dir else {
+            return;
         };
-
-        // Step 2: Get connector type
-        let connector_type = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Select connector type")
-            .items(&["Source", "Sink"])
-            .interact()
-            .map_err(|e| {
-                ConnectUtilError::Config(format!("Failed to select connector type: {}", e))
-            })?;
-
-        let connector_type_enum = match connector_type {
-            0 => ConnectorType::Source,
-            1 => ConnectorType::Sink,
-            _ => {
-                return Err(ConnectUtilError::Config(
-                    "Invalid connector type selection".to_string(),
-                ))
+        match snapshot::write_snapshot(dir, local, live, findings) {
+            Ok(files) => {
+                let names: Vec<String> = files
+                    .iter()
+                    .filter_map(|file| file.file_name())
+                    .map(|name| name.to_string_lossy().to_string())
+                    .collect();
+                say!(
+                    "🐛 Saved '{}' to {}: {}",
+                    local.config.name,
+                    dir.display(),
+                    names.join(", ")
+                );
             }
-        };
-
-        // Step 4: Get connector selection with fuzzy search
-        let available_connectors =
-            ConnectorDefinition::get_connectors_by_type(&connector_type_enum);
-        let connector_names: Vec<&str> = available_connectors
-            .iter()
-            .map(|c| c.display_name.as_str())
-            .collect();
-
-        let selection = FuzzySelect::with_theme(&ColorfulTheme::default())
-            .with_prompt("Select connector (type to search)")
-            .items(&connector_names)
-            .interact()
-            .map_err(|e| ConnectUtilError::Config(format!("Failed to select connector: {}", e)))?;
-
-        let selected_connector = &available_connectors[selection];
-
-        // Step 5: Generate Terraform configuration
-        // Topics can be manually specified in the generated Terraform
-        let topics = vec![];
-        let terraform_options = TerraformConfigOptions {
-            connector_name,
-            connector: selected_connector.clone(),
-            topics,
-            input_data_format: None,
-            output_data_format: None,
-        };
-        let generator = TerraformGenerator;
-        let terraform_config = generator.generate_connector_config(terraform_options)?;
-
-        // Step 8: Output configuration
-        if let Some(output_path) = options.output {
-            std::fs::write(&output_path, &terraform_config)?;
-            println!("✅ Terraform configuration written to: {}", output_path);
-        } else {
-            println!("📄 Generated Terraform Configuration:");
-            println!("{}", terraform_config);
+            Err(e) => tracing::warn!(
+                "Couldn't save a snapshot of '{}' to {}: {}",
+                local.config.name,
+                dir.display(),
+                e
+            ),
         }
-
-        Ok(())
     }
 
-    /// Validates a Terraform connector configuration file
-    /// Checks both the connector configuration and Terraform structure
-    pub async fn validate_connector(&mut self, config_file: &str) -> Result<(), ConnectUtilError> {
+    /// Reads and parses a configuration file, or standard input for `-`
+    fn read_source(&self, config_file: &str) -> Result<SourceFile, ConnectUtilError> {
+        if stdio::is_stdio(config_file) {
+            let path = self
+                .stdin_filename
+                .clone()
+                .unwrap_or_else(|| PathBuf::from(stdio::STDIN_NAME));
+            return Ok(SourceFile::parse(&path, stdio::read_stdin()?));
+        }
         let config_path = Path::new(config_file);
         if !config_path.exists() {
             return Err(ConnectUtilError::Config(format!(
@@ -145,1314 +214,10805 @@ impl ConnectUtilApp {
                 config_file
             )));
         }
+        SourceFile::read(config_path)
+    }
 
-        let terraform_content = std::fs::read_to_string(config_path)?;
-
-        // Check if the entire file is commented out
-        let all_lines_commented = terraform_content
-            .lines()
-            .filter(|line| !line.trim().is_empty())
-            .all(|line| line.trim().starts_with('#'));
+    /// The policy file governing `path` with the validation profile applied, and
+    /// where it was loaded from
+    fn validation_policy(
+        &self,
+        path: &Path,
+    ) -> Result<(Option<PathBuf>, Policy), ConnectUtilError> {
+        let (policy_path, mut policy) = match Policy::discover(path)? {
+            Some((policy_path, policy)) => (Some(policy_path), policy),
+            None => (None, Policy::default()),
+        };
+        policy.profile = self.profile;
+        Ok((policy_path, policy))
+    }
 
-        if all_lines_commented {
-            println!("✅ File is commented out - no validation needed");
-            println!("📋 Configuration Summary:");
-            println!("  Status: Commented out");
-            println!("  Note: This file contains no active connector configuration");
-            return Ok(());
+    /// Path the generated configuration will land at, used to find the governing policy
+    fn output_target(options: &ConnectorOptions) -> PathBuf {
+        match (&options.layout, &options.output_dir, &options.output) {
+            (OutputLayout::Project, Some(dir), _) => Path::new(dir).join("main.tf"),
+            (_, _, Some(output)) => PathBuf::from(output),
+            _ => PathBuf::from("."),
         }
+    }
 
-        // Parse the Terraform file to extract all connector configurations
-        let connector_configs = self.parse_terraform_configs(&terraform_content)?;
-
-        if connector_configs.is_empty() {
-            return Err(ConnectUtilError::Config(
-                "No connector configurations found in the file.".to_string(),
-            ));
+    /// Rejects connector classes the repo policy doesn't allow at the output path
+    fn enforce_connector_policy(
+        options: &ConnectorOptions,
+        connector: &ConnectorDefinition,
+    ) -> Result<(), ConnectUtilError> {
+        let target = Self::output_target(options);
+        if let Some((_, policy)) = Policy::discover(&target)? {
+            policy
+                .check_connector_class(&connector.connector_class, &target)
+                .map_err(ConnectUtilError::Validation)?;
         }
+        Ok(())
+    }
 
-        println!(
-            "🔍 Found {} connector configuration(s) to validate",
-            connector_configs.len()
-        );
-
-        for (index, config) in connector_configs.iter().enumerate() {
-            println!(
-                "\n--- Validating Connector {} of {} ---",
-                index + 1,
-                connector_configs.len()
-            );
+    /// Resolves environment names against the profiles in the config file governing
+    /// the output path
+    fn environment_profiles(
+        options: &ConnectorOptions,
+        names: &[String],
+    ) -> Result<Vec<Environment>, ConnectUtilError> {
+        if names.is_empty() {
+            return Ok(vec![]);
+        }
+        let target = Self::output_target(options);
+        let (path, policy) = Policy::discover(&target)?.ok_or_else(|| {
+            ConnectUtilError::Config(format!(
+                "Environment '{}' requested but no {} was found for {}",
+                names[0],
+                Policy::FILE_NAME,
+                target.display()
+            ))
+        })?;
+        names
+            .iter()
+            .map(|name| {
+                policy
+                    .environment(name)
+                    .cloned()
+                    .map_err(|e| ConnectUtilError::Config(format!("{} ({})", e, path.display())))
+            })
+            .collect()
+    }
 
-            // Find the connector definition
-            let connector_def = ConnectorDefinition::get_connector_by_name(&config.connector_class)
-                .ok_or_else(|| {
-                    ConnectUtilError::Config(format!(
-                        "Unknown connector: {}",
-                        config.connector_class
-                    ))
-                })?;
+    /// The `--environment` profile, plus every `--environments` profile when they
+    /// share one `for_each` resource
+    fn environment_options(
+        options: &ConnectorOptions,
+    ) -> Result<(Option<Environment>, Vec<Environment>), ConnectUtilError> {
+        let environment = Self::environment_profiles(options, options.environment.as_slice())?
+            .into_iter()
+            .next();
+        let for_each = match options.environment_layout {
+            EnvironmentLayout::ForEach => {
+                Self::environment_profiles(options, &options.environments)?
+            }
+            EnvironmentLayout::Files => vec![],
+        };
+        let environment = environment.map(|e| Self::cluster_overrides(options, e));
+        let for_each: Vec<Environment> = for_each
+            .into_iter()
+            .map(|e| Self::cluster_overrides(options, e))
+            .collect();
 
-            // Validate the configuration
-            match connector_def.validate_config(&config.config, &config.sensitive_config) {
-                Ok(()) => {
-                    println!("✅ Configuration is valid!");
-                    println!("📋 Configuration Summary:");
-                    println!("  Connector: {}", connector_def.display_name);
-                    println!("  Required configs: ✅ All present");
-                    println!("  Sensitive configs: ✅ Properly separated");
-                    println!("  Non-sensitive configs: {} fields", config.config.len());
-                    println!(
-                        "  Sensitive configs: {} fields",
-                        config.sensitive_config.len()
-                    );
-                }
-                Err(error) => {
-                    println!("❌ Configuration validation failed:");
-                    println!("  {}", error);
-                }
+        // Every cluster the connector lands on has to support its settings
+        let clusters: Vec<ClusterType> = if for_each.is_empty() {
+            vec![options
+                .cluster_type
+                .or(environment.as_ref().and_then(|e| e.cluster_type))
+                .unwrap_or_default()]
+        } else {
+            for_each
+                .iter()
+                .map(|e| options.cluster_type.or(e.cluster_type).unwrap_or_default())
+                .collect()
+        };
+        for cluster in clusters {
+            if let Some(message) = cluster.unsupported(options.schema_registry.csfle) {
+                return Err(ConnectUtilError::UserInput(message));
             }
         }
+        Ok((environment, for_each))
+    }
 
-        // Note: We don't return an error here even if validation fails
-        // The validation errors are printed above, but the function should still return Ok
-        // unless there's a parsing error or other non-validation error
-
-        // Validate environment-specific Terraform structure
-        self.validate_terraform_structure(&terraform_content)?;
+    /// A profile's cluster type as overrides of the keys it sets, so each environment
+    /// of a `for_each` gets its own. `--cluster-type` applies to every environment
+    /// instead, and explicit overrides win
+    fn cluster_overrides(options: &ConnectorOptions, mut environment: Environment) -> Environment {
+        if let (None, Some(cluster)) = (options.cluster_type, environment.cluster_type) {
+            for (key, value) in cluster.config_values() {
+                environment
+                    .overrides
+                    .entry(key.to_string())
+                    .or_insert_with(|| value.to_string());
+            }
+        }
+        environment
+    }
 
-        Ok(())
+    /// An environment profile for a discovered environment and cluster, holding their
+    /// IDs or, given variable names, `var.` references to them
+    pub fn discovered_environment(
+        environment: &CloudEnvironment,
+        cluster: &KafkaCluster,
+        variables: Option<(&str, &str)>,
+    ) -> Environment {
+        let (id, cluster_id) = match variables {
+            Some((environment_var, cluster_var)) => (
+                format!("var.{}", environment_var),
+                format!("var.{}", cluster_var),
+            ),
+            None => (environment.id.clone(), cluster.id.clone()),
+        };
+        Environment {
+            name: if environment.display_name.is_empty() {
+                environment.id.clone()
+            } else {
+                environment.display_name.clone()
+            },
+            id,
+            cluster_id,
+            schema_registry_cluster_id: String::new(),
+            cluster_type: None,
+            overrides: BTreeMap::new(),
+        }
     }
 
-    /// Parses Terraform content and extracts all connector configurations
-    /// Uses hcl-rs to properly parse HCL structure
-    fn parse_terraform_configs(&self, terraform_content: &str) -> TerraformParseResults {
-        let mut connector_configs = Vec::new();
+    /// Checks a name typed at the variable prompt is a Terraform identifier
+    fn check_variable_name(name: &str) -> Result<(), String> {
+        let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("'{}' is not a valid Terraform variable name", name))
+        }
+    }
 
-        // Parse the HCL content
-        let body: Body = match hcl::from_str(terraform_content) {
-            Ok(body) => body,
+    /// Lets the user pick one of the organization's environments and one of its Kafka
+    /// clusters, written into the connector as IDs or variable references. None when
+    /// they skip or nothing can be listed, keeping the generic variables
+    #[cfg(not(tarpaulin_include))]
+    async fn prompt_cloud_environment(
+        api: &dyn OrgApi,
+    ) -> Result<Option<Environment>, ConnectUtilError> {
+        let prompt_error =
+            |e: dialoguer::Error| ConnectUtilError::Config(format!("Failed to pick: {}", e));
+        let environments = match api.list_environments().await {
+            Ok(environments) if !environments.is_empty() => environments,
+            Ok(_) => return Ok(None),
             Err(e) => {
-                return Err(ConnectUtilError::Config(format!(
-                    "Failed to parse Terraform file: {}",
-                    e
-                )));
+                say!("⚠️  Couldn't list Confluent Cloud environments: {}", e);
+                return Ok(None);
             }
         };
+        let mut items: Vec<String> = environments
+            .iter()
+            .map(|environment| format!("{} ({})", environment.display_name, environment.id))
+            .collect();
+        items.push("Skip: reference var.environment_id and var.kafka_cluster".to_string());
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Select Confluent Cloud environment")
+            .items(&items)
+            .default(0)
+            .interact()
+            .map_err(prompt_error)?;
+        let Some(environment) = environments.get(selection) else {
+            return Ok(None);
+        };
 
-        // Find all resource blocks with type "confluent_connector"
-        for block in body.blocks() {
-            if block.identifier() == "resource" {
-                let labels = block.labels();
-                if labels.len() >= 2 && labels[0].as_str() == "confluent_connector" {
-                    // Found a confluent_connector resource
-                    let connector_name = if labels.len() >= 2 {
-                        labels[1].as_str().to_string()
-                    } else {
-                        String::new()
-                    };
-                    let mut connector_class = String::new();
-                    let mut config_nonsensitive = HashMap::new();
-                    let mut config_sensitive = HashMap::new();
-
-                    // Extract attributes from the block body
-                    self.extract_config_from_block(
-                        block.body(),
-                        &mut connector_class,
-                        &mut config_nonsensitive,
-                        &mut config_sensitive,
-                    );
-
-                    // If we found a connector class, add it to our list
-                    if !connector_class.is_empty() {
-                        connector_configs.push(ConnectorConfig {
-                            name: connector_name,
-                            connector_class,
-                            config: config_nonsensitive,
-                            sensitive_config: config_sensitive,
-                        });
-                    }
-                }
-            } else if block.identifier() == "module" {
-                // Handle legacy module blocks - extract config from module body
-                let labels = block.labels();
-                let connector_name = if !labels.is_empty() {
-                    labels[0].as_str().to_string()
-                } else {
-                    String::new()
-                };
-                let mut connector_class = String::new();
-                let mut config_nonsensitive = HashMap::new();
-                let mut config_sensitive = HashMap::new();
-
-                self.extract_config_from_block(
-                    block.body(),
-                    &mut connector_class,
-                    &mut config_nonsensitive,
-                    &mut config_sensitive,
+        let clusters = match api.list_kafka_clusters(&environment.id).await {
+            Ok(clusters) => clusters,
+            Err(e) => {
+                say!(
+                    "⚠️  Couldn't list Kafka clusters in {}: {}",
+                    environment.id,
+                    e
                 );
-
-                if !connector_class.is_empty() {
-                    connector_configs.push(ConnectorConfig {
-                        name: connector_name,
-                        connector_class,
-                        config: config_nonsensitive,
-                        sensitive_config: config_sensitive,
-                    });
-                }
+                return Ok(None);
             }
+        };
+        if clusters.is_empty() {
+            say!("⚠️  No Kafka clusters in {}", environment.id);
+            return Ok(None);
+        }
+        let items: Vec<String> = clusters
+            .iter()
+            .map(|cluster| match cluster.location.as_str() {
+                "" => format!("{} ({})", cluster.display_name, cluster.id),
+                location => format!("{} ({}, {})", cluster.display_name, cluster.id, location),
+            })
+            .collect();
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Select Kafka cluster")
+            .items(&items)
+            .default(0)
+            .interact()
+            .map_err(prompt_error)?;
+        let cluster = &clusters[selection];
+
+        let emit = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Write into the environment and kafka_cluster blocks")
+            .items(&[
+                format!("The IDs ({}, {})", environment.id, cluster.id),
+                "Variables, declared in the generated variables".to_string(),
+            ])
+            .default(0)
+            .interact()
+            .map_err(prompt_error)?;
+        if emit == 0 {
+            return Ok(Some(Self::discovered_environment(
+                environment,
+                cluster,
+                None,
+            )));
         }
+        let prefix = TerraformGenerator::resource_name(&environment.display_name)
+            .to_lowercase()
+            .replace(|c: char| !c.is_ascii_alphanumeric() && c != '_', "_");
+        let prefix = if prefix.is_empty() || Self::check_variable_name(&prefix).is_err() {
+            String::new()
+        } else {
+            format!("{}_", prefix)
+        };
+        let variable = |prompt: &str, default: String| -> Result<String, ConnectUtilError> {
+            Input::with_theme(&ColorfulTheme::default())
+                .with_prompt(prompt)
+                .default(default)
+                .validate_with(|input: &String| Self::check_variable_name(input))
+                .interact_text()
+                .map_err(prompt_error)
+        };
+        let environment_var = variable(
+            "Environment ID variable",
+            format!("{}environment_id", prefix),
+        )?;
+        let cluster_var = variable(
+            "Kafka cluster ID variable",
+            format!("{}kafka_cluster_id", prefix),
+        )?;
+        Ok(Some(Self::discovered_environment(
+            environment,
+            cluster,
+            Some((&environment_var, &cluster_var)),
+        )))
+    }
 
-        Ok(connector_configs)
+    /// Splits a comma-separated topic list, checking each name is a legal Kafka topic
+    pub fn parse_topics(input: &str) -> Result<Vec<String>, String> {
+        let topics: Vec<String> = input
+            .split(',')
+            .map(str::trim)
+            .filter(|topic| !topic.is_empty())
+            .map(str::to_string)
+            .collect();
+        for topic in &topics {
+            if topic == "." || topic == ".." {
+                return Err(format!("'{}' is not a valid topic name", topic));
+            }
+            if topic.len() > 249 {
+                return Err(format!("Topic '{}' is longer than 249 characters", topic));
+            }
+            if let Some(c) = topic
+                .chars()
+                .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')))
+            {
+                return Err(format!(
+                    "Topic '{}' contains '{}'; use letters, digits, '.', '_' and '-'",
+                    topic, c
+                ));
+            }
+        }
+        Ok(topics)
     }
 
-    fn extract_config_from_block(
-        &self,
-        body: &Body,
-        connector_class: &mut String,
-        config_nonsensitive: &mut HashMap<String, String>,
-        config_sensitive: &mut HashMap<String, String>,
-    ) {
-        // Extract config_nonsensitive from body attributes
-        for attr in body.attributes() {
-            let key = attr.key();
-            if key == "config_nonsensitive" {
-                if let Some(map) = self.extract_map_from_expression(attr.expr()) {
-                    for (key, value) in map {
-                        if key == "connector.class" {
-                            *connector_class = value.clone();
-                        }
-                        config_nonsensitive.insert(key, value);
-                    }
-                }
-            } else if key == "config_sensitive" {
-                if let Some(map) = self.extract_map_from_expression(attr.expr()) {
-                    for (key, value) in map {
-                        config_sensitive.insert(key, value);
-                    }
-                }
+    /// Validates topics for a connector and splits out the prefix for connectors
+    /// configured with `topic.prefix`
+    fn topic_settings(
+        connector: &ConnectorDefinition,
+        topics: &[String],
+    ) -> Result<(Vec<String>, Option<String>), ConnectUtilError> {
+        let topics = Self::parse_topics(&topics.join(",")).map_err(ConnectUtilError::UserInput)?;
+        match connector.topic_strategy {
+            TopicStrategy::TopicPrefix => match topics.as_slice() {
+                [] => Ok((vec![], None)),
+                [prefix] => Ok((vec![], Some(prefix.clone()))),
+                _ => Err(ConnectUtilError::UserInput(format!(
+                    "{} takes a single topic prefix, not a list of topics",
+                    connector.name
+                ))),
+            },
+            TopicStrategy::KafkaTopicSingle if topics.len() > 1 => {
+                Err(ConnectUtilError::UserInput(format!(
+                    "{} writes to a single kafka.topic",
+                    connector.name
+                )))
             }
+            _ => Ok((topics, None)),
         }
     }
 
-    fn extract_map_from_expression(&self, expr: &Expression) -> Option<HashMap<String, String>> {
-        match expr {
-            Expression::Object(map) => {
-                let mut result = HashMap::new();
-                for (key, value) in map.iter() {
-                    if let Some(str_value) = self.extract_string_from_expression(value) {
-                        result.insert(key.to_string(), str_value);
-                    }
-                }
-                Some(result)
-            }
-            _ => None,
+    /// Validates `--tables` and `--exclude-tables` for a connector with
+    /// `table.include.list`, warning when the include and exclude lists (including
+    /// policy templates for either key) overlap
+    fn table_settings(
+        connector: &ConnectorDefinition,
+        tables: &[String],
+        exclude_tables: &[String],
+        template_values: &BTreeMap<String, String>,
+    ) -> Result<(Vec<String>, Vec<String>), ConnectUtilError> {
+        let tables =
+            parse_table_patterns(&tables.join(",")).map_err(ConnectUtilError::UserInput)?;
+        let exclude_tables =
+            parse_table_patterns(&exclude_tables.join(",")).map_err(ConnectUtilError::UserInput)?;
+        if (!tables.is_empty() || !exclude_tables.is_empty())
+            && !connector.has_field(TABLE_INCLUDE_LIST)
+        {
+            return Err(ConnectUtilError::Config(format!(
+                "--tables and --exclude-tables only apply to CDC sources with {}; {} isn't one",
+                TABLE_INCLUDE_LIST, connector.name
+            )));
         }
+        let effective = |key: &str, patterns: &[String]| match template_values.get(key) {
+            Some(value) => parse_table_patterns(value).unwrap_or_default(),
+            None => patterns.to_vec(),
+        };
+        for warning in table_warnings(
+            &effective(TABLE_INCLUDE_LIST, &tables),
+            &effective(TABLE_EXCLUDE_LIST, &exclude_tables),
+        ) {
+            tracing::warn!("{}", warning);
+        }
+        Ok((tables, exclude_tables))
     }
 
-    #[allow(clippy::only_used_in_recursion)] // This is a recursive function
-    fn extract_string_from_expression(&self, expr: &Expression) -> Option<String> {
-        match expr {
-            Expression::String(s) => Some(s.to_string()),
-            Expression::Variable(var) => Some(format!("var.{}", var.as_str())),
-            Expression::FuncCall(func) => {
-                // Handle function calls like join(",", [...])
-                // FuncCall is a Box, so we need to dereference it
-                let func_name = func.name.as_str();
-                if func_name == "join" {
-                    if let Some(Expression::Array(arr)) = func.args.first() {
-                        let values: Vec<String> = arr
-                            .iter()
-                            .filter_map(|e| self.extract_string_from_expression(e))
-                            .collect();
-                        return Some(values.join(", "));
-                    }
-                }
-                // For other function calls, try to format as string
-                Some(format!("{}(...)", func_name))
+    /// Validates the storage layout flags for an object-store sink, filling in the
+    /// partitioner they imply: `--partition-fields` means field partitioning, and
+    /// `--path-format`, `--locale`, or `--timezone` a time-based layout
+    fn storage_settings(
+        connector: &ConnectorDefinition,
+        storage: &StorageLayoutOptions,
+    ) -> Result<StorageLayoutOptions, ConnectUtilError> {
+        if storage.is_empty() {
+            return Ok(storage.clone());
+        }
+        if connector.family != ConnectorFamily::ObjectStorage
+            || connector.connector_type != ConnectorType::Sink
+        {
+            return Err(ConnectUtilError::Config(format!(
+                "--partitioner and the storage layout flags only apply to object-store sinks; {} isn't one",
+                connector.name
+            )));
+        }
+
+        let mut storage = storage.clone();
+        let time_settings =
+            storage.path_format.is_some() || storage.locale.is_some() || storage.timezone.is_some();
+        let partitioner = match storage.partitioner {
+            Some(partitioner) => partitioner,
+            None if !storage.partition_fields.is_empty() => Partitioner::Field,
+            None if storage.path_format.is_some() => Partitioner::Time,
+            None => Partitioner::Daily,
+        };
+        let invalid = |message: &str| Err(ConnectUtilError::UserInput(message.to_string()));
+        match partitioner {
+            Partitioner::Field if storage.partition_fields.is_empty() => {
+                return invalid("--partitioner field needs --partition-fields");
             }
-            Expression::Array(arr) => {
-                let values: Vec<String> = arr
-                    .iter()
-                    .filter_map(|e| self.extract_string_from_expression(e))
-                    .collect();
-                Some(format!("[{}]", values.join(", ")))
+            Partitioner::Time if storage.path_format.is_none() => {
+                return invalid("--partitioner time needs --path-format");
             }
-            Expression::Number(n) => Some(n.to_string()),
-            Expression::Bool(b) => Some(b.to_string()),
-            _ => {
-                // For other expression types, try to convert to string
-                // This is a fallback for expressions we don't handle explicitly
-                None
+            _ if partitioner != Partitioner::Field && !storage.partition_fields.is_empty() => {
+                return invalid("--partition-fields only applies to --partitioner field");
             }
-        }
-    }
-
-    fn validate_terraform_structure(
-        &self,
-        terraform_content: &str,
-    ) -> Result<(), ConnectUtilError> {
-        println!("🔍 Validating Terraform structure...");
-
-        // Parse the HCL content to validate structure properly
-        let body: Body = match hcl::from_str(terraform_content) {
-            Ok(body) => body,
-            Err(e) => {
-                return Err(ConnectUtilError::Config(format!(
-                    "Failed to parse Terraform file: {}",
-                    e
-                )));
+            _ if !partitioner.is_time_based() && time_settings => {
+                return invalid(
+                    "--path-format, --locale, and --timezone only apply to time-based partitioners",
+                );
             }
-        };
-
-        // Validate each confluent_connector resource block and module block individually
-        let mut connector_count = 0;
-        let mut module_count = 0;
-        for block in body.blocks() {
-            if block.identifier() == "resource" {
-                let labels = block.labels();
-                if labels.len() >= 2 && labels[0].as_str() == "confluent_connector" {
-                    connector_count += 1;
-                    let resource_name = labels[1].as_str();
-                    self.validate_resource_block(block.body(), resource_name)?;
+            _ => {}
+        }
+        if let Some(path_format) = &storage.path_format {
+            storage::check_path_format(path_format).map_err(ConnectUtilError::UserInput)?;
+            match (partitioner, storage::time_interval(path_format)) {
+                (Partitioner::Daily, "HOURLY") => {
+                    return invalid("--partitioner daily can't use a --path-format with an hour field; use hourly");
                 }
-            } else if block.identifier() == "module" {
-                // Check if this module has connector configuration by looking for config_nonsensitive
-                let has_connector_config = block.body().attributes().any(|attr| {
-                    attr.key() == "config_nonsensitive" || attr.key() == "config_sensitive"
-                });
-                if has_connector_config {
-                    module_count += 1;
-                    let labels = block.labels();
-                    let module_name = if !labels.is_empty() {
-                        labels[0].as_str()
-                    } else {
-                        "unknown"
-                    };
-                    self.validate_module_block(block.body(), module_name)?;
+                (Partitioner::Hourly, "DAILY") => {
+                    return invalid(
+                        "--partitioner hourly needs an hour field (HH) in --path-format",
+                    );
                 }
+                _ => {}
             }
         }
-
-        let total_count = connector_count + module_count;
-        if total_count == 0 {
-            return Err(ConnectUtilError::Config(
-                "❌ No connector configurations found in file (no 'confluent_connector' resources or connector modules)".to_string(),
-            ));
+        if let Some(locale) = &storage.locale {
+            storage::check_locale(locale).map_err(ConnectUtilError::UserInput)?;
         }
-
-        if connector_count > 0 && module_count > 0 {
-            println!(
-                "  ✅ Validated {} connector resource(s) and {} module(s)",
-                connector_count, module_count
-            );
-        } else if connector_count > 0 {
-            println!("  ✅ Validated {} connector resource(s)", connector_count);
-        } else {
-            println!("  ✅ Validated {} connector module(s)", module_count);
+        if let Some(timezone) = &storage.timezone {
+            storage::check_timezone(timezone).map_err(ConnectUtilError::UserInput)?;
         }
-        println!("✅ Terraform structure validation passed!");
-        Ok(())
+        storage.partitioner = Some(partitioner);
+        Ok(storage)
     }
 
-    /// Validates a single resource block structure
-    /// Ensures all required fields and nested blocks are present and correctly formatted
-    fn validate_resource_block(
-        &self,
-        body: &Body,
-        resource_name: &str,
-    ) -> Result<(), ConnectUtilError> {
-        // Check for status field
-        let mut has_status = false;
-        for attr in body.attributes() {
-            if attr.key() == "status" {
-                has_status = true;
-                break;
-            }
+    /// Validates the heartbeat and signal table flags for a Debezium-based source,
+    /// warning when `--tables` leaves the signal table uncaptured
+    fn debezium_settings(
+        connector: &ConnectorDefinition,
+        options: &DebeziumOptions,
+        tables: &[String],
+    ) -> Result<DebeziumOptions, ConnectUtilError> {
+        if options.is_empty() {
+            return Ok(options.clone());
         }
-        if !has_status {
-            return Err(ConnectUtilError::Config(format!(
-                "❌ Resource '{}' missing 'status' field",
-                resource_name
-            )));
+        let dialect = Dialect::for_connector(connector).ok_or_else(|| {
+            ConnectUtilError::Config(format!(
+                "--heartbeat-interval, --heartbeat-table, and --signal-table only apply to Debezium CDC sources; {} isn't one",
+                connector.name
+            ))
+        })?;
+        let invalid = |message: &str| Err(ConnectUtilError::UserInput(message.to_string()));
+        if let Some(table) = &options.heartbeat_table {
+            debezium::check_heartbeat_table(dialect, table).map_err(ConnectUtilError::UserInput)?;
+            if options.heartbeat_interval_ms == Some(0) {
+                return invalid("--heartbeat-table needs a --heartbeat-interval above 0");
+            }
         }
-
-        // Check for environment block with correct structure
-        let mut has_environment = false;
-        let mut environment_has_id = false;
-        let mut environment_attrs = Vec::new();
-        for block in body.blocks() {
-            if block.identifier() == "environment" {
-                has_environment = true;
-                // Check if environment block has 'id' attribute
-                for attr in block.body().attributes() {
-                    environment_attrs.push(attr.key().to_string());
-                    if attr.key() == "id" {
-                        environment_has_id = true;
-                    }
+        match (&options.signal_table, options.snapshot_chunk_size) {
+            (None, Some(_)) => return invalid("--snapshot-chunk-size needs --signal-table"),
+            (_, Some(0)) => return invalid("--snapshot-chunk-size must be above 0"),
+            (Some(table), _) => {
+                debezium::check_signal_table(dialect, table)
+                    .map_err(ConnectUtilError::UserInput)?;
+                let captured = debezium::captured_name(dialect, table);
+                if !tables.is_empty() && !tables::captures(tables, captured) {
+                    tracing::warn!(
+                        "The signal table {} isn't in --tables; Debezium only reads signals from a captured table",
+                        captured
+                    );
                 }
-                break;
             }
+            _ => {}
         }
+        Ok(options.clone())
+    }
 
-        if !has_environment {
+    /// Suggests `pk.fields`, `pk.mode`, and `table.name.format` from the record schema
+    /// for a JDBC-style sink, warning about fields its database can't map
+    fn schema_settings(
+        connector: &ConnectorDefinition,
+        schema: Option<&RecordSchema>,
+    ) -> Result<BTreeMap<String, String>, ConnectUtilError> {
+        let Some(schema) = schema else {
+            return Ok(BTreeMap::new());
+        };
+        // Some sinks prefix their keys, e.g. `alloydb.pk.fields`
+        let field = |key: &str| {
+            connector
+                .required_configs
+                .iter()
+                .chain(connector.optional_configs.iter())
+                .map(|field| field.name.as_str())
+                .find(|name| *name == key || name.ends_with(&format!(".{}", key)))
+        };
+        let Some(pk_fields) = field("pk.fields") else {
             return Err(ConnectUtilError::Config(format!(
-                "❌ Resource '{}' missing 'environment {{ id = ... }}' block",
-                resource_name
+                "--schema-file and --schema-subject only apply to JDBC-style sinks with pk.fields; {} isn't one",
+                connector.name
             )));
+        };
+        let suggestions = record_schema::suggest(
+            schema,
+            pk_fields,
+            field("pk.mode"),
+            field("table.name.format"),
+            &connector.name,
+        );
+        for issue in &suggestions.issues {
+            tracing::warn!("{}", issue);
         }
-        if !environment_has_id {
-            return Err(ConnectUtilError::Config(format!(
-                "❌ Resource '{}' environment block must have 'id' attribute (found: {})",
-                resource_name,
-                if environment_attrs.is_empty() {
-                    "none".to_string()
-                } else {
-                    environment_attrs.join(", ")
-                }
+        Ok(suggestions.values)
+    }
+
+    /// Checks requested data formats against what the connector supports; the input
+    /// format only applies to sinks
+    fn requested_formats(
+        options: &ConnectorOptions,
+        connector: &ConnectorDefinition,
+    ) -> Result<(Option<DataFormat>, Option<DataFormat>), ConnectUtilError> {
+        if options.input_format.is_some() && connector.connector_type != ConnectorType::Sink {
+            return Err(ConnectUtilError::Validation(format!(
+                "--input-format only applies to sink connectors; {} is a source",
+                connector.name
             )));
         }
-
-        // Check for kafka_cluster block with correct structure
-        let mut has_kafka_cluster = false;
-        let mut kafka_cluster_has_id = false;
-        for block in body.blocks() {
-            if block.identifier() == "kafka_cluster" {
-                has_kafka_cluster = true;
-                // Check if kafka_cluster block has 'id' attribute
-                for attr in block.body().attributes() {
-                    if attr.key() == "id" {
-                        kafka_cluster_has_id = true;
-                        break;
-                    }
-                }
-                break;
+        for (key, format) in [
+            ("input.data.format", options.input_format),
+            ("output.data.format", options.output_format),
+        ] {
+            let Some(format) = format else { continue };
+            let supported = connector.supported_formats(key);
+            if !supported.contains(&format) {
+                let names: Vec<&str> = supported.iter().map(|f| f.to_terraform_value()).collect();
+                return Err(ConnectUtilError::Validation(format!(
+                    "{} doesn't support {} {} (supported: {})",
+                    connector.name,
+                    key,
+                    format.to_terraform_value(),
+                    names.join(", ")
+                )));
             }
         }
+        Ok((options.input_format, options.output_format))
+    }
 
-        if !has_kafka_cluster {
-            return Err(ConnectUtilError::Config(format!(
-                "❌ Resource '{}' missing 'kafka_cluster {{ id = ... }}' block",
-                resource_name
-            )));
-        }
-        if !kafka_cluster_has_id {
-            return Err(ConnectUtilError::Config(format!(
-                "❌ Resource '{}' kafka_cluster block must have 'id' attribute",
-                resource_name
-            )));
+    /// First step of the connector picker: recently used connectors, then each
+    /// family that has connectors available, with its count
+    fn connector_menu(
+        available: &[ConnectorDefinition],
+        recent: &[String],
+    ) -> Vec<(String, ConnectorMenuEntry)> {
+        let mut menu: Vec<(String, ConnectorMenuEntry)> = recent
+            .iter()
+            .filter_map(|name| available.iter().position(|c| &c.name == name))
+            .map(|index| {
+                (
+                    format!("Recent: {}", available[index].display_name),
+                    ConnectorMenuEntry::Connector(index),
+                )
+            })
+            .collect();
+        for family in ConnectorFamily::ALL {
+            let count = available.iter().filter(|c| c.family == family).count();
+            if count > 0 {
+                menu.push((
+                    format!("{} ({})", family.display_name(), count),
+                    ConnectorMenuEntry::Family(family),
+                ));
+            }
         }
+        menu
+    }
 
-        // Check for config_sensitive attribute
-        let mut has_config_sensitive = false;
-        for attr in body.attributes() {
-            if attr.key() == "config_sensitive" {
-                has_config_sensitive = true;
-                break;
+    /// Picks a connector in two steps, a family then a connector within it with
+    /// fuzzy search, and remembers the choice for the top of the next menu
+    #[cfg(not(tarpaulin_include))]
+    fn prompt_connector(
+        available: &[ConnectorDefinition],
+    ) -> Result<&ConnectorDefinition, ConnectUtilError> {
+        let recent_path = crate::recent::default_path();
+        let recent = recent_path
+            .as_deref()
+            .map(crate::recent::load)
+            .unwrap_or_default();
+        let menu = Self::connector_menu(available, &recent);
+        let labels: Vec<&str> = menu.iter().map(|(label, _)| label.as_str()).collect();
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Select connector family")
+            .items(&labels)
+            .default(0)
+            .interact()
+            .map_err(|e| ConnectUtilError::Config(format!("Failed to select family: {}", e)))?;
+
+        let selected = match menu[selection].1 {
+            ConnectorMenuEntry::Connector(index) => &available[index],
+            ConnectorMenuEntry::Family(family) => {
+                let members: Vec<&ConnectorDefinition> =
+                    available.iter().filter(|c| c.family == family).collect();
+                let names: Vec<&str> = members.iter().map(|c| c.display_name.as_str()).collect();
+                let selection = FuzzySelect::with_theme(&ColorfulTheme::default())
+                    .with_prompt(format!(
+                        "Select {} connector (type to search)",
+                        family.display_name()
+                    ))
+                    .items(&names)
+                    .interact()
+                    .map_err(|e| {
+                        ConnectUtilError::Config(format!("Failed to select connector: {}", e))
+                    })?;
+                members[selection]
             }
-        }
-        if !has_config_sensitive {
-            return Err(ConnectUtilError::Config(format!(
-                "❌ Resource '{}' missing 'config_sensitive' attribute",
-                resource_name
-            )));
-        }
+        };
 
-        // Check for config_nonsensitive attribute
-        let mut has_config_nonsensitive = false;
-        for attr in body.attributes() {
-            if attr.key() == "config_nonsensitive" {
-                has_config_nonsensitive = true;
-                break;
+        // History is a convenience, so failing to save it shouldn't stop generation
+        if let Some(path) = recent_path {
+            if let Err(e) = crate::recent::record(&path, &selected.name) {
+                tracing::debug!(
+                    "Could not save recent connectors to {}: {}",
+                    path.display(),
+                    e
+                );
             }
         }
-        if !has_config_nonsensitive {
-            return Err(ConnectUtilError::Config(format!(
-                "❌ Resource '{}' missing 'config_nonsensitive' attribute",
-                resource_name
-            )));
-        }
+        Ok(selected)
+    }
 
-        Ok(())
+    /// `lifecycle.ignore_changes` for a generated connector: `--ignore-changes`, the
+    /// policy's list for the output path, or the defaults, plus the policy's required entries
+    fn ignore_changes(options: &ConnectorOptions) -> Result<Vec<String>, ConnectUtilError> {
+        let policy = Policy::discover(&Self::output_target(options))?
+            .map(|(_, policy)| policy)
+            .unwrap_or_default();
+        Ok(policy.ignore_changes(&options.ignore_changes))
     }
 
-    /// Validates a single module block structure
-    /// Modules use attributes instead of blocks for environment and kafka_cluster
-    fn validate_module_block(
-        &self,
-        body: &Body,
-        module_name: &str,
-    ) -> Result<(), ConnectUtilError> {
-        // Check for status field
-        let mut has_status = false;
-        for attr in body.attributes() {
-            if attr.key() == "status" {
-                has_status = true;
-                break;
-            }
+    /// Expands the policy file's templates for the output path. `connector` and,
+    /// with `--environment`, `env` are always available as tokens
+    fn template_values(
+        options: &ConnectorOptions,
+        connector_name: &str,
+    ) -> Result<BTreeMap<String, String>, ConnectUtilError> {
+        let policy = Policy::discover(&Self::output_target(options))?
+            .map(|(_, policy)| policy)
+            .unwrap_or_default();
+        let mut tokens = BTreeMap::from([("connector".to_string(), connector_name.to_string())]);
+        if let Some(environment) = &options.environment {
+            tokens.insert("env".to_string(), environment.clone());
         }
-        if !has_status {
-            return Err(ConnectUtilError::Config(format!(
-                "❌ Module '{}' missing 'status' field",
-                module_name
-            )));
+        tokens.extend(options.tokens.iter().cloned());
+        policy
+            .expand_templates(&tokens)
+            .map_err(ConnectUtilError::Config)
+    }
+
+    /// The backend placeholder secrets are read from: `--sops` variables, or
+    /// `--secrets-backend`
+    fn secrets_backend(options: &ConnectorOptions) -> SecretsBackend {
+        match options.sops {
+            Some(_) => SecretsBackend::Variables,
+            None => options.secrets_backend,
         }
+    }
 
-        // Check for environment or environment_id attribute (modules use attributes, not blocks)
-        let mut has_environment = false;
-        for attr in body.attributes() {
-            if attr.key() == "environment" || attr.key() == "environment_id" {
-                has_environment = true;
-                break;
-            }
+    /// The live connector's name for `--with-import-block`. The provider imports
+    /// connectors by name, so an `lcc-` connector ID or a full import ID is rejected
+    fn import_name(options: &ConnectorOptions) -> Result<Option<String>, ConnectUtilError> {
+        let Some(name) = &options.import_name else {
+            return Ok(None);
+        };
+        let is_id = name.strip_prefix("lcc-").is_some_and(|rest| {
+            !rest.is_empty() && rest.chars().all(|c| c.is_ascii_alphanumeric())
+        });
+        if is_id {
+            return Err(ConnectUtilError::UserInput(format!(
+                "'{}' is a connector ID, but the provider imports connectors by name; pass the connector's name as shown in Confluent Cloud",
+                name
+            )));
         }
-        if !has_environment {
-            return Err(ConnectUtilError::Config(format!(
-                "❌ Module '{}' missing 'environment' or 'environment_id' attribute",
-                module_name
+        if name.trim().is_empty() || name.contains('/') {
+            return Err(ConnectUtilError::UserInput(format!(
+                "Invalid connector name '{}': pass the connector's name alone, without the environment and cluster IDs",
+                name
             )));
         }
+        Ok(Some(name.clone()))
+    }
 
-        // Check for kafka_cluster attribute (modules use attributes, not blocks)
-        let mut has_kafka_cluster = false;
-        for attr in body.attributes() {
-            if attr.key() == "kafka_cluster" {
-                has_kafka_cluster = true;
-                break;
-            }
+    /// Prompts for a data format among those the connector supports for `key`,
+    /// unless one was already given on the command line
+    #[cfg(not(tarpaulin_include))]
+    fn prompt_data_format(
+        connector: &ConnectorDefinition,
+        key: &str,
+        preset: Option<DataFormat>,
+    ) -> Result<Option<DataFormat>, ConnectUtilError> {
+        if preset.is_some() {
+            return Ok(preset);
         }
-        if !has_kafka_cluster {
-            return Err(ConnectUtilError::Config(format!(
-                "❌ Module '{}' missing 'kafka_cluster' attribute",
-                module_name
-            )));
+        let supported = connector.supported_formats(key);
+        if supported.len() < 2 {
+            return Ok(supported.first().copied());
         }
+        let items: Vec<&str> = supported.iter().map(|f| f.to_terraform_value()).collect();
+        let default = supported
+            .iter()
+            .position(|f| *f == DataFormat::Avro)
+            .unwrap_or(0);
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Select {}", key))
+            .items(&items)
+            .default(default)
+            .interact()
+            .map_err(|e| ConnectUtilError::Config(format!("Failed to select {}: {}", key, e)))?;
+        Ok(Some(supported[selection]))
+    }
 
-        // Check for config_sensitive attribute
-        let mut has_config_sensitive = false;
-        for attr in body.attributes() {
-            if attr.key() == "config_sensitive" {
-                has_config_sensitive = true;
-                break;
+    /// Prompts an object-store sink for its partitioner, then the partition fields or
+    /// timezone the choice needs
+    fn prompt_storage_layout() -> Result<StorageLayoutOptions, ConnectUtilError> {
+        let choices = [
+            (
+                None,
+                "Keep the default layout ('effective_date'=YYYY-MM-dd)",
+            ),
+            (
+                Some(Partitioner::Daily),
+                "Daily ('year'=YYYY/'month'=MM/'day'=dd)",
+            ),
+            (
+                Some(Partitioner::Hourly),
+                "Hourly ('year'=YYYY/'month'=MM/'day'=dd/'hour'=HH)",
+            ),
+            (Some(Partitioner::Field), "By record field"),
+            (Some(Partitioner::Default), "By Kafka partition"),
+        ];
+        let labels: Vec<&str> = choices.iter().map(|(_, label)| *label).collect();
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Select the partitioner")
+            .items(&labels)
+            .default(0)
+            .interact()
+            .map_err(|e| {
+                ConnectUtilError::Config(format!("Failed to select partitioner: {}", e))
+            })?;
+
+        let partitioner = choices[selection].0;
+        let mut storage = StorageLayoutOptions {
+            partitioner,
+            ..Default::default()
+        };
+        match partitioner {
+            Some(Partitioner::Field) => {
+                let input: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Partition fields, comma-separated")
+                    .validate_with(|input: &String| -> Result<(), String> {
+                        if Self::parse_topics(input)?.is_empty() {
+                            Err("Enter at least one field".to_string())
+                        } else {
+                            Ok(())
+                        }
+                    })
+                    .interact_text()
+                    .map_err(|e| {
+                        ConnectUtilError::Config(format!("Failed to get partition fields: {}", e))
+                    })?;
+                storage.partition_fields =
+                    Self::parse_topics(&input).map_err(ConnectUtilError::UserInput)?;
             }
+            Some(partitioner) if partitioner.is_time_based() => {
+                let timezone: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Timezone")
+                    .default("UTC".to_string())
+                    .validate_with(|input: &String| storage::check_timezone(input))
+                    .interact_text()
+                    .map_err(|e| {
+                        ConnectUtilError::Config(format!("Failed to get timezone: {}", e))
+                    })?;
+                storage.timezone = Some(timezone);
+            }
+            _ => {}
         }
-        if !has_config_sensitive {
-            return Err(ConnectUtilError::Config(format!(
-                "❌ Module '{}' missing 'config_sensitive' attribute",
-                module_name
-            )));
+        Ok(storage)
+    }
+
+    /// Returns naming convention violations for a connector name and its resource label,
+    /// each with a suggested compliant name when one can be derived
+    fn naming_violations(
+        &self,
+        connector_name: &str,
+        resource_label: &str,
+        connector_type: &ConnectorType,
+    ) -> Vec<String> {
+        let mut violations = Vec::new();
+        if let Err(error) = self.naming.check_connector_name(connector_name) {
+            let suggestion = self
+                .naming
+                .suggest_connector_name(connector_name, connector_type);
+            violations.push(NamingRules::describe_violation(error, suggestion));
+        }
+        if let Err(error) = self.naming.check_resource_label(resource_label) {
+            let suggestion = self.naming.suggest_resource_label(resource_label);
+            violations.push(NamingRules::describe_violation(error, suggestion));
         }
+        violations
+    }
 
-        // Check for config_nonsensitive attribute
-        let mut has_config_nonsensitive = false;
-        for attr in body.attributes() {
-            if attr.key() == "config_nonsensitive" {
-                has_config_nonsensitive = true;
-                break;
+    /// Non-interactive version for testing and programmatic use
+    /// Generates Terraform configuration without user prompts
+    pub fn generate_terraform_non_interactive(
+        &self,
+        options: ConnectorOptions,
+    ) -> Result<String, ConnectUtilError> {
+        let terraform_options = self.build_terraform_options(&options)?;
+        let generator = TerraformGenerator;
+        generator.generate_connector_config(terraform_options)
+    }
+
+    /// Builds generator options from CLI options without prompting
+    fn build_terraform_options(
+        &self,
+        options: &ConnectorOptions,
+    ) -> Result<TerraformConfigOptions, ConnectUtilError> {
+        // Validate required options
+        let connector_name = options.name.clone().ok_or_else(|| {
+            ConnectUtilError::Config(
+                "Connector name is required for non-interactive mode".to_string(),
+            )
+        })?;
+
+        // Use the requested connector, or default to the first Source connector
+        let selected_connector = match &options.connector {
+            Some(name) => ConnectorDefinition::get_connector_by_name(name)
+                .ok_or_else(|| ConnectUtilError::Config(format!("Unknown connector: {}", name)))?,
+            None => ConnectorDefinition::get_connectors_by_type(&ConnectorType::Source)
+                .into_iter()
+                .next()
+                .ok_or_else(|| ConnectUtilError::Config("No connectors available".to_string()))?,
+        };
+
+        Self::enforce_connector_policy(options, &selected_connector)?;
+
+        // A `name` template replaces the name, and with it the resource label
+        let mut template_values = Self::template_values(options, &connector_name)?;
+        let connector_name = template_values.remove("name").unwrap_or(connector_name);
+
+        let violations = self.naming_violations(
+            &connector_name,
+            &TerraformGenerator::resource_name(&connector_name),
+            &selected_connector.connector_type,
+        );
+        if !violations.is_empty() {
+            return Err(ConnectUtilError::Validation(violations.join("; ")));
+        }
+
+        let (topics, topic_prefix) = Self::topic_settings(&selected_connector, &options.topics)?;
+        let (tables, exclude_tables) = Self::table_settings(
+            &selected_connector,
+            &options.tables,
+            &options.exclude_tables,
+            &template_values,
+        )?;
+        let storage = Self::storage_settings(&selected_connector, &options.storage)?;
+        let debezium = Self::debezium_settings(&selected_connector, &options.debezium, &tables)?;
+        let schema_values =
+            Self::schema_settings(&selected_connector, options.record_schema.as_ref())?;
+        let (environment, for_each_environments) = Self::environment_options(options)?;
+        let (input_data_format, output_data_format) =
+            Self::requested_formats(options, &selected_connector)?;
+
+        Ok(TerraformConfigOptions {
+            connector_name,
+            connector: selected_connector,
+            topics,
+            topic_prefix,
+            tables,
+            exclude_tables,
+            storage,
+            debezium,
+            schema_values,
+            input_data_format,
+            output_data_format,
+            companions: options.companions,
+            schema_registry: options.schema_registry.clone(),
+            environment,
+            for_each_environments,
+            cluster_type: options.cluster_type,
+            import_name: Self::import_name(options)?,
+            ignore_changes: Some(Self::ignore_changes(options)?),
+            template_values,
+            secrets_backend: Self::secrets_backend(options),
+        })
+    }
+
+    /// Generates Terraform configuration, prompting only when the connector
+    /// name or plugin is not supplied up front
+    #[cfg(not(tarpaulin_include))]
+    pub async fn generate_terraform(
+        &mut self,
+        options: ConnectorOptions,
+        discovery: Option<&dyn OrgApi>,
+    ) -> Result<(), ConnectUtilError> {
+        if options.name.is_some()
+            && options.connector.is_some()
+            && !options.environments.is_empty()
+            && options.environment_layout == EnvironmentLayout::Files
+        {
+            for (environment_options, files) in self.render_environment_files(&options)? {
+                say!(
+                    "🌍 Environment: {}",
+                    environment_options
+                        .environment
+                        .as_deref()
+                        .unwrap_or_default()
+                );
+                if !options.dry_run && environment_options.layout == OutputLayout::Single {
+                    if let Some(parent) = environment_options
+                        .output
+                        .as_deref()
+                        .and_then(|output| Path::new(output).parent())
+                    {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                }
+                self.emit_files(&environment_options, &files)?;
             }
+            Ok(())
+        } else if options.name.is_some() && options.connector.is_some() {
+            let terraform_options = self.build_terraform_options(&options)?;
+            let report = self.trace_values(&options, &terraform_options, &BTreeSet::new())?;
+            let files = self.render_output_files(&options, terraform_options)?;
+            self.emit_files(&options, &files)?;
+            self.report_values(&options, report, &files)
+        } else {
+            self.generate_terraform_interactive(options, discovery)
+                .await
         }
-        if !has_config_nonsensitive {
+    }
+
+    /// Renders the connector once per `--environments` profile, placing each
+    /// environment's output in a directory named after it, e.g. `prod/orders.tf`
+    pub fn render_environment_files(
+        &self,
+        options: &ConnectorOptions,
+    ) -> Result<Vec<(ConnectorOptions, Vec<GeneratedFile>)>, ConnectUtilError> {
+        let mut rendered = Vec::new();
+        for name in &options.environments {
+            let in_environment = |path: &Path| {
+                let file_name = path.file_name().unwrap_or_default();
+                path.parent()
+                    .unwrap_or(Path::new(""))
+                    .join(name)
+                    .join(file_name)
+                    .to_string_lossy()
+                    .to_string()
+            };
+            let environment_options = ConnectorOptions {
+                environment: Some(name.clone()),
+                environments: vec![],
+                output: options
+                    .output
+                    .as_deref()
+                    .map(|o| in_environment(Path::new(o))),
+                output_dir: options
+                    .output_dir
+                    .as_deref()
+                    .map(|dir| Path::new(dir).join(name).to_string_lossy().to_string()),
+                ..options.clone()
+            };
+            let terraform_options = self.build_terraform_options(&environment_options)?;
+            if let Some(report) =
+                self.trace_values(&environment_options, &terraform_options, &BTreeSet::new())?
+            {
+                say!("📋 Value sources in {}:", name);
+                say!("{}", provenance::render_text(&report.values));
+            }
+            let files = self.render_output_files(&environment_options, terraform_options)?;
+            rendered.push((environment_options, files));
+        }
+        Ok(rendered)
+    }
+
+    /// Renders the generated configuration into files according to the output layout
+    pub fn render_output_files(
+        &self,
+        options: &ConnectorOptions,
+        terraform_options: TerraformConfigOptions,
+    ) -> Result<Vec<GeneratedFile>, ConnectUtilError> {
+        if options.target != GenerateTarget::Cloud
+            || options.provider != TerraformProvider::Confluent
+        {
+            return self.render_self_managed(options, terraform_options);
+        }
+        let generator = TerraformGenerator;
+        let connector_name = terraform_options.connector_name.clone();
+        let terraform_options_companions = terraform_options.companions;
+        let environments: Vec<Environment> = if terraform_options.for_each_environments.is_empty() {
+            terraform_options.environment.iter().cloned().collect()
+        } else {
+            terraform_options.for_each_environments.clone()
+        };
+        let streams = Self::ksql_streams(options, &terraform_options)?;
+        let secrets_backend = terraform_options.secrets_backend;
+        let secret_keys = match secrets_backend {
+            SecretsBackend::Inline => Vec::new(),
+            _ => TerraformGenerator::secret_keys(&terraform_options),
+        };
+        let secret_variables = generator.generate_secret_backend_variables(
+            &connector_name,
+            &secret_keys,
+            secrets_backend,
+        )?;
+        let secret_data_sources = generator.generate_secret_data_sources(
+            &connector_name,
+            &secret_keys,
+            secrets_backend,
+        )?;
+        let definition = terraform_options.connector.clone();
+        let mut main = stamp(
+            &annotate(
+                &generator.generate_connector_config(terraform_options)?,
+                &options.metadata,
+            ),
+            Some(&definition),
+        );
+        if let Some(data_sources) = &secret_data_sources {
+            main = format!("{}\n{}", main, data_sources);
+        }
+
+        let mut files = match options.layout {
+            OutputLayout::Single => {
+                let mut content = if options.with_outputs {
+                    format!(
+                        "{}\n{}",
+                        main,
+                        generator.generate_outputs_config(&connector_name)?
+                    )
+                } else {
+                    main
+                };
+                if let Some(variables) = &secret_variables {
+                    content = format!("{}\n{}", content, variables);
+                }
+                vec![GeneratedFile {
+                    path: options.output.clone(),
+                    content,
+                }]
+            }
+            OutputLayout::Project => {
+                let output_dir = options.output_dir.as_deref().ok_or_else(|| {
+                    ConnectUtilError::Config(
+                        "--output-dir is required for the project layout".to_string(),
+                    )
+                })?;
+                let path_for = |file_name: &str| {
+                    Path::new(output_dir)
+                        .join(file_name)
+                        .to_string_lossy()
+                        .to_string()
+                };
+                let variables = generator
+                    .generate_variables_config(&terraform_options_companions, &environments)?;
+                vec![
+                    GeneratedFile {
+                        path: Some(path_for("main.tf")),
+                        content: main,
+                    },
+                    GeneratedFile {
+                        path: Some(path_for("variables.tf")),
+                        content: stamp(
+                            &match &secret_variables {
+                                Some(secrets) => format!("{}\n{}", variables, secrets),
+                                None => variables,
+                            },
+                            None,
+                        ),
+                    },
+                    GeneratedFile {
+                        path: Some(path_for("outputs.tf")),
+                        content: stamp(&generator.generate_outputs_config(&connector_name)?, None),
+                    },
+                ]
+            }
+        };
+        if let Some(content) = streams {
+            let path = match options.layout {
+                OutputLayout::Single => options.output.as_ref().map(|output| {
+                    Path::new(output)
+                        .with_extension("sql")
+                        .to_string_lossy()
+                        .to_string()
+                }),
+                OutputLayout::Project => options.output_dir.as_ref().map(|dir| {
+                    Path::new(dir)
+                        .join("streams.sql")
+                        .to_string_lossy()
+                        .to_string()
+                }),
+            };
+            files.push(GeneratedFile { path, content });
+        }
+        if let (Some(sops_options), false) = (&options.sops, secret_keys.is_empty()) {
+            let path = match options.layout {
+                OutputLayout::Single => options.output.as_ref().map(|output| {
+                    Path::new(output)
+                        .with_file_name(sops::SECRETS_FILE)
+                        .to_string_lossy()
+                        .to_string()
+                }),
+                OutputLayout::Project => options.output_dir.as_ref().map(|dir| {
+                    Path::new(dir)
+                        .join(sops::SECRETS_FILE)
+                        .to_string_lossy()
+                        .to_string()
+                }),
+            };
+            let names: Vec<String> = secret_keys
+                .iter()
+                .map(|key| TerraformGenerator::secret_variable_name(&connector_name, key))
+                .collect();
+            let content = sops::encrypt(
+                &sops::secrets_tfvars(&names),
+                path.as_deref().unwrap_or(sops::SECRETS_FILE),
+                sops_options,
+            )?;
+            files.push(GeneratedFile { path, content });
+        }
+        Ok(files)
+    }
+
+    /// Renders the connector for a self-managed Connect cluster: the config is
+    /// assembled as for Confluent Cloud, then converted to the self-managed class
+    /// and converters and emitted as a REST payload or the provider's resource
+    fn render_self_managed(
+        &self,
+        options: &ConnectorOptions,
+        terraform_options: TerraformConfigOptions,
+    ) -> Result<Vec<GeneratedFile>, ConnectUtilError> {
+        let companions = options.companions;
+        let unsupported = [
+            (options.layout == OutputLayout::Project, "--layout project"),
+            (options.with_outputs, "--with-outputs"),
+            (
+                companions.topics || companions.service_account || companions.api_key,
+                "--with-topics, --with-service-account, and --with-api-key",
+            ),
+            (options.import_name.is_some(), "--with-import-block"),
+            (
+                !terraform_options.for_each_environments.is_empty(),
+                "--environment-layout for-each",
+            ),
+            (options.sops.is_some(), "--sops"),
+            (
+                options.secrets_backend != SecretsBackend::Inline,
+                "--secrets-backend",
+            ),
+            (!options.ksql_tables.is_empty(), "--with-ksql"),
+            (options.schema_registry.csfle, "--csfle"),
+            (options.merge, "--merge-into"),
+            (
+                options.append && options.target == GenerateTarget::SelfManaged,
+                "--append",
+            ),
+        ];
+        if let Some((_, flag)) = unsupported.iter().find(|(set, _)| *set) {
             return Err(ConnectUtilError::Config(format!(
-                "❌ Module '{}' missing 'config_nonsensitive' attribute",
-                module_name
+                "{} only applies to Confluent Cloud connectors",
+                flag
+            )));
+        }
+
+        let definition = terraform_options.connector.clone();
+        let main = TerraformGenerator.generate_connector_config(terraform_options)?;
+        let config = self
+            .parse_terraform_configs(&main)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                ConnectUtilError::Terraform("Generated connector could not be parsed".to_string())
+            })?;
+        let connector = self_managed_connector(&config)?;
+        for note in &connector.notes {
+            say!("ℹ️  {}", note);
+        }
+
+        let content = match options.target {
+            GenerateTarget::SelfManaged => {
+                let mut config = connector.config.clone();
+                config.extend(connector.config_sensitive.clone());
+                let payload = serde_json::json!({ "name": connector.name, "config": config });
+                serde_json::to_string_pretty(&payload)? + "\n"
+            }
+            GenerateTarget::Cloud => {
+                // A file being appended to already configures the provider
+                let appending = options.append
+                    && options
+                        .output
+                        .as_deref()
+                        .is_some_and(|output| Path::new(output).exists());
+                let resource = TerraformGenerator.generate_provider_resource(
+                    options.provider,
+                    &connector,
+                    !appending,
+                )?;
+                stamp(&annotate(&resource, &options.metadata), Some(&definition))
+            }
+        };
+        Ok(vec![GeneratedFile {
+            path: options.output.clone(),
+            content,
+        }])
+    }
+
+    /// ksqlDB streams over a CDC source's change topics for `--with-ksql`, reading
+    /// the prefix and format the connector is generated with
+    fn ksql_streams(
+        options: &ConnectorOptions,
+        terraform_options: &TerraformConfigOptions,
+    ) -> Result<Option<String>, ConnectUtilError> {
+        if options.ksql_tables.is_empty() {
+            return Ok(None);
+        }
+        let connector = &terraform_options.connector;
+        if connector.topic_strategy != TopicStrategy::TopicPrefix {
+            return Err(ConnectUtilError::Config(format!(
+                "--with-ksql needs a CDC source configured with topic.prefix; {} isn't one",
+                connector.name
             )));
         }
+        let templates = &terraform_options.template_values;
+        let topic_prefix = templates
+            .get("topic.prefix")
+            .or(terraform_options.topic_prefix.as_ref());
+        let value_format = match templates.get("output.data.format") {
+            Some(format) => format.parse().map_err(ConnectUtilError::Config)?,
+            None => terraform_options
+                .output_data_format
+                .unwrap_or(DataFormat::Avro),
+        };
+        generate_streams(&StreamScaffold {
+            connector_name: &terraform_options.connector_name,
+            topic_prefix: topic_prefix.map(String::as_str),
+            tables: &options.ksql_tables,
+            value_format,
+        })
+        .map(Some)
+    }
+
+    /// Traces the generated config's values to their sources for `--verbose` and
+    /// `--report`; None when neither is set
+    fn trace_values(
+        &self,
+        options: &ConnectorOptions,
+        terraform_options: &TerraformConfigOptions,
+        prompted: &BTreeSet<String>,
+    ) -> Result<Option<GenerationReport>, ConnectUtilError> {
+        if !options.verbose && options.report.is_none() {
+            return Ok(None);
+        }
+        let main = TerraformGenerator.generate_connector_config(terraform_options.clone())?;
+        let config = self
+            .parse_terraform_configs(&main)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                ConnectUtilError::Terraform("Generated connector could not be parsed".to_string())
+            })?;
+        Ok(Some(GenerationReport {
+            connector_name: terraform_options.connector_name.clone(),
+            connector: terraform_options.connector.name.clone(),
+            files: Vec::new(),
+            values: provenance::trace(terraform_options, &config, prompted),
+        }))
+    }
 
+    /// Prints the traced values for `--verbose` and writes the `--report` file
+    fn report_values(
+        &self,
+        options: &ConnectorOptions,
+        report: Option<GenerationReport>,
+        files: &[GeneratedFile],
+    ) -> Result<(), ConnectUtilError> {
+        let Some(mut report) = report else {
+            return Ok(());
+        };
+        if options.verbose {
+            say!("📋 Value sources for {}:", report.connector_name);
+            say!("{}", provenance::render_text(&report.values));
+        }
+        let Some(path) = &options.report else {
+            return Ok(());
+        };
+        report.files = files.iter().filter_map(|file| file.path.clone()).collect();
+        let content = Self::with_newlines(
+            options,
+            path,
+            None,
+            &format!("{}\n", serde_json::to_string_pretty(&report)?),
+        );
+        if options.dry_run {
+            say!("📝 {}", WritePlan::new(Some(path), &content));
+        } else {
+            std::fs::write(path, content)?;
+            say!("📝 Wrote report to {}", path);
+        }
         Ok(())
     }
 
-    pub async fn list_plugins(
+    /// Writes or prints every rendered file, creating the project directory if needed
+    pub fn emit_files(
+        &self,
+        options: &ConnectorOptions,
+        files: &[GeneratedFile],
+    ) -> Result<(), ConnectUtilError> {
+        if options.layout == OutputLayout::Project {
+            if options.append {
+                return Err(ConnectUtilError::Config(
+                    "--append is not supported with the project layout".to_string(),
+                ));
+            }
+            if options.merge {
+                return Err(ConnectUtilError::Config(
+                    "--merge-into is not supported with the project layout".to_string(),
+                ));
+            }
+            // A dry run refuses existing files the same way, so it predicts the real run
+            if !options.force {
+                if let Some(existing) = files
+                    .iter()
+                    .filter_map(|f| f.path.as_deref())
+                    .find(|p| Path::new(p).exists())
+                {
+                    return Err(ConnectUtilError::Config(format!(
+                        "Output file already exists: {} (use --force to overwrite)",
+                        existing
+                    )));
+                }
+            }
+            if let (Some(dir), false) = (&options.output_dir, options.dry_run) {
+                std::fs::create_dir_all(dir)?;
+            }
+        }
+
+        for file in files {
+            self.emit_file(options, file.path.as_deref(), &file.content)?;
+        }
+        Ok(())
+    }
+
+    /// Writes or prints generated configuration to the configured output path
+    pub fn emit_output(
+        &self,
+        options: &ConnectorOptions,
+        terraform_config: &str,
+    ) -> Result<(), ConnectUtilError> {
+        self.emit_file(options, options.output.as_deref(), terraform_config)
+    }
+
+    /// Writes or prints a single generated file.
+    /// With `dry_run` set, the rendered output and write plan are printed and
+    /// nothing is written. Existing files are only replaced with `force` or
+    /// extended with `append`, in a dry run too.
+    fn emit_file(
+        &self,
+        options: &ConnectorOptions,
+        output_path: Option<&str>,
+        terraform_config: &str,
+    ) -> Result<(), ConnectUtilError> {
+        let kind = match options.target {
+            GenerateTarget::SelfManaged => "Connect",
+            _ => "Terraform",
+        };
+        if output_path.is_some_and(stdio::is_stdio) && (options.append || options.merge) {
+            return Err(ConnectUtilError::Config(
+                "--append and --merge-into need a file; standard output can't be extended"
+                    .to_string(),
+            ));
+        }
+        let Some(output_path) = output_path.filter(|path| !stdio::is_stdio(path)) else {
+            if options.dry_run {
+                say!("📄 Generated {} Configuration (dry run):", kind);
+                println!("{}", terraform_config);
+                say!("📝 {}", WritePlan::new(None, terraform_config));
+            } else {
+                say!("📄 Generated {} Configuration:", kind);
+                println!("{}", terraform_config);
+            }
+            return Ok(());
+        };
+
+        if Path::new(output_path).file_name().is_none() {
+            return Err(ConnectUtilError::Config(format!(
+                "Output path {} names a directory or drive, not a file",
+                output_path
+            )));
+        }
+        let exists = Path::new(output_path).exists();
+        if options.merge {
+            return self.merge_file(options, output_path, terraform_config);
+        }
+        let existing = if Path::new(output_path).is_file() {
+            Some(std::fs::read_to_string(output_path)?)
+        } else {
+            None
+        };
+        let content = match &existing {
+            Some(existing) if options.append => {
+                TerraformGenerator::append_to_existing(existing, terraform_config)?
+            }
+            _ => {
+                if exists && !options.force {
+                    return Err(ConnectUtilError::Config(format!(
+                        "Output file already exists: {} (use --force to overwrite or --append to add to it)",
+                        output_path
+                    )));
+                }
+                terraform_config.to_string()
+            }
+        };
+        let content = Self::with_newlines(options, output_path, existing.as_deref(), &content);
+
+        if options.dry_run {
+            let mut plan = WritePlan::new(Some(output_path), &content);
+            plan.append = options.append && exists;
+            say!("📄 Generated {} Configuration (dry run):", kind);
+            println!("{}", terraform_config);
+            say!("📝 {}", plan);
+            return Ok(());
+        }
+
+        std::fs::write(output_path, &content)?;
+        say!("✅ {} configuration written to: {}", kind, output_path);
+        Ok(())
+    }
+
+    /// `content` with the line endings of `--newline`, else those of the file it
+    /// replaces, else the platform's. The SOPS secrets file is left as sops wrote it
+    fn with_newlines(
+        options: &ConnectorOptions,
+        output_path: &str,
+        existing: Option<&str>,
+        content: &str,
+    ) -> String {
+        if Path::new(output_path).file_name() == Some(std::ffi::OsStr::new(sops::SECRETS_FILE)) {
+            return content.to_string();
+        }
+        options
+            .newline
+            .or_else(|| existing.and_then(Newline::detect))
+            .unwrap_or_else(Newline::platform)
+            .apply(content)
+    }
+
+    /// Merges generated configuration into the connector resource already in
+    /// `output_path`, reporting added keys and keys the definition no longer has
+    fn merge_file(
+        &self,
+        options: &ConnectorOptions,
+        output_path: &str,
+        terraform_config: &str,
+    ) -> Result<(), ConnectUtilError> {
+        if !Path::new(output_path).exists() {
+            return Err(ConnectUtilError::Config(format!(
+                "File to merge into not found: {}",
+                output_path
+            )));
+        }
+        let existing = std::fs::read_to_string(output_path)?;
+        let (content, report) = merge_connector(&existing, terraform_config)?;
+        let content = restamp(&content, terraform_config);
+        let content = Self::with_newlines(options, output_path, Some(&existing), &content);
+
+        say!("🔀 Merging {} in {}", report.address, output_path);
+        for key in &report.added {
+            say!("  + {}", key);
+        }
+        if report.kept > 0 {
+            say!("  ✓ Kept {} filled-in value(s)", report.kept);
+        }
+        for unknown in &report.unknown {
+            match &unknown.renamed_to {
+                Some(renamed_to) => say!(
+                    "  ⚠️  {} is no longer in the connector definition (renamed to {}?)",
+                    unknown.key,
+                    renamed_to
+                ),
+                None => say!(
+                    "  ⚠️  {} is no longer in the connector definition",
+                    unknown.key
+                ),
+            }
+        }
+        for block in &report.appended {
+            say!("  + {} block", block);
+        }
+
+        if options.dry_run {
+            say!("📄 Merged Terraform Configuration (dry run):");
+            println!("{}", content);
+            say!("📝 {}", WritePlan::new(Some(output_path), &content));
+            return Ok(());
+        }
+        std::fs::write(output_path, &content)?;
+        say!("✅ Terraform configuration merged into: {}", output_path);
+        Ok(())
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    /// Prompts for everything the options leave open. With `discovery`, and no
+    /// environment profile requested, also offers the organization's environments
+    /// and clusters
+    pub async fn generate_terraform_interactive(
         &mut self,
-        filter_type: Option<String>,
+        options: ConnectorOptions,
+        discovery: Option<&dyn OrgApi>,
     ) -> Result<(), ConnectUtilError> {
-        let all_connectors = ConnectorDefinition::get_all_connectors();
+        say!("🚀 Welcome to the Kafka Connect Terraform Generator!");
+        say!();
 
-        let filtered_connectors = if let Some(filter) = filter_type {
-            let connector_type = match filter.to_lowercase().as_str() {
-                "source" => ConnectorType::Source,
-                "sink" => ConnectorType::Sink,
-                _ => {
-                    println!("❌ Invalid filter type. Use 'source' or 'sink'");
+        // Resolve environment profiles before prompting so a typo fails fast
+        if !options.environments.is_empty()
+            && options.environment_layout == EnvironmentLayout::Files
+        {
+            return Err(ConnectUtilError::Config(
+                "--environments with the files layout requires --name and --connector".to_string(),
+            ));
+        }
+        let (mut environment, for_each_environments) = Self::environment_options(&options)?;
+
+        // Connectors added after the first go into the same file, so their answers are
+        // prompted for rather than taken from the flags the first one used
+        let mut files: Vec<GeneratedFile> = Vec::new();
+        let mut reports = Vec::new();
+        let mut connector_options = options.clone();
+        loop {
+            let (connector_files, report) = self
+                .prompt_connector_files(
+                    &connector_options,
+                    discovery,
+                    &mut environment,
+                    &for_each_environments,
+                )
+                .await?;
+            Self::combine_files(&mut files, connector_files)?;
+            reports.extend(report);
+            if !Self::offers_more_connectors(&options) {
+                break;
+            }
+            say!();
+            let another = Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt("Add another connector to this file?")
+                .default(false)
+                .interact()
+                .map_err(|e| {
+                    ConnectUtilError::Config(format!("Failed to confirm another connector: {}", e))
+                })?;
+            if !another {
+                break;
+            }
+            say!();
+            connector_options = Self::next_connector_options(&options);
+        }
+
+        // Step 9: Output configuration, confirming before clobbering existing files
+        let mut options = options;
+        if !options.force && !options.append && !options.merge && !options.dry_run {
+            if let Some(existing) = files
+                .iter()
+                .filter_map(|f| f.path.as_deref())
+                .find(|p| Path::new(p).exists())
+            {
+                let overwrite = Confirm::with_theme(&ColorfulTheme::default())
+                    .with_prompt(format!("{} already exists. Overwrite?", existing))
+                    .default(false)
+                    .interact()
+                    .map_err(|e| {
+                        ConnectUtilError::Config(format!("Failed to confirm overwrite: {}", e))
+                    })?;
+                if !overwrite {
+                    say!("⏭️  Skipped writing {}", existing);
                     return Ok(());
                 }
-            };
-            all_connectors
-                .into_iter()
-                .filter(|c| c.connector_type == connector_type)
-                .collect()
+                options.force = true;
+            }
+        }
+        self.emit_files(&options, &files)?;
+        for report in reports {
+            self.report_values(&options, Some(report), &files)?;
+        }
+        Ok(())
+    }
+
+    /// Whether the wizard offers to add another connector to the same output.
+    /// Merging targets one existing resource, and the report, SOPS secrets, and
+    /// self-managed payloads describe a single connector
+    fn offers_more_connectors(options: &ConnectorOptions) -> bool {
+        options.target == GenerateTarget::Cloud
+            && options.provider == TerraformProvider::Confluent
+            && !options.merge
+            && options.report.is_none()
+            && options.sops.is_none()
+    }
+
+    /// Options for a further connector in the session: the output and environment
+    /// settings carry over, the per-connector flags don't
+    fn next_connector_options(options: &ConnectorOptions) -> ConnectorOptions {
+        ConnectorOptions {
+            name: None,
+            topics: Vec::new(),
+            input_format: None,
+            output_format: None,
+            import_name: None,
+            tables: Vec::new(),
+            exclude_tables: Vec::new(),
+            storage: StorageLayoutOptions::default(),
+            debezium: DebeziumOptions::default(),
+            record_schema: None,
+            ksql_tables: Vec::new(),
+            ..options.clone()
+        }
+    }
+
+    /// Adds a further connector's files to those generated so far. Files at the same
+    /// path are combined into one body: blocks both declare, such as shared
+    /// variables, are kept once, and the added connector's outputs are prefixed
+    /// with its resource name so they don't clash
+    fn combine_files(
+        files: &mut Vec<GeneratedFile>,
+        more: Vec<GeneratedFile>,
+    ) -> Result<(), ConnectUtilError> {
+        let resource = more
+            .iter()
+            .filter_map(|file| parse_body(&file.content).ok())
+            .flat_map(|body| TerraformGenerator::resource_addresses(&body))
+            .find_map(|address| {
+                address
+                    .strip_prefix("confluent_connector.")
+                    .map(str::to_string)
+            });
+        for file in more {
+            match files.iter_mut().find(|existing| existing.path == file.path) {
+                Some(existing) => {
+                    let mut added = TerraformFile::parse(&file.content)?;
+                    if let Some(resource) = &resource {
+                        added.prefix_outputs(resource);
+                    }
+                    let mut combined = TerraformFile::parse(&existing.content)?;
+                    combined.append_new(&added.to_string())?;
+                    existing.content = combined.to_string();
+                }
+                None => files.push(file),
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    /// Prompts for one connector and renders its files; the environment picked for
+    /// the first connector is reused for the rest
+    async fn prompt_connector_files(
+        &self,
+        options: &ConnectorOptions,
+        discovery: Option<&dyn OrgApi>,
+        environment: &mut Option<Environment>,
+        for_each_environments: &[Environment],
+    ) -> Result<(Vec<GeneratedFile>, Option<GenerationReport>), ConnectUtilError> {
+        // Step 1: Get connector name
+        let connector_name = if let Some(name) = options.name.clone() {
+            name
         } else {
-            all_connectors
+            Input::with_theme(&ColorfulTheme::default())
+                .with_prompt("Enter connector name")
+                .interact()
+                .map_err(|e| {
+                    ConnectUtilError::Config(format!("Failed to get connector name: {}", e))
+                })?
+        };
+
+        // Step 2: Get connector type
+        let connector_type = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Select connector type")
+            .items(&["Source", "Sink"])
+            .interact()
+            .map_err(|e| {
+                ConnectUtilError::Config(format!("Failed to select connector type: {}", e))
+            })?;
+
+        let connector_type_enum = match connector_type {
+            0 => ConnectorType::Source,
+            1 => ConnectorType::Sink,
+            _ => {
+                return Err(ConnectUtilError::Config(
+                    "Invalid connector type selection".to_string(),
+                ))
+            }
+        };
+
+        // Step 3: Pick the environment and cluster when Confluent Cloud credentials are set
+        if let (Some(api), None, true) = (discovery, &environment, for_each_environments.is_empty())
+        {
+            *environment = Self::prompt_cloud_environment(api).await?;
+        }
+
+        // Step 4: Get connector selection by family,
+        // offering only the classes the repo policy allows at the output path
+        let target = Self::output_target(options);
+        let policy = Policy::discover(&target)?.map(|(_, policy)| policy);
+        let available_connectors: Vec<ConnectorDefinition> =
+            ConnectorDefinition::get_connectors_by_type(&connector_type_enum)
+                .into_iter()
+                .filter(|c| {
+                    policy.as_ref().is_none_or(|policy| {
+                        policy
+                            .check_connector_class(&c.connector_class, &target)
+                            .is_ok()
+                    })
+                })
+                .collect();
+        if available_connectors.is_empty() {
+            return Err(ConnectUtilError::Validation(format!(
+                "The policy for {} doesn't allow any connector of this type",
+                target.display()
+            )));
+        }
+        let selected_connector = Self::prompt_connector(&available_connectors)?;
+
+        // Offer a compliant name when the entered one breaks the naming conventions
+        let mut connector_name = connector_name;
+        if self.naming.check_connector_name(&connector_name).is_err() {
+            let suggestion = self
+                .naming
+                .suggest_connector_name(&connector_name, &connector_type_enum);
+            if let Some(suggestion) = suggestion {
+                let accept = Confirm::with_theme(&ColorfulTheme::default())
+                    .with_prompt(format!(
+                        "'{}' breaks the naming convention. Use '{}' instead?",
+                        connector_name, suggestion
+                    ))
+                    .default(true)
+                    .interact()
+                    .map_err(|e| {
+                        ConnectUtilError::Config(format!("Failed to confirm name: {}", e))
+                    })?;
+                if accept {
+                    connector_name = suggestion;
+                }
+            }
+        }
+        let violations = self.naming_violations(
+            &connector_name,
+            &TerraformGenerator::resource_name(&connector_name),
+            &connector_type_enum,
+        );
+        if !violations.is_empty() {
+            return Err(ConnectUtilError::Validation(violations.join("; ")));
+        }
+
+        say!();
+        say!(
+            "📋 {} configuration fields:",
+            selected_connector.display_name
+        );
+        Self::print_fields_by_group(selected_connector);
+        say!();
+
+        // Step 5: Pick data formats among those the connector supports
+        Self::requested_formats(options, selected_connector)?;
+        let input_data_format = if selected_connector.connector_type == ConnectorType::Sink {
+            Self::prompt_data_format(
+                selected_connector,
+                "input.data.format",
+                options.input_format,
+            )?
+        } else {
+            None
+        };
+        let output_data_format = Self::prompt_data_format(
+            selected_connector,
+            "output.data.format",
+            options.output_format,
+        )?;
+
+        // Step 6: Topics, or the topic prefix for CDC sources; empty keeps the placeholders
+        let topics = if options.topics.is_empty() {
+            let strategy = selected_connector.topic_strategy;
+            let prompt = match strategy {
+                TopicStrategy::TopicPrefix => "Topic prefix (leave empty for a placeholder)",
+                TopicStrategy::KafkaTopicSingle => "Topic (leave empty for a placeholder)",
+                TopicStrategy::TopicsList => {
+                    "Topics, comma-separated (leave empty for a placeholder)"
+                }
+            };
+            let input: String = Input::with_theme(&ColorfulTheme::default())
+                .with_prompt(prompt)
+                .allow_empty(true)
+                .validate_with(|input: &String| -> Result<(), String> {
+                    let topics = Self::parse_topics(input)?;
+                    match strategy {
+                        TopicStrategy::TopicPrefix if topics.len() > 1 => {
+                            Err("Enter a single topic prefix".to_string())
+                        }
+                        TopicStrategy::KafkaTopicSingle if topics.len() > 1 => {
+                            Err("Enter a single topic".to_string())
+                        }
+                        _ => Ok(()),
+                    }
+                })
+                .interact_text()
+                .map_err(|e| ConnectUtilError::Config(format!("Failed to get topics: {}", e)))?;
+            Self::parse_topics(&input).map_err(ConnectUtilError::UserInput)?
+        } else {
+            options.topics.clone()
+        };
+        let (topics, topic_prefix) = Self::topic_settings(selected_connector, &topics)?;
+
+        // Step 7: Captured tables for CDC sources, or the object layout for object-store
+        // sinks; empty leaves table.include.list out
+        let tables = if options.tables.is_empty()
+            && options.exclude_tables.is_empty()
+            && selected_connector.has_field(TABLE_INCLUDE_LIST)
+        {
+            let input: String = Input::with_theme(&ColorfulTheme::default())
+                .with_prompt("Tables to capture as schema.table patterns, comma-separated (leave empty for all)")
+                .allow_empty(true)
+                .validate_with(|input: &String| parse_table_patterns(input).map(|_| ()))
+                .interact_text()
+                .map_err(|e| ConnectUtilError::Config(format!("Failed to get tables: {}", e)))?;
+            parse_table_patterns(&input).map_err(ConnectUtilError::UserInput)?
+        } else {
+            options.tables.clone()
+        };
+        let storage = if options.storage.is_empty()
+            && selected_connector.family == ConnectorFamily::ObjectStorage
+            && selected_connector.connector_type == ConnectorType::Sink
+        {
+            Self::prompt_storage_layout()?
+        } else {
+            options.storage.clone()
+        };
+
+        // Step 8: Generate Terraform configuration
+        let mut template_values = Self::template_values(options, &connector_name)?;
+        let connector_name = template_values.remove("name").unwrap_or(connector_name);
+        let (tables, exclude_tables) = Self::table_settings(
+            selected_connector,
+            &tables,
+            &options.exclude_tables,
+            &template_values,
+        )?;
+        let storage = Self::storage_settings(selected_connector, &storage)?;
+        let debezium = Self::debezium_settings(selected_connector, &options.debezium, &tables)?;
+        let schema_values =
+            Self::schema_settings(selected_connector, options.record_schema.as_ref())?;
+        let terraform_options = TerraformConfigOptions {
+            connector_name,
+            connector: selected_connector.clone(),
+            topics,
+            topic_prefix,
+            tables,
+            exclude_tables,
+            storage,
+            debezium,
+            schema_values,
+            input_data_format,
+            output_data_format,
+            companions: options.companions,
+            schema_registry: options.schema_registry.clone(),
+            environment: environment.clone(),
+            for_each_environments: for_each_environments.to_vec(),
+            cluster_type: options.cluster_type,
+            import_name: Self::import_name(options)?,
+            ignore_changes: Some(Self::ignore_changes(options)?),
+            template_values,
+            secrets_backend: Self::secrets_backend(options),
+        };
+        // Keys answered in the wizard, for `--verbose` and `--report`
+        let prompted: BTreeSet<String> = [
+            (options.name.is_none(), "name"),
+            (
+                options.topics.is_empty()
+                    && (!terraform_options.topics.is_empty()
+                        || terraform_options.topic_prefix.is_some()),
+                selected_connector.topic_strategy.key(),
+            ),
+            (
+                options.input_format.is_none() && terraform_options.input_data_format.is_some(),
+                "input.data.format",
+            ),
+            (
+                options.output_format.is_none() && terraform_options.output_data_format.is_some(),
+                "output.data.format",
+            ),
+            (
+                options.tables.is_empty() && !terraform_options.tables.is_empty(),
+                TABLE_INCLUDE_LIST,
+            ),
+        ]
+        .into_iter()
+        .filter(|(answered, _)| *answered)
+        .map(|(_, key)| key.to_string())
+        .collect();
+        let report = self.trace_values(options, &terraform_options, &prompted)?;
+        let files = self.render_output_files(options, terraform_options)?;
+        Ok((files, report))
+    }
+
+    /// Validates a Terraform connector configuration file
+    /// Checks both the connector configuration and Terraform structure
+    pub async fn validate_connector(&mut self, config_file: &str) -> Result<(), ConnectUtilError> {
+        let source = self.read_source(config_file)?;
+        // Locals may live in any file of the module, e.g. a shared locals.tf
+        let module_dir = source
+            .path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        let mut locals = module_locals(module_dir);
+        if let Ok(body) = &source.body {
+            locals.extend(collect_locals(body));
+        }
+        self.validate_source(&source, &locals)
+    }
+
+    /// `validate_connector` for a file already read and parsed, given its module's
+    /// locals including the file's own
+    fn validate_source(
+        &self,
+        source: &SourceFile,
+        locals: &Locals,
+    ) -> Result<(), ConnectUtilError> {
+        let _span = tracing::debug_span!("validate").entered();
+        let config_path = source.path.as_path();
+        let terraform_content = source.content.as_str();
+
+        // Check if the entire file is commented out
+        let all_lines_commented = terraform_content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .all(|line| line.trim().starts_with('#'));
+
+        if all_lines_commented {
+            say!("✅ File is commented out - no validation needed");
+            say!("📋 Configuration Summary:");
+            say!("  Status: Commented out");
+            say!("  Note: This file contains no active connector configuration");
+            return Ok(());
+        }
+
+        // Extract all connector configurations from the parsed file
+        let body = source.body.as_ref().map_err(parse_error)?;
+        let connector_configs = self.connector_configs(body, locals);
+        for note in self.meta_argument_notes(body, locals) {
+            say!("ℹ️  {}", note);
+        }
+
+        // Pick up org policies from the repo-level policy file, if there is one
+        let (policy_path, policy) = self.validation_policy(config_path)?;
+        if let Some(policy_path) = policy_path {
+            say!("📜 Enforcing policy file: {}", policy_path.display());
+        }
+
+        let suppressions = Suppressions::parse(terraform_content);
+        for (line, rule) in &suppressions.unknown {
+            say!(
+                "⚠️  Line {}: connect-util:ignore names unknown rule '{}'",
+                line,
+                rule
+            );
+        }
+        let others = other_blocks(body);
+        // Connectors managed elsewhere get the data source's own checks, not the
+        // resource ones
+        let data_sources = connector_data_sources(body);
+        let check_data_sources = || {
+            let addresses: Vec<String> = data_sources.iter().map(|d| d.address()).collect();
+            say!(
+                "🔗 Skipping resource checks for {} existing connector(s) looked up with data sources: {}",
+                addresses.len(),
+                addresses.join(", ")
+            );
+            for data_source in &data_sources {
+                let line = block_line(terraform_content, &data_source.address());
+                for problem in data_source.problems() {
+                    Self::report_finding(
+                        &suppressions,
+                        &policy,
+                        LintRule::InvalidConfig,
+                        line,
+                        Some("Data source validation failed"),
+                        &problem,
+                    );
+                }
+            }
+        };
+
+        if connector_configs.is_empty() {
+            if !data_sources.is_empty() {
+                check_data_sources();
+                return Ok(());
+            }
+            // Topics, service accounts, and lookups often live in their own files
+            if others.is_empty() {
+                return Err(ConnectUtilError::Config(
+                    "No connector configurations found in the file.".to_string(),
+                ));
+            }
+            let message = format!(
+                "No connector configurations found in the file; it has {}",
+                others.join(", ")
+            );
+            let reported = Self::report_finding(
+                &suppressions,
+                &policy,
+                LintRule::NoConnectors,
+                1,
+                None,
+                &message,
+            );
+            if reported && policy.rule_severity(LintRule::NoConnectors) == Some("error") {
+                return Err(ConnectUtilError::Validation(message));
+            }
+            return Ok(());
+        }
+
+        say!(
+            "🔍 Found {} connector configuration(s) to validate",
+            connector_configs.len()
+        );
+        if !others.is_empty() {
+            say!(
+                "📦 Skipping {} other block(s): {}",
+                others.len(),
+                others.join(", ")
+            );
+        }
+        if !data_sources.is_empty() {
+            check_data_sources();
+        }
+
+        // Naming and policy violations that stand at error severity fail the file,
+        // so CI can enforce the naming rules and the policy file
+        let fails = |rule| policy.rule_severity(rule) == Some("error");
+        let (mut naming_violations, mut policy_violations) = (0, 0);
+        for (index, config) in connector_configs.iter().enumerate() {
+            say!(
+                "\n--- Validating Connector {} of {} ---",
+                index + 1,
+                connector_configs.len()
+            );
+
+            if is_dynamic_value(&config.connector_class) {
+                say!(
+                    "ℹ️  Connector class for '{}' is dynamic ({}) — skipped configuration checks",
+                    config.name,
+                    config.connector_class
+                );
+                continue;
+            }
+
+            // Find the connector definition
+            let connector_def = ConnectorDefinition::get_connector_by_name(&config.connector_class)
+                .ok_or_else(|| {
+                    ConnectUtilError::Config(format!(
+                        "Unknown connector: {}",
+                        config.connector_class
+                    ))
+                })?;
+
+            let line = block_line(terraform_content, &config.name);
+            let report = |rule, heading, message: &str| {
+                Self::report_finding(&suppressions, &policy, rule, line, heading, message)
+            };
+
+            // Check naming conventions for the connector name and resource label
+            let connector_name = config.config.get("name").unwrap_or(&config.name);
+            for violation in
+                self.naming_violations(connector_name, &config.name, &connector_def.connector_type)
+            {
+                if report(
+                    LintRule::NamingConvention,
+                    Some("Naming convention violation"),
+                    &violation,
+                ) && fails(LintRule::NamingConvention)
+                {
+                    naming_violations += 1;
+                }
+            }
+
+            for violation in policy.check(config, config_path) {
+                if report(LintRule::Policy, Some("Policy violation"), &violation)
+                    && fails(LintRule::Policy)
+                {
+                    policy_violations += 1;
+                }
+            }
+            if let Some(ignored) = connector_ignore_changes(body, base_label(&config.name)) {
+                for violation in policy.check_ignore_changes(&config.name, &ignored) {
+                    if report(LintRule::Policy, Some("Policy violation"), &violation)
+                        && fails(LintRule::Policy)
+                    {
+                        policy_violations += 1;
+                    }
+                }
+                for warning in unset_ignored_keys(&config.name, &ignored, &config.config) {
+                    report(LintRule::UnsetIgnoredKey, None, &warning);
+                }
+            }
+
+            for key in config.dynamic_keys() {
+                say!("ℹ️  Dynamic value for '{}' — skipped strict checks", key);
+            }
+
+            // Validate the configuration
+            match connector_def.validate_config(&config.config, &config.sensitive_config) {
+                Ok(()) => {
+                    say!("✅ Configuration is valid!");
+                    say!("📋 Configuration Summary:");
+                    say!("  Connector: {}", connector_def.display_name);
+                    say!("  Required configs: ✅ All present");
+                    say!("  Sensitive configs: ✅ Properly separated");
+                    say!("  Non-sensitive configs: {} fields", config.config.len());
+                    say!(
+                        "  Sensitive configs: {} fields",
+                        config.sensitive_config.len()
+                    );
+                }
+                Err(error) => {
+                    report(
+                        LintRule::InvalidConfig,
+                        Some("Configuration validation failed"),
+                        &error.to_string(),
+                    );
+                }
+            }
+        }
+
+        // Note: We don't return an error here even if validation fails
+        // The validation errors are printed above, but the function should still return Ok
+        // unless there's a parsing error or other non-validation error
+
+        // Validate environment-specific Terraform structure
+        self.validate_terraform_structure(body)?;
+
+        let failures: Vec<String> = [
+            (naming_violations, "naming convention"),
+            (policy_violations, "policy"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, kind)| format!("{} {} violation(s)", count, kind))
+        .collect();
+        if !failures.is_empty() {
+            return Err(ConnectUtilError::Validation(format!(
+                "{} in {}",
+                failures.join(" and "),
+                config_path.display()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validates every connector file in a directory, then checks that each module
+    /// declares the variables its connectors reference and routes sensitive ones
+    /// into `config_sensitive`
+    pub async fn validate_directory(&mut self, dir: &str) -> Result<(), ConnectUtilError> {
+        let root = Path::new(dir);
+        if !root.is_dir() {
+            return Err(ConnectUtilError::Config(format!(
+                "Directory not found: {}",
+                dir
+            )));
+        }
+        let mut files = Vec::new();
+        collect_terraform_files(root, &mut files)?;
+        files.sort();
+        let modules = group_by_module(&files);
+        let progress = Progress::new(files.len(), "files");
+
+        let scanner = SecretScanner::new();
+        let mut findings = ValidationFindings {
+            issues: Vec::new(),
+            entries: Vec::new(),
+            timings: Vec::new(),
+        };
+        let (mut unreadable, mut failed) = (0, 0);
+        let (mut conflict_count, mut variable_errors) = (0, 0);
+        for (module_dir, module_files) in &modules {
+            // Every check below, and the stats, share the module's one parse
+            let module = ModuleSources::load(module_dir, module_files)?;
+            let mut connectors = Vec::new();
+            for source in &module.files {
+                progress.advance();
+                progress.start(source.path.display().to_string());
+                let Ok(body) = &source.body else {
+                    continue;
+                };
+                let configs = self.connector_configs(body, &module.locals);
+                if configs.is_empty() && connector_data_sources(body).is_empty() {
+                    continue;
+                }
+                say!("\n📄 {}", source.path.display());
+                match self.validate_source(source, &module.locals) {
+                    Ok(()) => {}
+                    Err(e @ ConnectUtilError::Validation(_)) => {
+                        failed += 1;
+                        say!("❌ {}", e);
+                    }
+                    Err(e) => {
+                        unreadable += 1;
+                        say!("❌ {}", e);
+                    }
+                }
+                connectors.extend(configs.into_iter().map(|config| ModuleConnector {
+                    file: source.path.clone(),
+                    config,
+                }));
+            }
+
+            let conflicts = find_conflicts(&connectors);
+            if connectors.len() > 1 {
+                say!(
+                    "\n🔍 Checking {} connectors in {} for conflicts",
+                    connectors.len(),
+                    module_dir.display()
+                );
+                if conflicts.is_empty() {
+                    say!("✅ No duplicate names, labels, or topic conflicts");
+                }
+            }
+            conflict_count += conflicts.len();
+            for conflict in conflicts {
+                say!("❌ Conflict: {}", conflict);
+            }
+
+            let (issues, timings) = self.lint_module(&scanner, &module)?;
+            findings.issues.extend(issues);
+            findings.timings.extend(timings);
+            findings.entries.extend(self.inventory_module(&module));
+
+            let variables = match ModuleVariables::analyze_parsed(&module.files) {
+                Ok(variables) => variables,
+                Err(e) => {
+                    unreadable += 1;
+                    say!("❌ {}", e);
+                    continue;
+                }
+            };
+            if variables.references.is_empty() && variables.declarations.is_empty() {
+                continue;
+            }
+            say!(
+                "\n🔍 Checking variable references in {}",
+                module_dir.display()
+            );
+            let variable_findings = variables.findings();
+            if variable_findings.is_empty() {
+                say!("✅ All variable references are declared and routed correctly");
+            }
+            for finding in variable_findings {
+                if finding.is_error() {
+                    variable_errors += 1;
+                    say!("❌ {}", finding);
+                } else {
+                    say!("⚠️  {}", finding);
+                }
+            }
+        }
+
+        drop(progress);
+        findings.issues = self.apply_suppressions(findings.issues, &[])?;
+        let stats = findings.stats();
+        say!("\n{}", stats.render().trim_end());
+
+        if unreadable > 0 {
+            return Err(ConnectUtilError::Config(format!(
+                "{} file(s) in {} could not be validated",
+                unreadable, dir
+            )));
+        }
+        let failures: Vec<String> = [
+            (failed, "file(s) failed validation"),
+            (conflict_count, "conflict(s) between connectors"),
+            (variable_errors, "variable error(s)"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, what)| format!("{} {}", count, what))
+        .collect();
+        if !failures.is_empty() {
+            return Err(ConnectUtilError::Validation(format!(
+                "{} in {}",
+                failures.join(", "),
+                dir
+            )));
+        }
+        Ok(())
+    }
+
+    /// Runs the connector, conflict, variable, and secret checks over every module in
+    /// `dir` and returns the findings with the file and line each one belongs to
+    pub fn lint_directory(&self, dir: &str) -> Result<Vec<LintIssue>, ConnectUtilError> {
+        self.lint_directory_timed(dir).map(|(issues, _)| issues)
+    }
+
+    /// `lint_directory`, also timing the checks of each file
+    fn lint_directory_timed(
+        &self,
+        dir: &str,
+    ) -> Result<(Vec<LintIssue>, Vec<FileTiming>), ConnectUtilError> {
+        let root = Path::new(dir);
+        if !root.is_dir() {
+            return Err(ConnectUtilError::Config(format!(
+                "Directory not found: {}",
+                dir
+            )));
+        }
+        let mut files = Vec::new();
+        collect_terraform_files(root, &mut files)?;
+        files.sort();
+
+        let scanner = SecretScanner::new();
+        let mut issues = Vec::new();
+        let mut timings = Vec::new();
+        for (module_dir, module_files) in group_by_module(&files) {
+            let module = ModuleSources::load(&module_dir, &module_files)?;
+            let (module_issues, module_timings) = self.lint_module(&scanner, &module)?;
+            issues.extend(module_issues);
+            timings.extend(module_timings);
+        }
+        Ok((self.apply_suppressions(issues, &[])?, timings))
+    }
+
+    /// The findings of one parsed module before suppressions are applied, and how
+    /// long each file took to parse and check
+    fn lint_module(
+        &self,
+        scanner: &SecretScanner,
+        module: &ModuleSources,
+    ) -> Result<(Vec<LintIssue>, Vec<FileTiming>), ConnectUtilError> {
+        let _span = tracing::debug_span!("validate").entered();
+        let mut issues = Vec::new();
+        let mut timings = Vec::new();
+        let line_in = |file: &Path, label: &str| {
+            module
+                .file(file)
+                .map(|source| block_line(&source.content, label))
+                .unwrap_or(1)
+        };
+
+        let mut connectors = Vec::new();
+        for source in &module.files {
+            let (file, content) = (&source.path, &source.content);
+            let started = Instant::now();
+            let elapsed = || source.elapsed + started.elapsed();
+            issues.extend(
+                scanner
+                    .scan_parsed(file, content, source.body.as_ref().ok())
+                    .into_iter()
+                    .map(|finding| LintIssue {
+                        rule: LintRule::Scan(finding.rule),
+                        severity: LintRule::Scan(finding.rule).severity(),
+                        file: finding.file,
+                        line: finding.line,
+                        connector: None,
+                        message: finding.message,
+                    }),
+            );
+            let Ok(body) = &source.body else {
+                timings.push(FileTiming {
+                    file: file.clone(),
+                    elapsed: elapsed(),
+                });
+                continue;
+            };
+            let configs = self.connector_configs(body, &module.locals);
+            let (_, policy) = self.validation_policy(file)?;
+            for config in &configs {
+                let issue = |rule, message| LintIssue {
+                    rule,
+                    severity: rule.severity(),
+                    file: file.clone(),
+                    line: block_line(content, &config.name),
+                    connector: Some(config.name.clone()),
+                    message,
+                };
+                if is_dynamic_value(&config.connector_class) {
+                    continue;
+                }
+                let Some(definition) =
+                    ConnectorDefinition::get_connector_by_name(&config.connector_class)
+                else {
+                    issues.push(issue(
+                        LintRule::UnknownConnector,
+                        format!(
+                            "'{}' uses unknown connector class '{}'",
+                            config.name, config.connector_class
+                        ),
+                    ));
+                    continue;
+                };
+                let connector_name = config.config.get("name").unwrap_or(&config.name);
+                for violation in
+                    self.naming_violations(connector_name, &config.name, &definition.connector_type)
+                {
+                    issues.push(issue(LintRule::NamingConvention, violation));
+                }
+                for violation in policy.check(config, file) {
+                    issues.push(issue(LintRule::Policy, violation));
+                }
+                if let Some(ignored) = connector_ignore_changes(body, base_label(&config.name)) {
+                    for violation in policy.check_ignore_changes(&config.name, &ignored) {
+                        issues.push(issue(LintRule::Policy, violation));
+                    }
+                    for warning in unset_ignored_keys(&config.name, &ignored, &config.config) {
+                        issues.push(issue(LintRule::UnsetIgnoredKey, warning));
+                    }
+                }
+                if let Err(error) =
+                    definition.validate_config(&config.config, &config.sensitive_config)
+                {
+                    issues.push(issue(
+                        LintRule::InvalidConfig,
+                        format!("'{}': {}", config.name, error),
+                    ));
+                }
+            }
+            for data_source in connector_data_sources(body) {
+                let address = data_source.address();
+                for problem in data_source.problems() {
+                    issues.push(LintIssue {
+                        rule: LintRule::InvalidConfig,
+                        severity: LintRule::InvalidConfig.severity(),
+                        file: file.clone(),
+                        line: block_line(content, &address),
+                        connector: Some(address.clone()),
+                        message: problem,
+                    });
+                }
+            }
+            connectors.extend(configs.into_iter().map(|config| ModuleConnector {
+                file: file.clone(),
+                config,
+            }));
+            timings.push(FileTiming {
+                file: file.clone(),
+                elapsed: elapsed(),
+            });
+        }
+
+        for conflict in find_conflicts(&connectors) {
+            let message = conflict.to_string();
+            let anchor = connectors
+                .iter()
+                .find(|connector| message.contains(&connector.location()));
+            let (file, line) = match anchor {
+                Some(connector) => (
+                    connector.file.clone(),
+                    line_in(&connector.file, &connector.config.name),
+                ),
+                None => (module.files[0].path.clone(), 1),
+            };
+            issues.push(LintIssue {
+                rule: LintRule::Conflict,
+                severity: LintRule::Conflict.severity(),
+                file,
+                line,
+                connector: anchor.map(|connector| connector.config.name.clone()),
+                message,
+            });
+        }
+
+        let Ok(variables) = ModuleVariables::analyze_parsed(&module.files) else {
+            return Ok((issues, timings));
+        };
+        for finding in variables.findings() {
+            let message = finding.to_string();
+            let (rule, file, line, connector) = match finding {
+                VariableFinding::Undeclared { address, file, .. } => {
+                    let label = address.rsplit('.').next().unwrap_or(&address).to_string();
+                    let line = line_in(&file, &label);
+                    (LintRule::UndeclaredVariable, file, line, Some(label))
+                }
+                VariableFinding::SensitiveNotRouted { address, file, .. } => {
+                    let label = address.rsplit('.').next().unwrap_or(&address).to_string();
+                    let line = line_in(&file, &label);
+                    (
+                        LintRule::SensitiveVariableNotRouted,
+                        file,
+                        line,
+                        Some(label),
+                    )
+                }
+                VariableFinding::Unused { name, file } => {
+                    let declaration = format!("variable \"{}\"", name);
+                    let line = module
+                        .file(&file)
+                        .and_then(|source| {
+                            source
+                                .content
+                                .lines()
+                                .position(|l| l.trim_start().starts_with(&declaration))
+                        })
+                        .map(|index| index + 1)
+                        .unwrap_or(1);
+                    (LintRule::UnusedVariable, file, line, None)
+                }
+            };
+            issues.push(LintIssue {
+                rule,
+                severity: rule.severity(),
+                file,
+                line,
+                connector,
+                message,
+            });
+        }
+        Ok((issues, timings))
+    }
+
+    /// Drops findings silenced by a `connect-util:ignore` comment or turned `off` in
+    /// the policy file, and applies the policy's severity overrides to the rest.
+    /// Comments are read from `sources` when a file is among them, else from disk
+    fn apply_suppressions(
+        &self,
+        issues: Vec<LintIssue>,
+        sources: &[SourceFile],
+    ) -> Result<Vec<LintIssue>, ConnectUtilError> {
+        let mut files: HashMap<PathBuf, (Suppressions, Policy)> = HashMap::new();
+        let mut kept = Vec::new();
+        for mut issue in issues {
+            if !files.contains_key(&issue.file) {
+                let suppressions = match sources.iter().find(|source| source.path == issue.file) {
+                    Some(source) => Suppressions::parse(&source.content),
+                    None => std::fs::read_to_string(&issue.file)
+                        .map(|content| Suppressions::parse(&content))
+                        .unwrap_or_default(),
+                };
+                for (line, rule) in &suppressions.unknown {
+                    tracing::warn!(
+                        "{}:{}: connect-util:ignore names unknown rule '{}'",
+                        issue.file.display(),
+                        line,
+                        rule
+                    );
+                }
+                let (_, policy) = self.validation_policy(&issue.file)?;
+                files.insert(issue.file.clone(), (suppressions, policy));
+            }
+            let (suppressions, policy) = &files[&issue.file];
+            if let Some(suppression) = suppressions.find(issue.rule, issue.line, &issue.message) {
+                tracing::debug!(
+                    "{}:{}: {} suppressed: {}",
+                    issue.file.display(),
+                    suppression.line,
+                    issue.rule.name(),
+                    suppression.reason
+                );
+                continue;
+            }
+            let Some(severity) = policy.rule_severity(issue.rule) else {
+                continue;
+            };
+            issue.severity = severity;
+            kept.push(issue);
+        }
+        Ok(kept)
+    }
+
+    /// Prints a connector finding, as `heading` then the message or as one line
+    /// without a heading, unless it is suppressed or turned `off`. Returns whether
+    /// the finding still stands
+    fn report_finding(
+        suppressions: &Suppressions,
+        policy: &Policy,
+        rule: LintRule,
+        line: usize,
+        heading: Option<&str>,
+        message: &str,
+    ) -> bool {
+        if let Some(suppression) = suppressions.find(rule, line, message) {
+            say!(
+                "🔕 {} suppressed by line {}: {}",
+                rule.name(),
+                suppression.line,
+                message
+            );
+            return false;
+        }
+        let icon = match policy.rule_severity(rule) {
+            Some("error") => "❌",
+            Some(_) => "⚠️ ",
+            None => return false,
+        };
+        match heading {
+            Some(heading) => {
+                say!("{} {}:", icon, heading);
+                say!("  {}", message);
+            }
+            None => say!("{} {}", icon, message),
+        }
+        true
+    }
+
+    /// Builds an inventory entry for every connector under `dir`, resolving each
+    /// module's locals and expanding static `for_each`/`count` instances
+    pub fn inventory(&self, dir: &str) -> Result<Vec<InventoryEntry>, ConnectUtilError> {
+        let root = Path::new(dir);
+        if !root.is_dir() {
+            return Err(ConnectUtilError::Config(format!(
+                "Directory not found: {}",
+                dir
+            )));
+        }
+        let mut files = Vec::new();
+        collect_terraform_files(root, &mut files)?;
+        files.sort();
+
+        let mut entries = Vec::new();
+        for (module_dir, module_files) in group_by_module(&files) {
+            let module = ModuleSources::load(&module_dir, &module_files)?;
+            entries.extend(self.inventory_module(&module));
+        }
+        Ok(entries)
+    }
+
+    /// Inventory entries for the connectors of one parsed module
+    fn inventory_module(&self, module: &ModuleSources) -> Vec<InventoryEntry> {
+        let mut entries = Vec::new();
+        for source in &module.files {
+            let Ok(body) = &source.body else {
+                continue;
+            };
+            let configs = self.connector_configs(body, &module.locals);
+            let metadata = parse_metadata(&source.content);
+            entries.extend(configs.iter().map(|config| {
+                let mut entry = InventoryEntry::from_config(source.path.clone(), config);
+                // Instances of a for_each/count block share its comments
+                let label = config.name.split('[').next().unwrap_or_default();
+                if let Some(metadata) = metadata.get(label) {
+                    entry.metadata = metadata.clone();
+                }
+                entry
+            }));
+            entries.extend(connector_data_sources(body).iter().map(|data_source| {
+                InventoryEntry::from_data_source(source.path.clone(), data_source)
+            }));
+        }
+        entries
+    }
+
+    /// Prints the connector inventory for quarterly security and platform reviews,
+    /// failing when a connector under one of the `production` directories has no owner
+    pub async fn audit(
+        &mut self,
+        dir: &str,
+        format: InventoryFormat,
+        production: &[String],
+    ) -> Result<(), ConnectUtilError> {
+        let entries = self.inventory(dir)?;
+        println!("{}", render_inventory(&entries, format)?.trim_end());
+
+        // Connectors looked up with a data source are owned where they're managed
+        let unowned: Vec<String> = entries
+            .iter()
+            .filter(|entry| entry.metadata.owner.is_none() && !entry.is_data_source())
+            .filter(|entry| {
+                entry.file.components().any(|component| {
+                    production
+                        .iter()
+                        .any(|name| component.as_os_str() == name.as_str())
+                })
+            })
+            .map(|entry| format!("{} ({})", entry.name, entry.file.display()))
+            .collect();
+        if !unowned.is_empty() {
+            return Err(ConnectUtilError::Validation(format!(
+                "{} production connector(s) have no `# connect-util:owner` comment: {}",
+                unowned.len(),
+                unowned.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    /// The recommended creation order of the topics and connectors in each module
+    /// under `dir`, with the `depends_on` entries connectors are missing
+    pub fn apply_order(&self, dir: &str) -> Result<Vec<ModuleOrder>, ConnectUtilError> {
+        let root = Path::new(dir);
+        if !root.is_dir() {
+            return Err(ConnectUtilError::Config(format!(
+                "Directory not found: {}",
+                dir
+            )));
+        }
+        let mut files = Vec::new();
+        collect_terraform_files(root, &mut files)?;
+        files.sort();
+
+        let mut orders = Vec::new();
+        for (module_dir, module_files) in group_by_module(&files) {
+            let module = ModuleSources::load(&module_dir, &module_files)?;
+            let (mut connectors, mut topics, mut existing, mut declared) =
+                (Vec::new(), Vec::new(), BTreeMap::new(), BTreeMap::new());
+            for source in &module.files {
+                let Ok(body) = &source.body else {
+                    continue;
+                };
+                connectors.extend(
+                    self.connector_configs(body, &module.locals)
+                        .into_iter()
+                        .map(|config| ModuleConnector {
+                            file: source.path.clone(),
+                            config,
+                        }),
+                );
+                topics.extend(topic_resources(&source.path, body));
+                existing.extend(
+                    connector_data_sources(body)
+                        .iter()
+                        .map(|data_source| (data_source.address(), source.path.clone())),
+                );
+                declared.extend(declared_dependencies(body));
+            }
+            if !connectors.is_empty() {
+                orders.push(plan_order(
+                    &module_dir,
+                    &connectors,
+                    &topics,
+                    &existing,
+                    &declared,
+                ));
+            }
+        }
+        Ok(orders)
+    }
+
+    /// The connectors under `dir` that use the topic or set the secret key in `query`
+    pub fn find_usages(
+        &self,
+        dir: &str,
+        query: &UsageQuery,
+    ) -> Result<UsageReport, ConnectUtilError> {
+        let root = Path::new(dir);
+        if !root.is_dir() {
+            return Err(ConnectUtilError::Config(format!(
+                "Directory not found: {}",
+                dir
+            )));
+        }
+        let mut files = Vec::new();
+        collect_terraform_files(root, &mut files)?;
+        files.sort();
+
+        let (mut connectors, mut topics) = (Vec::new(), Vec::new());
+        for (module_dir, module_files) in group_by_module(&files) {
+            let module = ModuleSources::load(&module_dir, &module_files)?;
+            for source in &module.files {
+                let Ok(body) = &source.body else {
+                    continue;
+                };
+                connectors.extend(
+                    self.connector_configs(body, &module.locals)
+                        .into_iter()
+                        .map(|config| ModuleConnector {
+                            file: source.path.clone(),
+                            config,
+                        }),
+                );
+                topics.extend(topic_resources(&source.path, body));
+            }
+        }
+        Ok(find_usages(&connectors, &topics, query))
+    }
+
+    /// Prints every connector under `dir` that references the topic or secret key,
+    /// for impact analysis before a schema change or a credential rotation
+    pub async fn where_used(
+        &mut self,
+        dir: &str,
+        query: &UsageQuery,
+        format: UsageFormat,
+    ) -> Result<(), ConnectUtilError> {
+        let report = self.find_usages(dir, query)?;
+        println!("{}", render_usages(&report, format)?.trim_end());
+        Ok(())
+    }
+
+    /// Points every connector under `dir` that sets `key` at the expression `to`,
+    /// when given, and prints the checklist of rotations to do outside the files
+    pub async fn rotate_secret(
+        &mut self,
+        dir: &str,
+        key: &str,
+        to: Option<&str>,
+        dry_run: bool,
+    ) -> Result<(), ConnectUtilError> {
+        let query = UsageQuery {
+            topic: None,
+            secret_key: Some(key.to_string()),
+        };
+        let report = self.find_usages(dir, &query)?;
+        if report.secret_usages.is_empty() {
+            say!("✅ No connectors set {}", key);
+            return Ok(());
+        }
+        let usages = render_usages(&report, UsageFormat::Text)?;
+        say!("{}", usages.trim_end());
+
+        if let Some(to) = to {
+            let mut by_file: BTreeMap<&Path, Vec<_>> = BTreeMap::new();
+            for usage in &report.secret_usages {
+                by_file
+                    .entry(usage.file.as_path())
+                    .or_default()
+                    .push(usage.clone());
+            }
+            let mut changed_files = 0;
+            for (file, usages) in by_file {
+                let content = std::fs::read_to_string(file)?;
+                let (rewritten, edits) =
+                    rotate_references(&content, key, to, &usages).map_err(|e| {
+                        ConnectUtilError::Terraform(format!("{}: {}", file.display(), e))
+                    })?;
+                if edits.is_empty() {
+                    continue;
+                }
+                changed_files += 1;
+                say!("\n📄 {}", file.display());
+                for edit in &edits {
+                    say!("  - {}.{} = {}", edit.from, key, edit.before);
+                    say!("  + config_sensitive.{} = {}", key, to);
+                }
+                if !dry_run {
+                    std::fs::write(file, rewritten)?;
+                }
+            }
+            match (changed_files, dry_run) {
+                (0, _) => say!("\n✅ Every connector already uses {}", to),
+                (n, true) => say!("\n📋 Dry run: {} file(s) would be updated", n),
+                (n, false) => say!("\n✅ Pointed {} at {} in {} file(s)", key, to, n),
+            }
+        }
+
+        let steps = rotation_checklist(key, to, &report.secret_usages);
+        println!("\n{}", render_checklist(key, &steps).trim_end());
+        Ok(())
+    }
+
+    /// Prints the creation order for the connectors under `dir` and the `depends_on`
+    /// patches that would make Terraform follow it, failing on a dependency cycle
+    pub async fn order(&mut self, dir: &str, format: OrderFormat) -> Result<(), ConnectUtilError> {
+        let orders = self.apply_order(dir)?;
+        if orders.is_empty() {
+            say!("ℹ️  No connectors found in {}", dir);
+            return Ok(());
+        }
+        println!("{}", render_order(&orders, format)?.trim_end());
+
+        let cycles: Vec<String> = orders
+            .iter()
+            .filter(|order| !order.cycle.is_empty())
+            .map(|order| order.dir.display().to_string())
+            .collect();
+        if !cycles.is_empty() {
+            return Err(ConnectUtilError::Validation(format!(
+                "Connectors depend on each other in a cycle in {}",
+                cycles.join(", ")
+            )));
+        }
+        if format == OrderFormat::Text {
+            match orders
+                .iter()
+                .map(|order| order.patches.len())
+                .sum::<usize>()
+            {
+                0 => say!("✅ Terraform already creates every connector after what it needs"),
+                patches => say!(
+                    "💡 {} connector(s) need depends_on entries to keep this order",
+                    patches
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Prints connector checks in `tflint --format json` shape, or the ruleset itself,
+    /// and fails when any error-severity issue is found
+    pub async fn tflint(&mut self, dir: &str, rules: bool) -> Result<(), ConnectUtilError> {
+        if rules {
+            println!("{}", serde_json::to_string_pretty(&ruleset_json())?);
+            return Ok(());
+        }
+        let issues = self.lint_directory(dir)?;
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&to_tflint_json(&issues))?
+        );
+
+        let errors = issues
+            .iter()
+            .filter(|issue| issue.severity == "error")
+            .count();
+        if errors > 0 {
+            return Err(ConnectUtilError::Validation(format!(
+                "Found {} connector lint error(s) in {}",
+                errors, dir
+            )));
+        }
+        Ok(())
+    }
+
+    /// Generates every catalog connector, or just `connector`, with placeholders,
+    /// with topics, and with companion resources, then runs each result back through
+    /// the validator's structure and config checks. Returns one
+    /// `connector (case): error` line per round trip that fails
+    pub fn selftest_failures(
+        &self,
+        connector: Option<&str>,
+    ) -> Result<Vec<String>, ConnectUtilError> {
+        let connectors = match connector {
+            Some(name) => vec![ConnectorDefinition::get_connector_by_name(name)
+                .ok_or_else(|| ConnectUtilError::Config(format!("Unknown connector: {}", name)))?],
+            None => ConnectorDefinition::get_all_connectors(),
+        };
+
+        let mut failures = Vec::new();
+        for connector in connectors {
+            let base = TerraformConfigOptions {
+                connector_name: format!("selftest-{}", connector.name.to_lowercase()),
+                connector: connector.clone(),
+                ..Default::default()
+            };
+            let with_topics = match connector.topic_strategy {
+                TopicStrategy::TopicPrefix => TerraformConfigOptions {
+                    topic_prefix: Some("orders".to_string()),
+                    ..base.clone()
+                },
+                _ => TerraformConfigOptions {
+                    topics: vec!["orders".to_string()],
+                    ..base.clone()
+                },
+            };
+            let with_companions = TerraformConfigOptions {
+                companions: CompanionResources {
+                    topics: true,
+                    service_account: true,
+                    api_key: true,
+                },
+                ..with_topics.clone()
+            };
+
+            for (case, options) in [
+                ("placeholders", base),
+                ("topics", with_topics),
+                ("companions", with_companions),
+            ] {
+                if let Err(e) = self.round_trip(options) {
+                    failures.push(format!("{} ({}): {}", connector.name, case, e));
+                }
+            }
+        }
+        Ok(failures)
+    }
+
+    /// Generates one connector and checks the validator accepts it as that connector
+    fn round_trip(&self, options: TerraformConfigOptions) -> Result<(), ConnectUtilError> {
+        let connector = options.connector.clone();
+        let terraform = TerraformGenerator.generate_connector_config(options)?;
+        let body = parse_body(&terraform).map_err(|e| parse_error(&e))?;
+        for block in body.blocks() {
+            let labels = block.labels();
+            if block.identifier() == "resource"
+                && labels.len() >= 2
+                && labels[0].as_str() == "confluent_connector"
+            {
+                self.validate_resource_block(block.body(), labels[1].as_str())?;
+            }
+        }
+
+        let configs = self.parse_terraform_configs(&terraform)?;
+        let [config] = configs.as_slice() else {
+            return Err(ConnectUtilError::Validation(format!(
+                "expected 1 connector, parsed {}",
+                configs.len()
+            )));
+        };
+        let resolved = ConnectorDefinition::get_connector_by_name(&config.connector_class);
+        if resolved.as_ref().map(|def| def.name.as_str()) != Some(connector.name.as_str()) {
+            return Err(ConnectUtilError::Validation(format!(
+                "connector.class '{}' doesn't resolve back to {}",
+                config.connector_class, connector.name
+            )));
+        }
+        connector
+            .validate_config(&config.config, &config.sensitive_config)
+            .map_err(ConnectUtilError::Validation)
+    }
+
+    /// Runs `selftest_failures`, listing each failed round trip and failing when any did
+    pub async fn selftest(&mut self, connector: Option<&str>) -> Result<(), ConnectUtilError> {
+        let total = match connector {
+            Some(_) => 1,
+            None => ConnectorDefinition::get_all_connectors().len(),
+        };
+        let failures = self.selftest_failures(connector)?;
+        for failure in &failures {
+            say!("❌ {}", failure);
+        }
+
+        let failed: BTreeSet<&str> = failures
+            .iter()
+            .filter_map(|failure| failure.split(" (").next())
+            .collect();
+        if !failed.is_empty() {
+            return Err(ConnectUtilError::Validation(format!(
+                "{} of {} connector(s) failed the round trip",
+                failed.len(),
+                total
+            )));
+        }
+        say!("✅ {} connector(s) generated and passed validation", total);
+        Ok(())
+    }
+
+    /// Lints a single file (within its module) or every module under a directory,
+    /// returning the findings and every `(file, label)` connector checked
+    fn validation_findings(
+        &self,
+        config_file: Option<&str>,
+        dir: Option<&str>,
+    ) -> Result<ValidationFindings, ConnectUtilError> {
+        let (root, only) = match (dir, config_file) {
+            (Some(dir), _) => (PathBuf::from(dir), None),
+            (None, Some(config_file)) if stdio::is_stdio(config_file) => {
+                return self.stdin_findings(self.read_source(config_file)?);
+            }
+            (None, Some(config_file)) => {
+                let path = Path::new(config_file);
+                if !path.exists() {
+                    return Err(ConnectUtilError::Config(format!(
+                        "Configuration file not found: {}",
+                        config_file
+                    )));
+                }
+                let parent = path
+                    .parent()
+                    .filter(|p| !p.as_os_str().is_empty())
+                    .unwrap_or(Path::new("."));
+                (parent.to_path_buf(), Some(std::fs::canonicalize(path)?))
+            }
+            (None, None) => {
+                return Err(ConnectUtilError::UserInput(
+                    "Provide a configuration file or a directory to validate".to_string(),
+                ))
+            }
+        };
+
+        let root = root.to_string_lossy();
+        let (mut issues, mut timings) = self.lint_directory_timed(&root)?;
+        let mut entries = self.inventory(&root)?;
+        if let Some(only) = &only {
+            let is_target =
+                |file: &Path| std::fs::canonicalize(file).is_ok_and(|file| file == *only);
+            issues.retain(|issue| is_target(&issue.file));
+            entries.retain(|entry| is_target(&entry.file));
+            timings.retain(|timing| is_target(&timing.file));
+        }
+        Ok(ValidationFindings {
+            issues,
+            entries,
+            timings,
+        })
+    }
+
+    /// Findings for standard input, checked in place of its file in the module of
+    /// the directory it names; without `--stdin-filename`, the current directory
+    fn stdin_findings(&self, source: SourceFile) -> Result<ValidationFindings, ConnectUtilError> {
+        let dir = source
+            .path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(Path::new("."))
+            .to_path_buf();
+        let mut siblings = Vec::new();
+        if dir.is_dir() {
+            for entry in std::fs::read_dir(&dir)? {
+                let path = entry?.path();
+                let terraform = path
+                    .extension()
+                    .is_some_and(|ext| ext == "tf" || ext == "tfvars");
+                if terraform && path.is_file() && path.file_name() != source.path.file_name() {
+                    siblings.push(path);
+                }
+            }
+        }
+        siblings.sort();
+        let mut module = ModuleSources::load(&dir, &siblings)?;
+        if let Ok(body) = &source.body {
+            module.locals.extend(collect_locals(body));
+        }
+        let path = source.path.clone();
+        module.files.push(source);
+
+        let (issues, mut timings) = self.lint_module(&SecretScanner::new(), &module)?;
+        let mut issues = self.apply_suppressions(issues, &module.files)?;
+        let mut entries = self.inventory_module(&module);
+        issues.retain(|issue| issue.file == path);
+        entries.retain(|entry| entry.file == path);
+        timings.retain(|timing| timing.file == path);
+        Ok(ValidationFindings {
+            issues,
+            entries,
+            timings,
+        })
+    }
+
+    /// Prints the findings for a file, or for every module under a directory, as a
+    /// Markdown summary a CI bot can post on pull requests
+    pub async fn validate_markdown(
+        &mut self,
+        config_file: Option<&str>,
+        dir: Option<&str>,
+    ) -> Result<(), ConnectUtilError> {
+        let findings = self.validation_findings(config_file, dir)?;
+        println!(
+            "{}",
+            to_markdown(
+                "connect-util validation",
+                &findings.issues,
+                &findings.connectors()
+            )
+            .trim_end()
+        );
+        Ok(())
+    }
+
+    /// Fails when findings at `error` severity remain under the validation profile,
+    /// so the profile can gate a merge or a deploy; without a profile findings are
+    /// only reported
+    pub fn enforce_profile(
+        &self,
+        config_file: Option<&str>,
+        dir: Option<&str>,
+    ) -> Result<(), ConnectUtilError> {
+        let Some(profile) = self.profile else {
+            return Ok(());
+        };
+        let findings = self.validation_findings(config_file, dir)?;
+        let errors: Vec<&LintIssue> = findings
+            .issues
+            .iter()
+            .filter(|issue| issue.severity == "error")
+            .collect();
+        if errors.is_empty() {
+            return Ok(());
+        }
+        // Text output doesn't list every finding the profile counts, so name them
+        say!("\n❌ Findings failing the {} profile:", profile.name());
+        for issue in &errors {
+            say!(
+                "  {} {}:{}: {}",
+                issue.rule.name(),
+                issue.file.display(),
+                issue.line,
+                issue.message
+            );
+        }
+        Err(ConnectUtilError::Validation(format!(
+            "{} error finding(s) fail the {} profile",
+            errors.len(),
+            profile.name()
+        )))
+    }
+
+    /// Warns about files generated from an older definition of their connector than
+    /// the catalog's, for `validate --check-stale`. Returns how many were stale
+    pub fn check_stale(
+        &self,
+        config_file: Option<&str>,
+        dir: Option<&str>,
+    ) -> Result<usize, ConnectUtilError> {
+        let sources: Vec<(PathBuf, String)> = match (dir, config_file) {
+            (Some(dir), _) => {
+                let mut files = Vec::new();
+                collect_terraform_files(Path::new(dir), &mut files)?;
+                files.sort();
+                files
+                    .into_iter()
+                    .map(|file| Ok((file.clone(), std::fs::read_to_string(&file)?)))
+                    .collect::<Result<_, ConnectUtilError>>()?
+            }
+            (None, Some(config_file)) => {
+                let source = self.read_source(config_file)?;
+                vec![(source.path, source.content)]
+            }
+            (None, None) => return Ok(0),
+        };
+        let catalog = CatalogBundle::builtin();
+        let mut stale = 0;
+        for (path, content) in &sources {
+            let reasons: Vec<String> = stamps(content)
+                .iter()
+                .filter_map(|stamp| stamp.staleness(&catalog))
+                .collect();
+            if !reasons.is_empty() {
+                stale += 1;
+            }
+            for reason in reasons {
+                say!("⚠️  {} was {}", path.display(), reason);
+            }
+        }
+        if stale > 0 {
+            say!(
+                "🕰️  {} file(s) were generated from an older catalog than this one",
+                stale
+            );
+        }
+        Ok(stale)
+    }
+
+    /// Prints the findings with run statistics as JSON, for dashboards
+    pub async fn validate_json(
+        &mut self,
+        config_file: Option<&str>,
+        dir: Option<&str>,
+    ) -> Result<(), ConnectUtilError> {
+        let findings = self.validation_findings(config_file, dir)?;
+        let issues: Vec<serde_json::Value> = findings
+            .issues
+            .iter()
+            .map(|issue| {
+                serde_json::json!({
+                    "rule": issue.rule.name(),
+                    "severity": issue.severity,
+                    "file": issue.file.display().to_string(),
+                    "line": issue.line,
+                    "connector": issue.connector,
+                    "message": issue.message,
+                })
+            })
+            .collect();
+        let report = serde_json::json!({
+            "summary": findings.stats(),
+            "issues": issues,
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        Ok(())
+    }
+
+    /// Steps through the findings one at a time, letting `reviewer` apply the
+    /// suggested fix, suppress the finding with a comment, open the file at its
+    /// line, or skip it. Returns how many findings were left as they were
+    pub async fn validate_interactive(
+        &mut self,
+        reviewer: &mut dyn Reviewer,
+        config_file: Option<&str>,
+        dir: Option<&str>,
+    ) -> Result<usize, ConnectUtilError> {
+        let mut issues = self.validation_findings(config_file, dir)?.issues;
+        let total = issues.len();
+        if total == 0 {
+            say!("✅ No findings to review");
+            return Ok(0);
+        }
+
+        let mut declared_modules = Vec::new();
+        let mut remaining = 0;
+        for index in 0..total {
+            let issue = issues[index].clone();
+            let module_dir = issue.file.parent().unwrap_or(Path::new(".")).to_path_buf();
+            if issue.rule == LintRule::UndeclaredVariable && declared_modules.contains(&module_dir)
+            {
+                continue;
+            }
+            say!(
+                "\n[{}/{}] {} {}:{}",
+                index + 1,
+                total,
+                issue.severity,
+                issue.file.display(),
+                issue.line
+            );
+            say!("  {}: {}", issue.rule.name(), issue.message);
+            let fix = suggested_fix(&issue);
+            if let Some(fix) = &fix {
+                say!("  💡 Suggested fix: {}", fix.describe());
+            }
+
+            let handled = match reviewer.choose(&issue, &actions(&issue))? {
+                ReviewAction::Fix => match self.apply_fix(&issue, fix.as_ref()).await {
+                    Ok(()) => {
+                        if fix == Some(SuggestedFix::DeclareVariables) {
+                            declared_modules.push(module_dir);
+                        }
+                        true
+                    }
+                    Err(error) => {
+                        say!("⚠️  Couldn't apply the fix: {}", error);
+                        false
+                    }
+                },
+                ReviewAction::Suppress => {
+                    let reason = reviewer.reason(&issue)?;
+                    let content = std::fs::read_to_string(&issue.file)?;
+                    std::fs::write(
+                        &issue.file,
+                        insert_suppression(&content, issue.line, issue.rule, &reason),
+                    )?;
+                    // Later findings in the file moved down a line
+                    for later in issues.iter_mut().skip(index + 1) {
+                        if later.file == issue.file && later.line >= issue.line {
+                            later.line += 1;
+                        }
+                    }
+                    say!("🔇 Suppressed at {}:{}", issue.file.display(), issue.line);
+                    true
+                }
+                ReviewAction::Open => {
+                    let editor = std::env::var("VISUAL")
+                        .or_else(|_| std::env::var("EDITOR"))
+                        .unwrap_or_default();
+                    let (program, args) = editor_command(&editor, &issue.file, issue.line);
+                    let status = std::process::Command::new(&program).args(&args).status();
+                    match status {
+                        Ok(status) if status.success() => true,
+                        Ok(status) => {
+                            say!("⚠️  {} exited with {}", program, status);
+                            false
+                        }
+                        Err(error) => {
+                            say!("⚠️  Couldn't run {}: {}", program, error);
+                            false
+                        }
+                    }
+                }
+                ReviewAction::Skip => false,
+                ReviewAction::Quit => {
+                    remaining += total - index;
+                    break;
+                }
+            };
+            if !handled {
+                remaining += 1;
+            }
+        }
+
+        say!(
+            "\n📋 Reviewed {} finding(s); {} left as they were",
+            total,
+            remaining
+        );
+        Ok(remaining)
+    }
+
+    /// Applies a finding's suggested fix in place
+    async fn apply_fix(
+        &mut self,
+        issue: &LintIssue,
+        fix: Option<&SuggestedFix>,
+    ) -> Result<(), ConnectUtilError> {
+        let module_dir = issue.file.parent().unwrap_or(Path::new("."));
+        let module_dir = module_dir.to_string_lossy();
+        match fix {
+            Some(SuggestedFix::RenameResource { old, new }) => {
+                self.rename_connector_resource(&module_dir, old, new, false)
+                    .await
+            }
+            Some(SuggestedFix::RenameConnector { old, new }) => {
+                let content = std::fs::read_to_string(&issue.file)?;
+                let renamed =
+                    rename_connector_name(&content, issue.line, old, new).ok_or_else(|| {
+                        ConnectUtilError::Validation(format!(
+                            "No \"name\" = \"{}\" setting found in {}",
+                            old,
+                            issue.file.display()
+                        ))
+                    })?;
+                std::fs::write(&issue.file, renamed)?;
+                say!("✅ Renamed connector '{}' to '{}'", old, new);
+                Ok(())
+            }
+            Some(SuggestedFix::DeclareVariables) => self.fix_variables(&module_dir, false).await,
+            None => Err(ConnectUtilError::UserInput(
+                "This finding has no suggested fix".to_string(),
+            )),
+        }
+    }
+
+    /// Sends a pass/fail summary of the same findings `validate --format markdown`
+    /// reports, so scheduled runs can alert a channel
+    pub async fn notify_validation(
+        &self,
+        notifier: &dyn Notifier,
+        format: NotifyFormat,
+        config_file: Option<&str>,
+        dir: Option<&str>,
+        failed_check: Option<String>,
+    ) -> Result<ValidationSummary, ConnectUtilError> {
+        let findings = self.validation_findings(config_file, dir)?;
+        let target = dir.or(config_file).unwrap_or(".");
+        let mut summary =
+            ValidationSummary::from_issues(target, &findings.issues, &findings.connectors());
+        summary.failed_check = failed_check;
+        notifier.send(&summary.payload(format)).await?;
+        say!("📣 Sent validation summary: {}", summary.headline());
+        Ok(summary)
+    }
+
+    /// Appends `variable` blocks to each module's `variables.tf` for every variable
+    /// its connectors reference without declaring
+    pub async fn fix_variables(
+        &mut self,
+        dir: &str,
+        dry_run: bool,
+    ) -> Result<(), ConnectUtilError> {
+        let root = Path::new(dir);
+        if !root.is_dir() {
+            return Err(ConnectUtilError::Config(format!(
+                "Directory not found: {}",
+                dir
+            )));
+        }
+        let mut files = Vec::new();
+        collect_terraform_files(root, &mut files)?;
+        files.sort();
+
+        let generator = TerraformGenerator;
+        let mut added = 0;
+        for (module_dir, module_files) in group_by_module(&files) {
+            let sources = module_files
+                .iter()
+                .map(|file| Ok((file.clone(), std::fs::read_to_string(file)?)))
+                .collect::<Result<Vec<_>, ConnectUtilError>>()?;
+            let missing = ModuleVariables::analyze(&sources)?.missing_declarations();
+            if missing.is_empty() {
+                continue;
+            }
+            added += missing.len();
+
+            let generated = generator.generate_missing_variables(&missing)?;
+            let target = module_dir.join("variables.tf");
+            let names: Vec<&str> = missing.iter().map(|m| m.name.as_str()).collect();
+            if dry_run {
+                say!("📄 Would add to {}: {}", target.display(), names.join(", "));
+                println!("{}", generated.trim_end());
+                continue;
+            }
+            let content = if target.exists() {
+                TerraformGenerator::append_to_existing(
+                    &std::fs::read_to_string(&target)?,
+                    &generated,
+                )?
+            } else {
+                generated
+            };
+            std::fs::write(&target, content)?;
+            say!(
+                "✅ Added {} variable declaration(s) to {}: {}",
+                missing.len(),
+                target.display(),
+                names.join(", ")
+            );
+        }
+
+        if added == 0 {
+            say!("✅ Every referenced variable is already declared");
+        }
+        Ok(())
+    }
+
+    /// Renames a topic prefix in `topic.prefix`, `topics`, and related SMT settings
+    /// across every `.tf` and `.tfvars` file, printing each changed line before writing
+    pub async fn rename_topic_prefix(
+        &mut self,
+        dir: &str,
+        old_prefix: &str,
+        new_prefix: &str,
+        dry_run: bool,
+    ) -> Result<(), ConnectUtilError> {
+        if old_prefix.is_empty() || new_prefix.is_empty() {
+            return Err(ConnectUtilError::UserInput(
+                "Topic prefixes must not be empty".to_string(),
+            ));
+        }
+        if old_prefix == new_prefix {
+            return Err(ConnectUtilError::UserInput(format!(
+                "Old and new prefix are both '{}'",
+                old_prefix
+            )));
+        }
+        let root = Path::new(dir);
+        if !root.is_dir() {
+            return Err(ConnectUtilError::Config(format!(
+                "Directory not found: {}",
+                dir
+            )));
+        }
+        let mut files = Vec::new();
+        collect_terraform_files(root, &mut files)?;
+        files.sort();
+
+        let mut changed_files = 0;
+        for file in files {
+            let content = std::fs::read_to_string(&file)?;
+            let (rewritten, changes) = rename_prefix(&content, old_prefix, new_prefix);
+            if changes.is_empty() {
+                continue;
+            }
+            changed_files += 1;
+            say!("\n📄 {}", file.display());
+            for change in &changes {
+                say!("  @@ line {} @@", change.line);
+                say!("  - {}", change.before.trim());
+                say!("  + {}", change.after.trim());
+            }
+            if !dry_run {
+                std::fs::write(&file, rewritten)?;
+            }
+        }
+
+        match (changed_files, dry_run) {
+            (0, _) => say!("✅ No topic settings use the prefix '{}'", old_prefix),
+            (n, true) => say!("\n📋 Dry run: {} file(s) would be updated", n),
+            (n, false) => say!(
+                "\n✅ Renamed '{}' to '{}' in {} file(s)",
+                old_prefix,
+                new_prefix,
+                n
+            ),
+        }
+        Ok(())
+    }
+
+    /// Relabels a `confluent_connector` resource and its references within the
+    /// module that declares it, adding a `moved` block after the declaration
+    pub async fn rename_connector_resource(
+        &mut self,
+        dir: &str,
+        old_label: &str,
+        new_label: &str,
+        dry_run: bool,
+    ) -> Result<(), ConnectUtilError> {
+        if old_label == new_label {
+            return Err(ConnectUtilError::UserInput(format!(
+                "Old and new resource names are both '{}'",
+                old_label
+            )));
+        }
+        for label in [old_label, new_label] {
+            hcl::Identifier::new(label).map_err(|_| {
+                ConnectUtilError::UserInput(format!("'{}' is not a valid resource name", label))
+            })?;
+        }
+        let root = Path::new(dir);
+        if !root.is_dir() {
+            return Err(ConnectUtilError::Config(format!(
+                "Directory not found: {}",
+                dir
+            )));
+        }
+        let mut files = Vec::new();
+        collect_terraform_files(root, &mut files)?;
+        files.retain(|file| file.extension().is_some_and(|ext| ext == "tf"));
+        files.sort();
+
+        let declares = |file: &Path, label: &str| -> Result<bool, ConnectUtilError> {
+            let body = parse_body(&std::fs::read_to_string(file)?)
+                .map_err(|e| ConnectUtilError::Terraform(format!("{}: {}", file.display(), e)))?;
+            Ok(TerraformGenerator::resource_addresses(&body)
+                .contains(&format!("confluent_connector.{}", label)))
+        };
+        let mut declaring = Vec::new();
+        for file in &files {
+            if declares(file, old_label)? {
+                declaring.push(file.clone());
+            }
+        }
+        let declared_in = match declaring.as_slice() {
+            [] => {
+                return Err(ConnectUtilError::Config(format!(
+                    "No confluent_connector.{} resource found under {}",
+                    old_label, dir
+                )))
+            }
+            [file] => file.clone(),
+            _ => {
+                let paths: Vec<String> =
+                    declaring.iter().map(|f| f.display().to_string()).collect();
+                return Err(ConnectUtilError::Config(format!(
+                    "confluent_connector.{} is declared in several modules ({}); pass the module's directory with --dir",
+                    old_label,
+                    paths.join(", ")
+                )));
+            }
+        };
+
+        // Resource addresses are scoped to a module, which is a single directory
+        let module_dir = declared_in.parent().unwrap_or(root).to_path_buf();
+        let module_files: Vec<PathBuf> = files
+            .into_iter()
+            .filter(|file| file.parent() == Some(module_dir.as_path()))
+            .collect();
+        for file in &module_files {
+            if declares(file, new_label)? {
+                return Err(ConnectUtilError::Validation(format!(
+                    "confluent_connector.{} already exists in {}",
+                    new_label,
+                    file.display()
+                )));
+            }
+        }
+
+        let moved = TerraformGenerator.generate_moved_config(old_label, new_label)?;
+        let mut changed_files = 0;
+        for file in &module_files {
+            let content = std::fs::read_to_string(file)?;
+            let (mut rewritten, changes) = rename_resource(&content, old_label, new_label)?;
+            if changes.is_empty() {
+                continue;
+            }
+            changed_files += 1;
+            say!("\n📄 {}", file.display());
+            for change in &changes {
+                say!("  @@ line {} @@", change.line);
+                say!("  - {}", change.before.trim());
+                say!("  + {}", change.after.trim());
+            }
+            if *file == declared_in {
+                say!("  @@ end of file @@");
+                for line in moved.lines() {
+                    say!("  + {}", line);
+                }
+                if !rewritten.ends_with('\n') {
+                    rewritten.push('\n');
+                }
+                rewritten.push('\n');
+                rewritten.push_str(&moved);
+                if let Some(newline) = Newline::detect(&content) {
+                    rewritten = newline.apply(&rewritten);
+                }
+            }
+            if !dry_run {
+                std::fs::write(file, rewritten)?;
+            }
+        }
+
+        if dry_run {
+            say!("\n📋 Dry run: {} file(s) would be updated", changed_files);
+        } else {
+            say!(
+                "\n✅ Moved confluent_connector.{} to confluent_connector.{} in {} file(s)",
+                old_label,
+                new_label,
+                changed_files
+            );
+        }
+        Ok(())
+    }
+
+    /// Combines Terraform files into `output`, keeping their text and references as
+    /// they are. Warns about variables the output's module no longer declares
+    pub async fn merge_terraform_files(
+        &mut self,
+        files: &[String],
+        output: &str,
+        force: bool,
+        dry_run: bool,
+    ) -> Result<(), ConnectUtilError> {
+        if files.len() < 2 {
+            return Err(ConnectUtilError::UserInput(
+                "Merging needs at least two files".to_string(),
+            ));
+        }
+        let sources = files
+            .iter()
+            .map(|file| {
+                if !Path::new(file).is_file() {
+                    return Err(ConnectUtilError::Config(format!(
+                        "File not found: {}",
+                        file
+                    )));
+                }
+                Ok((PathBuf::from(file), std::fs::read_to_string(file)?))
+            })
+            .collect::<Result<Vec<_>, ConnectUtilError>>()?;
+        let mut content = merge_files(&sources)?;
+        if let Some(newline) = Newline::detect(&sources[0].1) {
+            content = newline.apply(&content);
+        }
+        let connectors = parse_body(&content)
+            .map(|body| {
+                TerraformGenerator::resource_addresses(&body)
+                    .iter()
+                    .filter(|address| address.starts_with("confluent_connector."))
+                    .count()
+            })
+            .unwrap_or(0);
+
+        if stdio::is_stdio(output) {
+            println!("{}", content.trim_end());
+            return Ok(());
+        }
+        let output_path = Path::new(output);
+        let is_input = sources.iter().any(|(path, _)| path == output_path);
+        if output_path.exists() && !is_input && !force && !dry_run {
+            return Err(ConnectUtilError::Config(format!(
+                "Output file already exists: {} (use --force to overwrite)",
+                output
+            )));
+        }
+
+        // The merged file stands in for the inputs in its module
+        let module_of = |path: &Path| -> PathBuf {
+            path.parent()
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .unwrap_or(Path::new("."))
+                .to_path_buf()
+        };
+        let module_dir = module_of(output_path);
+        let replaced = |path: &Path| {
+            std::iter::once(output_path)
+                .chain(sources.iter().map(|(source, _)| source.as_path()))
+                .any(|other| {
+                    other.file_name() == path.file_name() && module_of(other) == module_dir
+                })
+        };
+        let mut module = Vec::new();
+        if module_dir.is_dir() {
+            for entry in std::fs::read_dir(&module_dir)? {
+                let path = entry?.path();
+                if path.extension().is_some_and(|ext| ext == "tf") && !replaced(&path) {
+                    module.push((path.clone(), std::fs::read_to_string(&path)?));
+                }
+            }
+        }
+        module.push((output_path.to_path_buf(), content.clone()));
+        for missing in ModuleVariables::analyze(&module)?.missing_declarations() {
+            say!(
+                "⚠️  var.{} is referenced but not declared in {}; move its declaration or run `connect-util fix-variables --dir {}`",
+                missing.name,
+                module_dir.display(),
+                module_dir.display()
+            );
+        }
+
+        if dry_run {
+            say!("📄 Merged Terraform Configuration (dry run):");
+            println!("{}", content.trim_end());
+            say!("📝 {}", WritePlan::new(Some(output), &content));
+            return Ok(());
+        }
+        std::fs::write(output_path, &content)?;
+        say!(
+            "✅ Merged {} file(s) with {} connector(s) into {}",
+            sources.len(),
+            connectors,
+            output
+        );
+        let leftovers: Vec<String> = sources
+            .iter()
+            .filter(|(path, _)| path != output_path && module_of(path) == module_dir)
+            .map(|(path, _)| path.display().to_string())
+            .collect();
+        if !leftovers.is_empty() {
+            say!(
+                "ℹ️  Delete {} before planning: the module now declares their blocks twice",
+                leftovers.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    /// Moves each connector in `file` to `<label>.tf` next to it, along with the
+    /// outputs, imports, and moves that refer only to it. Everything else stays in
+    /// `file`, which is removed if nothing is left
+    pub async fn split_terraform_file(
+        &mut self,
+        file: &str,
+        force: bool,
+        dry_run: bool,
+    ) -> Result<(), ConnectUtilError> {
+        let path = Path::new(file);
+        if !path.is_file() {
+            return Err(ConnectUtilError::Config(format!(
+                "File not found: {}",
+                file
+            )));
+        }
+        let original = std::fs::read_to_string(path)?;
+        let split = split_connectors(&original)?;
+        if split.connectors.is_empty() {
+            say!("✅ {} declares no connectors; nothing to split", file);
+            return Ok(());
+        }
+        let newline = Newline::detect(&original);
+        let with_newlines = |content: &str| match newline {
+            Some(newline) => newline.apply(content),
+            None => content.to_string(),
+        };
+        let dir = path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        let targets: Vec<(PathBuf, String)> = split
+            .connectors
+            .iter()
+            .map(|connector| {
+                (
+                    dir.join(format!("{}.tf", connector.label)),
+                    with_newlines(&connector.content),
+                )
+            })
+            .collect();
+        for (target, _) in &targets {
+            let is_source = target.file_name() == path.file_name();
+            if is_source && split.rest.is_some() {
+                return Err(ConnectUtilError::Config(format!(
+                    "{} would hold both a connector and the shared blocks; rename it first",
+                    file
+                )));
+            }
+            if target.exists() && !is_source && !force && !dry_run {
+                return Err(ConnectUtilError::Config(format!(
+                    "Output file already exists: {} (use --force to overwrite)",
+                    target.display()
+                )));
+            }
+        }
+
+        for (target, content) in &targets {
+            if dry_run {
+                say!("📄 {} (dry run):", target.display());
+                println!("{}", content.trim_end());
+                say!(
+                    "📝 {}",
+                    WritePlan::new(Some(&target.to_string_lossy()), content)
+                );
+            } else {
+                std::fs::write(target, content)?;
+                say!("✅ Wrote {}", target.display());
+            }
+        }
+        let kept_source = targets
+            .iter()
+            .any(|(target, _)| target.file_name() == path.file_name());
+        match (&split.rest, dry_run) {
+            (Some(rest), true) => {
+                say!("📄 {} (dry run):", file);
+                println!("{}", rest.trim_end());
+            }
+            (Some(rest), false) => {
+                std::fs::write(path, with_newlines(rest))?;
+                say!("✅ Kept the shared blocks in {}", file);
+            }
+            (None, _) if kept_source => {}
+            (None, true) => say!("🗑️  Would remove {}, which has nothing left", file),
+            (None, false) => {
+                std::fs::remove_file(path)?;
+                say!("🗑️  Removed {}, which had nothing left", file);
+            }
+        }
+        say!(
+            "\n📋 {} connector(s) {} into their own files",
+            targets.len(),
+            if dry_run { "would be split" } else { "split" }
+        );
+        Ok(())
+    }
+
+    /// Parses Terraform content and extracts all connector configurations
+    /// Uses hcl-rs to properly parse HCL structure
+    pub fn parse_terraform_configs(&self, terraform_content: &str) -> TerraformParseResults {
+        self.parse_terraform_configs_with_locals(terraform_content, &Locals::new())
+    }
+
+    /// Like `parse_terraform_configs`, resolving `local.*` references and `merge()`
+    /// calls against the given module locals plus any `locals` blocks in the content
+    fn parse_terraform_configs_with_locals(
+        &self,
+        terraform_content: &str,
+        module_locals: &Locals,
+    ) -> TerraformParseResults {
+        let body = parse_body(terraform_content).map_err(|e| parse_error(&e))?;
+        let mut locals = module_locals.clone();
+        locals.extend(collect_locals(&body));
+        Ok(self.connector_configs(&body, &locals))
+    }
+
+    /// Extracts the connector configurations of a parsed file, given its module's
+    /// locals including the file's own
+    fn connector_configs(&self, body: &Body, locals: &Locals) -> Vec<ConnectorConfig> {
+        let mut connector_configs = Vec::new();
+
+        // Find all resource blocks with type "confluent_connector"
+        for block in body.blocks() {
+            let labels = block.labels();
+            let connector_name = match block.identifier() {
+                "resource" if labels.len() >= 2 && labels[0].as_str() == "confluent_connector" => {
+                    labels[1].as_str().to_string()
+                }
+                // Handle legacy module blocks - extract config from module body
+                "module" => labels
+                    .first()
+                    .map(|label| label.as_str().to_string())
+                    .unwrap_or_default(),
+                _ => continue,
+            };
+
+            // Expand for_each/count into one config per instance when the collection is static
+            match instances(block.body(), locals) {
+                Instances::Static(instances) => {
+                    for instance in instances {
+                        connector_configs.extend(self.connector_config_from_block(
+                            instance.address(&connector_name),
+                            &instance.apply(block.body()),
+                            locals,
+                        ));
+                    }
+                }
+                Instances::Single | Instances::Dynamic { .. } => {
+                    connector_configs.extend(self.connector_config_from_block(
+                        connector_name,
+                        block.body(),
+                        locals,
+                    ));
+                }
+            }
+        }
+
+        connector_configs
+    }
+
+    /// Builds a config from a connector block body; `None` without a connector class
+    fn connector_config_from_block(
+        &self,
+        name: String,
+        body: &Body,
+        locals: &Locals,
+    ) -> Option<ConnectorConfig> {
+        let mut connector_class = String::new();
+        let mut config_nonsensitive = HashMap::new();
+        let mut config_sensitive = HashMap::new();
+
+        // Extract attributes from the block body
+        self.extract_config_from_block(
+            body,
+            locals,
+            &mut connector_class,
+            &mut config_nonsensitive,
+            &mut config_sensitive,
+        );
+
+        // If we found a connector class, add it to our list
+        (!connector_class.is_empty()).then_some(ConnectorConfig {
+            name,
+            connector_class,
+            config: config_nonsensitive,
+            sensitive_config: config_sensitive,
+        })
+    }
+
+    /// Notes on connector blocks using `for_each` or `count`, given the module's
+    /// locals including the file's own
+    fn meta_argument_notes(&self, body: &Body, locals: &Locals) -> Vec<String> {
+        let mut notes = Vec::new();
+        for block in body.blocks() {
+            let labels = block.labels();
+            let label = match block.identifier() {
+                "resource" if labels.len() >= 2 && labels[0].as_str() == "confluent_connector" => {
+                    labels[1].as_str()
+                }
+                "module" if !labels.is_empty() && is_connector_module(block.body()) => {
+                    labels[0].as_str()
+                }
+                _ => continue,
+            };
+            match instances(block.body(), locals) {
+                Instances::Static(instances) => notes.push(format!(
+                    "'{}' expands to {} instance(s); validating each",
+                    label,
+                    instances.len()
+                )),
+                Instances::Dynamic { meta } => notes.push(format!(
+                    "'{}' has a dynamic number of instances ({}); validating the template only",
+                    label, meta
+                )),
+                Instances::Single => {}
+            }
+        }
+        notes
+    }
+
+    fn extract_config_from_block(
+        &self,
+        body: &Body,
+        locals: &Locals,
+        connector_class: &mut String,
+        config_nonsensitive: &mut HashMap<String, String>,
+        config_sensitive: &mut HashMap<String, String>,
+    ) {
+        // Extract config_nonsensitive from body attributes
+        for attr in body.attributes() {
+            let key = attr.key();
+            if key == "config_nonsensitive" {
+                if let Some(map) = self.extract_map_from_expression(&resolve(attr.expr(), locals)) {
+                    for (key, value) in map {
+                        if key == "connector.class" {
+                            *connector_class = value.clone();
+                        }
+                        config_nonsensitive.insert(key, value);
+                    }
+                }
+            } else if key == "config_sensitive" {
+                if let Some(map) = self.extract_map_from_expression(&resolve(attr.expr(), locals)) {
+                    for (key, value) in map {
+                        config_sensitive.insert(key, value);
+                    }
+                }
+            }
+        }
+    }
+
+    fn extract_map_from_expression(&self, expr: &Expression) -> Option<HashMap<String, String>> {
+        match expr {
+            Expression::Object(map) => {
+                let mut result = HashMap::new();
+                for (key, value) in map.iter() {
+                    if let Some(str_value) = self.extract_string_from_expression(value) {
+                        result.insert(key.to_string(), str_value);
+                    }
+                }
+                Some(result)
+            }
+            _ => None,
+        }
+    }
+
+    /// Renders a config value as a string. Literals come back as-is; any other
+    /// expression (references, conditionals, for-expressions, operations, function
+    /// calls) comes back as `${...}` so validation can tell it's dynamic. `null`
+    /// entries are left out since Terraform drops them from the map
+    #[allow(clippy::only_used_in_recursion)] // This is a recursive function
+    fn extract_string_from_expression(&self, expr: &Expression) -> Option<String> {
+        let dynamic = |expr: &Expression| {
+            let rendered = hcl::format::to_string(expr).unwrap_or_default();
+            Some(format!("${{{}}}", rendered.trim()))
+        };
+        match expr {
+            Expression::String(s) => Some(s.to_string()),
+            Expression::TemplateExpr(template) => Some(template_text(template)),
+            Expression::FuncCall(func) => {
+                // join(",", [...]) over literals flattens to the joined string
+                if func.name.as_str() == "join" {
+                    if let [Expression::String(separator), Expression::Array(arr)] =
+                        func.args.as_slice()
+                    {
+                        let values: Option<Vec<String>> = arr
+                            .iter()
+                            .map(|e| self.extract_string_from_expression(e))
+                            .collect();
+                        if let Some(values) =
+                            values.filter(|v| !v.iter().any(|v| is_dynamic_value(v)))
+                        {
+                            return Some(values.join(separator));
+                        }
+                    }
+                }
+                dynamic(expr)
+            }
+            Expression::Array(arr) => {
+                let values: Vec<String> = arr
+                    .iter()
+                    .filter_map(|e| self.extract_string_from_expression(e))
+                    .collect();
+                Some(format!("[{}]", values.join(", ")))
+            }
+            Expression::Number(n) => Some(n.to_string()),
+            Expression::Bool(b) => Some(b.to_string()),
+            Expression::Parenthesis(inner) => self.extract_string_from_expression(inner),
+            Expression::Null => None,
+            _ => dynamic(expr),
+        }
+    }
+
+    fn validate_terraform_structure(&self, body: &Body) -> Result<(), ConnectUtilError> {
+        say!("🔍 Validating Terraform structure...");
+
+        // Validate each confluent_connector resource block and module block individually
+        let mut connector_count = 0;
+        let mut module_count = 0;
+        for block in body.blocks() {
+            if block.identifier() == "resource" {
+                let labels = block.labels();
+                if labels.len() >= 2 && labels[0].as_str() == "confluent_connector" {
+                    connector_count += 1;
+                    let resource_name = labels[1].as_str();
+                    self.validate_resource_block(block.body(), resource_name)?;
+                }
+            } else if block.identifier() == "module" && is_connector_module(block.body()) {
+                module_count += 1;
+                let labels = block.labels();
+                let module_name = if !labels.is_empty() {
+                    labels[0].as_str()
+                } else {
+                    "unknown"
+                };
+                self.validate_module_block(block.body(), module_name)?;
+            }
+        }
+
+        let total_count = connector_count + module_count;
+        if total_count == 0 {
+            return Err(ConnectUtilError::Config(
+                "❌ No connector configurations found in file (no 'confluent_connector' resources or connector modules)".to_string(),
+            ));
+        }
+
+        if connector_count > 0 && module_count > 0 {
+            say!(
+                "  ✅ Validated {} connector resource(s) and {} module(s)",
+                connector_count,
+                module_count
+            );
+        } else if connector_count > 0 {
+            say!("  ✅ Validated {} connector resource(s)", connector_count);
+        } else {
+            say!("  ✅ Validated {} connector module(s)", module_count);
+        }
+        say!("✅ Terraform structure validation passed!");
+        Ok(())
+    }
+
+    /// Validates a single resource block structure
+    /// Ensures all required fields and nested blocks are present and correctly formatted
+    fn validate_resource_block(
+        &self,
+        body: &Body,
+        resource_name: &str,
+    ) -> Result<(), ConnectUtilError> {
+        // Check for status field
+        let mut has_status = false;
+        for attr in body.attributes() {
+            if attr.key() == "status" {
+                has_status = true;
+                break;
+            }
+        }
+        if !has_status {
+            return Err(ConnectUtilError::Config(format!(
+                "❌ Resource '{}' missing 'status' field",
+                resource_name
+            )));
+        }
+
+        // Terraform can't generate meta-argument blocks, so a dynamic lifecycle never works
+        if body.blocks().any(|block| {
+            block.identifier() == "dynamic"
+                && block.labels().first().map(|l| l.as_str()) == Some("lifecycle")
+        }) {
+            return Err(ConnectUtilError::Config(format!(
+                "❌ Resource '{}' generates 'lifecycle' with a dynamic block; lifecycle must be written literally",
+                resource_name
+            )));
+        }
+
+        // Check for environment block with correct structure
+        let environment = Self::nested_block(body, "environment");
+        let Some((environment_body, dynamic)) = environment else {
+            return Err(ConnectUtilError::Config(format!(
+                "❌ Resource '{}' missing 'environment {{ id = ... }}' block",
+                resource_name
+            )));
+        };
+        if dynamic {
+            say!(
+                "  ℹ️  Resource '{}' sets 'environment' with a dynamic block; checking its content",
+                resource_name
+            );
+        }
+        let environment_attrs: Vec<&str> = environment_body
+            .attributes()
+            .map(|attr| attr.key())
+            .collect();
+        if !environment_attrs.contains(&"id") {
+            return Err(ConnectUtilError::Config(format!(
+                "❌ Resource '{}' environment block must have 'id' attribute (found: {})",
+                resource_name,
+                if environment_attrs.is_empty() {
+                    "none".to_string()
+                } else {
+                    environment_attrs.join(", ")
+                }
+            )));
+        }
+
+        // Check for kafka_cluster block with correct structure
+        let Some((kafka_cluster_body, dynamic)) = Self::nested_block(body, "kafka_cluster") else {
+            return Err(ConnectUtilError::Config(format!(
+                "❌ Resource '{}' missing 'kafka_cluster {{ id = ... }}' block",
+                resource_name
+            )));
+        };
+        if dynamic {
+            say!(
+                "  ℹ️  Resource '{}' sets 'kafka_cluster' with a dynamic block; checking its content",
+                resource_name
+            );
+        }
+        if !kafka_cluster_body
+            .attributes()
+            .any(|attr| attr.key() == "id")
+        {
+            return Err(ConnectUtilError::Config(format!(
+                "❌ Resource '{}' kafka_cluster block must have 'id' attribute",
+                resource_name
+            )));
+        }
+
+        // Check for config_sensitive attribute
+        let mut has_config_sensitive = false;
+        for attr in body.attributes() {
+            if attr.key() == "config_sensitive" {
+                has_config_sensitive = true;
+                break;
+            }
+        }
+        if !has_config_sensitive {
+            return Err(ConnectUtilError::Config(format!(
+                "❌ Resource '{}' missing 'config_sensitive' attribute",
+                resource_name
+            )));
+        }
+
+        // Check for config_nonsensitive attribute
+        let mut has_config_nonsensitive = false;
+        for attr in body.attributes() {
+            if attr.key() == "config_nonsensitive" {
+                has_config_nonsensitive = true;
+                break;
+            }
+        }
+        if !has_config_nonsensitive {
+            return Err(ConnectUtilError::Config(format!(
+                "❌ Resource '{}' missing 'config_nonsensitive' attribute",
+                resource_name
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Finds a nested block written literally or as `dynamic "<name>" { content { ... } }`,
+    /// returning the body Terraform will render and whether it came from a dynamic block
+    fn nested_block<'a>(body: &'a Body, name: &str) -> Option<(&'a Body, bool)> {
+        if let Some(block) = body.blocks().find(|block| block.identifier() == name) {
+            return Some((block.body(), false));
+        }
+        body.blocks()
+            .filter(|block| block.identifier() == "dynamic")
+            .find(|block| block.labels().first().map(|l| l.as_str()) == Some(name))
+            .map(|block| {
+                let content = block
+                    .body()
+                    .blocks()
+                    .find(|nested| nested.identifier() == "content")
+                    .map(|content| content.body())
+                    .unwrap_or(block.body());
+                (content, true)
+            })
+    }
+
+    /// Validates a single module block structure
+    /// Modules use attributes instead of blocks for environment and kafka_cluster
+    fn validate_module_block(
+        &self,
+        body: &Body,
+        module_name: &str,
+    ) -> Result<(), ConnectUtilError> {
+        // Check for status field
+        let mut has_status = false;
+        for attr in body.attributes() {
+            if attr.key() == "status" {
+                has_status = true;
+                break;
+            }
+        }
+        if !has_status {
+            return Err(ConnectUtilError::Config(format!(
+                "❌ Module '{}' missing 'status' field",
+                module_name
+            )));
+        }
+
+        // Check for environment or environment_id attribute (modules use attributes, not blocks)
+        let mut has_environment = false;
+        for attr in body.attributes() {
+            if attr.key() == "environment" || attr.key() == "environment_id" {
+                has_environment = true;
+                break;
+            }
+        }
+        if !has_environment {
+            return Err(ConnectUtilError::Config(format!(
+                "❌ Module '{}' missing 'environment' or 'environment_id' attribute",
+                module_name
+            )));
+        }
+
+        // Check for kafka_cluster attribute (modules use attributes, not blocks)
+        let mut has_kafka_cluster = false;
+        for attr in body.attributes() {
+            if attr.key() == "kafka_cluster" {
+                has_kafka_cluster = true;
+                break;
+            }
+        }
+        if !has_kafka_cluster {
+            return Err(ConnectUtilError::Config(format!(
+                "❌ Module '{}' missing 'kafka_cluster' attribute",
+                module_name
+            )));
+        }
+
+        // Check for config_sensitive attribute
+        let mut has_config_sensitive = false;
+        for attr in body.attributes() {
+            if attr.key() == "config_sensitive" {
+                has_config_sensitive = true;
+                break;
+            }
+        }
+        if !has_config_sensitive {
+            return Err(ConnectUtilError::Config(format!(
+                "❌ Module '{}' missing 'config_sensitive' attribute",
+                module_name
+            )));
+        }
+
+        // Check for config_nonsensitive attribute
+        let mut has_config_nonsensitive = false;
+        for attr in body.attributes() {
+            if attr.key() == "config_nonsensitive" {
+                has_config_nonsensitive = true;
+                break;
+            }
+        }
+        if !has_config_nonsensitive {
+            return Err(ConnectUtilError::Config(format!(
+                "❌ Module '{}' missing 'config_nonsensitive' attribute",
+                module_name
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Prints a connector's details with its configuration fields organized by group
+    pub async fn describe_connector(&mut self, name: &str) -> Result<(), ConnectUtilError> {
+        let connector = ConnectorDefinition::get_connector_by_name(name)
+            .ok_or_else(|| ConnectUtilError::Config(format!("Unknown connector: {}", name)))?;
+
+        let connector_type_str = match connector.connector_type {
+            ConnectorType::Source => "source",
+            ConnectorType::Sink => "sink",
+        };
+        say!("{} ({})", connector.display_name, connector_type_str);
+        say!("  Class: {}", connector.connector_class);
+        if !connector.aliases.is_empty() {
+            say!("  Aliases: {}", connector.aliases.join(", "));
+        }
+        say!("  Description: {}", i18n::connector_description(&connector));
+        Self::print_fields_by_group(&connector);
+
+        Ok(())
+    }
+
+    /// Prints a JSON Schema of the connector's config map for editor validation
+    pub async fn describe_connector_schema(&mut self, name: &str) -> Result<(), ConnectUtilError> {
+        let connector = ConnectorDefinition::get_connector_by_name(name)
+            .ok_or_else(|| ConnectUtilError::Config(format!("Unknown connector: {}", name)))?;
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&config_schema(&connector))?
+        );
+        Ok(())
+    }
+
+    /// Prints what the catalog knows about one of a connector's config keys
+    pub async fn explain(&mut self, connector: &str, key: &str) -> Result<(), ConnectUtilError> {
+        for line in self.explain_lines(connector, key)? {
+            say!("{}", line);
+        }
+        Ok(())
+    }
+
+    /// `explain` output: the field's description, type, default, valid values, and
+    /// the connector's related keys (those sharing its leading segment, or whose
+    /// description mentions it). The default is the catalog's, or else the value
+    /// `generate` writes
+    fn explain_lines(&self, connector: &str, key: &str) -> Result<Vec<String>, ConnectUtilError> {
+        let connector = ConnectorDefinition::get_connector_by_name(connector)
+            .ok_or_else(|| ConnectUtilError::Config(format!("Unknown connector: {}", connector)))?;
+        let fields: Vec<&ConfigField> = connector
+            .required_configs
+            .iter()
+            .chain(connector.optional_configs.iter())
+            .collect();
+        let generated = TerraformGenerator
+            .generate_connector_config(TerraformConfigOptions {
+                connector_name: "explain".to_string(),
+                connector: connector.clone(),
+                ..Default::default()
+            })
+            .ok()
+            .and_then(|terraform| self.parse_terraform_configs(&terraform).ok())
+            .and_then(|configs| configs.into_iter().next())
+            .and_then(|config| config.config.get(key).cloned())
+            .filter(|value| !value.contains("<REPLACE_WITH_"));
+
+        let Some(field) = fields.iter().find(|field| field.name == key) else {
+            if connector.sensitive_configs.iter().any(|name| name == key) {
+                return Ok(vec![
+                    format!("{} ({})", key, connector.name),
+                    "  Sensitive: yes (goes in config_sensitive)".to_string(),
+                    "  Default: none".to_string(),
+                ]);
+            }
+            if let Some(value) = generated {
+                return Ok(vec![
+                    format!("{} ({})", key, connector.name),
+                    format!(
+                        "  Not in the catalog's fields, but generate writes it for {}",
+                        connector.name
+                    ),
+                    format!("  Default: {} (as generated)", value),
+                ]);
+            }
+            let similar: Vec<&str> = fields
+                .iter()
+                .map(|field| field.name.as_str())
+                .filter(|name| name.contains(key) || key.contains(*name))
+                .collect();
+            let hint = if similar.is_empty() {
+                format!(
+                    "run `connect-util describe {}` to list them",
+                    connector.name
+                )
+            } else {
+                format!("similar fields: {}", similar.join(", "))
+            };
+            return Err(ConnectUtilError::Config(format!(
+                "{} has no field '{}'; {}",
+                connector.name, key, hint
+            )));
+        };
+
+        let mut lines = vec![
+            format!("{} ({})", field.name, connector.name),
+            format!("  {}", i18n::field_description(&connector, field)),
+            format!("  Type: {}", field.field_type),
+            format!("  Required: {}", if field.required { "yes" } else { "no" }),
+            format!("  Group: {}", field.group.display_name()),
+        ];
+        if connector.sensitive_configs.contains(&field.name) {
+            lines.push("  Sensitive: yes (goes in config_sensitive)".to_string());
+        }
+        lines.push(match (&field.default_value, generated) {
+            (Some(default), _) => format!("  Default: {}", default),
+            (None, Some(value)) => format!("  Default: {} (as generated)", value),
+            (None, None) => "  Default: none".to_string(),
+        });
+        if let Some(valid_values) = &field.valid_values {
+            lines.push(format!("  Valid values: {}", valid_values.join(", ")));
+        }
+        let prefix = key.split('.').next().unwrap_or(key);
+        let related: Vec<&str> = fields
+            .iter()
+            .filter(|other| other.name != key)
+            .filter(|other| {
+                other.name.split('.').next() == Some(prefix) || other.description.contains(key)
+            })
+            .map(|other| other.name.as_str())
+            .collect();
+        if !related.is_empty() {
+            lines.push(format!("  Related: {}", related.join(", ")));
+        }
+        Ok(lines)
+    }
+
+    fn print_fields_by_group(connector: &ConnectorDefinition) {
+        for (group, fields) in connector.fields_by_group() {
+            say!();
+            say!("  [{}]", group.display_name());
+            for field in fields {
+                let required = if field.required { " (required)" } else { "" };
+                let sensitive = if connector.sensitive_configs.contains(&field.name) {
+                    " (sensitive)"
+                } else {
+                    ""
+                };
+                say!(
+                    "    - {}{}{}: {}",
+                    field.name,
+                    required,
+                    sensitive,
+                    i18n::field_description(connector, field)
+                );
+                if let Some(valid_values) = &field.valid_values {
+                    say!("      Valid values: {}", valid_values.join(", "));
+                }
+            }
+        }
+    }
+
+    /// Converts a self-managed worker `.properties` file into a connector resource,
+    /// warning about settings that don't carry over to fully-managed connectors
+    pub fn convert_properties_file(
+        &self,
+        properties_file: &str,
+        options: &ConnectorOptions,
+    ) -> Result<(), ConnectUtilError> {
+        let converted =
+            convert_properties(&stdio::read_input(properties_file, "Properties file")?)?;
+        self.emit_converted(&converted, options)
+    }
+
+    /// Converts connector JSON into a connector resource: a Connect REST payload, a
+    /// Confluent Cloud UI export, or a bare config map, detected from its layout
+    pub fn convert_json_file(
+        &self,
+        json_file: &str,
+        options: &ConnectorOptions,
+    ) -> Result<(), ConnectUtilError> {
+        let (format, converted) = convert_json(&stdio::read_input(json_file, "Connector JSON")?)?;
+        say!("📄 Read {} as a {}", json_file, format.display_name());
+        self.emit_converted(&converted, options)
+    }
+
+    /// Reports a conversion's warnings and writes its resource
+    fn emit_converted(
+        &self,
+        converted: &ConvertedConnector,
+        options: &ConnectorOptions,
+    ) -> Result<(), ConnectUtilError> {
+        for warning in &converted.warnings {
+            say!("⚠️  {}", warning);
+        }
+        if !converted.config_sensitive.is_empty() {
+            say!(
+                "🔒 Moved {} sensitive key(s) to config_sensitive with placeholder values",
+                converted.config_sensitive.len()
+            );
+        }
+
+        let generator = TerraformGenerator;
+        let terraform = generator.generate_resource_from_config(
+            &converted.name,
+            &converted.config_nonsensitive,
+            &converted.config_sensitive,
+        )?;
+        let definition = ConnectorDefinition::get_all_connectors()
+            .into_iter()
+            .find(|connector| connector.matches_class(&converted.connector_class));
+        self.emit_output(options, &stamp(&terraform, definition.as_ref()))
+    }
+
+    /// Validates planned `confluent_connector` creates and updates from
+    /// `terraform show -json` output, using the values after variable resolution
+    pub async fn plan_check(&mut self, plan_file: &str) -> Result<(), ConnectUtilError> {
+        let plan_path = Path::new(plan_file);
+        let planned = parse_plan(&stdio::read_input(plan_file, "Plan file")?)?;
+        if planned.is_empty() {
+            say!("✅ No connector creates or updates in the plan");
+            return Ok(());
+        }
+        let policy = Policy::discover(plan_path)?
+            .map(|(_, policy)| policy)
+            .unwrap_or_default();
+
+        say!(
+            "🔍 Found {} planned connector change(s) to validate",
+            planned.len()
+        );
+        let mut failed = 0;
+        for change in &planned {
+            say!(
+                "\n--- {} ({}) ---",
+                change.address,
+                change.actions.join(", ")
+            );
+            let problems = self.plan_change_problems(change, &policy, plan_path);
+            if problems.is_empty() {
+                say!("✅ Planned configuration is valid!");
+            } else {
+                failed += 1;
+                for problem in problems {
+                    say!("❌ {}", problem);
+                }
+            }
+        }
+
+        if failed > 0 {
+            return Err(ConnectUtilError::Validation(format!(
+                "{} of {} planned connector change(s) failed validation",
+                failed,
+                planned.len()
+            )));
+        }
+        say!(
+            "\n✅ All {} planned connector change(s) passed",
+            planned.len()
+        );
+        Ok(())
+    }
+
+    fn plan_change_problems(
+        &self,
+        change: &PlannedConnector,
+        policy: &Policy,
+        plan_path: &Path,
+    ) -> Vec<String> {
+        let unknown = change.unknown_values();
+        if !unknown.is_empty() {
+            say!("ℹ️  Known after apply: {}", unknown.join(", "));
+        }
+        if change.unknown_nonsensitive.entire_map {
+            // Not even the connector class is known yet
+            say!(
+                "ℹ️  config_nonsensitive is only known after apply — skipped configuration checks"
+            );
+            return Vec::new();
+        }
+        let config = &change.config;
+        let Some(connector_def) =
+            ConnectorDefinition::get_connector_by_name(&config.connector_class)
+        else {
+            return vec![format!("Unknown connector: {}", config.connector_class)];
+        };
+
+        let connector_name = config.config.get("name").unwrap_or(&config.name);
+        let mut problems =
+            self.naming_violations(connector_name, &config.name, &connector_def.connector_type);
+        problems.extend(policy.check(config, plan_path));
+
+        // Values only known after apply can't be checked, so treat them as present and
+        // valid, in the map they come from
+        let placeholder = |key: &str| {
+            connector_def
+                .required_configs
+                .iter()
+                .chain(connector_def.optional_configs.iter())
+                .find(|field| field.name == key)
+                .and_then(|field| field.valid_values.as_ref()?.first().cloned())
+                .unwrap_or_default()
+        };
+        let mut config_nonsensitive = config.config.clone();
+        for key in &change.unknown_nonsensitive.keys {
+            config_nonsensitive
+                .entry(key.clone())
+                .or_insert_with(|| placeholder(key));
+        }
+        let mut config_sensitive = config.sensitive_config.clone();
+        let mut sensitive_unknown: Vec<&str> = change
+            .unknown_sensitive
+            .keys
+            .iter()
+            .map(String::as_str)
+            .collect();
+        if change.unknown_sensitive.entire_map {
+            // Its keys aren't known either, so whatever has to be in it is assumed to be
+            sensitive_unknown.extend(
+                connector_def
+                    .required_configs
+                    .iter()
+                    .map(|field| field.name.as_str())
+                    .filter(|key| !config_nonsensitive.contains_key(*key)),
+            );
+            if config_nonsensitive
+                .get("kafka.auth.mode")
+                .map(String::as_str)
+                == Some("KAFKA_API_KEY")
+            {
+                sensitive_unknown.extend([
+                    "kafka.api.key",
+                    "kafka.api.secret",
+                    schema_registry::SR_API_KEY,
+                    schema_registry::SR_API_SECRET,
+                ]);
+            }
+        }
+        for key in sensitive_unknown {
+            config_sensitive
+                .entry(key.to_string())
+                .or_insert_with(|| placeholder(key));
+        }
+        if let Err(error) = connector_def.validate_config(&config_nonsensitive, &config_sensitive) {
+            problems.push(error);
+        }
+        problems
+    }
+
+    /// Builds Kafka Connect REST payloads for every connector in a Terraform file,
+    /// with warnings about settings that can't be exercised locally
+    pub fn local_test_payloads(
+        &self,
+        config_file: &str,
+        overrides: &BTreeMap<String, String>,
+    ) -> Result<Vec<(serde_json::Value, Vec<String>)>, ConnectUtilError> {
+        let path = Path::new(config_file);
+        if !path.exists() {
+            return Err(ConnectUtilError::Config(format!(
+                "Configuration file not found: {}",
+                config_file
+            )));
+        }
+        let configs = self.parse_terraform_configs(&std::fs::read_to_string(path)?)?;
+        if configs.is_empty() {
+            return Err(ConnectUtilError::Config(
+                "No connector configurations found in the file.".to_string(),
+            ));
+        }
+        configs
+            .iter()
+            .map(|config| connect_rest_payload(config, overrides))
+            .collect()
+    }
+
+    /// Smoke tests each connector in a Terraform file against local Kafka and
+    /// Connect containers, failing unless every connector reaches RUNNING
+    #[cfg(feature = "local-test")]
+    #[cfg(not(tarpaulin_include))]
+    pub async fn local_test(
+        &mut self,
+        config_file: &str,
+        overrides: &BTreeMap<String, String>,
+        options: crate::harness::LocalTestOptions,
+    ) -> Result<(), ConnectUtilError> {
+        let harness = crate::harness::LocalHarness::new(options);
+        for (payload, warnings) in self.local_test_payloads(config_file, overrides)? {
+            for warning in &warnings {
+                say!("⚠️  {}", warning);
+            }
+            harness.run(&payload).await?;
+            say!("✅ Connector '{}' reached RUNNING", payload["name"]);
+        }
+        Ok(())
+    }
+
+    /// Scans a directory for hardcoded secrets, unreplaced placeholders, and
+    /// misplaced sensitive keys, failing when any error-level finding is present
+    pub async fn scan(&mut self, dir: &str, format: ReportFormat) -> Result<(), ConnectUtilError> {
+        let scanner = SecretScanner::new();
+        let report = if stdio::is_stdio(dir) {
+            let source = self.read_source(dir)?;
+            ScanReport {
+                files_scanned: 1,
+                findings: scanner.scan_content(&source.path, &source.content),
+            }
+        } else {
+            scanner.scan_dir(Path::new(dir))?
+        };
+        println!("{}", report.render(format)?.trim_end());
+
+        let errors = report.error_count();
+        if errors > 0 {
+            return Err(ConnectUtilError::Validation(format!(
+                "Scan found {} error(s) in {}",
+                errors, dir
+            )));
+        }
+        Ok(())
+    }
+
+    /// Scaffolds a connectors repository under `dir`, leaving existing files alone
+    /// unless `force` is set. Returns the paths written
+    pub async fn init(
+        &mut self,
+        dir: &str,
+        environments: &[String],
+        ci: CiProvider,
+        force: bool,
+    ) -> Result<Vec<PathBuf>, ConnectUtilError> {
+        let root = Path::new(dir);
+        let mut written = Vec::new();
+        for file in scaffold(environments, ci) {
+            let path = root.join(file.path.as_deref().unwrap_or_default());
+            if path.exists() && !force {
+                say!(
+                    "⏭️  {} exists, skipping (use --force to overwrite)",
+                    path.display()
+                );
+                continue;
+            }
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, &file.content)?;
+            say!("📝 Wrote {}", path.display());
+            written.push(path);
+        }
+
+        say!("");
+        say!(
+            "✅ Scaffolded {}. Next: set the environment IDs in {}, then run `connect-util generate --environment <name>`",
+            root.display(),
+            Policy::FILE_NAME
+        );
+        Ok(written)
+    }
+
+    /// Reports connectors running in Confluent Cloud that neither the state file
+    /// nor the `.tf` files under `dir` account for, with an import command for each
+    pub async fn find_orphans(
+        &mut self,
+        api: &dyn ConnectApi,
+        environment_id: &str,
+        cluster_id: &str,
+        state_file: Option<&str>,
+        dir: Option<&str>,
+    ) -> Result<Vec<LiveConnector>, ConnectUtilError> {
+        let mut managed = ManagedConnectors::default();
+        if let Some(state_file) = state_file {
+            if Path::new(state_file).exists() {
+                managed = ManagedConnectors::from_state(&std::fs::read_to_string(state_file)?)?;
+                say!(
+                    "📄 {} connector(s) recorded in {}",
+                    managed.names.len().max(managed.ids.len()),
+                    state_file
+                );
+            } else {
+                say!("⚠️  State file not found: {}", state_file);
+            }
+        }
+        if let Some(dir) = dir {
+            managed.names.extend(
+                self.declared_connectors(Path::new(dir))?
+                    .into_iter()
+                    .filter_map(|c| c.config.config.get("name").cloned()),
+            );
+        }
+
+        let live = api.list_connectors(environment_id, cluster_id).await?;
+        let orphans: Vec<LiveConnector> = live
+            .into_iter()
+            .filter(|c| !managed.contains(&c.name, c.id.as_deref()))
+            .collect();
+
+        if orphans.is_empty() {
+            say!(
+                "✅ Every connector in {} is managed by Terraform",
+                cluster_id
+            );
+            return Ok(orphans);
+        }
+        say!("⚠️  Found {} unmanaged connector(s):", orphans.len());
+        for orphan in &orphans {
+            say!(
+                "\n  {} ({}, {})",
+                orphan.name,
+                orphan.connector_class.as_deref().unwrap_or("unknown class"),
+                orphan.state.as_deref().unwrap_or("unknown state")
+            );
+            say!(
+                "    terraform import confluent_connector.{} \"{}\"",
+                TerraformGenerator::resource_name(&orphan.name),
+                TerraformGenerator::connector_import_id(environment_id, cluster_id, &orphan.name)
+            );
+        }
+        Ok(orphans)
+    }
+
+    /// Every connector declared by the `.tf` files under `dir`; files that don't
+    /// parse are skipped
+    fn declared_connectors(&self, dir: &Path) -> Result<Vec<ModuleConnector>, ConnectUtilError> {
+        let mut files = Vec::new();
+        collect_terraform_files(dir, &mut files)?;
+        files.sort();
+        let mut connectors = Vec::new();
+        for file in files
+            .iter()
+            .filter(|f| f.extension().is_some_and(|e| e == "tf"))
+        {
+            let Ok(configs) = self.parse_terraform_configs(&std::fs::read_to_string(file)?) else {
+                continue;
+            };
+            connectors.extend(configs.into_iter().map(|config| ModuleConnector {
+                file: file.clone(),
+                config,
+            }));
+        }
+        Ok(connectors)
+    }
+
+    /// Lists every connector deployed to a cluster or declared under `dir`, marking
+    /// each as managed, unmanaged (deployed only), or missing (declared only)
+    pub async fn list_deployed_connectors(
+        &mut self,
+        api: &dyn ConnectApi,
+        environment_id: &str,
+        cluster_id: &str,
+        dir: &str,
+    ) -> Result<Vec<ConnectorCoverage>, ConnectUtilError> {
+        if !Path::new(dir).is_dir() {
+            return Err(ConnectUtilError::Config(format!(
+                "Directory not found: {}",
+                dir
+            )));
+        }
+        let declared = self.declared_connectors(Path::new(dir))?;
+        let live = api.list_connectors(environment_id, cluster_id).await?;
+        let rows = coverage(live, &declared);
+
+        say!("📋 Connectors in {}/{}:", environment_id, cluster_id);
+        for row in &rows {
+            let detail = match (&row.live, &row.resource) {
+                (_, Some((file, label))) => {
+                    format!("confluent_connector.{} in {}", label, file.display())
+                }
+                (Some(live), None) => format!(
+                    "{}, {}",
+                    live.connector_class.as_deref().unwrap_or("unknown class"),
+                    live.state.as_deref().unwrap_or("unknown state")
+                ),
+                (None, None) => String::new(),
+            };
+            say!("  {:<10} {} ({})", row.status, row.name, detail);
+        }
+
+        let count = |status| rows.iter().filter(|row| row.status == status).count();
+        let managed = count(CoverageStatus::Managed);
+        let deployed = managed + count(CoverageStatus::Unmanaged);
+        say!(
+            "\n{} managed, {} unmanaged, {} missing",
+            managed,
+            count(CoverageStatus::Unmanaged),
+            count(CoverageStatus::Missing)
+        );
+        if let Some(percent) = (managed * 100).checked_div(deployed) {
+            say!("Terraform coverage: {}% of deployed connectors", percent);
+        }
+        Ok(rows)
+    }
+
+    /// Builds one environment's health report: each connector's state, config drift
+    /// against Confluent Cloud, validation findings, and deprecation. The report is
+    /// written to `output`, or printed when there is none
+    pub async fn health_report(
+        &mut self,
+        api: &dyn ConnectApi,
+        environment_id: &str,
+        cluster_id: &str,
+        dir: &str,
+        format: HealthFormat,
+        output: Option<&str>,
+    ) -> Result<HealthReport, ConnectUtilError> {
+        if !Path::new(dir).is_dir() {
+            return Err(ConnectUtilError::Config(format!(
+                "Directory not found: {}",
+                dir
+            )));
+        }
+        let declared = self.declared_connectors(Path::new(dir))?;
+        let mut issues = self.lint_directory(dir)?;
+        let live = api.list_connectors(environment_id, cluster_id).await?;
+
+        let mut connectors = Vec::new();
+        for row in coverage(live, &declared) {
+            let resource = row.resource.as_ref().and_then(|(file, label)| {
+                declared
+                    .iter()
+                    .find(|d| d.file == *file && d.config.name == *label)
+            });
+            let drift = match (resource, &row.live) {
+                (Some(resource), Some(live)) => config_drift(&resource.config, &live.config),
+                _ => Vec::new(),
+            };
+            if let (Some(resource), false) = (resource, drift.is_empty()) {
+                let findings: Vec<String> = drift.iter().map(ToString::to_string).collect();
+                self.snapshot_failure(resource, row.live.as_ref(), &findings);
+            }
+            let findings = match &row.resource {
+                Some((file, label)) => {
+                    let (mine, rest) = issues.into_iter().partition(|issue: &LintIssue| {
+                        issue.file == *file
+                            && issue.connector.as_deref().map(base_label) == Some(base_label(label))
+                    });
+                    issues = rest;
+                    mine
+                }
+                None => Vec::new(),
+            };
+            let class = resource
+                .map(|r| r.config.connector_class.clone())
+                .or_else(|| row.live.as_ref().and_then(|l| l.connector_class.clone()))
+                .unwrap_or_default();
+            connectors.push(ConnectorHealth {
+                coverage: row,
+                drift,
+                findings,
+                deprecated: ConnectorDefinition::superseded_by(&class).map(str::to_string),
+            });
+        }
+
+        let report = HealthReport {
+            environment: environment_id.to_string(),
+            cluster: cluster_id.to_string(),
+            generated_at: chrono::Utc::now().format("%Y-%m-%d %H:%M UTC").to_string(),
+            connectors,
+            other_findings: issues,
+        };
+        let rendered = report.render(format);
+        match output.filter(|path| !stdio::is_stdio(path)) {
+            Some(path) => {
+                std::fs::write(path, &rendered)?;
+                say!(
+                    "📝 Wrote the health report for {} to {}",
+                    environment_id,
+                    path
+                );
+            }
+            None => println!("{}", rendered.trim_end()),
+        }
+        Ok(report)
+    }
+
+    /// Checks connectors in the file or directory `path` against the config
+    /// definitions their plugins report in Confluent Cloud. Each plugin's schema is
+    /// fetched once through `cache`, with up to `concurrency` requests in flight, and
+    /// a plugin's connectors are reported as soon as its schema arrives. Returns the
+    /// number of findings
+    pub async fn validate_remote(
+        &mut self,
+        api: &dyn ConnectApi,
+        cache: &SchemaCache,
+        environment_id: &str,
+        cluster_id: &str,
+        path: &str,
+        concurrency: usize,
+    ) -> Result<usize, ConnectUtilError> {
+        let connectors = self.connectors_at(Path::new(path))?;
+        let versions = api.plugin_versions(environment_id, cluster_id).await?;
+        // Live configs are only needed for the snapshots of failed connectors
+        let live = match &self.debug_dir {
+            Some(_) => api
+                .list_connectors(environment_id, cluster_id)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Couldn't fetch live configs for --debug-dir: {}", e);
+                    Vec::new()
+                }),
+            None => Vec::new(),
+        };
+
+        say!(
+            "\n🌐 Checking {} connector(s) against {}/{}",
+            connectors.len(),
+            environment_id,
+            cluster_id
+        );
+        // One lookup per plugin, in the order connectors first use it
+        let mut plugins: Vec<&str> = Vec::new();
+        for connector in &connectors {
+            let class = connector.config.connector_class.as_str();
+            if !is_dynamic_value(class) && !plugins.contains(&class) {
+                plugins.push(class);
+            }
+        }
+        let mut lookups = futures::stream::iter(plugins.into_iter().map(|class| {
+            let version = versions.get(class).map(String::as_str);
+            async move {
+                let schema = cache
+                    .get(api, environment_id, cluster_id, class, version)
+                    .await;
+                (class, version, schema)
+            }
+        }))
+        .buffer_unordered(concurrency.max(1));
+
+        let progress = Progress::new(connectors.len(), "connectors");
+        progress.start("Fetching plugin schemas");
+        let mut findings = 0;
+        while let Some((class, version, schema)) = lookups.next().await {
+            let schema = schema?;
+            for connector in connectors
+                .iter()
+                .filter(|connector| connector.config.connector_class == class)
+            {
+                let config = &connector.config;
+                let problems = schema_findings(config, &schema);
+                if problems.is_empty() {
+                    say!(
+                        "✅ '{}' matches {} {}",
+                        config.name,
+                        class,
+                        version.unwrap_or("")
+                    );
+                }
+                for problem in &problems {
+                    say!("❌ {}", problem);
+                }
+                if !problems.is_empty() {
+                    let deployed = live.iter().find(|live| live.name == config.name);
+                    self.snapshot_failure(connector, deployed, &problems);
+                }
+                findings += problems.len();
+                progress.advance();
+            }
+        }
+        drop(progress);
+        say!(
+            "📦 Downloaded {} plugin schema(s); the rest came from the cache",
+            cache.downloads()
+        );
+        Ok(findings)
+    }
+
+    /// The connectors declared in a file, or in every file under a directory
+    fn connectors_at(&self, path: &Path) -> Result<Vec<ModuleConnector>, ConnectUtilError> {
+        if path.is_dir() {
+            return self.declared_connectors(path);
+        }
+        let locals = module_locals(path.parent().unwrap_or(Path::new(".")));
+        Ok(self
+            .parse_terraform_configs_with_locals(&std::fs::read_to_string(path)?, &locals)?
+            .into_iter()
+            .map(|config| ModuleConnector {
+                file: path.to_path_buf(),
+                config,
+            })
+            .collect())
+    }
+
+    /// Converts connectors in the file or directory `path` for a self-managed
+    /// cluster, as `generate --target self-managed` does, and runs each through the
+    /// worker's plugin validation. Returns the number of rejected fields
+    pub async fn validate_connect(
+        &mut self,
+        api: &dyn ConnectRestApi,
+        path: &str,
+    ) -> Result<usize, ConnectUtilError> {
+        let connectors = self.connectors_at(Path::new(path))?;
+        say!(
+            "\n🔌 Checking {} connector(s) against {}",
+            connectors.len(),
+            api.url()
+        );
+        let mut findings = 0;
+        for connector in &connectors {
+            let config = &connector.config;
+            if is_dynamic_value(&config.connector_class) {
+                say!(
+                    "⏭️  Skipping '{}': its connector.class is computed",
+                    config.name
+                );
+                continue;
+            }
+            let converted = match self_managed_connector(config) {
+                Ok(converted) => converted,
+                Err(error) => {
+                    say!("⏭️  Skipping '{}': {}", config.name, error);
+                    continue;
+                }
+            };
+            let mut rest_config = converted.config.clone();
+            rest_config.extend(converted.config_sensitive.clone());
+            let errors = api
+                .validate_config(&converted.connector_class, &rest_config)
+                .await?;
+            if errors.is_empty() {
+                say!(
+                    "✅ '{}' passes {} validation",
+                    config.name,
+                    converted.connector_class
+                );
+            }
+            for error in &errors {
+                say!(
+                    "❌ {}: '{}' {}: {}",
+                    connector.file.display(),
+                    config.name,
+                    error.key,
+                    error.errors.join("; ")
+                );
+            }
+            findings += errors.len();
+        }
+        Ok(findings)
+    }
+
+    /// Prints a connector's committed offsets, one line per partition
+    pub async fn show_offsets(
+        &mut self,
+        api: &dyn ConnectApi,
+        environment_id: &str,
+        cluster_id: &str,
+        connector: &str,
+    ) -> Result<ConnectorOffsets, ConnectUtilError> {
+        let offsets = api
+            .get_offsets(environment_id, cluster_id, connector)
+            .await?;
+        if offsets.offsets.is_empty() {
+            say!("ℹ️  '{}' has no committed offsets", connector);
+            return Ok(offsets);
+        }
+        say!(
+            "📍 Offsets for '{}' ({} partition(s)):",
+            connector,
+            offsets.offsets.len()
+        );
+        for offset in &offsets.offsets {
+            say!(
+                "  {} → {}",
+                serde_json::Value::Object(offset.partition.clone()),
+                serde_json::Value::Object(offset.offset.clone())
+            );
+        }
+        if let Some(observed_at) = &offsets.observed_at {
+            say!("  Observed at: {}", observed_at);
+        }
+        Ok(offsets)
+    }
+
+    /// Shows the current offsets, then asks for confirmation (unless `yes`) before
+    /// requesting the reset
+    pub async fn reset_offsets(
+        &mut self,
+        api: &dyn ConnectApi,
+        environment_id: &str,
+        cluster_id: &str,
+        connector: &str,
+        to: OffsetReset,
+        yes: bool,
+    ) -> Result<(), ConnectUtilError> {
+        self.show_offsets(api, environment_id, cluster_id, connector)
+            .await?;
+        if !yes {
+            let proceed = Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!(
+                    "Reset '{}' to the earliest offsets? It will reprocess its data",
+                    connector
+                ))
+                .default(false)
+                .interact()
+                .map_err(|e| {
+                    ConnectUtilError::UserInput(format!("Failed to confirm reset: {}", e))
+                })?;
+            if !proceed {
+                say!("ℹ️  Reset cancelled");
+                return Ok(());
+            }
+        }
+        api.reset_offsets(environment_id, cluster_id, connector, to)
+            .await?;
+        say!(
+            "✅ Requested an offset reset for '{}'; run `connect-util offsets {}` to check when it has been applied",
+            connector, connector
+        );
+        Ok(())
+    }
+
+    /// Writes the built-in catalog as a JSON bundle, or prints it when no output is given
+    pub async fn export_catalog(&mut self, output: Option<&str>) -> Result<(), ConnectUtilError> {
+        let bundle = CatalogBundle::builtin();
+        let json = bundle.to_json()?;
+        match output.filter(|path| !stdio::is_stdio(path)) {
+            Some(path) => {
+                std::fs::write(path, format!("{}\n", json))?;
+                say!(
+                    "✅ Exported {} connector(s) to {}",
+                    bundle.connectors.len(),
+                    path
+                );
+            }
+            None => println!("{}", json),
+        }
+        Ok(())
+    }
+
+    fn load_catalog(path: &str) -> Result<CatalogBundle, ConnectUtilError> {
+        CatalogBundle::from_json(&stdio::read_input(path, "Catalog bundle")?)
+            .map_err(|e| ConnectUtilError::Config(format!("{}: {}", path, e)))
+    }
+
+    /// Prints connector counts and field metadata coverage for the built-in catalog,
+    /// or for an exported bundle
+    pub async fn catalog_stats(
+        &mut self,
+        bundle: Option<&str>,
+        json: bool,
+    ) -> Result<CatalogStats, ConnectUtilError> {
+        let bundle = match bundle {
+            Some(path) => Self::load_catalog(path)?,
+            None => CatalogBundle::builtin(),
+        };
+        let stats = CatalogStats::collect(&bundle);
+        if json {
+            println!("{}", serde_json::to_string_pretty(&stats)?);
+        } else {
+            say!("{}", stats.render().trim_end());
+        }
+        Ok(stats)
+    }
+
+    /// Merges a metadata file into the built-in catalog, or an exported bundle, and
+    /// reports what it filled in, the conflicts it left alone, and the fields still
+    /// missing metadata. With `output`, the connectors that changed are written as a
+    /// definitions file that `--definitions` loads over the built-ins
+    pub async fn enrich_catalog(
+        &mut self,
+        metadata: &str,
+        bundle: Option<&str>,
+        output: Option<&str>,
+        overwrite: bool,
+        json: bool,
+    ) -> Result<EnrichReport, ConnectUtilError> {
+        if json && output.is_some_and(stdio::is_stdio) {
+            return Err(ConnectUtilError::UserInput(
+                "--format json prints the report on stdout; write the definitions to a file with --output".to_string(),
+            ));
+        }
+        let metadata =
+            CatalogMetadata::from_json(&stdio::read_input(metadata, "Catalog metadata")?)
+                .map_err(|e| ConnectUtilError::Config(format!("{}: {}", metadata, e)))?;
+        let bundle = match bundle {
+            Some(path) => Self::load_catalog(path)?,
+            None => CatalogBundle::builtin(),
+        };
+        let (enriched, report) = enrich(&bundle, &metadata, overwrite);
+        if json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            say!("{}", report.render().trim_end());
+        }
+
+        let Some(output) = output else {
+            return Ok(report);
+        };
+        let changed: Vec<&ConnectorDefinition> = enriched
+            .connectors
+            .iter()
+            .zip(&bundle.connectors)
+            .filter(|(after, before)| fingerprint(after) != fingerprint(before))
+            .map(|(after, _)| after)
+            .collect();
+        if changed.is_empty() {
+            say!("ℹ️  No connector changed, so nothing was written");
+            return Ok(report);
+        }
+        let definitions = serde_json::to_string_pretty(&changed)?;
+        if stdio::is_stdio(output) {
+            println!("{}", definitions);
+        } else {
+            std::fs::write(output, format!("{}\n", definitions))?;
+            say!(
+                "✅ Wrote {} enriched definition(s) to {}; load them with --definitions",
+                changed.len(),
+                output
+            );
+        }
+        Ok(report)
+    }
+
+    /// Reports connectors added, removed, and changed between two exported bundles,
+    /// optionally as a Markdown summary for posting on pull requests
+    pub async fn diff_catalog_bundles(
+        &mut self,
+        old_file: &str,
+        new_file: &str,
+        markdown: bool,
+    ) -> Result<(), ConnectUtilError> {
+        let diff = diff_catalogs(
+            &Self::load_catalog(old_file)?,
+            &Self::load_catalog(new_file)?,
+        );
+        if markdown {
+            println!("{}", diff.to_markdown(old_file, new_file).trim_end());
+            return Ok(());
+        }
+        if diff.is_empty() {
+            say!(
+                "✅ No catalog differences between {} and {}",
+                old_file,
+                new_file
+            );
+            return Ok(());
+        }
+
+        say!("📋 Catalog changes from {} to {}", old_file, new_file);
+        if !diff.added.is_empty() {
+            say!();
+            say!("  Added ({}):", diff.added.len());
+            for name in &diff.added {
+                say!("    + {}", name);
+            }
+        }
+        if !diff.removed.is_empty() {
+            say!();
+            say!("  Removed ({}):", diff.removed.len());
+            for name in &diff.removed {
+                say!("    - {}", name);
+            }
+        }
+        if !diff.changed.is_empty() {
+            say!();
+            say!("  Changed ({}):", diff.changed.len());
+            for connector in &diff.changed {
+                say!("    {}", connector.name);
+                for change in &connector.changes {
+                    say!("      {}", change);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn list_plugins(
+        &mut self,
+        filter_type: Option<String>,
+    ) -> Result<(), ConnectUtilError> {
+        let all_connectors = ConnectorDefinition::get_all_connectors();
+
+        let filtered_connectors = if let Some(filter) = filter_type {
+            let connector_type = match filter.to_lowercase().as_str() {
+                "source" => ConnectorType::Source,
+                "sink" => ConnectorType::Sink,
+                _ => {
+                    say!("❌ Invalid filter type. Use 'source' or 'sink'");
+                    return Ok(());
+                }
+            };
+            all_connectors
+                .into_iter()
+                .filter(|c| c.connector_type == connector_type)
+                .collect()
+        } else {
+            all_connectors
+        };
+
+        say!("Available connector plugins:");
+        for connector in filtered_connectors {
+            let connector_type_str = match connector.connector_type {
+                ConnectorType::Source => "source",
+                ConnectorType::Sink => "sink",
+            };
+            say!("  - {} ({})", connector.display_name, connector_type_str);
+            say!("    Class: {}", connector.connector_class);
+            say!(
+                "    Description: {}",
+                i18n::connector_description(&connector)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cloud::{PluginSchema, SchemaFetch};
+    use crate::metadata::ConnectorMetadata;
+    use crate::plan::UnknownValues;
+    use crate::types::{CompanionResources, ConfigGroup, SchemaRegistryOptions};
+    use hcl::Object;
+    use proptest::prelude::*;
+    use proptest::test_runner::TestCaseError;
+
+    #[tokio::test]
+    async fn test_parse_terraform_config_success() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let terraform_content = r#"
+        module "test_connector" {
+          config_sensitive = {
+            "connection.password" = "secret_password"
+          }
+          config_nonsensitive = {
+            "connector.class" = "PostgresSink"
+            "connection.host" = "localhost"
+            "connection.port" = "5432"
+            "connection.user" = "test_user"
+            "db.name" = "test_db"
+          }
+        }
+        "#;
+
+        let result = app.parse_terraform_configs(terraform_content);
+        assert!(result.is_ok(), "Should parse valid Terraform config");
+
+        let configs = result.unwrap();
+        assert!(!configs.is_empty(), "Should have at least one config");
+        let config = &configs[0];
+        assert_eq!(config.connector_class, "PostgresSink");
+        assert_eq!(
+            config.config.get("connection.host"),
+            Some(&"localhost".to_string())
+        );
+        assert_eq!(
+            config.config.get("connection.port"),
+            Some(&"5432".to_string())
+        );
+        assert_eq!(
+            config.sensitive_config.get("connection.password"),
+            Some(&"secret_password".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_terraform_config_with_comments() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let terraform_content = r#"
+        module "test_connector" {
+          config_sensitive = {
+            "connection.password" = "secret_password"
+          }
+          config_nonsensitive = {
+            "connector.class" = "PostgresSink"
+            # This is a comment
+            "connection.host" = "localhost"
+            "connection.port" = "5432"
+            # Another comment
+            "connection.user" = "test_user"
+            "db.name" = "test_db"
+          }
+        }
+        "#;
+
+        let result = app.parse_terraform_configs(terraform_content);
+        assert!(
+            result.is_ok(),
+            "Should parse Terraform config with comments"
+        );
+
+        let configs = result.unwrap();
+        assert!(!configs.is_empty(), "Should have at least one config");
+        let config = &configs[0];
+        assert_eq!(config.connector_class, "PostgresSink");
+        assert_eq!(
+            config.config.get("connection.host"),
+            Some(&"localhost".to_string())
+        );
+        assert_eq!(
+            config.config.get("connection.port"),
+            Some(&"5432".to_string())
+        );
+        assert_eq!(
+            config.sensitive_config.get("connection.password"),
+            Some(&"secret_password".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_terraform_config_dynamic_expressions() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let terraform_content = r#"
+        resource "confluent_connector" "orders" {
+          config_sensitive = {
+            "kafka.api.secret" = var.kafka_api_secret
+          }
+          config_nonsensitive = {
+            "connector.class"    = "DatagenSource"
+            "name"               = "${var.prefix}-orders"
+            "kafka.topic"        = "orders"
+            "output.data.format" = var.avro ? "AVRO" : "JSON"
+            "tasks.max"          = (var.tasks * 2)
+            "topics"             = join(",", [for t in var.topics : "${t}.v1"])
+            "quickstart"         = local.quickstart
+            "max.interval"       = null
+          }
+        }
+        "#;
+
+        let configs = app.parse_terraform_configs(terraform_content).unwrap();
+        let config = &configs[0];
+        let value = |key: &str| config.config.get(key).map(String::as_str);
+        assert_eq!(value("kafka.topic"), Some("orders"));
+        assert_eq!(value("name"), Some("${var.prefix}-orders"));
+        assert_eq!(
+            value("output.data.format"),
+            Some("${var.avro ? \"AVRO\" : \"JSON\"}")
+        );
+        assert_eq!(value("tasks.max"), Some("${var.tasks * 2}"));
+        assert!(value("topics")
+            .unwrap()
+            .starts_with("${join(\",\", [for t in var.topics"));
+        assert_eq!(value("quickstart"), Some("${local.quickstart}"));
+        assert_eq!(value("max.interval"), None);
+        assert_eq!(
+            config.sensitive_config.get("kafka.api.secret"),
+            Some(&"${var.kafka_api_secret}".to_string())
+        );
+        assert_eq!(
+            config.dynamic_keys(),
+            vec![
+                "kafka.api.secret",
+                "name",
+                "output.data.format",
+                "quickstart",
+                "tasks.max",
+                "topics"
+            ]
+        );
+
+        // Dynamic values skip enum checks instead of failing them
+        let definition = ConnectorDefinition::get_connector_by_name("DatagenSource").unwrap();
+        let mut nonsensitive = config.config.clone();
+        // Datagen names its topic with kafka.topic, so `topics` is rejected
+        assert!(definition
+            .validate_config(&nonsensitive, &config.sensitive_config)
+            .unwrap_err()
+            .contains("'topics' doesn't apply to DatagenSource"));
+        nonsensitive.remove("topics");
+        assert!(definition
+            .validate_config(&nonsensitive, &config.sensitive_config)
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_parse_terraform_config_resolves_locals_and_merge() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let terraform_content = r#"
+        locals {
+          common_config = {
+            "kafka.auth.mode" = "SERVICE_ACCOUNT"
+            "tasks.max"       = "1"
+          }
+        }
+
+        resource "confluent_connector" "orders" {
+          config_sensitive = {}
+          config_nonsensitive = merge(local.common_config, {
+            "connector.class" = "DatagenSource"
+            "kafka.topic"     = local.topic
+            "tasks.max"       = "2"
+          })
+        }
+        "#;
+        let module_locals = Locals::from([(
+            "topic".to_string(),
+            Expression::String("orders".to_string()),
+        )]);
+
+        let configs = app
+            .parse_terraform_configs_with_locals(terraform_content, &module_locals)
+            .unwrap();
+        assert_eq!(configs.len(), 1);
+        let config = &configs[0].config;
+        assert_eq!(configs[0].connector_class, "DatagenSource");
+        assert_eq!(
+            config.get("kafka.auth.mode"),
+            Some(&"SERVICE_ACCOUNT".to_string())
+        );
+        assert_eq!(config.get("tasks.max"), Some(&"2".to_string()));
+        assert_eq!(config.get("kafka.topic"), Some(&"orders".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_validate_connector_uses_module_locals() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("locals.tf"),
+            "locals {\n  connector_class = \"DatagenSource\"\n}\n",
+        )
+        .unwrap();
+        let main = dir.path().join("main.tf");
+        std::fs::write(
+            &main,
+            r#"resource "confluent_connector" "orders" {
+  status = "RUNNING"
+  environment {
+    id = "env-123"
+  }
+  kafka_cluster {
+    id = "lkc-123"
+  }
+  config_sensitive = {}
+  config_nonsensitive = {
+    "connector.class" = local.connector_class
+  }
+}
+"#,
+        )
+        .unwrap();
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        // Without the sibling locals.tf, the connector class would be unknown
+        assert!(app.validate_connector(main.to_str().unwrap()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_validate_connector_mixed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".git")).unwrap();
+        let topics = r#"
+resource "confluent_kafka_topic" "orders" {
+  topic_name = "orders"
+}
+resource "confluent_service_account" "app" {
+  display_name = "app"
+}
+data "confluent_environment" "prod" {
+  id = var.environment_id
+}
+module "acls" {
+  source   = "./acls"
+  for_each = toset(["read", "write"])
+}
+"#;
+        let mixed = dir.path().join("main.tf");
+        std::fs::write(
+            &mixed,
+            format!(
+                r#"{}
+resource "confluent_connector" "datagen" {{
+  status = "RUNNING"
+  environment {{
+    id = data.confluent_environment.prod.id
+  }}
+  kafka_cluster {{
+    id = var.kafka_cluster_id
+  }}
+  config_sensitive = {{}}
+  config_nonsensitive = {{
+    "connector.class"          = "DatagenSource"
+    "name"                     = "datagen"
+    "kafka.auth.mode"          = "SERVICE_ACCOUNT"
+    "kafka.service.account.id" = confluent_service_account.app.id
+    "kafka.topic"              = confluent_kafka_topic.orders.topic_name
+    "output.data.format"       = "JSON"
+    "quickstart"               = "ORDERS"
+  }}
+}}
+"#,
+                topics
+            ),
+        )
+        .unwrap();
+        let topics_file = dir.path().join("topics.tf");
+        std::fs::write(&topics_file, topics).unwrap();
+
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        assert!(app
+            .meta_argument_notes(&parse_body(topics).unwrap(), &Locals::new())
+            .is_empty());
+        assert!(app
+            .validate_connector(mixed.to_str().unwrap())
+            .await
+            .is_ok());
+        // A file without connectors only warns, unless the policy file says otherwise
+        assert!(app
+            .validate_connector(topics_file.to_str().unwrap())
+            .await
+            .is_ok());
+        std::fs::write(
+            dir.path().join(Policy::FILE_NAME),
+            "severities:\n  no_connectors: error\n",
+        )
+        .unwrap();
+        let error = app
+            .validate_connector(topics_file.to_str().unwrap())
+            .await
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("it has confluent_kafka_topic.orders, confluent_service_account.app"));
+
+        // Connectors looked up with a data source count, and skip the resource checks
+        let existing = dir.path().join("existing.tf");
+        std::fs::write(
+            &existing,
+            r#"data "confluent_connector" "upstream" {
+  id = "lcc-abc123"
+  environment {
+    id = data.confluent_environment.prod.id
+  }
+  kafka_cluster {
+    id = var.kafka_cluster_id
+  }
+}
+"#,
+        )
+        .unwrap();
+        assert!(app
+            .validate_connector(existing.to_str().unwrap())
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_parse_terraform_config_expands_for_each() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let terraform_content = r#"
+        locals {
+          sinks = {
+            orders  = "orders.v1"
+            refunds = "refunds.v1"
+          }
+        }
+
+        resource "confluent_connector" "s3" {
+          for_each         = local.sinks
+          config_sensitive = {}
+          config_nonsensitive = {
+            "connector.class" = "S3_SINK"
+            "name"            = "${each.key}-s3"
+            "topics"          = each.value
+          }
+        }
+
+        resource "confluent_connector" "dynamic" {
+          for_each         = var.connectors
+          config_sensitive = {}
+          config_nonsensitive = {
+            "connector.class" = each.value.connector_class
+          }
+        }
+
+        resource "confluent_connector" "replicas" {
+          count            = 2
+          config_sensitive = {}
+          config_nonsensitive = {
+            "connector.class" = "DatagenSource"
+            "kafka.topic"     = "replica-${count.index}"
+          }
+        }
+        "#;
+
+        let configs = app.parse_terraform_configs(terraform_content).unwrap();
+        let names: Vec<&str> = configs.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "s3[\"orders\"]",
+                "s3[\"refunds\"]",
+                "dynamic",
+                "replicas[0]",
+                "replicas[1]"
+            ]
+        );
+        assert_eq!(
+            configs[1].config.get("name"),
+            Some(&"refunds-s3".to_string())
+        );
+        assert_eq!(
+            configs[1].config.get("topics"),
+            Some(&"refunds.v1".to_string())
+        );
+        assert_eq!(configs[2].connector_class, "${each.value.connector_class}");
+        assert_eq!(
+            configs[4].config.get("kafka.topic"),
+            Some(&"replica-1".to_string())
+        );
+
+        let body = parse_body(terraform_content).unwrap();
+        let notes = app.meta_argument_notes(&body, &collect_locals(&body));
+        assert_eq!(notes.len(), 3);
+        assert!(notes[0].contains("expands to 2 instance(s)"));
+        assert!(notes[1].contains("dynamic number of instances (for_each = var.connectors)"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_resource_block_dynamic_blocks() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let body: Body = hcl::from_str(
+            r#"
+status = "RUNNING"
+dynamic "environment" {
+  for_each = [var.environment_id]
+  content {
+    id = environment.value
+  }
+}
+dynamic "kafka_cluster" {
+  for_each = [var.kafka_cluster_id]
+  content {
+    id = kafka_cluster.value
+  }
+}
+config_sensitive    = {}
+config_nonsensitive = {}
+lifecycle {
+  ignore_changes = [config_sensitive["kafka.api.secret"], config_nonsensitive.*]
+}
+"#,
+        )
+        .unwrap();
+        assert!(app.validate_resource_block(&body, "orders").is_ok());
+
+        let body: Body = hcl::from_str(
+            r#"
+status = "RUNNING"
+dynamic "environment" {
+  for_each = [var.environment_id]
+  content {
+    name = environment.value
+  }
+}
+"#,
+        )
+        .unwrap();
+        let error = app.validate_resource_block(&body, "orders").unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("must have 'id' attribute (found: name)"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_resource_block_rejects_dynamic_lifecycle() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let body: Body = hcl::from_str(
+            r#"
+status = "RUNNING"
+dynamic "lifecycle" {
+  for_each = [1]
+  content {
+    prevent_destroy = true
+  }
+}
+"#,
+        )
+        .unwrap();
+        let error = app.validate_resource_block(&body, "orders").unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("lifecycle must be written literally"));
+    }
+
+    #[tokio::test]
+    async fn test_parse_terraform_config_missing_connector_class() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let terraform_content = r#"
+        module "test_connector" {
+          config_sensitive = {
+            "connection.password" = "secret_password"
+          }
+          config_nonsensitive = {
+            "connection.host" = "localhost"
+            "connection.port" = "5432"
+            "connection.user" = "test_user"
+            "db.name" = "test_db"
+          }
+        }
+        "#;
+
+        let result = app.parse_terraform_configs(terraform_content);
+        assert!(
+            result.is_ok(),
+            "Should parse even without connector.class (returns empty list)"
+        );
+        let configs = result.unwrap();
+        assert!(
+            configs.is_empty(),
+            "Should return empty list when connector.class is missing"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_terraform_config_entirely_commented() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let terraform_content = r#"
+        # module "test_connector" {
+        #   config_sensitive = {
+        #     "connection.password" = "secret_password"
+        #   }
+        #   config_nonsensitive = {
+        #     "connector.class" = "PostgresSink"
+        #     "connection.host" = "localhost"
+        #     "connection.port" = "5432"
+        #     "connection.user" = "test_user"
+        #     "db.name" = "test_db"
+        #   }
+        # }
+        "#;
+
+        let result = app.parse_terraform_configs(terraform_content);
+        assert!(
+            result.is_ok(),
+            "Should parse even when entire file is commented (returns empty list)"
+        );
+        let configs = result.unwrap();
+        assert!(
+            configs.is_empty(),
+            "Should return empty list when entire file is commented"
+        );
+    }
+
+    /// Characters for generated HCL strings, some of which need escaping
+    const STRING_CHARS: &[&str] = &[
+        "a", "Z", "0", ".", "-", "_", " ", "/", ":", ",", "\"", "\\", "${", "%{", "}", "{", "\n",
+        "\t", "é", "日", "=", "#", "//",
+    ];
+
+    /// A quoted HCL string of arbitrary characters, escaped so it stays valid
+    fn hcl_string() -> impl Strategy<Value = String> {
+        prop::collection::vec(prop::sample::select(STRING_CHARS), 0..12).prop_map(|parts| {
+            let escaped: String = parts
+                .into_iter()
+                .map(|part| match part {
+                    "\"" => "\\\"",
+                    "\\" => "\\\\",
+                    "${" => "$${",
+                    "%{" => "%%{",
+                    "\n" => "\\n",
+                    "\t" => "\\t",
+                    other => other,
+                })
+                .collect();
+            format!("\"{}\"", escaped)
+        })
+    }
+
+    fn select_string(items: &'static [&'static str]) -> impl Strategy<Value = String> {
+        prop::sample::select(items).prop_map(str::to_string)
+    }
+
+    /// Literals, references, templates, and heredocs
+    fn leaf_expression() -> BoxedStrategy<String> {
+        prop_oneof![
+            2 => hcl_string(),
+            1 => select_string(&["0", "-1", "4", "3.5", "1e3", "9223372036854775807"]),
+            1 => select_string(&["true", "false", "null"]),
+            1 => select_string(&[
+                "var.database_password",
+                "local.settings",
+                "local.settings.topic",
+                "each.key",
+                "each.value.topic",
+                "count.index",
+                "confluent_kafka_topic.orders.topic_name",
+                "var.map[\"key\"]",
+            ]),
+            1 => Just("\"${var.topic}-${each.key}\"".to_string()),
+            1 => Just(
+                "<<-EOT\n  line one ${var.x}\n  %{ if true }yes%{ endif }\n  EOT\n".to_string()
+            ),
+            1 => Just("[]".to_string()),
+            1 => Just("{}".to_string()),
+        ]
+        .boxed()
+    }
+
+    /// An HCL expression nested up to `depth` levels: lists, objects, function
+    /// calls, conditionals, and `for` expressions
+    fn expression(depth: u32) -> BoxedStrategy<String> {
+        leaf_expression()
+            .prop_recursive(depth, 32, 4, |inner| {
+                prop_oneof![
+                    prop::collection::vec(inner.clone(), 0..4)
+                        .prop_map(|items| format!("[{}]", items.join(", "))),
+                    object(inner.clone()),
+                    prop::collection::vec(inner.clone(), 0..4)
+                        .prop_map(|items| format!("join(\",\", [{}])", items.join(", "))),
+                    (object(inner.clone()), inner.clone())
+                        .prop_map(|(object, other)| format!("merge({}, {})", object, other)),
+                    (
+                        select_string(&["var.enabled", "true"]),
+                        inner.clone(),
+                        inner.clone()
+                    )
+                        .prop_map(|(condition, yes, no)| format!(
+                            "{} ? {} : {}",
+                            condition, yes, no
+                        )),
+                    inner
+                        .clone()
+                        .prop_map(|items| format!("[for s in {} : upper(s)]", items)),
+                    inner.clone().prop_map(|inner| format!("({})", inner)),
+                    (
+                        select_string(&["toset", "lookup", "tostring", "jsonencode", "unknown_fn"]),
+                        inner
+                    )
+                        .prop_map(|(function, argument)| format!("{}({})", function, argument)),
+                ]
+            })
+            .boxed()
+    }
+
+    /// An object literal whose values come from `value`
+    fn object(value: impl Strategy<Value = String> + 'static) -> BoxedStrategy<String> {
+        let key = prop_oneof![select_string(&["name", "tasks_max", "topic"]), hcl_string()];
+        prop::collection::vec((key, value), 0..5)
+            .prop_map(|entries| {
+                let mut out = String::from("{\n");
+                for (key, value) in entries {
+                    out.push_str(&format!("  {} = {}\n", key, value));
+                }
+                out.push('}');
+                out
+            })
+            .boxed()
+    }
+
+    /// The header of a generated block, by kind
+    fn block_header(kind: u8, index: usize) -> String {
+        match kind {
+            0 => format!("module \"m{}\"", index),
+            1 => "locals".to_string(),
+            2 => format!("resource \"confluent_kafka_topic\" \"t{}\"", index),
+            _ => format!("resource \"confluent_connector\" \"c{}\"", index),
+        }
+    }
+
+    /// One block's contents: its kind, an optional `for_each` or `count`, and the
+    /// body used for `locals` or for connector-shaped blocks
+    fn block() -> impl Strategy<Value = (u8, String, String)> {
+        let meta = prop_oneof![
+            3 => Just(String::new()),
+            1 => expression(2).prop_map(|collection| format!("  for_each = {}\n", collection)),
+            1 => select_string(&["0", "2", "var.n", "1.5", "-1", "10000000000"])
+                .prop_map(|count| format!("  count = {}\n", count)),
+        ];
+        let class = prop_oneof![
+            Just("\"DatagenSource\"".to_string()),
+            hcl_string(),
+            expression(2),
+        ];
+        (
+            0..5u8,
+            meta,
+            expression(3),
+            (class, expression(1), expression(3), object(expression(3))),
+        )
+            .prop_map(|(kind, meta, settings, (class, environment, sensitive, nonsensitive))| {
+                let body = if kind == 1 {
+                    format!("  settings = {}\n", settings)
+                } else {
+                    format!(
+                        "  environment {{\n    id = {}\n  }}\n  config_sensitive = {}\n  config_nonsensitive = merge({}, {{\n    \"connector.class\" = {}\n  }})\n",
+                        environment, sensitive, nonsensitive, class
+                    )
+                };
+                (kind, meta, body)
+            })
+    }
+
+    /// A file of connector resources, modules, locals, and other blocks
+    fn terraform_file() -> impl Strategy<Value = String> {
+        prop::collection::vec(block(), 1..4).prop_map(|blocks| {
+            let mut out = String::new();
+            for (index, (kind, meta, body)) in blocks.into_iter().enumerate() {
+                out.push_str(&format!(
+                    "# block {}\n{} {{\n{}{}}}\n\n",
+                    index,
+                    block_header(kind, index),
+                    meta,
+                    body
+                ));
+            }
+            out
+        })
+    }
+
+    /// An edit that damages valid HCL the way editing mistakes do
+    #[derive(Debug, Clone)]
+    struct Mutation {
+        at: prop::sample::Index,
+        len: usize,
+        kind: u8,
+        fragment: &'static str,
+    }
+
+    impl Mutation {
+        fn apply(&self, text: &str) -> String {
+            let chars: Vec<char> = text.chars().collect();
+            let at = self.at.index(chars.len() + 1);
+            let end = (at + self.len).min(chars.len());
+            let (head, tail): (String, String) =
+                (chars[..at].iter().collect(), chars[end..].iter().collect());
+            match self.kind {
+                0 => head,
+                1 => format!("{}{}", head, tail),
+                2 => format!("{}{}{}", head, self.fragment, tail),
+                _ => format!(
+                    "{}{}{}",
+                    head,
+                    &chars[at..].iter().collect::<String>(),
+                    tail
+                ),
+            }
+        }
+    }
+
+    fn mutation() -> impl Strategy<Value = Mutation> {
+        (
+            any::<prop::sample::Index>(),
+            0..8usize,
+            0..4u8,
+            prop::sample::select(
+                &[
+                    "{",
+                    "}",
+                    "\"",
+                    "${",
+                    "=",
+                    "[",
+                    "\n",
+                    "<<EOT",
+                    "#",
+                    "\\",
+                    "18446744073709551615",
+                    "\"🚀\\n\"",
+                ][..],
+            ),
+        )
+            .prop_map(|(at, len, kind, fragment)| Mutation {
+                at,
+                len,
+                kind,
+                fragment,
+            })
+    }
+
+    /// A generated file damaged by one to three mutations
+    fn malformed_terraform_file() -> impl Strategy<Value = String> {
+        (terraform_file(), prop::collection::vec(mutation(), 1..4)).prop_map(|(text, mutations)| {
+            mutations
+                .iter()
+                .fold(text, |text, mutation| mutation.apply(&text))
+        })
+    }
+
+    /// Parses `text` and checks its structure as `validate` does
+    fn validate_structure(app: &ConnectUtilApp, text: &str) -> Result<(), ConnectUtilError> {
+        let body = parse_body(text).map_err(|e| parse_error(&e))?;
+        app.validate_terraform_structure(&body)
+    }
+
+    /// Parses `text` as `validate` does, including the meta-argument notes; proptest
+    /// reports and shrinks any input that panics
+    fn parse_generated(app: &ConnectUtilApp, text: &str) -> TerraformParseResults {
+        if let Ok(body) = parse_body(text) {
+            let _ = app.meta_argument_notes(&body, &collect_locals(&body));
+        }
+        app.parse_terraform_configs(text)
+    }
+
+    #[test]
+    fn test_parse_terraform_configs_arbitrary_connector_bodies() {
+        let app = futures::executor::block_on(ConnectUtilApp::new()).unwrap();
+        proptest!(ProptestConfig::with_cases(300), |(text in terraform_file())| {
+            let configs = parse_generated(&app, &text)
+                .map_err(|e| TestCaseError::fail(format!("failed to parse: {}", e)))?;
+            for config in configs {
+                let label = config.name.split('[').next().unwrap();
+                prop_assert!(
+                    text.contains(&format!("\"{}\" {{", label)),
+                    "unexpected connector '{}'",
+                    config.name
+                );
+                prop_assert!(!config.connector_class.is_empty());
+            }
+        });
+    }
+
+    #[test]
+    fn test_parse_terraform_configs_malformed_input() {
+        let app = futures::executor::block_on(ConnectUtilApp::new()).unwrap();
+        proptest!(ProptestConfig::with_cases(300), |(text in malformed_terraform_file())| {
+            // Either parses or reports a Config error; never panics
+            if let Err(error) = parse_generated(&app, &text) {
+                prop_assert!(matches!(error, ConnectUtilError::Config(_)), "{}", error);
+            }
+        });
+    }
+
+    #[tokio::test]
+    async fn test_add_connector_specific_config_postgres_cdc() {
+        let _app = ConnectUtilApp::new().await.unwrap();
+        let mut config_obj = Object::new();
+        let connector_def =
+            ConnectorDefinition::get_connector_by_name("PostgresCdcSourceV2").unwrap();
+
+        let options = TerraformConfigOptions {
+            connector_name: "test".to_string(),
+            connector: connector_def.clone(),
+            topics: vec![],
+            input_data_format: None,
+            output_data_format: None,
+            ..Default::default()
+        };
+        let result = TerraformGenerator::add_connector_specific_config_to_object(
+            &mut config_obj,
+            &connector_def,
+            &options,
+        );
+        assert!(result.is_ok(), "Should successfully add connector config");
+
+        // Check that configuration was added to config object
+        assert!(config_obj.contains_key(&TerraformGenerator::make_object_key("database.sslmode")));
+        assert!(config_obj.contains_key(&TerraformGenerator::make_object_key("publication.name")));
+        assert!(config_obj.contains_key(&TerraformGenerator::make_object_key("snapshot.mode")));
+    }
+
+    #[tokio::test]
+    async fn test_add_connector_specific_config_s3_sink() {
+        let _app = ConnectUtilApp::new().await.unwrap();
+        let mut config_obj = Object::new();
+        let connector_def = ConnectorDefinition::get_connector_by_name("S3_SINK").unwrap();
+
+        let options = TerraformConfigOptions {
+            connector_name: "test".to_string(),
+            connector: connector_def.clone(),
+            topics: vec![],
+            input_data_format: None,
+            output_data_format: None,
+            ..Default::default()
+        };
+        let result = TerraformGenerator::add_connector_specific_config_to_object(
+            &mut config_obj,
+            &connector_def,
+            &options,
+        );
+        assert!(result.is_ok(), "Should successfully add connector config");
+
+        // Check that configuration was added to config object
+        assert!(config_obj.contains_key(&TerraformGenerator::make_object_key("s3.bucket.name")));
+        assert!(config_obj.contains_key(&TerraformGenerator::make_object_key("topics.dir")));
+        assert!(config_obj.contains_key(&TerraformGenerator::make_object_key("input.data.format")));
+    }
+
+    #[tokio::test]
+    async fn test_add_connector_specific_config_unknown_connector() {
+        let _app = ConnectUtilApp::new().await.unwrap();
+        let mut config_obj = Object::new();
+        let connector_def = ConnectorDefinition {
+            name: "UnknownConnector".to_string(),
+            display_name: "Unknown Connector".to_string(),
+            connector_class: "UnknownConnector".to_string(),
+            aliases: vec![],
+            connector_type: ConnectorType::Source,
+            family: ConnectorFamily::Other,
+            topic_strategy: TopicStrategy::KafkaTopicSingle,
+            description: "Unknown connector".to_string(),
+            required_configs: vec![],
+            optional_configs: vec![],
+            sensitive_configs: vec![],
+        };
+
+        let options = TerraformConfigOptions {
+            connector_name: "test".to_string(),
+            connector: connector_def.clone(),
+            topics: vec![],
+            input_data_format: None,
+            output_data_format: None,
+            ..Default::default()
+        };
+        let result = TerraformGenerator::add_connector_specific_config_to_object(
+            &mut config_obj,
+            &connector_def,
+            &options,
+        );
+        assert!(result.is_ok(), "Should not panic for unknown connector");
+        // Unknown connectors should not add any config
+        assert!(
+            config_obj.is_empty(),
+            "Unknown connector should not add any config"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_environment_specific_terraform_generation() {
+        let _app = ConnectUtilApp::new().await.unwrap();
+        let connector = ConnectorDefinition::get_connector_by_name("PostgresSink").unwrap();
+
+        // Test production environment
+        let prod_options = TerraformConfigOptions {
+            connector_name: "test-connector".to_string(),
+            connector: connector.clone(),
+            topics: vec!["test-topic".to_string()],
+            input_data_format: None,
+            output_data_format: None,
+            ..Default::default()
+        };
+
+        let generator = TerraformGenerator;
+        let prod_result = generator.generate_connector_config(prod_options);
+        assert!(prod_result.is_ok());
+        let prod_terraform = prod_result.unwrap();
+
+        // Check that terraform is generated with resource format
+        assert!(prod_terraform.contains("resource \"confluent_connector\""));
+        assert!(prod_terraform.contains("status = var.status"));
+        assert!(prod_terraform.contains("environment {"));
+        assert!(prod_terraform.contains("kafka_cluster {"));
+
+        // Test dev environment
+        let dev_options = TerraformConfigOptions {
+            connector_name: "test-connector".to_string(),
+            connector: connector.clone(),
+            topics: vec!["test-topic".to_string()],
+            input_data_format: None,
+            output_data_format: None,
+            ..Default::default()
+        };
+
+        let dev_result = generator.generate_connector_config(dev_options);
+        assert!(dev_result.is_ok());
+        let dev_terraform = dev_result.unwrap();
+
+        // Check dev-specific values
+        assert!(dev_terraform.contains("resource \"confluent_connector\""));
+        assert!(dev_terraform.contains("status = var.status"));
+        assert!(dev_terraform.contains("environment {"));
+        assert!(dev_terraform.contains("kafka_cluster {"));
+    }
+
+    #[tokio::test]
+    async fn test_terraform_structure_validation() {
+        let app = ConnectUtilApp::new().await.unwrap();
+
+        // Test resource-based Terraform validation
+        let terraform = r#"
+resource "confluent_connector" "test_connector" {
+  status = "RUNNING"
+
+  environment {
+    id = var.environment_id
+  }
+
+  kafka_cluster {
+    id = var.kafka_cluster.id
+  }
+
+  config_sensitive = {
+    "connection.password" = "secret"
+  }
+
+  config_nonsensitive = {
+    "connector.class" = "PostgresSink"
+    "name" = "test-connector"
+  }
+
+  lifecycle {
+    ignore_changes = [
+      config_nonsensitive["kafka.deployment.type"],
+    ]
+  }
+}
+"#;
+
+        let result = validate_structure(&app, terraform);
+        assert!(
+            result.is_ok(),
+            "Resource-based Terraform structure should be valid"
+        );
+
+        // Test invalid Terraform (missing required blocks)
+        let invalid_terraform = r#"
+resource "confluent_connector" "test_connector" {
+  status = "RUNNING"
+  # Missing environment and kafka_cluster blocks
+  config_sensitive = {
+    "connection.password" = "secret"
+  }
+  config_nonsensitive = {
+    "connector.class" = "PostgresSink"
+    "name" = "test-connector"
+  }
+}
+"#;
+
+        let result = validate_structure(&app, invalid_terraform);
+        assert!(
+            result.is_err(),
+            "Invalid Terraform structure should fail validation"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_terraform_structure_with_modules() {
+        let app = ConnectUtilApp::new().await.unwrap();
+
+        // Test module-based Terraform validation
+        let terraform = r#"
+module "test-connector" {
+  source = "../../modules/connector"
+
+  status = "RUNNING"
+  environment = var.environment
+  environment_id = var.environment_id
+  kafka_cluster = local.cluster
+
+  config_sensitive = {
+    "database.password" = "secret"
+  }
+
+  config_nonsensitive = {
+    "connector.class" = "MySqlCdcSourceV2"
+    "name" = "test-connector"
+  }
+}
+"#;
+
+        let result = validate_structure(&app, terraform);
+        assert!(
+            result.is_ok(),
+            "Module-based Terraform structure should be valid"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_terraform_structure_mixed_resources_and_modules() {
+        let app = ConnectUtilApp::new().await.unwrap();
+
+        // Test mixed resource and module blocks
+        let terraform = r#"
+resource "confluent_connector" "test_resource" {
+  status = "RUNNING"
+  environment {
+    id = var.environment_id
+  }
+  kafka_cluster {
+    id = var.kafka_cluster.id
+  }
+  config_sensitive = {}
+  config_nonsensitive = {
+    "connector.class" = "PostgresSink"
+  }
+}
+
+module "test_module" {
+  source = "../../modules/connector"
+  status = "RUNNING"
+  environment = var.environment
+  kafka_cluster = local.cluster
+  config_sensitive = {}
+  config_nonsensitive = {
+    "connector.class" = "MySqlCdcSourceV2"
+  }
+}
+"#;
+
+        let result = validate_structure(&app, terraform);
+        assert!(
+            result.is_ok(),
+            "Mixed resource and module blocks should be valid"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_resource_block_missing_status() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let terraform = r#"
+resource "confluent_connector" "test" {
+  environment {
+    id = var.environment_id
+  }
+  kafka_cluster {
+    id = var.kafka_cluster.id
+  }
+  config_sensitive = {}
+  config_nonsensitive = {
+    "connector.class" = "PostgresSink"
+  }
+}
+"#;
+        let body: Body = hcl::from_str(terraform).unwrap();
+        let resource_block = body
+            .blocks()
+            .find(|b| {
+                b.identifier() == "resource"
+                    && b.labels().len() >= 2
+                    && b.labels()[0].as_str() == "confluent_connector"
+            })
+            .unwrap();
+
+        let result = app.validate_resource_block(resource_block.body(), "test");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("missing 'status' field"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_resource_block_missing_environment() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let terraform = r#"
+resource "confluent_connector" "test" {
+  status = "RUNNING"
+  kafka_cluster {
+    id = var.kafka_cluster.id
+  }
+  config_sensitive = {}
+  config_nonsensitive = {
+    "connector.class" = "PostgresSink"
+  }
+}
+"#;
+        let body: Body = hcl::from_str(terraform).unwrap();
+        let resource_block = body
+            .blocks()
+            .find(|b| {
+                b.identifier() == "resource"
+                    && b.labels().len() >= 2
+                    && b.labels()[0].as_str() == "confluent_connector"
+            })
+            .unwrap();
+
+        let result = app.validate_resource_block(resource_block.body(), "test");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("missing 'environment"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_resource_block_environment_wrong_attribute() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let terraform = r#"
+resource "confluent_connector" "test" {
+  status = "RUNNING"
+  environment {
+    exo = var.environment_id
+  }
+  kafka_cluster {
+    id = var.kafka_cluster.id
+  }
+  config_sensitive = {}
+  config_nonsensitive = {
+    "connector.class" = "PostgresSink"
+  }
+}
+"#;
+        let body: Body = hcl::from_str(terraform).unwrap();
+        let resource_block = body
+            .blocks()
+            .find(|b| {
+                b.identifier() == "resource"
+                    && b.labels().len() >= 2
+                    && b.labels()[0].as_str() == "confluent_connector"
+            })
+            .unwrap();
+
+        let result = app.validate_resource_block(resource_block.body(), "test");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("environment block must have 'id' attribute"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_resource_block_missing_kafka_cluster() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let terraform = r#"
+resource "confluent_connector" "test" {
+  status = "RUNNING"
+  environment {
+    id = var.environment_id
+  }
+  config_sensitive = {}
+  config_nonsensitive = {
+    "connector.class" = "PostgresSink"
+  }
+}
+"#;
+        let body: Body = hcl::from_str(terraform).unwrap();
+        let resource_block = body
+            .blocks()
+            .find(|b| {
+                b.identifier() == "resource"
+                    && b.labels().len() >= 2
+                    && b.labels()[0].as_str() == "confluent_connector"
+            })
+            .unwrap();
+
+        let result = app.validate_resource_block(resource_block.body(), "test");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("missing 'kafka_cluster"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_resource_block_kafka_cluster_wrong_attribute() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let terraform = r#"
+resource "confluent_connector" "test" {
+  status = "RUNNING"
+  environment {
+    id = var.environment_id
+  }
+  kafka_cluster {
+    name = var.kafka_cluster.name
+  }
+  config_sensitive = {}
+  config_nonsensitive = {
+    "connector.class" = "PostgresSink"
+  }
+}
+"#;
+        let body: Body = hcl::from_str(terraform).unwrap();
+        let resource_block = body
+            .blocks()
+            .find(|b| {
+                b.identifier() == "resource"
+                    && b.labels().len() >= 2
+                    && b.labels()[0].as_str() == "confluent_connector"
+            })
+            .unwrap();
+
+        let result = app.validate_resource_block(resource_block.body(), "test");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("kafka_cluster block must have 'id' attribute"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_resource_block_missing_config_sensitive() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let terraform = r#"
+resource "confluent_connector" "test" {
+  status = "RUNNING"
+  environment {
+    id = var.environment_id
+  }
+  kafka_cluster {
+    id = var.kafka_cluster.id
+  }
+  config_nonsensitive = {
+    "connector.class" = "PostgresSink"
+  }
+}
+"#;
+        let body: Body = hcl::from_str(terraform).unwrap();
+        let resource_block = body
+            .blocks()
+            .find(|b| {
+                b.identifier() == "resource"
+                    && b.labels().len() >= 2
+                    && b.labels()[0].as_str() == "confluent_connector"
+            })
+            .unwrap();
+
+        let result = app.validate_resource_block(resource_block.body(), "test");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("missing 'config_sensitive'"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_resource_block_missing_config_nonsensitive() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let terraform = r#"
+resource "confluent_connector" "test" {
+  status = "RUNNING"
+  environment {
+    id = var.environment_id
+  }
+  kafka_cluster {
+    id = var.kafka_cluster.id
+  }
+  config_sensitive = {}
+}
+"#;
+        let body: Body = hcl::from_str(terraform).unwrap();
+        let resource_block = body
+            .blocks()
+            .find(|b| {
+                b.identifier() == "resource"
+                    && b.labels().len() >= 2
+                    && b.labels()[0].as_str() == "confluent_connector"
+            })
+            .unwrap();
+
+        let result = app.validate_resource_block(resource_block.body(), "test");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("missing 'config_nonsensitive'"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_terraform_structure_no_resources() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let terraform = r#"
+variable "test" {
+  default = "value"
+}
+"#;
+        let result = validate_structure(&app, terraform);
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("No connector configurations found"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_terraform_structure_invalid_hcl() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let terraform = r#"
+resource "confluent_connector" "test" {
+  status = "RUNNING"
+  # Missing closing brace
+"#;
+        let result = validate_structure(&app, terraform);
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Failed to parse Terraform file"));
+    }
+
+    #[tokio::test]
+    async fn test_list_plugins_all() {
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let result = app.list_plugins(None).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_list_plugins_filtered_source() {
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let result = app.list_plugins(Some("source".to_string())).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_list_plugins_filtered_sink() {
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let result = app.list_plugins(Some("sink".to_string())).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_list_plugins_invalid_filter() {
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let result = app.list_plugins(Some("invalid".to_string())).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_convert_properties_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let properties = dir.path().join("orders.properties");
+        std::fs::write(
+            &properties,
+            "name=orders-archive\nconnector.class=io.confluent.connect.s3.S3SinkConnector\n\
+             aws.secret.access.key=abc\nvalue.converter=org.apache.kafka.connect.json.JsonConverter\n",
+        )
+        .unwrap();
+        let output = dir.path().join("orders.tf");
+        let app = ConnectUtilApp::new().await.unwrap();
+        let options = ConnectorOptions {
+            output: Some(output.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+
+        app.convert_properties_file(properties.to_str().unwrap(), &options)
+            .unwrap();
+        let terraform = std::fs::read_to_string(&output).unwrap();
+        assert!(terraform.contains("resource \"confluent_connector\" \"orders_archive\""));
+        assert!(terraform.contains("\"connector.class\" = \"S3_SINK\""));
+        assert!(terraform.contains("\"aws.secret.access.key\" = \"<REPLACE_WITH_ACTUAL_VALUE>\""));
+        assert!(!terraform.contains("abc"));
+        assert!(!terraform.contains("value.converter"));
+    }
+
+    #[tokio::test]
+    async fn test_convert_json_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let export = dir.path().join("orders.json");
+        std::fs::write(
+            &export,
+            r#"{"id": {"id": "lcc-abc123", "id_type": "ID"},
+                "info": {"name": "orders-datagen", "type": "source",
+                         "config": {"connector.class": "DatagenSource", "kafka.topic": "orders",
+                                    "kafka.api.secret": "****************", "tasks.max": 1}}}"#,
+        )
+        .unwrap();
+        let output = dir.path().join("orders.tf");
+        let app = ConnectUtilApp::new().await.unwrap();
+        let options = ConnectorOptions {
+            output: Some(output.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+
+        app.convert_json_file(export.to_str().unwrap(), &options)
+            .unwrap();
+        let terraform = std::fs::read_to_string(&output).unwrap();
+        assert!(terraform.contains("resource \"confluent_connector\" \"orders_datagen\""));
+        assert!(terraform.contains("name = \"orders-datagen\""));
+        assert!(terraform.contains("\"tasks.max\" = \"1\""));
+        assert!(terraform.contains("\"kafka.api.secret\" = \"<REPLACE_WITH_ACTUAL_VALUE>\""));
+        assert!(!terraform.contains("lcc-abc123"));
+    }
+
+    #[tokio::test]
+    async fn test_convert_properties_file_missing() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let result =
+            app.convert_properties_file("/nonexistent/x.properties", &ConnectorOptions::default());
+        assert!(matches!(result, Err(ConnectUtilError::Config(_))));
+    }
+
+    #[tokio::test]
+    async fn test_local_test_payloads() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("datagen.tf");
+        let app = ConnectUtilApp::new().await.unwrap();
+        let terraform = app
+            .generate_terraform_non_interactive(ConnectorOptions {
+                name: Some("orders-datagen".to_string()),
+                connector: Some("DatagenSource".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        std::fs::write(&file, terraform).unwrap();
+
+        let payloads = app
+            .local_test_payloads(file.to_str().unwrap(), &BTreeMap::new())
+            .unwrap();
+        assert_eq!(payloads.len(), 1);
+        let (payload, warnings) = &payloads[0];
+        assert_eq!(
+            payload["config"]["connector.class"],
+            "io.confluent.kafka.connect.datagen.DatagenConnector"
+        );
+        assert!(warnings.iter().any(|w| w.contains("'kafka.auth.mode'")));
+    }
+
+    #[tokio::test]
+    async fn test_validate_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("main.tf"),
+            r#"resource "confluent_connector" "datagen" {
+  status = "RUNNING"
+  environment {
+    id = var.environment_id
+  }
+  kafka_cluster {
+    id = var.kafka_cluster_id
+  }
+  config_sensitive = {}
+  config_nonsensitive = {
+    "connector.class"          = "DatagenSource"
+    "name"                     = "datagen"
+    "kafka.auth.mode"          = "KAFKA_API_KEY"
+    "kafka.api.key"            = var.kafka_api_key
+    "kafka.topic"              = "orders"
+    "output.data.format"       = "JSON"
+    "quickstart"               = "ORDERS"
+    "tasks.max"                = "1"
+  }
+}
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("variables.tf"),
+            "variable \"environment_id\" {}\nvariable \"kafka_api_key\" {\n  sensitive = true\n}\n",
+        )
+        .unwrap();
+
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let error = app
+            .validate_directory(dir.path().to_str().unwrap())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ConnectUtilError::Validation(_)));
+        assert!(
+            error.to_string().contains("2 variable error(s)"),
+            "{}",
+            error
+        );
+
+        // The module check flags the undeclared cluster ID and the sensitive API key
+        // in config_nonsensitive
+        let issues = app.lint_directory(dir.path().to_str().unwrap()).unwrap();
+        let variable_issues: Vec<(LintRule, &str)> = issues
+            .iter()
+            .filter(|issue| {
+                matches!(
+                    issue.rule,
+                    LintRule::UndeclaredVariable | LintRule::SensitiveVariableNotRouted
+                )
+            })
+            .map(|issue| (issue.rule, issue.message.as_str()))
+            .collect();
+        assert_eq!(variable_issues.len(), 2, "{:?}", variable_issues);
+        assert!(variable_issues.iter().any(|(rule, message)| {
+            *rule == LintRule::UndeclaredVariable
+                && message.starts_with(
+                    "var.kafka_cluster_id is referenced by confluent_connector.datagen",
+                )
+        }));
+        assert!(variable_issues.iter().any(|(rule, message)| {
+            *rule == LintRule::SensitiveVariableNotRouted
+                && message.starts_with("var.kafka_api_key is declared sensitive")
+        }));
+
+        // Declaring the cluster ID and routing the key into config_sensitive passes
+        let main = std::fs::read_to_string(dir.path().join("main.tf"))
+            .unwrap()
+            .lines()
+            .filter(|line| !line.trim_start().starts_with("\"kafka.api.key\""))
+            .collect::<Vec<_>>()
+            .join("\n")
+            .replace(
+                "  config_sensitive = {}",
+                "  config_sensitive = {\n    \"kafka.api.key\"    = var.kafka_api_key\n    \"kafka.api.secret\" = var.kafka_api_secret\n  }",
+            );
+        std::fs::write(dir.path().join("main.tf"), main).unwrap();
+        std::fs::write(
+            dir.path().join("variables.tf"),
+            "variable \"environment_id\" {}\nvariable \"kafka_cluster_id\" {}\nvariable \"kafka_api_key\" {\n  sensitive = true\n}\nvariable \"kafka_api_secret\" {\n  sensitive = true\n}\n",
+        )
+        .unwrap();
+        let result = app.validate_directory(dir.path().to_str().unwrap()).await;
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[tokio::test]
+    async fn test_validate_directory_with_conflicts() {
+        let dir = tempfile::tempdir().unwrap();
+        for label in ["orders", "orders_again"] {
+            std::fs::write(
+                dir.path().join(format!("{}.tf", label)),
+                format!(
+                    r#"resource "confluent_connector" "{}" {{
+  status = "RUNNING"
+  environment {{
+    id = "env-123"
+  }}
+  kafka_cluster {{
+    id = "lkc-123"
+  }}
+  config_sensitive = {{}}
+  config_nonsensitive = {{
+    "connector.class" = "S3_SINK"
+    "name"            = "orders-s3"
+    "topics"          = join(",", ["orders", "refunds"])
+  }}
+}}
+"#,
+                    label
+                ),
+            )
+            .unwrap();
+        }
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        // Conflicts fail the run, so duplicates are caught before apply
+        let error = app
+            .validate_directory(dir.path().to_str().unwrap())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ConnectUtilError::Validation(_)));
+        assert!(
+            error.to_string().contains("conflict(s) between connectors"),
+            "{}",
+            error
+        );
+
+        let configs = app
+            .parse_terraform_configs(
+                &std::fs::read_to_string(dir.path().join("orders.tf")).unwrap(),
+            )
+            .unwrap();
+        assert_eq!(
+            configs[0].config.get("topics"),
+            Some(&"orders,refunds".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_inventory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("prod")).unwrap();
+        std::fs::write(
+            dir.path().join("prod/main.tf"),
+            r#"locals {
+  format = "AVRO"
+}
+
+resource "confluent_connector" "cdc" {
+  for_each = toset(["eu", "us"])
+  config_sensitive = {
+    "database.password" = var.db_password
+  }
+  config_nonsensitive = {
+    "connector.class"    = "PostgresCdcSource"
+    "name"               = "cdc-${each.key}"
+    "kafka.auth.mode"    = "KAFKA_API_KEY"
+    "topic.prefix"       = each.key
+    "output.data.format" = local.format
+  }
+}
+"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("notes.tf"), "variable \"unused\" {}\n").unwrap();
+
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let entries = app.inventory(dir.path().to_str().unwrap()).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].resource, "cdc[\"eu\"]");
+        assert_eq!(entries[0].name, "cdc-eu");
+        assert_eq!(entries[0].topics, vec!["eu*"]);
+        assert_eq!(entries[0].data_formats, vec!["output.data.format=AVRO"]);
+        assert_eq!(entries[0].secrets_backend, vec!["terraform-variable"]);
+        assert!(entries[0].deprecated.is_some());
+
+        assert_eq!(entries[0].metadata.owner, None);
+
+        // Production connectors need an owner
+        let production = vec!["prod".to_string()];
+        let error = app
+            .audit(
+                dir.path().to_str().unwrap(),
+                InventoryFormat::Csv,
+                &production,
+            )
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("2 production connector(s)"));
+        assert!(app
+            .audit(dir.path().to_str().unwrap(), InventoryFormat::Csv, &[])
+            .await
+            .is_ok());
+        let main = dir.path().join("prod/main.tf");
+        let content = std::fs::read_to_string(&main).unwrap().replace(
+            "resource \"confluent_connector\"",
+            "# connect-util:owner team-orders\n# connect-util:on-call #orders-oncall\nresource \"confluent_connector\"",
+        );
+        std::fs::write(&main, content).unwrap();
+        let entries = app.inventory(dir.path().to_str().unwrap()).unwrap();
+        assert_eq!(entries[1].metadata.owner.as_deref(), Some("team-orders"));
+        assert_eq!(
+            entries[1].metadata.on_call.as_deref(),
+            Some("#orders-oncall")
+        );
+        assert!(app
+            .audit(
+                dir.path().to_str().unwrap(),
+                InventoryFormat::Csv,
+                &production
+            )
+            .await
+            .is_ok());
+
+        // A production connector looked up with a data source is owned elsewhere
+        std::fs::write(
+            dir.path().join("prod/upstream.tf"),
+            "data \"confluent_connector\" \"upstream\" {\n  display_name = \"orders-cdc\"\n}\n",
+        )
+        .unwrap();
+        let entries = app.inventory(dir.path().to_str().unwrap()).unwrap();
+        assert_eq!(entries[2].resource, "data.confluent_connector.upstream");
+        assert_eq!(entries[2].name, "orders-cdc");
+        assert_eq!(entries[2].connector_type, "existing");
+        assert!(app
+            .audit(
+                dir.path().to_str().unwrap(),
+                InventoryFormat::Csv,
+                &production
+            )
+            .await
+            .is_ok());
+
+        assert!(matches!(
+            app.audit("/nonexistent", InventoryFormat::Json, &[]).await,
+            Err(ConnectUtilError::Config(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_lint_directory_suppressions_and_severities() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".git")).unwrap();
+        std::fs::write(
+            dir.path().join(Policy::FILE_NAME),
+            "severities:\n  unused_variable: error\n  undeclared-variable: off\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("main.tf"),
+            r#"# connect-util:ignore invalid_config demo connector, no credentials
+resource "confluent_connector" "datagen" {
+  environment {
+    id = var.environment_id
+  }
+  kafka_cluster {
+    id = var.kafka_cluster_id
+  }
+  config_sensitive = {}
+  config_nonsensitive = {
+    "connector.class"    = "DatagenSource"
+    "name"               = "datagen"
+    "kafka.auth.mode"    = "KAFKA_API_KEY"
+    "kafka.topic"        = "orders"
+  }
+}
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("variables.tf"),
+            "variable \"environment_id\" {}\n\nvariable \"legacy\" {}\n",
+        )
+        .unwrap();
+
+        let app = ConnectUtilApp::new().await.unwrap();
+        let issues = app.lint_directory(dir.path().to_str().unwrap()).unwrap();
+        let rules: Vec<(LintRule, &str)> = issues
+            .iter()
+            .map(|issue| (issue.rule, issue.severity))
+            .collect();
+        assert_eq!(rules, vec![(LintRule::UnusedVariable, "error")]);
+    }
+
+    #[tokio::test]
+    async fn test_lint_directory_crlf() {
+        let main = r#"# Orders datagen
+# connect-util:ignore invalid_config demo connector, no credentials
+resource "confluent_connector" "Orders" {
+  environment {
+    id = var.environment_id
+  }
+  kafka_cluster {
+    id = var.kafka_cluster_id
+  }
+  config_sensitive = {
+    "kafka.api.secret" = "s3cr3t-value"
+  }
+  config_nonsensitive = {
+    "connector.class" = "DatagenSource"
+    "name"            = "Orders"
+    "kafka.topic"     = "orders"
+    "café"            = <<-EOT
+      é
+      EOT
+  }
+}
+"#;
+        let app = ConnectUtilApp::new()
+            .await
+            .unwrap()
+            .with_naming_rules(NamingRules::new(None, Some("^[a-z][a-z0-9_]*$")).unwrap());
+        let findings = |newline: Newline| {
+            let dir = tempfile::tempdir().unwrap();
+            std::fs::create_dir(dir.path().join(".git")).unwrap();
+            std::fs::write(dir.path().join("main.tf"), newline.apply(main)).unwrap();
+            std::fs::write(
+                dir.path().join("variables.tf"),
+                newline.apply("variable \"environment_id\" {}\n\nvariable \"legacy\" {}\n"),
+            )
+            .unwrap();
+            let issues: Vec<(LintRule, usize, String)> = app
+                .lint_directory(dir.path().to_str().unwrap())
+                .unwrap()
+                .into_iter()
+                .map(|issue| {
+                    let message = issue.message.replace(dir.path().to_str().unwrap(), "");
+                    (issue.rule, issue.line, message)
+                })
+                .collect();
+            let scanned: Vec<(usize, String)> = SecretScanner::new()
+                .scan_dir(dir.path())
+                .unwrap()
+                .findings
+                .into_iter()
+                .map(|finding| (finding.line, finding.message))
+                .collect();
+            (issues, scanned)
+        };
+
+        // A CRLF checkout reports the same findings on the same lines
+        let (lf_issues, lf_scanned) = findings(Newline::Lf);
+        let (crlf_issues, crlf_scanned) = findings(Newline::Crlf);
+        assert!(lf_issues
+            .iter()
+            .any(|(rule, line, _)| *rule == LintRule::NamingConvention && *line == 3));
+        assert!(lf_scanned.iter().any(|(line, _)| *line == 11));
+        assert_eq!(crlf_issues, lf_issues);
+        assert_eq!(crlf_scanned, lf_scanned);
+    }
+
+    #[tokio::test]
+    async fn test_lint_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("main.tf"),
+            r#"# Orders datagen
+resource "confluent_connector" "datagen" {
+  status = "RUNNING"
+  environment {
+    id = var.environment_id
+  }
+  kafka_cluster {
+    id = var.kafka_cluster_id
+  }
+  config_sensitive = {}
+  config_nonsensitive = {
+    "connector.class"    = "DatagenSource"
+    "name"               = "datagen"
+    "kafka.auth.mode"    = "KAFKA_API_KEY"
+    "kafka.topic"        = "orders"
+    "output.data.format" = "JSON"
+  }
+}
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("variables.tf"),
+            "variable \"environment_id\" {}\n\nvariable \"legacy\" {}\n",
+        )
+        .unwrap();
+
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let issues = app.lint_directory(dir.path().to_str().unwrap()).unwrap();
+        let summary: Vec<(LintRule, String, usize)> = issues
+            .iter()
+            .map(|issue| {
+                (
+                    issue.rule,
+                    issue
+                        .file
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .to_string(),
+                    issue.line,
+                )
+            })
+            .collect();
+        assert!(summary.contains(&(LintRule::UndeclaredVariable, "main.tf".to_string(), 2)));
+        assert!(summary.contains(&(LintRule::UnusedVariable, "variables.tf".to_string(), 3)));
+        assert!(summary.contains(&(LintRule::InvalidConfig, "main.tf".to_string(), 2)));
+
+        let result = app.tflint(dir.path().to_str().unwrap(), false).await;
+        assert!(matches!(result, Err(ConnectUtilError::Validation(_))));
+        assert!(app.tflint("/nonexistent", true).await.is_ok());
+        assert!(matches!(
+            app.lint_directory("/nonexistent"),
+            Err(ConnectUtilError::Config(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_validation_profiles() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".git")).unwrap();
+        std::fs::write(
+            dir.path().join("main.tf"),
+            r#"resource "confluent_connector" "orders" {
+  status = "RUNNING"
+  environment {
+    id = "env-123"
+  }
+  kafka_cluster {
+    id = "lkc-123"
+  }
+  config_sensitive = {}
+  config_nonsensitive = {
+    "connector.class"          = "DatagenSource"
+    "name"                     = "orders"
+    "kafka.auth.mode"          = "SERVICE_ACCOUNT"
+    "kafka.service.account.id" = "sa-123"
+    "kafka.topic"              = "<REPLACE_WITH_TOPIC_NAME>"
+    "output.data.format"       = "JSON"
+    "quickstart"               = "ORDERS"
+    "tasks.max"                = "1"
+  }
+}
+
+variable "unused" {
+  type = string
+}
+"#,
+        )
+        .unwrap();
+        let dir_str = dir.path().to_str().unwrap();
+        let with_profile = |profile: Option<&'static str>| async move {
+            ConnectUtilApp::new()
+                .await
+                .unwrap()
+                .with_validation_profile(profile.map(|p| p.parse().unwrap()))
+        };
+        let rules = |app: &ConnectUtilApp| -> Vec<(String, &'static str)> {
+            app.lint_directory(dir_str)
+                .unwrap()
+                .into_iter()
+                .map(|issue| (issue.rule.name(), issue.severity))
+                .collect()
+        };
+
+        let app = with_profile(None).await;
+        assert!(app.enforce_profile(None, Some(dir_str)).is_ok());
+
+        let app = with_profile(Some("scaffold")).await;
+        assert_eq!(rules(&app), vec![]);
+        assert!(app.enforce_profile(None, Some(dir_str)).is_ok());
+
+        let app = with_profile(Some("pre-merge")).await;
+        assert!(rules(&app)
+            .iter()
+            .all(|(_, severity)| *severity == "warning"));
+        assert!(app.enforce_profile(None, Some(dir_str)).is_ok());
+
+        let app = with_profile(Some("pre-deploy")).await;
+        assert_eq!(
+            rules(&app),
+            vec![
+                ("connect_util_placeholder".to_string(), "error"),
+                ("connect_util_unused_variable".to_string(), "error"),
+            ]
+        );
+        let error = app.enforce_profile(None, Some(dir_str)).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("2 error finding(s) fail the pre-deploy profile"));
+
+        // The policy file's severities still win over the profile
+        std::fs::write(
+            dir.path().join(Policy::FILE_NAME),
+            "severities:\n  placeholder: warning\n",
+        )
+        .unwrap();
+        let error = app.enforce_profile(None, Some(dir_str)).unwrap_err();
+        assert!(error.to_string().contains("1 error finding(s)"));
+    }
+
+    #[tokio::test]
+    async fn test_lint_directory_resolves_class_aliases() {
+        let dir = tempfile::tempdir().unwrap();
+        let resource = |label: &str, class: &str| {
+            format!(
+                r#"resource "confluent_connector" "{label}" {{
+  status = "RUNNING"
+  environment {{
+    id = "env-123"
+  }}
+  kafka_cluster {{
+    id = "lkc-123"
+  }}
+  config_sensitive = {{}}
+  config_nonsensitive = {{
+    "connector.class"    = "{class}"
+    "name"               = "{label}"
+    "kafka.auth.mode"    = "KAFKA_API_KEY"
+    "kafka.topic"        = "orders"
+    "output.data.format" = "JSON"
+    "quickstart"         = "ORDERS"
+    "tasks.max"          = "1"
+  }}
+}}
+"#
+            )
+        };
+        std::fs::write(
+            dir.path().join("main.tf"),
+            [
+                resource(
+                    "java",
+                    "io.confluent.kafka.connect.datagen.DatagenConnector",
+                ),
+                resource("lower", "datagensource"),
+                resource("unknown", "com.example.Missing"),
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        let app = ConnectUtilApp::new().await.unwrap();
+        let unknown: Vec<String> = app
+            .lint_directory(dir.path().to_str().unwrap())
+            .unwrap()
+            .into_iter()
+            .filter(|issue| issue.rule == LintRule::UnknownConnector)
+            .filter_map(|issue| issue.connector)
+            .collect();
+        assert_eq!(unknown, vec!["unknown".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_validate_markdown() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("main.tf"),
+            "resource \"confluent_connector\" \"datagen\" {\n  config_nonsensitive = {\n    \"connector.class\" = \"DatagenSource\"\n  }\n}\n",
+        )
+        .unwrap();
+        let file = dir.path().join("main.tf");
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        assert!(app
+            .validate_markdown(None, Some(dir.path().to_str().unwrap()))
+            .await
+            .is_ok());
+        assert!(app
+            .validate_markdown(Some(file.to_str().unwrap()), None)
+            .await
+            .is_ok());
+        assert!(matches!(
+            app.validate_markdown(Some("/nonexistent/main.tf"), None)
+                .await,
+            Err(ConnectUtilError::Config(_))
+        ));
+        assert!(matches!(
+            app.validate_markdown(None, None).await,
+            Err(ConnectUtilError::UserInput(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_validation_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("main.tf"),
+            "resource \"confluent_connector\" \"datagen\" {\n  config_nonsensitive = {\n    \"connector.class\" = \"DatagenSource\"\n  }\n}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("variables.tf"), "variable \"legacy\" {}\n").unwrap();
+        let file = dir.path().join("main.tf");
+        let mut app = ConnectUtilApp::new().await.unwrap();
+
+        let stats = app
+            .validation_findings(None, Some(dir.path().to_str().unwrap()))
+            .unwrap()
+            .stats();
+        assert_eq!(stats.files_scanned, 2);
+        assert_eq!(stats.connectors, 1);
+        assert_eq!(stats.connectors_by_type["source"], 1);
+        assert_eq!(stats.connectors_by_class["DatagenSource"], 1);
+        assert_eq!(stats.findings_by_rule["connect_util_invalid_config"], 1);
+        assert_eq!(stats.findings_by_rule["connect_util_unused_variable"], 1);
+        assert_eq!(stats.slowest_files.len(), 2);
+
+        let stats = app
+            .validation_findings(Some(file.to_str().unwrap()), None)
+            .unwrap()
+            .stats();
+        assert_eq!(stats.files_scanned, 1);
+        assert!(!stats
+            .findings_by_rule
+            .contains_key("connect_util_unused_variable"));
+        assert!(app
+            .validate_json(None, Some(dir.path().to_str().unwrap()))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_stdin_findings() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("main.tf");
+        std::fs::write(&main, "variable \"stale\" {}\n").unwrap();
+        std::fs::write(
+            dir.path().join("variables.tf"),
+            "variable \"topic\" {}\nvariable \"legacy\" {}\n",
+        )
+        .unwrap();
+        let app = ConnectUtilApp::new().await.unwrap();
+        let content = "resource \"confluent_connector\" \"datagen\" {\n  config_nonsensitive = {\n    \"connector.class\" = \"DatagenSource\"\n    \"kafka.topic\" = var.topic\n    \"quickstart\" = var.quickstart\n  }\n}\n";
+
+        // Piped content replaces main.tf and is checked with the module's other files
+        let findings = app
+            .stdin_findings(SourceFile::parse(&main, content.to_string()))
+            .unwrap();
+        let issues: Vec<(String, &str)> = findings
+            .issues
+            .iter()
+            .map(|issue| (issue.rule.name(), issue.message.as_str()))
+            .collect();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].0, "connect_util_undeclared_variable");
+        assert!(issues[0].1.contains("var.quickstart"));
+        assert_eq!(findings.issues[0].file, main);
+        assert_eq!(findings.entries.len(), 1);
+        assert_eq!(findings.timings.len(), 1);
+
+        // Suppressions come from the piped content, not the file on disk
+        let suppressed = format!(
+            "# connect-util:ignore undeclared_variable set by the caller\n{}",
+            content
+        );
+        let findings = app
+            .stdin_findings(SourceFile::parse(&main, suppressed))
+            .unwrap();
+        assert!(findings.issues.is_empty());
+
+        // Without --stdin-filename the content stands alone under <stdin>
+        let stdin = PathBuf::from(stdio::STDIN_NAME);
+        let findings = app
+            .stdin_findings(SourceFile::parse(&stdin, content.to_string()))
+            .unwrap();
+        assert!(findings.issues.iter().all(|issue| issue.file == stdin));
+        assert_eq!(findings.entries[0].file, stdin);
+    }
+
+    #[derive(Default)]
+    struct RecordingNotifier(std::sync::Mutex<Vec<serde_json::Value>>);
+
+    #[async_trait::async_trait]
+    impl Notifier for RecordingNotifier {
+        async fn send(&self, payload: &serde_json::Value) -> Result<(), ConnectUtilError> {
+            self.0.lock().unwrap().push(payload.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_notify_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("main.tf"),
+            "resource \"confluent_connector\" \"datagen\" {\n  config_nonsensitive = {\n    \"connector.class\" = \"DatagenSource\"\n  }\n}\n",
+        )
+        .unwrap();
+        let app = ConnectUtilApp::new().await.unwrap();
+        let notifier = RecordingNotifier::default();
+        let root = dir.path().to_str().unwrap();
+
+        let summary = app
+            .notify_validation(&notifier, NotifyFormat::Slack, None, Some(root), None)
+            .await
+            .unwrap();
+        assert_eq!(summary.target, root);
+        assert_eq!(summary.connectors, 1);
+        assert_eq!(summary.failed, 1);
+        assert!(!summary.top_findings.is_empty());
+
+        {
+            let sent = notifier.0.lock().unwrap();
+            assert_eq!(sent.len(), 1);
+            assert_eq!(sent[0]["text"], summary.headline());
+            assert!(sent[0]["blocks"].is_array());
+        }
+
+        assert!(matches!(
+            app.notify_validation(&notifier, NotifyFormat::Json, None, None, None)
+                .await,
+            Err(ConnectUtilError::UserInput(_))
+        ));
+        assert_eq!(notifier.0.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_lint_directory_ignore_changes() {
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::create_dir(repo.path().join(".git")).unwrap();
+        std::fs::write(
+            repo.path().join(Policy::FILE_NAME),
+            "required_ignore_changes:\n  - config_nonsensitive[\"kafka.deployment.type\"]\n",
+        )
+        .unwrap();
+        for (label, lifecycle) in [
+            ("orders", "[config_nonsensitive[\"kafka.deployment.type\"]]"),
+            ("refunds", "[status, config_nonsensitive[\"tasks.max\"]]"),
+            ("payments", "all"),
+        ] {
+            std::fs::write(
+                repo.path().join(format!("{}.tf", label)),
+                format!(
+                    "resource \"confluent_connector\" \"{0}\" {{\n  config_nonsensitive = {{\n    \"connector.class\" = \"DatagenSource\"\n    \"name\" = \"{0}\"\n  }}\n  lifecycle {{\n    ignore_changes = {1}\n  }}\n}}\n",
+                    label, lifecycle
+                ),
+            )
+            .unwrap();
+        }
+        let app = ConnectUtilApp::new().await.unwrap();
+        let issues = app.lint_directory(repo.path().to_str().unwrap()).unwrap();
+        let missing: Vec<_> = issues
+            .iter()
+            .filter(|issue| issue.rule == LintRule::Policy)
+            .collect();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].rule, LintRule::Policy);
+        assert_eq!(missing[0].connector.as_deref(), Some("refunds"));
+        assert_eq!(missing[0].line, 1);
+
+        let unset: Vec<_> = issues
+            .iter()
+            .filter(|issue| issue.rule == LintRule::UnsetIgnoredKey)
+            .collect();
+        assert_eq!(unset.len(), 1);
+        assert_eq!(unset[0].connector.as_deref(), Some("refunds"));
+        assert!(unset[0]
+            .message
+            .contains("config_nonsensitive[\"tasks.max\"]"));
+    }
+
+    #[tokio::test]
+    async fn test_lint_directory_conflicts_anchor_to_connector() {
+        let dir = tempfile::tempdir().unwrap();
+        for label in ["orders", "orders_again"] {
+            std::fs::write(
+                dir.path().join(format!("{}.tf", label)),
+                format!(
+                    "\nresource \"confluent_connector\" \"{}\" {{\n  config_nonsensitive = {{\n    \"connector.class\" = \"DatagenSource\"\n    \"name\" = \"orders\"\n  }}\n}}\n",
+                    label
+                ),
+            )
+            .unwrap();
+        }
+        let app = ConnectUtilApp::new().await.unwrap();
+        let issues = app.lint_directory(dir.path().to_str().unwrap()).unwrap();
+        let conflict = issues
+            .iter()
+            .find(|issue| issue.rule == LintRule::Conflict)
+            .unwrap();
+        assert!(conflict.message.contains("'orders'"));
+        assert_eq!(conflict.line, 2);
+    }
+
+    #[tokio::test]
+    async fn test_validate_directory_reports_unparseable_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("broken.tf"), "resource \"x\" {").unwrap();
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let result = app.validate_directory(dir.path().to_str().unwrap()).await;
+        assert!(matches!(result, Err(ConnectUtilError::Config(_))));
+    }
+
+    #[tokio::test]
+    async fn test_validate_directory_not_found() {
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        assert!(app.validate_directory("/nonexistent/dir").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fix_variables() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("main.tf"),
+            r#"resource "confluent_connector" "orders" {
+  environment {
+    id = var.environment_id
+  }
+  config_sensitive = {
+    "database.password" = var.database_password
+  }
+  config_nonsensitive = {
+    "connector.class" = "PostgresCdcSourceV2"
+  }
+}
+"#,
+        )
+        .unwrap();
+        let variables = dir.path().join("variables.tf");
+        std::fs::write(&variables, "variable \"environment_id\" {}\n").unwrap();
+        let dir_str = dir.path().to_str().unwrap();
+
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        app.fix_variables(dir_str, true).await.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&variables).unwrap(),
+            "variable \"environment_id\" {}\n"
+        );
+
+        app.fix_variables(dir_str, false).await.unwrap();
+        let content = std::fs::read_to_string(&variables).unwrap();
+        assert!(content.starts_with("variable \"environment_id\" {}"));
+        assert!(content.contains("variable \"database_password\""));
+        assert!(content.contains("sensitive = true"));
+
+        // A second run has nothing left to add
+        app.fix_variables(dir_str, false).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&variables).unwrap(), content);
+    }
+
+    #[tokio::test]
+    async fn test_rename_topic_prefix() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("main.tf");
+        let original = "resource \"confluent_connector\" \"cdc\" {\n  config_nonsensitive = {\n    \"topic.prefix\" = \"orders\"\n  }\n}\n";
+        std::fs::write(&main, original).unwrap();
+        std::fs::write(dir.path().join("prod.tfvars"), "environment = \"prod\"\n").unwrap();
+        let dir_str = dir.path().to_str().unwrap();
+
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        app.rename_topic_prefix(dir_str, "orders", "sales", true)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&main).unwrap(), original);
+
+        app.rename_topic_prefix(dir_str, "orders", "sales", false)
+            .await
+            .unwrap();
+        assert!(std::fs::read_to_string(&main)
+            .unwrap()
+            .contains("\"topic.prefix\" = \"sales\""));
+    }
+
+    #[tokio::test]
+    async fn test_rename_topic_prefix_rejects_same_prefix() {
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let result = app.rename_topic_prefix(".", "orders", "orders", true).await;
+        assert!(matches!(result, Err(ConnectUtilError::UserInput(_))));
+    }
+
+    #[tokio::test]
+    async fn test_rename_connector_resource() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("main.tf");
+        let original = "resource \"confluent_connector\" \"orders\" {\n  config_nonsensitive = {\n    \"name\" = \"orders\"\n  }\n}\n";
+        std::fs::write(&main, original).unwrap();
+        let outputs = dir.path().join("outputs.tf");
+        std::fs::write(
+            &outputs,
+            "output \"connector_id\" {\n  value = confluent_connector.orders.id\n}\n",
+        )
+        .unwrap();
+        let dir_str = dir.path().to_str().unwrap();
+
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        app.rename_connector_resource(dir_str, "orders", "sales", true)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&main).unwrap(), original);
+
+        app.rename_connector_resource(dir_str, "orders", "sales", false)
+            .await
+            .unwrap();
+        let rewritten = std::fs::read_to_string(&main).unwrap();
+        assert!(rewritten.contains("resource \"confluent_connector\" \"sales\" {"));
+        assert!(rewritten.ends_with(
+            "}\n\nmoved {\n  from = confluent_connector.orders\n  to = confluent_connector.sales\n}\n"
+        ));
+        assert!(std::fs::read_to_string(&outputs)
+            .unwrap()
+            .contains("value = confluent_connector.sales.id"));
+
+        // The old label is gone and the new one now exists
+        for (old, new) in [("orders", "refunds"), ("refunds", "sales")] {
+            assert!(app
+                .rename_connector_resource(dir_str, old, new, true)
+                .await
+                .is_err());
+        }
+        let result = app
+            .rename_connector_resource(dir_str, "sales", "bad label", true)
+            .await;
+        assert!(matches!(result, Err(ConnectUtilError::UserInput(_))));
+    }
+
+    #[tokio::test]
+    async fn test_split_and_merge_terraform_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let big = dir.path().join("connectors.tf");
+        std::fs::write(
+            &big,
+            "variable \"db_password\" {\n  sensitive = true\n}\n\n# Orders CDC\nresource \"confluent_connector\" \"orders\" {\n  config_sensitive = {\n    \"database.password\" = var.db_password\n  }\n}\n\nresource \"confluent_connector\" \"refunds\" {\n}\n\noutput \"refunds_id\" {\n  value = confluent_connector.refunds.id\n}\n",
+        )
+        .unwrap();
+        let big_str = big.to_str().unwrap();
+        let mut app = ConnectUtilApp::new().await.unwrap();
+
+        app.split_terraform_file(big_str, false, true)
+            .await
+            .unwrap();
+        assert!(!dir.path().join("orders.tf").exists());
+
+        app.split_terraform_file(big_str, false, false)
+            .await
+            .unwrap();
+        let orders = std::fs::read_to_string(dir.path().join("orders.tf")).unwrap();
+        assert!(orders.starts_with("# Orders CDC\nresource \"confluent_connector\" \"orders\""));
+        assert!(orders.contains("var.db_password"));
+        let refunds = std::fs::read_to_string(dir.path().join("refunds.tf")).unwrap();
+        assert!(refunds.contains("output \"refunds_id\""));
+        assert_eq!(
+            std::fs::read_to_string(&big).unwrap(),
+            "variable \"db_password\" {\n  sensitive = true\n}\n"
+        );
+
+        // Connector files aren't overwritten without --force
+        std::fs::write(&big, "resource \"confluent_connector\" \"orders\" {}\n").unwrap();
+        assert!(app
+            .split_terraform_file(big_str, false, false)
+            .await
+            .is_err());
+        std::fs::remove_file(&big).unwrap();
+
+        let combined = dir.path().join("combined.tf");
+        let files: Vec<String> = ["orders.tf", "refunds.tf"]
+            .iter()
+            .map(|name| dir.path().join(name).to_string_lossy().to_string())
+            .collect();
+        app.merge_terraform_files(&files, combined.to_str().unwrap(), false, false)
+            .await
+            .unwrap();
+        let merged = std::fs::read_to_string(&combined).unwrap();
+        assert!(merged.starts_with(&orders));
+        assert!(merged.ends_with(&refunds));
+        assert!(app
+            .merge_terraform_files(&files, combined.to_str().unwrap(), false, false)
+            .await
+            .is_err());
+        assert!(app
+            .merge_terraform_files(&files[..1], combined.to_str().unwrap(), true, false)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_plan_check_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let plan = dir.path().join("plan.json");
+        std::fs::write(
+            &plan,
+            r#"{"resource_changes":[{"address":"confluent_connector.orders","type":"confluent_connector",
+                "name":"orders","change":{"actions":["update"],
+                "after":{"config_nonsensitive":{"connector.class":"PostgresCdcSourceV2"},"config_sensitive":{}}}}]}"#,
+        )
+        .unwrap();
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let result = app.plan_check(plan.to_str().unwrap()).await;
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("1 of 1 planned connector change(s) failed"));
+    }
+
+    #[tokio::test]
+    async fn test_plan_check_unknown_values_are_not_missing() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let connector = ConnectorDefinition::get_connector_by_name("DatagenSource").unwrap();
+        let mut config = HashMap::new();
+        config.insert("connector.class".to_string(), "DatagenSource".to_string());
+        let required: Vec<String> = connector
+            .required_configs
+            .iter()
+            .map(|f| f.name.clone())
+            .collect();
+        let change = PlannedConnector {
+            address: "confluent_connector.gen".to_string(),
+            actions: vec!["create".to_string()],
+            config: ConnectorConfig {
+                name: "gen".to_string(),
+                connector_class: "DatagenSource".to_string(),
+                config,
+                sensitive_config: HashMap::new(),
+            },
+            unknown_nonsensitive: UnknownValues {
+                keys: required,
+                entire_map: false,
+            },
+            unknown_sensitive: UnknownValues::default(),
+        };
+
+        let problems =
+            app.plan_change_problems(&change, &Policy::default(), Path::new("plan.json"));
+        assert!(problems.is_empty(), "{:?}", problems);
+    }
+
+    #[tokio::test]
+    async fn test_plan_check_unknown_sensitive_values_stay_sensitive() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let connector = ConnectorDefinition::get_connector_by_name("PostgresCdcSourceV2").unwrap();
+        let mut config: HashMap<String, String> = connector
+            .required_configs
+            .iter()
+            .filter(|field| !connector.sensitive_configs.contains(&field.name))
+            .map(|field| {
+                let value = field
+                    .valid_values
+                    .as_ref()
+                    .and_then(|values| values.first().cloned())
+                    .unwrap_or_else(|| "x".to_string());
+                (field.name.clone(), value)
+            })
+            .collect();
+        config.insert(
+            "connector.class".to_string(),
+            "PostgresCdcSourceV2".to_string(),
+        );
+        config.insert("kafka.auth.mode".to_string(), "KAFKA_API_KEY".to_string());
+        let mut change = PlannedConnector {
+            address: "confluent_connector.orders".to_string(),
+            actions: vec!["create".to_string()],
+            config: ConnectorConfig {
+                name: "orders".to_string(),
+                connector_class: "PostgresCdcSourceV2".to_string(),
+                config,
+                sensitive_config: HashMap::new(),
+            },
+            unknown_nonsensitive: UnknownValues::default(),
+            unknown_sensitive: UnknownValues {
+                keys: vec![
+                    "database.password".to_string(),
+                    "kafka.api.key".to_string(),
+                    "kafka.api.secret".to_string(),
+                ],
+                entire_map: false,
+            },
+        };
+        let policy = Policy::default();
+        let problems = app.plan_change_problems(&change, &policy, Path::new("plan.json"));
+        assert!(problems.is_empty(), "{:?}", problems);
+
+        // A wholly unknown map still holds the keys that belong in it
+        change.unknown_sensitive = UnknownValues {
+            keys: Vec::new(),
+            entire_map: true,
+        };
+        let problems = app.plan_change_problems(&change, &policy, Path::new("plan.json"));
+        assert!(problems.is_empty(), "{:?}", problems);
+    }
+
+    #[tokio::test]
+    async fn test_plan_check_no_connector_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let plan = dir.path().join("plan.json");
+        std::fs::write(&plan, r#"{"resource_changes":[]}"#).unwrap();
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        assert!(app.plan_check(plan.to_str().unwrap()).await.is_ok());
+    }
+
+    #[derive(Default)]
+    struct StubApi {
+        connectors: Vec<LiveConnector>,
+        offsets: ConnectorOffsets,
+        resets: std::sync::Mutex<Vec<(String, OffsetReset)>>,
+        schema: PluginSchema,
+        /// Schema requests in flight now, and the most seen at once
+        in_flight: std::sync::atomic::AtomicUsize,
+        peak_in_flight: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl ConnectApi for StubApi {
+        async fn list_connectors(
+            &self,
+            _environment_id: &str,
+            _cluster_id: &str,
+        ) -> Result<Vec<LiveConnector>, ConnectUtilError> {
+            Ok(self.connectors.clone())
+        }
+
+        async fn get_offsets(
+            &self,
+            _environment_id: &str,
+            _cluster_id: &str,
+            _connector: &str,
+        ) -> Result<ConnectorOffsets, ConnectUtilError> {
+            Ok(self.offsets.clone())
+        }
+
+        async fn plugin_versions(
+            &self,
+            _environment_id: &str,
+            _cluster_id: &str,
+        ) -> Result<HashMap<String, String>, ConnectUtilError> {
+            Ok(HashMap::from([(
+                "DatagenSource".to_string(),
+                "1.0".to_string(),
+            )]))
+        }
+
+        async fn plugin_schema(
+            &self,
+            _environment_id: &str,
+            _cluster_id: &str,
+            _connector_class: &str,
+            _etag: Option<&str>,
+        ) -> Result<SchemaFetch, ConnectUtilError> {
+            use std::sync::atomic::Ordering;
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(SchemaFetch::Fetched {
+                schema: self.schema.clone(),
+                etag: None,
+            })
+        }
+
+        async fn reset_offsets(
+            &self,
+            _environment_id: &str,
+            _cluster_id: &str,
+            connector: &str,
+            to: OffsetReset,
+        ) -> Result<(), ConnectUtilError> {
+            self.resets
+                .lock()
+                .unwrap()
+                .push((connector.to_string(), to));
+            Ok(())
+        }
+    }
+
+    fn live(name: &str, id: &str) -> LiveConnector {
+        LiveConnector {
+            name: name.to_string(),
+            id: Some(id.to_string()),
+            connector_class: None,
+            state: Some("RUNNING".to_string()),
+            config: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_find_orphans() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = dir.path().join("terraform.tfstate");
+        std::fs::write(
+            &state,
+            r#"{"resources":[{"mode":"managed","type":"confluent_connector","name":"a",
+                "instances":[{"attributes":{"id":"lcc-1","config_nonsensitive":{"name":"renamed"}}}]}]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("main.tf"),
+            r#"resource "confluent_connector" "b" {
+  config_nonsensitive = {
+    "connector.class" = "DatagenSource"
+    "name" = "from-config"
+  }
+}
+"#,
+        )
+        .unwrap();
+        let api = StubApi {
+            connectors: vec![
+                live("original", "lcc-1"),
+                live("from-config", "lcc-2"),
+                live("click-ops", "lcc-3"),
+            ],
+            ..Default::default()
+        };
+
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let orphans = app
+            .find_orphans(
+                &api,
+                "env-1",
+                "lkc-1",
+                Some(state.to_str().unwrap()),
+                Some(dir.path().to_str().unwrap()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(orphans, vec![live("click-ops", "lcc-3")]);
+    }
+
+    #[tokio::test]
+    async fn test_find_orphans_missing_state_reports_everything() {
+        let api = StubApi {
+            connectors: vec![live("a", "lcc-1")],
+            ..Default::default()
+        };
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let orphans = app
+            .find_orphans(&api, "env-1", "lkc-1", Some("/nonexistent.tfstate"), None)
+            .await
+            .unwrap();
+        assert_eq!(orphans.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_list_deployed_connectors() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("main.tf"),
+            r#"resource "confluent_connector" "orders" {
+  config_nonsensitive = {
+    "connector.class" = "DatagenSource"
+    "name" = "orders"
+  }
+}
+
+resource "confluent_connector" "refunds" {
+  config_nonsensitive = {
+    "connector.class" = "DatagenSource"
+    "name" = "refunds"
+  }
+}
+"#,
+        )
+        .unwrap();
+        let api = StubApi {
+            connectors: vec![live("orders", "lcc-1"), live("click-ops", "lcc-2")],
+            ..Default::default()
+        };
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let rows = app
+            .list_deployed_connectors(&api, "env-1", "lkc-1", dir.path().to_str().unwrap())
+            .await
+            .unwrap();
+        let summary: Vec<(&str, CoverageStatus)> = rows
+            .iter()
+            .map(|row| (row.name.as_str(), row.status))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("click-ops", CoverageStatus::Unmanaged),
+                ("orders", CoverageStatus::Managed),
+                ("refunds", CoverageStatus::Missing),
+            ]
+        );
+
+        assert!(app
+            .list_deployed_connectors(&api, "env-1", "lkc-1", "/nonexistent")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_health_report() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("main.tf"),
+            r#"resource "confluent_connector" "orders" {
+  config_nonsensitive = {
+    "connector.class" = "DatagenSource"
+    "name"            = "orders"
+    "tasks.max"       = "1"
+  }
+}
+
+resource "confluent_connector" "refunds" {
+  config_nonsensitive = {
+    "connector.class" = "DatagenSource"
+    "name"            = "refunds"
+  }
+}
+"#,
+        )
+        .unwrap();
+        let mut orders = live("orders", "lcc-1");
+        orders.config = HashMap::from([
+            ("connector.class".to_string(), "DatagenSource".to_string()),
+            ("name".to_string(), "orders".to_string()),
+            ("tasks.max".to_string(), "2".to_string()),
+        ]);
+        let api = StubApi {
+            connectors: vec![orders, live("click-ops", "lcc-2")],
+            ..Default::default()
+        };
+        let output = dir.path().join("health.html");
+        let debug_dir = dir.path().join("debug");
+        let mut app = ConnectUtilApp::new()
+            .await
+            .unwrap()
+            .with_debug_dir(Some(debug_dir.clone()));
+        let report = app
+            .health_report(
+                &api,
+                "env-1",
+                "lkc-1",
+                dir.path().to_str().unwrap(),
+                HealthFormat::Html,
+                Some(output.to_str().unwrap()),
+            )
+            .await
+            .unwrap();
+
+        let names: Vec<&str> = report
+            .connectors
+            .iter()
+            .map(|c| c.coverage.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["click-ops", "orders", "refunds"]);
+        let orders = &report.connectors[1];
+        assert_eq!(orders.drift.len(), 1);
+        assert_eq!(orders.drift[0].live.as_deref(), Some("2"));
+        // The missing environment and cluster blocks are reported per connector
+        assert!(!orders.findings.is_empty());
+        assert!(orders
+            .findings
+            .iter()
+            .all(|issue| issue.connector.as_deref() == Some("orders")));
+        assert!(report.connectors[0].findings.is_empty());
+        assert!(std::fs::read_to_string(&output)
+            .unwrap()
+            .contains("<h1>Connector health: env-1 / lkc-1</h1>"));
+        // Only the drifted connector is snapshotted
+        let mut saved: Vec<String> = std::fs::read_dir(&debug_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        saved.sort();
+        assert_eq!(
+            saved,
+            vec![
+                "orders.findings.txt",
+                "orders.live.json",
+                "orders.local.json"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_remote() {
+        let dir = tempfile::tempdir().unwrap();
+        for label in ["orders", "refunds"] {
+            std::fs::write(
+                dir.path().join(format!("{}.tf", label)),
+                format!(
+                    "resource \"confluent_connector\" \"{0}\" {{\n  config_nonsensitive = {{\n    \"connector.class\" = \"DatagenSource\"\n    \"name\" = \"{0}\"\n    \"kafka.topik\" = \"{0}\"\n  }}\n}}\n",
+                    label
+                ),
+            )
+            .unwrap();
+        }
+        let def = |name: &str| crate::cloud::PluginConfigDef {
+            name: name.to_string(),
+            required: false,
+            default_value: None,
+        };
+        let api = StubApi {
+            schema: PluginSchema {
+                configs: vec![def("connector.class"), def("name"), def("kafka.topic")],
+            },
+            ..Default::default()
+        };
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let cache = SchemaCache::new(None);
+        let findings = app
+            .validate_remote(
+                &api,
+                &cache,
+                "env-1",
+                "lkc-1",
+                dir.path().to_str().unwrap(),
+                4,
+            )
+            .await
+            .unwrap();
+        assert_eq!(findings, 2);
+        assert_eq!(cache.downloads(), 1);
+    }
+
+    /// Rejects `flush.size` and records what it was asked to validate
+    #[derive(Default)]
+    struct StubConnect {
+        validated: std::sync::Mutex<Vec<(String, BTreeMap<String, String>)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl ConnectRestApi for StubConnect {
+        fn url(&self) -> &str {
+            "http://connect:8083"
+        }
+
+        async fn validate_config(
+            &self,
+            connector_class: &str,
+            config: &BTreeMap<String, String>,
+        ) -> Result<Vec<crate::connect::FieldError>, ConnectUtilError> {
+            self.validated
+                .lock()
+                .unwrap()
+                .push((connector_class.to_string(), config.clone()));
+            Ok(config
+                .get("flush.size")
+                .filter(|size| *size == "0")
+                .map(|_| crate::connect::FieldError {
+                    key: "flush.size".to_string(),
+                    errors: vec!["Value must be at least 1".to_string()],
+                })
+                .into_iter()
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validate_connect() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("connectors.tf"),
+            r#"
+resource "confluent_connector" "archive" {
+  config_sensitive = {
+    "aws.secret.access.key" = "secret"
+  }
+  config_nonsensitive = {
+    "connector.class"    = "S3_SINK"
+    "name"               = "orders-archive"
+    "kafka.auth.mode"    = "SERVICE_ACCOUNT"
+    "input.data.format"  = "AVRO"
+    "flush.size"         = "0"
+  }
+}
+
+resource "confluent_connector" "tickets" {
+  config_nonsensitive = {
+    "connector.class" = "ZendeskSource"
+    "name"            = "tickets"
+  }
+}
+"#,
+        )
+        .unwrap();
+        let api = StubConnect::default();
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let findings = app
+            .validate_connect(&api, dir.path().to_str().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(findings, 1);
+
+        // Only the connector with a self-managed equivalent is sent, converted
+        let validated = api.validated.lock().unwrap();
+        assert_eq!(validated.len(), 1);
+        let (class, config) = &validated[0];
+        assert_eq!(class, "io.confluent.connect.s3.S3SinkConnector");
+        assert_eq!(config["aws.secret.access.key"], "secret");
+        assert_eq!(
+            config["value.converter"],
+            "io.confluent.connect.avro.AvroConverter"
+        );
+        assert!(!config.contains_key("kafka.auth.mode"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_remote_bounds_concurrency() {
+        let dir = tempfile::tempdir().unwrap();
+        for (label, class) in [
+            ("orders", "DatagenSource"),
+            ("refunds", "PostgresCdcSourceV2"),
+            ("archive", "S3_SINK"),
+            ("audit", "MySqlSource"),
+        ] {
+            std::fs::write(
+                dir.path().join(format!("{}.tf", label)),
+                format!(
+                    "resource \"confluent_connector\" \"{}\" {{\n  config_nonsensitive = {{\n    \"connector.class\" = \"{}\"\n  }}\n}}\n",
+                    label, class
+                ),
+            )
+            .unwrap();
+        }
+        let api = StubApi::default();
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let cache = SchemaCache::new(None);
+        app.validate_remote(
+            &api,
+            &cache,
+            "env-1",
+            "lkc-1",
+            dir.path().to_str().unwrap(),
+            2,
+        )
+        .await
+        .unwrap();
+        assert_eq!(cache.downloads(), 4);
+        assert_eq!(
+            api.peak_in_flight.load(std::sync::atomic::Ordering::SeqCst),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_show_and_reset_offsets() {
+        let offsets = crate::cloud::parse_offsets(
+            r#"{"offsets": [{"partition": {"kafka_topic": "orders", "kafka_partition": 0}, "offset": {"kafka_offset": 42}}]}"#,
+        )
+        .unwrap();
+        let api = StubApi {
+            offsets: offsets.clone(),
+            ..Default::default()
+        };
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        assert_eq!(
+            app.show_offsets(&api, "env-1", "lkc-1", "orders-sink")
+                .await
+                .unwrap(),
+            offsets
+        );
+
+        app.reset_offsets(
+            &api,
+            "env-1",
+            "lkc-1",
+            "orders-sink",
+            OffsetReset::Earliest,
+            true,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            *api.resets.lock().unwrap(),
+            vec![("orders-sink".to_string(), OffsetReset::Earliest)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_and_diff_catalog_bundles() {
+        let dir = tempfile::tempdir().unwrap();
+        let v1 = dir.path().join("v1.json");
+        let v2 = dir.path().join("v2.json");
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        app.export_catalog(Some(v1.to_str().unwrap()))
+            .await
+            .unwrap();
+
+        let mut bundle = CatalogBundle::from_json(&std::fs::read_to_string(&v1).unwrap()).unwrap();
+        assert_eq!(
+            bundle.connectors.len(),
+            ConnectorDefinition::get_all_connectors().len()
+        );
+        bundle.connectors.pop();
+        bundle.connectors[0].sensitive_configs.clear();
+        std::fs::write(&v2, bundle.to_json().unwrap()).unwrap();
+
+        app.diff_catalog_bundles(v1.to_str().unwrap(), v1.to_str().unwrap(), false)
+            .await
+            .unwrap();
+        app.diff_catalog_bundles(v1.to_str().unwrap(), v2.to_str().unwrap(), false)
+            .await
+            .unwrap();
+        app.diff_catalog_bundles(v1.to_str().unwrap(), v2.to_str().unwrap(), true)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_catalog_stats() {
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let stats = app.catalog_stats(None, false).await.unwrap();
+        assert_eq!(
+            stats.connectors,
+            ConnectorDefinition::get_all_connectors().len()
+        );
+        assert_eq!(stats.by_type.values().sum::<usize>(), stats.connectors);
+        assert_eq!(stats.by_family.values().sum::<usize>(), stats.connectors);
+        assert_eq!(stats.defaults.total, stats.fields);
+
+        // An exported bundle with a definition stripped of its fields reports it as thin
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("catalog.json");
+        let mut bundle = CatalogBundle::builtin();
+        bundle.connectors[0].required_configs.clear();
+        bundle.connectors[0].optional_configs.clear();
+        std::fs::write(&path, bundle.to_json().unwrap()).unwrap();
+        let stripped = app
+            .catalog_stats(Some(path.to_str().unwrap()), true)
+            .await
+            .unwrap();
+        assert_eq!(stripped.thin[0], bundle.connectors[0].name);
+        assert!(stripped.fields < stats.fields);
+        assert!(matches!(
+            app.catalog_stats(Some("/nonexistent/catalog.json"), false)
+                .await,
+            Err(ConnectUtilError::Config(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_enrich_catalog() {
+        let dir = tempfile::tempdir().unwrap();
+        let metadata = dir.path().join("metadata.json");
+        let output = dir.path().join("enriched.json");
+        std::fs::write(
+            &metadata,
+            r#"{"connectors": [
+              {"name": "DatagenSource", "fields": [{"name": "max.interval", "default": "500"}]},
+              {"name": "io.confluent.connect.s3.S3SinkConnector", "description": "Archive topics to S3"}
+            ]}"#,
+        )
+        .unwrap();
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let report = app
+            .enrich_catalog(
+                metadata.to_str().unwrap(),
+                None,
+                Some(output.to_str().unwrap()),
+                false,
+                false,
+            )
+            .await
+            .unwrap();
+        assert_eq!(report.applied.len(), 1);
+        assert_eq!(report.conflicts[0].connector, "S3_SINK");
+        assert!(!report.missing.is_empty());
+
+        let written = crate::definitions::load_definitions(&output).unwrap();
+        assert_eq!(written.len(), 1);
+        let field = written[0]
+            .optional_configs
+            .iter()
+            .find(|field| field.name == "max.interval")
+            .unwrap();
+        assert_eq!(field.default_value.as_deref(), Some("500"));
+
+        assert!(matches!(
+            app.enrich_catalog(metadata.to_str().unwrap(), None, Some("-"), false, true)
+                .await,
+            Err(ConnectUtilError::UserInput(_))
+        ));
+        std::fs::write(&metadata, "{}").unwrap();
+        assert!(matches!(
+            app.enrich_catalog(metadata.to_str().unwrap(), None, None, false, false)
+                .await,
+            Err(ConnectUtilError::Config(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_diff_catalog_bundles_missing_file() {
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let result = app
+            .diff_catalog_bundles("/nonexistent/v1.json", "/nonexistent/v2.json", true)
+            .await;
+        assert!(matches!(result, Err(ConnectUtilError::Config(_))));
+    }
+
+    #[tokio::test]
+    async fn test_scan_clean_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("main.tf"),
+            "variable \"database_password\" {}\n",
+        )
+        .unwrap();
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let result = app
+            .scan(dir.path().to_str().unwrap(), ReportFormat::Json)
+            .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_scan_fails_on_secrets() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("prod.tfvars"), "db_password = \"x\"\n").unwrap();
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let result = app
+            .scan(dir.path().to_str().unwrap(), ReportFormat::Text)
+            .await;
+        assert!(matches!(result, Err(ConnectUtilError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_explain() {
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let lines = app
+            .explain_lines("PostgresCdcSourceV2", "snapshot.mode")
+            .unwrap();
+        assert_eq!(lines[0], "snapshot.mode (PostgresCdcSourceV2)");
+        assert_eq!(lines[1], "  Snapshot mode for initial data capture");
+        assert!(lines.contains(&"  Type: string".to_string()));
+        assert!(lines.contains(&"  Required: no".to_string()));
+        assert!(lines.contains(&"  Default: initial (as generated)".to_string()));
+        assert!(lines.contains(&"  Valid values: initial, never, when_needed".to_string()));
+
+        let lines = app
+            .explain_lines("PostgresCdcSourceV2", "table.include.list")
+            .unwrap();
+        assert_eq!(lines.last().unwrap(), "  Related: table.exclude.list");
+        let lines = app
+            .explain_lines("PostgresCdcSourceV2", "database.password")
+            .unwrap();
+        assert!(lines.contains(&"  Sensitive: yes (goes in config_sensitive)".to_string()));
+        assert!(lines.contains(&"  Default: none".to_string()));
+
+        // Keys outside the catalog still explain what generate writes
+        let lines = app
+            .explain_lines("PostgresCdcSourceV2", "tasks.max")
+            .unwrap();
+        assert_eq!(lines[2], "  Default: 1 (as generated)");
+
+        let error = app
+            .explain_lines("PostgresCdcSourceV2", "snapshot")
+            .unwrap_err();
+        assert!(error.to_string().contains("similar fields: snapshot.mode"));
+        assert!(app.explain("NoSuchSource", "snapshot.mode").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_apply_order() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("topics.tf"),
+            "resource \"confluent_kafka_topic\" \"refunds\" {\n  topic_name = \"refunds\"\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("connectors.tf"),
+            r#"resource "confluent_connector" "archive" {
+  config_nonsensitive = {
+    "connector.class" = "S3_SINK"
+    "topics"          = "shop.public.orders,refunds"
+  }
+}
+
+resource "confluent_connector" "cdc" {
+  config_nonsensitive = {
+    "connector.class" = "PostgresCdcSourceV2"
+    "topic.prefix"    = "shop"
+  }
+}
+
+resource "confluent_connector" "refunds" {
+  config_nonsensitive = {
+    "connector.class" = "DatagenSource"
+    "kafka.topic"     = confluent_kafka_topic.refunds.topic_name
+  }
+}
+"#,
+        )
+        .unwrap();
+
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let root = dir.path().to_str().unwrap();
+        let orders = app.apply_order(root).unwrap();
+        assert_eq!(orders.len(), 1);
+        let steps: Vec<&str> = orders[0].steps.iter().map(|s| s.address.as_str()).collect();
+        assert_eq!(
+            steps,
+            vec![
+                "confluent_kafka_topic.refunds",
+                "confluent_connector.cdc",
+                "confluent_connector.refunds",
+                "confluent_connector.archive",
+            ]
+        );
+        assert_eq!(orders[0].patches.len(), 1);
+        assert_eq!(
+            orders[0].patches[0].depends_on,
+            vec![
+                "confluent_connector.cdc",
+                "confluent_connector.refunds",
+                "confluent_kafka_topic.refunds",
+            ]
+        );
+        let text = render_order(&orders, OrderFormat::Text).unwrap();
+        assert!(text.contains(
+            "  4. confluent_connector.archive (connectors.tf), after confluent_connector.cdc"
+        ));
+        assert!(text.contains(
+            "     depends_on = [confluent_connector.cdc, confluent_connector.refunds, confluent_kafka_topic.refunds]"
+        ));
+        assert!(app.order(root, OrderFormat::Json).await.is_ok());
+
+        // Once the sink waits on both sources, nothing is left to patch
+        let connectors = dir.path().join("connectors.tf");
+        let content = std::fs::read_to_string(&connectors).unwrap().replacen(
+            "    \"topics\"          = \"shop.public.orders,refunds\"\n  }\n",
+            "    \"topics\"          = \"shop.public.orders,refunds\"\n  }\n  depends_on = [confluent_connector.cdc, confluent_connector.refunds, confluent_kafka_topic.refunds]\n",
+            1,
+        );
+        std::fs::write(&connectors, content).unwrap();
+        assert!(app.apply_order(root).unwrap()[0].patches.is_empty());
+
+        assert!(matches!(
+            app.order("/nonexistent", OrderFormat::Text).await,
+            Err(ConnectUtilError::Config(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_selftest_catalog_round_trips() {
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let failures = app.selftest_failures(None).unwrap();
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+        assert!(app.selftest(Some("PostgresCdcSourceV2")).await.is_ok());
+        assert!(matches!(
+            app.selftest(Some("NoSuchConnector")).await,
+            Err(ConnectUtilError::Config(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_init() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "target/\n").unwrap();
+        let environments = vec!["dev".to_string(), "prod".to_string()];
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let root = dir.path().to_str().unwrap();
+
+        let written = app
+            .init(root, &environments, CiProvider::Github, false)
+            .await
+            .unwrap();
+        assert_eq!(written.len(), 6);
+        assert!(dir.path().join("environments/prod/.gitkeep").exists());
+        assert!(dir.path().join(".github/workflows/connectors.yml").exists());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(".gitignore")).unwrap(),
+            "target/\n"
+        );
+        assert!(Policy::load(&dir.path().join(Policy::FILE_NAME)).is_ok());
+
+        // The scaffolded repository passes the checks it wires into CI
+        assert!(app
+            .validate_directory(dir.path().join("environments").to_str().unwrap())
+            .await
+            .is_ok());
+        assert!(app.scan(root, ReportFormat::Text).await.is_ok());
+
+        let written = app
+            .init(root, &environments, CiProvider::None, true)
+            .await
+            .unwrap();
+        assert_eq!(written.len(), 6);
+        assert!(std::fs::read_to_string(dir.path().join(".gitignore"))
+            .unwrap()
+            .contains(".terraform/"));
+    }
+
+    /// Fixes connector names, suppresses label findings, and skips the rest
+    struct ScriptedReviewer {
+        asked: usize,
+    }
+
+    impl Reviewer for ScriptedReviewer {
+        fn choose(
+            &mut self,
+            issue: &LintIssue,
+            actions: &[ReviewAction],
+        ) -> Result<ReviewAction, ConnectUtilError> {
+            self.asked += 1;
+            Ok(match issue.rule {
+                LintRule::NamingConvention if issue.message.starts_with("Resource label") => {
+                    ReviewAction::Suppress
+                }
+                LintRule::NamingConvention | LintRule::UndeclaredVariable => {
+                    assert_eq!(actions[0], ReviewAction::Fix);
+                    ReviewAction::Fix
+                }
+                _ => ReviewAction::Skip,
+            })
+        }
+
+        fn reason(&mut self, _issue: &LintIssue) -> Result<String, ConnectUtilError> {
+            Ok("kept for the dashboards".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validate_interactive() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("main.tf");
+        std::fs::write(
+            &main,
+            r#"# Orders datagen
+resource "confluent_connector" "Orders" {
+  environment {
+    id = var.environment_id
+  }
+  kafka_cluster {
+    id = var.kafka_cluster_id
+  }
+  config_sensitive = {}
+  config_nonsensitive = {
+    "connector.class"    = "DatagenSource"
+    "name"                     = "Orders"
+    "kafka.auth.mode"          = "SERVICE_ACCOUNT"
+    "kafka.service.account.id" = "sa-123"
+    "kafka.topic"        = "orders"
+    "output.data.format" = "JSON"
+    "quickstart"         = "ORDERS"
+  }
+}
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("variables.tf"),
+            "variable \"environment_id\" {}\n\nvariable \"legacy\" {}\n",
+        )
+        .unwrap();
+
+        let mut app = ConnectUtilApp::new().await.unwrap().with_naming_rules(
+            NamingRules::new(Some("^(src|sink)-[a-z0-9-]+$"), Some("^[a-z][a-z0-9_]*$")).unwrap(),
+        );
+        let root = dir.path().to_str().unwrap();
+        let mut reviewer = ScriptedReviewer { asked: 0 };
+        let remaining = app
+            .validate_interactive(&mut reviewer, None, Some(root))
+            .await
+            .unwrap();
+        // The unused `legacy` variable was skipped
+        assert_eq!(remaining, 1);
+        assert_eq!(reviewer.asked, 4);
+
+        let content = std::fs::read_to_string(&main).unwrap();
+        assert!(content.contains("\"name\"                     = \"src-orders\""));
+        assert!(content.contains(
+            "# Orders datagen\n# connect-util:ignore naming_convention kept for the dashboards\nresource \"confluent_connector\" \"Orders\""
+        ));
+        let issues = app.lint_directory(root).unwrap();
+        assert!(issues
+            .iter()
+            .all(|issue| issue.rule == LintRule::UnusedVariable));
+        assert_eq!(issues.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_describe_connector() {
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let result = app.describe_connector("PostgresCdcSourceV2").await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_describe_connector_unknown() {
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let result = app.describe_connector("NotAConnector").await;
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Unknown connector"));
+    }
+
+    #[tokio::test]
+    async fn test_describe_connector_schema() {
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        assert!(app
+            .describe_connector_schema("PostgresCdcSourceV2")
+            .await
+            .is_ok());
+        assert!(matches!(
+            app.describe_connector_schema("NotAConnector").await,
+            Err(ConnectUtilError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_supported_formats() {
+        let s3 = ConnectorDefinition::get_connector_by_name("S3_SINK").unwrap();
+        assert_eq!(
+            s3.supported_formats("output.data.format"),
+            vec![DataFormat::Avro, DataFormat::Json, DataFormat::Parquet]
+        );
+        assert_eq!(
+            s3.supported_formats("input.data.format"),
+            vec![DataFormat::Avro, DataFormat::Json, DataFormat::Protobuf]
+        );
+        let datagen = ConnectorDefinition::get_connector_by_name("DatagenSource").unwrap();
+        assert!(!datagen
+            .supported_formats("output.data.format")
+            .contains(&DataFormat::Parquet));
+        assert_eq!("json-sr".parse(), Ok(DataFormat::JsonSr));
+        assert!("xml".parse::<DataFormat>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_generate_terraform_with_data_formats() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let options = |connector: &str, input, output| ConnectorOptions {
+            name: Some("orders".to_string()),
+            connector: Some(connector.to_string()),
+            input_format: input,
+            output_format: output,
+            ..Default::default()
         };
 
-        println!("Available connector plugins:");
-        for connector in filtered_connectors {
-            let connector_type_str = match connector.connector_type {
-                ConnectorType::Source => "source",
-                ConnectorType::Sink => "sink",
-            };
-            println!("  - {} ({})", connector.display_name, connector_type_str);
-            println!("    Class: {}", connector.connector_class);
-            println!("    Description: {}", connector.description);
-        }
+        let terraform = app
+            .generate_terraform_non_interactive(options(
+                "S3_SINK",
+                Some(DataFormat::Json),
+                Some(DataFormat::Parquet),
+            ))
+            .unwrap();
+        assert!(terraform.contains("\"input.data.format\" = \"JSON\""));
+        assert!(terraform.contains("\"output.data.format\" = \"PARQUET\""));
+
+        let terraform = app
+            .generate_terraform_non_interactive(options(
+                "DatagenSource",
+                None,
+                Some(DataFormat::Protobuf),
+            ))
+            .unwrap();
+        assert!(terraform.contains("\"output.data.format\" = \"PROTOBUF\""));
 
-        Ok(())
+        let error = app
+            .generate_terraform_non_interactive(options("S3_SINK", Some(DataFormat::JsonSr), None))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("doesn't support input.data.format JSON_SR"));
+        assert!(matches!(
+            app.generate_terraform_non_interactive(options(
+                "DatagenSource",
+                Some(DataFormat::Json),
+                None
+            )),
+            Err(ConnectUtilError::Validation(_))
+        ));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use hcl::Object;
+    #[test]
+    fn test_parse_topics() {
+        assert_eq!(
+            ConnectUtilApp::parse_topics(" orders, refunds.v1 ,"),
+            Ok(vec!["orders".to_string(), "refunds.v1".to_string()])
+        );
+        assert_eq!(ConnectUtilApp::parse_topics(""), Ok(vec![]));
+        assert!(ConnectUtilApp::parse_topics("orders topic")
+            .unwrap_err()
+            .contains("contains ' '"));
+        assert!(ConnectUtilApp::parse_topics("..").is_err());
+        assert!(ConnectUtilApp::parse_topics(&"a".repeat(250)).is_err());
+    }
 
     #[tokio::test]
-    async fn test_parse_terraform_config_success() {
+    async fn test_generate_terraform_with_topics() {
         let app = ConnectUtilApp::new().await.unwrap();
-        let terraform_content = r#"
-        module "test_connector" {
-          config_sensitive = {
-            "connection.password" = "secret_password"
-          }
-          config_nonsensitive = {
-            "connector.class" = "PostgresSink"
-            "connection.host" = "localhost"
-            "connection.port" = "5432"
-            "connection.user" = "test_user"
-            "db.name" = "test_db"
-          }
-        }
-        "#;
+        let options = |connector: &str, topics: &[&str]| ConnectorOptions {
+            name: Some("orders".to_string()),
+            connector: Some(connector.to_string()),
+            topics: topics.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        };
 
-        let result = app.parse_terraform_configs(terraform_content);
-        assert!(result.is_ok(), "Should parse valid Terraform config");
+        let cdc = app
+            .generate_terraform_non_interactive(options("PostgresCdcSourceV2", &["orders"]))
+            .unwrap();
+        assert!(cdc.contains("\"topic.prefix\" = \"orders\""));
+        assert!(!cdc.contains("<REPLACE_WITH_TOPIC_PREFIX>"));
 
-        let configs = result.unwrap();
-        assert!(!configs.is_empty(), "Should have at least one config");
-        let config = &configs[0];
-        assert_eq!(config.connector_class, "PostgresSink");
+        let datagen = app
+            .generate_terraform_non_interactive(options("DatagenSource", &["orders"]))
+            .unwrap();
+        assert!(datagen.contains("\"kafka.topic\" = \"orders\""));
+
+        let sink = app
+            .generate_terraform_non_interactive(options("S3_SINK", &["orders", "refunds"]))
+            .unwrap();
+        assert!(sink.contains("topics = join(\",\", [\"orders\", \"refunds\"])"));
+
+        for (connector, topics) in [
+            ("PostgresCdcSourceV2", &["orders", "refunds"][..]),
+            ("DatagenSource", &["orders", "refunds"][..]),
+            ("S3_SINK", &["bad topic"][..]),
+        ] {
+            assert!(matches!(
+                app.generate_terraform_non_interactive(options(connector, topics)),
+                Err(ConnectUtilError::UserInput(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_import_name() {
+        let options = |id: &str| ConnectorOptions {
+            import_name: Some(id.to_string()),
+            ..Default::default()
+        };
         assert_eq!(
-            config.config.get("connection.host"),
-            Some(&"localhost".to_string())
+            ConnectUtilApp::import_name(&options("orders-cdc")).unwrap(),
+            Some("orders-cdc".to_string())
         );
         assert_eq!(
-            config.config.get("connection.port"),
-            Some(&"5432".to_string())
+            ConnectUtilApp::import_name(&ConnectorOptions::default()).unwrap(),
+            None
         );
+        for id in ["lcc-abc123", "", "env-abc/lkc-abc/orders-cdc"] {
+            assert!(matches!(
+                ConnectUtilApp::import_name(&options(id)),
+                Err(ConnectUtilError::UserInput(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_connector_menu() {
+        let sources = ConnectorDefinition::get_connectors_by_type(&ConnectorType::Source);
+        let recent = vec![
+            "PostgresCdcSourceV2".to_string(),
+            "S3_SINK".to_string(),
+            "GitHubSource".to_string(),
+        ];
+        let menu = ConnectUtilApp::connector_menu(&sources, &recent);
+        let labels: Vec<&str> = menu.iter().map(|(label, _)| label.as_str()).collect();
+
+        // Recent connectors of another type are left out
+        assert_eq!(labels[0], "Recent: PostgreSQL CDC Source V2 (Debezium)");
+        assert_eq!(labels[1], "Recent: GitHub Source");
+        let ConnectorMenuEntry::Connector(index) = menu[1].1 else {
+            panic!("Expected a recent connector");
+        };
+        assert_eq!(sources[index].name, "GitHubSource");
+
+        let families: Vec<ConnectorFamily> = menu
+            .iter()
+            .filter_map(|(_, entry)| match entry {
+                ConnectorMenuEntry::Family(family) => Some(*family),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(families, ConnectorFamily::ALL);
+        let total: usize = labels[2..]
+            .iter()
+            .map(|label| {
+                let count = label.rsplit('(').next().unwrap().trim_end_matches(')');
+                count.parse::<usize>().unwrap()
+            })
+            .sum();
+        assert_eq!(total, sources.len());
+
+        let sinks = ConnectorDefinition::get_connectors_by_type(&ConnectorType::Sink);
+        let menu = ConnectUtilApp::connector_menu(&sinks, &[]);
+        assert!(!menu
+            .iter()
+            .any(|(_, entry)| *entry == ConnectorMenuEntry::Family(ConnectorFamily::Messaging)));
+    }
+
+    #[tokio::test]
+    async fn test_fields_by_group() {
+        let connector = ConnectorDefinition::get_connector_by_name("PostgresSink").unwrap();
+        let groups = connector.fields_by_group();
+        assert!(!groups.is_empty());
+
+        let field_count: usize = groups.iter().map(|(_, fields)| fields.len()).sum();
         assert_eq!(
-            config.sensitive_config.get("connection.password"),
-            Some(&"secret_password".to_string())
+            field_count,
+            connector.required_configs.len() + connector.optional_configs.len()
         );
+
+        // Groups come out in display order
+        let order: Vec<usize> = groups
+            .iter()
+            .map(|(g, _)| ConfigGroup::ALL.iter().position(|a| a == g).unwrap())
+            .collect();
+        let mut sorted = order.clone();
+        sorted.sort();
+        assert_eq!(order, sorted);
     }
 
     #[tokio::test]
-    async fn test_parse_terraform_config_with_comments() {
+    async fn test_new() {
+        let result = ConnectUtilApp::new().await;
+        assert!(result.is_ok());
+        let app = result.unwrap();
+        // Just verify we can create the app
+        assert!(matches!(app, ConnectUtilApp { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_generate_terraform_non_interactive() {
         let app = ConnectUtilApp::new().await.unwrap();
-        let terraform_content = r#"
-        module "test_connector" {
-          config_sensitive = {
-            "connection.password" = "secret_password"
-          }
-          config_nonsensitive = {
-            "connector.class" = "PostgresSink"
-            # This is a comment
-            "connection.host" = "localhost"
-            "connection.port" = "5432"
-            # Another comment
-            "connection.user" = "test_user"
-            "db.name" = "test_db"
-          }
-        }
-        "#;
+        let options = ConnectorOptions {
+            name: Some("test-connector".to_string()),
+            output: Some("test-output.tf".to_string()),
+            ..Default::default()
+        };
 
-        let result = app.parse_terraform_configs(terraform_content);
-        assert!(
-            result.is_ok(),
-            "Should parse Terraform config with comments"
-        );
+        // This test uses the non-interactive function
+        let result = app.generate_terraform_non_interactive(options);
+        assert!(result.is_ok());
 
-        let configs = result.unwrap();
-        assert!(!configs.is_empty(), "Should have at least one config");
-        let config = &configs[0];
-        assert_eq!(config.connector_class, "PostgresSink");
-        assert_eq!(
-            config.config.get("connection.host"),
-            Some(&"localhost".to_string())
-        );
-        assert_eq!(
-            config.config.get("connection.port"),
-            Some(&"5432".to_string())
-        );
-        assert_eq!(
-            config.sensitive_config.get("connection.password"),
-            Some(&"secret_password".to_string())
-        );
+        let terraform = result.unwrap();
+        assert!(terraform.contains("test-connector"));
+        assert!(terraform.contains("var.environment"));
     }
 
     #[tokio::test]
-    async fn test_parse_terraform_config_missing_connector_class() {
+    async fn test_generate_terraform_non_interactive_missing_required() {
         let app = ConnectUtilApp::new().await.unwrap();
-        let terraform_content = r#"
-        module "test_connector" {
-          config_sensitive = {
-            "connection.password" = "secret_password"
-          }
-          config_nonsensitive = {
-            "connection.host" = "localhost"
-            "connection.port" = "5432"
-            "connection.user" = "test_user"
-            "db.name" = "test_db"
-          }
+        let options = ConnectorOptions {
+            name: None, // Missing required field
+            output: Some("test-output.tf".to_string()),
+            ..Default::default()
+        };
+
+        // This should fail because required fields are missing
+        let result = app.generate_terraform_non_interactive(options);
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Connector name is required"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_terraform_non_interactive_with_connector() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let options = ConnectorOptions {
+            name: Some("orders-sink".to_string()),
+            connector: Some("PostgresSink".to_string()),
+            ..Default::default()
+        };
+
+        let terraform = app.generate_terraform_non_interactive(options).unwrap();
+        assert!(terraform.contains("\"connector.class\" = \"PostgresSink\""));
+    }
+
+    #[tokio::test]
+    async fn test_generate_terraform_non_interactive_unknown_connector() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let options = ConnectorOptions {
+            name: Some("orders-sink".to_string()),
+            connector: Some("NotAConnector".to_string()),
+            ..Default::default()
+        };
+
+        let result = app.generate_terraform_non_interactive(options);
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Unknown connector"));
+    }
+
+    #[tokio::test]
+    async fn test_emit_output_dry_run_does_not_write() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output = temp_dir.path().join("dry-run.tf");
+        let options = ConnectorOptions {
+            output: Some(output.to_str().unwrap().to_string()),
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let result = app.emit_output(&options, "resource {}");
+        assert!(result.is_ok());
+        assert!(!output.exists(), "Dry run must not touch the filesystem");
+
+        // An existing file is refused as it would be without --dry-run
+        std::fs::write(&output, "original").unwrap();
+        let error = app
+            .emit_output(&options, "resource {}")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("already exists"), "{}", error);
+        let forced = ConnectorOptions {
+            force: true,
+            ..options.clone()
+        };
+        assert!(app.emit_output(&forced, "resource {}").is_ok());
+        assert_eq!(std::fs::read_to_string(&output).unwrap(), "original");
+    }
+
+    #[tokio::test]
+    async fn test_emit_output_writes_file() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output = temp_dir.path().join("out.tf");
+        let options = ConnectorOptions {
+            output: Some(output.to_str().unwrap().to_string()),
+            ..Default::default()
+        };
+
+        app.emit_output(&options, "resource {}").unwrap();
+        assert_eq!(std::fs::read_to_string(&output).unwrap(), "resource {}");
+    }
+
+    #[tokio::test]
+    async fn test_emit_output_stdout() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let stdout = ConnectorOptions {
+            output: Some("-".to_string()),
+            ..Default::default()
+        };
+        app.emit_output(&stdout, "resource {}").unwrap();
+        assert!(!Path::new("-").exists());
+
+        for options in [
+            ConnectorOptions {
+                append: true,
+                ..stdout.clone()
+            },
+            ConnectorOptions {
+                merge: true,
+                ..stdout.clone()
+            },
+        ] {
+            let error = app
+                .emit_output(&options, "resource {}")
+                .unwrap_err()
+                .to_string();
+            assert!(error.contains("standard output can't be extended"));
         }
-        "#;
+    }
 
-        let result = app.parse_terraform_configs(terraform_content);
-        assert!(
-            result.is_ok(),
-            "Should parse even without connector.class (returns empty list)"
+    #[tokio::test]
+    async fn test_emit_output_refuses_overwrite() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output = temp_dir.path().join("existing.tf");
+        std::fs::write(&output, "original").unwrap();
+        let options = ConnectorOptions {
+            output: Some(output.to_str().unwrap().to_string()),
+            ..Default::default()
+        };
+
+        let result = app.emit_output(&options, "resource {}");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("--force"));
+        assert_eq!(std::fs::read_to_string(&output).unwrap(), "original");
+    }
+
+    #[tokio::test]
+    async fn test_emit_output_force_overwrites() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output = temp_dir.path().join("existing.tf");
+        std::fs::write(&output, "original").unwrap();
+        let options = ConnectorOptions {
+            output: Some(output.to_str().unwrap().to_string()),
+            force: true,
+            ..Default::default()
+        };
+
+        app.emit_output(&options, "resource {}").unwrap();
+        assert_eq!(std::fs::read_to_string(&output).unwrap(), "resource {}");
+    }
+
+    #[tokio::test]
+    async fn test_emit_output_newline() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output = temp_dir.path().join("crlf.tf");
+        let generated = app
+            .generate_terraform_non_interactive(ConnectorOptions {
+                name: Some("orders".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        let options = ConnectorOptions {
+            output: Some(output.to_str().unwrap().to_string()),
+            newline: Some(Newline::Crlf),
+            ..Default::default()
+        };
+        app.emit_output(&options, &generated).unwrap();
+        let content = std::fs::read_to_string(&output).unwrap();
+        assert_eq!(content, generated.replace('\n', "\r\n"));
+
+        // Without --newline, appending keeps the file's CRLF endings
+        let second = app
+            .generate_terraform_non_interactive(ConnectorOptions {
+                name: Some("refunds".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        let append = ConnectorOptions {
+            newline: None,
+            append: true,
+            ..options.clone()
+        };
+        app.emit_output(&append, &second).unwrap();
+        let content = std::fs::read_to_string(&output).unwrap();
+        assert!(content.contains("\"confluent_connector\" \"refunds\""));
+        assert!(!content.replace("\r\n", "").contains('\n'));
+
+        // --newline lf converts the whole file
+        let lf = ConnectorOptions {
+            newline: Some(Newline::Lf),
+            force: true,
+            ..options.clone()
+        };
+        app.emit_output(&lf, &content).unwrap();
+        assert!(!std::fs::read_to_string(&output).unwrap().contains('\r'));
+
+        // A drive or share root isn't a file to write to
+        let result = app.emit_output(
+            &ConnectorOptions {
+                output: Some("/".to_string()),
+                ..Default::default()
+            },
+            &generated,
         );
-        let configs = result.unwrap();
-        assert!(
-            configs.is_empty(),
-            "Should return empty list when connector.class is missing"
+        assert!(result.unwrap_err().to_string().contains("not a file"));
+    }
+
+    #[cfg(windows)]
+    #[tokio::test]
+    async fn test_emit_output_windows_paths() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        // Backslash separators under a drive letter, e.g. C:\Users\...\Temp\...
+        let output = format!(
+            r"{}\nested\main.tf",
+            temp_dir.path().to_str().unwrap().trim_end_matches('\\')
         );
+        std::fs::create_dir(temp_dir.path().join("nested")).unwrap();
+        let options = ConnectorOptions {
+            output: Some(output.clone()),
+            ..Default::default()
+        };
+        app.emit_output(&options, "resource {}\n").unwrap();
+        // Windows writes CRLF by default
+        assert_eq!(std::fs::read_to_string(&output).unwrap(), "resource {}\r\n");
+
+        // The same file through its verbatim UNC form, \\?\C:\...
+        let verbatim = std::fs::canonicalize(&output).unwrap();
+        assert!(verbatim.to_str().unwrap().starts_with(r"\\?\"));
+        let options = ConnectorOptions {
+            output: Some(verbatim.to_str().unwrap().to_string()),
+            force: true,
+            ..Default::default()
+        };
+        app.emit_output(&options, "resource {}\n").unwrap();
+        assert!(app
+            .emit_output(
+                &ConnectorOptions {
+                    output: Some(r"C:\".to_string()),
+                    ..Default::default()
+                },
+                "resource {}\n"
+            )
+            .unwrap_err()
+            .to_string()
+            .contains("not a file"));
+        assert!(app
+            .emit_output(
+                &ConnectorOptions {
+                    output: Some(r"\\server\share".to_string()),
+                    ..Default::default()
+                },
+                "resource {}\n"
+            )
+            .unwrap_err()
+            .to_string()
+            .contains("not a file"));
     }
 
     #[tokio::test]
-    async fn test_parse_terraform_config_entirely_commented() {
+    async fn test_emit_output_merge() {
         let app = ConnectUtilApp::new().await.unwrap();
-        let terraform_content = r#"
-        # module "test_connector" {
-        #   config_sensitive = {
-        #     "connection.password" = "secret_password"
-        #   }
-        #   config_nonsensitive = {
-        #     "connector.class" = "PostgresSink"
-        #     "connection.host" = "localhost"
-        #     "connection.port" = "5432"
-        #     "connection.user" = "test_user"
-        #     "db.name" = "test_db"
-        #   }
-        # }
-        "#;
-
-        let result = app.parse_terraform_configs(terraform_content);
-        assert!(
-            result.is_ok(),
-            "Should parse even when entire file is commented (returns empty list)"
-        );
-        let configs = result.unwrap();
-        assert!(
-            configs.is_empty(),
-            "Should return empty list when entire file is commented"
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output = temp_dir.path().join("connectors.tf");
+        let options = ConnectorOptions {
+            name: Some("orders".to_string()),
+            connector: Some("PostgresCdcSourceV2".to_string()),
+            ..Default::default()
+        };
+        let generated = app
+            .generate_terraform_non_interactive(options.clone())
+            .unwrap();
+        let edited = generated.replace(
+            "\"database.hostname\" = \"<REPLACE_WITH_DATABASE_HOST>\"",
+            "\"database.hostname\" = \"db.internal\"",
         );
+        std::fs::write(&output, format!("# managed by platform\n{}", edited)).unwrap();
+
+        let options = ConnectorOptions {
+            output: Some(output.to_str().unwrap().to_string()),
+            merge: true,
+            topics: vec!["orders".to_string()],
+            ..options
+        };
+        let regenerated = app
+            .generate_terraform_non_interactive(options.clone())
+            .unwrap();
+        app.emit_output(&options, &regenerated).unwrap();
+
+        let content = std::fs::read_to_string(&output).unwrap();
+        assert!(content.starts_with("# managed by platform\n"));
+        assert!(content.contains("\"database.hostname\" = \"db.internal\""));
+        assert!(content.contains("\"topic.prefix\" = \"orders\""));
+
+        // The file has to exist and hold the resource being regenerated
+        let missing = ConnectorOptions {
+            output: Some(
+                temp_dir
+                    .path()
+                    .join("missing.tf")
+                    .to_str()
+                    .unwrap()
+                    .to_string(),
+            ),
+            ..options.clone()
+        };
+        assert!(app.emit_output(&missing, &regenerated).is_err());
+        let other = app
+            .generate_terraform_non_interactive(ConnectorOptions {
+                name: Some("refunds".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(app.emit_output(&options, &other).is_err());
     }
 
     #[tokio::test]
-    async fn test_add_connector_specific_config_postgres_cdc() {
-        let _app = ConnectUtilApp::new().await.unwrap();
-        let mut config_obj = Object::new();
-        let connector_def =
-            ConnectorDefinition::get_connector_by_name("PostgresCdcSourceV2").unwrap();
+    async fn test_emit_output_append() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output = temp_dir.path().join("connectors.tf");
+        let first = app
+            .generate_terraform_non_interactive(ConnectorOptions {
+                name: Some("first".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        let second = app
+            .generate_terraform_non_interactive(ConnectorOptions {
+                name: Some("second".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        std::fs::write(&output, &first).unwrap();
 
-        let options = TerraformConfigOptions {
-            connector_name: "test".to_string(),
-            connector: connector_def.clone(),
-            topics: vec![],
-            input_data_format: None,
-            output_data_format: None,
+        let options = ConnectorOptions {
+            output: Some(output.to_str().unwrap().to_string()),
+            append: true,
+            ..Default::default()
         };
-        let result = TerraformGenerator::add_connector_specific_config_to_object(
-            &mut config_obj,
-            &connector_def,
-            &options,
-        );
-        assert!(result.is_ok(), "Should successfully add connector config");
+        app.emit_output(&options, &second).unwrap();
 
-        // Check that configuration was added to config object
-        assert!(config_obj.contains_key(&TerraformGenerator::make_object_key("database.sslmode")));
-        assert!(config_obj.contains_key(&TerraformGenerator::make_object_key("publication.name")));
-        assert!(config_obj.contains_key(&TerraformGenerator::make_object_key("snapshot.mode")));
+        let content = std::fs::read_to_string(&output).unwrap();
+        assert!(content.contains("\"confluent_connector\" \"first\""));
+        assert!(content.contains("\"confluent_connector\" \"second\""));
+
+        // Appending the same resource again is rejected
+        let result = app.emit_output(&options, &second);
+        assert!(result.is_err());
     }
 
     #[tokio::test]
-    async fn test_add_connector_specific_config_s3_sink() {
-        let _app = ConnectUtilApp::new().await.unwrap();
-        let mut config_obj = Object::new();
-        let connector_def = ConnectorDefinition::get_connector_by_name("S3_SINK").unwrap();
-
-        let options = TerraformConfigOptions {
-            connector_name: "test".to_string(),
-            connector: connector_def.clone(),
-            topics: vec![],
-            input_data_format: None,
-            output_data_format: None,
+    async fn test_render_output_files_single_with_outputs() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let options = ConnectorOptions {
+            name: Some("orders-sink".to_string()),
+            connector: Some("PostgresSink".to_string()),
+            with_outputs: true,
+            ..Default::default()
         };
-        let result = TerraformGenerator::add_connector_specific_config_to_object(
-            &mut config_obj,
-            &connector_def,
-            &options,
-        );
-        assert!(result.is_ok(), "Should successfully add connector config");
 
-        // Check that configuration was added to config object
-        assert!(config_obj.contains_key(&TerraformGenerator::make_object_key("s3.bucket.name")));
-        assert!(config_obj.contains_key(&TerraformGenerator::make_object_key("topics.dir")));
-        assert!(config_obj.contains_key(&TerraformGenerator::make_object_key("input.data.format")));
+        let terraform_options = app.build_terraform_options(&options).unwrap();
+        let files = app
+            .render_output_files(&options, terraform_options)
+            .unwrap();
+        assert_eq!(files.len(), 1);
+        let content = &files[0].content;
+        assert!(content.contains("resource \"confluent_connector\" \"orders_sink\""));
+        assert!(content.contains("output \"connector_id\""));
+        assert!(content.contains("value = confluent_connector.orders_sink.status"));
+        assert!(hcl::from_str::<Body>(content).is_ok());
     }
 
     #[tokio::test]
-    async fn test_add_connector_specific_config_unknown_connector() {
-        let _app = ConnectUtilApp::new().await.unwrap();
-        let mut config_obj = Object::new();
-        let connector_def = ConnectorDefinition {
-            name: "UnknownConnector".to_string(),
-            display_name: "Unknown Connector".to_string(),
-            connector_class: "UnknownConnector".to_string(),
-            connector_type: ConnectorType::Source,
-            description: "Unknown connector".to_string(),
-            required_configs: vec![],
-            optional_configs: vec![],
-            sensitive_configs: vec![],
+    async fn test_render_output_files_single_without_outputs() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let options = ConnectorOptions {
+            name: Some("orders-sink".to_string()),
+            ..Default::default()
         };
 
-        let options = TerraformConfigOptions {
-            connector_name: "test".to_string(),
-            connector: connector_def.clone(),
-            topics: vec![],
-            input_data_format: None,
-            output_data_format: None,
+        let terraform_options = app.build_terraform_options(&options).unwrap();
+        let files = app
+            .render_output_files(&options, terraform_options)
+            .unwrap();
+        assert!(!files[0].content.contains("output \""));
+    }
+
+    #[tokio::test]
+    async fn test_combine_files_for_several_connectors() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let first = ConnectorOptions {
+            name: Some("orders-sink".to_string()),
+            connector: Some("PostgresSink".to_string()),
+            output_dir: Some("connectors".to_string()),
+            layout: OutputLayout::Project,
+            topics: vec!["orders".to_string()],
+            ..Default::default()
         };
-        let result = TerraformGenerator::add_connector_specific_config_to_object(
-            &mut config_obj,
-            &connector_def,
-            &options,
+        let second = ConnectorOptions {
+            name: Some("refunds-sink".to_string()),
+            ..ConnectUtilApp::next_connector_options(&first)
+        };
+        assert!(second.topics.is_empty());
+        assert_eq!(second.output_dir.as_deref(), Some("connectors"));
+        assert!(ConnectUtilApp::offers_more_connectors(&first));
+        assert!(!ConnectUtilApp::offers_more_connectors(&ConnectorOptions {
+            merge: true,
+            ..first.clone()
+        }));
+
+        let render = |options: &ConnectorOptions| {
+            let terraform_options = app.build_terraform_options(options).unwrap();
+            app.render_output_files(options, terraform_options).unwrap()
+        };
+        let mut files = Vec::new();
+        ConnectUtilApp::combine_files(&mut files, render(&first)).unwrap();
+        ConnectUtilApp::combine_files(&mut files, render(&second)).unwrap();
+        assert_eq!(files.len(), 3);
+        let main = hcl::from_str::<Body>(&files[0].content).unwrap();
+        assert_eq!(
+            TerraformGenerator::resource_addresses(&main),
+            vec![
+                "confluent_connector.orders_sink",
+                "confluent_connector.refunds_sink"
+            ]
         );
-        assert!(result.is_ok(), "Should not panic for unknown connector");
-        // Unknown connectors should not add any config
-        assert!(
-            config_obj.is_empty(),
-            "Unknown connector should not add any config"
+        // Shared variables are declared once
+        assert_eq!(
+            files[1]
+                .content
+                .matches("variable \"environment_id\"")
+                .count(),
+            1
         );
+        assert!(files[2]
+            .content
+            .contains("confluent_connector.refunds_sink"));
+
+        let error = ConnectUtilApp::combine_files(&mut files, render(&second)).unwrap_err();
+        assert!(error.to_string().contains("already exists"));
     }
 
     #[tokio::test]
-    async fn test_environment_specific_terraform_generation() {
-        let _app = ConnectUtilApp::new().await.unwrap();
-        let connector = ConnectorDefinition::get_connector_by_name("PostgresSink").unwrap();
-
-        // Test production environment
-        let prod_options = TerraformConfigOptions {
-            connector_name: "test-connector".to_string(),
-            connector: connector.clone(),
-            topics: vec!["test-topic".to_string()],
-            input_data_format: None,
-            output_data_format: None,
+    async fn test_render_output_files_with_ksql_streams() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let options = |connector: &str, layout: OutputLayout| ConnectorOptions {
+            name: Some("orders-cdc".to_string()),
+            connector: Some(connector.to_string()),
+            output: Some("cdc/orders.tf".to_string()),
+            output_dir: Some("cdc".to_string()),
+            layout,
+            topics: vec!["shop".to_string()],
+            output_format: Some(DataFormat::Protobuf),
+            ksql_tables: vec!["public.orders".to_string()],
+            ..Default::default()
+        };
+        let render = |options: &ConnectorOptions| {
+            let terraform_options = app.build_terraform_options(options)?;
+            app.render_output_files(options, terraform_options)
         };
 
-        let generator = TerraformGenerator;
-        let prod_result = generator.generate_connector_config(prod_options);
-        assert!(prod_result.is_ok());
-        let prod_terraform = prod_result.unwrap();
+        let files = render(&options("PostgresCdcSourceV2", OutputLayout::Single)).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[1].path.as_deref(), Some("cdc/orders.sql"));
+        assert!(files[1].content.contains(
+            "CREATE STREAM IF NOT EXISTS public_orders\n  WITH (KAFKA_TOPIC = 'shop.public.orders', VALUE_FORMAT = 'PROTOBUF');"
+        ));
 
-        // Check that terraform is generated with resource format
-        assert!(prod_terraform.contains("resource \"confluent_connector\""));
-        assert!(prod_terraform.contains("status = var.status"));
-        assert!(prod_terraform.contains("environment {"));
-        assert!(prod_terraform.contains("kafka_cluster {"));
+        let files = render(&options("PostgresCdcSourceV2", OutputLayout::Project)).unwrap();
+        assert_eq!(files.len(), 4);
+        assert_eq!(
+            files[3].path.as_deref(),
+            Some(Path::new("cdc").join("streams.sql").to_str().unwrap())
+        );
 
-        // Test dev environment
-        let dev_options = TerraformConfigOptions {
-            connector_name: "test-connector".to_string(),
-            connector: connector.clone(),
-            topics: vec!["test-topic".to_string()],
-            input_data_format: None,
-            output_data_format: None,
+        let error = render(&options("DatagenSource", OutputLayout::Single)).unwrap_err();
+        assert!(error.to_string().contains("needs a CDC source"));
+    }
+
+    #[tokio::test]
+    async fn test_render_output_files_self_managed() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let options = |target: GenerateTarget| ConnectorOptions {
+            name: Some("orders-archive".to_string()),
+            connector: Some("S3_SINK".to_string()),
+            output: Some("orders-archive.json".to_string()),
+            topics: vec!["orders".to_string()],
+            input_format: Some(DataFormat::Json),
+            target,
+            ..Default::default()
+        };
+        let render = |options: &ConnectorOptions| {
+            let terraform_options = app.build_terraform_options(options)?;
+            app.render_output_files(options, terraform_options)
         };
 
-        let dev_result = generator.generate_connector_config(dev_options);
-        assert!(dev_result.is_ok());
-        let dev_terraform = dev_result.unwrap();
+        let files = render(&options(GenerateTarget::SelfManaged)).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path.as_deref(), Some("orders-archive.json"));
+        let payload: serde_json::Value = serde_json::from_str(&files[0].content).unwrap();
+        assert_eq!(payload["name"], "orders-archive");
+        assert_eq!(
+            payload["config"]["connector.class"],
+            "io.confluent.connect.s3.S3SinkConnector"
+        );
+        assert_eq!(payload["config"]["topics"], "orders");
+        assert_eq!(
+            payload["config"]["value.converter"],
+            "org.apache.kafka.connect.json.JsonConverter"
+        );
+        assert!(payload["config"].get("kafka.auth.mode").is_none());
+        assert!(payload["config"].get("input.data.format").is_none());
+
+        for (provider, resource) in [
+            (TerraformProvider::KafkaConnect, "kafka_connect_connector"),
+            (TerraformProvider::Aiven, "aiven_kafka_connector"),
+            (TerraformProvider::Instaclustr, "kafka_connect_connector"),
+        ] {
+            let files = render(&ConnectorOptions {
+                provider,
+                ..options(GenerateTarget::Cloud)
+            })
+            .unwrap();
+            assert!(files[0]
+                .content
+                .contains(&format!("resource \"{}\" \"orders_archive\"", resource)));
+            assert!(files[0]
+                .content
+                .contains("io.confluent.connect.s3.S3SinkConnector"));
+            assert!(!files[0].content.contains("confluent_connector"));
+        }
 
-        // Check dev-specific values
-        assert!(dev_terraform.contains("resource \"confluent_connector\""));
-        assert!(dev_terraform.contains("status = var.status"));
-        assert!(dev_terraform.contains("environment {"));
-        assert!(dev_terraform.contains("kafka_cluster {"));
+        let with_topics = ConnectorOptions {
+            companions: CompanionResources {
+                topics: true,
+                ..Default::default()
+            },
+            ..options(GenerateTarget::SelfManaged)
+        };
+        let error = render(&with_topics).unwrap_err();
+        assert!(error.to_string().contains("--with-topics"));
+
+        let owned = ConnectorOptions {
+            provider: TerraformProvider::KafkaConnect,
+            metadata: ConnectorMetadata {
+                owner: Some("team-orders".to_string()),
+                ..Default::default()
+            },
+            ..options(GenerateTarget::Cloud)
+        };
+        assert!(render(&owned).unwrap()[0]
+            .content
+            .contains("# connect-util:owner team-orders\nresource \"kafka_connect_connector\""));
+        let cloud = ConnectorOptions {
+            provider: TerraformProvider::Confluent,
+            output: Some("orders-archive.tf".to_string()),
+            ..owned
+        };
+        assert!(render(&cloud).unwrap()[0]
+            .content
+            .contains("# connect-util:owner team-orders\nresource \"confluent_connector\""));
+
+        // The self-managed class comes from the catalog aliases; AlloyDB has none
+        let error = render(&ConnectorOptions {
+            connector: Some("AlloyDBSink".to_string()),
+            ..options(GenerateTarget::SelfManaged)
+        })
+        .unwrap_err();
+        assert!(error.to_string().contains("No self-managed equivalent"));
     }
 
     #[tokio::test]
-    async fn test_terraform_structure_validation() {
-        let app = ConnectUtilApp::new().await.unwrap();
+    async fn test_generate_terraform_non_interactive_naming_violation() {
+        let app = ConnectUtilApp::new().await.unwrap().with_naming_rules(
+            NamingRules::new(Some("^(src|sink)-[a-z0-9-]+$"), Some("^[a-z][a-z0-9_]*$")).unwrap(),
+        );
+        let options = ConnectorOptions {
+            name: Some("Orders_CDC".to_string()),
+            connector: Some("PostgresCdcSourceV2".to_string()),
+            ..Default::default()
+        };
 
-        // Test resource-based Terraform validation
-        let terraform = r#"
-resource "confluent_connector" "test_connector" {
-  status = "RUNNING"
+        let result = app.generate_terraform_non_interactive(options);
+        assert!(result.is_err());
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("^(src|sink)-[a-z0-9-]+$"));
+        assert!(error.contains("suggested: 'src-orders-cdc'"));
+        assert!(error.contains("suggested: 'orders_cdc'"));
+    }
 
-  environment {
-    id = var.environment_id
-  }
+    #[tokio::test]
+    async fn test_generate_terraform_non_interactive_naming_compliant() {
+        let app = ConnectUtilApp::new().await.unwrap().with_naming_rules(
+            NamingRules::new(Some("^(src|sink)-[a-z0-9-]+$"), Some("^[a-z][a-z0-9_]*$")).unwrap(),
+        );
+        let options = ConnectorOptions {
+            name: Some("src-orders".to_string()),
+            connector: Some("PostgresCdcSourceV2".to_string()),
+            ..Default::default()
+        };
 
-  kafka_cluster {
-    id = var.kafka_cluster.id
-  }
+        let terraform = app.generate_terraform_non_interactive(options).unwrap();
+        assert!(terraform.contains("resource \"confluent_connector\" \"src_orders\""));
+    }
 
-  config_sensitive = {
-    "connection.password" = "secret"
-  }
+    #[tokio::test]
+    async fn test_validate_connector_reports_naming_violations() {
+        use std::io::Write;
+        let app_rules =
+            NamingRules::new(Some("^(src|sink)-[a-z0-9-]+$"), Some("^[a-z][a-z0-9_]*$")).unwrap();
+        let mut app = ConnectUtilApp::new()
+            .await
+            .unwrap()
+            .with_naming_rules(app_rules);
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            file,
+            "{}",
+            app.generate_terraform_non_interactive(ConnectorOptions {
+                name: Some("src-orders".to_string()),
+                connector: Some("PostgresCdcSourceV2".to_string()),
+                ..Default::default()
+            })
+            .unwrap()
+            .replace("\"src_orders\"", "\"SrcOrders\"")
+        )
+        .unwrap();
 
-  config_nonsensitive = {
-    "connector.class" = "PostgresSink"
-    "name" = "test-connector"
-  }
+        // Naming violations fail validation, naming the expected pattern
+        let error = app
+            .validate_connector(file.path().to_str().unwrap())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ConnectUtilError::Validation(_)));
+        assert!(error
+            .to_string()
+            .contains("1 naming convention violation(s)"));
+        let violations = app.naming_violations("src-orders", "SrcOrders", &ConnectorType::Source);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].contains("^[a-z][a-z0-9_]*$"));
+    }
 
-  lifecycle {
-    ignore_changes = [
-      config_nonsensitive["kafka.deployment.type"],
-    ]
-  }
-}
-"#;
+    #[tokio::test]
+    async fn test_validate_connector_with_policy_file() {
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::create_dir(repo.path().join(".git")).unwrap();
+        std::fs::write(
+            repo.path().join(Policy::FILE_NAME),
+            "allowed_connector_classes: [S3_SINK]\n",
+        )
+        .unwrap();
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let terraform = app
+            .generate_terraform_non_interactive(ConnectorOptions {
+                name: Some("orders".to_string()),
+                connector: Some("PostgresCdcSourceV2".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        let file = repo.path().join("orders.tf");
+        std::fs::write(&file, terraform).unwrap();
 
-        let result = app.validate_terraform_structure(terraform);
-        assert!(
-            result.is_ok(),
-            "Resource-based Terraform structure should be valid"
-        );
+        // Policy violations fail the run, so CI can enforce the policy file
+        let error = app
+            .validate_connector(file.to_str().unwrap())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ConnectUtilError::Validation(_)));
+        assert!(error.to_string().contains("1 policy violation(s)"));
 
-        // Test invalid Terraform (missing required blocks)
-        let invalid_terraform = r#"
-resource "confluent_connector" "test_connector" {
-  status = "RUNNING"
-  # Missing environment and kafka_cluster blocks
-  config_sensitive = {
-    "connection.password" = "secret"
-  }
-  config_nonsensitive = {
-    "connector.class" = "PostgresSink"
-    "name" = "test-connector"
-  }
-}
-"#;
+        std::fs::write(
+            repo.path().join(Policy::FILE_NAME),
+            "denied_connector_classes: [PostgresCdcSourceV2]\n",
+        )
+        .unwrap();
+        assert!(app
+            .validate_connector(file.to_str().unwrap())
+            .await
+            .is_err());
+
+        // Unless the policy file turns the rule down to a warning
+        std::fs::write(
+            repo.path().join(Policy::FILE_NAME),
+            "denied_connector_classes: [PostgresCdcSourceV2]\nseverities:\n  policy: warning\n",
+        )
+        .unwrap();
+        assert!(app.validate_connector(file.to_str().unwrap()).await.is_ok());
+    }
 
-        let result = app.validate_terraform_structure(invalid_terraform);
-        assert!(
-            result.is_err(),
-            "Invalid Terraform structure should fail validation"
-        );
+    #[tokio::test]
+    async fn test_validate_connector_with_invalid_policy_file() {
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::create_dir(repo.path().join(".git")).unwrap();
+        std::fs::write(repo.path().join(Policy::FILE_NAME), "max_tasks: many\n").unwrap();
+        let mut app = ConnectUtilApp::new().await.unwrap();
+        let terraform = app
+            .generate_terraform_non_interactive(ConnectorOptions {
+                name: Some("orders".to_string()),
+                connector: Some("PostgresCdcSourceV2".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        let file = repo.path().join("orders.tf");
+        std::fs::write(&file, terraform).unwrap();
+
+        let result = app.validate_connector(file.to_str().unwrap()).await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid policy file"));
     }
 
     #[tokio::test]
-    async fn test_validate_terraform_structure_with_modules() {
+    async fn test_generate_terraform_non_interactive_denied_by_policy() {
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::create_dir(repo.path().join(".git")).unwrap();
+        std::fs::write(
+            repo.path().join(Policy::FILE_NAME),
+            "overrides:\n  - path: prod\n    denied_connector_classes: [DatagenSource]\n",
+        )
+        .unwrap();
         let app = ConnectUtilApp::new().await.unwrap();
+        let options = |dir: &str| ConnectorOptions {
+            name: Some("orders".to_string()),
+            connector: Some("DatagenSource".to_string()),
+            output: Some(
+                repo.path()
+                    .join(dir)
+                    .join("orders.tf")
+                    .to_string_lossy()
+                    .to_string(),
+            ),
+            ..Default::default()
+        };
 
-        // Test module-based Terraform validation
-        let terraform = r#"
-module "test-connector" {
-  source = "../../modules/connector"
-
-  status = "RUNNING"
-  environment = var.environment
-  environment_id = var.environment_id
-  kafka_cluster = local.cluster
+        let error = app
+            .generate_terraform_non_interactive(options("prod"))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("'DatagenSource' is denied for prod/orders.tf"));
+        assert!(app
+            .generate_terraform_non_interactive(options("dev"))
+            .is_ok());
+    }
 
-  config_sensitive = {
-    "database.password" = "secret"
-  }
+    #[tokio::test]
+    async fn test_generate_terraform_ignore_changes_from_policy() {
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::create_dir(repo.path().join(".git")).unwrap();
+        std::fs::write(
+            repo.path().join(Policy::FILE_NAME),
+            "ignore_changes:\n  - status\nrequired_ignore_changes:\n  - config_nonsensitive[\"kafka.deployment.type\"]\n",
+        )
+        .unwrap();
+        let app = ConnectUtilApp::new().await.unwrap();
+        let output = repo.path().join("orders.tf");
+        let options = |ignore_changes: Vec<String>| ConnectorOptions {
+            name: Some("orders".to_string()),
+            connector: Some("DatagenSource".to_string()),
+            output: Some(output.to_string_lossy().to_string()),
+            ignore_changes,
+            ..Default::default()
+        };
 
-  config_nonsensitive = {
-    "connector.class" = "MySqlCdcSourceV2"
-    "name" = "test-connector"
-  }
-}
-"#;
+        let content = app
+            .generate_terraform_non_interactive(options(vec![]))
+            .unwrap();
+        assert!(content.contains("\"status\""));
+        assert!(content.contains("kafka.deployment.type"));
+        assert!(!content.contains("kafka.max.partition.validation"));
 
-        let result = app.validate_terraform_structure(terraform);
-        assert!(
-            result.is_ok(),
-            "Module-based Terraform structure should be valid"
-        );
+        let content = app
+            .generate_terraform_non_interactive(options(vec!["tasks_max".to_string()]))
+            .unwrap();
+        assert!(content.contains("\"tasks_max\""));
+        assert!(!content.contains("\"status\""));
+        assert!(content.contains("kafka.deployment.type"));
     }
 
     #[tokio::test]
-    async fn test_validate_terraform_structure_mixed_resources_and_modules() {
+    async fn test_generate_terraform_expands_policy_templates() {
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::create_dir(repo.path().join(".git")).unwrap();
+        std::fs::write(
+            repo.path().join(Policy::FILE_NAME),
+            "tokens:\n  team: payments\ntemplates:\n  name: \"{{team}}-{{env}}-{{connector}}\"\n  topics.dir: \"{{team}}/{{connector}}\"\nmetadata:\n  confluent.custom.tags: \"team={{team}}\"\n",
+        )
+        .unwrap();
         let app = ConnectUtilApp::new().await.unwrap();
+        let options = |tokens: &[(&str, &str)]| ConnectorOptions {
+            name: Some("orders".to_string()),
+            connector: Some("DatagenSource".to_string()),
+            output: Some(repo.path().join("orders.tf").to_string_lossy().to_string()),
+            tokens: tokens
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            ..Default::default()
+        };
 
-        // Test mixed resource and module blocks
-        let terraform = r#"
-resource "confluent_connector" "test_resource" {
-  status = "RUNNING"
-  environment {
-    id = var.environment_id
-  }
-  kafka_cluster {
-    id = var.kafka_cluster.id
-  }
-  config_sensitive = {}
-  config_nonsensitive = {
-    "connector.class" = "PostgresSink"
-  }
-}
+        let content = app
+            .generate_terraform_non_interactive(options(&[("env", "prod")]))
+            .unwrap();
+        assert!(content.contains("resource \"confluent_connector\" \"payments_prod_orders\""));
+        assert!(content.contains("name = \"payments-prod-orders\""));
+        assert!(content.contains("\"topics.dir\" = \"payments/orders\""));
+        assert!(content.contains("\"confluent.custom.tags\" = \"team=payments\""));
 
-module "test_module" {
-  source = "../../modules/connector"
-  status = "RUNNING"
-  environment = var.environment
-  kafka_cluster = local.cluster
-  config_sensitive = {}
-  config_nonsensitive = {
-    "connector.class" = "MySqlCdcSourceV2"
-  }
-}
-"#;
+        let content = app
+            .generate_terraform_non_interactive(options(&[("env", "dev"), ("team", "risk")]))
+            .unwrap();
+        assert!(content.contains("name = \"risk-dev-orders\""));
 
-        let result = app.validate_terraform_structure(terraform);
-        assert!(
-            result.is_ok(),
-            "Mixed resource and module blocks should be valid"
-        );
+        let error = app
+            .generate_terraform_non_interactive(options(&[]))
+            .unwrap_err();
+        assert!(error.to_string().contains("--token env="));
     }
 
     #[tokio::test]
-    async fn test_validate_resource_block_missing_status() {
+    async fn test_generation_report_traces_value_sources() {
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::create_dir(repo.path().join(".git")).unwrap();
+        std::fs::write(
+            repo.path().join(Policy::FILE_NAME),
+            "templates:\n  database.hostname: \"db.{{connector}}.internal\"\n",
+        )
+        .unwrap();
         let app = ConnectUtilApp::new().await.unwrap();
-        let terraform = r#"
-resource "confluent_connector" "test" {
-  environment {
-    id = var.environment_id
-  }
-  kafka_cluster {
-    id = var.kafka_cluster.id
-  }
-  config_sensitive = {}
-  config_nonsensitive = {
-    "connector.class" = "PostgresSink"
-  }
-}
-"#;
-        let body: Body = hcl::from_str(terraform).unwrap();
-        let resource_block = body
-            .blocks()
-            .find(|b| {
-                b.identifier() == "resource"
-                    && b.labels().len() >= 2
-                    && b.labels()[0].as_str() == "confluent_connector"
-            })
-            .unwrap();
+        let report_path = repo.path().join("report.json");
+        let options = ConnectorOptions {
+            name: Some("orders".to_string()),
+            connector: Some("PostgresCdcSourceV2".to_string()),
+            output: Some(repo.path().join("orders.tf").to_string_lossy().to_string()),
+            topics: vec!["orders".to_string()],
+            report: Some(report_path.to_string_lossy().to_string()),
+            ..Default::default()
+        };
 
-        let result = app.validate_resource_block(resource_block.body(), "test");
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("missing 'status' field"));
+        let terraform_options = app.build_terraform_options(&options).unwrap();
+        let prompted = BTreeSet::from(["topic.prefix".to_string()]);
+        let report = app
+            .trace_values(&options, &terraform_options, &prompted)
+            .unwrap();
+        let files = app
+            .render_output_files(&options, terraform_options)
+            .unwrap();
+        app.report_values(&options, report, &files).unwrap();
+
+        let report: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&report_path).unwrap()).unwrap();
+        assert_eq!(report["connector"], "PostgresCdcSourceV2");
+        assert_eq!(report["files"][0], options.output.clone().unwrap());
+        let source = |key: &str| {
+            report["values"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|entry| entry["key"] == key)
+                .map(|entry| entry["source"].as_str().unwrap().to_string())
+        };
+        assert_eq!(source("database.hostname").as_deref(), Some("policy"));
+        assert_eq!(source("topic.prefix").as_deref(), Some("prompt"));
+        assert_eq!(source("name").as_deref(), Some("flag"));
+        assert_eq!(source("database.port").as_deref(), Some("catalog"));
+        assert_eq!(source("tasks.max").as_deref(), Some("default"));
+        assert_eq!(source("database.password").as_deref(), Some("default"));
+
+        // Without --verbose or --report nothing is traced
+        let options = ConnectorOptions {
+            report: None,
+            ..options
+        };
+        let terraform_options = app.build_terraform_options(&options).unwrap();
+        assert!(app
+            .trace_values(&options, &terraform_options, &prompted)
+            .unwrap()
+            .is_none());
     }
 
     #[tokio::test]
-    async fn test_validate_resource_block_missing_environment() {
+    async fn test_generate_terraform_with_environment_profile() {
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::create_dir(repo.path().join(".git")).unwrap();
+        std::fs::write(
+            repo.path().join(Policy::FILE_NAME),
+            "environments:\n  prod:\n    id: env-prod1\n    cluster_id: var.prod_cluster_id\n",
+        )
+        .unwrap();
         let app = ConnectUtilApp::new().await.unwrap();
-        let terraform = r#"
-resource "confluent_connector" "test" {
-  status = "RUNNING"
-  kafka_cluster {
-    id = var.kafka_cluster.id
-  }
-  config_sensitive = {}
-  config_nonsensitive = {
-    "connector.class" = "PostgresSink"
-  }
-}
-"#;
-        let body: Body = hcl::from_str(terraform).unwrap();
-        let resource_block = body
-            .blocks()
-            .find(|b| {
-                b.identifier() == "resource"
-                    && b.labels().len() >= 2
-                    && b.labels()[0].as_str() == "confluent_connector"
-            })
+        let options = |environment: &str| ConnectorOptions {
+            name: Some("orders".to_string()),
+            connector: Some("DatagenSource".to_string()),
+            layout: OutputLayout::Project,
+            output_dir: Some(repo.path().join("prod").to_string_lossy().to_string()),
+            environment: Some(environment.to_string()),
+            ..Default::default()
+        };
+
+        let terraform_options = app.build_terraform_options(&options("prod")).unwrap();
+        let files = app
+            .render_output_files(&options("prod"), terraform_options)
             .unwrap();
+        assert!(files[0].content.contains("id = \"env-prod1\""));
+        assert!(files[0].content.contains("id = var.prod_cluster_id"));
+        assert!(files[1].content.contains("variable \"prod_cluster_id\""));
+        assert!(!files[1].content.contains("variable \"environment_id\""));
 
-        let result = app.validate_resource_block(resource_block.body(), "test");
-        assert!(result.is_err());
-        assert!(result
+        let error = app
+            .generate_terraform_non_interactive(options("staging"))
             .unwrap_err()
-            .to_string()
-            .contains("missing 'environment"));
+            .to_string();
+        assert!(error.contains("Unknown environment 'staging' (defined: prod)"));
+
+        let elsewhere = tempfile::tempdir().unwrap();
+        std::fs::create_dir(elsewhere.path().join(".git")).unwrap();
+        let error = app
+            .generate_terraform_non_interactive(ConnectorOptions {
+                output: Some(
+                    elsewhere
+                        .path()
+                        .join("orders.tf")
+                        .to_string_lossy()
+                        .to_string(),
+                ),
+                layout: OutputLayout::Single,
+                output_dir: None,
+                ..options("prod")
+            })
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("no .connect-util.yaml was found"));
     }
 
     #[tokio::test]
-    async fn test_validate_resource_block_environment_wrong_attribute() {
+    async fn test_discovered_environment() {
+        let environment = CloudEnvironment {
+            id: "env-abc123".to_string(),
+            display_name: "prod".to_string(),
+        };
+        let cluster = KafkaCluster {
+            id: "lkc-def456".to_string(),
+            display_name: "orders".to_string(),
+            location: "AWS us-east-1".to_string(),
+        };
         let app = ConnectUtilApp::new().await.unwrap();
-        let terraform = r#"
-resource "confluent_connector" "test" {
-  status = "RUNNING"
-  environment {
-    exo = var.environment_id
-  }
-  kafka_cluster {
-    id = var.kafka_cluster.id
-  }
-  config_sensitive = {}
-  config_nonsensitive = {
-    "connector.class" = "PostgresSink"
-  }
-}
-"#;
-        let body: Body = hcl::from_str(terraform).unwrap();
-        let resource_block = body
-            .blocks()
-            .find(|b| {
-                b.identifier() == "resource"
-                    && b.labels().len() >= 2
-                    && b.labels()[0].as_str() == "confluent_connector"
+        let options = ConnectorOptions {
+            name: Some("orders".to_string()),
+            connector: Some("DatagenSource".to_string()),
+            layout: OutputLayout::Project,
+            output_dir: Some("connectors".to_string()),
+            ..Default::default()
+        };
+        let render = |profile: Environment| {
+            let terraform_options = TerraformConfigOptions {
+                environment: Some(profile),
+                ..app.build_terraform_options(&options).unwrap()
+            };
+            app.render_output_files(&options, terraform_options)
+                .unwrap()
+        };
+
+        let ids = ConnectUtilApp::discovered_environment(&environment, &cluster, None);
+        assert_eq!(ids.name, "prod");
+        let files = render(ids);
+        assert!(files[0].content.contains("id = \"env-abc123\""));
+        assert!(files[0].content.contains("id = \"lkc-def456\""));
+        assert!(!files[1].content.contains("variable \"environment_id\""));
+
+        let variables = ConnectUtilApp::discovered_environment(
+            &environment,
+            &cluster,
+            Some(("prod_environment_id", "prod_kafka_cluster_id")),
+        );
+        let files = render(variables);
+        assert!(files[0].content.contains("id = var.prod_environment_id"));
+        assert!(files[0].content.contains("id = var.prod_kafka_cluster_id"));
+        assert!(files[1]
+            .content
+            .contains("variable \"prod_kafka_cluster_id\""));
+        assert!(files[1].content.contains("Kafka cluster ID for prod"));
+
+        let unnamed = CloudEnvironment {
+            display_name: String::new(),
+            ..environment
+        };
+        assert_eq!(
+            ConnectUtilApp::discovered_environment(&unnamed, &cluster, None).name,
+            "env-abc123"
+        );
+        assert!(ConnectUtilApp::check_variable_name("prod_cluster-id").is_ok());
+        assert!(ConnectUtilApp::check_variable_name("1prod").is_err());
+        assert!(ConnectUtilApp::check_variable_name("var.prod").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_render_environment_files() {
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::create_dir(repo.path().join(".git")).unwrap();
+        std::fs::write(
+            repo.path().join(Policy::FILE_NAME),
+            "environments:\n  dev:\n    id: env-dev1\n    cluster_id: lkc-dev1\n  prod:\n    id: env-prod1\n    cluster_id: lkc-prod1\n    overrides:\n      tasks.max: 4\n",
+        )
+        .unwrap();
+        let app = ConnectUtilApp::new().await.unwrap();
+        let output = repo.path().join("infra").join("orders.tf");
+        let options = ConnectorOptions {
+            name: Some("orders".to_string()),
+            connector: Some("DatagenSource".to_string()),
+            output: Some(output.to_string_lossy().to_string()),
+            environments: vec!["dev".to_string(), "prod".to_string()],
+            ..Default::default()
+        };
+
+        let rendered = app.render_environment_files(&options).unwrap();
+        assert_eq!(rendered.len(), 2);
+        let (prod_options, prod_files) = &rendered[1];
+        assert_eq!(prod_options.environment.as_deref(), Some("prod"));
+        assert_eq!(
+            prod_files[0].path.as_deref().map(PathBuf::from),
+            Some(repo.path().join("infra").join("prod").join("orders.tf"))
+        );
+        assert!(prod_files[0].content.contains("id = \"lkc-prod1\""));
+        assert!(prod_files[0].content.contains("\"tasks.max\" = \"4\""));
+        assert!(rendered[0].1[0].content.contains("\"tasks.max\" = \"1\""));
+
+        let for_each = app
+            .generate_terraform_non_interactive(ConnectorOptions {
+                environment_layout: EnvironmentLayout::ForEach,
+                ..options.clone()
             })
             .unwrap();
+        assert!(for_each.contains("for_each = local.orders_environments"));
 
-        let result = app.validate_resource_block(resource_block.body(), "test");
-        assert!(result.is_err());
-        assert!(result
+        let error = app
+            .render_environment_files(&ConnectorOptions {
+                environments: vec!["qa".to_string()],
+                ..options
+            })
             .unwrap_err()
-            .to_string()
-            .contains("environment block must have 'id' attribute"));
+            .to_string();
+        assert!(error.contains("Unknown environment 'qa'"));
     }
 
     #[tokio::test]
-    async fn test_validate_resource_block_missing_kafka_cluster() {
+    async fn test_generate_for_cluster_types() {
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::create_dir(repo.path().join(".git")).unwrap();
+        std::fs::write(
+            repo.path().join(Policy::FILE_NAME),
+            "environments:\n  dev:\n    id: env-dev1\n    cluster_id: lkc-dev1\n    cluster_type: basic\n  prod:\n    id: env-prod1\n    cluster_id: lkc-prod1\n",
+        )
+        .unwrap();
         let app = ConnectUtilApp::new().await.unwrap();
-        let terraform = r#"
-resource "confluent_connector" "test" {
-  status = "RUNNING"
-  environment {
-    id = var.environment_id
-  }
-  config_sensitive = {}
-  config_nonsensitive = {
-    "connector.class" = "PostgresSink"
-  }
-}
-"#;
-        let body: Body = hcl::from_str(terraform).unwrap();
-        let resource_block = body
-            .blocks()
-            .find(|b| {
-                b.identifier() == "resource"
-                    && b.labels().len() >= 2
-                    && b.labels()[0].as_str() == "confluent_connector"
-            })
-            .unwrap();
+        let options = ConnectorOptions {
+            name: Some("orders".to_string()),
+            connector: Some("DatagenSource".to_string()),
+            output: Some(repo.path().join("orders.tf").to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        let generate = |options: ConnectorOptions| app.generate_terraform_non_interactive(options);
+
+        let dedicated = generate(options.clone()).unwrap();
+        assert!(dedicated.contains("\"kafka.deployment.type\" = \"DEDICATED\""));
+        assert!(!dedicated.contains("\"kafka.max.partition.validation.disable\" ="));
+
+        let standard = generate(ConnectorOptions {
+            cluster_type: Some(ClusterType::Standard),
+            ..options.clone()
+        })
+        .unwrap();
+        assert!(standard.contains("\"kafka.deployment.type\" = \"STANDARD\""));
+        assert!(standard.contains("\"kafka.max.partition.validation.disable\" = \"false\""));
+
+        // The profile's cluster type applies to its own environment of a for_each
+        let for_each = generate(ConnectorOptions {
+            environments: vec!["dev".to_string(), "prod".to_string()],
+            environment_layout: EnvironmentLayout::ForEach,
+            ..options.clone()
+        })
+        .unwrap();
+        assert!(for_each.contains("\"kafka.deployment.type\" = \"BASIC\""));
+        assert!(for_each.contains("\"kafka.deployment.type\" = \"DEDICATED\""));
+
+        let error = generate(ConnectorOptions {
+            environment: Some("dev".to_string()),
+            schema_registry: SchemaRegistryOptions {
+                csfle: true,
+                ..Default::default()
+            },
+            ..options.clone()
+        })
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("CSFLE isn't available on Basic clusters"));
+        // --cluster-type wins over the profile
+        assert!(generate(ConnectorOptions {
+            environment: Some("dev".to_string()),
+            cluster_type: Some(ClusterType::Dedicated),
+            schema_registry: SchemaRegistryOptions {
+                csfle: true,
+                ..Default::default()
+            },
+            ..options
+        })
+        .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_output_target() {
+        let project = ConnectorOptions {
+            layout: OutputLayout::Project,
+            output_dir: Some("infra".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            ConnectUtilApp::output_target(&project),
+            Path::new("infra").join("main.tf")
+        );
+        let single = ConnectorOptions {
+            output: Some("orders.tf".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            ConnectUtilApp::output_target(&single),
+            PathBuf::from("orders.tf")
+        );
+        assert_eq!(
+            ConnectUtilApp::output_target(&ConnectorOptions::default()),
+            PathBuf::from(".")
+        );
+    }
 
-        let result = app.validate_resource_block(resource_block.body(), "test");
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("missing 'kafka_cluster"));
+    #[tokio::test]
+    async fn test_generate_terraform_non_interactive_with_companions() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let options = ConnectorOptions {
+            name: Some("orders-sink".to_string()),
+            connector: Some("PostgresSink".to_string()),
+            companions: CompanionResources {
+                service_account: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let terraform = app.generate_terraform_non_interactive(options).unwrap();
+        assert!(terraform.contains("resource \"confluent_service_account\" \"orders_sink\""));
+        assert!(terraform.contains("confluent_service_account.orders_sink.id"));
     }
 
     #[tokio::test]
-    async fn test_validate_resource_block_kafka_cluster_wrong_attribute() {
+    async fn test_render_output_files_project_layout() {
         let app = ConnectUtilApp::new().await.unwrap();
-        let terraform = r#"
-resource "confluent_connector" "test" {
-  status = "RUNNING"
-  environment {
-    id = var.environment_id
-  }
-  kafka_cluster {
-    name = var.kafka_cluster.name
-  }
-  config_sensitive = {}
-  config_nonsensitive = {
-    "connector.class" = "PostgresSink"
-  }
-}
-"#;
-        let body: Body = hcl::from_str(terraform).unwrap();
-        let resource_block = body
-            .blocks()
-            .find(|b| {
-                b.identifier() == "resource"
-                    && b.labels().len() >= 2
-                    && b.labels()[0].as_str() == "confluent_connector"
-            })
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output_dir = temp_dir.path().join("my-connector");
+        let options = ConnectorOptions {
+            name: Some("my-connector".to_string()),
+            connector: Some("PostgresSink".to_string()),
+            layout: OutputLayout::Project,
+            output_dir: Some(output_dir.to_str().unwrap().to_string()),
+            ..Default::default()
+        };
+
+        let terraform_options = app.build_terraform_options(&options).unwrap();
+        let files = app
+            .render_output_files(&options, terraform_options)
             .unwrap();
+        let names: Vec<String> = files
+            .iter()
+            .map(|f| {
+                Path::new(f.path.as_ref().unwrap())
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(names, vec!["main.tf", "variables.tf", "outputs.tf"]);
 
-        let result = app.validate_resource_block(resource_block.body(), "test");
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("kafka_cluster block must have 'id' attribute"));
+        app.emit_files(&options, &files).unwrap();
+        assert!(output_dir.join("main.tf").exists());
+        assert!(output_dir.join("variables.tf").exists());
+        let outputs = std::fs::read_to_string(output_dir.join("outputs.tf")).unwrap();
+        assert!(outputs.contains("confluent_connector.my_connector.id"));
+
+        // A second run without --force refuses to clobber the project
+        assert!(app.emit_files(&options, &files).is_err());
     }
 
     #[tokio::test]
-    async fn test_validate_resource_block_missing_config_sensitive() {
+    async fn test_find_usages() {
         let app = ConnectUtilApp::new().await.unwrap();
-        let terraform = r#"
-resource "confluent_connector" "test" {
-  status = "RUNNING"
-  environment {
-    id = var.environment_id
+        let temp_dir = tempfile::tempdir().unwrap();
+        let prod = temp_dir.path().join("prod");
+        std::fs::create_dir_all(&prod).unwrap();
+        std::fs::write(
+            prod.join("topics.tf"),
+            r#"resource "confluent_kafka_topic" "orders" {
+  topic_name = "orders.v1"
+}
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            prod.join("main.tf"),
+            r#"resource "confluent_connector" "orders_sink" {
+  environment { id = var.environment_id }
+  kafka_cluster { id = var.kafka_cluster }
+  config_sensitive = {
+    "connection.password" = var.db_password
   }
-  kafka_cluster {
-    id = var.kafka_cluster.id
+  config_nonsensitive = {
+    "connector.class" = "PostgresSink"
+    "name"            = "orders-sink"
+    "topics"          = confluent_kafka_topic.orders.topic_name
   }
+}
+
+resource "confluent_connector" "refunds_sink" {
+  environment { id = var.environment_id }
+  kafka_cluster { id = var.kafka_cluster }
   config_nonsensitive = {
     "connector.class" = "PostgresSink"
+    "name"            = "refunds-sink"
+    "topics"          = "refunds"
   }
 }
-"#;
-        let body: Body = hcl::from_str(terraform).unwrap();
-        let resource_block = body
-            .blocks()
-            .find(|b| {
-                b.identifier() == "resource"
-                    && b.labels().len() >= 2
-                    && b.labels()[0].as_str() == "confluent_connector"
-            })
-            .unwrap();
+"#,
+        )
+        .unwrap();
 
-        let result = app.validate_resource_block(resource_block.body(), "test");
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("missing 'config_sensitive'"));
+        let report = app
+            .find_usages(
+                temp_dir.path().to_str().unwrap(),
+                &UsageQuery {
+                    topic: Some("orders.v1".to_string()),
+                    secret_key: Some("connection.password".to_string()),
+                },
+            )
+            .unwrap();
+        let topic_users: Vec<&str> = report
+            .topic_usages
+            .iter()
+            .map(|usage| usage.name.as_str())
+            .collect();
+        assert_eq!(topic_users, vec!["orders-sink"]);
+        assert_eq!(report.secret_usages.len(), 1);
+        assert_eq!(report.secret_usages[0].resource, "orders_sink");
+        assert!(report.secret_usages[0].value.contains("var.db_password"));
+        assert!(report.unresolved.is_empty());
+
+        assert!(app
+            .find_usages(
+                temp_dir.path().join("missing").to_str().unwrap(),
+                &UsageQuery::default()
+            )
+            .is_err());
     }
 
     #[tokio::test]
-    async fn test_validate_resource_block_missing_config_nonsensitive() {
+    async fn test_check_stale() {
         let app = ConnectUtilApp::new().await.unwrap();
-        let terraform = r#"
-resource "confluent_connector" "test" {
-  status = "RUNNING"
-  environment {
-    id = var.environment_id
-  }
-  kafka_cluster {
-    id = var.kafka_cluster.id
-  }
-  config_sensitive = {}
-}
-"#;
-        let body: Body = hcl::from_str(terraform).unwrap();
-        let resource_block = body
-            .blocks()
-            .find(|b| {
-                b.identifier() == "resource"
-                    && b.labels().len() >= 2
-                    && b.labels()[0].as_str() == "confluent_connector"
-            })
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output_dir = temp_dir.path().join("orders");
+        let options = ConnectorOptions {
+            name: Some("orders".to_string()),
+            connector: Some("DatagenSource".to_string()),
+            layout: OutputLayout::Project,
+            output_dir: Some(output_dir.to_str().unwrap().to_string()),
+            ..Default::default()
+        };
+        let terraform_options = app.build_terraform_options(&options).unwrap();
+        let files = app
+            .render_output_files(&options, terraform_options)
             .unwrap();
-
-        let result = app.validate_resource_block(resource_block.body(), "test");
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("missing 'config_nonsensitive'"));
+        assert!(files[0].content.starts_with("# Generated by connect-util "));
+        assert!(files[0]
+            .content
+            .lines()
+            .next()
+            .unwrap()
+            .contains("(DatagenSource definition "));
+        assert!(files[1].content.starts_with("# Generated by connect-util "));
+        app.emit_files(&options, &files).unwrap();
+
+        let dir = temp_dir.path().to_str().unwrap();
+        assert_eq!(app.check_stale(None, Some(dir)).unwrap(), 0);
+
+        // A definition hash that no longer matches marks the file stale
+        let main = output_dir.join("main.tf");
+        let content = std::fs::read_to_string(&main).unwrap();
+        let first = content.lines().next().unwrap();
+        let (stamped, _) = first.rsplit_once(' ').unwrap();
+        std::fs::write(
+            &main,
+            content.replacen(first, &format!("{} 0000000000000000)", stamped), 1),
+        )
+        .unwrap();
+        assert_eq!(app.check_stale(None, Some(dir)).unwrap(), 1);
+        assert_eq!(
+            app.check_stale(Some(output_dir.join("outputs.tf").to_str().unwrap()), None)
+                .unwrap(),
+            0
+        );
     }
 
     #[tokio::test]
-    async fn test_validate_terraform_structure_no_resources() {
+    async fn test_generate_terraform_config_with_tables() {
         let app = ConnectUtilApp::new().await.unwrap();
-        let terraform = r#"
-variable "test" {
-  default = "value"
-}
-"#;
-        let result = app.validate_terraform_structure(terraform);
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("No connector configurations found"));
+        let options = |connector: &str, tables: &[&str]| ConnectorOptions {
+            name: Some("orders-cdc".to_string()),
+            connector: Some(connector.to_string()),
+            tables: tables.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        };
+
+        let terraform = app
+            .generate_terraform_non_interactive(options(
+                "PostgresCdcSourceV2",
+                &["public.orders", "inventory\\..*"],
+            ))
+            .unwrap();
+        assert!(terraform.contains("\"table.include.list\" = \"public.orders,inventory\\\\..*\""));
+        assert!(!terraform.contains("table.exclude.list"));
+
+        let terraform = app
+            .generate_terraform_non_interactive(options("PostgresCdcSourceV2", &[]))
+            .unwrap();
+        assert!(!terraform.contains("table.include.list"));
+
+        let error = app
+            .generate_terraform_non_interactive(options("PostgresCdcSourceV2", &["orders"]))
+            .unwrap_err();
+        assert!(error.to_string().contains("expected schema.table"));
+        let error = app
+            .generate_terraform_non_interactive(options("PostgresSink", &["public.orders"]))
+            .unwrap_err();
+        assert!(error.to_string().contains("PostgresSink isn't one"));
     }
 
     #[tokio::test]
-    async fn test_validate_terraform_structure_invalid_hcl() {
+    async fn test_generate_terraform_config_with_storage_layout() {
         let app = ConnectUtilApp::new().await.unwrap();
-        let terraform = r#"
-resource "confluent_connector" "test" {
-  status = "RUNNING"
-  # Missing closing brace
-"#;
-        let result = app.validate_terraform_structure(terraform);
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("Failed to parse Terraform file"));
-    }
+        let options = |connector: &str, storage: StorageLayoutOptions| ConnectorOptions {
+            name: Some("orders-archive".to_string()),
+            connector: Some(connector.to_string()),
+            storage,
+            ..Default::default()
+        };
+        let validate = |terraform: &str| {
+            let configs = app.parse_terraform_configs(terraform).unwrap();
+            let connector =
+                ConnectorDefinition::get_connector_by_name(&configs[0].connector_class).unwrap();
+            connector.validate_config(&configs[0].config, &configs[0].sensitive_config)
+        };
 
-    #[tokio::test]
-    async fn test_list_plugins_all() {
-        let mut app = ConnectUtilApp::new().await.unwrap();
-        let result = app.list_plugins(None).await;
-        assert!(result.is_ok());
+        let terraform = app
+            .generate_terraform_non_interactive(options(
+                "GcsSink",
+                StorageLayoutOptions {
+                    partitioner: Some(Partitioner::Hourly),
+                    timezone: Some("America/New_York".to_string()),
+                    ..Default::default()
+                },
+            ))
+            .unwrap();
+        assert!(terraform.contains("\"partitioner.class\" = \"TimeBasedPartitioner\""));
+        assert!(
+            terraform.contains("\"path.format\" = \"'year'=YYYY/'month'=MM/'day'=dd/'hour'=HH\"")
+        );
+        assert!(terraform.contains("\"time.interval\" = \"HOURLY\""));
+        assert!(terraform.contains("timezone = \"America/New_York\""));
+        assert!(terraform.contains("locale = \"en-US\""));
+        assert!(validate(&terraform).is_ok());
+
+        // --partition-fields alone implies the field partitioner
+        let terraform = app
+            .generate_terraform_non_interactive(options(
+                "S3_SINK",
+                StorageLayoutOptions {
+                    partition_fields: vec!["region".to_string(), "country".to_string()],
+                    ..Default::default()
+                },
+            ))
+            .unwrap();
+        assert!(terraform.contains("\"partitioner.class\" = \"FieldPartitioner\""));
+        assert!(terraform.contains("\"partition.field.name\" = \"region,country\""));
+        assert!(!terraform.contains("path.format"));
+        assert!(validate(&terraform).is_ok());
+
+        // Without layout flags the S3 sink keeps its dated layout
+        let terraform = app
+            .generate_terraform_non_interactive(options("S3_SINK", Default::default()))
+            .unwrap();
+        assert!(terraform.contains("\"path.format\" = \"'effective_date'=YYYY-MM-dd\""));
+        assert!(!terraform.contains("partitioner.class"));
+
+        for (storage, message) in [
+            (
+                StorageLayoutOptions {
+                    path_format: Some("year=YYYY".to_string()),
+                    ..Default::default()
+                },
+                "unquoted text 'year'",
+            ),
+            (
+                StorageLayoutOptions {
+                    partitioner: Some(Partitioner::Field),
+                    ..Default::default()
+                },
+                "needs --partition-fields",
+            ),
+            (
+                StorageLayoutOptions {
+                    partitioner: Some(Partitioner::Default),
+                    timezone: Some("UTC".to_string()),
+                    ..Default::default()
+                },
+                "only apply to time-based partitioners",
+            ),
+            (
+                StorageLayoutOptions {
+                    partitioner: Some(Partitioner::Hourly),
+                    path_format: Some("'day'=YYYY-MM-dd".to_string()),
+                    ..Default::default()
+                },
+                "needs an hour field",
+            ),
+        ] {
+            let error = app
+                .generate_terraform_non_interactive(options("AzureBlobSink", storage))
+                .unwrap_err();
+            assert!(error.to_string().contains(message), "{}", error);
+        }
+        let error = app
+            .generate_terraform_non_interactive(options(
+                "PostgresSink",
+                StorageLayoutOptions {
+                    partitioner: Some(Partitioner::Daily),
+                    ..Default::default()
+                },
+            ))
+            .unwrap_err();
+        assert!(error.to_string().contains("PostgresSink isn't one"));
     }
 
     #[tokio::test]
-    async fn test_list_plugins_filtered_source() {
-        let mut app = ConnectUtilApp::new().await.unwrap();
-        let result = app.list_plugins(Some("source".to_string())).await;
-        assert!(result.is_ok());
-    }
+    async fn test_generate_terraform_config_with_debezium_signals() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let options = |connector: &str, debezium: DebeziumOptions| ConnectorOptions {
+            name: Some("orders-cdc".to_string()),
+            connector: Some(connector.to_string()),
+            tables: vec![
+                "public.orders".to_string(),
+                "public.debezium_signal".to_string(),
+            ],
+            debezium,
+            ..Default::default()
+        };
+        let signals = DebeziumOptions {
+            heartbeat_table: Some("public.debezium_heartbeat".to_string()),
+            signal_table: Some("public.debezium_signal".to_string()),
+            snapshot_chunk_size: Some(2048),
+            ..Default::default()
+        };
 
-    #[tokio::test]
-    async fn test_list_plugins_filtered_sink() {
-        let mut app = ConnectUtilApp::new().await.unwrap();
-        let result = app.list_plugins(Some("sink".to_string())).await;
-        assert!(result.is_ok());
-    }
+        let terraform = app
+            .generate_terraform_non_interactive(options("PostgresCdcSourceV2", signals.clone()))
+            .unwrap();
+        assert!(terraform.contains("\"heartbeat.interval.ms\" = \"10000\""));
+        assert!(terraform.contains(
+            "\"heartbeat.action.query\" = \"INSERT INTO public.debezium_heartbeat (id, ts) VALUES (1, now())"
+        ));
+        assert!(terraform.contains("\"signal.data.collection\" = \"public.debezium_signal\""));
+        assert!(terraform.contains("\"incremental.snapshot.chunk.size\" = \"2048\""));
+        // The setup notes sit directly above the connector resource
+        let setup = terraform
+            .find("# Create the signal table public.debezium_signal")
+            .unwrap();
+        let resource = terraform
+            .find("resource \"confluent_connector\" \"orders_cdc\"")
+            .unwrap();
+        assert!(setup < resource);
+        assert!(terraform[setup..resource]
+            .lines()
+            .all(|line| line.starts_with('#')));
 
-    #[tokio::test]
-    async fn test_list_plugins_invalid_filter() {
-        let mut app = ConnectUtilApp::new().await.unwrap();
-        let result = app.list_plugins(Some("invalid".to_string())).await;
-        assert!(result.is_ok());
+        let configs = app.parse_terraform_configs(&terraform).unwrap();
+        let connector = ConnectorDefinition::get_connector_by_name("PostgresCdcSourceV2").unwrap();
+        assert!(connector
+            .validate_config(&configs[0].config, &configs[0].sensitive_config)
+            .is_ok());
+
+        // Heartbeats alone write no query and no setup notes
+        let terraform = app
+            .generate_terraform_non_interactive(options(
+                "MicrosoftSqlServerCdcSourceV2",
+                DebeziumOptions {
+                    heartbeat_interval_ms: Some(5000),
+                    ..Default::default()
+                },
+            ))
+            .unwrap();
+        assert!(terraform.contains("\"heartbeat.interval.ms\" = \"5000\""));
+        assert!(!terraform.contains("heartbeat.action.query"));
+        assert!(!terraform.contains("# "));
+
+        let error = app
+            .generate_terraform_non_interactive(options(
+                "MicrosoftSqlServerCdcSourceV2",
+                DebeziumOptions {
+                    signal_table: Some("dbo.debezium_signal".to_string()),
+                    ..Default::default()
+                },
+            ))
+            .unwrap_err();
+        assert!(error.to_string().contains("database.schema.table"));
+        let error = app
+            .generate_terraform_non_interactive(options(
+                "PostgresCdcSourceV2",
+                DebeziumOptions {
+                    snapshot_chunk_size: Some(1024),
+                    ..Default::default()
+                },
+            ))
+            .unwrap_err();
+        assert!(error.to_string().contains("needs --signal-table"));
+        let error = app
+            .generate_terraform_non_interactive(options("OracleCdcSource", signals))
+            .unwrap_err();
+        assert!(error.to_string().contains("OracleCdcSource isn't one"));
     }
 
     #[tokio::test]
-    async fn test_new() {
-        let result = ConnectUtilApp::new().await;
-        assert!(result.is_ok());
-        let app = result.unwrap();
-        // Just verify we can create the app
-        assert!(matches!(app, ConnectUtilApp { .. }));
+    async fn test_generate_terraform_config_with_record_schema() {
+        let app = ConnectUtilApp::new().await.unwrap();
+        let schema = RecordSchema::parse(
+            r#"{"type": "record", "name": "Refund", "fields": [
+                {"name": "refund_id", "type": "string"},
+                {"name": "amount", "type": {"type": "bytes", "logicalType": "decimal", "precision": 12, "scale": 2}}
+            ]}"#,
+            "AVRO",
+        )
+        .unwrap();
+        let options = |connector: &str| ConnectorOptions {
+            name: Some("refunds-sink".to_string()),
+            connector: Some(connector.to_string()),
+            record_schema: Some(schema.clone()),
+            ..Default::default()
+        };
+
+        let terraform_options = app
+            .build_terraform_options(&options("PostgresSink"))
+            .unwrap();
+        let terraform = TerraformGenerator
+            .generate_connector_config(terraform_options.clone())
+            .unwrap();
+        assert!(terraform.contains("\"pk.fields\" = \"refund_id\""));
+        assert!(terraform.contains("\"pk.mode\" = \"record_value\""));
+        assert!(terraform.contains("\"table.name.format\" = \"refund\""));
+        assert!(!terraform.contains("REPLACE_WITH_PK_FIELDS"));
+
+        let config = &app.parse_terraform_configs(&terraform).unwrap()[0];
+        let values = provenance::trace(&terraform_options, config, &BTreeSet::new());
+        let source = |key: &str| values.iter().find(|v| v.key == key).unwrap().source;
+        assert_eq!(source("pk.fields"), provenance::ValueSource::Schema);
+        assert_eq!(source("table.name.format"), provenance::ValueSource::Schema);
+
+        // Prefixed sinks get the suggestions under their own keys
+        let terraform = app
+            .generate_terraform_non_interactive(options("AlloyDBSink"))
+            .unwrap();
+        assert!(terraform.contains("\"alloydb.pk.fields\" = \"refund_id\""));
+
+        let error = app
+            .generate_terraform_non_interactive(options("S3_SINK"))
+            .unwrap_err();
+        assert!(error.to_string().contains("S3_SINK isn't one"));
     }
 
     #[tokio::test]
-    async fn test_generate_terraform_non_interactive() {
+    async fn test_render_output_files_secret_managers() {
         let app = ConnectUtilApp::new().await.unwrap();
         let options = ConnectorOptions {
-            name: Some("test-connector".to_string()),
-            output: Some("test-output.tf".to_string()),
+            name: Some("orders-cdc".to_string()),
+            connector: Some("PostgresCdcSourceV2".to_string()),
+            secrets_backend: SecretsBackend::AzureKeyVault,
+            ..Default::default()
         };
+        let terraform_options = app.build_terraform_options(&options).unwrap();
+        let files = app
+            .render_output_files(&options, terraform_options)
+            .unwrap();
+        assert_eq!(files.len(), 1);
+        let content = &files[0].content;
+        assert!(
+            content.contains("data.azurerm_key_vault_secret.orders_cdc_database_password.value")
+        );
+        assert!(
+            content.contains("data \"azurerm_key_vault_secret\" \"orders_cdc_database_password\"")
+        );
+        assert!(content.contains("variable \"key_vault_id\""));
+        assert!(hcl::from_str::<Body>(content).is_ok());
 
-        // This test uses the non-interactive function
-        let result = app.generate_terraform_non_interactive(options);
-        assert!(result.is_ok());
+        let temp_dir = tempfile::tempdir().unwrap();
+        let options = ConnectorOptions {
+            layout: OutputLayout::Project,
+            output_dir: Some(temp_dir.path().to_str().unwrap().to_string()),
+            secrets_backend: SecretsBackend::GcpSecretManager,
+            ..options
+        };
+        let terraform_options = app.build_terraform_options(&options).unwrap();
+        let files = app
+            .render_output_files(&options, terraform_options)
+            .unwrap();
+        assert_eq!(files.len(), 3);
+        assert!(files[0].content.contains(
+            "data \"google_secret_manager_secret_version\" \"orders_cdc_database_password\""
+        ));
+        assert!(!files[1].content.contains("orders_cdc_database_password"));
 
-        let terraform = result.unwrap();
-        assert!(terraform.contains("test-connector"));
-        assert!(terraform.contains("var.environment"));
+        // Self-managed payloads have nowhere to put the data sources
+        let options = ConnectorOptions {
+            target: GenerateTarget::SelfManaged,
+            layout: OutputLayout::Single,
+            ..options
+        };
+        let terraform_options = app.build_terraform_options(&options).unwrap();
+        assert!(app
+            .render_output_files(&options, terraform_options)
+            .is_err());
     }
 
     #[tokio::test]
-    async fn test_generate_terraform_non_interactive_missing_required() {
+    async fn test_render_output_files_project_layout_requires_output_dir() {
         let app = ConnectUtilApp::new().await.unwrap();
         let options = ConnectorOptions {
-            name: None, // Missing required field
-            output: Some("test-output.tf".to_string()),
+            name: Some("my-connector".to_string()),
+            layout: OutputLayout::Project,
+            ..Default::default()
         };
 
-        // This should fail because required fields are missing
-        let result = app.generate_terraform_non_interactive(options);
+        let terraform_options = app.build_terraform_options(&options).unwrap();
+        let result = app.render_output_files(&options, terraform_options);
         assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("Connector name is required"));
+        assert!(result.unwrap_err().to_string().contains("--output-dir"));
     }
 
     #[tokio::test]
@@ -1462,8 +11022,11 @@ resource "confluent_connector" "test" {
             name: "MySqlCdcSourceV2".to_string(),
             display_name: "MySQL CDC Source V2".to_string(),
             connector_class: "MySqlCdcSourceV2".to_string(),
+            aliases: vec![],
             description: "MySQL CDC Source V2".to_string(),
             connector_type: ConnectorType::Source,
+            family: ConnectorFamily::Other,
+            topic_strategy: TopicStrategy::TopicPrefix,
             required_configs: vec![],
             optional_configs: vec![],
             sensitive_configs: vec![],
@@ -1476,6 +11039,7 @@ resource "confluent_connector" "test" {
             topics: vec![],
             input_data_format: None,
             output_data_format: None,
+            ..Default::default()
         };
         let result = TerraformGenerator::add_connector_specific_config_to_object(
             &mut config_obj,
@@ -1503,6 +11067,7 @@ resource "confluent_connector" "test" {
             topics: vec!["topic1".to_string(), "topic2".to_string()],
             input_data_format: None,
             output_data_format: None,
+            ..Default::default()
         };
 
         let generator = TerraformGenerator;
@@ -1521,24 +11086,56 @@ resource "confluent_connector" "test" {
         let _app = ConnectUtilApp::new().await.unwrap();
         let connector = ConnectorDefinition::get_connector_by_name("PostgresCdcSourceV2").unwrap();
 
-        let options = TerraformConfigOptions {
+        let options = |topics: &[&str]| TerraformConfigOptions {
             connector_name: "test-connector".to_string(),
             connector: connector.clone(),
-            topics: vec!["topic1".to_string(), "topic2".to_string()],
+            topics: topics.iter().map(|t| t.to_string()).collect(),
             input_data_format: None,
             output_data_format: None,
+            ..Default::default()
         };
 
+        // CDC connectors take a single topic.prefix and never `topics`
         let generator = TerraformGenerator;
-        let result = generator.generate_connector_config(options);
-        assert!(result.is_ok());
-        let terraform = result.unwrap();
-
-        // Check that topic.prefix is used for CDC connectors
+        let terraform = generator.generate_connector_config(options(&[])).unwrap();
         assert!(terraform.contains("\"topic.prefix\" = \"<REPLACE_WITH_TOPIC_PREFIX>\""));
-        assert!(terraform.contains("join(\",\", ["));
-        assert!(terraform.contains("\"topic1\""));
-        assert!(terraform.contains("\"topic2\""));
+        assert!(!terraform.contains("\"topics\""));
+        let terraform = generator
+            .generate_connector_config(options(&["shop"]))
+            .unwrap();
+        assert!(terraform.contains("\"topic.prefix\" = \"shop\""));
+        assert!(!terraform.contains("join(\",\", ["));
+        assert!(generator
+            .generate_connector_config(options(&["topic1", "topic2"]))
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_generate_terraform_config_kafka_topic_source() {
+        let connector = ConnectorDefinition::get_connector_by_name("DatagenSource").unwrap();
+        assert_eq!(connector.topic_strategy, TopicStrategy::KafkaTopicSingle);
+        let options = |topics: Vec<String>| TerraformConfigOptions {
+            connector_name: "orders".to_string(),
+            connector: connector.clone(),
+            topics,
+            ..Default::default()
+        };
+
+        // Without topics Datagen gets a kafka.topic placeholder, not a topic prefix
+        let generator = TerraformGenerator;
+        let terraform = generator
+            .generate_connector_config(options(vec![]))
+            .unwrap();
+        assert!(terraform.contains("\"kafka.topic\" = \"<REPLACE_WITH_TOPIC_NAME>\""));
+        assert!(!terraform.contains("topic.prefix"));
+        let terraform = generator
+            .generate_connector_config(options(vec!["orders".to_string()]))
+            .unwrap();
+        assert!(terraform.contains("\"kafka.topic\" = \"orders\""));
+
+        let two = vec!["orders".to_string(), "refunds".to_string()];
+        assert!(ConnectUtilApp::topic_settings(&connector, &two).is_err());
+        assert!(generator.generate_connector_config(options(two)).is_err());
     }
 
     #[tokio::test]
@@ -1552,6 +11149,7 @@ resource "confluent_connector" "test" {
             topics: vec![],
             input_data_format: None,
             output_data_format: None,
+            ..Default::default()
         };
 
         let generator = TerraformGenerator;
@@ -1574,6 +11172,7 @@ resource "confluent_connector" "test" {
             topics: vec![],
             input_data_format: None,
             output_data_format: None,
+            ..Default::default()
         };
 
         let generator = TerraformGenerator;
@@ -1596,6 +11195,7 @@ resource "confluent_connector" "test" {
             topics: vec![],
             input_data_format: None,
             output_data_format: None,
+            ..Default::default()
         };
 
         let generator = TerraformGenerator;
@@ -1621,6 +11221,7 @@ resource "confluent_connector" "test" {
             topics: vec![],
             input_data_format: None,
             output_data_format: None,
+            ..Default::default()
         };
 
         let generator = TerraformGenerator;
@@ -1768,8 +11369,11 @@ resource "confluent_connector" "test_connector" {
             name: "PostgreSQLSource".to_string(),
             display_name: "PostgreSQL Source".to_string(),
             connector_class: "PostgreSQLSource".to_string(),
+            aliases: vec![],
             description: "PostgreSQL Source".to_string(),
             connector_type: ConnectorType::Source,
+            family: ConnectorFamily::Other,
+            topic_strategy: TopicStrategy::TopicPrefix,
             required_configs: vec![],
             optional_configs: vec![],
             sensitive_configs: vec![],
@@ -1782,6 +11386,7 @@ resource "confluent_connector" "test_connector" {
             topics: vec![],
             input_data_format: None,
             output_data_format: None,
+            ..Default::default()
         };
         let result = TerraformGenerator::add_connector_specific_config_to_object(
             &mut config_obj,
@@ -1803,8 +11408,11 @@ resource "confluent_connector" "test_connector" {
             name: "PostgresSink".to_string(),
             display_name: "PostgreSQL Sink".to_string(),
             connector_class: "PostgresSink".to_string(),
+            aliases: vec![],
             description: "PostgreSQL Sink".to_string(),
             connector_type: ConnectorType::Sink,
+            family: ConnectorFamily::Other,
+            topic_strategy: TopicStrategy::TopicsList,
             required_configs: vec![],
             optional_configs: vec![],
             sensitive_configs: vec![],
@@ -1817,6 +11425,7 @@ resource "confluent_connector" "test_connector" {
             topics: vec![],
             input_data_format: None,
             output_data_format: None,
+            ..Default::default()
         };
         let result = TerraformGenerator::add_connector_specific_config_to_object(
             &mut config_obj,
@@ -1838,8 +11447,11 @@ resource "confluent_connector" "test_connector" {
             name: "MySQLSource".to_string(),
             display_name: "MySQL Source".to_string(),
             connector_class: "MySQLSource".to_string(),
+            aliases: vec![],
             description: "MySQL Source".to_string(),
             connector_type: ConnectorType::Source,
+            family: ConnectorFamily::Other,
+            topic_strategy: TopicStrategy::TopicPrefix,
             required_configs: vec![],
             optional_configs: vec![],
             sensitive_configs: vec![],
@@ -1852,6 +11464,7 @@ resource "confluent_connector" "test_connector" {
             topics: vec![],
             input_data_format: None,
             output_data_format: None,
+            ..Default::default()
         };
         let result = TerraformGenerator::add_connector_specific_config_to_object(
             &mut config_obj,
@@ -1872,8 +11485,11 @@ resource "confluent_connector" "test_connector" {
             name: "MySQLSink".to_string(),
             display_name: "MySQL Sink".to_string(),
             connector_class: "MySQLSink".to_string(),
+            aliases: vec![],
             description: "MySQL Sink".to_string(),
             connector_type: ConnectorType::Sink,
+            family: ConnectorFamily::Other,
+            topic_strategy: TopicStrategy::TopicsList,
             required_configs: vec![],
             optional_configs: vec![],
             sensitive_configs: vec![],
@@ -1886,6 +11502,7 @@ resource "confluent_connector" "test_connector" {
             topics: vec![],
             input_data_format: None,
             output_data_format: None,
+            ..Default::default()
         };
         let result = TerraformGenerator::add_connector_specific_config_to_object(
             &mut config_obj,
@@ -1908,8 +11525,11 @@ resource "confluent_connector" "test_connector" {
             name: "TestConnector".to_string(),
             display_name: "Test Connector".to_string(),
             connector_class: "TestConnector".to_string(),
+            aliases: vec![],
             description: "Test Connector".to_string(),
             connector_type: ConnectorType::Source,
+            family: ConnectorFamily::Other,
+            topic_strategy: TopicStrategy::KafkaTopicSingle,
             required_configs: vec![],
             optional_configs: vec![],
             sensitive_configs: vec!["password".to_string(), "secret".to_string()],
@@ -1921,6 +11541,7 @@ resource "confluent_connector" "test_connector" {
             topics: vec![],
             input_data_format: None,
             output_data_format: None,
+            ..Default::default()
         };
 
         let generator = TerraformGenerator;
@@ -0,0 +1,550 @@
+use crate::error::ConnectUtilError;
+use crate::metadata::ConnectorMetadata;
+use crate::types::{is_dynamic_value, ConnectorConfig, ConnectorDefinition, ConnectorType};
+use hcl::{Body, Expression};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Keys naming the topics a connector reads or writes
+const TOPIC_KEYS: &[&str] = &["kafka.topic", "topics", "topic.prefix", "topics.regex"];
+
+/// Keys selecting record formats
+const FORMAT_KEYS: &[&str] = &[
+    "input.data.format",
+    "input.key.format",
+    "output.data.format",
+    "output.key.format",
+];
+
+/// Secret-store data sources recognised in `config_sensitive` expressions
+const SECRET_SOURCES: &[(&str, &str)] = &[
+    ("data.aws_secretsmanager_secret", "aws-secrets-manager"),
+    ("data.aws_ssm_parameter", "aws-ssm"),
+    ("data.azurerm_key_vault_secret", "azure-key-vault"),
+    ("data.google_secret_manager_secret", "gcp-secret-manager"),
+    ("data.vault_", "vault"),
+];
+
+const CSV_COLUMNS: &[&str] = &[
+    "file",
+    "resource",
+    "name",
+    "connector_class",
+    "connector_type",
+    "topics",
+    "data_formats",
+    "auth_mode",
+    "secrets_backend",
+    "deprecated",
+    "owner",
+    "on_call",
+    "maintenance_window",
+];
+
+/// Output format for inventory reports
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InventoryFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+impl FromStr for InventoryFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(InventoryFormat::Csv),
+            "json" => Ok(InventoryFormat::Json),
+            _ => Err(format!(
+                "Invalid inventory format '{}'. Use 'csv' or 'json'",
+                s
+            )),
+        }
+    }
+}
+
+/// One connector in the inventory
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct InventoryEntry {
+    pub file: PathBuf,
+    /// Terraform label, including the instance key for `for_each`/`count` blocks
+    pub resource: String,
+    pub name: String,
+    pub connector_class: String,
+    /// `source`, `sink`, `unknown` for classes outside the catalog, or `existing`
+    /// for a connector looked up with a data source
+    pub connector_type: String,
+    pub topics: Vec<String>,
+    /// `key=value` pairs such as `output.data.format=AVRO`
+    pub data_formats: Vec<String>,
+    pub auth_mode: Option<String>,
+    /// Where `config_sensitive` values come from, e.g. `terraform-variable` or `vault`
+    pub secrets_backend: Vec<String>,
+    /// Replacement connector when the class is deprecated
+    pub deprecated: Option<String>,
+    /// Owner, on-call, and maintenance window from the resource's comments
+    #[serde(flatten)]
+    pub metadata: ConnectorMetadata,
+}
+
+/// How a single sensitive value is supplied
+fn secret_backend(value: &str) -> Option<&'static str> {
+    if value.is_empty() {
+        return None;
+    }
+    if !is_dynamic_value(value) {
+        return Some("hardcoded");
+    }
+    if let Some((_, backend)) = SECRET_SOURCES
+        .iter()
+        .find(|(prefix, _)| value.contains(prefix))
+    {
+        return Some(backend);
+    }
+    if value.contains("var.") {
+        Some("terraform-variable")
+    } else if value.contains("local.") {
+        Some("terraform-local")
+    } else {
+        Some("expression")
+    }
+}
+
+impl InventoryEntry {
+    pub fn from_config(file: PathBuf, config: &ConnectorConfig) -> Self {
+        let connector_type =
+            match ConnectorDefinition::get_connector_by_name(&config.connector_class) {
+                Some(definition) => match definition.connector_type {
+                    ConnectorType::Source => "source",
+                    ConnectorType::Sink => "sink",
+                },
+                None => "unknown",
+            };
+
+        let mut topics = Vec::new();
+        for key in TOPIC_KEYS {
+            let Some(value) = config.config.get(*key) else {
+                continue;
+            };
+            match *key {
+                "topics" if !is_dynamic_value(value) => topics.extend(
+                    value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|t| !t.is_empty())
+                        .map(str::to_string),
+                ),
+                "topic.prefix" => topics.push(format!("{}*", value)),
+                _ => topics.push(value.clone()),
+            }
+        }
+
+        let data_formats = FORMAT_KEYS
+            .iter()
+            .filter_map(|key| config.config.get(*key).map(|v| format!("{}={}", key, v)))
+            .collect();
+
+        let secrets_backend: BTreeSet<&str> = config
+            .sensitive_config
+            .values()
+            .filter_map(|value| secret_backend(value))
+            .collect();
+
+        Self {
+            file,
+            resource: config.name.clone(),
+            name: config
+                .config
+                .get("name")
+                .cloned()
+                .unwrap_or_else(|| config.name.clone()),
+            connector_class: config.connector_class.clone(),
+            connector_type: connector_type.to_string(),
+            topics,
+            data_formats,
+            auth_mode: config.config.get("kafka.auth.mode").cloned(),
+            secrets_backend: secrets_backend.into_iter().map(str::to_string).collect(),
+            deprecated: ConnectorDefinition::superseded_by(&config.connector_class)
+                .map(|replacement| format!("superseded by {}", replacement)),
+            metadata: ConnectorMetadata::default(),
+        }
+    }
+
+    /// An entry for a connector managed elsewhere and looked up here. Only its
+    /// name is known, and only when the lookup is literal
+    pub fn from_data_source(file: PathBuf, data_source: &ConnectorDataSource) -> Self {
+        Self {
+            file,
+            resource: data_source.address(),
+            name: data_source.name().to_string(),
+            connector_class: String::new(),
+            connector_type: EXISTING.to_string(),
+            topics: Vec::new(),
+            data_formats: Vec::new(),
+            auth_mode: None,
+            secrets_backend: Vec::new(),
+            deprecated: None,
+            metadata: ConnectorMetadata::default(),
+        }
+    }
+
+    /// Whether the entry is a connector looked up with a data source
+    pub fn is_data_source(&self) -> bool {
+        self.connector_type == EXISTING
+    }
+
+    fn csv_fields(&self) -> Vec<String> {
+        vec![
+            self.file.display().to_string(),
+            self.resource.clone(),
+            self.name.clone(),
+            self.connector_class.clone(),
+            self.connector_type.clone(),
+            self.topics.join(";"),
+            self.data_formats.join(";"),
+            self.auth_mode.clone().unwrap_or_default(),
+            self.secrets_backend.join(";"),
+            self.deprecated.clone().unwrap_or_default(),
+            self.metadata.owner.clone().unwrap_or_default(),
+            self.metadata.on_call.clone().unwrap_or_default(),
+            self.metadata.maintenance_window.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+/// `connector_type` of inventory entries for connector data sources
+const EXISTING: &str = "existing";
+
+/// An existing connector looked up with `data "confluent_connector"`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectorDataSource {
+    pub label: String,
+    /// `id` it's looked up by, with `${...}` around an expression
+    pub id: Option<String>,
+    /// `display_name` it's looked up by, with `${...}` around an expression
+    pub display_name: Option<String>,
+    /// Nested blocks it sets, such as `environment`
+    pub blocks: BTreeSet<String>,
+}
+
+impl ConnectorDataSource {
+    pub fn address(&self) -> String {
+        format!("data.confluent_connector.{}", self.label)
+    }
+
+    /// The connector's display name or ID, or the label when neither is set
+    pub fn name(&self) -> &str {
+        self.display_name
+            .as_deref()
+            .or(self.id.as_deref())
+            .unwrap_or(&self.label)
+    }
+
+    /// What the provider would reject: the lookup needs exactly one of `id` and
+    /// `display_name`, and the connector's environment and cluster
+    pub fn problems(&self) -> Vec<String> {
+        let address = self.address();
+        let mut problems = Vec::new();
+        match (&self.id, &self.display_name) {
+            (None, None) => problems.push(format!(
+                "'{}' needs an 'id' or a 'display_name' to look the connector up",
+                address
+            )),
+            (Some(_), Some(_)) => problems.push(format!(
+                "'{}' sets both 'id' and 'display_name'; set only one",
+                address
+            )),
+            _ => {}
+        }
+        for block in ["environment", "kafka_cluster"] {
+            if !self.blocks.contains(block) {
+                problems.push(format!("'{}' is missing its '{}' block", address, block));
+            }
+        }
+        problems
+    }
+}
+
+/// The `confluent_connector` data sources a file declares
+pub fn connector_data_sources(body: &Body) -> Vec<ConnectorDataSource> {
+    let value = |expr: &Expression| match expr {
+        Expression::String(s) => s.clone(),
+        _ => format!(
+            "${{{}}}",
+            hcl::format::to_string(expr).unwrap_or_default().trim()
+        ),
+    };
+    body.blocks()
+        .filter(|block| block.identifier() == "data")
+        .filter_map(|block| match block.labels() {
+            [kind, label, ..] if kind.as_str() == "confluent_connector" => {
+                let attribute = |key: &str| {
+                    block
+                        .body()
+                        .attributes()
+                        .find(|attr| attr.key() == key)
+                        .map(|attr| value(attr.expr()))
+                };
+                Some(ConnectorDataSource {
+                    label: label.as_str().to_string(),
+                    id: attribute("id"),
+                    display_name: attribute("display_name"),
+                    blocks: block
+                        .body()
+                        .blocks()
+                        .map(|block| block.identifier().to_string())
+                        .collect(),
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Whether a module block passes connector config, as the legacy connector modules do
+pub fn is_connector_module(body: &Body) -> bool {
+    body.attributes()
+        .any(|attr| attr.key() == "config_nonsensitive" || attr.key() == "config_sensitive")
+}
+
+/// Addresses of the resources, data sources, and modules in a file that aren't
+/// connectors, such as `confluent_kafka_topic.orders` or `data.confluent_environment.prod`
+pub fn other_blocks(body: &Body) -> Vec<String> {
+    body.blocks()
+        .filter_map(|block| {
+            let labels: Vec<&str> = block.labels().iter().map(|l| l.as_str()).collect();
+            match (block.identifier(), labels.as_slice()) {
+                ("resource" | "data", ["confluent_connector", ..]) => None,
+                ("resource", [kind, name, ..]) => Some(format!("{}.{}", kind, name)),
+                ("data", [kind, name, ..]) => Some(format!("data.{}.{}", kind, name)),
+                ("module", [name, ..]) if !is_connector_module(block.body()) => {
+                    Some(format!("module.{}", name))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Renders the inventory as CSV with a header row, or as a JSON array
+pub fn render_inventory(
+    entries: &[InventoryEntry],
+    format: InventoryFormat,
+) -> Result<String, ConnectUtilError> {
+    match format {
+        InventoryFormat::Json => Ok(serde_json::to_string_pretty(entries)?),
+        InventoryFormat::Csv => {
+            let mut out = String::new();
+            let _ = writeln!(out, "{}", CSV_COLUMNS.join(","));
+            for entry in entries {
+                let row: Vec<String> = entry.csv_fields().iter().map(|f| csv_field(f)).collect();
+                let _ = writeln!(out, "{}", row.join(","));
+            }
+            Ok(out)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config(class: &str, config: &[(&str, &str)], sensitive: &[(&str, &str)]) -> ConnectorConfig {
+        let to_map = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        ConnectorConfig {
+            name: "orders".to_string(),
+            connector_class: class.to_string(),
+            config: to_map(config),
+            sensitive_config: to_map(sensitive),
+        }
+    }
+
+    #[test]
+    fn test_entry_from_config() {
+        let entry = InventoryEntry::from_config(
+            PathBuf::from("infra/main.tf"),
+            &config(
+                "PostgresCdcSource",
+                &[
+                    ("name", "orders-cdc"),
+                    ("kafka.auth.mode", "SERVICE_ACCOUNT"),
+                    ("topic.prefix", "orders"),
+                    ("output.data.format", "AVRO"),
+                ],
+                &[
+                    ("database.password", "${var.db_password}"),
+                    (
+                        "kafka.api.secret",
+                        "${data.vault_generic_secret.kafka.data[\"secret\"]}",
+                    ),
+                ],
+            ),
+        );
+        assert_eq!(entry.name, "orders-cdc");
+        assert_eq!(entry.connector_type, "source");
+        assert_eq!(entry.topics, vec!["orders*"]);
+        assert_eq!(entry.data_formats, vec!["output.data.format=AVRO"]);
+        assert_eq!(entry.auth_mode, Some("SERVICE_ACCOUNT".to_string()));
+        assert_eq!(entry.secrets_backend, vec!["terraform-variable", "vault"]);
+        assert_eq!(
+            entry.deprecated,
+            Some("superseded by PostgresCdcSourceV2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_entry_for_unknown_sink_with_hardcoded_secret() {
+        let entry = InventoryEntry::from_config(
+            PathBuf::from("main.tf"),
+            &config(
+                "com.example.CustomSink",
+                &[("topics", "orders, refunds")],
+                &[("password", "hunter2"), ("token", "")],
+            ),
+        );
+        assert_eq!(entry.name, "orders");
+        assert_eq!(entry.connector_type, "unknown");
+        assert_eq!(entry.topics, vec!["orders", "refunds"]);
+        assert_eq!(entry.secrets_backend, vec!["hardcoded"]);
+        assert_eq!(entry.deprecated, None);
+    }
+
+    #[test]
+    fn test_render_inventory_csv_quotes_fields() {
+        let mut entry = InventoryEntry::from_config(
+            PathBuf::from("main.tf"),
+            &config("DatagenSource", &[("kafka.topic", "orders")], &[]),
+        );
+        entry.resource = "orders[\"a,b\"]".to_string();
+        entry.metadata.owner = Some("team-orders".to_string());
+        let csv = render_inventory(&[entry], InventoryFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_COLUMNS.join(","));
+        assert_eq!(
+            lines[1],
+            "main.tf,\"orders[\"\"a,b\"\"]\",orders,DatagenSource,source,orders,,,,,team-orders,,"
+        );
+    }
+
+    #[test]
+    fn test_render_inventory_json() {
+        let entry = InventoryEntry::from_config(
+            PathBuf::from("main.tf"),
+            &config("DatagenSource", &[("kafka.topic", "orders")], &[]),
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&render_inventory(&[entry], InventoryFormat::Json).unwrap())
+                .unwrap();
+        assert_eq!(json[0]["connector_class"], "DatagenSource");
+        assert_eq!(json[0]["auth_mode"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_other_blocks() {
+        let body: Body = hcl::from_str(
+            r#"
+variable "environment_id" {}
+resource "confluent_kafka_topic" "orders" {
+  topic_name = "orders"
+}
+resource "confluent_service_account" "app" {
+  display_name = "app"
+}
+resource "confluent_connector" "orders_sink" {
+  status = "RUNNING"
+}
+data "confluent_environment" "prod" {
+  id = var.environment_id
+}
+data "confluent_connector" "upstream" {
+  id = "lcc-abc123"
+}
+module "topics" {
+  source = "./topics"
+}
+module "legacy_sink" {
+  config_nonsensitive = {
+    "connector.class" = "S3_SINK"
+  }
+}
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            other_blocks(&body),
+            vec![
+                "confluent_kafka_topic.orders",
+                "confluent_service_account.app",
+                "data.confluent_environment.prod",
+                "module.topics",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_connector_data_sources() {
+        let body: Body = hcl::from_str(
+            r#"
+data "confluent_connector" "orders" {
+  display_name = "orders-cdc"
+  environment {
+    id = var.environment_id
+  }
+  kafka_cluster {
+    id = "lkc-123"
+  }
+}
+data "confluent_connector" "refunds" {
+  id           = "lcc-abc123"
+  display_name = "refunds"
+}
+data "confluent_environment" "prod" {}
+"#,
+        )
+        .unwrap();
+        let data_sources = connector_data_sources(&body);
+        assert_eq!(data_sources.len(), 2);
+        assert_eq!(data_sources[0].address(), "data.confluent_connector.orders");
+        assert_eq!(data_sources[0].name(), "orders-cdc");
+        assert_eq!(data_sources[0].problems(), Vec::<String>::new());
+        assert_eq!(
+            data_sources[1].problems(),
+            vec![
+                "'data.confluent_connector.refunds' sets both 'id' and 'display_name'; set only one",
+                "'data.confluent_connector.refunds' is missing its 'environment' block",
+                "'data.confluent_connector.refunds' is missing its 'kafka_cluster' block",
+            ]
+        );
+
+        let entry = InventoryEntry::from_data_source(PathBuf::from("main.tf"), &data_sources[0]);
+        assert_eq!(entry.resource, "data.confluent_connector.orders");
+        assert_eq!(entry.name, "orders-cdc");
+        assert_eq!(entry.connector_type, "existing");
+        assert!(entry.is_data_source());
+    }
+
+    #[test]
+    fn test_inventory_format_from_str() {
+        assert_eq!("CSV".parse(), Ok(InventoryFormat::Csv));
+        assert_eq!("json".parse(), Ok(InventoryFormat::Json));
+        assert!("xml".parse::<InventoryFormat>().is_err());
+    }
+}
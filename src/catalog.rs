@@ -0,0 +1,761 @@
+use crate::error::ConnectUtilError;
+use crate::i18n::Locale;
+use crate::types::{ConfigField, ConnectorDefinition, ConnectorType};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fmt::Write;
+
+/// Bundle layout version written by `catalog export`
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// A portable snapshot of the connector catalog, as written by `catalog export`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogBundle {
+    pub format_version: u32,
+    pub connectors: Vec<ConnectorDefinition>,
+}
+
+impl CatalogBundle {
+    /// The catalog compiled into this binary
+    pub fn builtin() -> Self {
+        Self {
+            format_version: BUNDLE_FORMAT_VERSION,
+            connectors: ConnectorDefinition::get_all_connectors(),
+        }
+    }
+
+    pub fn from_json(content: &str) -> Result<Self, ConnectUtilError> {
+        let bundle: CatalogBundle = serde_json::from_str(content)
+            .map_err(|e| ConnectUtilError::Config(format!("Invalid catalog bundle: {}", e)))?;
+        if bundle.format_version != BUNDLE_FORMAT_VERSION {
+            return Err(ConnectUtilError::Config(format!(
+                "Unsupported catalog bundle format_version {} (expected {})",
+                bundle.format_version, BUNDLE_FORMAT_VERSION
+            )));
+        }
+        Ok(bundle)
+    }
+
+    pub fn to_json(&self) -> Result<String, ConnectUtilError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Hash of the bundle's connectors, for telling catalogs apart
+    pub fn fingerprint(&self) -> String {
+        fingerprint(&self.connectors)
+    }
+}
+
+/// FNV-1a hash of a value's JSON, as 16 hex digits. Definitions hold no maps, so
+/// their JSON, and with it the hash, is the same from run to run
+pub fn fingerprint<T: Serialize + ?Sized>(value: &T) -> String {
+    let json = serde_json::to_vec(value).unwrap_or_default();
+    let hash = json.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// One difference within a connector present in both bundles
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// A connector-level attribute such as `connector_class` changed
+    Attribute {
+        attribute: &'static str,
+        before: String,
+        after: String,
+    },
+    FieldAdded {
+        field: String,
+        required: bool,
+    },
+    FieldRemoved {
+        field: String,
+    },
+    FieldChanged {
+        field: String,
+        attribute: &'static str,
+        before: String,
+        after: String,
+    },
+    SensitivityChanged {
+        field: String,
+        sensitive: bool,
+    },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Attribute {
+                attribute,
+                before,
+                after,
+            } => write!(f, "~ {}: '{}' -> '{}'", attribute, before, after),
+            Change::FieldAdded { field, required } => {
+                let required = if *required { " (required)" } else { "" };
+                write!(f, "+ field '{}'{}", field, required)
+            }
+            Change::FieldRemoved { field } => write!(f, "- field '{}'", field),
+            Change::FieldChanged {
+                field,
+                attribute,
+                before,
+                after,
+            } => write!(f, "~ '{}' {}: {} -> {}", field, attribute, before, after),
+            Change::SensitivityChanged { field, sensitive } => {
+                if *sensitive {
+                    write!(f, "~ '{}' is now sensitive", field)
+                } else {
+                    write!(f, "~ '{}' is no longer sensitive", field)
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectorDiff {
+    pub name: String,
+    pub changes: Vec<Change>,
+}
+
+/// Connectors added, removed, and changed between two bundles, each sorted by name
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CatalogDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<ConnectorDiff>,
+}
+
+impl CatalogDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Renders a PR-comment-ready summary with one collapsed section per changed connector
+    pub fn to_markdown(&self, old: &str, new: &str) -> String {
+        let mut out = format!("## Catalog changes: `{}` → `{}`\n\n", old, new);
+        if self.is_empty() {
+            out.push_str("✅ No catalog differences\n");
+            return out;
+        }
+        out.push_str("| Added | Removed | Changed |\n|---:|---:|---:|\n");
+        out.push_str(&format!(
+            "| {} | {} | {} |\n",
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len()
+        ));
+        let names = |names: &[String]| {
+            names
+                .iter()
+                .map(|n| format!("`{}`", n))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        if !self.added.is_empty() {
+            out.push_str(&format!("\n**Added:** {}\n", names(&self.added)));
+        }
+        if !self.removed.is_empty() {
+            out.push_str(&format!("\n**Removed:** {}\n", names(&self.removed)));
+        }
+        for connector in &self.changed {
+            out.push_str(&format!(
+                "\n<details>\n<summary><code>{}</code>: {} change(s)</summary>\n\n",
+                connector.name,
+                connector.changes.len()
+            ));
+            for change in &connector.changes {
+                out.push_str(&format!("- `{}`\n", change));
+            }
+            out.push_str("\n</details>\n");
+        }
+        out
+    }
+}
+
+fn connector_type_name(connector_type: &ConnectorType) -> String {
+    match connector_type {
+        ConnectorType::Source => "source".to_string(),
+        ConnectorType::Sink => "sink".to_string(),
+    }
+}
+
+fn optional(value: &Option<String>) -> String {
+    value
+        .as_ref()
+        .map(|v| format!("'{}'", v))
+        .unwrap_or_else(|| "(none)".to_string())
+}
+
+fn valid_values(values: &Option<Vec<String>>) -> String {
+    values
+        .as_ref()
+        .map(|v| format!("[{}]", v.join(", ")))
+        .unwrap_or_else(|| "(any)".to_string())
+}
+
+fn fields(connector: &ConnectorDefinition) -> BTreeMap<&str, &ConfigField> {
+    connector
+        .required_configs
+        .iter()
+        .chain(connector.optional_configs.iter())
+        .map(|field| (field.name.as_str(), field))
+        .collect()
+}
+
+fn diff_field(before: &ConfigField, after: &ConfigField, changes: &mut Vec<Change>) {
+    let attributes = [
+        (
+            "required",
+            before.required.to_string(),
+            after.required.to_string(),
+        ),
+        ("type", before.field_type.clone(), after.field_type.clone()),
+        (
+            "default",
+            optional(&before.default_value),
+            optional(&after.default_value),
+        ),
+        (
+            "valid values",
+            valid_values(&before.valid_values),
+            valid_values(&after.valid_values),
+        ),
+        (
+            "description",
+            format!("'{}'", before.description),
+            format!("'{}'", after.description),
+        ),
+    ];
+    for (attribute, old, new) in attributes {
+        if old != new {
+            changes.push(Change::FieldChanged {
+                field: after.name.clone(),
+                attribute,
+                before: old,
+                after: new,
+            });
+        }
+    }
+}
+
+fn diff_connector(before: &ConnectorDefinition, after: &ConnectorDefinition) -> Vec<Change> {
+    let mut changes = Vec::new();
+    let attributes = [
+        (
+            "connector_class",
+            before.connector_class.clone(),
+            after.connector_class.clone(),
+        ),
+        (
+            "aliases",
+            format!("[{}]", before.aliases.join(", ")),
+            format!("[{}]", after.aliases.join(", ")),
+        ),
+        (
+            "connector_type",
+            connector_type_name(&before.connector_type),
+            connector_type_name(&after.connector_type),
+        ),
+        (
+            "topic_strategy",
+            before.topic_strategy.key().to_string(),
+            after.topic_strategy.key().to_string(),
+        ),
+        (
+            "display_name",
+            before.display_name.clone(),
+            after.display_name.clone(),
+        ),
+        (
+            "description",
+            before.description.clone(),
+            after.description.clone(),
+        ),
+    ];
+    for (attribute, old, new) in attributes {
+        if old != new {
+            changes.push(Change::Attribute {
+                attribute,
+                before: old,
+                after: new,
+            });
+        }
+    }
+
+    let old_fields = fields(before);
+    let new_fields = fields(after);
+    for name in old_fields.keys() {
+        if !new_fields.contains_key(name) {
+            changes.push(Change::FieldRemoved {
+                field: name.to_string(),
+            });
+        }
+    }
+    for (name, field) in &new_fields {
+        match old_fields.get(name) {
+            None => changes.push(Change::FieldAdded {
+                field: name.to_string(),
+                required: field.required,
+            }),
+            Some(old) => diff_field(old, field, &mut changes),
+        }
+    }
+
+    let old_sensitive: BTreeSet<&String> = before.sensitive_configs.iter().collect();
+    let new_sensitive: BTreeSet<&String> = after.sensitive_configs.iter().collect();
+    for field in new_sensitive.difference(&old_sensitive) {
+        changes.push(Change::SensitivityChanged {
+            field: field.to_string(),
+            sensitive: true,
+        });
+    }
+    for field in old_sensitive.difference(&new_sensitive) {
+        changes.push(Change::SensitivityChanged {
+            field: field.to_string(),
+            sensitive: false,
+        });
+    }
+    changes
+}
+
+/// Compares two bundles, matching connectors by `name`
+pub fn diff_catalogs(old: &CatalogBundle, new: &CatalogBundle) -> CatalogDiff {
+    let old_connectors: BTreeMap<&str, &ConnectorDefinition> = old
+        .connectors
+        .iter()
+        .map(|c| (c.name.as_str(), c))
+        .collect();
+    let new_connectors: BTreeMap<&str, &ConnectorDefinition> = new
+        .connectors
+        .iter()
+        .map(|c| (c.name.as_str(), c))
+        .collect();
+
+    let mut diff = CatalogDiff::default();
+    for name in old_connectors.keys() {
+        if !new_connectors.contains_key(name) {
+            diff.removed.push(name.to_string());
+        }
+    }
+    for (name, connector) in &new_connectors {
+        match old_connectors.get(name) {
+            None => diff.added.push(name.to_string()),
+            Some(old) => {
+                let changes = diff_connector(old, connector);
+                if !changes.is_empty() {
+                    diff.changed.push(ConnectorDiff {
+                        name: name.to_string(),
+                        changes,
+                    });
+                }
+            }
+        }
+    }
+    diff
+}
+
+/// Connectors with fewer fields than this are listed as thin definitions
+pub const THIN_FIELDS: usize = 3;
+
+/// Field metadata counts for one connector, for `catalog stats`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConnectorStats {
+    pub name: String,
+    pub connector_type: &'static str,
+    pub family: &'static str,
+    pub fields: usize,
+    pub required: usize,
+    pub sensitive: usize,
+    pub with_default: usize,
+    pub with_valid_values: usize,
+    pub with_description: usize,
+}
+
+/// How many fields, out of `total`, have a piece of metadata
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Coverage {
+    pub count: usize,
+    pub total: usize,
+}
+
+impl Coverage {
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.count as f64 * 100.0 / self.total as f64
+        }
+    }
+}
+
+impl fmt::Display for Coverage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.0}% ({}/{})", self.percent(), self.count, self.total)
+    }
+}
+
+/// How many connector and field descriptions a locale's message table covers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct TranslationCoverage {
+    pub connectors: Coverage,
+    pub fields: Coverage,
+}
+
+/// Size and metadata coverage of a catalog, for spotting thin definitions
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CatalogStats {
+    pub connectors: usize,
+    pub by_type: BTreeMap<&'static str, usize>,
+    pub by_family: BTreeMap<&'static str, usize>,
+    pub fields: usize,
+    pub defaults: Coverage,
+    pub valid_values: Coverage,
+    pub descriptions: Coverage,
+    /// Connectors that mark at least one config as sensitive
+    pub with_sensitive: Coverage,
+    /// Connectors with fewer than `THIN_FIELDS` fields, fewest first
+    pub thin: Vec<String>,
+    pub per_connector: Vec<ConnectorStats>,
+    /// Description coverage by locale code
+    pub translations: BTreeMap<&'static str, TranslationCoverage>,
+}
+
+impl CatalogStats {
+    pub fn collect(bundle: &CatalogBundle) -> Self {
+        let mut stats = CatalogStats {
+            connectors: bundle.connectors.len(),
+            ..Default::default()
+        };
+        for connector in &bundle.connectors {
+            let fields: Vec<&ConfigField> = connector
+                .required_configs
+                .iter()
+                .chain(connector.optional_configs.iter())
+                .collect();
+            let count = |has: fn(&ConfigField) -> bool| fields.iter().filter(|f| has(f)).count();
+            let entry = ConnectorStats {
+                name: connector.name.clone(),
+                connector_type: match connector.connector_type {
+                    ConnectorType::Source => "source",
+                    ConnectorType::Sink => "sink",
+                },
+                family: connector.family.display_name(),
+                fields: fields.len(),
+                required: connector.required_configs.len(),
+                sensitive: connector.sensitive_configs.len(),
+                with_default: count(|f| f.default_value.is_some()),
+                with_valid_values: count(|f| {
+                    f.valid_values.as_ref().is_some_and(|v| !v.is_empty())
+                }),
+                with_description: count(|f| !f.description.trim().is_empty()),
+            };
+            *stats.by_type.entry(entry.connector_type).or_default() += 1;
+            *stats.by_family.entry(entry.family).or_default() += 1;
+            stats.fields += entry.fields;
+            stats.defaults.count += entry.with_default;
+            stats.valid_values.count += entry.with_valid_values;
+            stats.descriptions.count += entry.with_description;
+            stats.with_sensitive.count += usize::from(entry.sensitive > 0);
+            stats.per_connector.push(entry);
+        }
+        for coverage in [
+            &mut stats.defaults,
+            &mut stats.valid_values,
+            &mut stats.descriptions,
+        ] {
+            coverage.total = stats.fields;
+        }
+        stats.with_sensitive.total = stats.connectors;
+        stats.per_connector.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut thin: Vec<&ConnectorStats> = stats
+            .per_connector
+            .iter()
+            .filter(|c| c.fields < THIN_FIELDS)
+            .collect();
+        thin.sort_by(|a, b| a.fields.cmp(&b.fields).then_with(|| a.name.cmp(&b.name)));
+        stats.thin = thin.into_iter().map(|c| c.name.clone()).collect();
+        stats.translations = Locale::ALL
+            .into_iter()
+            .map(|locale| {
+                (
+                    locale.code(),
+                    locale.messages().coverage(&bundle.connectors),
+                )
+            })
+            .collect();
+        stats
+    }
+
+    /// Summary, a table with a row per connector, and the thin definitions
+    pub fn render(&self) -> String {
+        let counts = |map: &BTreeMap<&'static str, usize>| {
+            map.iter()
+                .map(|(key, count)| format!("{} {}", count, key))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "📊 Catalog: {} connector(s) ({})",
+            self.connectors,
+            counts(&self.by_type)
+        );
+        let _ = writeln!(out, "  By family: {}", counts(&self.by_family));
+        let average = if self.connectors == 0 {
+            0.0
+        } else {
+            self.fields as f64 / self.connectors as f64
+        };
+        let _ = writeln!(
+            out,
+            "  Fields: {} ({:.1} per connector)",
+            self.fields, average
+        );
+        let _ = writeln!(out, "  With a default: {}", self.defaults);
+        let _ = writeln!(out, "  With valid values: {}", self.valid_values);
+        let _ = writeln!(out, "  With a description: {}", self.descriptions);
+        let _ = writeln!(
+            out,
+            "  Connectors with sensitive configs: {}",
+            self.with_sensitive
+        );
+        for (code, coverage) in &self.translations {
+            let _ = writeln!(
+                out,
+                "  Described in locales/{}.json: connectors {}, fields {}",
+                code, coverage.connectors, coverage.fields
+            );
+        }
+
+        let width = self
+            .per_connector
+            .iter()
+            .map(|c| c.name.len())
+            .max()
+            .unwrap_or(0)
+            .max("Connector".len());
+        let _ = writeln!(out);
+        let _ = writeln!(
+            out,
+            "  {:<width$}  {:<6}  {:>6}  {:>8}  {:>9}  {:>8}  {:>12}",
+            "Connector", "Type", "Fields", "Required", "Sensitive", "Defaults", "Valid values"
+        );
+        for c in &self.per_connector {
+            let _ = writeln!(
+                out,
+                "  {:<width$}  {:<6}  {:>6}  {:>8}  {:>9}  {:>8}  {:>12}",
+                c.name,
+                c.connector_type,
+                c.fields,
+                c.required,
+                c.sensitive,
+                c.with_default,
+                c.with_valid_values
+            );
+        }
+        if !self.thin.is_empty() {
+            let _ = writeln!(out);
+            let _ = writeln!(
+                out,
+                "⚠️  {} thin definition(s) with fewer than {} fields: {}",
+                self.thin.len(),
+                THIN_FIELDS,
+                self.thin.join(", ")
+            );
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::config_field;
+    use crate::types::{ConnectorFamily, TopicStrategy};
+
+    fn connector(name: &str) -> ConnectorDefinition {
+        ConnectorDefinition {
+            name: name.to_string(),
+            display_name: name.to_string(),
+            connector_class: name.to_string(),
+            aliases: vec![],
+            connector_type: ConnectorType::Sink,
+            family: ConnectorFamily::Other,
+            topic_strategy: TopicStrategy::TopicsList,
+            description: "Example".to_string(),
+            required_configs: vec![config_field("topics", "Topics", "string", true, None)],
+            optional_configs: vec![config_field(
+                "insert.mode",
+                "Insert mode",
+                "string",
+                false,
+                Some(vec!["INSERT".to_string(), "UPSERT".to_string()]),
+            )],
+            sensitive_configs: vec![],
+        }
+    }
+
+    fn bundle(connectors: Vec<ConnectorDefinition>) -> CatalogBundle {
+        CatalogBundle {
+            format_version: BUNDLE_FORMAT_VERSION,
+            connectors,
+        }
+    }
+
+    #[test]
+    fn test_bundle_round_trip() {
+        let builtin = CatalogBundle::builtin();
+        let parsed = CatalogBundle::from_json(&builtin.to_json().unwrap()).unwrap();
+        assert_eq!(parsed.connectors.len(), builtin.connectors.len());
+        assert!(diff_catalogs(&builtin, &parsed).is_empty());
+    }
+
+    #[test]
+    fn test_bundle_rejects_unknown_format_version() {
+        let err = CatalogBundle::from_json(r#"{"format_version": 2, "connectors": []}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("format_version 2"));
+        assert!(matches!(
+            CatalogBundle::from_json("[]"),
+            Err(ConnectUtilError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_diff_added_and_removed_connectors() {
+        let old = bundle(vec![connector("A"), connector("B")]);
+        let new = bundle(vec![connector("B"), connector("C")]);
+        let diff = diff_catalogs(&old, &new);
+        assert_eq!(diff.added, vec!["C"]);
+        assert_eq!(diff.removed, vec!["A"]);
+        assert!(diff.changed.is_empty());
+    }
+
+    #[test]
+    fn test_diff_field_changes() {
+        let old = connector("PostgresSink");
+        let mut new = connector("PostgresSink");
+        new.connector_class = "PostgresSinkV2".to_string();
+        new.optional_configs[0].valid_values = Some(vec!["INSERT".to_string()]);
+        new.optional_configs
+            .push(config_field("pk.mode", "PK mode", "string", false, None));
+        new.required_configs
+            .push(config_field("auth.token", "Token", "password", true, None));
+        new.sensitive_configs.push("auth.token".to_string());
+        let mut moved = new.optional_configs.remove(0);
+        moved.required = true;
+        new.required_configs.push(moved);
+
+        let diff = diff_catalogs(&bundle(vec![old]), &bundle(vec![new]));
+        assert_eq!(diff.changed.len(), 1);
+        let changes: Vec<String> = diff.changed[0]
+            .changes
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                "~ connector_class: 'PostgresSink' -> 'PostgresSinkV2'",
+                "+ field 'auth.token' (required)",
+                "~ 'insert.mode' required: false -> true",
+                "~ 'insert.mode' valid values: [INSERT, UPSERT] -> [INSERT]",
+                "+ field 'pk.mode'",
+                "~ 'auth.token' is now sensitive",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_to_markdown() {
+        let old = bundle(vec![connector("A"), connector("B")]);
+        let mut changed = connector("B");
+        changed.optional_configs.clear();
+        let new = bundle(vec![changed, connector("C")]);
+        let markdown = diff_catalogs(&old, &new).to_markdown("v1.json", "v2.json");
+        assert!(markdown.starts_with("## Catalog changes: `v1.json` → `v2.json`"));
+        assert!(markdown.contains("| 1 | 1 | 1 |"));
+        assert!(markdown.contains("**Added:** `C`"));
+        assert!(markdown.contains("**Removed:** `A`"));
+        assert!(markdown.contains("<summary><code>B</code>: 1 change(s)</summary>"));
+        assert!(markdown.contains("- `- field 'insert.mode'`"));
+
+        let clean = diff_catalogs(&old, &old).to_markdown("a", "b");
+        assert!(clean.ends_with("✅ No catalog differences\n"));
+    }
+
+    #[test]
+    fn test_diff_removed_field_and_sensitivity() {
+        let mut old = connector("S3Sink");
+        old.sensitive_configs.push("topics".to_string());
+        let mut new = connector("S3Sink");
+        new.optional_configs.clear();
+
+        let diff = diff_catalogs(&bundle(vec![old]), &bundle(vec![new]));
+        assert_eq!(
+            diff.changed[0].changes,
+            vec![
+                Change::FieldRemoved {
+                    field: "insert.mode".to_string()
+                },
+                Change::SensitivityChanged {
+                    field: "topics".to_string(),
+                    sensitive: false
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_catalog_stats() {
+        let mut secured = connector("SecuredSink");
+        secured.connector_type = ConnectorType::Source;
+        secured.family = ConnectorFamily::Databases;
+        secured.sensitive_configs = vec!["connection.password".to_string()];
+        secured.optional_configs[0].default_value = Some("INSERT".to_string());
+        secured
+            .optional_configs
+            .push(config_field("batch.size", "", "int", false, None));
+        let mut bare = connector("BareSink");
+        bare.required_configs.clear();
+        bare.optional_configs.clear();
+
+        let stats = CatalogStats::collect(&bundle(vec![secured, connector("PlainSink"), bare]));
+        assert_eq!(stats.connectors, 3);
+        assert_eq!(stats.by_type, BTreeMap::from([("sink", 2), ("source", 1)]));
+        assert_eq!(
+            stats.by_family,
+            BTreeMap::from([("Databases", 1), ("Other", 2)])
+        );
+        assert_eq!(stats.fields, 5);
+        assert_eq!(stats.defaults, Coverage { count: 1, total: 5 });
+        assert_eq!(stats.valid_values.count, 2);
+        assert_eq!(stats.descriptions.count, 4);
+        assert_eq!(stats.with_sensitive, Coverage { count: 1, total: 3 });
+        assert_eq!(stats.thin, vec!["BareSink", "PlainSink"]);
+        let names: Vec<&str> = stats
+            .per_connector
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["BareSink", "PlainSink", "SecuredSink"]);
+
+        let text = stats.render();
+        assert!(text.contains("📊 Catalog: 3 connector(s) (2 sink, 1 source)"));
+        assert!(text.contains("With a default: 20% (1/5)"));
+        assert!(text.contains("Described in locales/es.json: connectors 0% (0/3), fields "));
+        assert!(text.contains("2 thin definition(s) with fewer than 3 fields: BareSink, PlainSink"));
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["per_connector"][2]["sensitive"], 1);
+        assert_eq!(json["defaults"]["total"], 5);
+        assert_eq!(json["translations"]["es"]["fields"]["total"], 5);
+        assert_eq!(json["translations"]["en"]["connectors"]["count"], 0);
+    }
+}
@@ -0,0 +1,909 @@
+use crate::error::ConnectUtilError;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+const DEFAULT_API_URL: &str = "https://api.confluent.cloud";
+
+/// A connector as reported by the Confluent Cloud Connect API
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiveConnector {
+    pub name: String,
+    pub id: Option<String>,
+    pub connector_class: Option<String>,
+    pub state: Option<String>,
+    /// Running config; Confluent Cloud leaves out or masks sensitive values
+    pub config: HashMap<String, String>,
+}
+
+/// One partition's committed position: `kafka_topic`/`kafka_partition` and
+/// `kafka_offset` for sinks, connector-defined keys for sources
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PartitionOffset {
+    pub partition: serde_json::Map<String, serde_json::Value>,
+    pub offset: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A connector's offsets as reported by the Connect API
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ConnectorOffsets {
+    pub offsets: Vec<PartitionOffset>,
+    /// When Confluent Cloud last read the offsets from the connector
+    pub observed_at: Option<String>,
+}
+
+/// Where `offsets reset` moves a connector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OffsetReset {
+    /// Forget the stored offsets: sinks resume from `auto.offset.reset` (earliest by
+    /// default) and sources start over
+    Earliest,
+}
+
+impl std::str::FromStr for OffsetReset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "earliest" => Ok(OffsetReset::Earliest),
+            other if other.chars().next().is_some_and(|c| c.is_ascii_digit()) => Err(format!(
+                "Cannot reset to '{}': the Connect API can't seek by timestamp. Look up the offsets for that time and set them with the Connect API's PATCH offsets request",
+                s
+            )),
+            _ => Err(format!("Unknown reset target '{}' (expected: earliest)", s)),
+        }
+    }
+}
+
+/// One config definition a connector plugin declares
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginConfigDef {
+    pub name: String,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub default_value: Option<String>,
+}
+
+/// Every config a connector plugin accepts
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct PluginSchema {
+    pub configs: Vec<PluginConfigDef>,
+}
+
+/// Result of a conditional schema request
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaFetch {
+    /// The server answered 304 to `If-None-Match`; the cached copy is current
+    NotModified,
+    Fetched {
+        schema: PluginSchema,
+        etag: Option<String>,
+    },
+}
+
+/// Confluent Cloud operations used by remote features
+#[async_trait]
+pub trait ConnectApi: Send + Sync {
+    /// Lists every connector deployed to a Kafka cluster
+    async fn list_connectors(
+        &self,
+        environment_id: &str,
+        cluster_id: &str,
+    ) -> Result<Vec<LiveConnector>, ConnectUtilError>;
+
+    /// Reads a connector's current source or sink offsets
+    async fn get_offsets(
+        &self,
+        environment_id: &str,
+        cluster_id: &str,
+        connector: &str,
+    ) -> Result<ConnectorOffsets, ConnectUtilError>;
+
+    /// Versions of the managed connector plugins available to a cluster, keyed by class
+    async fn plugin_versions(
+        &self,
+        environment_id: &str,
+        cluster_id: &str,
+    ) -> Result<HashMap<String, String>, ConnectUtilError>;
+
+    /// Fetches a plugin's config definitions, sending `etag` as `If-None-Match`
+    async fn plugin_schema(
+        &self,
+        environment_id: &str,
+        cluster_id: &str,
+        connector_class: &str,
+        etag: Option<&str>,
+    ) -> Result<SchemaFetch, ConnectUtilError>;
+
+    /// Submits an offset reset; Confluent Cloud applies it asynchronously
+    async fn reset_offsets(
+        &self,
+        environment_id: &str,
+        cluster_id: &str,
+        connector: &str,
+        to: OffsetReset,
+    ) -> Result<(), ConnectUtilError>;
+}
+
+/// A Confluent Cloud environment as listed by the org API
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CloudEnvironment {
+    pub id: String,
+    #[serde(default)]
+    pub display_name: String,
+}
+
+/// A Kafka cluster as listed by the cmk API
+#[derive(Debug, Clone, PartialEq)]
+pub struct KafkaCluster {
+    pub id: String,
+    pub display_name: String,
+    /// Cloud provider and region, e.g. `AWS us-east-1`; empty when not reported
+    pub location: String,
+}
+
+/// Organization listings used to pick an environment and cluster interactively
+#[async_trait]
+pub trait OrgApi: Send + Sync {
+    /// Lists every environment the credentials can see
+    async fn list_environments(&self) -> Result<Vec<CloudEnvironment>, ConnectUtilError>;
+
+    /// Lists the Kafka clusters in an environment
+    async fn list_kafka_clusters(
+        &self,
+        environment_id: &str,
+    ) -> Result<Vec<KafkaCluster>, ConnectUtilError>;
+}
+
+/// Cloud API key credentials
+#[derive(Clone)]
+pub struct CloudCredentials {
+    pub api_key: String,
+    pub api_secret: String,
+}
+
+impl std::fmt::Debug for CloudCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CloudCredentials")
+            .field("api_key", &self.api_key)
+            .field("api_secret", &"<redacted>")
+            .finish()
+    }
+}
+
+impl CloudCredentials {
+    /// Reads the same environment variables the Confluent Terraform provider uses
+    pub fn from_env() -> Result<Self, ConnectUtilError> {
+        let read = |name: &str| {
+            std::env::var(name).map_err(|_| {
+                ConnectUtilError::Config(format!(
+                    "{} is not set; export a Confluent Cloud API key and secret or run `confluent login`",
+                    name
+                ))
+            })
+        };
+        Ok(Self {
+            api_key: read("CONFLUENT_CLOUD_API_KEY")?,
+            api_secret: read("CONFLUENT_CLOUD_API_SECRET")?,
+        })
+    }
+}
+
+/// How requests to the Cloud API authenticate
+#[derive(Clone)]
+pub enum CloudAuth {
+    /// A Cloud API key and secret, sent with basic auth
+    ApiKey(CloudCredentials),
+    /// The token `confluent login` stored for a CLI context, sent as a bearer token
+    CliLogin { context: String, token: String },
+}
+
+impl std::fmt::Debug for CloudAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CloudAuth::ApiKey(credentials) => credentials.fmt(f),
+            CloudAuth::CliLogin { context, .. } => f
+                .debug_struct("CliLogin")
+                .field("context", context)
+                .field("token", &"<redacted>")
+                .finish(),
+        }
+    }
+}
+
+impl From<CloudCredentials> for CloudAuth {
+    fn from(credentials: CloudCredentials) -> Self {
+        CloudAuth::ApiKey(credentials)
+    }
+}
+
+impl CloudAuth {
+    /// `CONFLUENT_CLOUD_API_KEY`/`CONFLUENT_CLOUD_API_SECRET` when set, otherwise the
+    /// current context of a logged-in Confluent CLI
+    pub fn from_env() -> Result<Self, ConnectUtilError> {
+        let env_error = match CloudCredentials::from_env() {
+            Ok(credentials) => return Ok(CloudAuth::ApiKey(credentials)),
+            Err(error) => error,
+        };
+        let Some(path) = cli_config_path().filter(|path| path.exists()) else {
+            return Err(env_error);
+        };
+        parse_cli_login(&std::fs::read_to_string(&path)?)?.ok_or_else(|| {
+            ConnectUtilError::Config(format!(
+                "Set CONFLUENT_CLOUD_API_KEY and CONFLUENT_CLOUD_API_SECRET, or run `confluent login` (no logged-in context in {})",
+                path.display()
+            ))
+        })
+    }
+}
+
+/// The Confluent CLI's config file, `~/.confluent/config.json`
+pub fn cli_config_path() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .filter(|home| !home.is_empty())
+        .map(|home| PathBuf::from(home).join(".confluent").join("config.json"))
+}
+
+#[derive(Debug, Deserialize)]
+struct CliConfig {
+    #[serde(default)]
+    current_context: String,
+    #[serde(default)]
+    context_states: HashMap<String, CliContextState>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CliContextState {
+    #[serde(default)]
+    auth_token: String,
+}
+
+/// The current context's login token from a Confluent CLI config file; `None` when
+/// nobody is logged in
+pub fn parse_cli_login(json: &str) -> Result<Option<CloudAuth>, ConnectUtilError> {
+    let config: CliConfig = serde_json::from_str(json)?;
+    Ok(config
+        .context_states
+        .get(&config.current_context)
+        .filter(|state| !state.auth_token.is_empty())
+        .map(|state| CloudAuth::CliLogin {
+            context: config.current_context.clone(),
+            token: state.auth_token.clone(),
+        }))
+}
+
+/// HTTP client for the Confluent Cloud REST API
+pub struct CloudClient {
+    http: reqwest::Client,
+    base_url: String,
+    auth: CloudAuth,
+}
+
+impl CloudClient {
+    pub fn new(auth: impl Into<CloudAuth>) -> Self {
+        let base_url = std::env::var("CONFLUENT_CLOUD_API_URL")
+            .unwrap_or_else(|_| DEFAULT_API_URL.to_string());
+        Self::with_base_url(auth, base_url)
+    }
+
+    /// Targets a different API endpoint, e.g. a proxy or a local mock
+    pub fn with_base_url(auth: impl Into<CloudAuth>, base_url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            auth: auth.into(),
+        }
+    }
+
+    pub fn from_env() -> Result<Self, ConnectUtilError> {
+        let auth = CloudAuth::from_env()?;
+        if let CloudAuth::CliLogin { context, .. } = &auth {
+            tracing::debug!("Authenticating with Confluent CLI context {}", context);
+        }
+        Ok(Self::new(auth))
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<T, ConnectUtilError> {
+        self.send_json(self.http.get(format!("{}{}", self.base_url, path)), path)
+            .await
+    }
+
+    async fn post_json<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &serde_json::Value,
+    ) -> Result<T, ConnectUtilError> {
+        let request = self
+            .http
+            .post(format!("{}{}", self.base_url, path))
+            .json(body);
+        self.send_json(request, path).await
+    }
+
+    async fn send_json<T: serde::de::DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+        path: &str,
+    ) -> Result<T, ConnectUtilError> {
+        self.send(request, path).await?.json().await.map_err(|e| {
+            ConnectUtilError::Unknown(format!("Unexpected response from {}: {}", path, e))
+        })
+    }
+
+    /// Sends an authenticated request, turning error statuses into errors. 304 Not
+    /// Modified is passed through for conditional requests
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+        path: &str,
+    ) -> Result<reqwest::Response, ConnectUtilError> {
+        let url = format!("{}{}", self.base_url, path);
+        let request = match &self.auth {
+            CloudAuth::ApiKey(credentials) => {
+                request.basic_auth(&credentials.api_key, Some(&credentials.api_secret))
+            }
+            CloudAuth::CliLogin { token, .. } => request.bearer_auth(token),
+        };
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ConnectUtilError::Unknown(format!("Request to {} failed: {}", url, e)))?;
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED
+            && matches!(self.auth, CloudAuth::CliLogin { .. })
+        {
+            return Err(ConnectUtilError::Config(
+                "The Confluent CLI login has expired; run `confluent login` again".to_string(),
+            ));
+        }
+        if !status.is_success() && status != reqwest::StatusCode::NOT_MODIFIED {
+            let body = response.text().await.unwrap_or_default();
+            return Err(ConnectUtilError::Config(format!(
+                "Confluent Cloud API returned {} for {}: {}",
+                status, path, body
+            )));
+        }
+        Ok(response)
+    }
+}
+#[derive(Debug, Deserialize)]
+struct ExpandedConnector {
+    #[serde(default)]
+    id: Option<ConnectorId>,
+    #[serde(default)]
+    info: Option<ConnectorInfo>,
+    #[serde(default)]
+    status: Option<ConnectorStatusInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConnectorId {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConnectorInfo {
+    #[serde(default)]
+    config: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConnectorStatusInfo {
+    connector: ConnectorState,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConnectorState {
+    state: String,
+}
+
+/// Parses the `?expand=id,info,status` connector listing, keyed by connector name
+pub fn parse_connector_listing(json: &str) -> Result<Vec<LiveConnector>, ConnectUtilError> {
+    let listing: HashMap<String, ExpandedConnector> = serde_json::from_str(json)?;
+    let mut connectors: Vec<LiveConnector> = listing
+        .into_iter()
+        .map(|(name, expanded)| {
+            let config = expanded.info.map(|info| info.config).unwrap_or_default();
+            LiveConnector {
+                connector_class: config.get("connector.class").cloned(),
+                id: expanded.id.map(|id| id.id),
+                state: expanded.status.map(|status| status.connector.state),
+                config,
+                name,
+            }
+        })
+        .collect();
+    connectors.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(connectors)
+}
+
+#[derive(Debug, Deserialize)]
+struct OffsetsResponse {
+    #[serde(default)]
+    offsets: Vec<PartitionOffset>,
+    #[serde(default)]
+    metadata: Option<OffsetsMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OffsetsMetadata {
+    #[serde(default)]
+    observed_at: Option<String>,
+}
+
+/// Parses the response of `GET .../connectors/{name}/offsets`
+pub fn parse_offsets(json: &str) -> Result<ConnectorOffsets, ConnectUtilError> {
+    let response: OffsetsResponse = serde_json::from_str(json)?;
+    Ok(ConnectorOffsets {
+        offsets: response.offsets,
+        observed_at: response.metadata.and_then(|m| m.observed_at),
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct ValidateResponse {
+    #[serde(default)]
+    configs: Vec<ValidatedConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ValidatedConfig {
+    definition: PluginConfigDef,
+}
+
+/// Parses a plugin's `config/validate` response into its config definitions
+pub fn parse_plugin_schema(json: &str) -> Result<PluginSchema, ConnectUtilError> {
+    let response: ValidateResponse = serde_json::from_str(json)?;
+    Ok(PluginSchema {
+        configs: response
+            .configs
+            .into_iter()
+            .map(|config| config.definition)
+            .collect(),
+    })
+}
+
+/// A parsed list page's items and the token of the page after it
+type ParsedPage<T> = Result<(Vec<T>, Option<String>), ConnectUtilError>;
+
+/// One page of an org or cmk API list response
+#[derive(Debug, Deserialize)]
+struct ListPage<T> {
+    #[serde(default = "Vec::new")]
+    data: Vec<T>,
+    #[serde(default)]
+    metadata: Option<ListMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListMetadata {
+    #[serde(default)]
+    next: Option<String>,
+}
+
+impl<T> ListPage<T> {
+    /// The `page_token` of the next page, taken from the `metadata.next` URL
+    fn next_page_token(&self) -> Option<String> {
+        let next = self.metadata.as_ref()?.next.as_deref()?;
+        let (_, query) = next.split_once('?')?;
+        query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("page_token="))
+            .filter(|token| !token.is_empty())
+            .map(str::to_string)
+    }
+}
+
+/// Parses one page of `GET /org/v2/environments`, with the token of the next page
+pub fn parse_environments(json: &str) -> ParsedPage<CloudEnvironment> {
+    let page: ListPage<CloudEnvironment> = serde_json::from_str(json)?;
+    let next = page.next_page_token();
+    Ok((page.data, next))
+}
+
+#[derive(Debug, Deserialize)]
+struct ClusterListing {
+    id: String,
+    #[serde(default)]
+    spec: Option<ClusterSpec>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ClusterSpec {
+    #[serde(default)]
+    display_name: String,
+    #[serde(default)]
+    cloud: String,
+    #[serde(default)]
+    region: String,
+}
+
+/// Parses one page of `GET /cmk/v2/clusters`, with the token of the next page
+pub fn parse_kafka_clusters(json: &str) -> ParsedPage<KafkaCluster> {
+    let page: ListPage<ClusterListing> = serde_json::from_str(json)?;
+    let next = page.next_page_token();
+    let clusters = page
+        .data
+        .into_iter()
+        .map(|cluster| {
+            let spec = cluster.spec.unwrap_or_default();
+            KafkaCluster {
+                id: cluster.id,
+                display_name: spec.display_name,
+                location: format!("{} {}", spec.cloud, spec.region).trim().to_string(),
+            }
+        })
+        .collect();
+    Ok((clusters, next))
+}
+
+#[derive(Debug, Deserialize)]
+struct PluginListing {
+    class: String,
+    #[serde(default)]
+    version: Option<String>,
+}
+
+#[async_trait]
+impl ConnectApi for CloudClient {
+    async fn list_connectors(
+        &self,
+        environment_id: &str,
+        cluster_id: &str,
+    ) -> Result<Vec<LiveConnector>, ConnectUtilError> {
+        let listing: serde_json::Value = self
+            .get_json(&format!(
+                "/connect/v1/environments/{}/clusters/{}/connectors?expand=id,info,status",
+                environment_id, cluster_id
+            ))
+            .await?;
+        parse_connector_listing(&listing.to_string())
+    }
+
+    async fn get_offsets(
+        &self,
+        environment_id: &str,
+        cluster_id: &str,
+        connector: &str,
+    ) -> Result<ConnectorOffsets, ConnectUtilError> {
+        let response: serde_json::Value = self
+            .get_json(&format!(
+                "/connect/v1/environments/{}/clusters/{}/connectors/{}/offsets",
+                environment_id, cluster_id, connector
+            ))
+            .await?;
+        parse_offsets(&response.to_string())
+    }
+
+    async fn plugin_versions(
+        &self,
+        environment_id: &str,
+        cluster_id: &str,
+    ) -> Result<HashMap<String, String>, ConnectUtilError> {
+        let plugins: Vec<PluginListing> = self
+            .get_json(&format!(
+                "/connect/v1/environments/{}/clusters/{}/connector-plugins",
+                environment_id, cluster_id
+            ))
+            .await?;
+        Ok(plugins
+            .into_iter()
+            .filter_map(|plugin| Some((plugin.class, plugin.version?)))
+            .collect())
+    }
+
+    /// Kafka Connect has no schema endpoint; validating a config that names only the
+    /// class returns every definition the plugin declares
+    async fn plugin_schema(
+        &self,
+        environment_id: &str,
+        cluster_id: &str,
+        connector_class: &str,
+        etag: Option<&str>,
+    ) -> Result<SchemaFetch, ConnectUtilError> {
+        let path = format!(
+            "/connect/v1/environments/{}/clusters/{}/connector-plugins/{}/config/validate",
+            environment_id, cluster_id, connector_class
+        );
+        let mut request = self
+            .http
+            .put(format!("{}{}", self.base_url, path))
+            .json(&serde_json::json!({ "connector.class": connector_class }));
+        if let Some(etag) = etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        let response = self.send(request, &path).await?;
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(SchemaFetch::NotModified);
+        }
+        let etag = response
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let body = response.text().await.map_err(|e| {
+            ConnectUtilError::Unknown(format!("Unexpected response from {}: {}", path, e))
+        })?;
+        Ok(SchemaFetch::Fetched {
+            schema: parse_plugin_schema(&body)?,
+            etag,
+        })
+    }
+
+    async fn reset_offsets(
+        &self,
+        environment_id: &str,
+        cluster_id: &str,
+        connector: &str,
+        to: OffsetReset,
+    ) -> Result<(), ConnectUtilError> {
+        let body = match to {
+            OffsetReset::Earliest => serde_json::json!({ "type": "DELETE" }),
+        };
+        let _: serde_json::Value = self
+            .post_json(
+                &format!(
+                    "/connect/v1/environments/{}/clusters/{}/connectors/{}/offsets/request",
+                    environment_id, cluster_id, connector
+                ),
+                &body,
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+impl CloudClient {
+    /// Follows a list endpoint's pages, parsing each with `parse`
+    async fn list_pages<T>(
+        &self,
+        path: &str,
+        parse: fn(&str) -> ParsedPage<T>,
+    ) -> Result<Vec<T>, ConnectUtilError> {
+        let separator = if path.contains('?') { '&' } else { '?' };
+        let mut items = Vec::new();
+        let mut token: Option<String> = None;
+        loop {
+            let page_path = match &token {
+                Some(token) => format!("{}{}page_size=100&page_token={}", path, separator, token),
+                None => format!("{}{}page_size=100", path, separator),
+            };
+            let page: serde_json::Value = self.get_json(&page_path).await?;
+            let (page_items, next) = parse(&page.to_string())?;
+            items.extend(page_items);
+            match next {
+                Some(next) if Some(&next) != token.as_ref() => token = Some(next),
+                _ => return Ok(items),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl OrgApi for CloudClient {
+    async fn list_environments(&self) -> Result<Vec<CloudEnvironment>, ConnectUtilError> {
+        let mut environments = self
+            .list_pages("/org/v2/environments", parse_environments)
+            .await?;
+        environments.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+        Ok(environments)
+    }
+
+    async fn list_kafka_clusters(
+        &self,
+        environment_id: &str,
+    ) -> Result<Vec<KafkaCluster>, ConnectUtilError> {
+        let mut clusters = self
+            .list_pages(
+                &format!("/cmk/v2/clusters?environment={}", environment_id),
+                parse_kafka_clusters,
+            )
+            .await?;
+        clusters.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+        Ok(clusters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_connector_listing() {
+        let json = r#"{
+            "orders-cdc": {
+                "id": {"id": "lcc-abc123", "id_type": "ID"},
+                "info": {"config": {"connector.class": "PostgresCdcSourceV2"}, "name": "orders-cdc", "type": "source"},
+                "status": {"name": "orders-cdc", "connector": {"state": "RUNNING", "trace": ""}, "tasks": [], "type": "source"}
+            },
+            "adhoc-datagen": {}
+        }"#;
+
+        let connectors = parse_connector_listing(json).unwrap();
+        assert_eq!(connectors.len(), 2);
+        assert_eq!(connectors[0].name, "adhoc-datagen");
+        assert_eq!(connectors[0].id, None);
+        assert_eq!(connectors[1].id.as_deref(), Some("lcc-abc123"));
+        assert_eq!(
+            connectors[1].connector_class.as_deref(),
+            Some("PostgresCdcSourceV2")
+        );
+        assert_eq!(connectors[1].state.as_deref(), Some("RUNNING"));
+        assert_eq!(
+            connectors[1].config["connector.class"],
+            "PostgresCdcSourceV2"
+        );
+        assert!(connectors[0].config.is_empty());
+    }
+
+    #[test]
+    fn test_parse_offsets() {
+        let json = r#"{
+            "name": "orders-sink",
+            "id": "lcc-abc123",
+            "offsets": [
+                {"partition": {"kafka_partition": 0, "kafka_topic": "orders"}, "offset": {"kafka_offset": 42}}
+            ],
+            "metadata": {"observed_at": "2024-03-28T17:57:48.139635200Z", "updated_at": "2024-03-27T12:00:00Z"}
+        }"#;
+
+        let offsets = parse_offsets(json).unwrap();
+        assert_eq!(offsets.offsets.len(), 1);
+        assert_eq!(offsets.offsets[0].partition["kafka_topic"], "orders");
+        assert_eq!(offsets.offsets[0].offset["kafka_offset"], 42);
+        assert_eq!(
+            offsets.observed_at.as_deref(),
+            Some("2024-03-28T17:57:48.139635200Z")
+        );
+        assert_eq!(parse_offsets("{}").unwrap(), ConnectorOffsets::default());
+    }
+
+    #[test]
+    fn test_parse_offset_reset() {
+        assert_eq!("Earliest".parse::<OffsetReset>(), Ok(OffsetReset::Earliest));
+        assert!("2024-01-01T00:00:00Z"
+            .parse::<OffsetReset>()
+            .unwrap_err()
+            .contains("can't seek by timestamp"));
+        assert!("latest".parse::<OffsetReset>().is_err());
+    }
+
+    #[test]
+    fn test_credentials_debug_redacts_secret() {
+        let credentials = CloudCredentials {
+            api_key: "KEY".to_string(),
+            api_secret: "SECRET".to_string(),
+        };
+        let debug = format!("{:?}", credentials);
+        assert!(debug.contains("KEY"));
+        assert!(!debug.contains("SECRET"));
+    }
+
+    #[test]
+    fn test_parse_plugin_schema() {
+        let json = r#"{
+            "name": "PostgresCdcSourceV2",
+            "error_count": 1,
+            "groups": ["Database details"],
+            "configs": [
+                {"definition": {"name": "database.hostname", "type": "STRING", "required": true, "default_value": "", "importance": "HIGH"},
+                 "value": {"name": "database.hostname", "value": null, "errors": ["Missing required configuration"], "visible": true}},
+                {"definition": {"name": "tasks.max", "type": "INT", "required": false, "default_value": "1"},
+                 "value": {"name": "tasks.max", "value": "1", "errors": []}}
+            ]
+        }"#;
+        let schema = parse_plugin_schema(json).unwrap();
+        assert_eq!(
+            schema.configs,
+            vec![
+                PluginConfigDef {
+                    name: "database.hostname".to_string(),
+                    required: true,
+                    default_value: Some(String::new()),
+                },
+                PluginConfigDef {
+                    name: "tasks.max".to_string(),
+                    required: false,
+                    default_value: Some("1".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_cli_login() {
+        let json = r#"{
+            "current_context": "login-dev@example.com-https://confluent.cloud",
+            "contexts": {"login-dev@example.com-https://confluent.cloud": {"platform": "confluent.cloud"}},
+            "context_states": {
+                "login-dev@example.com-https://confluent.cloud": {"auth_token": "TOKEN", "auth_refresh_token": "REFRESH"},
+                "login-old@example.com-https://confluent.cloud": {"auth_token": "OLD"}
+            }
+        }"#;
+        let auth = parse_cli_login(json).unwrap().unwrap();
+        match &auth {
+            CloudAuth::CliLogin { context, token } => {
+                assert_eq!(context, "login-dev@example.com-https://confluent.cloud");
+                assert_eq!(token, "TOKEN");
+            }
+            other => panic!("Expected a CLI login, got {:?}", other),
+        }
+        assert!(!format!("{:?}", auth).contains("TOKEN"));
+
+        assert!(parse_cli_login(r#"{"current_context": ""}"#)
+            .unwrap()
+            .is_none());
+        assert!(parse_cli_login("not json").is_err());
+    }
+
+    #[test]
+    fn test_parse_org_listings() {
+        let json = r#"{
+            "api_version": "org/v2",
+            "kind": "EnvironmentList",
+            "metadata": {"next": "https://api.confluent.cloud/org/v2/environments?page_size=100&page_token=UvmDWOB1iwfAIBPj6EYb"},
+            "data": [
+                {"api_version": "org/v2", "kind": "Environment", "id": "env-abc123", "display_name": "prod"},
+                {"id": "env-def456"}
+            ]
+        }"#;
+        let (environments, next) = parse_environments(json).unwrap();
+        assert_eq!(
+            environments,
+            vec![
+                CloudEnvironment {
+                    id: "env-abc123".to_string(),
+                    display_name: "prod".to_string(),
+                },
+                CloudEnvironment {
+                    id: "env-def456".to_string(),
+                    display_name: String::new(),
+                },
+            ]
+        );
+        assert_eq!(next.as_deref(), Some("UvmDWOB1iwfAIBPj6EYb"));
+
+        let json = r#"{
+            "metadata": {"next": null},
+            "data": [
+                {"id": "lkc-abc123", "spec": {"display_name": "orders", "cloud": "AWS", "region": "us-east-1", "environment": {"id": "env-abc123"}}},
+                {"id": "lkc-def456"}
+            ]
+        }"#;
+        let (clusters, next) = parse_kafka_clusters(json).unwrap();
+        assert_eq!(
+            clusters,
+            vec![
+                KafkaCluster {
+                    id: "lkc-abc123".to_string(),
+                    display_name: "orders".to_string(),
+                    location: "AWS us-east-1".to_string(),
+                },
+                KafkaCluster {
+                    id: "lkc-def456".to_string(),
+                    display_name: String::new(),
+                    location: String::new(),
+                },
+            ]
+        );
+        assert_eq!(next, None);
+        assert!(parse_environments("{}").unwrap().0.is_empty());
+    }
+
+    #[test]
+    fn test_with_base_url_trims_trailing_slash() {
+        let client = CloudClient::with_base_url(
+            CloudCredentials {
+                api_key: "k".to_string(),
+                api_secret: "s".to_string(),
+            },
+            "http://localhost:8080/",
+        );
+        assert_eq!(client.base_url, "http://localhost:8080");
+    }
+}
@@ -0,0 +1,176 @@
+use crate::schema_registry::CSFLE_ENABLED;
+use crate::types::is_dynamic_value;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// The type of Kafka cluster the connector runs against, as Confluent Cloud names it
+pub const DEPLOYMENT_TYPE: &str = "kafka.deployment.type";
+/// Turns off the check that the connector's topics fit the cluster's partition limit
+pub const PARTITION_VALIDATION_DISABLE: &str = "kafka.max.partition.validation.disable";
+
+/// Confluent Cloud Kafka cluster type
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClusterType {
+    Basic,
+    Standard,
+    #[default]
+    Dedicated,
+}
+
+impl std::str::FromStr for ClusterType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "basic" => Ok(ClusterType::Basic),
+            "standard" => Ok(ClusterType::Standard),
+            "dedicated" => Ok(ClusterType::Dedicated),
+            _ => Err(format!(
+                "Invalid cluster type '{}'. Use 'basic', 'standard', or 'dedicated'",
+                s
+            )),
+        }
+    }
+}
+
+impl ClusterType {
+    /// The `kafka.deployment.type` value for the cluster type
+    pub fn deployment_type(self) -> &'static str {
+        match self {
+            ClusterType::Basic => "BASIC",
+            ClusterType::Standard => "STANDARD",
+            ClusterType::Dedicated => "DEDICATED",
+        }
+    }
+
+    pub fn from_deployment_type(value: &str) -> Option<Self> {
+        [
+            ClusterType::Basic,
+            ClusterType::Standard,
+            ClusterType::Dedicated,
+        ]
+        .into_iter()
+        .find(|cluster| cluster.deployment_type().eq_ignore_ascii_case(value))
+    }
+
+    /// Basic and Standard clusters share brokers, and always enforce their
+    /// partition limit
+    pub fn is_shared(self) -> bool {
+        self != ClusterType::Dedicated
+    }
+
+    /// The `config_nonsensitive` values a connector on this cluster type gets:
+    /// the deployment type, and on shared clusters, partition validation left on
+    pub fn config_values(self) -> Vec<(&'static str, &'static str)> {
+        let mut values = vec![(DEPLOYMENT_TYPE, self.deployment_type())];
+        if self.is_shared() {
+            values.push((PARTITION_VALIDATION_DISABLE, "false"));
+        }
+        values
+    }
+
+    /// Settings the cluster type can't run, as an error message
+    pub fn unsupported(self, csfle: bool) -> Option<String> {
+        (self == ClusterType::Basic && csfle).then(|| {
+            "CSFLE isn't available on Basic clusters; use a Standard or Dedicated cluster"
+                .to_string()
+        })
+    }
+}
+
+/// Checks the settings agree with the cluster type in `kafka.deployment.type`:
+/// shared clusters can't turn off partition validation, and Basic clusters can't
+/// run CSFLE. Configs without a literal deployment type aren't checked
+pub fn check(config_nonsensitive: &HashMap<String, String>) -> Result<(), String> {
+    let known = |key: &str| {
+        config_nonsensitive
+            .get(key)
+            .filter(|value| !is_dynamic_value(value))
+    };
+    let Some(cluster) =
+        known(DEPLOYMENT_TYPE).and_then(|value| ClusterType::from_deployment_type(value))
+    else {
+        return Ok(());
+    };
+    let enabled = |key: &str| known(key).is_some_and(|value| value.eq_ignore_ascii_case("true"));
+    if cluster.is_shared() && enabled(PARTITION_VALIDATION_DISABLE) {
+        return Err(format!(
+            "'{}' can't be true on a {} cluster, which always checks partition limits; only Dedicated clusters can turn it off",
+            PARTITION_VALIDATION_DISABLE,
+            cluster.deployment_type()
+        ));
+    }
+    match cluster.unsupported(enabled(CSFLE_ENABLED)) {
+        Some(message) => Err(message),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_cluster_type() {
+        assert_eq!("Basic".parse::<ClusterType>(), Ok(ClusterType::Basic));
+        assert!("enterprise"
+            .parse::<ClusterType>()
+            .unwrap_err()
+            .contains("Use 'basic', 'standard', or 'dedicated'"));
+        assert_eq!(
+            ClusterType::from_deployment_type("standard"),
+            Some(ClusterType::Standard)
+        );
+        assert_eq!(
+            ClusterType::Dedicated.config_values(),
+            vec![(DEPLOYMENT_TYPE, "DEDICATED")]
+        );
+        assert_eq!(
+            ClusterType::Basic.config_values(),
+            vec![
+                (DEPLOYMENT_TYPE, "BASIC"),
+                (PARTITION_VALIDATION_DISABLE, "false")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        assert!(check(&config(&[
+            (DEPLOYMENT_TYPE, "DEDICATED"),
+            (PARTITION_VALIDATION_DISABLE, "true"),
+            (CSFLE_ENABLED, "true"),
+        ]))
+        .is_ok());
+        assert!(check(&config(&[
+            (DEPLOYMENT_TYPE, "STANDARD"),
+            (PARTITION_VALIDATION_DISABLE, "true"),
+        ]))
+        .unwrap_err()
+        .contains("can't be true on a STANDARD cluster"));
+        assert!(check(&config(&[
+            (DEPLOYMENT_TYPE, "STANDARD"),
+            (CSFLE_ENABLED, "true")
+        ]))
+        .is_ok());
+        assert!(check(&config(&[
+            (DEPLOYMENT_TYPE, "BASIC"),
+            (CSFLE_ENABLED, "true")
+        ]))
+        .unwrap_err()
+        .contains("CSFLE isn't available on Basic clusters"));
+        assert!(check(&config(&[
+            (DEPLOYMENT_TYPE, "${var.deployment_type}"),
+            (CSFLE_ENABLED, "true"),
+        ]))
+        .is_ok());
+    }
+}
@@ -0,0 +1,325 @@
+use crate::types::{is_dynamic_value, ConnectorConfig, ConnectorDefinition, ConnectorType};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// A connector in a module along with the file that declares it
+#[derive(Debug, Clone)]
+pub struct ModuleConnector {
+    pub file: PathBuf,
+    pub config: ConnectorConfig,
+}
+
+impl ModuleConnector {
+    pub(crate) fn location(&self) -> String {
+        format!("'{}' ({})", self.config.name, self.file.display())
+    }
+}
+
+/// Configuration that Terraform accepts but fails or misbehaves at apply time
+#[derive(Debug, Clone, PartialEq)]
+pub enum Conflict {
+    /// Two connectors share a `name`, which must be unique per cluster
+    DuplicateName {
+        name: String,
+        locations: Vec<String>,
+    },
+    /// Two connector blocks share a Terraform label
+    DuplicateLabel {
+        label: String,
+        locations: Vec<String>,
+    },
+    /// Two sources write to the same topic or overlapping topic prefixes
+    OverlappingOutput {
+        first: String,
+        second: String,
+        locations: Vec<String>,
+    },
+    /// Two sinks of the same class consume the same topic
+    DuplicateSink {
+        topic: String,
+        connector_class: String,
+        locations: Vec<String>,
+    },
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Conflict::DuplicateName { name, locations } => write!(
+                f,
+                "Connector name '{}' is used by {}",
+                name,
+                locations.join(", ")
+            ),
+            Conflict::DuplicateLabel { label, locations } => write!(
+                f,
+                "Resource label '{}' is declared by {}",
+                label,
+                locations.join(", ")
+            ),
+            Conflict::OverlappingOutput {
+                first,
+                second,
+                locations,
+            } if first == second => {
+                write!(f, "Sources {} all write to {}", locations.join(", "), first)
+            }
+            Conflict::OverlappingOutput {
+                first,
+                second,
+                locations,
+            } => write!(
+                f,
+                "Sources {} write to overlapping topics ({} and {})",
+                locations.join(", "),
+                first,
+                second
+            ),
+            Conflict::DuplicateSink {
+                topic,
+                connector_class,
+                locations,
+            } => write!(
+                f,
+                "Topic '{}' is consumed by multiple {} sinks: {}",
+                topic,
+                connector_class,
+                locations.join(", ")
+            ),
+        }
+    }
+}
+
+/// Where a source connector writes
+#[derive(Debug, Clone, PartialEq)]
+enum Output {
+    Topic(String),
+    Prefix(String),
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Output::Topic(topic) => write!(f, "topic '{}'", topic),
+            Output::Prefix(prefix) => write!(f, "topic prefix '{}'", prefix),
+        }
+    }
+}
+
+/// Whether a topic prefix covers a topic, as Debezium names `<prefix>.<schema>.<table>`
+pub(crate) fn covers(prefix: &str, topic: &str) -> bool {
+    topic == prefix || topic.starts_with(&format!("{}.", prefix))
+}
+
+impl Output {
+    fn overlaps(&self, other: &Output) -> bool {
+        match (self, other) {
+            (Output::Topic(a), Output::Topic(b)) => a == b,
+            (Output::Prefix(p), Output::Topic(t)) | (Output::Topic(t), Output::Prefix(p)) => {
+                covers(p, t)
+            }
+            (Output::Prefix(a), Output::Prefix(b)) => covers(a, b) || covers(b, a),
+        }
+    }
+}
+
+/// Literal values only; variables, function calls, and placeholders can't be compared
+pub(crate) fn literal(value: &str) -> Option<&str> {
+    let value = value.trim();
+    let unresolved = value.is_empty()
+        || value.starts_with("var.")
+        || is_dynamic_value(value)
+        || value.contains("<REPLACE_WITH_");
+    (!unresolved).then_some(value)
+}
+
+fn duplicates<'a>(
+    connectors: &'a [ModuleConnector],
+    key: impl Fn(&'a ModuleConnector) -> Option<&'a str>,
+) -> Vec<(String, Vec<String>)> {
+    let mut groups: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for connector in connectors {
+        if let Some(value) = key(connector) {
+            groups.entry(value).or_default().push(connector.location());
+        }
+    }
+    groups
+        .into_iter()
+        .filter(|(_, locations)| locations.len() > 1)
+        .map(|(value, locations)| (value.to_string(), locations))
+        .collect()
+}
+
+/// The connector's catalog name and type; aliases and other spellings of a class
+/// resolve to the same name
+fn connector_kind(config: &ConnectorConfig) -> Option<(String, ConnectorType)> {
+    ConnectorDefinition::get_connector_by_name(&config.connector_class)
+        .map(|definition| (definition.name, definition.connector_type))
+}
+
+/// Finds name, label, and topic conflicts among the connectors of one module
+pub fn find_conflicts(connectors: &[ModuleConnector]) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+
+    for (name, locations) in duplicates(connectors, |c| {
+        c.config.config.get("name").and_then(|n| literal(n))
+    }) {
+        conflicts.push(Conflict::DuplicateName { name, locations });
+    }
+    for (label, locations) in duplicates(connectors, |c| Some(c.config.name.as_str())) {
+        conflicts.push(Conflict::DuplicateLabel { label, locations });
+    }
+
+    let mut outputs: Vec<(&ModuleConnector, Output)> = Vec::new();
+    let mut sinks: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+    for connector in connectors {
+        let config = &connector.config.config;
+        match connector_kind(&connector.config) {
+            Some((_, ConnectorType::Source)) => {
+                if let Some(topic) = config.get("kafka.topic").and_then(|t| literal(t)) {
+                    outputs.push((connector, Output::Topic(topic.to_string())));
+                }
+                if let Some(prefix) = config.get("topic.prefix").and_then(|p| literal(p)) {
+                    outputs.push((connector, Output::Prefix(prefix.to_string())));
+                }
+            }
+            Some((name, ConnectorType::Sink)) => {
+                let topics = config.get("topics").map(String::as_str).unwrap_or("");
+                for topic in topics.split(',').filter_map(literal) {
+                    sinks
+                        .entry((topic.to_string(), name.clone()))
+                        .or_default()
+                        .push(connector.location());
+                }
+            }
+            None => {}
+        }
+    }
+
+    for (i, (first_connector, first)) in outputs.iter().enumerate() {
+        for (second_connector, second) in &outputs[i + 1..] {
+            if std::ptr::eq(*first_connector, *second_connector) || !first.overlaps(second) {
+                continue;
+            }
+            conflicts.push(Conflict::OverlappingOutput {
+                first: first.to_string(),
+                second: second.to_string(),
+                locations: vec![first_connector.location(), second_connector.location()],
+            });
+        }
+    }
+
+    for ((topic, connector_class), locations) in sinks {
+        if locations.len() > 1 {
+            conflicts.push(Conflict::DuplicateSink {
+                topic,
+                connector_class,
+                locations,
+            });
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn connector(label: &str, class: &str, config: &[(&str, &str)]) -> ModuleConnector {
+        ModuleConnector {
+            file: PathBuf::from(format!("{}.tf", label)),
+            config: ConnectorConfig {
+                name: label.to_string(),
+                connector_class: class.to_string(),
+                config: config
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect::<HashMap<_, _>>(),
+                sensitive_config: HashMap::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_duplicate_names_and_labels() {
+        let connectors = vec![
+            connector("orders", "DatagenSource", &[("name", "orders")]),
+            connector("orders_copy", "DatagenSource", &[("name", "orders")]),
+            connector("orders_copy", "DatagenSource", &[("name", "${var.name}")]),
+        ];
+        let conflicts = find_conflicts(&connectors);
+        assert_eq!(conflicts.len(), 2);
+        assert!(
+            matches!(&conflicts[0], Conflict::DuplicateName { name, locations }
+            if name == "orders" && locations.len() == 2)
+        );
+        assert!(
+            matches!(&conflicts[1], Conflict::DuplicateLabel { label, .. }
+            if label == "orders_copy")
+        );
+    }
+
+    #[test]
+    fn test_overlapping_source_outputs() {
+        let connectors = vec![
+            connector("a", "PostgresCdcSourceV2", &[("topic.prefix", "orders")]),
+            connector(
+                "b",
+                "PostgresCdcSourceV2",
+                &[("topic.prefix", "orders.public")],
+            ),
+            connector(
+                "c",
+                "DatagenSource",
+                &[("kafka.topic", "orders.public.items")],
+            ),
+            connector("d", "DatagenSource", &[("kafka.topic", "ordersx")]),
+            connector(
+                "e",
+                "PostgresCdcSourceV2",
+                &[("topic.prefix", "<REPLACE_WITH_TOPIC_PREFIX>")],
+            ),
+        ];
+        let conflicts = find_conflicts(&connectors);
+        // a/b, a/c and b/c overlap; d and e are independent
+        assert_eq!(conflicts.len(), 3);
+        assert_eq!(
+            conflicts[0].to_string(),
+            "Sources 'a' (a.tf), 'b' (b.tf) write to overlapping topics (topic prefix 'orders' and topic prefix 'orders.public')"
+        );
+    }
+
+    #[test]
+    fn test_identical_source_topic() {
+        let connectors = vec![
+            connector("a", "DatagenSource", &[("kafka.topic", "orders")]),
+            connector("b", "DatagenSource", &[("kafka.topic", "orders")]),
+        ];
+        let conflicts = find_conflicts(&connectors);
+        assert_eq!(
+            conflicts[0].to_string(),
+            "Sources 'a' (a.tf), 'b' (b.tf) all write to topic 'orders'"
+        );
+    }
+
+    #[test]
+    fn test_duplicate_sinks() {
+        let connectors = vec![
+            connector("a", "S3_SINK", &[("topics", "orders,refunds")]),
+            connector(
+                "b",
+                "io.confluent.connect.s3.S3SinkConnector",
+                &[("topics", "refunds")],
+            ),
+            connector("c", "ElasticsearchSink", &[("topics", "refunds")]),
+        ];
+        let conflicts = find_conflicts(&connectors);
+        assert_eq!(conflicts.len(), 1);
+        assert!(
+            matches!(&conflicts[0], Conflict::DuplicateSink { topic, connector_class, .. }
+            if topic == "refunds" && connector_class == "S3_SINK")
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use crate::error::ConnectUtilError;
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// A config the Connect worker rejected, with the worker's messages
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub key: String,
+    pub errors: Vec<String>,
+}
+
+/// Kafka Connect REST operations used against self-managed clusters
+#[async_trait]
+pub trait ConnectRestApi: Send + Sync {
+    /// Where the worker's REST API lives, for messages
+    fn url(&self) -> &str;
+
+    /// Runs the plugin's validation over `config`, returning the fields it rejects
+    async fn validate_config(
+        &self,
+        connector_class: &str,
+        config: &BTreeMap<String, String>,
+    ) -> Result<Vec<FieldError>, ConnectUtilError>;
+}
+
+/// Client for a self-managed Connect worker's REST API
+pub struct ConnectRestClient {
+    http: reqwest::Client,
+    base_url: String,
+    /// Basic auth credentials, as Instaclustr and secured workers need
+    auth: Option<(String, String)>,
+}
+
+impl ConnectRestClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            auth: None,
+        }
+    }
+
+    /// Uses basic auth from `CONNECT_REST_USERNAME` and `CONNECT_REST_PASSWORD`
+    /// when both are set
+    pub fn from_env(base_url: impl Into<String>) -> Self {
+        let mut client = Self::new(base_url);
+        let var = |name| std::env::var(name).ok().filter(|v: &String| !v.is_empty());
+        if let (Some(username), Some(password)) =
+            (var("CONNECT_REST_USERNAME"), var("CONNECT_REST_PASSWORD"))
+        {
+            client.auth = Some((username, password));
+        }
+        client
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ValidateResponse {
+    #[serde(default)]
+    configs: Vec<ValidatedConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ValidatedConfig {
+    value: ValidatedValue,
+}
+
+#[derive(Debug, Deserialize)]
+struct ValidatedValue {
+    name: String,
+    #[serde(default)]
+    errors: Vec<String>,
+}
+
+/// Parses a `PUT /connector-plugins/{class}/config/validate` response into the
+/// fields that have errors
+pub fn parse_validate_errors(json: &str) -> Result<Vec<FieldError>, ConnectUtilError> {
+    let response: ValidateResponse = serde_json::from_str(json)?;
+    Ok(response
+        .configs
+        .into_iter()
+        .filter(|config| !config.value.errors.is_empty())
+        .map(|config| FieldError {
+            key: config.value.name,
+            errors: config.value.errors,
+        })
+        .collect())
+}
+
+#[async_trait]
+impl ConnectRestApi for ConnectRestClient {
+    fn url(&self) -> &str {
+        &self.base_url
+    }
+
+    async fn validate_config(
+        &self,
+        connector_class: &str,
+        config: &BTreeMap<String, String>,
+    ) -> Result<Vec<FieldError>, ConnectUtilError> {
+        let path = format!("/connector-plugins/{}/config/validate", connector_class);
+        let url = format!("{}{}", self.base_url, path);
+        let mut request = self.http.put(&url).json(config);
+        if let Some((username, password)) = &self.auth {
+            request = request.basic_auth(username, Some(password));
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ConnectUtilError::Unknown(format!("Request to {} failed: {}", url, e)))?;
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(ConnectUtilError::Config(format!(
+                "Kafka Connect returned {} for {}: {}",
+                status, path, body
+            )));
+        }
+        parse_validate_errors(&body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_validate_errors() {
+        let json = r#"{
+            "name": "io.confluent.connect.s3.S3SinkConnector",
+            "error_count": 2,
+            "groups": ["Common", "S3"],
+            "configs": [
+                {
+                    "definition": {"name": "topics", "type": "LIST", "required": false},
+                    "value": {"name": "topics", "value": "orders", "recommended_values": [], "errors": [], "visible": true}
+                },
+                {
+                    "definition": {"name": "s3.bucket.name", "type": "STRING", "required": true},
+                    "value": {"name": "s3.bucket.name", "value": null, "recommended_values": [], "errors": ["Missing required configuration \"s3.bucket.name\" which has no default value."], "visible": true}
+                },
+                {
+                    "definition": {"name": "flush.size", "type": "INT", "required": true},
+                    "value": {"name": "flush.size", "value": "0", "errors": ["Invalid value 0 for configuration flush.size: Value must be at least 1"]}
+                }
+            ]
+        }"#;
+
+        let errors = parse_validate_errors(json).unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].key, "s3.bucket.name");
+        assert!(errors[0].errors[0].starts_with("Missing required configuration"));
+        assert_eq!(errors[1].key, "flush.size");
+        assert!(parse_validate_errors("not json").is_err());
+    }
+}
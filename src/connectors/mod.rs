@@ -1,4 +1,7 @@
-use crate::types::{ConfigField, ConnectorDefinition, ConnectorType};
+use crate::types::{
+    is_dynamic_value, ConfigField, ConfigGroup, ConnectorDefinition, ConnectorFamily,
+    ConnectorType, DataFormat, TopicStrategy,
+};
 use std::collections::HashMap;
 
 mod sinks;
@@ -25,12 +28,156 @@ pub(crate) fn config_field(
         required,
         default_value: None,
         valid_values,
+        group: infer_config_group(name),
     }
 }
 
+// Infer the UX group for a field from its name so the catalog doesn't need
+// to annotate every entry by hand. Keywords match whole dot-separated segments
+// of the key, or their start, so "port" matches "database.port" but not
+// "report" or "transport". Multi-segment keywords such as "api.key" match
+// consecutive segments, and a trailing '.' asks for the whole segment
+pub(crate) fn infer_config_group(name: &str) -> ConfigGroup {
+    let name = name.to_lowercase();
+    let segments: Vec<&str> = name.split(['.', '_', '-']).collect();
+    let matches = |keyword: &&str| {
+        let whole = keyword.ends_with('.');
+        let words: Vec<&str> = keyword.trim_end_matches('.').split('.').collect();
+        let (last, leading) = words.split_last().expect("keywords are not empty");
+        (0..segments.len()).any(|start| {
+            let window = &segments[start..];
+            window.len() > leading.len()
+                && window
+                    .iter()
+                    .zip(leading)
+                    .all(|(segment, word)| segment == word)
+                && if whole {
+                    window[leading.len()] == *last
+                } else {
+                    window[leading.len()].starts_with(last)
+                }
+        })
+    };
+    const AUTH: &[&str] = &[
+        "password",
+        "secret",
+        "token",
+        "credential",
+        "api.key",
+        "auth",
+        "user",
+        "sasl",
+        "key.store",
+        "trust.store",
+        "access.key",
+        "service.account",
+    ];
+    const CONNECTION: &[&str] = &[
+        "host",
+        "port",
+        "url",
+        "uri",
+        "endpoint",
+        "server",
+        "database",
+        "db.name",
+        "bucket",
+        "region",
+        "project",
+        "instance",
+        "account",
+        "dataset",
+        "cluster",
+        "connection",
+        "ssl",
+    ];
+    const BEHAVIOR: &[&str] = &[
+        "mode",
+        "format",
+        "topic",
+        "table",
+        "poll",
+        "batch",
+        "insert",
+        "pk.",
+        "interval",
+        "snapshot",
+        "auto.",
+        "flush",
+        "rotate",
+        "tasks",
+        "include",
+        "exclude",
+        "pushtopic",
+        "row.key",
+    ];
+
+    if AUTH.iter().any(matches) {
+        ConfigGroup::Authentication
+    } else if CONNECTION.iter().any(matches) {
+        ConfigGroup::Connection
+    } else if BEHAVIOR.iter().any(matches) {
+        ConfigGroup::Behavior
+    } else {
+        ConfigGroup::Advanced
+    }
+}
+
+/// Connectors Confluent has superseded, with the connector to migrate to
+const SUPERSEDED: &[(&str, &str)] = &[
+    ("AzureCosmosDBSource", "AzureCosmosDBSourceV2"),
+    ("HttpSource", "HttpSourceV2"),
+    ("MySqlCdcSource", "MySqlCdcSourceV2"),
+    ("PostgresCdcSource", "PostgresCdcSourceV2"),
+];
+
 impl ConnectorDefinition {
+    /// Replacement for a deprecated connector class, if it has one
+    pub fn superseded_by(connector_class: &str) -> Option<&'static str> {
+        SUPERSEDED
+            .iter()
+            .find(|(deprecated, _)| deprecated.eq_ignore_ascii_case(connector_class))
+            .map(|(_, replacement)| *replacement)
+    }
+
+    /// Whether the connector declares a field, required or optional
+    pub fn has_field(&self, name: &str) -> bool {
+        self.required_configs
+            .iter()
+            .chain(self.optional_configs.iter())
+            .any(|field| field.name == name)
+    }
+
+    /// Formats a connector accepts for a format key such as `output.data.format`.
+    /// A field with valid values restricts the list; otherwise sources can write and
+    /// sinks can read any record format, and only connectors declaring an
+    /// output format field write files such as Parquet
+    pub fn supported_formats(&self, key: &str) -> Vec<DataFormat> {
+        let field = self
+            .required_configs
+            .iter()
+            .chain(self.optional_configs.iter())
+            .find(|field| field.name == key);
+        match field.and_then(|field| field.valid_values.as_ref()) {
+            Some(valid_values) => DataFormat::ALL
+                .into_iter()
+                .filter(|format| {
+                    valid_values
+                        .iter()
+                        .any(|value| value == format.to_terraform_value())
+                })
+                .collect(),
+            None => DataFormat::ALL
+                .into_iter()
+                .filter(|format| field.is_some() || *format != DataFormat::Parquet)
+                .collect(),
+        }
+    }
+
+    /// Built-in connectors plus any registered with `--definitions`
     pub fn get_all_connectors() -> Vec<ConnectorDefinition> {
-        vec![
+        let _span = tracing::debug_span!("catalog_load").entered();
+        crate::definitions::with_external(vec![
             // Source Connectors
             activemq_source(),
             amazon_cloudwatch_logs_source(),
@@ -79,6 +226,8 @@ impl ConnectorDefinition {
             // Sink Connectors
             alloydb_sink(),
             amazon_s3_sink(),
+            google_cloud_storage_sink(),
+            azure_blob_storage_sink(),
             snowflake_sink(),
             postgresql_sink(),
             mysql_sink(),
@@ -92,7 +241,10 @@ impl ConnectorDefinition {
             jdbc_sink(),
             splunk_sink(),
             clickhouse_sink(),
-        ]
+            azure_synapse_analytics_sink(),
+            google_bigtable_sink(),
+            google_spanner_sink(),
+        ])
     }
 
     pub fn get_connectors_by_type(connector_type: &ConnectorType) -> Vec<ConnectorDefinition> {
@@ -105,10 +257,49 @@ impl ConnectorDefinition {
             .collect()
     }
 
+    /// Looks a connector up by name, `connector.class`, or alias, ignoring case. An
+    /// exact name match wins, so a definition can't be shadowed by another's alias
     pub fn get_connector_by_name(name: &str) -> Option<ConnectorDefinition> {
-        Self::get_all_connectors()
-            .into_iter()
-            .find(|connector| connector.name == name)
+        let connectors = Self::get_all_connectors();
+        match connectors
+            .iter()
+            .position(|connector| connector.name == name)
+        {
+            Some(index) => connectors.into_iter().nth(index),
+            None => connectors
+                .into_iter()
+                .find(|connector| connector.matches_class(name)),
+        }
+    }
+
+    /// Whether `class` names this connector: its name, `connector.class`, or an
+    /// alias, compared case-insensitively
+    pub fn matches_class(&self, class: &str) -> bool {
+        std::iter::once(&self.name)
+            .chain(std::iter::once(&self.connector_class))
+            .chain(self.aliases.iter())
+            .any(|candidate| candidate.eq_ignore_ascii_case(class))
+    }
+
+    /// Returns all fields (required first) organized by group in display order,
+    /// skipping groups with no fields
+    pub fn fields_by_group(&self) -> Vec<(ConfigGroup, Vec<&ConfigField>)> {
+        ConfigGroup::ALL
+            .iter()
+            .filter_map(|group| {
+                let fields: Vec<&ConfigField> = self
+                    .required_configs
+                    .iter()
+                    .chain(self.optional_configs.iter())
+                    .filter(|field| field.group == *group)
+                    .collect();
+                if fields.is_empty() {
+                    None
+                } else {
+                    Some((*group, fields))
+                }
+            })
+            .collect()
     }
 
     pub fn validate_config(
@@ -116,6 +307,7 @@ impl ConnectorDefinition {
         config_nonsensitive: &HashMap<String, String>,
         config_sensitive: &HashMap<String, String>,
     ) -> Result<(), String> {
+        let _span = tracing::debug_span!("config_checks").entered();
         // Check required configs (should be in either block)
         let mut all_config = config_nonsensitive.clone();
         all_config.extend(config_sensitive.clone());
@@ -128,6 +320,7 @@ impl ConnectorDefinition {
                 ));
             }
         }
+        self.check_topic_key(&all_config)?;
 
         // Check sensitive configs are not in non-sensitive config (unless they're empty strings)
         for sensitive_config in &self.sensitive_configs {
@@ -141,8 +334,8 @@ impl ConnectorDefinition {
             }
         }
 
-        // Validate field values
-        for (key, value) in &all_config {
+        // Validate field values; expressions can't be checked until plan time
+        for (key, value) in all_config.iter().filter(|(_, v)| !is_dynamic_value(v)) {
             if let Some(field) = self
                 .required_configs
                 .iter()
@@ -160,6 +353,300 @@ impl ConnectorDefinition {
             }
         }
 
+        if self.family == ConnectorFamily::ObjectStorage
+            && self.connector_type == ConnectorType::Sink
+        {
+            crate::storage::check(config_nonsensitive)?;
+        }
+        if let Some(dialect) = crate::debezium::Dialect::for_connector(self) {
+            crate::debezium::check(dialect, config_nonsensitive)?;
+        }
+        if self.connector_type == ConnectorType::Sink {
+            if let Some(prefix) = crate::primary_key::key_prefix(self) {
+                crate::primary_key::check(prefix, config_nonsensitive)?;
+            }
+        }
+        crate::cluster::check(config_nonsensitive)?;
+        Self::check_auth_wiring(config_nonsensitive, config_sensitive)?;
+        crate::schema_registry::check(config_nonsensitive, config_sensitive)
+    }
+
+    /// Checks the config names its topics with the key the topic strategy expects
+    /// (sinks may use `topics.regex` instead), and sets no other strategy's key the
+    /// connector doesn't declare
+    fn check_topic_key(&self, config: &HashMap<String, String>) -> Result<(), String> {
+        let expected = self.topic_strategy.key();
+        let regex =
+            self.topic_strategy == TopicStrategy::TopicsList && config.contains_key("topics.regex");
+        if !config.contains_key(expected) && !regex {
+            return Err(format!("Missing required configuration: {}", expected));
+        }
+        for other in TopicStrategy::ALL
+            .iter()
+            .map(TopicStrategy::key)
+            .filter(|key| *key != expected)
+        {
+            if config.contains_key(other) && !self.has_field(other) {
+                return Err(format!(
+                    "'{}' doesn't apply to {}, which names its topics with '{}'",
+                    other, self.name, expected
+                ));
+            }
+        }
         Ok(())
     }
+
+    /// Checks the credentials `kafka.auth.mode` needs are present, and that a
+    /// service account connector doesn't also carry an API key
+    fn check_auth_wiring(
+        config_nonsensitive: &HashMap<String, String>,
+        config_sensitive: &HashMap<String, String>,
+    ) -> Result<(), String> {
+        let mode = config_nonsensitive
+            .get("kafka.auth.mode")
+            .filter(|mode| !is_dynamic_value(mode));
+        match mode.map(String::as_str) {
+            Some("SERVICE_ACCOUNT") => {
+                if !config_nonsensitive.contains_key("kafka.service.account.id")
+                    && !config_sensitive.contains_key("kafka.service.account.id")
+                {
+                    return Err(
+                        "SERVICE_ACCOUNT auth requires 'kafka.service.account.id'".to_string()
+                    );
+                }
+                for key in ["kafka.api.key", "kafka.api.secret"] {
+                    if config_nonsensitive.contains_key(key) || config_sensitive.contains_key(key) {
+                        return Err(format!(
+                            "'{}' is not used with SERVICE_ACCOUNT auth; remove it or switch to KAFKA_API_KEY",
+                            key
+                        ));
+                    }
+                }
+            }
+            Some("KAFKA_API_KEY") => {
+                for key in ["kafka.api.key", "kafka.api.secret"] {
+                    if !config_sensitive.contains_key(key) {
+                        return Err(format!(
+                            "KAFKA_API_KEY auth requires '{}' in config_sensitive",
+                            key
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_config_group_matches_key_segments() {
+        assert_eq!(infer_config_group("database.port"), ConfigGroup::Connection);
+        assert_eq!(
+            infer_config_group("database.hostname"),
+            ConfigGroup::Connection
+        );
+        assert_eq!(
+            infer_config_group("connection.password"),
+            ConfigGroup::Authentication
+        );
+        assert_eq!(
+            infer_config_group("kafka.api.key"),
+            ConfigGroup::Authentication
+        );
+        assert_eq!(infer_config_group("pk.fields"), ConfigGroup::Behavior);
+        assert_eq!(
+            infer_config_group("consumer.override.auto.offset.reset"),
+            ConfigGroup::Behavior
+        );
+        // Keywords inside a segment don't count
+        assert_eq!(
+            infer_config_group("error.report.enabled"),
+            ConfigGroup::Advanced
+        );
+        assert_eq!(
+            infer_config_group("http.transport.timeout"),
+            ConfigGroup::Advanced
+        );
+        assert_eq!(infer_config_group("automatic.retry"), ConfigGroup::Advanced);
+        assert_eq!(infer_config_group("apikey.header"), ConfigGroup::Advanced);
+    }
+
+    #[test]
+    fn test_get_connector_by_name_aliases_and_case() {
+        let s3 = |name| ConnectorDefinition::get_connector_by_name(name).map(|c| c.name);
+        assert_eq!(s3("S3_SINK"), Some("S3_SINK".to_string()));
+        assert_eq!(s3("s3_sink"), Some("S3_SINK".to_string()));
+        assert_eq!(
+            s3("io.confluent.connect.s3.S3SinkConnector"),
+            Some("S3_SINK".to_string())
+        );
+        assert_eq!(
+            s3("IO.CONFLUENT.CONNECT.S3.S3SINKCONNECTOR"),
+            Some("S3_SINK".to_string())
+        );
+        assert_eq!(s3("S3SinkConnector"), None);
+        assert_eq!(
+            ConnectorDefinition::superseded_by("postgrescdcsource"),
+            Some("PostgresCdcSourceV2")
+        );
+    }
+
+    #[test]
+    fn test_validate_config_topic_key() {
+        let config = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        let mut sink = amazon_s3_sink();
+        sink.required_configs.clear();
+        let none = HashMap::new();
+        assert_eq!(
+            sink.validate_config(&config(&[]), &none),
+            Err("Missing required configuration: topics".to_string())
+        );
+        assert!(sink
+            .validate_config(&config(&[("topics.regex", "orders.*")]), &none)
+            .is_ok());
+        assert_eq!(
+            sink.validate_config(&config(&[("topics", "a"), ("kafka.topic", "a")]), &none),
+            Err(
+                "'kafka.topic' doesn't apply to S3_SINK, which names its topics with 'topics'"
+                    .to_string()
+            )
+        );
+
+        let mut source = postgresql_cdc_source_v2();
+        source.required_configs.clear();
+        assert!(source
+            .validate_config(&config(&[("topic.prefix", "shop")]), &none)
+            .is_ok());
+        assert!(source
+            .validate_config(&config(&[("topics", "shop")]), &none)
+            .is_err());
+    }
+
+    #[test]
+    fn test_validate_config_storage_layout() {
+        let config = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        let mut sink = google_cloud_storage_sink();
+        sink.required_configs.clear();
+        let none = HashMap::new();
+        assert!(sink
+            .validate_config(
+                &config(&[("topics", "orders"), ("path.format", "'dt'=YYYY-MM-dd")]),
+                &none
+            )
+            .is_ok());
+        assert!(sink
+            .validate_config(
+                &config(&[("topics", "orders"), ("path.format", "dt=YYYY-MM-dd")]),
+                &none
+            )
+            .unwrap_err()
+            .contains("unquoted text 'dt'"));
+        assert!(sink
+            .validate_config(
+                &config(&[
+                    ("topics", "orders"),
+                    ("partitioner.class", "HourlyPartitioner")
+                ]),
+                &none
+            )
+            .unwrap_err()
+            .contains("Invalid value 'HourlyPartitioner'"));
+
+        // Other families keep their own meaning for these keys
+        let mut source = postgresql_cdc_source_v2();
+        source.required_configs.clear();
+        assert!(source
+            .validate_config(
+                &config(&[("topic.prefix", "shop"), ("timezone", "Eastern")]),
+                &none
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn test_aliases_are_unambiguous() {
+        let connectors = ConnectorDefinition::get_all_connectors();
+        for connector in &connectors {
+            for alias in &connector.aliases {
+                let matching: Vec<&str> = connectors
+                    .iter()
+                    .filter(|c| c.matches_class(alias))
+                    .map(|c| c.name.as_str())
+                    .collect();
+                assert_eq!(matching, vec![connector.name.as_str()], "{}", alias);
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_config_auth_wiring() {
+        let connector = ConnectorDefinition::get_connector_by_name("DatagenSource").unwrap();
+        let map = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        let base = [("kafka.topic", "orders"), ("quickstart", "ORDERS")];
+        let with = |extra: &[(&str, &str)]| {
+            let mut config = map(&base);
+            config.extend(map(extra));
+            config
+        };
+
+        let service_account = with(&[("kafka.auth.mode", "SERVICE_ACCOUNT")]);
+        assert_eq!(
+            connector
+                .validate_config(&service_account, &HashMap::new())
+                .unwrap_err(),
+            "SERVICE_ACCOUNT auth requires 'kafka.service.account.id'"
+        );
+        let wired = with(&[
+            ("kafka.auth.mode", "SERVICE_ACCOUNT"),
+            (
+                "kafka.service.account.id",
+                "${confluent_service_account.orders.id}",
+            ),
+        ]);
+        assert!(connector.validate_config(&wired, &HashMap::new()).is_ok());
+        assert!(connector
+            .validate_config(&wired, &map(&[("kafka.api.key", "key")]))
+            .unwrap_err()
+            .contains("not used with SERVICE_ACCOUNT auth"));
+
+        let api_key = with(&[("kafka.auth.mode", "KAFKA_API_KEY")]);
+        assert_eq!(
+            connector
+                .validate_config(&api_key, &map(&[("kafka.api.key", "key")]))
+                .unwrap_err(),
+            "KAFKA_API_KEY auth requires 'kafka.api.secret' in config_sensitive"
+        );
+        assert!(connector
+            .validate_config(
+                &api_key,
+                &map(&[("kafka.api.key", "key"), ("kafka.api.secret", "secret")])
+            )
+            .is_ok());
+        assert!(connector
+            .validate_config(
+                &with(&[("kafka.auth.mode", "${var.auth_mode}")]),
+                &HashMap::new()
+            )
+            .is_ok());
+    }
 }
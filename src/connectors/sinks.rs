@@ -1,5 +1,61 @@
 use super::config_field;
-use crate::types::{ConnectorDefinition, ConnectorType};
+use crate::storage;
+use crate::types::{
+    ConfigField, ConnectorDefinition, ConnectorFamily, ConnectorType, TopicStrategy,
+};
+
+/// Partitioner and path layout fields shared by the object-store sinks
+fn storage_layout_fields() -> Vec<ConfigField> {
+    vec![
+        config_field(
+            storage::PARTITIONER_CLASS,
+            "Partitioner that lays out the objects",
+            "string",
+            false,
+            Some(
+                storage::PARTITIONER_CLASSES
+                    .iter()
+                    .map(|class| class.to_string())
+                    .collect(),
+            ),
+        ),
+        config_field(
+            storage::PARTITION_FIELD_NAME,
+            "Record fields the field partitioner splits objects by",
+            "list",
+            false,
+            None,
+        ),
+        config_field(
+            storage::PATH_FORMAT,
+            "Joda-Time pattern for the object path under topics.dir",
+            "string",
+            false,
+            None,
+        ),
+        config_field(
+            storage::TIME_INTERVAL,
+            "Time interval for file rotation",
+            "string",
+            false,
+            Some(vec!["HOURLY".to_string(), "DAILY".to_string()]),
+        ),
+        config_field(
+            storage::LOCALE,
+            "Locale of the time-based path",
+            "string",
+            false,
+            None,
+        ),
+        config_field(
+            storage::TIMEZONE,
+            "Timezone of the time-based path",
+            "string",
+            false,
+            None,
+        ),
+    ]
+}
 
 // Sink Connectors
 pub(crate) fn alloydb_sink() -> ConnectorDefinition {
@@ -7,7 +63,10 @@ pub(crate) fn alloydb_sink() -> ConnectorDefinition {
         name: "AlloyDBSink".to_string(),
         display_name: "AlloyDB Sink".to_string(),
         connector_class: "AlloyDBSink".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Sink,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicsList,
         description: "Write data from Kafka topics to AlloyDB".to_string(),
         required_configs: vec![
             config_field("alloydb.hostname", "AlloyDB hostname", "string", true, None),
@@ -100,7 +159,10 @@ pub(crate) fn amazon_s3_sink() -> ConnectorDefinition {
         name: "S3_SINK".to_string(),
         display_name: "Amazon S3 Sink".to_string(),
         connector_class: "S3_SINK".to_string(),
+        aliases: vec!["io.confluent.connect.s3.S3SinkConnector".to_string()],
         connector_type: ConnectorType::Sink,
+        family: ConnectorFamily::ObjectStorage,
+        topic_strategy: TopicStrategy::TopicsList,
         description: "Write data from Kafka topics to Amazon S3".to_string(),
         required_configs: vec![
             config_field("s3.bucket.name", "S3 bucket name", "string", true, None),
@@ -145,13 +207,6 @@ pub(crate) fn amazon_s3_sink() -> ConnectorDefinition {
                     "PARQUET".to_string(),
                 ]),
             ),
-            config_field(
-                "time.interval",
-                "Time interval for file rotation",
-                "string",
-                false,
-                Some(vec!["HOURLY".to_string(), "DAILY".to_string()]),
-            ),
             config_field(
                 "rotate.interval.ms",
                 "Rotation interval in milliseconds",
@@ -195,18 +250,132 @@ pub(crate) fn amazon_s3_sink() -> ConnectorDefinition {
                 None,
             ),
             config_field("s3.part.size", "S3 part size in bytes", "long", false, None),
+        ]
+        .into_iter()
+        .chain(storage_layout_fields())
+        .collect(),
+        sensitive_configs: vec![
+            "aws.access.key.id".to_string(),
+            "aws.secret.access.key".to_string(),
+        ],
+    }
+}
+
+/// Input and output formats the GCS and Azure Blob sinks accept
+fn object_store_format_fields() -> Vec<ConfigField> {
+    let formats = |values: &[&str]| Some(values.iter().map(|v| v.to_string()).collect());
+    vec![
+        config_field(
+            "input.data.format",
+            "Input data format",
+            "string",
+            false,
+            formats(&["AVRO", "JSON", "JSON_SR", "PROTOBUF", "BYTES"]),
+        ),
+        config_field(
+            "output.data.format",
+            "Output data format",
+            "string",
+            false,
+            formats(&["AVRO", "JSON", "PARQUET", "BYTES"]),
+        ),
+        config_field(
+            "flush.size",
+            "Number of records written before an object is committed",
+            "int",
+            false,
+            None,
+        ),
+        config_field(
+            "rotate.schedule.interval.ms",
+            "Wall-clock interval after which an object is committed",
+            "long",
+            false,
+            None,
+        ),
+    ]
+}
+
+pub(crate) fn google_cloud_storage_sink() -> ConnectorDefinition {
+    ConnectorDefinition {
+        name: "GcsSink".to_string(),
+        display_name: "Google Cloud Storage Sink".to_string(),
+        connector_class: "GcsSink".to_string(),
+        aliases: vec!["io.confluent.connect.gcs.GcsSinkConnector".to_string()],
+        connector_type: ConnectorType::Sink,
+        family: ConnectorFamily::ObjectStorage,
+        topic_strategy: TopicStrategy::TopicsList,
+        description: "Write data from Kafka topics to Google Cloud Storage".to_string(),
+        required_configs: vec![
+            config_field("gcs.bucket.name", "GCS bucket name", "string", true, None),
             config_field(
-                "path.format",
-                "Path format for S3 objects",
-                "string",
-                false,
+                "gcs.credentials.config",
+                "GCP service account JSON key",
+                "password",
+                true,
                 None,
             ),
         ],
-        sensitive_configs: vec![
-            "aws.access.key.id".to_string(),
-            "aws.secret.access.key".to_string(),
+        optional_configs: vec![config_field(
+            "topics.dir",
+            "Directory prefix for topics in the bucket",
+            "string",
+            false,
+            None,
+        )]
+        .into_iter()
+        .chain(object_store_format_fields())
+        .chain(storage_layout_fields())
+        .collect(),
+        sensitive_configs: vec!["gcs.credentials.config".to_string()],
+    }
+}
+
+pub(crate) fn azure_blob_storage_sink() -> ConnectorDefinition {
+    ConnectorDefinition {
+        name: "AzureBlobSink".to_string(),
+        display_name: "Azure Blob Storage Sink".to_string(),
+        connector_class: "AzureBlobSink".to_string(),
+        aliases: vec!["io.confluent.connect.azure.blob.AzureBlobStorageSinkConnector".to_string()],
+        connector_type: ConnectorType::Sink,
+        family: ConnectorFamily::ObjectStorage,
+        topic_strategy: TopicStrategy::TopicsList,
+        description: "Write data from Kafka topics to Azure Blob Storage".to_string(),
+        required_configs: vec![
+            config_field(
+                "azblob.account.name",
+                "Azure storage account name",
+                "string",
+                true,
+                None,
+            ),
+            config_field(
+                "azblob.account.key",
+                "Azure storage account key",
+                "password",
+                true,
+                None,
+            ),
+            config_field(
+                "azblob.container.name",
+                "Azure storage container name",
+                "string",
+                true,
+                None,
+            ),
         ],
+        optional_configs: vec![config_field(
+            "topics.dir",
+            "Directory prefix for topics in the container",
+            "string",
+            false,
+            None,
+        )]
+        .into_iter()
+        .chain(object_store_format_fields())
+        .chain(storage_layout_fields())
+        .collect(),
+        sensitive_configs: vec!["azblob.account.key".to_string()],
     }
 }
 
@@ -215,7 +384,10 @@ pub(crate) fn snowflake_sink() -> ConnectorDefinition {
         name: "SnowflakeSink".to_string(),
         display_name: "Snowflake Sink".to_string(),
         connector_class: "SnowflakeSink".to_string(),
+        aliases: vec!["com.snowflake.kafka.connector.SnowflakeSinkConnector".to_string()],
         connector_type: ConnectorType::Sink,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicsList,
         description: "Write data from Kafka topics to Snowflake".to_string(),
         required_configs: vec![
             config_field("snowflake.url", "Snowflake URL", "string", true, None),
@@ -319,7 +491,10 @@ pub(crate) fn postgresql_sink() -> ConnectorDefinition {
         name: "PostgresSink".to_string(),
         display_name: "PostgreSQL Sink (JDBC)".to_string(),
         connector_class: "PostgresSink".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Sink,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicsList,
         description: "Write data from Kafka topics to PostgreSQL using JDBC".to_string(),
         required_configs: vec![
             config_field("connection.host", "Database hostname", "string", true, None),
@@ -408,7 +583,10 @@ pub(crate) fn mysql_sink() -> ConnectorDefinition {
         name: "MySQLSink".to_string(),
         display_name: "MySQL Sink (JDBC)".to_string(),
         connector_class: "MySQLSink".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Sink,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicsList,
         description: "Write data from Kafka topics to MySQL using JDBC".to_string(),
         required_configs: vec![
             config_field("connection.host", "Database hostname", "string", true, None),
@@ -497,7 +675,10 @@ pub(crate) fn microsoft_sql_server_sink() -> ConnectorDefinition {
         name: "MicrosoftSqlServerSink".to_string(),
         display_name: "Microsoft SQL Server Sink (JDBC)".to_string(),
         connector_class: "MicrosoftSqlServerSink".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Sink,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicsList,
         description: "Write data from Kafka topics to Microsoft SQL Server using JDBC".to_string(),
         required_configs: vec![
             config_field("connection.host", "Database hostname", "string", true, None),
@@ -577,7 +758,10 @@ pub(crate) fn oracle_sink() -> ConnectorDefinition {
         name: "OracleSink".to_string(),
         display_name: "Oracle Sink (JDBC)".to_string(),
         connector_class: "OracleSink".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Sink,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicsList,
         description: "Write data from Kafka topics to Oracle Database using JDBC".to_string(),
         required_configs: vec![
             config_field("connection.host", "Database hostname", "string", true, None),
@@ -657,7 +841,10 @@ pub(crate) fn mongodb_sink() -> ConnectorDefinition {
         name: "MongoDBSink".to_string(),
         display_name: "MongoDB Sink".to_string(),
         connector_class: "MongoDBSink".to_string(),
+        aliases: vec!["com.mongodb.kafka.connect.MongoSinkConnector".to_string()],
         connector_type: ConnectorType::Sink,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicsList,
         description: "Write data from Kafka topics to MongoDB".to_string(),
         required_configs: vec![
             config_field("mongodb.host", "MongoDB hostname", "string", true, None),
@@ -743,7 +930,10 @@ pub(crate) fn elasticsearch_sink() -> ConnectorDefinition {
         name: "ElasticsearchSink".to_string(),
         display_name: "Elasticsearch Sink".to_string(),
         connector_class: "ElasticsearchSink".to_string(),
+        aliases: vec!["io.confluent.connect.elasticsearch.ElasticsearchSinkConnector".to_string()],
         connector_type: ConnectorType::Sink,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicsList,
         description: "Write data from Kafka topics to Elasticsearch".to_string(),
         required_configs: vec![
             config_field(
@@ -840,7 +1030,10 @@ pub(crate) fn bigquery_sink() -> ConnectorDefinition {
         name: "BigQuerySink".to_string(),
         display_name: "Google BigQuery Sink".to_string(),
         connector_class: "BigQuerySink".to_string(),
+        aliases: vec!["com.wepay.kafka.connect.bigquery.BigQuerySinkConnector".to_string()],
         connector_type: ConnectorType::Sink,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicsList,
         description: "Write data from Kafka topics to Google BigQuery".to_string(),
         required_configs: vec![
             config_field(
@@ -931,7 +1124,10 @@ pub(crate) fn redshift_sink() -> ConnectorDefinition {
         name: "RedshiftSink".to_string(),
         display_name: "Amazon Redshift Sink".to_string(),
         connector_class: "RedshiftSink".to_string(),
+        aliases: vec!["io.confluent.connect.aws.redshift.RedshiftSinkConnector".to_string()],
         connector_type: ConnectorType::Sink,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicsList,
         description: "Write data from Kafka topics to Amazon Redshift".to_string(),
         required_configs: vec![
             config_field("redshift.host", "Redshift hostname", "string", true, None),
@@ -1024,7 +1220,10 @@ pub(crate) fn databricks_sink() -> ConnectorDefinition {
         name: "DatabricksSink".to_string(),
         display_name: "Databricks Sink".to_string(),
         connector_class: "DatabricksSink".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Sink,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicsList,
         description: "Write data from Kafka topics to Databricks".to_string(),
         required_configs: vec![
             config_field(
@@ -1123,7 +1322,10 @@ pub(crate) fn jdbc_sink() -> ConnectorDefinition {
         name: "JdbcSinkConnector".to_string(),
         display_name: "JDBC Sink (Generic)".to_string(),
         connector_class: "JdbcSinkConnector".to_string(),
+        aliases: vec!["io.confluent.connect.jdbc.JdbcSinkConnector".to_string()],
         connector_type: ConnectorType::Sink,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicsList,
         description: "Write data from Kafka topics to any JDBC-compatible database".to_string(),
         required_configs: vec![
             config_field(
@@ -1195,7 +1397,10 @@ pub(crate) fn splunk_sink() -> ConnectorDefinition {
         name: "SplunkSink".to_string(),
         display_name: "Splunk Sink".to_string(),
         connector_class: "SplunkSink".to_string(),
+        aliases: vec!["com.splunk.kafka.connect.SplunkSinkConnector".to_string()],
         connector_type: ConnectorType::Sink,
+        family: ConnectorFamily::Saas,
+        topic_strategy: TopicStrategy::TopicsList,
         description: "Write data from Kafka topics to Splunk".to_string(),
         required_configs: vec![
             config_field("splunk.hec.uri", "Splunk HEC URI", "string", true, None),
@@ -1287,7 +1492,10 @@ pub(crate) fn clickhouse_sink() -> ConnectorDefinition {
         name: "ClickHouseSink".to_string(),
         display_name: "ClickHouse Sink".to_string(),
         connector_class: "ClickHouseSink".to_string(),
+        aliases: vec!["com.clickhouse.kafka.connect.ClickHouseSinkConnector".to_string()],
         connector_type: ConnectorType::Sink,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicsList,
         description: "Write data from Kafka topics to ClickHouse".to_string(),
         required_configs: vec![
             config_field("hostname", "ClickHouse hostname", "string", true, None),
@@ -1364,3 +1572,301 @@ pub(crate) fn clickhouse_sink() -> ConnectorDefinition {
         sensitive_configs: vec!["password".to_string()],
     }
 }
+
+pub(crate) fn azure_synapse_analytics_sink() -> ConnectorDefinition {
+    ConnectorDefinition {
+        name: "AzureSqlDwSink".to_string(),
+        display_name: "Azure Synapse Analytics Sink".to_string(),
+        connector_class: "AzureSqlDwSink".to_string(),
+        aliases: vec![],
+        connector_type: ConnectorType::Sink,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicsList,
+        description: "Write data from Kafka topics to Azure Synapse Analytics".to_string(),
+        required_configs: vec![
+            config_field(
+                "azure.sql.dw.server.name",
+                "Azure Synapse Analytics server name",
+                "string",
+                true,
+                None,
+            ),
+            config_field(
+                "azure.sql.dw.user",
+                "Azure Synapse Analytics user",
+                "string",
+                true,
+                None,
+            ),
+            config_field(
+                "azure.sql.dw.database.name",
+                "Azure Synapse Analytics database name",
+                "string",
+                true,
+                None,
+            ),
+            config_field(
+                "input.data.format",
+                "Input data format",
+                "string",
+                true,
+                Some(vec![
+                    "AVRO".to_string(),
+                    "JSON_SR".to_string(),
+                    "PROTOBUF".to_string(),
+                    "local.schema_formats.avro".to_string(),
+                    "local.schema_formats.json_sr".to_string(),
+                    "local.schema_formats.protobuf".to_string(),
+                ]),
+            ),
+        ],
+        optional_configs: vec![
+            config_field(
+                "azure.sql.dw.password",
+                "Azure Synapse Analytics password",
+                "string",
+                false,
+                None,
+            ),
+            config_field(
+                "insert.mode",
+                "Azure Synapse Analytics insert mode",
+                "string",
+                false,
+                Some(vec!["insert".to_string(), "INSERT".to_string()]),
+            ),
+            config_field(
+                "table.name.format",
+                "Azure Synapse Analytics table name format",
+                "string",
+                false,
+                None,
+            ),
+            config_field(
+                "auto.create",
+                "Azure Synapse Analytics auto create",
+                "boolean",
+                false,
+                Some(vec!["true".to_string(), "false".to_string()]),
+            ),
+            config_field(
+                "auto.evolve",
+                "Azure Synapse Analytics auto evolve",
+                "boolean",
+                false,
+                Some(vec!["true".to_string(), "false".to_string()]),
+            ),
+            config_field("db.timezone", "Database timezone", "string", false, None),
+            config_field(
+                "batch.size",
+                "Azure Synapse Analytics batch size",
+                "int",
+                false,
+                None,
+            ),
+        ],
+        sensitive_configs: vec!["azure.sql.dw.password".to_string()],
+    }
+}
+
+pub(crate) fn google_bigtable_sink() -> ConnectorDefinition {
+    ConnectorDefinition {
+        name: "BigTableSink".to_string(),
+        display_name: "Google Cloud BigTable Sink".to_string(),
+        connector_class: "BigTableSink".to_string(),
+        aliases: vec![],
+        connector_type: ConnectorType::Sink,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicsList,
+        description: "Write data from Kafka topics to Google Cloud BigTable".to_string(),
+        required_configs: vec![
+            config_field(
+                "gcp.bigtable.project.id",
+                "Google Cloud project ID",
+                "string",
+                true,
+                None,
+            ),
+            config_field(
+                "gcp.bigtable.instance.id",
+                "Google Cloud BigTable instance ID",
+                "string",
+                true,
+                None,
+            ),
+            config_field(
+                "input.data.format",
+                "Input data format",
+                "string",
+                true,
+                Some(vec![
+                    "AVRO".to_string(),
+                    "JSON_SR".to_string(),
+                    "PROTOBUF".to_string(),
+                    "local.schema_formats.avro".to_string(),
+                    "local.schema_formats.json_sr".to_string(),
+                    "local.schema_formats.protobuf".to_string(),
+                ]),
+            ),
+        ],
+        optional_configs: vec![
+            config_field(
+                "gcp.bigtable.credentials.json",
+                "Google Cloud credentials JSON",
+                "string",
+                false,
+                None,
+            ),
+            config_field(
+                "insert.mode",
+                "Google Cloud BigTable insert mode",
+                "string",
+                false,
+                Some(vec![
+                    "insert".to_string(),
+                    "upsert".to_string(),
+                    "INSERT".to_string(),
+                    "UPSERT".to_string(),
+                ]),
+            ),
+            config_field(
+                "table.name.format",
+                "Google Cloud BigTable table name format",
+                "string",
+                false,
+                None,
+            ),
+            config_field(
+                "bigtable.row.key.definition",
+                "Fields used to build the row key",
+                "string",
+                false,
+                None,
+            ),
+            config_field(
+                "bigtable.row.key.delimiter",
+                "Delimiter between row key fields",
+                "string",
+                false,
+                None,
+            ),
+            config_field(
+                "auto.create.tables",
+                "Google Cloud BigTable auto create tables",
+                "boolean",
+                false,
+                Some(vec!["true".to_string(), "false".to_string()]),
+            ),
+            config_field(
+                "auto.create.column.families",
+                "Google Cloud BigTable auto create column families",
+                "boolean",
+                false,
+                Some(vec!["true".to_string(), "false".to_string()]),
+            ),
+            config_field(
+                "max.batch.size",
+                "Google Cloud BigTable max batch size",
+                "int",
+                false,
+                None,
+            ),
+        ],
+        sensitive_configs: vec!["gcp.bigtable.credentials.json".to_string()],
+    }
+}
+
+pub(crate) fn google_spanner_sink() -> ConnectorDefinition {
+    ConnectorDefinition {
+        name: "SpannerSink".to_string(),
+        display_name: "Google Cloud Spanner Sink".to_string(),
+        connector_class: "SpannerSink".to_string(),
+        aliases: vec![],
+        connector_type: ConnectorType::Sink,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicsList,
+        description: "Write data from Kafka topics to Google Cloud Spanner".to_string(),
+        required_configs: vec![
+            config_field(
+                "gcp.spanner.instance.id",
+                "Google Cloud Spanner instance ID",
+                "string",
+                true,
+                None,
+            ),
+            config_field(
+                "gcp.spanner.database.id",
+                "Google Cloud Spanner database ID",
+                "string",
+                true,
+                None,
+            ),
+            config_field(
+                "input.data.format",
+                "Input data format",
+                "string",
+                true,
+                Some(vec![
+                    "AVRO".to_string(),
+                    "JSON_SR".to_string(),
+                    "PROTOBUF".to_string(),
+                    "local.schema_formats.avro".to_string(),
+                    "local.schema_formats.json_sr".to_string(),
+                    "local.schema_formats.protobuf".to_string(),
+                ]),
+            ),
+        ],
+        optional_configs: vec![
+            config_field(
+                "gcp.spanner.credentials.json",
+                "Google Cloud credentials JSON",
+                "string",
+                false,
+                None,
+            ),
+            config_field(
+                "insert.mode",
+                "Google Cloud Spanner insert mode",
+                "string",
+                false,
+                Some(vec![
+                    "insert".to_string(),
+                    "upsert".to_string(),
+                    "update".to_string(),
+                    "INSERT".to_string(),
+                    "UPSERT".to_string(),
+                    "UPDATE".to_string(),
+                ]),
+            ),
+            config_field(
+                "table.name.format",
+                "Google Cloud Spanner table name format",
+                "string",
+                false,
+                None,
+            ),
+            config_field(
+                "auto.create",
+                "Google Cloud Spanner auto create",
+                "boolean",
+                false,
+                Some(vec!["true".to_string(), "false".to_string()]),
+            ),
+            config_field(
+                "auto.evolve",
+                "Google Cloud Spanner auto evolve",
+                "boolean",
+                false,
+                Some(vec!["true".to_string(), "false".to_string()]),
+            ),
+            config_field(
+                "max.batch.size",
+                "Google Cloud Spanner max batch size",
+                "int",
+                false,
+                None,
+            ),
+        ],
+        sensitive_configs: vec!["gcp.spanner.credentials.json".to_string()],
+    }
+}
@@ -1,5 +1,5 @@
 use super::config_field;
-use crate::types::{ConnectorDefinition, ConnectorType};
+use crate::types::{ConnectorDefinition, ConnectorFamily, ConnectorType, TopicStrategy};
 
 // Source Connectors
 pub(crate) fn activemq_source() -> ConnectorDefinition {
@@ -7,7 +7,10 @@ pub(crate) fn activemq_source() -> ConnectorDefinition {
         name: "ActiveMQSource".to_string(),
         display_name: "ActiveMQ Source".to_string(),
         connector_class: "ActiveMQSource".to_string(),
+        aliases: vec!["io.confluent.connect.activemq.ActiveMQSourceConnector".to_string()],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Messaging,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read messages from ActiveMQ queues".to_string(),
         required_configs: vec![
             config_field(
@@ -89,7 +92,10 @@ pub(crate) fn amazon_cloudwatch_logs_source() -> ConnectorDefinition {
         name: "AmazonCloudWatchLogsSource".to_string(),
         display_name: "Amazon CloudWatch Logs Source".to_string(),
         connector_class: "AmazonCloudWatchLogsSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Saas,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read log data from Amazon CloudWatch Logs".to_string(),
         required_configs: vec![
             config_field("aws.region", "AWS region", "string", true, None),
@@ -172,7 +178,10 @@ pub(crate) fn amazon_dynamodb_cdc_source() -> ConnectorDefinition {
         name: "AmazonDynamoDBCdcSource".to_string(),
         display_name: "Amazon DynamoDB CDC Source".to_string(),
         connector_class: "AmazonDynamoDBCdcSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicPrefix,
         description: "Capture change data from Amazon DynamoDB tables".to_string(),
         required_configs: vec![
             config_field("aws.region", "AWS region", "string", true, None),
@@ -240,7 +249,10 @@ pub(crate) fn amazon_kinesis_source() -> ConnectorDefinition {
         name: "AmazonKinesisSource".to_string(),
         display_name: "Amazon Kinesis Source".to_string(),
         connector_class: "AmazonKinesisSource".to_string(),
+        aliases: vec!["io.confluent.connect.kinesis.KinesisSourceConnector".to_string()],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Messaging,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read data from Amazon Kinesis streams".to_string(),
         required_configs: vec![
             config_field("aws.region", "AWS region", "string", true, None),
@@ -320,7 +332,10 @@ pub(crate) fn amazon_s3_source() -> ConnectorDefinition {
         name: "AmazonS3Source".to_string(),
         display_name: "Amazon S3 Source".to_string(),
         connector_class: "AmazonS3Source".to_string(),
+        aliases: vec!["io.confluent.connect.s3.source.S3SourceConnector".to_string()],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::ObjectStorage,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read data from Amazon S3 buckets".to_string(),
         required_configs: vec![
             config_field("aws.region", "AWS region", "string", true, None),
@@ -385,7 +400,10 @@ pub(crate) fn amazon_sqs_source() -> ConnectorDefinition {
         name: "AmazonSQSSource".to_string(),
         display_name: "Amazon SQS Source".to_string(),
         connector_class: "AmazonSQSSource".to_string(),
+        aliases: vec!["io.confluent.connect.sqs.source.SqsSourceConnector".to_string()],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Messaging,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read messages from Amazon SQS queues".to_string(),
         required_configs: vec![
             config_field("aws.region", "AWS region", "string", true, None),
@@ -455,7 +473,12 @@ pub(crate) fn azure_blob_storage_source() -> ConnectorDefinition {
         name: "AzureBlobStorageSource".to_string(),
         display_name: "Azure Blob Storage Source".to_string(),
         connector_class: "AzureBlobStorageSource".to_string(),
+        aliases: vec![
+            "io.confluent.connect.azure.blob.storage.AzureBlobStorageSourceConnector".to_string(),
+        ],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::ObjectStorage,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read data from Azure Blob Storage".to_string(),
         required_configs: vec![
             config_field(
@@ -529,7 +552,10 @@ pub(crate) fn azure_cosmos_db_source() -> ConnectorDefinition {
         name: "AzureCosmosDBSource".to_string(),
         display_name: "Azure Cosmos DB Source".to_string(),
         connector_class: "AzureCosmosDBSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read data from Azure Cosmos DB".to_string(),
         required_configs: vec![
             config_field(
@@ -610,7 +636,10 @@ pub(crate) fn azure_cosmos_db_source_v2() -> ConnectorDefinition {
         name: "AzureCosmosDBSourceV2".to_string(),
         display_name: "Azure Cosmos DB Source V2".to_string(),
         connector_class: "AzureCosmosDBSourceV2".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read data from Azure Cosmos DB (V2)".to_string(),
         required_configs: vec![
             config_field(
@@ -698,7 +727,10 @@ pub(crate) fn azure_event_hubs_source() -> ConnectorDefinition {
         name: "AzureEventHubsSource".to_string(),
         display_name: "Azure Event Hubs Source".to_string(),
         connector_class: "AzureEventHubsSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Messaging,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read data from Azure Event Hubs".to_string(),
         required_configs: vec![
             config_field(
@@ -779,7 +811,10 @@ pub(crate) fn azure_service_bus_source() -> ConnectorDefinition {
         name: "AzureServiceBusSource".to_string(),
         display_name: "Azure Service Bus Source".to_string(),
         connector_class: "AzureServiceBusSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Messaging,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read messages from Azure Service Bus".to_string(),
         required_configs: vec![
             config_field(
@@ -860,7 +895,10 @@ pub(crate) fn couchbase_source() -> ConnectorDefinition {
         name: "CouchbaseSource".to_string(),
         display_name: "Couchbase Source".to_string(),
         connector_class: "CouchbaseSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read data from Couchbase".to_string(),
         required_configs: vec![
             config_field(
@@ -937,7 +975,10 @@ pub(crate) fn datagen_source() -> ConnectorDefinition {
         name: "DatagenSource".to_string(),
         display_name: "Datagen Source (development and testing)".to_string(),
         connector_class: "DatagenSource".to_string(),
+        aliases: vec!["io.confluent.kafka.connect.datagen.DatagenConnector".to_string()],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Other,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Generate test data for development and testing".to_string(),
         required_configs: vec![
             config_field(
@@ -1011,7 +1052,10 @@ pub(crate) fn github_source() -> ConnectorDefinition {
         name: "GitHubSource".to_string(),
         display_name: "GitHub Source".to_string(),
         connector_class: "GitHubSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Saas,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read data from GitHub repositories".to_string(),
         required_configs: vec![
             config_field(
@@ -1076,7 +1120,10 @@ pub(crate) fn google_cloud_pubsub_source() -> ConnectorDefinition {
         name: "GoogleCloudPubSubSource".to_string(),
         display_name: "Google Cloud Pub/Sub Source".to_string(),
         connector_class: "GoogleCloudPubSubSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Messaging,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read messages from Google Cloud Pub/Sub".to_string(),
         required_configs: vec![
             config_field(
@@ -1140,7 +1187,10 @@ pub(crate) fn http_source() -> ConnectorDefinition {
         name: "HttpSource".to_string(),
         display_name: "HTTP Source".to_string(),
         connector_class: "HttpSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Saas,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read data from HTTP endpoints".to_string(),
         required_configs: vec![config_field(
             "http.url",
@@ -1208,7 +1258,10 @@ pub(crate) fn http_source_v2() -> ConnectorDefinition {
         name: "HttpSourceV2".to_string(),
         display_name: "HTTP Source V2".to_string(),
         connector_class: "HttpSourceV2".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Saas,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read data from HTTP endpoints (V2)".to_string(),
         required_configs: vec![config_field(
             "http.url",
@@ -1277,7 +1330,10 @@ pub(crate) fn ibm_mq_source() -> ConnectorDefinition {
         name: "IBMMQSource".to_string(),
         display_name: "IBM MQ Source".to_string(),
         connector_class: "IBMMQSource".to_string(),
+        aliases: vec!["io.confluent.connect.ibm.mq.IbmMQSourceConnector".to_string()],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Messaging,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read messages from IBM MQ".to_string(),
         required_configs: vec![
             config_field("ibm.mq.hostname", "IBM MQ hostname", "string", true, None),
@@ -1332,7 +1388,10 @@ pub(crate) fn influxdb_2_source() -> ConnectorDefinition {
         name: "InfluxDB2Source".to_string(),
         display_name: "InfluxDB 2 Source".to_string(),
         connector_class: "InfluxDB2Source".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read data from InfluxDB 2".to_string(),
         required_configs: vec![
             config_field("influxdb.url", "InfluxDB URL", "string", true, None),
@@ -1380,7 +1439,10 @@ pub(crate) fn jira_source() -> ConnectorDefinition {
         name: "JiraSource".to_string(),
         display_name: "Jira Source".to_string(),
         connector_class: "JiraSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Saas,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read data from Jira".to_string(),
         required_configs: vec![
             config_field("jira.url", "Jira URL", "string", true, None),
@@ -1421,7 +1483,10 @@ pub(crate) fn mariadb_cdc_source() -> ConnectorDefinition {
         name: "MariaDBCdcSource".to_string(),
         display_name: "MariaDB CDC Source".to_string(),
         connector_class: "MariaDBCdcSource".to_string(),
+        aliases: vec!["io.debezium.connector.mariadb.MariaDbConnector".to_string()],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicPrefix,
         description: "Capture change data from MariaDB databases".to_string(),
         required_configs: vec![
             config_field(
@@ -1512,7 +1577,10 @@ pub(crate) fn microsoft_sql_server_cdc_source_v2() -> ConnectorDefinition {
         name: "MicrosoftSqlServerCdcSourceV2".to_string(),
         display_name: "Microsoft SQL Server CDC Source V2 (Debezium)".to_string(),
         connector_class: "MicrosoftSqlServerCdcSourceV2".to_string(),
+        aliases: vec!["io.debezium.connector.sqlserver.SqlServerConnector".to_string()],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicPrefix,
         description: "Capture change data from Microsoft SQL Server databases (V2)".to_string(),
         required_configs: vec![
             config_field(
@@ -1610,7 +1678,10 @@ pub(crate) fn microsoft_sql_server_source() -> ConnectorDefinition {
         name: "MicrosoftSqlServerSource".to_string(),
         display_name: "Microsoft SQL Server Source (JDBC)".to_string(),
         connector_class: "MicrosoftSqlServerSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicPrefix,
         description: "Read data from Microsoft SQL Server databases".to_string(),
         required_configs: vec![
             config_field("connection.host", "Database hostname", "string", true, None),
@@ -1685,7 +1756,10 @@ pub(crate) fn mongodb_atlas_source() -> ConnectorDefinition {
         name: "MongoDBAtlasSource".to_string(),
         display_name: "MongoDB Atlas Source".to_string(),
         connector_class: "MongoDBAtlasSource".to_string(),
+        aliases: vec!["com.mongodb.kafka.connect.MongoSourceConnector".to_string()],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicPrefix,
         description: "Read data from MongoDB Atlas".to_string(),
         required_configs: vec![
             config_field("mongodb.host", "MongoDB hostname", "string", true, None),
@@ -1760,7 +1834,10 @@ pub(crate) fn mqtt_source() -> ConnectorDefinition {
         name: "MQTTSource".to_string(),
         display_name: "MQTT Source".to_string(),
         connector_class: "MQTTSource".to_string(),
+        aliases: vec!["io.confluent.connect.mqtt.MqttSourceConnector".to_string()],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Messaging,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read messages from MQTT brokers".to_string(),
         required_configs: vec![
             config_field("mqtt.broker.url", "MQTT broker URL", "string", true, None),
@@ -1814,7 +1891,10 @@ pub(crate) fn mysql_cdc_source_v2() -> ConnectorDefinition {
         name: "MySqlCdcSourceV2".to_string(),
         display_name: "MySQL CDC Source V2 (Debezium)".to_string(),
         connector_class: "MySqlCdcSourceV2".to_string(),
+        aliases: vec!["io.debezium.connector.mysql.MySqlConnector".to_string()],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicPrefix,
         description: "Capture change data from MySQL databases (V2)".to_string(),
         required_configs: vec![
             config_field(
@@ -1926,7 +2006,10 @@ pub(crate) fn mysql_cdc_source() -> ConnectorDefinition {
         name: "MySqlCdcSource".to_string(),
         display_name: "MySQL CDC Source (Debezium V1)".to_string(),
         connector_class: "MySqlCdcSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicPrefix,
         description: "Capture change data from MySQL databases (V1)".to_string(),
         required_configs: vec![
             config_field(
@@ -2038,7 +2121,10 @@ pub(crate) fn mysql_source() -> ConnectorDefinition {
         name: "MySQLSource".to_string(),
         display_name: "MySQL Source (JDBC)".to_string(),
         connector_class: "MySQLSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicPrefix,
         description: "Read data from MySQL databases".to_string(),
         required_configs: vec![
             config_field("connection.host", "Database hostname", "string", true, None),
@@ -2113,7 +2199,10 @@ pub(crate) fn oracle_cdc_source() -> ConnectorDefinition {
         name: "OracleCdcSource".to_string(),
         display_name: "Oracle CDC Source".to_string(),
         connector_class: "OracleCdcSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicPrefix,
         description: "Capture change data from Oracle databases".to_string(),
         required_configs: vec![
             config_field(
@@ -2204,7 +2293,10 @@ pub(crate) fn oracle_xstream_cdc_source() -> ConnectorDefinition {
         name: "OracleXStreamCdcSource".to_string(),
         display_name: "Oracle XStream CDC Source".to_string(),
         connector_class: "OracleXStreamCdcSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicPrefix,
         description: "Capture change data from Oracle databases using XStream".to_string(),
         required_configs: vec![
             config_field(
@@ -2302,7 +2394,10 @@ pub(crate) fn oracle_database_source() -> ConnectorDefinition {
         name: "OracleDatabaseSource".to_string(),
         display_name: "Oracle Database Source (JDBC)".to_string(),
         connector_class: "OracleDatabaseSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicPrefix,
         description: "Read data from Oracle databases".to_string(),
         required_configs: vec![
             config_field("connection.host", "Database hostname", "string", true, None),
@@ -2377,7 +2472,10 @@ pub(crate) fn postgresql_cdc_source_v2() -> ConnectorDefinition {
         name: "PostgresCdcSourceV2".to_string(),
         display_name: "PostgreSQL CDC Source V2 (Debezium)".to_string(),
         connector_class: "PostgresCdcSourceV2".to_string(),
+        aliases: vec!["io.debezium.connector.postgresql.PostgresConnector".to_string()],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicPrefix,
         description: "Capture change data from PostgreSQL databases (V2)".to_string(),
         required_configs: vec![
             config_field(
@@ -2492,7 +2590,10 @@ pub(crate) fn postgresql_cdc_source() -> ConnectorDefinition {
         name: "PostgresCdcSource".to_string(),
         display_name: "PostgreSQL CDC Source (Debezium V1)".to_string(),
         connector_class: "PostgresCdcSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicPrefix,
         description: "Capture change data from PostgreSQL databases (V1)".to_string(),
         required_configs: vec![
             config_field(
@@ -2607,7 +2708,10 @@ pub(crate) fn postgresql_source() -> ConnectorDefinition {
         name: "PostgreSQLSource".to_string(),
         display_name: "PostgreSQL Source (JDBC)".to_string(),
         connector_class: "PostgreSQLSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicPrefix,
         description: "Read data from PostgreSQL databases".to_string(),
         required_configs: vec![
             config_field("connection.host", "Database hostname", "string", true, None),
@@ -2680,7 +2784,10 @@ pub(crate) fn rabbitmq_source() -> ConnectorDefinition {
         name: "RabbitMQSource".to_string(),
         display_name: "RabbitMQ Source".to_string(),
         connector_class: "RabbitMQSource".to_string(),
+        aliases: vec!["io.confluent.connect.rabbitmq.RabbitMQSourceConnector".to_string()],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Messaging,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read messages from RabbitMQ".to_string(),
         required_configs: vec![
             config_field("rabbitmq.host", "RabbitMQ hostname", "string", true, None),
@@ -2760,7 +2867,10 @@ pub(crate) fn salesforce_bulk_api_source() -> ConnectorDefinition {
         name: "SalesforceBulkAPISource".to_string(),
         display_name: "Salesforce Bulk API Source".to_string(),
         connector_class: "SalesforceBulkAPISource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Saas,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read data from Salesforce using Bulk API".to_string(),
         required_configs: vec![
             config_field(
@@ -2848,7 +2958,10 @@ pub(crate) fn salesforce_bulk_api_2_0_source() -> ConnectorDefinition {
         name: "SalesforceBulkAPI2_0Source".to_string(),
         display_name: "Salesforce Bulk API 2.0 Source".to_string(),
         connector_class: "SalesforceBulkAPI2_0Source".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Saas,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read data from Salesforce using Bulk API 2.0".to_string(),
         required_configs: vec![
             config_field(
@@ -2943,7 +3056,10 @@ pub(crate) fn salesforce_cdc_source() -> ConnectorDefinition {
         name: "SalesforceCdcSource".to_string(),
         display_name: "Salesforce CDC Source".to_string(),
         connector_class: "SalesforceCdcSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Saas,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Capture change data from Salesforce".to_string(),
         required_configs: vec![
             config_field(
@@ -3031,7 +3147,10 @@ pub(crate) fn salesforce_platform_event_source() -> ConnectorDefinition {
         name: "SalesforcePlatformEventSource".to_string(),
         display_name: "Salesforce Platform Event Source".to_string(),
         connector_class: "SalesforcePlatformEventSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Saas,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read Salesforce Platform Events".to_string(),
         required_configs: vec![
             config_field(
@@ -3117,7 +3236,10 @@ pub(crate) fn salesforce_pushtopic_source() -> ConnectorDefinition {
         name: "SalesforcePushTopicSource".to_string(),
         display_name: "Salesforce PushTopic Source".to_string(),
         connector_class: "SalesforcePushTopicSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Saas,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read Salesforce PushTopics".to_string(),
         required_configs: vec![
             config_field(
@@ -3198,7 +3320,10 @@ pub(crate) fn servicenow_source_v2() -> ConnectorDefinition {
         name: "ServiceNowSourceV2".to_string(),
         display_name: "ServiceNow Source V2".to_string(),
         connector_class: "ServiceNowSourceV2".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Saas,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read data from ServiceNow (V2)".to_string(),
         required_configs: vec![
             config_field("servicenow.url", "ServiceNow URL", "string", true, None),
@@ -3270,7 +3395,10 @@ pub(crate) fn sftp_source() -> ConnectorDefinition {
         name: "SFTPSource".to_string(),
         display_name: "SFTP Source".to_string(),
         connector_class: "SFTPSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::ObjectStorage,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read files from SFTP servers".to_string(),
         required_configs: vec![
             config_field("sftp.host", "SFTP hostname", "string", true, None),
@@ -3332,7 +3460,10 @@ pub(crate) fn snowflake_source() -> ConnectorDefinition {
         name: "SnowflakeSource".to_string(),
         display_name: "Snowflake Source".to_string(),
         connector_class: "SnowflakeSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Databases,
+        topic_strategy: TopicStrategy::TopicPrefix,
         description: "Read data from Snowflake".to_string(),
         required_configs: vec![
             config_field("snowflake.url", "Snowflake URL", "string", true, None),
@@ -3404,7 +3535,10 @@ pub(crate) fn zendesk_source() -> ConnectorDefinition {
         name: "ZendeskSource".to_string(),
         display_name: "Zendesk Source".to_string(),
         connector_class: "ZendeskSource".to_string(),
+        aliases: vec![],
         connector_type: ConnectorType::Source,
+        family: ConnectorFamily::Saas,
+        topic_strategy: TopicStrategy::KafkaTopicSingle,
         description: "Read data from Zendesk".to_string(),
         required_configs: vec![
             config_field("zendesk.url", "Zendesk URL", "string", true, None),
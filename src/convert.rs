@@ -0,0 +1,945 @@
+use crate::error::ConnectUtilError;
+use crate::scan::SecretScanner;
+use crate::terraform::TerraformGenerator;
+use crate::types::{is_dynamic_value, ConnectorConfig, ConnectorDefinition, ConnectorType};
+use std::collections::BTreeMap;
+
+/// Keys (or key prefixes ending in '.') that only mean something to a self-managed worker
+const SELF_MANAGED_ONLY: &[&str] = &[
+    "key.converter",
+    "value.converter",
+    "header.converter",
+    "consumer.override.",
+    "producer.override.",
+    "admin.override.",
+    "config.action.reload",
+    "plugin.path",
+    "bootstrap.servers",
+    "group.id",
+    "offset.flush.",
+    "offset.storage.",
+    "config.providers",
+];
+
+/// A connector converted from a worker `.properties` file
+#[derive(Debug, Default)]
+pub struct ConvertedConnector {
+    pub name: String,
+    pub connector_class: String,
+    pub config_nonsensitive: BTreeMap<String, String>,
+    pub config_sensitive: BTreeMap<String, String>,
+    pub warnings: Vec<String>,
+}
+
+/// Parses Java `.properties` content into ordered key/value pairs
+///
+/// Handles `#`/`!` comments, `=`/`:`/whitespace separators, backslash line
+/// continuations, and the common escapes.
+pub fn parse_properties(content: &str) -> Result<Vec<(String, String)>, ConnectUtilError> {
+    let mut entries = Vec::new();
+    let mut logical = String::new();
+
+    for (index, raw) in content.lines().enumerate() {
+        // Leading whitespace is dropped, including on continuation lines
+        let line = raw.trim_start();
+        if logical.is_empty() && (line.is_empty() || line.starts_with('#') || line.starts_with('!'))
+        {
+            continue;
+        }
+
+        // An odd number of trailing backslashes continues the line
+        let trailing = line.chars().rev().take_while(|c| *c == '\\').count();
+        if trailing % 2 == 1 {
+            logical.push_str(&line[..line.len() - 1]);
+            continue;
+        }
+        logical.push_str(line);
+
+        let (key, value) = split_property(&logical);
+        if key.is_empty() {
+            return Err(ConnectUtilError::Config(format!(
+                "Invalid property on line {}: missing key",
+                index + 1
+            )));
+        }
+        entries.push((unescape(key), unescape(value)));
+        logical.clear();
+    }
+
+    if !logical.is_empty() {
+        let (key, value) = split_property(&logical);
+        entries.push((unescape(key), unescape(value)));
+    }
+    Ok(entries)
+}
+
+/// Splits on the first unescaped `=`, `:`, or whitespace
+fn split_property(line: &str) -> (&str, &str) {
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '=' | ':' => return (line[..i].trim_end(), line[i + 1..].trim_start()),
+            c if c.is_whitespace() => {
+                let rest = line[i..].trim_start();
+                let rest = rest
+                    .strip_prefix('=')
+                    .or_else(|| rest.strip_prefix(':'))
+                    .unwrap_or(rest);
+                return (&line[..i], rest.trim_start());
+            }
+            _ => {}
+        }
+    }
+    (line, "")
+}
+
+fn unescape(value: &str) -> String {
+    let mut out = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('u') => {
+                let code: String = chars.by_ref().take(4).collect();
+                match u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+                    Some(decoded) => out.push(decoded),
+                    None => {
+                        out.push_str("\\u");
+                        out.push_str(&code);
+                    }
+                }
+            }
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
+
+/// Maps a self-managed class to the fully-managed plugin that lists it as a catalog
+/// alias, if any
+pub fn managed_plugin_for(connector_class: &str) -> Option<String> {
+    ConnectorDefinition::get_all_connectors()
+        .into_iter()
+        .find(|connector| {
+            connector
+                .aliases
+                .iter()
+                .any(|alias| alias.eq_ignore_ascii_case(connector_class))
+        })
+        .map(|connector| connector.name)
+}
+
+/// Maps a fully-managed plugin name back to its self-managed class, the first of
+/// its catalog aliases, if it has one
+pub fn self_managed_class_for(plugin: &str) -> Option<String> {
+    ConnectorDefinition::get_connector_by_name(plugin)?
+        .aliases
+        .into_iter()
+        .next()
+}
+
+/// Keys that only mean something to Confluent Cloud
+const CLOUD_ONLY: &[&str] = &[
+    "kafka.auth.mode",
+    "kafka.api.key",
+    "kafka.api.secret",
+    "kafka.service.account.id",
+    "kafka.deployment.type",
+    "kafka.max.partition.validation",
+    "kafka.max.partition.validation.disable",
+    "kafka.max.partition.validation.enable",
+    "input.data.format",
+    "output.data.format",
+];
+
+/// Builds a Kafka Connect REST `POST /connectors` payload from a parsed
+/// fully-managed connector, applying `overrides` for unresolved values
+///
+/// Returns the payload and warnings about dropped or unresolved settings.
+pub fn connect_rest_payload(
+    config: &ConnectorConfig,
+    overrides: &BTreeMap<String, String>,
+) -> Result<(serde_json::Value, Vec<String>), ConnectUtilError> {
+    let connector_class = self_managed_class_for(&config.connector_class).ok_or_else(|| {
+        ConnectUtilError::Config(format!(
+            "No self-managed equivalent is known for '{}'",
+            config.connector_class
+        ))
+    })?;
+
+    let mut warnings = Vec::new();
+    let mut rest_config = serde_json::Map::new();
+    for (key, value) in config.config.iter().chain(config.sensitive_config.iter()) {
+        if key == "name" || key == "connector.class" {
+            continue;
+        }
+        if CLOUD_ONLY.contains(&key.as_str()) {
+            warnings.push(format!(
+                "'{}' only applies to Confluent Cloud and was dropped",
+                key
+            ));
+            continue;
+        }
+        rest_config.insert(key.clone(), serde_json::Value::String(value.clone()));
+    }
+    for (key, value) in overrides {
+        rest_config.insert(key.clone(), serde_json::Value::String(value.clone()));
+    }
+    rest_config.insert(
+        "connector.class".to_string(),
+        serde_json::Value::String(connector_class.to_string()),
+    );
+
+    // Anything still pointing at Terraform variables or placeholders can't run locally
+    let mut unresolved: Vec<&String> = rest_config
+        .iter()
+        .filter(|(_, value)| {
+            value.as_str().is_some_and(|v| {
+                v.starts_with("var.") || is_dynamic_value(v) || v.contains("<REPLACE_WITH_")
+            })
+        })
+        .map(|(key, _)| key)
+        .collect();
+    unresolved.sort();
+    for key in unresolved {
+        warnings.push(format!(
+            "'{}' is unresolved; pass --set {}=<value> to test it locally",
+            key, key
+        ));
+    }
+
+    let name = config
+        .config
+        .get("name")
+        .cloned()
+        .unwrap_or_else(|| config.name.clone());
+    Ok((
+        serde_json::json!({ "name": name, "config": rest_config }),
+        warnings,
+    ))
+}
+
+/// A generated connector's config, converted for a self-managed Connect cluster
+#[derive(Debug, Default, PartialEq)]
+pub struct SelfManagedConnector {
+    pub name: String,
+    pub connector_class: String,
+    pub config: BTreeMap<String, String>,
+    pub config_sensitive: BTreeMap<String, String>,
+    /// What was dropped or replaced on the way
+    pub notes: Vec<String>,
+}
+
+/// The converter standing in for a Confluent Cloud data format, with the settings
+/// it needs; `converter` is `key.converter` or `value.converter`
+fn converter_settings(converter: &str, format: &str) -> Option<Vec<(String, String)>> {
+    let setting =
+        |suffix: &str, value: &str| (format!("{}.{}", converter, suffix), value.to_string());
+    let registry = || setting("schema.registry.url", "<REPLACE_WITH_SCHEMA_REGISTRY_URL>");
+    let (class, extra) = match format.to_uppercase().as_str() {
+        "AVRO" => ("io.confluent.connect.avro.AvroConverter", Some(registry())),
+        "JSON_SR" => (
+            "io.confluent.connect.json.JsonSchemaConverter",
+            Some(registry()),
+        ),
+        "PROTOBUF" => (
+            "io.confluent.connect.protobuf.ProtobufConverter",
+            Some(registry()),
+        ),
+        "JSON" => (
+            "org.apache.kafka.connect.json.JsonConverter",
+            Some(setting("schemas.enable", "false")),
+        ),
+        "STRING" => ("org.apache.kafka.connect.storage.StringConverter", None),
+        _ => return None,
+    };
+    Some(
+        std::iter::once((converter.to_string(), class.to_string()))
+            .chain(extra)
+            .collect(),
+    )
+}
+
+/// Converts a generated fully-managed connector for a self-managed Connect
+/// cluster: the plugin becomes its self-managed class, Cloud-only keys are
+/// dropped, and the topic data formats become converters
+pub fn self_managed_connector(
+    config: &ConnectorConfig,
+) -> Result<SelfManagedConnector, ConnectUtilError> {
+    let connector_class = self_managed_class_for(&config.connector_class).ok_or_else(|| {
+        ConnectUtilError::Config(format!(
+            "No self-managed equivalent is known for '{}'",
+            config.connector_class
+        ))
+    })?;
+    // Sinks read topics in the input formats, sources write the output formats
+    let format_keys = match ConnectorDefinition::get_connector_by_name(&config.connector_class)
+        .map(|definition| definition.connector_type)
+    {
+        Some(ConnectorType::Sink) => [
+            ("input.data.format", "value.converter"),
+            ("input.key.format", "key.converter"),
+        ],
+        _ => [
+            ("output.data.format", "value.converter"),
+            ("output.key.format", "key.converter"),
+        ],
+    };
+
+    let name = config
+        .config
+        .get("name")
+        .cloned()
+        .unwrap_or_else(|| config.name.clone());
+    let mut connector = SelfManagedConnector {
+        name: name.clone(),
+        connector_class: connector_class.to_string(),
+        ..Default::default()
+    };
+    connector.config.insert("name".to_string(), name);
+    connector
+        .config
+        .insert("connector.class".to_string(), connector_class.to_string());
+
+    let mut converters = Vec::new();
+    for (key, value, sensitive) in config
+        .config
+        .iter()
+        .map(|(key, value)| (key, value, false))
+        .chain(config.sensitive_config.iter().map(|(k, v)| (k, v, true)))
+    {
+        if key == "name" || key == "connector.class" {
+            continue;
+        }
+        let converter = format_keys
+            .iter()
+            .find(|(format_key, _)| format_key == key)
+            .and_then(|(_, converter)| converter_settings(converter, value));
+        if let Some(settings) = converter {
+            connector.notes.push(format!(
+                "'{}' was replaced by {} {}",
+                key, settings[0].0, settings[0].1
+            ));
+            converters.extend(settings);
+            continue;
+        }
+        if CLOUD_ONLY.contains(&key.as_str()) {
+            connector.notes.push(format!(
+                "'{}' only applies to Confluent Cloud and was dropped",
+                key
+            ));
+            continue;
+        }
+        let target = if sensitive {
+            &mut connector.config_sensitive
+        } else {
+            &mut connector.config
+        };
+        target.insert(key.clone(), value.clone());
+    }
+    for (key, value) in converters {
+        connector.config.entry(key).or_insert(value);
+    }
+    connector.notes.sort();
+    Ok(connector)
+}
+
+/// Keys Confluent Cloud adds to a connector's config when it reports it, which the
+/// provider has no way to set
+const CLOUD_MANAGED: &[&str] = &[
+    "cloud.environment",
+    "cloud.provider",
+    "kafka.endpoint",
+    "kafka.region",
+    "kafka.dedicated",
+    "valid.kafka.api.key",
+];
+
+/// Whether a value is masked, as Confluent Cloud shows secrets
+fn is_masked(value: &str) -> bool {
+    value.len() >= 3 && value.chars().all(|c| c == '*')
+}
+
+/// The JSON layouts `convert --from-json` reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonFormat {
+    /// `{"name": ..., "config": {...}}`, as the Connect REST API takes and returns it
+    ConnectRest,
+    /// The Confluent Cloud UI export, `{"id": {...}, "info": {"name", "type",
+    /// "config"}, "status": {...}}`, on its own or keyed by connector name
+    CloudExport,
+    /// A bare config map with `connector.class`, as the Confluent CLI takes it
+    ConfigMap,
+}
+
+impl JsonFormat {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            JsonFormat::ConnectRest => "Connect REST payload",
+            JsonFormat::CloudExport => "Confluent Cloud export",
+            JsonFormat::ConfigMap => "connector config map",
+        }
+    }
+}
+
+/// Which layout the JSON is in, and the connector object within it
+fn detect_json_format(
+    value: &serde_json::Value,
+) -> Result<(JsonFormat, &serde_json::Map<String, serde_json::Value>), ConnectUtilError> {
+    let object = value
+        .as_object()
+        .ok_or_else(|| ConnectUtilError::Config("Connector JSON must be an object".to_string()))?;
+    let is_export = |object: &serde_json::Map<String, serde_json::Value>| {
+        object
+            .get("info")
+            .and_then(|info| info.get("config"))
+            .is_some_and(|config| config.is_object())
+    };
+    if is_export(object) {
+        return Ok((JsonFormat::CloudExport, object));
+    }
+    // `?expand=` listings key each connector by name
+    let exports: Vec<(&String, &serde_json::Map<String, serde_json::Value>)> = object
+        .iter()
+        .filter_map(|(name, value)| Some((name, value.as_object().filter(|o| is_export(o))?)))
+        .collect();
+    match exports.as_slice() {
+        [(_, export)] if object.len() == 1 => return Ok((JsonFormat::CloudExport, export)),
+        [_, _, ..] => {
+            let names: Vec<&str> = exports.iter().map(|(name, _)| name.as_str()).collect();
+            return Err(ConnectUtilError::Config(format!(
+                "The export holds {} connectors ({}); convert one at a time, e.g. with jq '.[\"{}\"]'",
+                names.len(),
+                names.join(", "),
+                names[0]
+            )));
+        }
+        _ => {}
+    }
+    if object
+        .get("config")
+        .is_some_and(|config| config.is_object())
+    {
+        return Ok((JsonFormat::ConnectRest, object));
+    }
+    if object.contains_key("connector.class") {
+        return Ok((JsonFormat::ConfigMap, object));
+    }
+    Err(ConnectUtilError::Config(
+        "Unrecognized connector JSON: expected a Connect REST payload ({\"name\", \"config\"}), a Confluent Cloud export ({\"info\": {\"config\"}}), or a config map with connector.class".to_string(),
+    ))
+}
+
+/// Config map entries as strings. Numbers and booleans are written as text, and
+/// `null` entries are left out; nested values can't be config and are reported
+fn config_entries(
+    config: &serde_json::Map<String, serde_json::Value>,
+    warnings: &mut Vec<String>,
+) -> Vec<(String, String)> {
+    let mut entries = Vec::new();
+    for (key, value) in config {
+        match value {
+            serde_json::Value::String(value) => entries.push((key.clone(), value.clone())),
+            serde_json::Value::Number(_) | serde_json::Value::Bool(_) => {
+                entries.push((key.clone(), value.to_string()))
+            }
+            serde_json::Value::Null => {}
+            _ => warnings.push(format!(
+                "'{}' isn't a string value and can't go in the connector's config; dropped",
+                key
+            )),
+        }
+    }
+    entries
+}
+
+/// Converts connector JSON into fully-managed connector config: a Connect REST
+/// payload, a Confluent Cloud UI export, or a bare config map. For an export,
+/// the envelope's connector ID, type, and state are checked or reported, since
+/// the resource has no place for them
+pub fn convert_json(content: &str) -> Result<(JsonFormat, ConvertedConnector), ConnectUtilError> {
+    let value: serde_json::Value = serde_json::from_str(content)
+        .map_err(|e| ConnectUtilError::Config(format!("Invalid connector JSON: {}", e)))?;
+    let (format, object) = detect_json_format(&value)?;
+    let mut warnings = Vec::new();
+    let (envelope_name, config) = match format {
+        JsonFormat::ConfigMap => (None, object),
+        JsonFormat::ConnectRest => (
+            object.get("name").and_then(|name| name.as_str()),
+            object["config"]
+                .as_object()
+                .expect("detected config object"),
+        ),
+        JsonFormat::CloudExport => {
+            let info = &object["info"];
+            (
+                info.get("name").and_then(|name| name.as_str()),
+                info["config"].as_object().expect("detected config object"),
+            )
+        }
+    };
+    let mut entries = config_entries(config, &mut warnings);
+    if let Some(name) = envelope_name.filter(|_| !config.contains_key("name")) {
+        entries.push(("name".to_string(), name.to_string()));
+    }
+    let mut converted = convert_entries(entries, "Connector JSON")?;
+
+    if format == JsonFormat::CloudExport {
+        for key in object.keys() {
+            if !["id", "info", "status"].contains(&key.as_str()) {
+                warnings.push(format!(
+                    "'{}' in the export isn't part of the connector resource; ignored",
+                    key
+                ));
+            }
+        }
+        if let Some(id) = object
+            .get("id")
+            .and_then(|id| id.get("id"))
+            .and_then(|id| id.as_str())
+        {
+            // The provider imports connectors by name, not ID
+            warnings.push(format!(
+                "Connector ID '{}' can't be set on the resource; to manage the existing connector, import it with terraform import confluent_connector.{} \"{}\"",
+                id,
+                TerraformGenerator::resource_name(&converted.name),
+                TerraformGenerator::connector_import_id(
+                    "<environment_id>",
+                    "<kafka_cluster_id>",
+                    &converted.name
+                )
+            ));
+        }
+        let exported_type = object["info"].get("type").and_then(|t| t.as_str());
+        let catalog_type = ConnectorDefinition::get_connector_by_name(&converted.connector_class)
+            .map(|definition| match definition.connector_type {
+                ConnectorType::Source => "source",
+                ConnectorType::Sink => "sink",
+            });
+        if let (Some(exported), Some(catalog)) = (exported_type, catalog_type) {
+            if !exported.eq_ignore_ascii_case(catalog) {
+                warnings.push(format!(
+                    "The export calls the connector a {}, but {} is a {} in the catalog",
+                    exported, converted.connector_class, catalog
+                ));
+            }
+        }
+        let state = object
+            .get("status")
+            .and_then(|status| status.get("connector"))
+            .and_then(|connector| connector.get("state"))
+            .and_then(|state| state.as_str());
+        if state.is_some_and(|state| state.eq_ignore_ascii_case("PAUSED")) {
+            warnings.push(
+                "The connector is paused; set var.status to \"PAUSED\" to keep it paused"
+                    .to_string(),
+            );
+        }
+    }
+    warnings.append(&mut converted.warnings);
+    converted.warnings = warnings;
+    Ok((format, converted))
+}
+
+fn is_self_managed_only(key: &str) -> bool {
+    SELF_MANAGED_ONLY.iter().any(|entry| {
+        if entry.ends_with('.') {
+            key.starts_with(entry)
+        } else {
+            key == *entry || key.starts_with(&format!("{}.", entry))
+        }
+    })
+}
+
+/// Converts a worker `.properties` file into fully-managed connector config
+///
+/// Sensitive values are replaced with placeholders so converted files never
+/// carry credentials into Terraform.
+pub fn convert_properties(content: &str) -> Result<ConvertedConnector, ConnectUtilError> {
+    convert_entries(parse_properties(content)?, "Properties file")
+}
+
+/// Converts config entries from `what` into fully-managed connector config
+fn convert_entries(
+    entries: Vec<(String, String)>,
+    what: &str,
+) -> Result<ConvertedConnector, ConnectUtilError> {
+    let scanner = SecretScanner::new();
+    let mut converted = ConvertedConnector::default();
+
+    let raw_class = entries
+        .iter()
+        .find(|(key, _)| key == "connector.class")
+        .map(|(_, value)| value.clone())
+        .ok_or_else(|| ConnectUtilError::Config(format!("{} has no connector.class", what)))?;
+    converted.connector_class = match managed_plugin_for(&raw_class) {
+        Some(plugin) => plugin,
+        None if ConnectorDefinition::get_connector_by_name(&raw_class).is_some() => raw_class,
+        None => {
+            converted.warnings.push(format!(
+                "No fully-managed plugin is known for '{}'; set connector.class manually",
+                raw_class
+            ));
+            raw_class
+        }
+    };
+    let definition = ConnectorDefinition::get_connector_by_name(&converted.connector_class);
+
+    for (key, value) in entries {
+        match key.as_str() {
+            "name" => converted.name = value,
+            "connector.class" => {}
+            _ if is_self_managed_only(&key) => converted.warnings.push(format!(
+                "'{}' only applies to self-managed workers and was dropped",
+                key
+            )),
+            _ if CLOUD_MANAGED.contains(&key.as_str()) => converted.warnings.push(format!(
+                "'{}' is set by Confluent Cloud and can't be managed in the provider; dropped",
+                key
+            )),
+            _ => {
+                let sensitive = definition
+                    .as_ref()
+                    .is_some_and(|d| d.sensitive_configs.contains(&key))
+                    || scanner.is_sensitive_key(&key)
+                    || is_masked(&value);
+                if sensitive {
+                    converted
+                        .config_sensitive
+                        .insert(key, "<REPLACE_WITH_ACTUAL_VALUE>".to_string());
+                } else {
+                    converted.config_nonsensitive.insert(key, value);
+                }
+            }
+        }
+    }
+    converted.config_nonsensitive.insert(
+        "connector.class".to_string(),
+        converted.connector_class.clone(),
+    );
+    if converted.name.is_empty() {
+        return Err(ConnectUtilError::Config(format!(
+            "{} has no connector name",
+            what
+        )));
+    }
+    converted
+        .config_nonsensitive
+        .insert("name".to_string(), converted.name.clone());
+
+    Ok(converted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROPERTIES: &str = r#"
+# Orders CDC
+name=orders-cdc
+connector.class=io.debezium.connector.postgresql.PostgresConnector
+database.hostname = db.internal
+database.password: hunter2
+table.include.list=public.orders,\
+    public.refunds
+key.converter=io.confluent.connect.avro.AvroConverter
+value.converter.schema.registry.url=http://sr:8081
+consumer.override.max.poll.records=500
+tasks.max 2
+"#;
+
+    #[test]
+    fn test_parse_properties() {
+        let entries = parse_properties(PROPERTIES).unwrap();
+        assert_eq!(entries[0], ("name".to_string(), "orders-cdc".to_string()));
+        assert!(entries.contains(&("database.hostname".to_string(), "db.internal".to_string())));
+        assert!(entries.contains(&("database.password".to_string(), "hunter2".to_string())));
+        assert!(entries.contains(&(
+            "table.include.list".to_string(),
+            "public.orders,public.refunds".to_string()
+        )));
+        assert!(entries.contains(&("tasks.max".to_string(), "2".to_string())));
+    }
+
+    #[test]
+    fn test_parse_properties_escapes() {
+        let entries = parse_properties("path\\ with\\ spaces=a\\tb\\u0041\n! comment\n").unwrap();
+        assert_eq!(
+            entries,
+            vec![("path with spaces".to_string(), "a\tbA".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_convert_properties() {
+        let converted = convert_properties(PROPERTIES).unwrap();
+        assert_eq!(converted.name, "orders-cdc");
+        assert_eq!(converted.connector_class, "PostgresCdcSourceV2");
+        assert_eq!(
+            converted.config_nonsensitive.get("connector.class"),
+            Some(&"PostgresCdcSourceV2".to_string())
+        );
+        assert_eq!(
+            converted.config_sensitive.get("database.password"),
+            Some(&"<REPLACE_WITH_ACTUAL_VALUE>".to_string())
+        );
+        assert!(!converted
+            .config_nonsensitive
+            .contains_key("database.password"));
+        assert!(!converted.config_nonsensitive.contains_key("key.converter"));
+        assert_eq!(converted.warnings.len(), 3);
+        assert!(converted.warnings[0].contains("'key.converter'"));
+    }
+
+    #[test]
+    fn test_convert_properties_unknown_class() {
+        let converted =
+            convert_properties("name=legacy\nconnector.class=com.example.LegacySource\n").unwrap();
+        assert_eq!(converted.connector_class, "com.example.LegacySource");
+        assert!(converted.warnings[0].contains("No fully-managed plugin"));
+    }
+
+    #[test]
+    fn test_convert_properties_requires_class_and_name() {
+        assert!(convert_properties("name=orders\n").is_err());
+        assert!(convert_properties("connector.class=S3_SINK\n").is_err());
+    }
+
+    const CLOUD_EXPORT: &str = r#"{
+      "id": {"id": "lcc-abc123", "id_type": "ID"},
+      "info": {
+        "name": "orders-archive",
+        "type": "source",
+        "config": {
+          "connector.class": "S3_SINK",
+          "name": "orders-archive",
+          "topics": "orders",
+          "kafka.api.key": "****************",
+          "s3.credentials.secret": "****************",
+          "cloud.provider": "aws",
+          "kafka.endpoint": "SASL_SSL://pkc-123.us-west-2.aws.confluent.cloud:9092",
+          "tasks.max": 2,
+          "errors.tolerance": null
+        }
+      },
+      "status": {"connector": {"state": "PAUSED"}, "tasks": [], "type": "sink"},
+      "extensions": []
+    }"#;
+
+    #[test]
+    fn test_convert_json_cloud_export() {
+        let (format, converted) = convert_json(CLOUD_EXPORT).unwrap();
+        assert_eq!(format, JsonFormat::CloudExport);
+        assert_eq!(converted.name, "orders-archive");
+        assert_eq!(converted.connector_class, "S3_SINK");
+        assert_eq!(converted.config_nonsensitive["tasks.max"], "2");
+        assert!(!converted
+            .config_nonsensitive
+            .contains_key("errors.tolerance"));
+        assert!(!converted.config_nonsensitive.contains_key("cloud.provider"));
+        assert!(!converted.config_nonsensitive.contains_key("kafka.endpoint"));
+        // Masked values are secrets, whatever the key
+        assert_eq!(
+            converted.config_sensitive.keys().collect::<Vec<_>>(),
+            vec!["kafka.api.key", "s3.credentials.secret"]
+        );
+
+        let warnings = converted.warnings.join("\n");
+        assert!(
+            warnings.contains("'extensions' in the export isn't part of the connector resource")
+        );
+        assert!(warnings.contains(
+            "terraform import confluent_connector.orders_archive \"<environment_id>/<kafka_cluster_id>/orders-archive\""
+        ));
+        assert!(warnings.contains("The export calls the connector a source, but S3_SINK is a sink"));
+        assert!(warnings.contains("set var.status to \"PAUSED\""));
+        assert!(warnings.contains("'cloud.provider' is set by Confluent Cloud"));
+        assert!(warnings.contains("'kafka.endpoint' is set by Confluent Cloud"));
+
+        // Listings key each connector by name
+        let keyed = format!("{{\"orders-archive\": {}}}", CLOUD_EXPORT);
+        assert_eq!(convert_json(&keyed).unwrap().0, JsonFormat::CloudExport);
+        let two = format!("{{\"a\": {}, \"b\": {}}}", CLOUD_EXPORT, CLOUD_EXPORT);
+        assert!(convert_json(&two)
+            .unwrap_err()
+            .to_string()
+            .contains("holds 2 connectors (a, b)"));
+    }
+
+    #[test]
+    fn test_convert_json_connect_rest_and_config_map() {
+        let (format, converted) = convert_json(
+            r#"{"name": "orders-cdc", "config": {
+                 "connector.class": "io.debezium.connector.postgresql.PostgresConnector",
+                 "database.password": "hunter2",
+                 "key.converter": "io.confluent.connect.avro.AvroConverter"}, "tasks": []}"#,
+        )
+        .unwrap();
+        assert_eq!(format, JsonFormat::ConnectRest);
+        assert_eq!(converted.name, "orders-cdc");
+        assert_eq!(converted.connector_class, "PostgresCdcSourceV2");
+        assert!(converted.config_sensitive.contains_key("database.password"));
+        assert!(converted.warnings[0].contains("'key.converter'"));
+
+        let (format, converted) =
+            convert_json(r#"{"connector.class": "DatagenSource", "name": "orders"}"#).unwrap();
+        assert_eq!(format, JsonFormat::ConfigMap);
+        assert_eq!(converted.name, "orders");
+
+        assert!(convert_json(r#"{"connector": "x"}"#)
+            .unwrap_err()
+            .to_string()
+            .contains("Unrecognized connector JSON"));
+        assert!(convert_json("[]").is_err());
+        assert!(
+            convert_json(r#"{"config": {"connector.class": "DatagenSource"}}"#)
+                .unwrap_err()
+                .to_string()
+                .contains("Connector JSON has no connector name")
+        );
+    }
+
+    #[test]
+    fn test_connect_rest_payload() {
+        let config = ConnectorConfig {
+            name: "orders_archive".to_string(),
+            connector_class: "S3_SINK".to_string(),
+            config: [
+                ("connector.class", "S3_SINK"),
+                ("name", "orders-archive"),
+                ("kafka.auth.mode", "SERVICE_ACCOUNT"),
+                ("s3.bucket.name", "<REPLACE_WITH_BUCKET_NAME>"),
+                ("topics", "orders"),
+            ]
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+            sensitive_config: [(
+                "aws.secret.access.key".to_string(),
+                "var.aws_secret".to_string(),
+            )]
+            .into_iter()
+            .collect(),
+        };
+        let overrides =
+            BTreeMap::from([("aws.secret.access.key".to_string(), "local".to_string())]);
+
+        let (payload, warnings) = connect_rest_payload(&config, &overrides).unwrap();
+        assert_eq!(payload["name"], "orders-archive");
+        assert_eq!(
+            payload["config"]["connector.class"],
+            "io.confluent.connect.s3.S3SinkConnector"
+        );
+        assert_eq!(payload["config"]["aws.secret.access.key"], "local");
+        assert!(payload["config"].get("kafka.auth.mode").is_none());
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("'kafka.auth.mode'"));
+        assert!(warnings[1].contains("--set s3.bucket.name=<value>"));
+    }
+
+    #[test]
+    fn test_connect_rest_payload_unknown_plugin() {
+        let config = ConnectorConfig {
+            name: "x".to_string(),
+            connector_class: "ZendeskSource".to_string(),
+            config: Default::default(),
+            sensitive_config: Default::default(),
+        };
+        assert!(connect_rest_payload(&config, &BTreeMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_self_managed_connector() {
+        let config = ConnectorConfig {
+            name: "orders_cdc".to_string(),
+            connector_class: "PostgresCdcSourceV2".to_string(),
+            config: [
+                ("connector.class", "PostgresCdcSourceV2"),
+                ("name", "orders-cdc"),
+                ("kafka.auth.mode", "SERVICE_ACCOUNT"),
+                ("output.data.format", "AVRO"),
+                ("output.key.format", "STRING"),
+                ("topic.prefix", "shop"),
+            ]
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+            sensitive_config: [
+                ("database.password", "<REPLACE_WITH_DATABASE_PASSWORD>"),
+                ("kafka.api.secret", "var.kafka_api_secret"),
+            ]
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+        };
+
+        let connector = self_managed_connector(&config).unwrap();
+        assert_eq!(connector.name, "orders-cdc");
+        assert_eq!(
+            connector.config["connector.class"],
+            "io.debezium.connector.postgresql.PostgresConnector"
+        );
+        assert_eq!(
+            connector.config["value.converter"],
+            "io.confluent.connect.avro.AvroConverter"
+        );
+        assert!(connector
+            .config
+            .contains_key("value.converter.schema.registry.url"));
+        assert_eq!(
+            connector.config["key.converter"],
+            "org.apache.kafka.connect.storage.StringConverter"
+        );
+        assert_eq!(connector.config["topic.prefix"], "shop");
+        assert!(!connector.config.contains_key("kafka.auth.mode"));
+        assert!(!connector.config.contains_key("output.key.format"));
+        assert!(!connector.config.contains_key("output.data.format"));
+        assert_eq!(
+            connector.config_sensitive.keys().collect::<Vec<_>>(),
+            vec!["database.password"]
+        );
+        assert_eq!(connector.notes.len(), 4);
+
+        let unknown = ConnectorConfig {
+            connector_class: "ZendeskSource".to_string(),
+            ..config
+        };
+        assert!(self_managed_connector(&unknown).is_err());
+    }
+
+    #[test]
+    fn test_managed_plugin_for() {
+        assert_eq!(
+            managed_plugin_for("io.confluent.connect.s3.S3SinkConnector").as_deref(),
+            Some("S3_SINK")
+        );
+        assert_eq!(
+            managed_plugin_for("io.debezium.connector.postgresql.PostgresConnector").as_deref(),
+            Some("PostgresCdcSourceV2")
+        );
+        assert_eq!(managed_plugin_for("com.example.Unknown"), None);
+        assert_eq!(managed_plugin_for("S3_SINK"), None);
+    }
+
+    #[test]
+    fn test_self_managed_class_for() {
+        assert_eq!(
+            self_managed_class_for("S3_SINK").as_deref(),
+            Some("io.confluent.connect.s3.S3SinkConnector")
+        );
+        assert_eq!(self_managed_class_for("AlloyDBSink"), None);
+        assert_eq!(self_managed_class_for("com.example.Unknown"), None);
+    }
+}
@@ -0,0 +1,145 @@
+use crate::cloud::LiveConnector;
+use crate::conflicts::ModuleConnector;
+use crate::types::is_dynamic_value;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Whether a connector is both deployed and declared in Terraform
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CoverageStatus {
+    /// Deployed and declared by a `confluent_connector` resource
+    Managed,
+    /// Deployed, but no resource declares it
+    Unmanaged,
+    /// Declared, but not deployed to the cluster
+    Missing,
+}
+
+impl fmt::Display for CoverageStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CoverageStatus::Managed => "managed",
+            CoverageStatus::Unmanaged => "unmanaged",
+            CoverageStatus::Missing => "missing",
+        })
+    }
+}
+
+/// A connector name with its deployment and its declaring resource, when each exists
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectorCoverage {
+    pub name: String,
+    pub status: CoverageStatus,
+    pub live: Option<LiveConnector>,
+    /// File and resource label of the declaring resource
+    pub resource: Option<(PathBuf, String)>,
+}
+
+/// Matches deployed connectors to declared ones by connector name, sorted by name.
+/// Declarations whose name is dynamic can't be matched and are left out
+pub fn coverage(live: Vec<LiveConnector>, declared: &[ModuleConnector]) -> Vec<ConnectorCoverage> {
+    let declared_name = |declared: &ModuleConnector| {
+        declared
+            .config
+            .config
+            .get("name")
+            .filter(|name| !is_dynamic_value(name))
+            .cloned()
+    };
+    let resource =
+        |declared: &ModuleConnector| (declared.file.clone(), declared.config.name.clone());
+
+    let mut rows: Vec<ConnectorCoverage> = live
+        .into_iter()
+        .map(|connector| {
+            let resource = declared
+                .iter()
+                .find(|d| declared_name(d).as_deref() == Some(connector.name.as_str()))
+                .map(resource);
+            ConnectorCoverage {
+                name: connector.name.clone(),
+                status: if resource.is_some() {
+                    CoverageStatus::Managed
+                } else {
+                    CoverageStatus::Unmanaged
+                },
+                live: Some(connector),
+                resource,
+            }
+        })
+        .collect();
+    for connector in declared {
+        let Some(name) = declared_name(connector) else {
+            continue;
+        };
+        if rows.iter().any(|row| row.name == name) {
+            continue;
+        }
+        rows.push(ConnectorCoverage {
+            name,
+            status: CoverageStatus::Missing,
+            live: None,
+            resource: Some(resource(connector)),
+        });
+    }
+    rows.sort_by(|a, b| a.name.cmp(&b.name));
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ConnectorConfig;
+    use std::collections::HashMap;
+
+    fn declared(label: &str, name: &str) -> ModuleConnector {
+        ModuleConnector {
+            file: PathBuf::from("main.tf"),
+            config: ConnectorConfig {
+                name: label.to_string(),
+                connector_class: "DatagenSource".to_string(),
+                config: HashMap::from([("name".to_string(), name.to_string())]),
+                sensitive_config: HashMap::new(),
+            },
+        }
+    }
+
+    fn live(name: &str) -> LiveConnector {
+        LiveConnector {
+            name: name.to_string(),
+            id: None,
+            connector_class: None,
+            state: Some("RUNNING".to_string()),
+            config: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_coverage() {
+        let rows = coverage(
+            vec![live("orders"), live("adhoc")],
+            &[
+                declared("orders", "orders"),
+                declared("refunds", "refunds"),
+                declared("per_env", "${var.env}-orders"),
+            ],
+        );
+        let summary: Vec<(&str, CoverageStatus)> = rows
+            .iter()
+            .map(|row| (row.name.as_str(), row.status))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("adhoc", CoverageStatus::Unmanaged),
+                ("orders", CoverageStatus::Managed),
+                ("refunds", CoverageStatus::Missing),
+            ]
+        );
+        assert_eq!(
+            rows[1].resource,
+            Some((PathBuf::from("main.tf"), "orders".to_string()))
+        );
+        assert!(rows[2].live.is_none());
+    }
+}
@@ -0,0 +1,308 @@
+use crate::types::{is_dynamic_value, ConnectorDefinition, DebeziumOptions};
+use regex::Regex;
+use std::collections::HashMap;
+
+/// How often the connector emits heartbeats while its tables are quiet
+pub const HEARTBEAT_INTERVAL_MS: &str = "heartbeat.interval.ms";
+/// Statement run against the database on every heartbeat
+pub const HEARTBEAT_ACTION_QUERY: &str = "heartbeat.action.query";
+/// Table the connector watches for signals such as `execute-snapshot`
+pub const SIGNAL_DATA_COLLECTION: &str = "signal.data.collection";
+/// Rows read per chunk of an incremental snapshot
+pub const INCREMENTAL_SNAPSHOT_CHUNK_SIZE: &str = "incremental.snapshot.chunk.size";
+
+/// Interval written when `--heartbeat-table` is given without `--heartbeat-interval`
+pub const DEFAULT_HEARTBEAT_INTERVAL_MS: u64 = 10_000;
+
+/// The database behind a Debezium-based source, which decides how its signal and
+/// heartbeat tables are named and set up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    Postgres,
+    /// MySQL and MariaDB
+    MySql,
+    SqlServer,
+}
+
+impl Dialect {
+    /// The dialect of a Debezium-based source; None for every other connector
+    pub fn for_connector(connector: &ConnectorDefinition) -> Option<Dialect> {
+        match connector.name.as_str() {
+            "PostgresCdcSourceV2" | "PostgresCdcSource" => Some(Dialect::Postgres),
+            "MySqlCdcSourceV2" | "MySqlCdcSource" | "MariaDBCdcSource" => Some(Dialect::MySql),
+            "MicrosoftSqlServerCdcSourceV2" => Some(Dialect::SqlServer),
+            _ => None,
+        }
+    }
+
+    /// The form Debezium expects for `signal.data.collection`
+    fn collection_format(&self) -> &'static str {
+        match self {
+            Dialect::Postgres => "schema.table",
+            Dialect::MySql => "database.table",
+            Dialect::SqlServer => "database.schema.table",
+        }
+    }
+
+    /// The upsert run on each heartbeat, keeping one row's timestamp current; SQL
+    /// Server sources don't run heartbeat queries
+    pub fn heartbeat_query(&self, table: &str) -> Option<String> {
+        match self {
+            Dialect::Postgres => Some(format!(
+                "INSERT INTO {} (id, ts) VALUES (1, now()) ON CONFLICT (id) DO UPDATE SET ts = EXCLUDED.ts",
+                table
+            )),
+            Dialect::MySql => Some(format!(
+                "INSERT INTO {} (id, ts) VALUES (1, NOW()) ON DUPLICATE KEY UPDATE ts = VALUES(ts)",
+                table
+            )),
+            Dialect::SqlServer => None,
+        }
+    }
+}
+
+/// Checks a signal table is a literal name in the form the dialect expects, e.g.
+/// `public.debezium_signal` for Postgres or `inventory.dbo.debezium_signal` for
+/// SQL Server
+pub fn check_signal_table(dialect: Dialect, table: &str) -> Result<(), String> {
+    check_table_name(table, dialect.collection_format(), "signal table")
+}
+
+/// Checks a heartbeat table is a literal `schema.table` (`database.table` for MySQL)
+pub fn check_heartbeat_table(dialect: Dialect, table: &str) -> Result<(), String> {
+    if dialect == Dialect::SqlServer {
+        return Err(
+            "SQL Server sources don't run heartbeat queries; use --heartbeat-interval alone"
+                .to_string(),
+        );
+    }
+    check_table_name(table, dialect.collection_format(), "heartbeat table")
+}
+
+fn check_table_name(table: &str, format: &str, what: &str) -> Result<(), String> {
+    let identifier = Regex::new(r"^[A-Za-z_][A-Za-z0-9_$]*$").expect("valid regex");
+    let parts: Vec<&str> = table.split('.').collect();
+    if parts.len() != format.split('.').count() || !parts.iter().all(|p| identifier.is_match(p)) {
+        return Err(format!(
+            "Invalid {} '{}': expected a literal {}",
+            what, table, format
+        ));
+    }
+    Ok(())
+}
+
+/// Checks a Debezium source's heartbeat and signal settings agree: the interval and
+/// chunk size are numbers, a heartbeat query has heartbeats to run on, incremental
+/// snapshots have a signal table to be triggered from, and that table is named in
+/// the dialect's form
+pub fn check(
+    dialect: Dialect,
+    config_nonsensitive: &HashMap<String, String>,
+) -> Result<(), String> {
+    let known = |key: &str| {
+        config_nonsensitive
+            .get(key)
+            .filter(|value| !is_dynamic_value(value))
+    };
+    let interval = match known(HEARTBEAT_INTERVAL_MS) {
+        Some(value) => Some(value.parse::<u64>().map_err(|_| {
+            format!(
+                "'{}' must be a number of milliseconds, not '{}'",
+                HEARTBEAT_INTERVAL_MS, value
+            )
+        })?),
+        None => None,
+    };
+    if config_nonsensitive.contains_key(HEARTBEAT_ACTION_QUERY)
+        && interval.is_none_or(|interval| interval == 0)
+        && !config_nonsensitive
+            .get(HEARTBEAT_INTERVAL_MS)
+            .is_some_and(|value| is_dynamic_value(value))
+    {
+        return Err(format!(
+            "'{}' only runs when '{}' is above 0",
+            HEARTBEAT_ACTION_QUERY, HEARTBEAT_INTERVAL_MS
+        ));
+    }
+    if let Some(table) = known(SIGNAL_DATA_COLLECTION) {
+        check_signal_table(dialect, table)?;
+    }
+    if let Some(size) = known(INCREMENTAL_SNAPSHOT_CHUNK_SIZE) {
+        if !size.parse::<u32>().is_ok_and(|size| size > 0) {
+            return Err(format!(
+                "'{}' must be a positive number of rows, not '{}'",
+                INCREMENTAL_SNAPSHOT_CHUNK_SIZE, size
+            ));
+        }
+        if !config_nonsensitive.contains_key(SIGNAL_DATA_COLLECTION) {
+            return Err(format!(
+                "'{}' needs '{}' to trigger incremental snapshots",
+                INCREMENTAL_SNAPSHOT_CHUNK_SIZE, SIGNAL_DATA_COLLECTION
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The signal table as `table.include.list` names it: SQL Server lists tables
+/// without their database
+pub fn captured_name(dialect: Dialect, signal_table: &str) -> &str {
+    match (dialect, signal_table.split_once('.')) {
+        (Dialect::SqlServer, Some((_, table))) => table,
+        _ => signal_table,
+    }
+}
+
+/// Comment lines with the database-side setup the heartbeat and signal tables
+/// need, written above the connector resource; empty without either table
+pub fn setup_comments(dialect: Dialect, options: &DebeziumOptions) -> String {
+    let mut lines = Vec::new();
+    if let Some(signal) = &options.signal_table {
+        let table = captured_name(dialect, signal);
+        lines.push(format!(
+            "Create the signal table {} and capture it with the connector:",
+            signal
+        ));
+        lines.push(format!(
+            "  CREATE TABLE {} (id VARCHAR(42) PRIMARY KEY, type VARCHAR(32) NOT NULL, data VARCHAR(2048) NULL);",
+            table
+        ));
+        match dialect {
+            Dialect::Postgres => lines.push(format!(
+                "  ALTER PUBLICATION dbz_publication ADD TABLE {};  -- unless the publication is FOR ALL TABLES",
+                table
+            )),
+            Dialect::MySql => lines.push(format!(
+                "  GRANT INSERT ON {} TO <database.user>;  -- incremental snapshots write watermarks to it",
+                table
+            )),
+            Dialect::SqlServer => {
+                let (schema, name) = table.split_once('.').unwrap_or(("dbo", table));
+                lines.push(format!(
+                    "  EXEC sys.sp_cdc_enable_table @source_schema = N'{}', @source_name = N'{}', @role_name = NULL;",
+                    schema, name
+                ));
+            }
+        }
+        let prefix = signal.rsplit_once('.').map_or("", |(prefix, _)| prefix);
+        lines.push("Start an incremental snapshot by inserting a signal:".to_string());
+        lines.push(format!(
+            "  INSERT INTO {} (id, type, data) VALUES ('snapshot-1', 'execute-snapshot', '{{\"data-collections\": [\"{}.my_table\"], \"type\": \"incremental\"}}');",
+            table, prefix
+        ));
+    }
+    if let Some(heartbeat) = &options.heartbeat_table {
+        let timestamp = match dialect {
+            Dialect::Postgres => "TIMESTAMPTZ",
+            _ => "DATETIME",
+        };
+        lines.push(format!(
+            "Create the heartbeat table {} for heartbeat.action.query to update:",
+            heartbeat
+        ));
+        lines.push(format!(
+            "  CREATE TABLE {} (id INT PRIMARY KEY, ts {} NOT NULL);",
+            heartbeat, timestamp
+        ));
+        lines.push(format!(
+            "  GRANT INSERT, UPDATE ON {} TO <database.user>;",
+            heartbeat
+        ));
+    }
+    lines.iter().map(|line| format!("# {}\n", line)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_check_table_names() {
+        assert!(check_signal_table(Dialect::Postgres, "public.debezium_signal").is_ok());
+        assert!(check_signal_table(Dialect::SqlServer, "inventory.dbo.debezium_signal").is_ok());
+        let error = check_signal_table(Dialect::SqlServer, "dbo.debezium_signal").unwrap_err();
+        assert!(error.contains("expected a literal database.schema.table"));
+        assert!(check_signal_table(Dialect::MySql, "inventory\\..*").is_err());
+        assert!(check_heartbeat_table(Dialect::MySql, "inventory.debezium_heartbeat").is_ok());
+        assert!(
+            check_heartbeat_table(Dialect::SqlServer, "inventory.dbo.heartbeat")
+                .unwrap_err()
+                .contains("don't run heartbeat queries")
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let postgres = |pairs: &[(&str, &str)]| check(Dialect::Postgres, &config(pairs));
+        assert!(postgres(&[
+            (HEARTBEAT_INTERVAL_MS, "10000"),
+            (HEARTBEAT_ACTION_QUERY, "INSERT INTO public.heartbeat ..."),
+            (SIGNAL_DATA_COLLECTION, "public.debezium_signal"),
+            (INCREMENTAL_SNAPSHOT_CHUNK_SIZE, "2048"),
+        ])
+        .is_ok());
+        assert!(postgres(&[(HEARTBEAT_ACTION_QUERY, "SELECT 1")])
+            .unwrap_err()
+            .contains("only runs when"));
+        assert!(postgres(&[
+            (HEARTBEAT_INTERVAL_MS, "0"),
+            (HEARTBEAT_ACTION_QUERY, "SELECT 1")
+        ])
+        .is_err());
+        assert!(postgres(&[(HEARTBEAT_INTERVAL_MS, "10s")]).is_err());
+        assert!(postgres(&[(INCREMENTAL_SNAPSHOT_CHUNK_SIZE, "1024")])
+            .unwrap_err()
+            .contains("needs 'signal.data.collection'"));
+        assert!(postgres(&[
+            (SIGNAL_DATA_COLLECTION, "public.debezium_signal"),
+            (INCREMENTAL_SNAPSHOT_CHUNK_SIZE, "0"),
+        ])
+        .is_err());
+        assert!(postgres(&[(SIGNAL_DATA_COLLECTION, "debezium_signal")]).is_err());
+        // Interpolated values are checked at plan time
+        assert!(postgres(&[
+            (HEARTBEAT_INTERVAL_MS, "${var.heartbeat_ms}"),
+            (HEARTBEAT_ACTION_QUERY, "SELECT 1"),
+        ])
+        .is_ok());
+    }
+
+    #[test]
+    fn test_setup_comments() {
+        let options = DebeziumOptions {
+            heartbeat_table: Some("public.debezium_heartbeat".to_string()),
+            signal_table: Some("public.debezium_signal".to_string()),
+            ..Default::default()
+        };
+        let comments = setup_comments(Dialect::Postgres, &options);
+        assert!(comments.lines().all(|line| line.starts_with('#')));
+        assert!(comments.contains("CREATE TABLE public.debezium_signal (id VARCHAR(42)"));
+        assert!(
+            comments.contains("ALTER PUBLICATION dbz_publication ADD TABLE public.debezium_signal")
+        );
+        assert!(comments.contains(r#"["public.my_table"]"#));
+        assert!(comments.contains("ts TIMESTAMPTZ NOT NULL"));
+
+        let options = DebeziumOptions {
+            signal_table: Some("inventory.dbo.debezium_signal".to_string()),
+            ..Default::default()
+        };
+        let comments = setup_comments(Dialect::SqlServer, &options);
+        assert!(comments.contains("CREATE TABLE dbo.debezium_signal "));
+        assert!(comments.contains("@source_schema = N'dbo', @source_name = N'debezium_signal'"));
+        assert!(comments.contains(r#"["inventory.dbo.my_table"]"#));
+        assert!(!comments.contains("heartbeat"));
+
+        assert_eq!(
+            setup_comments(Dialect::MySql, &DebeziumOptions::default()),
+            ""
+        );
+    }
+}
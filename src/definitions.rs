@@ -0,0 +1,473 @@
+use crate::error::ConnectUtilError;
+use crate::i18n;
+use crate::types::{ConfigField, ConnectorDefinition, TopicStrategy};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::RwLock;
+
+/// Published JSON Schema for user-provided `ConnectorDefinition` files
+pub const DEFINITION_SCHEMA: &str = include_str!("../schemas/connector-definition.schema.json");
+
+/// Definitions loaded with `--definitions`, merged into the built-in catalog
+static EXTERNAL: RwLock<Vec<ConnectorDefinition>> = RwLock::new(Vec::new());
+
+/// Makes `definitions` part of the catalog for the rest of the process
+pub fn register(definitions: Vec<ConnectorDefinition>) {
+    *EXTERNAL.write().unwrap_or_else(|e| e.into_inner()) = definitions;
+}
+
+/// Adds registered definitions to `builtin`; a definition with a built-in's name replaces it
+pub(crate) fn with_external(builtin: Vec<ConnectorDefinition>) -> Vec<ConnectorDefinition> {
+    let external = EXTERNAL.read().unwrap_or_else(|e| e.into_inner());
+    merge(builtin, &external)
+}
+
+fn merge(
+    mut builtin: Vec<ConnectorDefinition>,
+    external: &[ConnectorDefinition],
+) -> Vec<ConnectorDefinition> {
+    for definition in external {
+        match builtin.iter_mut().find(|c| c.name == definition.name) {
+            Some(existing) => *existing = definition.clone(),
+            None => builtin.push(definition.clone()),
+        }
+    }
+    builtin
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn matches_type(value: &Value, expected: &str) -> bool {
+    let actual = type_name(value);
+    actual == expected || (expected == "number" && actual == "integer")
+}
+
+fn resolve_ref<'a>(root: &'a Value, reference: &str) -> Option<&'a Value> {
+    root.pointer(reference.strip_prefix('#')?)
+}
+
+/// Checks `value` against the subset of JSON Schema the definition schema uses:
+/// `$ref`, `type`, `enum`, `required`, `properties`, `additionalProperties`, `items`
+fn check(value: &Value, schema: &Value, root: &Value, path: &str, errors: &mut Vec<String>) {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        match resolve_ref(root, reference) {
+            Some(target) => check(value, target, root, path, errors),
+            None => errors.push(format!(
+                "{}: schema reference {} not found",
+                path, reference
+            )),
+        }
+        return;
+    }
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+        if !types.is_empty() && !types.iter().any(|t| matches_type(value, t)) {
+            errors.push(format!(
+                "{}: expected {}, found {}",
+                path,
+                types.join(" or "),
+                type_name(value)
+            ));
+            return;
+        }
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            let names: Vec<String> = allowed.iter().map(|v| v.to_string()).collect();
+            errors.push(format!(
+                "{}: unknown value {} (expected one of {})",
+                path,
+                value,
+                names.join(", ")
+            ));
+        }
+    }
+
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for field in required.iter().filter_map(Value::as_str) {
+                    if !object.contains_key(field) {
+                        errors.push(format!("{}: missing required field '{}'", path, field));
+                    }
+                }
+            }
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let closed = schema.get("additionalProperties") == Some(&Value::Bool(false));
+            for (key, child) in object {
+                let child_path = format!("{}.{}", path, key);
+                match properties.and_then(|p| p.get(key)) {
+                    Some(child_schema) => check(child, child_schema, root, &child_path, errors),
+                    None if closed => {
+                        errors.push(format!("{}: unknown field '{}'", path, key));
+                    }
+                    None => {}
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    check(
+                        item,
+                        item_schema,
+                        root,
+                        &format!("{}[{}]", path, index),
+                        errors,
+                    );
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Schema violations for one definition value, each prefixed with a `$`-rooted path
+pub fn schema_errors(value: &Value) -> Vec<String> {
+    let schema: Value =
+        serde_json::from_str(DEFINITION_SCHEMA).expect("definition schema is valid JSON");
+    let mut errors = Vec::new();
+    check(value, &schema, &schema, "$", &mut errors);
+    errors
+}
+
+/// Parses a definitions file holding one definition or an array of them,
+/// reporting every schema violation instead of stopping at the first
+pub fn parse_definitions(
+    content: &str,
+    source: &str,
+) -> Result<Vec<ConnectorDefinition>, ConnectUtilError> {
+    let value: Value = serde_json::from_str(content)
+        .map_err(|e| ConnectUtilError::Config(format!("{}: invalid JSON: {}", source, e)))?;
+
+    let items = match value {
+        Value::Array(items) => items,
+        other => vec![other],
+    };
+
+    let mut errors = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        let prefix = if items.len() == 1 {
+            source.to_string()
+        } else {
+            format!("{}[{}]", source, index)
+        };
+        errors.extend(
+            schema_errors(item)
+                .into_iter()
+                .map(|e| format!("{}: {}", prefix, e)),
+        );
+    }
+    if !errors.is_empty() {
+        return Err(ConnectUtilError::Config(format!(
+            "Invalid connector definitions:\n  {}",
+            errors.join("\n  ")
+        )));
+    }
+
+    items
+        .into_iter()
+        .map(|item| {
+            let inferred = item.get("topic_strategy").is_none();
+            let mut definition: ConnectorDefinition = serde_json::from_value(item)
+                .map_err(|e| ConnectUtilError::Config(format!("{}: {}", source, e)))?;
+            if inferred {
+                definition.topic_strategy = TopicStrategy::infer(&definition);
+            }
+            Ok(definition)
+        })
+        .collect()
+}
+
+/// Loads definitions from a `.json` file, or from every `.json` file in a directory
+pub fn load_definitions(path: &Path) -> Result<Vec<ConnectorDefinition>, ConnectUtilError> {
+    let _span = tracing::debug_span!("catalog_load").entered();
+    let files = if path.is_dir() {
+        let mut files: Vec<_> = std::fs::read_dir(path)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        files.sort();
+        files
+    } else if path.exists() {
+        vec![path.to_path_buf()]
+    } else {
+        return Err(ConnectUtilError::Config(format!(
+            "Definitions path not found: {}",
+            path.display()
+        )));
+    };
+
+    let mut definitions = Vec::new();
+    let mut sources: HashMap<String, String> = HashMap::new();
+    for file in files {
+        let source = file.display().to_string();
+        for definition in parse_definitions(&std::fs::read_to_string(&file)?, &source)? {
+            if let Some(first) = sources.insert(definition.name.clone(), source.clone()) {
+                return Err(ConnectUtilError::Config(format!(
+                    "Connector '{}' is defined in both {} and {}",
+                    definition.name, first, source
+                )));
+            }
+            definitions.push(definition);
+        }
+    }
+    Ok(definitions)
+}
+
+/// Schema for one config value; Connect configs are string maps, so numbers and
+/// booleans are constrained by pattern or enum rather than by JSON type
+fn field_schema(field: &ConfigField, description: &str, sensitive: bool) -> Value {
+    let mut schema = Map::new();
+    schema.insert("type".to_string(), json!("string"));
+    schema.insert("title".to_string(), json!(field.display_name));
+    schema.insert("description".to_string(), json!(description));
+    match (
+        &field.valid_values,
+        field.field_type.to_lowercase().as_str(),
+    ) {
+        (Some(values), _) => {
+            schema.insert("enum".to_string(), json!(values));
+        }
+        (None, "int" | "integer" | "long" | "short") => {
+            schema.insert("pattern".to_string(), json!("^-?[0-9]+$"));
+        }
+        (None, "boolean") => {
+            schema.insert("enum".to_string(), json!(["true", "false"]));
+        }
+        _ => {}
+    }
+    if let Some(default) = &field.default_value {
+        schema.insert("default".to_string(), json!(default));
+    }
+    if sensitive {
+        schema.insert("writeOnly".to_string(), json!(true));
+    }
+    Value::Object(schema)
+}
+
+/// JSON Schema of a connector's config map, for editor validation of hand-written
+/// connector JSON or tfvars; keys outside the catalog are allowed as strings
+pub fn config_schema(connector: &ConnectorDefinition) -> Value {
+    let mut properties = Map::new();
+    properties.insert(
+        "connector.class".to_string(),
+        json!({ "type": "string", "const": connector.connector_class }),
+    );
+    for field in connector
+        .required_configs
+        .iter()
+        .chain(connector.optional_configs.iter())
+    {
+        let sensitive = connector.sensitive_configs.contains(&field.name);
+        let description = i18n::field_description(connector, field);
+        properties.insert(
+            field.name.clone(),
+            field_schema(field, description, sensitive),
+        );
+    }
+    let topic_key = connector.topic_strategy.key();
+    if !properties.contains_key(topic_key) {
+        properties.insert(
+            topic_key.to_string(),
+            json!({ "type": "string", "description": "Topics the connector reads or writes" }),
+        );
+    }
+    let required: Vec<&str> = connector
+        .required_configs
+        .iter()
+        .map(|field| field.name.as_str())
+        .collect();
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": format!("{} configuration", connector.display_name),
+        "description": i18n::connector_description(connector),
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": { "type": "string" }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFINITION: &str = r#"{
+      "name": "AcmeSink",
+      "display_name": "Acme Sink",
+      "connector_class": "AcmeSink",
+      "connector_type": "Sink",
+      "description": "Writes to Acme",
+      "required_configs": [
+        {"name": "acme.url", "display_name": "URL", "description": "Endpoint",
+         "field_type": "string", "required": true, "group": "Connection"}
+      ],
+      "optional_configs": [
+        {"name": "acme.mode", "display_name": "Mode", "description": "Write mode",
+         "field_type": "string", "required": false, "default_value": null,
+         "valid_values": ["APPEND", "UPSERT"]}
+      ],
+      "sensitive_configs": []
+    }"#;
+
+    fn config_error(result: Result<Vec<ConnectorDefinition>, ConnectUtilError>) -> String {
+        match result {
+            Err(ConnectUtilError::Config(message)) => message,
+            other => panic!("Expected config error, got {:?}", other.map(|d| d.len())),
+        }
+    }
+
+    #[test]
+    fn test_builtin_catalog_matches_schema() {
+        for connector in ConnectorDefinition::get_all_connectors() {
+            let value = serde_json::to_value(&connector).unwrap();
+            assert_eq!(
+                schema_errors(&value),
+                Vec::<String>::new(),
+                "{}",
+                connector.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_definitions() {
+        let definitions = parse_definitions(DEFINITION, "acme.json").unwrap();
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(definitions[0].name, "AcmeSink");
+        assert_eq!(definitions[0].required_configs[0].name, "acme.url");
+        assert_eq!(definitions[0].topic_strategy, TopicStrategy::TopicsList);
+
+        let array = format!("[{}]", DEFINITION);
+        assert_eq!(parse_definitions(&array, "acme.json").unwrap().len(), 1);
+
+        // Without a topic_strategy a source falls back to kafka.topic, and an
+        // explicit one is kept
+        let source = DEFINITION.replace("\"Sink\"", "\"Source\"");
+        let definitions = parse_definitions(&source, "acme.json").unwrap();
+        assert_eq!(
+            definitions[0].topic_strategy,
+            TopicStrategy::KafkaTopicSingle
+        );
+        let prefixed = source.replace(
+            "\"description\": \"Writes to Acme\"",
+            "\"description\": \"Writes to Acme\", \"topic_strategy\": \"TopicPrefix\"",
+        );
+        let definitions = parse_definitions(&prefixed, "acme.json").unwrap();
+        assert_eq!(definitions[0].topic_strategy, TopicStrategy::TopicPrefix);
+    }
+
+    #[test]
+    fn test_parse_definitions_reports_every_violation() {
+        let content = DEFINITION
+            .replace("\"connector_class\": \"AcmeSink\",", "")
+            .replace("\"Sink\"", "\"Transform\"")
+            .replace("\"required\": true", "\"required\": \"yes\"")
+            .replace(
+                "\"description\": \"Writes to Acme\"",
+                "\"descripton\": \"typo\"",
+            );
+        let message = config_error(parse_definitions(&content, "acme.json"));
+        assert!(message.contains("acme.json: $: missing required field 'connector_class'"));
+        assert!(message.contains("acme.json: $: missing required field 'description'"));
+        assert!(message.contains("acme.json: $: unknown field 'descripton'"));
+        assert!(message.contains(
+            "acme.json: $.connector_type: unknown value \"Transform\" (expected one of \"Source\", \"Sink\")"
+        ));
+        assert!(message
+            .contains("acme.json: $.required_configs[0].required: expected boolean, found string"));
+    }
+
+    #[test]
+    fn test_parse_definitions_prefixes_array_index() {
+        let content = format!("[{}, {{\"name\": 1}}]", DEFINITION);
+        let message = config_error(parse_definitions(&content, "defs.json"));
+        assert!(message.contains("defs.json[1]: $.name: expected string, found integer"));
+        assert!(!message.contains("defs.json[0]"));
+    }
+
+    #[test]
+    fn test_parse_definitions_invalid_json() {
+        let message = config_error(parse_definitions("{", "broken.json"));
+        assert!(message.starts_with("broken.json: invalid JSON"));
+    }
+
+    #[test]
+    fn test_load_definitions_from_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("acme.json"), DEFINITION).unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+        let definitions = load_definitions(dir.path()).unwrap();
+        assert_eq!(definitions.len(), 1);
+
+        std::fs::write(dir.path().join("copy.json"), DEFINITION).unwrap();
+        let message = config_error(load_definitions(dir.path()));
+        assert!(message.contains("'AcmeSink' is defined in both"));
+
+        assert!(load_definitions(&dir.path().join("missing.json")).is_err());
+    }
+
+    #[test]
+    fn test_config_schema() {
+        let mut connector = parse_definitions(DEFINITION, "acme.json")
+            .unwrap()
+            .remove(0);
+        connector.sensitive_configs.push("acme.url".to_string());
+        connector.optional_configs.push(ConfigField {
+            name: "acme.batch".to_string(),
+            display_name: "Batch".to_string(),
+            description: "Batch size".to_string(),
+            field_type: "int".to_string(),
+            required: false,
+            default_value: Some("100".to_string()),
+            valid_values: None,
+            group: Default::default(),
+        });
+
+        let schema = config_schema(&connector);
+        assert_eq!(schema["title"], "Acme Sink configuration");
+        assert_eq!(schema["required"], json!(["acme.url"]));
+        assert_eq!(schema["additionalProperties"], json!({"type": "string"}));
+        let properties = &schema["properties"];
+        assert_eq!(properties["connector.class"]["const"], "AcmeSink");
+        assert_eq!(properties["acme.url"]["writeOnly"], true);
+        assert_eq!(properties["acme.mode"]["enum"], json!(["APPEND", "UPSERT"]));
+        assert_eq!(properties["acme.batch"]["pattern"], "^-?[0-9]+$");
+        assert_eq!(properties["acme.batch"]["default"], "100");
+        assert_eq!(properties["topics"]["type"], "string");
+    }
+
+    #[test]
+    fn test_merge_overrides_builtin_by_name() {
+        let external = parse_definitions(DEFINITION, "acme.json").unwrap();
+        let mut replacement = external[0].clone();
+        replacement.name = "PostgresSink".to_string();
+        replacement.description = "Patched".to_string();
+
+        let builtin = ConnectorDefinition::get_all_connectors();
+        let count = builtin.len();
+        let merged = merge(builtin, &[external[0].clone(), replacement]);
+        assert_eq!(merged.len(), count + 1);
+        let postgres = merged.iter().find(|c| c.name == "PostgresSink").unwrap();
+        assert_eq!(postgres.description, "Patched");
+    }
+}
@@ -0,0 +1,270 @@
+use crate::error::ConnectUtilError;
+use clap::{Arg, Command};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+fn visible_args(command: &Command) -> impl Iterator<Item = &Arg> {
+    command
+        .get_arguments()
+        .filter(|arg| !arg.is_hide_set() && !matches!(arg.get_id().as_str(), "help" | "version"))
+}
+
+fn visible_subcommands(command: &Command) -> impl Iterator<Item = &Command> {
+    command
+        .get_subcommands()
+        .filter(|sub| !sub.is_hide_set() && sub.get_name() != "help")
+}
+
+/// `-d, --dir <DIR>` style label for an argument
+fn arg_label(arg: &Arg) -> String {
+    let value = arg
+        .get_value_names()
+        .and_then(|names| names.first())
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| arg.get_id().as_str().to_uppercase());
+    if arg.is_positional() {
+        return format!("<{}>", value);
+    }
+    let mut label = match (arg.get_short(), arg.get_long()) {
+        (Some(short), Some(long)) => format!("-{}, --{}", short, long),
+        (Some(short), None) => format!("-{}", short),
+        (None, Some(long)) => format!("--{}", long),
+        (None, None) => value.clone(),
+    };
+    if arg.get_action().takes_values() {
+        label.push_str(&format!(" <{}>", value));
+    }
+    label
+}
+
+fn arg_line(arg: &Arg) -> String {
+    let mut line = format!("- `{}`", arg_label(arg));
+    if let Some(help) = arg.get_help() {
+        line.push_str(&format!(": {}", help));
+    }
+    let mut notes = Vec::new();
+    if arg.is_required_set() {
+        notes.push("required".to_string());
+    }
+    let possible: Vec<String> = arg
+        .get_possible_values()
+        .iter()
+        .filter(|value| !value.is_hide_set())
+        .map(|value| format!("`{}`", value.get_name()))
+        .collect();
+    if !possible.is_empty() && arg.get_action().takes_values() {
+        notes.push(format!("one of {}", possible.join(", ")));
+    }
+    let defaults: Vec<String> = arg
+        .get_default_values()
+        .iter()
+        .map(|value| format!("`{}`", value.to_string_lossy()))
+        .collect();
+    if !defaults.is_empty() && arg.get_action().takes_values() {
+        notes.push(format!("default: {}", defaults.join(", ")));
+    }
+    if !notes.is_empty() {
+        line.push_str(&format!(" ({})", notes.join("; ")));
+    }
+    line
+}
+
+fn write_section(out: &mut String, heading: &str, args: &[&Arg]) {
+    if args.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "**{}:**", heading);
+    for arg in args {
+        let _ = writeln!(out, "{}", arg_line(arg));
+    }
+    let _ = writeln!(out);
+}
+
+/// Writes a section for `sub` and then for each of its own subcommands
+fn write_subcommand(out: &mut String, parent: &str, sub: &Command) {
+    let path = format!("{} {}", parent, sub.get_name());
+    let _ = writeln!(out, "## `{}`\n", path);
+    if let Some(about) = sub.get_long_about().or(sub.get_about()) {
+        let _ = writeln!(out, "{}\n", about);
+    }
+    let usage = sub.clone().render_usage().to_string();
+    let usage = usage.trim_start_matches("Usage:").trim();
+    let _ = writeln!(out, "```text\n{}\n```\n", usage);
+
+    let own: Vec<&Arg> = visible_args(sub)
+        .filter(|arg| !arg.is_global_set())
+        .collect();
+    let positional: Vec<&Arg> = own.iter().copied().filter(|a| a.is_positional()).collect();
+    let options: Vec<&Arg> = own.iter().copied().filter(|a| !a.is_positional()).collect();
+    write_section(out, "Arguments", &positional);
+    write_section(out, "Options", &options);
+
+    for nested in visible_subcommands(sub) {
+        write_subcommand(out, &path, nested);
+    }
+}
+
+/// Renders a Markdown reference of every visible subcommand and flag
+pub fn markdown_reference(command: &Command) -> String {
+    let mut command = command.clone();
+    command.build();
+    let name = command.get_name().to_string();
+
+    let mut out = String::new();
+    let _ = writeln!(out, "# `{}` CLI reference\n", name);
+    let _ = writeln!(
+        out,
+        "<!-- Generated by `{} gen-docs` from the CLI definition; do not edit by hand -->\n",
+        name
+    );
+    if let Some(about) = command.get_about() {
+        let _ = writeln!(out, "{}\n", about);
+    }
+
+    let globals: Vec<&Arg> = visible_args(&command)
+        .filter(|arg| arg.is_global_set())
+        .collect();
+    if !globals.is_empty() {
+        let _ = writeln!(out, "## Global options\n");
+        for arg in globals {
+            let _ = writeln!(out, "{}", arg_line(arg));
+        }
+        let _ = writeln!(out);
+    }
+
+    for sub in visible_subcommands(&command) {
+        write_subcommand(&mut out, &name, sub);
+    }
+    out.trim_end().to_string() + "\n"
+}
+
+fn write_man_page(
+    page: &Command,
+    page_name: String,
+    dir: &Path,
+    written: &mut Vec<PathBuf>,
+) -> Result<(), ConnectUtilError> {
+    let mut buffer = Vec::new();
+    clap_mangen::Man::new(page.clone().name(page_name.clone())).render(&mut buffer)?;
+    let path = dir.join(format!("{}.1", page_name));
+    std::fs::write(&path, buffer)?;
+    written.push(path);
+
+    for sub in visible_subcommands(page) {
+        write_man_page(
+            sub,
+            format!("{}-{}", page_name, sub.get_name()),
+            dir,
+            written,
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes `<name>.1` plus a `<name>-<subcommand>.1` page per visible subcommand,
+/// nesting further for subcommands of subcommands
+pub fn write_man_pages(command: &Command, dir: &Path) -> Result<Vec<PathBuf>, ConnectUtilError> {
+    let mut command = command.clone();
+    command.build();
+    std::fs::create_dir_all(dir)?;
+
+    let mut written = Vec::new();
+    write_man_page(&command, command.get_name().to_string(), dir, &mut written)?;
+    Ok(written)
+}
+
+/// Writes `man/*.1` and `cli-reference.md` under `out_dir`
+pub fn write_docs(command: &Command, out_dir: &Path) -> Result<Vec<PathBuf>, ConnectUtilError> {
+    let mut written = write_man_pages(command, &out_dir.join("man"))?;
+    let reference = out_dir.join("cli-reference.md");
+    std::fs::write(&reference, markdown_reference(command))?;
+    written.push(reference);
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::ArgAction;
+
+    fn command() -> Command {
+        Command::new("tool")
+            .about("Example tool")
+            .arg(
+                Arg::new("verbose")
+                    .long("verbose")
+                    .global(true)
+                    .action(ArgAction::SetTrue)
+                    .help("Print more"),
+            )
+            .subcommand(
+                Command::new("scan")
+                    .about("Scan a directory")
+                    .arg(
+                        Arg::new("dir")
+                            .short('d')
+                            .long("dir")
+                            .default_value(".")
+                            .help("Directory to scan"),
+                    )
+                    .arg(
+                        Arg::new("format")
+                            .long("format")
+                            .value_parser(["text", "json"])
+                            .help("Report format"),
+                    )
+                    .arg(Arg::new("target").required(true).help("Target name")),
+            )
+            .subcommand(
+                Command::new("catalog")
+                    .about("Catalog tools")
+                    .subcommand(Command::new("diff").about("Compare bundles")),
+            )
+            .subcommand(Command::new("secret").hide(true))
+    }
+
+    #[test]
+    fn test_markdown_reference() {
+        let markdown = markdown_reference(&command());
+        assert!(markdown.starts_with("# `tool` CLI reference"));
+        assert!(markdown.contains("## Global options\n\n- `--verbose`: Print more"));
+        assert!(markdown.contains("## `tool scan`\n\nScan a directory"));
+        assert!(markdown.contains("- `<TARGET>`: Target name (required)"));
+        assert!(markdown.contains("- `-d, --dir <DIR>`: Directory to scan (default: `.`)"));
+        assert!(markdown.contains("- `--format <FORMAT>`: Report format (one of `text`, `json`)"));
+        assert!(markdown.contains("## `tool catalog diff`\n\nCompare bundles"));
+        assert!(!markdown.contains("secret"));
+        assert!(!markdown.contains("--help"));
+    }
+
+    #[test]
+    fn test_write_man_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let written = write_man_pages(&command(), dir.path()).unwrap();
+        let names: Vec<String> = written
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "tool.1",
+                "tool-scan.1",
+                "tool-catalog.1",
+                "tool-catalog-diff.1"
+            ]
+        );
+        let page = std::fs::read_to_string(&written[1]).unwrap();
+        assert!(page.contains(".TH tool-scan"));
+    }
+
+    #[test]
+    fn test_write_docs() {
+        let dir = tempfile::tempdir().unwrap();
+        let written = write_docs(&command(), dir.path()).unwrap();
+        assert_eq!(written.len(), 5);
+        assert!(dir.path().join("man/tool.1").exists());
+        let reference = std::fs::read_to_string(dir.path().join("cli-reference.md")).unwrap();
+        assert_eq!(reference, markdown_reference(&command()));
+    }
+}
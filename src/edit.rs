@@ -0,0 +1,586 @@
+use crate::error::ConnectUtilError;
+use hcl_edit::expr::{Expression, Object, ObjectKey, ObjectValue, TraversalOperator};
+use hcl_edit::structure::{Block, BlockLabel, Body};
+use hcl_edit::visit_mut::{visit_block_mut, visit_traversal_mut, VisitMut};
+use hcl_edit::{Decor, Decorate, Decorated, Ident};
+use std::fmt;
+
+/// A Terraform file parsed for editing. Unlike `hcl::Body`, the tree keeps comments,
+/// blank lines, alignment, and attribute order, so writing it back only changes
+/// what was edited
+#[derive(Debug, Clone)]
+pub struct TerraformFile {
+    body: Body,
+}
+
+fn parse_error(what: &str, error: impl fmt::Display) -> ConnectUtilError {
+    ConnectUtilError::Terraform(format!("Failed to parse {}: {}", what, error))
+}
+
+/// Indentation before the first line of a decorated item, skipping comment lines
+fn indentation(decor: &Decor) -> String {
+    let prefix = decor.prefix().map(|p| p.to_string()).unwrap_or_default();
+    let last_line = prefix.rsplit('\n').next().unwrap_or_default();
+    last_line
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect()
+}
+
+fn key_name(key: &ObjectKey) -> String {
+    match key {
+        ObjectKey::Ident(ident) => ident.as_str().to_string(),
+        ObjectKey::Expression(Expression::String(name)) => name.as_str().to_string(),
+        ObjectKey::Expression(other) => other.to_string().trim().to_string(),
+    }
+}
+
+fn is_connector(block: &Block, label: &str) -> bool {
+    block.ident.as_str() == "resource" && block.has_exact_labels(&["confluent_connector", label])
+}
+
+impl TerraformFile {
+    pub fn parse(content: &str) -> Result<Self, ConnectUtilError> {
+        let body =
+            hcl_edit::parser::parse_body(content).map_err(|e| parse_error("Terraform file", e))?;
+        Ok(TerraformFile { body })
+    }
+
+    /// Whether the file declares `confluent_connector.<label>`
+    pub fn has_connector(&self, label: &str) -> bool {
+        self.body.blocks().any(|block| is_connector(block, label))
+    }
+
+    fn connector_mut(&mut self, label: &str) -> Result<&mut Block, ConnectUtilError> {
+        self.body
+            .blocks_mut()
+            .find(|block| is_connector(block, label))
+            .ok_or_else(|| {
+                ConnectUtilError::Config(format!("No confluent_connector.{} in the file", label))
+            })
+    }
+
+    /// Sets `key` in the connector's `attribute` map (`config_nonsensitive` or
+    /// `config_sensitive`) to the HCL expression `value`. An existing entry keeps its
+    /// place and trailing comment; a new one goes last, indented like the others.
+    /// Returns whether the file changed
+    pub fn set_config(
+        &mut self,
+        label: &str,
+        attribute: &str,
+        key: &str,
+        value: &str,
+    ) -> Result<bool, ConnectUtilError> {
+        let mut value: Expression = value
+            .parse()
+            .map_err(|e| parse_error(&format!("value for {}", key), e))?;
+        let block = self.connector_mut(label)?;
+        if !block.body.has_attribute(attribute) {
+            let indent = block
+                .body
+                .iter()
+                .last()
+                .map(|structure| indentation(structure.decor()))
+                .unwrap_or_else(|| "  ".to_string());
+            let source = format!("{}{} = {{\n{}}}\n", indent, attribute, indent);
+            push_structures(&mut block.body, &source)?;
+        }
+        let mut entry = block
+            .body
+            .get_attribute_mut(attribute)
+            .expect("attribute was just ensured");
+        let indent = indentation(entry.get().decor());
+        let Expression::Object(object) = entry.value_mut() else {
+            return Err(ConnectUtilError::Terraform(format!(
+                "Can't edit {} of confluent_connector.{}: it isn't a literal map",
+                attribute, label
+            )));
+        };
+
+        if let Some((_, existing)) = object.iter_mut().find(|(k, _)| key_name(k) == key) {
+            let current = existing.expr_mut();
+            // Compare without the trailing comment, which the new value will keep
+            let mut bare = current.clone();
+            bare.decor_mut().clear();
+            if bare.to_string().trim() == value.to_string().trim() {
+                return Ok(false);
+            }
+            value.decorate(current.decor().clone());
+            *current = value;
+            return Ok(true);
+        }
+        insert_entry(object, key, value, &indent);
+        Ok(true)
+    }
+
+    /// Removes `key` from the connector's `attribute` map, along with the comments
+    /// on the lines above it. Returns whether the file changed
+    pub fn remove_config(
+        &mut self,
+        label: &str,
+        attribute: &str,
+        key: &str,
+    ) -> Result<bool, ConnectUtilError> {
+        let block = self.connector_mut(label)?;
+        let Some(mut entry) = block.body.get_attribute_mut(attribute) else {
+            return Ok(false);
+        };
+        let Expression::Object(object) = entry.value_mut() else {
+            return Err(ConnectUtilError::Terraform(format!(
+                "Can't edit {} of confluent_connector.{}: it isn't a literal map",
+                attribute, label
+            )));
+        };
+        let Some(object_key) = object
+            .iter()
+            .map(|(k, _)| k)
+            .find(|k| key_name(k) == key)
+            .cloned()
+        else {
+            return Ok(false);
+        };
+        object.remove_entry(&object_key);
+        Ok(true)
+    }
+
+    /// Appends the attributes and blocks in `source` to the end of the connector's
+    /// body, such as a `lifecycle` block. Returns an error if they don't parse
+    pub fn push_to_connector(&mut self, label: &str, source: &str) -> Result<(), ConnectUtilError> {
+        let block = self.connector_mut(label)?;
+        let indent = block
+            .body
+            .iter()
+            .last()
+            .map(|structure| indentation(structure.decor()))
+            .unwrap_or_else(|| "  ".to_string());
+        let indented: String = source
+            .lines()
+            .map(|line| {
+                if line.is_empty() {
+                    "\n".to_string()
+                } else {
+                    format!("{}{}\n", indent, line)
+                }
+            })
+            .collect();
+        push_structures(&mut block.body, &indented)
+    }
+
+    /// Relabels `confluent_connector.<old>` to `<new>` and rewrites every reference
+    /// to it. `moved` blocks are left alone so earlier moves still chain onto the
+    /// new address. Returns whether anything changed
+    pub fn rename_connector(&mut self, old: &str, new: &str) -> bool {
+        let mut renamer = Renamer {
+            old,
+            new,
+            changed: false,
+        };
+        renamer.visit_body_mut(&mut self.body);
+        renamer.changed
+    }
+
+    /// Appends the blocks in `source` at the end of the file, after a blank line and
+    /// any comments that end the file
+    pub fn append(&mut self, source: &str) -> Result<(), ConnectUtilError> {
+        let appended: Body = source
+            .parse()
+            .map_err(|e| parse_error("generated config", e))?;
+        let trailing = self
+            .body
+            .decor()
+            .suffix()
+            .map(|s| s.to_string())
+            .unwrap_or_default();
+        for (index, mut structure) in appended.into_iter().enumerate() {
+            if index == 0 && !self.body.is_empty() {
+                let prefix = structure
+                    .decor()
+                    .prefix()
+                    .map(|p| p.to_string())
+                    .unwrap_or_default();
+                let comments = trailing.trim_matches(['\r', '\n']);
+                let comments = if comments.is_empty() {
+                    String::new()
+                } else {
+                    format!("{}\n\n", comments)
+                };
+                structure.decor_mut().set_prefix(format!(
+                    "\n{}{}",
+                    comments,
+                    prefix.trim_start_matches(['\r', '\n'])
+                ));
+                self.body.decor_mut().set_suffix("");
+            }
+            self.body.push(structure);
+        }
+        Ok(())
+    }
+
+    /// `append` for only the blocks the file doesn't have yet. A block the file
+    /// already has unchanged, such as a shared `variable`, is kept once; one with the
+    /// same address but different settings is an error, as is a second resource
+    pub fn append_new(&mut self, source: &str) -> Result<(), ConnectUtilError> {
+        let appended: Body = source
+            .parse()
+            .map_err(|e| parse_error("generated config", e))?;
+        let labels = |block: &Block| -> Vec<String> {
+            block
+                .labels
+                .iter()
+                .map(|label| label.as_str().to_string())
+                .collect()
+        };
+        let bare = |block: &Block| {
+            let mut block = block.clone();
+            block.decor_mut().clear();
+            Body::builder()
+                .block(block)
+                .build()
+                .to_string()
+                .trim()
+                .to_string()
+        };
+        let mut remaining = Body::new();
+        for structure in appended {
+            if let Some(block) = structure.as_block() {
+                let existing = self.body.blocks().find(|existing| {
+                    existing.ident.as_str() == block.ident.as_str()
+                        && labels(existing) == labels(block)
+                });
+                match existing {
+                    Some(_) if block.ident.as_str() == "resource" => {
+                        return Err(ConnectUtilError::Terraform(format!(
+                            "Resource '{}' already exists in the output file",
+                            labels(block).join(".")
+                        )));
+                    }
+                    Some(existing) if bare(existing) == bare(block) => continue,
+                    Some(_) => {
+                        return Err(ConnectUtilError::Terraform(format!(
+                            "{} '{}' already exists in the output file with different settings",
+                            block.ident.as_str(),
+                            labels(block).join(".")
+                        )));
+                    }
+                    None => {}
+                }
+            }
+            remaining.push(structure);
+        }
+        if remaining.is_empty() {
+            return Ok(());
+        }
+        self.append(&remaining.to_string())
+    }
+
+    /// Puts `<prefix>_` before the name of every `output` block, so outputs of
+    /// several connectors can share a module
+    pub fn prefix_outputs(&mut self, prefix: &str) {
+        for block in self
+            .body
+            .blocks_mut()
+            .filter(|block| block.ident.as_str() == "output")
+        {
+            let Some(first) = block.labels.first_mut() else {
+                continue;
+            };
+            let decor = first.decor().clone();
+            let mut label = BlockLabel::from(format!("{}_{}", prefix, first.as_str()));
+            if let BlockLabel::String(string) = &mut label {
+                string.decorate(decor);
+            }
+            *first = label;
+        }
+    }
+}
+
+/// Parses `source` as body content and pushes it onto `body`, keeping its decor
+fn push_structures(body: &mut Body, source: &str) -> Result<(), ConnectUtilError> {
+    let parsed: Body = source
+        .parse()
+        .map_err(|e| parse_error("generated config", e))?;
+    for structure in parsed {
+        body.push(structure);
+    }
+    Ok(())
+}
+
+/// Adds `key = value` as the map's last entry, indented like the entry before it.
+/// `indent` is the indentation of the map's attribute
+fn insert_entry(object: &mut Object, key: &str, mut value: Expression, indent: &str) {
+    use hcl_edit::expr::ObjectValueTerminator;
+
+    let mut object_key = ObjectKey::Expression(Expression::String(Decorated::new(key.to_string())));
+    value.decorate(Decor::new(" ", ""));
+    let mut entry = ObjectValue::new(value);
+    match object.iter().last() {
+        Some((last_key, last_value))
+            if last_value.terminator() == ObjectValueTerminator::Newline =>
+        {
+            object_key.decorate(Decor::new(indentation(last_key.decor()), " "));
+            entry.set_terminator(ObjectValueTerminator::Newline);
+        }
+        Some(_) => {
+            object_key.decorate(Decor::new(" ", " "));
+            entry.set_terminator(ObjectValueTerminator::Comma);
+        }
+        None => {
+            // An empty `{}` becomes a multi-line map
+            object_key.decorate(Decor::new(format!("\n{}  ", indent), " "));
+            entry.set_terminator(ObjectValueTerminator::Newline);
+            object.set_trailing(indent.to_string());
+        }
+    }
+    object.insert(object_key, entry);
+}
+
+impl fmt::Display for TerraformFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.body)
+    }
+}
+
+/// Rewrites `confluent_connector.<old>` to `<new>` in labels and traversals
+struct Renamer<'a> {
+    old: &'a str,
+    new: &'a str,
+    changed: bool,
+}
+
+impl VisitMut for Renamer<'_> {
+    fn visit_block_mut(&mut self, block: &mut Block) {
+        if block.ident.as_str() == "moved" {
+            return;
+        }
+        if is_connector(block, self.old) {
+            let decor = block.labels[1].decor().clone();
+            let mut label = BlockLabel::from(self.new);
+            if let BlockLabel::String(string) = &mut label {
+                string.decorate(decor);
+            }
+            block.labels[1] = label;
+            self.changed = true;
+        }
+        visit_block_mut(self, block);
+    }
+
+    fn visit_traversal_mut(&mut self, traversal: &mut hcl_edit::expr::Traversal) {
+        let is_resource = traversal
+            .expr
+            .as_variable()
+            .is_some_and(|variable| variable.as_str() == "confluent_connector");
+        if is_resource {
+            if let Some(operator) = traversal.operators.first_mut() {
+                if matches!(operator.value(), TraversalOperator::GetAttr(ident) if ident.as_str() == self.old)
+                {
+                    let decor = operator.decor().clone();
+                    let mut renamed = Decorated::new(TraversalOperator::GetAttr(Decorated::new(
+                        Ident::new(self.new),
+                    )));
+                    renamed.decorate(decor);
+                    *operator = renamed;
+                    self.changed = true;
+                }
+            }
+        }
+        visit_traversal_mut(self, traversal);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = r#"# Orders CDC, owned by the payments team
+resource "confluent_connector" "orders" {
+  status = "RUNNING" # paused during migrations
+
+  config_sensitive = {}
+  config_nonsensitive = {
+    "connector.class"   = "PostgresCdcSourceV2"
+    "database.hostname" = "db.internal" # primary
+
+    // Tuned for the nightly batch
+    "tasks.max" = "2"
+  }
+}
+
+output "orders_id" {
+  value = "${confluent_connector.orders.id}"
+}
+"#;
+
+    #[test]
+    fn test_set_config_keeps_comments_and_order() {
+        let mut file = TerraformFile::parse(CONTENT).unwrap();
+        assert!(file
+            .set_config("orders", "config_nonsensitive", "tasks.max", "\"4\"")
+            .unwrap());
+        assert!(!file
+            .set_config("orders", "config_nonsensitive", "tasks.max", "\"4\"")
+            .unwrap());
+        assert!(file
+            .set_config(
+                "orders",
+                "config_nonsensitive",
+                "topic.prefix",
+                "\"orders\""
+            )
+            .unwrap());
+        assert!(file
+            .set_config(
+                "orders",
+                "config_sensitive",
+                "database.password",
+                "var.orders_db_password"
+            )
+            .unwrap());
+        assert_eq!(
+            file.to_string(),
+            r#"# Orders CDC, owned by the payments team
+resource "confluent_connector" "orders" {
+  status = "RUNNING" # paused during migrations
+
+  config_sensitive = {
+    "database.password" = var.orders_db_password
+  }
+  config_nonsensitive = {
+    "connector.class"   = "PostgresCdcSourceV2"
+    "database.hostname" = "db.internal" # primary
+
+    // Tuned for the nightly batch
+    "tasks.max" = "4"
+    "topic.prefix" = "orders"
+  }
+}
+
+output "orders_id" {
+  value = "${confluent_connector.orders.id}"
+}
+"#
+        );
+        assert!(file
+            .set_config("refunds", "config_nonsensitive", "tasks.max", "\"1\"")
+            .is_err());
+    }
+
+    #[test]
+    fn test_remove_config() {
+        let mut file = TerraformFile::parse(CONTENT).unwrap();
+        assert!(file
+            .remove_config("orders", "config_nonsensitive", "tasks.max")
+            .unwrap());
+        assert!(!file
+            .remove_config("orders", "config_nonsensitive", "tasks.max")
+            .unwrap());
+        assert!(!file
+            .remove_config("orders", "config_sensitive", "database.password")
+            .unwrap());
+        let rendered = file.to_string();
+        assert!(!rendered.contains("tasks.max"));
+        assert!(!rendered.contains("nightly batch"));
+        assert!(rendered.contains("\"database.hostname\" = \"db.internal\" # primary\n"));
+    }
+
+    #[test]
+    fn test_push_to_connector_and_append() {
+        let mut file = TerraformFile::parse(CONTENT).unwrap();
+        file.push_to_connector(
+            "orders",
+            "lifecycle {\n  ignore_changes = [config_sensitive]\n}\n",
+        )
+        .unwrap();
+        file.append("moved {\n  from = confluent_connector.a\n  to   = confluent_connector.b\n}\n")
+            .unwrap();
+        let rendered = file.to_string();
+        assert!(rendered.contains(
+            "    \"tasks.max\" = \"2\"\n  }\n  lifecycle {\n    ignore_changes = [config_sensitive]\n  }\n}\n"
+        ));
+        assert!(rendered.ends_with(
+            "}\n\nmoved {\n  from = confluent_connector.a\n  to   = confluent_connector.b\n}\n"
+        ));
+
+        // The trailing comments of a CRLF file move above the appended block without a stray `\r`
+        let mut file = TerraformFile::parse("locals {}\r\n# end of connectors\r\n").unwrap();
+        file.append("moved {}\n").unwrap();
+        assert_eq!(
+            file.to_string(),
+            "locals {}\n\n# end of connectors\n\nmoved {}\n"
+        );
+    }
+
+    #[test]
+    fn test_append_new() {
+        let mut file = TerraformFile::parse(CONTENT).unwrap();
+        file.append_new(
+            "# Refunds sink\nresource \"confluent_connector\" \"refunds\" {}\n\noutput \"orders_id\" {\n  value = \"${confluent_connector.orders.id}\"\n}\n",
+        )
+        .unwrap();
+        let rendered = file.to_string();
+        assert!(rendered
+            .ends_with("}\n\n# Refunds sink\nresource \"confluent_connector\" \"refunds\" {}\n"));
+        assert_eq!(rendered.matches("output \"orders_id\"").count(), 1);
+
+        // Nothing new leaves the file alone
+        file.append_new(
+            "output \"orders_id\" {\n  value = \"${confluent_connector.orders.id}\"\n}\n",
+        )
+        .unwrap();
+        assert_eq!(file.to_string(), rendered);
+
+        let err = file.append_new("output \"orders_id\" {}\n").unwrap_err();
+        assert!(err.to_string().contains("with different settings"));
+
+        let err = file
+            .append_new("resource \"confluent_connector\" \"orders\" {}\n")
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("'confluent_connector.orders' already exists"));
+
+        let mut file = TerraformFile::parse("output \"connector_id\" {} # the sink\n").unwrap();
+        file.prefix_outputs("refunds");
+        assert_eq!(
+            file.to_string(),
+            "output \"refunds_connector_id\" {} # the sink\n"
+        );
+    }
+
+    #[test]
+    fn test_rename_connector() {
+        let content = r#"resource "confluent_connector" "orders" {
+  depends_on = [confluent_connector.orders_v2] # keep ordering
+}
+
+output "orders_id" {
+  value = "${confluent_connector.orders.id}"
+}
+
+moved {
+  from = confluent_connector.legacy
+  to   = confluent_connector.orders
+}
+
+locals {
+  ids = [confluent_connector.orders.id, data.confluent_connector.orders.id]
+}
+"#;
+        let mut file = TerraformFile::parse(content).unwrap();
+        assert!(file.rename_connector("orders", "sales"));
+        assert!(file.has_connector("sales"));
+        assert_eq!(
+            file.to_string(),
+            content
+                .replacen("\"orders\" {", "\"sales\" {", 1)
+                .replace(
+                    "${confluent_connector.orders.id}",
+                    "${confluent_connector.sales.id}"
+                )
+                .replace(
+                    "[confluent_connector.orders.id,",
+                    "[confluent_connector.sales.id,"
+                )
+        );
+        assert!(!file.rename_connector("refunds", "payments"));
+    }
+}
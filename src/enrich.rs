@@ -0,0 +1,464 @@
+use crate::catalog::CatalogBundle;
+use crate::error::ConnectUtilError;
+use crate::types::{ConfigField, ConnectorDefinition};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// Published or scraped metadata for the catalog's connectors, as read by
+/// `catalog enrich`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CatalogMetadata {
+    pub connectors: Vec<ConnectorMetadata>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ConnectorMetadata {
+    /// Catalog name, `connector.class`, or alias of the connector
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub fields: Vec<FieldMetadata>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FieldMetadata {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub default: Option<String>,
+    #[serde(default)]
+    pub valid_values: Option<Vec<String>>,
+}
+
+impl CatalogMetadata {
+    pub fn from_json(content: &str) -> Result<Self, ConnectUtilError> {
+        serde_json::from_str(content)
+            .map_err(|e| ConnectUtilError::Config(format!("Invalid catalog metadata: {}", e)))
+    }
+}
+
+/// One value the metadata sets, or would set with `--overwrite`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetadataUpdate {
+    pub connector: String,
+    /// None for the connector's own description
+    pub field: Option<String>,
+    pub attribute: &'static str,
+    pub before: Option<String>,
+    pub after: String,
+}
+
+impl MetadataUpdate {
+    fn target(&self) -> String {
+        match &self.field {
+            Some(field) => format!("{} '{}' {}", self.connector, field, self.attribute),
+            None => format!("{} {}", self.connector, self.attribute),
+        }
+    }
+}
+
+/// A field that still lacks metadata after enrichment
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MissingMetadata {
+    pub connector: String,
+    pub field: String,
+    /// `description`, and `default` for optional fields
+    pub missing: Vec<&'static str>,
+}
+
+/// What `catalog enrich` changed, kept, and couldn't place
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct EnrichReport {
+    /// Values filled in where the catalog had none, or replaced with `--overwrite`
+    pub applied: Vec<MetadataUpdate>,
+    /// Values that differ from the catalog's and were left alone
+    pub conflicts: Vec<MetadataUpdate>,
+    /// Metadata entries that match no connector
+    pub unmatched_connectors: Vec<String>,
+    /// Metadata fields the matched connector doesn't have, as `Connector: key`
+    pub unknown_fields: Vec<String>,
+    pub missing: Vec<MissingMetadata>,
+}
+
+impl EnrichReport {
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "📋 Catalog enrichment: {} value(s) applied, {} conflict(s) kept",
+            self.applied.len(),
+            self.conflicts.len()
+        );
+        let show = |out: &mut String, title: &str, updates: &[MetadataUpdate]| {
+            if updates.is_empty() {
+                return;
+            }
+            let _ = writeln!(out, "\n  {} ({}):", title, updates.len());
+            for update in updates {
+                let before = update.before.as_deref().unwrap_or("none");
+                let _ = writeln!(
+                    out,
+                    "    {}: '{}' -> '{}'",
+                    update.target(),
+                    before,
+                    update.after
+                );
+            }
+        };
+        show(&mut out, "Applied", &self.applied);
+        show(
+            &mut out,
+            "Conflicts, kept without --overwrite",
+            &self.conflicts,
+        );
+        for (title, names) in [
+            ("Unmatched connectors", &self.unmatched_connectors),
+            ("Unknown fields", &self.unknown_fields),
+        ] {
+            if !names.is_empty() {
+                let _ = writeln!(out, "\n  ⚠️  {} ({}):", title, names.len());
+                for name in names {
+                    let _ = writeln!(out, "    {}", name);
+                }
+            }
+        }
+        if !self.missing.is_empty() {
+            let _ = writeln!(
+                out,
+                "\n  Fields still missing metadata ({}):",
+                self.missing.len()
+            );
+            let mut connector = "";
+            for entry in &self.missing {
+                if entry.connector != connector {
+                    connector = &entry.connector;
+                    let _ = writeln!(out, "    {}", connector);
+                }
+                let _ = writeln!(out, "      {} ({})", entry.field, entry.missing.join(", "));
+            }
+        }
+        out
+    }
+}
+
+/// The connector a metadata entry names, by catalog name, then class or alias
+fn find_connector(connectors: &[ConnectorDefinition], name: &str) -> Option<usize> {
+    connectors.iter().position(|c| c.name == name).or_else(|| {
+        connectors
+            .iter()
+            .position(|c| c.connector_class == name || c.aliases.iter().any(|a| a == name))
+    })
+}
+
+/// Fills `current` from `value` when it's empty, or replaces it with `overwrite`;
+/// a differing value left alone is a conflict
+fn merge_value(
+    current: &mut Option<String>,
+    value: Option<String>,
+    overwrite: bool,
+    update: impl Fn(Option<String>, String) -> MetadataUpdate,
+    report: &mut EnrichReport,
+) {
+    let Some(value) = value.filter(|v| !v.trim().is_empty()) else {
+        return;
+    };
+    match current.as_ref().filter(|c| !c.trim().is_empty()) {
+        Some(existing) if *existing == value => {}
+        Some(existing) if !overwrite => {
+            report.conflicts.push(update(Some(existing.clone()), value))
+        }
+        _ => {
+            report.applied.push(update(current.clone(), value.clone()));
+            *current = Some(value);
+        }
+    }
+}
+
+fn enrich_field(
+    connector: &str,
+    field: &mut ConfigField,
+    metadata: &FieldMetadata,
+    overwrite: bool,
+    report: &mut EnrichReport,
+) {
+    let update = |attribute: &'static str| {
+        let field = field.name.clone();
+        move |before, after| MetadataUpdate {
+            connector: connector.to_string(),
+            field: Some(field.clone()),
+            attribute,
+            before,
+            after,
+        }
+    };
+    let mut description = Some(field.description.clone());
+    merge_value(
+        &mut description,
+        metadata.description.clone(),
+        overwrite,
+        update("description"),
+        report,
+    );
+    merge_value(
+        &mut field.default_value,
+        metadata.default.clone(),
+        overwrite,
+        update("default"),
+        report,
+    );
+    let mut valid_values = field.valid_values.as_ref().map(|values| values.join(", "));
+    merge_value(
+        &mut valid_values,
+        metadata
+            .valid_values
+            .as_ref()
+            .map(|values| values.join(", ")),
+        overwrite,
+        update("valid values"),
+        report,
+    );
+    field.description = description.unwrap_or_default();
+    if valid_values != field.valid_values.as_ref().map(|values| values.join(", ")) {
+        field.valid_values = metadata.valid_values.clone();
+    }
+}
+
+/// Merges the metadata entries for one connector into it
+fn enrich_connector(
+    mut connector: ConnectorDefinition,
+    entries: &[&ConnectorMetadata],
+    overwrite: bool,
+) -> (ConnectorDefinition, EnrichReport) {
+    let mut report = EnrichReport::default();
+    let name = connector.name.clone();
+    for entry in entries {
+        let mut description = Some(connector.description.clone());
+        merge_value(
+            &mut description,
+            entry.description.clone(),
+            overwrite,
+            |before, after| MetadataUpdate {
+                connector: name.clone(),
+                field: None,
+                attribute: "description",
+                before,
+                after,
+            },
+            &mut report,
+        );
+        connector.description = description.unwrap_or_default();
+        for metadata in &entry.fields {
+            let field = connector
+                .required_configs
+                .iter_mut()
+                .chain(connector.optional_configs.iter_mut())
+                .find(|field| field.name == metadata.name);
+            match field {
+                Some(field) => enrich_field(&name, field, metadata, overwrite, &mut report),
+                None => report
+                    .unknown_fields
+                    .push(format!("{}: {}", name, metadata.name)),
+            }
+        }
+    }
+    for field in connector
+        .required_configs
+        .iter()
+        .chain(&connector.optional_configs)
+    {
+        let mut missing = Vec::new();
+        if field.description.trim().is_empty() {
+            missing.push("description");
+        }
+        if !field.required && field.default_value.is_none() {
+            missing.push("default");
+        }
+        if !missing.is_empty() {
+            report.missing.push(MissingMetadata {
+                connector: name.clone(),
+                field: field.name.clone(),
+                missing,
+            });
+        }
+    }
+    (connector, report)
+}
+
+/// Merges metadata into the bundle's connectors. Values the catalog lacks are
+/// filled in; values that differ are kept unless `overwrite` is set. Connectors are
+/// enriched on as many threads as the machine has cores, in catalog order
+pub fn enrich(
+    bundle: &CatalogBundle,
+    metadata: &CatalogMetadata,
+    overwrite: bool,
+) -> (CatalogBundle, EnrichReport) {
+    let mut report = EnrichReport::default();
+    let mut entries: Vec<Vec<&ConnectorMetadata>> = vec![Vec::new(); bundle.connectors.len()];
+    for entry in &metadata.connectors {
+        match find_connector(&bundle.connectors, &entry.name) {
+            Some(index) => entries[index].push(entry),
+            None => report.unmatched_connectors.push(entry.name.clone()),
+        }
+    }
+
+    let work: Vec<(ConnectorDefinition, Vec<&ConnectorMetadata>)> =
+        bundle.connectors.iter().cloned().zip(entries).collect();
+    let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk = work.len().div_ceil(workers).max(1);
+    let results: Vec<(ConnectorDefinition, EnrichReport)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = work
+            .chunks(chunk)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|(connector, entries)| {
+                            enrich_connector(connector.clone(), entries, overwrite)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("enrichment thread panicked"))
+            .collect()
+    });
+
+    let mut connectors = Vec::with_capacity(results.len());
+    for (connector, connector_report) in results {
+        connectors.push(connector);
+        report.applied.extend(connector_report.applied);
+        report.conflicts.extend(connector_report.conflicts);
+        report
+            .unknown_fields
+            .extend(connector_report.unknown_fields);
+        report.missing.extend(connector_report.missing);
+    }
+    (
+        CatalogBundle {
+            format_version: bundle.format_version,
+            connectors,
+        },
+        report,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::config_field;
+
+    fn bundle() -> CatalogBundle {
+        let mut described = config_field("batch.size", "Batch size", "int", false, None);
+        described.default_value = Some("3000".to_string());
+        CatalogBundle {
+            format_version: 1,
+            connectors: vec![ConnectorDefinition {
+                name: "AcmeSink".to_string(),
+                connector_class: "AcmeSink".to_string(),
+                aliases: vec!["com.acme.AcmeSinkConnector".to_string()],
+                description: "Write to Acme".to_string(),
+                required_configs: vec![config_field("acme.url", "", "string", true, None)],
+                optional_configs: vec![
+                    described,
+                    config_field("acme.mode", "Write mode", "string", false, None),
+                ],
+                ..Default::default()
+            }],
+        }
+    }
+
+    const METADATA: &str = r#"{
+      "connectors": [
+        {
+          "name": "com.acme.AcmeSinkConnector",
+          "description": "Write records to Acme",
+          "fields": [
+            {"name": "acme.url", "description": "Acme endpoint URL"},
+            {"name": "acme.mode", "default": "INSERT", "valid_values": ["INSERT", "UPSERT"]},
+            {"name": "batch.size", "default": "500", "description": "Batch size"},
+            {"name": "acme.retired", "description": "Gone"}
+          ]
+        },
+        {"name": "NoSuchSource", "description": "Missing"}
+      ]
+    }"#;
+
+    #[test]
+    fn test_enrich() {
+        let metadata = CatalogMetadata::from_json(METADATA).unwrap();
+        let (enriched, report) = enrich(&bundle(), &metadata, false);
+        let connector = &enriched.connectors[0];
+        assert_eq!(connector.description, "Write to Acme");
+        assert_eq!(
+            connector.required_configs[0].description,
+            "Acme endpoint URL"
+        );
+        let mode = &connector.optional_configs[1];
+        assert_eq!(mode.default_value.as_deref(), Some("INSERT"));
+        assert_eq!(
+            mode.valid_values,
+            Some(vec!["INSERT".to_string(), "UPSERT".to_string()])
+        );
+        assert_eq!(
+            connector.optional_configs[0].default_value.as_deref(),
+            Some("3000")
+        );
+
+        assert_eq!(report.applied.len(), 3);
+        let conflicts: Vec<String> = report.conflicts.iter().map(|c| c.target()).collect();
+        assert_eq!(
+            conflicts,
+            vec!["AcmeSink description", "AcmeSink 'batch.size' default"]
+        );
+        assert_eq!(report.unmatched_connectors, vec!["NoSuchSource"]);
+        assert_eq!(report.unknown_fields, vec!["AcmeSink: acme.retired"]);
+        assert!(report.missing.is_empty());
+        let rendered = report.render();
+        assert!(rendered.contains("3 value(s) applied, 2 conflict(s) kept"));
+        assert!(rendered.contains("AcmeSink 'batch.size' default: '3000' -> '500'"));
+
+        let (enriched, report) = enrich(&bundle(), &metadata, true);
+        assert_eq!(enriched.connectors[0].description, "Write records to Acme");
+        assert_eq!(report.applied.len(), 5);
+        assert!(report.conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_enrich_reports_missing_metadata() {
+        let (_, report) = enrich(&bundle(), &CatalogMetadata::default(), false);
+        assert_eq!(
+            report.missing,
+            vec![
+                MissingMetadata {
+                    connector: "AcmeSink".to_string(),
+                    field: "acme.url".to_string(),
+                    missing: vec!["description"],
+                },
+                MissingMetadata {
+                    connector: "AcmeSink".to_string(),
+                    field: "acme.mode".to_string(),
+                    missing: vec!["default"],
+                },
+            ]
+        );
+        assert!(report.render().contains(
+            "Fields still missing metadata (2):\n    AcmeSink\n      acme.url (description)"
+        ));
+
+        // The whole built-in catalog comes back in order
+        let builtin = CatalogBundle::builtin();
+        let (enriched, _) = enrich(&builtin, &CatalogMetadata::default(), false);
+        let names = |bundle: &CatalogBundle| {
+            bundle
+                .connectors
+                .iter()
+                .map(|c| c.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&enriched), names(&builtin));
+    }
+}
@@ -0,0 +1,566 @@
+use crate::error::ConnectUtilError;
+use crate::types::ConnectorConfig;
+use std::fmt;
+
+/// A parsed policy rule expression, e.g.
+/// `config["snapshot.mode"] == "initial" implies config["table.include.list"] != ""`
+///
+/// Supported syntax:
+/// - literals: `"string"`, `42`, `1.5`, `true`, `false`
+/// - lookups: `config["key"]` (missing keys read as `""`), `connector_class`, `name`
+/// - functions: `has("key")`, `matches(value, "regex")`, `int(value)`
+/// - operators, lowest precedence first: `implies`, `||`/`or`, `&&`/`and`,
+///   `!`/`not`, comparisons `== != < <= > >= in`
+/// - lists for `in`: `config["mode"] in ["a", "b"]`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Literal(Value),
+    List(Vec<Expr>),
+    Config(String),
+    Ident(String),
+    Call(String, Vec<Expr>),
+    Not(Box<Expr>),
+    Binary(Box<Expr>, BinaryOp, Box<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOp {
+    Implies,
+    Or,
+    And,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    In,
+}
+
+/// Runtime value produced while evaluating an expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    Number(f64),
+    Bool(bool),
+    List(Vec<Value>),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::String(s) => write!(f, "\"{}\"", s),
+            Value::Number(n) => write!(f, "{}", n),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::List(items) => {
+                let items: Vec<String> = items.iter().map(|v| v.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    String(String),
+    Number(f64),
+    Ident(String),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    Op(&'static str),
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '[' => {
+                tokens.push(Token::LBracket);
+                i += 1;
+            }
+            ']' => {
+                tokens.push(Token::RBracket);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            '"' => {
+                let mut value = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err("unterminated string literal".to_string()),
+                        Some('"') => {
+                            i += 1;
+                            break;
+                        }
+                        Some('\\') => {
+                            match chars.get(i + 1) {
+                                Some('n') => value.push('\n'),
+                                Some('t') => value.push('\t'),
+                                Some(other) => value.push(*other),
+                                None => return Err("unterminated string literal".to_string()),
+                            }
+                            i += 2;
+                        }
+                        Some(other) => {
+                            value.push(*other);
+                            i += 1;
+                        }
+                    }
+                }
+                tokens.push(Token::String(value));
+            }
+            c if c.is_ascii_digit() => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let literal: String = chars[start..i].iter().collect();
+                let number = literal
+                    .parse()
+                    .map_err(|_| format!("invalid number '{}'", literal))?;
+                tokens.push(Token::Number(number));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect()));
+            }
+            _ => {
+                let two: String = chars[i..chars.len().min(i + 2)].iter().collect();
+                let op = match two.as_str() {
+                    "==" => Some("=="),
+                    "!=" => Some("!="),
+                    "<=" => Some("<="),
+                    ">=" => Some(">="),
+                    "&&" => Some("&&"),
+                    "||" => Some("||"),
+                    _ => None,
+                };
+                if let Some(op) = op {
+                    tokens.push(Token::Op(op));
+                    i += 2;
+                } else {
+                    let op = match c {
+                        '<' => "<",
+                        '>' => ">",
+                        '!' => "!",
+                        _ => return Err(format!("unexpected character '{}'", c)),
+                    };
+                    tokens.push(Token::Op(op));
+                    i += 1;
+                }
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(format!("expected {:?}, found {:?}", expected, token)),
+            None => Err(format!("expected {:?}, found end of expression", expected)),
+        }
+    }
+
+    /// Matches an operator given either as a symbol or as a keyword
+    fn eat_op(&mut self, symbol: &str, keyword: &str) -> bool {
+        let matched = match self.peek() {
+            Some(Token::Op(op)) => *op == symbol,
+            Some(Token::Ident(ident)) => ident == keyword,
+            _ => false,
+        };
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn implies(&mut self) -> Result<Expr, String> {
+        let lhs = self.or()?;
+        if self.eat_op("", "implies") {
+            // Right-associative: a implies b implies c == a implies (b implies c)
+            let rhs = self.implies()?;
+            return Ok(Expr::Binary(
+                Box::new(lhs),
+                BinaryOp::Implies,
+                Box::new(rhs),
+            ));
+        }
+        Ok(lhs)
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.and()?;
+        while self.eat_op("||", "or") {
+            let rhs = self.and()?;
+            lhs = Expr::Binary(Box::new(lhs), BinaryOp::Or, Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.not()?;
+        while self.eat_op("&&", "and") {
+            let rhs = self.not()?;
+            lhs = Expr::Binary(Box::new(lhs), BinaryOp::And, Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn not(&mut self) -> Result<Expr, String> {
+        if self.eat_op("!", "not") {
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr, String> {
+        let lhs = self.primary()?;
+        let op = match self.peek() {
+            Some(Token::Op("==")) => BinaryOp::Eq,
+            Some(Token::Op("!=")) => BinaryOp::Ne,
+            Some(Token::Op("<")) => BinaryOp::Lt,
+            Some(Token::Op("<=")) => BinaryOp::Le,
+            Some(Token::Op(">")) => BinaryOp::Gt,
+            Some(Token::Op(">=")) => BinaryOp::Ge,
+            Some(Token::Ident(ident)) if ident == "in" => BinaryOp::In,
+            _ => return Ok(lhs),
+        };
+        self.pos += 1;
+        let rhs = self.primary()?;
+        Ok(Expr::Binary(Box::new(lhs), op, Box::new(rhs)))
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::String(s)) => Ok(Expr::Literal(Value::String(s))),
+            Some(Token::Number(n)) => Ok(Expr::Literal(Value::Number(n))),
+            Some(Token::LParen) => {
+                let expr = self.implies()?;
+                self.expect(Token::RParen)?;
+                Ok(expr)
+            }
+            Some(Token::LBracket) => {
+                let mut items = Vec::new();
+                if self.peek() != Some(&Token::RBracket) {
+                    loop {
+                        items.push(self.implies()?);
+                        if self.peek() == Some(&Token::Comma) {
+                            self.pos += 1;
+                        } else {
+                            break;
+                        }
+                    }
+                }
+                self.expect(Token::RBracket)?;
+                Ok(Expr::List(items))
+            }
+            Some(Token::Ident(ident)) => match ident.as_str() {
+                "true" => Ok(Expr::Literal(Value::Bool(true))),
+                "false" => Ok(Expr::Literal(Value::Bool(false))),
+                "config" => {
+                    self.expect(Token::LBracket)?;
+                    let key = match self.next() {
+                        Some(Token::String(key)) => key,
+                        _ => return Err("config[...] requires a string key".to_string()),
+                    };
+                    self.expect(Token::RBracket)?;
+                    Ok(Expr::Config(key))
+                }
+                "connector_class" | "name" => Ok(Expr::Ident(ident)),
+                "has" | "matches" | "int" => {
+                    self.expect(Token::LParen)?;
+                    let mut args = Vec::new();
+                    if self.peek() != Some(&Token::RParen) {
+                        loop {
+                            args.push(self.implies()?);
+                            if self.peek() == Some(&Token::Comma) {
+                                self.pos += 1;
+                            } else {
+                                break;
+                            }
+                        }
+                    }
+                    self.expect(Token::RParen)?;
+                    Ok(Expr::Call(ident, args))
+                }
+                other => Err(format!("unknown identifier '{}'", other)),
+            },
+            Some(token) => Err(format!("unexpected token {:?}", token)),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+}
+
+impl Expr {
+    /// Parses an expression rule
+    pub fn parse(source: &str) -> Result<Self, ConnectUtilError> {
+        let tokens = tokenize(source).map_err(|e| {
+            ConnectUtilError::Config(format!("Invalid expression '{}': {}", source, e))
+        })?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser
+            .implies()
+            .and_then(|expr| match parser.peek() {
+                None => Ok(expr),
+                Some(token) => Err(format!("unexpected token {:?}", token)),
+            })
+            .map_err(|e| {
+                ConnectUtilError::Config(format!("Invalid expression '{}': {}", source, e))
+            })?;
+        Ok(expr)
+    }
+
+    /// Evaluates the expression against a connector configuration
+    pub fn evaluate(&self, config: &ConnectorConfig) -> Result<Value, String> {
+        match self {
+            Expr::Literal(value) => Ok(value.clone()),
+            Expr::List(items) => Ok(Value::List(
+                items
+                    .iter()
+                    .map(|item| item.evaluate(config))
+                    .collect::<Result<_, _>>()?,
+            )),
+            Expr::Config(key) => Ok(Value::String(
+                config
+                    .config
+                    .get(key)
+                    .or_else(|| config.sensitive_config.get(key))
+                    .cloned()
+                    .unwrap_or_default(),
+            )),
+            Expr::Ident(ident) => Ok(Value::String(match ident.as_str() {
+                "connector_class" => config.connector_class.clone(),
+                _ => config.name.clone(),
+            })),
+            Expr::Call(name, args) => Self::call(name, args, config),
+            Expr::Not(inner) => Ok(Value::Bool(!inner.evaluate_bool(config)?)),
+            Expr::Binary(lhs, op, rhs) => match op {
+                BinaryOp::Implies => Ok(Value::Bool(
+                    !lhs.evaluate_bool(config)? || rhs.evaluate_bool(config)?,
+                )),
+                BinaryOp::Or => Ok(Value::Bool(
+                    lhs.evaluate_bool(config)? || rhs.evaluate_bool(config)?,
+                )),
+                BinaryOp::And => Ok(Value::Bool(
+                    lhs.evaluate_bool(config)? && rhs.evaluate_bool(config)?,
+                )),
+                _ => {
+                    let lhs = lhs.evaluate(config)?;
+                    let rhs = rhs.evaluate(config)?;
+                    Self::compare(&lhs, *op, &rhs).map(Value::Bool)
+                }
+            },
+        }
+    }
+
+    /// Evaluates the expression and requires a boolean result
+    pub fn evaluate_bool(&self, config: &ConnectorConfig) -> Result<bool, String> {
+        match self.evaluate(config)? {
+            Value::Bool(b) => Ok(b),
+            other => Err(format!("expected a boolean, found {}", other)),
+        }
+    }
+
+    fn call(name: &str, args: &[Expr], config: &ConnectorConfig) -> Result<Value, String> {
+        let values: Vec<Value> = args
+            .iter()
+            .map(|arg| arg.evaluate(config))
+            .collect::<Result<_, _>>()?;
+        match (name, values.as_slice()) {
+            ("has", [Value::String(key)]) => Ok(Value::Bool(
+                config.config.contains_key(key) || config.sensitive_config.contains_key(key),
+            )),
+            ("matches", [Value::String(value), Value::String(pattern)]) => {
+                let regex = regex::Regex::new(pattern)
+                    .map_err(|e| format!("invalid regex '{}': {}", pattern, e))?;
+                Ok(Value::Bool(regex.is_match(value)))
+            }
+            ("int", [value]) => Self::as_number(value)
+                .map(|n| Value::Number(n.trunc()))
+                .ok_or_else(|| format!("int() cannot convert {}", value)),
+            _ => Err(format!("invalid arguments to {}()", name)),
+        }
+    }
+
+    /// Config values are strings, so numeric-looking strings compare as numbers
+    fn as_number(value: &Value) -> Option<f64> {
+        match value {
+            Value::Number(n) => Some(*n),
+            Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    fn loosely_equal(lhs: &Value, rhs: &Value) -> bool {
+        match (lhs, rhs) {
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::Bool(a), Value::String(b)) | (Value::String(b), Value::Bool(a)) => {
+                a.to_string() == *b
+            }
+            _ => match (Self::as_number(lhs), Self::as_number(rhs)) {
+                (Some(a), Some(b)) => a == b,
+                _ => lhs == rhs,
+            },
+        }
+    }
+
+    fn compare(lhs: &Value, op: BinaryOp, rhs: &Value) -> Result<bool, String> {
+        match op {
+            BinaryOp::Eq => Ok(Self::loosely_equal(lhs, rhs)),
+            BinaryOp::Ne => Ok(!Self::loosely_equal(lhs, rhs)),
+            BinaryOp::In => match rhs {
+                Value::List(items) => Ok(items.iter().any(|item| Self::loosely_equal(lhs, item))),
+                Value::String(s) => match lhs {
+                    Value::String(needle) => Ok(s.contains(needle.as_str())),
+                    _ => Err(format!("cannot check {} in {}", lhs, rhs)),
+                },
+                _ => Err(format!("'in' requires a list or string, found {}", rhs)),
+            },
+            _ => {
+                let (a, b) = match (Self::as_number(lhs), Self::as_number(rhs)) {
+                    (Some(a), Some(b)) => (a, b),
+                    _ => return Err(format!("cannot compare {} and {}", lhs, rhs)),
+                };
+                Ok(match op {
+                    BinaryOp::Lt => a < b,
+                    BinaryOp::Le => a <= b,
+                    BinaryOp::Gt => a > b,
+                    _ => a >= b,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config(entries: &[(&str, &str)]) -> ConnectorConfig {
+        ConnectorConfig {
+            name: "orders".to_string(),
+            connector_class: "PostgresCdcSourceV2".to_string(),
+            config: entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            sensitive_config: HashMap::new(),
+        }
+    }
+
+    fn eval(source: &str, config: &ConnectorConfig) -> bool {
+        Expr::parse(source).unwrap().evaluate_bool(config).unwrap()
+    }
+
+    #[test]
+    fn test_implies() {
+        let rule =
+            r#"config["snapshot.mode"] == "initial" implies config["table.include.list"] != """#;
+        assert!(!eval(rule, &config(&[("snapshot.mode", "initial")])));
+        assert!(eval(
+            rule,
+            &config(&[
+                ("snapshot.mode", "initial"),
+                ("table.include.list", "public.orders")
+            ])
+        ));
+        assert!(eval(rule, &config(&[("snapshot.mode", "never")])));
+    }
+
+    #[test]
+    fn test_boolean_operators_and_precedence() {
+        let cfg = config(&[("a", "1"), ("b", "2")]);
+        assert!(eval(r#"config["a"] == "1" && config["b"] == "2""#, &cfg));
+        assert!(eval(r#"config["a"] == "x" or config["b"] == "2""#, &cfg));
+        assert!(eval(r#"not config["a"] == "x""#, &cfg));
+        assert!(eval(r#"!(config["a"] == "1" && config["b"] == "x")"#, &cfg));
+        // && binds tighter than ||
+        assert!(eval(r#"true || false && false"#, &cfg));
+    }
+
+    #[test]
+    fn test_numeric_comparisons() {
+        let cfg = config(&[("tasks.max", "4")]);
+        assert!(eval(r#"config["tasks.max"] <= 4"#, &cfg));
+        assert!(eval(r#"int(config["tasks.max"]) > 2"#, &cfg));
+        assert!(eval(r#"config["tasks.max"] == 4"#, &cfg));
+        assert!(!eval(r#"config["tasks.max"] >= 5"#, &cfg));
+    }
+
+    #[test]
+    fn test_functions_and_identifiers() {
+        let cfg = config(&[("topic.prefix", "prod.orders")]);
+        assert!(eval(r#"has("topic.prefix")"#, &cfg));
+        assert!(!eval(r#"has("missing")"#, &cfg));
+        assert!(eval(r#"matches(config["topic.prefix"], "^prod\\.")"#, &cfg));
+        assert!(eval(r#"connector_class == "PostgresCdcSourceV2""#, &cfg));
+        assert!(eval(r#"name in ["orders", "payments"]"#, &cfg));
+        assert!(eval(r#""orders" in config["topic.prefix"]"#, &cfg));
+    }
+
+    #[test]
+    fn test_bool_config_values() {
+        let cfg = config(&[("auto.create", "true")]);
+        assert!(eval(r#"config["auto.create"] == true"#, &cfg));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Expr::parse(r#"config["a"] =="#).is_err());
+        assert!(Expr::parse(r#"config[a] == "1""#).is_err());
+        assert!(Expr::parse(r#"unknown == "1""#).is_err());
+        assert!(Expr::parse(r#""unterminated"#).is_err());
+        assert!(Expr::parse(r#"true true"#).is_err());
+    }
+
+    #[test]
+    fn test_evaluation_errors() {
+        let cfg = config(&[("a", "text")]);
+        let non_bool = Expr::parse(r#"config["a"]"#).unwrap();
+        assert!(non_bool.evaluate_bool(&cfg).is_err());
+        let bad_compare = Expr::parse(r#"config["a"] > 3"#).unwrap();
+        assert!(bad_compare.evaluate_bool(&cfg).is_err());
+    }
+}
@@ -0,0 +1,300 @@
+use crate::error::ConnectUtilError;
+use crate::say;
+use serde::Deserialize;
+use std::time::{Duration, Instant};
+use testcontainers::{
+    core::{IntoContainerPort, WaitFor},
+    runners::AsyncRunner,
+    ContainerAsync, GenericImage, ImageExt,
+};
+
+const DEFAULT_TAG: &str = "7.6.1";
+const CONNECT_REST_PORT: u16 = 8083;
+
+/// Settings for a local Kafka + Connect smoke test
+#[derive(Debug, Clone)]
+pub struct LocalTestOptions {
+    /// Image tag for `confluentinc/cp-kafka` and `confluentinc/cp-kafka-connect`
+    pub image_tag: String,
+    /// Confluent Hub plugins to install before Connect starts, e.g. `confluentinc/kafka-connect-s3:latest`
+    pub plugins: Vec<String>,
+    /// How long to wait for the connector to reach RUNNING
+    pub timeout: Duration,
+}
+
+impl Default for LocalTestOptions {
+    fn default() -> Self {
+        Self {
+            image_tag: DEFAULT_TAG.to_string(),
+            plugins: vec![],
+            timeout: Duration::from_secs(120),
+        }
+    }
+}
+
+/// Connector state reported by `GET /connectors/{name}/status`
+#[derive(Debug, Deserialize)]
+pub struct ConnectorStatus {
+    pub connector: StateInfo,
+    #[serde(default)]
+    pub tasks: Vec<TaskState>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StateInfo {
+    pub state: String,
+    #[serde(default)]
+    pub trace: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TaskState {
+    pub id: u32,
+    pub state: String,
+    #[serde(default)]
+    pub trace: Option<String>,
+}
+
+/// Outcome of polling a connector's status
+#[derive(Debug, PartialEq)]
+pub enum StatusOutcome {
+    Running,
+    Failed(String),
+    Pending,
+}
+
+impl ConnectorStatus {
+    /// RUNNING only once the connector and every task are running
+    pub fn outcome(&self) -> StatusOutcome {
+        if self.connector.state == "FAILED" {
+            return StatusOutcome::Failed(
+                self.connector
+                    .trace
+                    .clone()
+                    .unwrap_or_else(|| "connector failed".to_string()),
+            );
+        }
+        if let Some(task) = self.tasks.iter().find(|t| t.state == "FAILED") {
+            return StatusOutcome::Failed(format!(
+                "task {} failed: {}",
+                task.id,
+                task.trace.as_deref().unwrap_or("no trace")
+            ));
+        }
+        if self.connector.state == "RUNNING"
+            && !self.tasks.is_empty()
+            && self.tasks.iter().all(|t| t.state == "RUNNING")
+        {
+            StatusOutcome::Running
+        } else {
+            StatusOutcome::Pending
+        }
+    }
+}
+
+fn container_error(what: &str, e: impl std::fmt::Display) -> ConnectUtilError {
+    ConnectUtilError::Unknown(format!("Failed to start {}: {}", what, e))
+}
+
+/// Runs a connector payload against throwaway Kafka and Connect containers
+pub struct LocalHarness {
+    options: LocalTestOptions,
+}
+
+impl LocalHarness {
+    pub fn new(options: LocalTestOptions) -> Self {
+        Self { options }
+    }
+
+    async fn start_kafka(
+        &self,
+        network: &str,
+        host: &str,
+    ) -> Result<ContainerAsync<GenericImage>, ConnectUtilError> {
+        GenericImage::new("confluentinc/cp-kafka", self.options.image_tag.as_str())
+            .with_wait_for(WaitFor::message_on_stdout("Kafka Server started"))
+            .with_network(network)
+            .with_container_name(host)
+            .with_env_var("KAFKA_NODE_ID", "1")
+            .with_env_var("CLUSTER_ID", "MkU3OEVBNTcwNTJENDM2Qk")
+            .with_env_var("KAFKA_PROCESS_ROLES", "broker,controller")
+            .with_env_var(
+                "KAFKA_LISTENERS",
+                "PLAINTEXT://0.0.0.0:9092,CONTROLLER://0.0.0.0:9093",
+            )
+            .with_env_var(
+                "KAFKA_ADVERTISED_LISTENERS",
+                format!("PLAINTEXT://{}:9092", host),
+            )
+            .with_env_var("KAFKA_CONTROLLER_QUORUM_VOTERS", format!("1@{}:9093", host))
+            .with_env_var("KAFKA_CONTROLLER_LISTENER_NAMES", "CONTROLLER")
+            .with_env_var(
+                "KAFKA_LISTENER_SECURITY_PROTOCOL_MAP",
+                "CONTROLLER:PLAINTEXT,PLAINTEXT:PLAINTEXT",
+            )
+            .with_env_var("KAFKA_OFFSETS_TOPIC_REPLICATION_FACTOR", "1")
+            .with_env_var("KAFKA_TRANSACTION_STATE_LOG_REPLICATION_FACTOR", "1")
+            .with_env_var("KAFKA_TRANSACTION_STATE_LOG_MIN_ISR", "1")
+            .start()
+            .await
+            .map_err(|e| container_error("Kafka", e))
+    }
+
+    async fn start_connect(
+        &self,
+        network: &str,
+        bootstrap: &str,
+    ) -> Result<ContainerAsync<GenericImage>, ConnectUtilError> {
+        let mut script = String::new();
+        for plugin in &self.options.plugins {
+            script.push_str(&format!("confluent-hub install --no-prompt {} && ", plugin));
+        }
+        script.push_str("/etc/confluent/docker/run");
+
+        GenericImage::new(
+            "confluentinc/cp-kafka-connect",
+            self.options.image_tag.as_str(),
+        )
+        .with_exposed_port(CONNECT_REST_PORT.tcp())
+        .with_wait_for(WaitFor::message_on_either_std("Kafka Connect started"))
+        .with_network(network)
+        .with_env_var("CONNECT_BOOTSTRAP_SERVERS", bootstrap)
+        .with_env_var("CONNECT_REST_ADVERTISED_HOST_NAME", "localhost")
+        .with_env_var("CONNECT_GROUP_ID", "connect-util-local-test")
+        .with_env_var("CONNECT_CONFIG_STORAGE_TOPIC", "_connect-configs")
+        .with_env_var("CONNECT_OFFSET_STORAGE_TOPIC", "_connect-offsets")
+        .with_env_var("CONNECT_STATUS_STORAGE_TOPIC", "_connect-status")
+        .with_env_var("CONNECT_CONFIG_STORAGE_REPLICATION_FACTOR", "1")
+        .with_env_var("CONNECT_OFFSET_STORAGE_REPLICATION_FACTOR", "1")
+        .with_env_var("CONNECT_STATUS_STORAGE_REPLICATION_FACTOR", "1")
+        .with_env_var(
+            "CONNECT_KEY_CONVERTER",
+            "org.apache.kafka.connect.json.JsonConverter",
+        )
+        .with_env_var(
+            "CONNECT_VALUE_CONVERTER",
+            "org.apache.kafka.connect.json.JsonConverter",
+        )
+        .with_env_var(
+            "CONNECT_PLUGIN_PATH",
+            "/usr/share/java,/usr/share/confluent-hub-components",
+        )
+        .with_cmd(["bash", "-c", script.as_str()])
+        .with_startup_timeout(self.options.timeout)
+        .start()
+        .await
+        .map_err(|e| container_error("Kafka Connect", e))
+    }
+
+    /// Starts the containers, submits the connector, and waits for it to run
+    pub async fn run(&self, payload: &serde_json::Value) -> Result<(), ConnectUtilError> {
+        let name = payload["name"]
+            .as_str()
+            .ok_or_else(|| ConnectUtilError::Config("Connector payload has no name".to_string()))?;
+        let run_id = uuid::Uuid::new_v4().simple().to_string();
+        let network = format!("connect-util-{}", &run_id[..8]);
+        let kafka_host = format!("connect-util-kafka-{}", &run_id[..8]);
+
+        say!("🐳 Starting Kafka ({})...", self.options.image_tag);
+        let _kafka = self.start_kafka(&network, &kafka_host).await?;
+        say!("🐳 Starting Kafka Connect...");
+        let connect = self
+            .start_connect(&network, &format!("{}:9092", kafka_host))
+            .await?;
+        let port = connect
+            .get_host_port_ipv4(CONNECT_REST_PORT)
+            .await
+            .map_err(|e| container_error("Kafka Connect", e))?;
+        let base_url = format!("http://localhost:{}", port);
+
+        let client = reqwest::Client::new();
+        let http_error = |e: reqwest::Error| {
+            ConnectUtilError::Unknown(format!("Connect REST request failed: {}", e))
+        };
+        say!("📤 Submitting connector '{}'...", name);
+        let response = client
+            .post(format!("{}/connectors", base_url))
+            .json(payload)
+            .send()
+            .await
+            .map_err(http_error)?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ConnectUtilError::Validation(format!(
+                "Connect rejected the connector ({}): {}",
+                status, body
+            )));
+        }
+
+        let started = Instant::now();
+        loop {
+            let status: Option<ConnectorStatus> = client
+                .get(format!("{}/connectors/{}/status", base_url, name))
+                .send()
+                .await
+                .map_err(http_error)?
+                .json()
+                .await
+                .ok();
+            match status.as_ref().map(ConnectorStatus::outcome) {
+                Some(StatusOutcome::Running) => return Ok(()),
+                Some(StatusOutcome::Failed(trace)) => {
+                    return Err(ConnectUtilError::Validation(format!(
+                        "Connector '{}' failed: {}",
+                        name, trace
+                    )))
+                }
+                _ if started.elapsed() > self.options.timeout => {
+                    return Err(ConnectUtilError::Validation(format!(
+                        "Connector '{}' did not reach RUNNING within {}s",
+                        name,
+                        self.options.timeout.as_secs()
+                    )))
+                }
+                _ => tokio::time::sleep(Duration::from_secs(2)).await,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(json: &str) -> ConnectorStatus {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_status_running() {
+        let running =
+            status(r#"{"connector":{"state":"RUNNING"},"tasks":[{"id":0,"state":"RUNNING"}]}"#);
+        assert_eq!(running.outcome(), StatusOutcome::Running);
+    }
+
+    #[test]
+    fn test_status_pending_without_tasks() {
+        let pending = status(r#"{"connector":{"state":"RUNNING"},"tasks":[]}"#);
+        assert_eq!(pending.outcome(), StatusOutcome::Pending);
+    }
+
+    #[test]
+    fn test_status_failed_task() {
+        let failed = status(
+            r#"{"connector":{"state":"RUNNING"},
+                "tasks":[{"id":0,"state":"FAILED","trace":"ConnectException: bad host"}]}"#,
+        );
+        assert_eq!(
+            failed.outcome(),
+            StatusOutcome::Failed("task 0 failed: ConnectException: bad host".to_string())
+        );
+    }
+
+    #[test]
+    fn test_default_options() {
+        let options = LocalTestOptions::default();
+        assert_eq!(options.image_tag, DEFAULT_TAG);
+        assert_eq!(options.timeout, Duration::from_secs(120));
+    }
+}
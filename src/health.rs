@@ -0,0 +1,502 @@
+use crate::coverage::{ConnectorCoverage, CoverageStatus};
+use crate::lint::LintIssue;
+use crate::types::{is_dynamic_value, ConnectorConfig};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// Output format for `report`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HealthFormat {
+    #[default]
+    Markdown,
+    Html,
+}
+
+impl FromStr for HealthFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "markdown" | "md" => Ok(HealthFormat::Markdown),
+            "html" => Ok(HealthFormat::Html),
+            _ => Err(format!(
+                "Invalid report format '{}'. Use 'markdown' or 'html'",
+                s
+            )),
+        }
+    }
+}
+
+/// A `config_nonsensitive` value that differs from what Confluent Cloud runs
+#[derive(Debug, Clone, PartialEq)]
+pub struct Drift {
+    pub key: String,
+    pub declared: String,
+    /// `None` when the running connector doesn't set the key
+    pub live: Option<String>,
+}
+
+impl std::fmt::Display for Drift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.live {
+            Some(live) => write!(
+                f,
+                "{}: Terraform '{}', Confluent Cloud '{}'",
+                self.key, self.declared, live
+            ),
+            None => write!(
+                f,
+                "{}: Terraform '{}', not set in Confluent Cloud",
+                self.key, self.declared
+            ),
+        }
+    }
+}
+
+/// Declared values that differ from the running config, sorted by key. Dynamic
+/// values and placeholders can't be compared and are skipped
+pub fn config_drift(declared: &ConnectorConfig, live: &HashMap<String, String>) -> Vec<Drift> {
+    let mut drift: Vec<Drift> = declared
+        .config
+        .iter()
+        .filter(|(_, value)| !is_dynamic_value(value) && !value.contains("<REPLACE_WITH_"))
+        .filter(|(key, value)| live.get(*key) != Some(*value))
+        .map(|(key, value)| Drift {
+            key: key.clone(),
+            declared: value.clone(),
+            live: live.get(key).cloned(),
+        })
+        .collect();
+    drift.sort_by(|a, b| a.key.cmp(&b.key));
+    drift
+}
+
+/// One connector's row in the health report
+#[derive(Debug, Clone)]
+pub struct ConnectorHealth {
+    pub coverage: ConnectorCoverage,
+    pub drift: Vec<Drift>,
+    /// Validation findings of the declaring resource
+    pub findings: Vec<LintIssue>,
+    /// Replacement connector when the class is deprecated
+    pub deprecated: Option<String>,
+}
+
+impl ConnectorHealth {
+    fn errors(&self) -> usize {
+        self.findings
+            .iter()
+            .filter(|issue| issue.severity == "error")
+            .count()
+    }
+
+    fn state(&self) -> &str {
+        self.coverage
+            .live
+            .as_ref()
+            .and_then(|live| live.state.as_deref())
+            .unwrap_or(if self.coverage.live.is_some() {
+                "unknown"
+            } else {
+                "not deployed"
+            })
+    }
+
+    /// ❌ for failures, errors, and connectors that aren't deployed; ⚠️ for anything
+    /// else worth a look
+    fn icon(&self) -> &'static str {
+        if self.errors() > 0
+            || self.state() == "FAILED"
+            || self.coverage.status == CoverageStatus::Missing
+        {
+            "❌"
+        } else if !self.findings.is_empty()
+            || !self.drift.is_empty()
+            || self.deprecated.is_some()
+            || self.state() != "RUNNING"
+            || self.coverage.status == CoverageStatus::Unmanaged
+        {
+            "⚠️"
+        } else {
+            "✅"
+        }
+    }
+
+    fn resource(&self) -> String {
+        match &self.coverage.resource {
+            Some((file, label)) => format!("confluent_connector.{} ({})", label, file.display()),
+            None => "—".to_string(),
+        }
+    }
+
+    fn notes(&self) -> String {
+        let mut notes = Vec::new();
+        if self.coverage.status != CoverageStatus::Managed {
+            notes.push(self.coverage.status.to_string());
+        }
+        if let Some(replacement) = &self.deprecated {
+            notes.push(format!("deprecated, superseded by {}", replacement));
+        }
+        notes.join("; ")
+    }
+}
+
+/// Everything known about one environment's connectors, for weekly ops reviews
+#[derive(Debug, Clone)]
+pub struct HealthReport {
+    pub environment: String,
+    pub cluster: String,
+    pub generated_at: String,
+    pub connectors: Vec<ConnectorHealth>,
+    /// Findings that belong to no deployed or declared connector, e.g. variables
+    pub other_findings: Vec<LintIssue>,
+}
+
+/// A piece of the report, rendered as Markdown or HTML
+enum Section {
+    Heading(usize, String),
+    Paragraph(String),
+    Table(Vec<&'static str>, Vec<Vec<String>>),
+}
+
+fn findings_table(findings: &[LintIssue], with_file: bool) -> Section {
+    let mut headers = vec!["Severity", "Rule"];
+    if with_file {
+        headers.push("File");
+    }
+    headers.extend(["Line", "Message"]);
+    let rows = findings
+        .iter()
+        .map(|issue| {
+            let mut row = vec![issue.severity.to_string(), issue.rule.name()];
+            if with_file {
+                row.push(issue.file.display().to_string());
+            }
+            row.extend([issue.line.to_string(), issue.message.clone()]);
+            row
+        })
+        .collect();
+    Section::Table(headers, rows)
+}
+
+impl HealthReport {
+    fn summary(&self) -> String {
+        let count = |status| {
+            self.connectors
+                .iter()
+                .filter(|c| c.coverage.status == status)
+                .count()
+        };
+        let errors: usize = self.connectors.iter().map(ConnectorHealth::errors).sum();
+        let findings: usize = self.connectors.iter().map(|c| c.findings.len()).sum();
+        format!(
+            "{} connector(s): {} managed, {} unmanaged, {} missing. {} with config drift, {} deprecated. {} error(s), {} warning(s).",
+            self.connectors.len(),
+            count(CoverageStatus::Managed),
+            count(CoverageStatus::Unmanaged),
+            count(CoverageStatus::Missing),
+            self.connectors.iter().filter(|c| !c.drift.is_empty()).count(),
+            self.connectors.iter().filter(|c| c.deprecated.is_some()).count(),
+            errors,
+            findings - errors
+        )
+    }
+
+    fn sections(&self) -> Vec<Section> {
+        let mut sections = vec![
+            Section::Heading(
+                1,
+                format!("Connector health: {} / {}", self.environment, self.cluster),
+            ),
+            Section::Paragraph(format!("Generated {}", self.generated_at)),
+            Section::Paragraph(self.summary()),
+        ];
+        if !self.connectors.is_empty() {
+            sections.push(Section::Table(
+                vec![
+                    "",
+                    "Connector",
+                    "Resource",
+                    "State",
+                    "Drift",
+                    "Errors",
+                    "Warnings",
+                    "Notes",
+                ],
+                self.connectors
+                    .iter()
+                    .map(|c| {
+                        vec![
+                            c.icon().to_string(),
+                            c.coverage.name.clone(),
+                            c.resource(),
+                            c.state().to_string(),
+                            c.drift.len().to_string(),
+                            c.errors().to_string(),
+                            (c.findings.len() - c.errors()).to_string(),
+                            c.notes(),
+                        ]
+                    })
+                    .collect(),
+            ));
+        }
+
+        for connector in &self.connectors {
+            if connector.drift.is_empty() && connector.findings.is_empty() {
+                continue;
+            }
+            sections.push(Section::Heading(2, connector.coverage.name.clone()));
+            if !connector.drift.is_empty() {
+                sections.push(Section::Heading(3, "Config drift".to_string()));
+                sections.push(Section::Table(
+                    vec!["Key", "Terraform", "Confluent Cloud"],
+                    connector
+                        .drift
+                        .iter()
+                        .map(|d| {
+                            vec![
+                                d.key.clone(),
+                                d.declared.clone(),
+                                d.live.clone().unwrap_or_else(|| "(not set)".to_string()),
+                            ]
+                        })
+                        .collect(),
+                ));
+            }
+            if !connector.findings.is_empty() {
+                sections.push(Section::Heading(3, "Validation findings".to_string()));
+                sections.push(findings_table(&connector.findings, false));
+            }
+        }
+        if !self.other_findings.is_empty() {
+            sections.push(Section::Heading(2, "Other findings".to_string()));
+            sections.push(findings_table(&self.other_findings, true));
+        }
+        sections
+    }
+
+    pub fn render(&self, format: HealthFormat) -> String {
+        match format {
+            HealthFormat::Markdown => self.render_markdown(),
+            HealthFormat::Html => self.render_html(),
+        }
+    }
+
+    fn render_markdown(&self) -> String {
+        let cell = |text: &str| text.replace('|', "\\|").replace('\n', " ");
+        let mut out = String::new();
+        for section in self.sections() {
+            match section {
+                Section::Heading(level, text) => {
+                    let _ = writeln!(out, "{} {}\n", "#".repeat(level), text);
+                }
+                Section::Paragraph(text) => {
+                    let _ = writeln!(out, "{}\n", text);
+                }
+                Section::Table(headers, rows) => {
+                    let _ = writeln!(out, "| {} |", headers.join(" | "));
+                    let rule: Vec<&str> = headers.iter().map(|_| "---").collect();
+                    let _ = writeln!(out, "|{}|", rule.join("|"));
+                    for row in rows {
+                        let cells: Vec<String> = row.iter().map(|c| cell(c)).collect();
+                        let _ = writeln!(out, "| {} |", cells.join(" | "));
+                    }
+                    out.push('\n');
+                }
+            }
+        }
+        out
+    }
+
+    fn render_html(&self) -> String {
+        let escape = |text: &str| {
+            text.replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .replace('"', "&quot;")
+        };
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">"
+        );
+        let _ = writeln!(
+            out,
+            "<title>Connector health: {} / {}</title>",
+            escape(&self.environment),
+            escape(&self.cluster)
+        );
+        let _ = writeln!(
+            out,
+            "<style>body {{ font-family: sans-serif; }} table {{ border-collapse: collapse; }} th, td {{ border: 1px solid #ccc; padding: 4px 8px; text-align: left; }}</style>"
+        );
+        let _ = writeln!(out, "</head>\n<body>");
+        for section in self.sections() {
+            match section {
+                Section::Heading(level, text) => {
+                    let _ = writeln!(out, "<h{0}>{1}</h{0}>", level, escape(&text));
+                }
+                Section::Paragraph(text) => {
+                    let _ = writeln!(out, "<p>{}</p>", escape(&text));
+                }
+                Section::Table(headers, rows) => {
+                    let _ = writeln!(out, "<table>");
+                    let headers: Vec<String> = headers
+                        .iter()
+                        .map(|h| format!("<th>{}</th>", escape(h)))
+                        .collect();
+                    let _ = writeln!(out, "<tr>{}</tr>", headers.concat());
+                    for row in rows {
+                        let cells: Vec<String> = row
+                            .iter()
+                            .map(|c| format!("<td>{}</td>", escape(c)))
+                            .collect();
+                        let _ = writeln!(out, "<tr>{}</tr>", cells.concat());
+                    }
+                    let _ = writeln!(out, "</table>");
+                }
+            }
+        }
+        let _ = writeln!(out, "</body>\n</html>");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cloud::LiveConnector;
+    use crate::lint::LintRule;
+    use std::path::PathBuf;
+
+    fn declared(config: &[(&str, &str)]) -> ConnectorConfig {
+        ConnectorConfig {
+            name: "orders".to_string(),
+            connector_class: "DatagenSource".to_string(),
+            config: config
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            sensitive_config: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_config_drift() {
+        let live = HashMap::from([
+            ("tasks.max".to_string(), "4".to_string()),
+            ("kafka.topic".to_string(), "orders".to_string()),
+        ]);
+        let drift = config_drift(
+            &declared(&[
+                ("tasks.max", "2"),
+                ("kafka.topic", "orders"),
+                ("output.data.format", "AVRO"),
+                ("kafka.service.account.id", "${var.service_account}"),
+                ("quickstart", "<REPLACE_WITH_QUICKSTART>"),
+            ]),
+            &live,
+        );
+        assert_eq!(
+            drift,
+            vec![
+                Drift {
+                    key: "output.data.format".to_string(),
+                    declared: "AVRO".to_string(),
+                    live: None,
+                },
+                Drift {
+                    key: "tasks.max".to_string(),
+                    declared: "2".to_string(),
+                    live: Some("4".to_string()),
+                },
+            ]
+        );
+        assert_eq!(
+            drift[0].to_string(),
+            "output.data.format: Terraform 'AVRO', not set in Confluent Cloud"
+        );
+        assert_eq!(
+            drift[1].to_string(),
+            "tasks.max: Terraform '2', Confluent Cloud '4'"
+        );
+    }
+
+    #[test]
+    fn test_render_health_report() {
+        let issue = |rule: LintRule, connector: Option<&str>, message: &str| LintIssue {
+            rule,
+            severity: rule.severity(),
+            file: PathBuf::from("main.tf"),
+            line: 3,
+            connector: connector.map(str::to_string),
+            message: message.to_string(),
+        };
+        let report = HealthReport {
+            environment: "env-abc".to_string(),
+            cluster: "lkc-xyz".to_string(),
+            generated_at: "2026-10-14 09:00 UTC".to_string(),
+            connectors: vec![
+                ConnectorHealth {
+                    coverage: ConnectorCoverage {
+                        name: "orders".to_string(),
+                        status: CoverageStatus::Managed,
+                        live: Some(LiveConnector {
+                            name: "orders".to_string(),
+                            id: Some("lcc-1".to_string()),
+                            connector_class: Some("MySqlCdcSource".to_string()),
+                            state: Some("RUNNING".to_string()),
+                            config: HashMap::new(),
+                        }),
+                        resource: Some((PathBuf::from("main.tf"), "orders".to_string())),
+                    },
+                    drift: vec![Drift {
+                        key: "tasks.max".to_string(),
+                        declared: "2".to_string(),
+                        live: Some("4".to_string()),
+                    }],
+                    findings: vec![issue(
+                        LintRule::InvalidConfig,
+                        Some("orders"),
+                        "missing <database.hostname>",
+                    )],
+                    deprecated: Some("MySqlCdcSourceV2".to_string()),
+                },
+                ConnectorHealth {
+                    coverage: ConnectorCoverage {
+                        name: "refunds".to_string(),
+                        status: CoverageStatus::Missing,
+                        live: None,
+                        resource: Some((PathBuf::from("main.tf"), "refunds".to_string())),
+                    },
+                    drift: vec![],
+                    findings: vec![],
+                    deprecated: None,
+                },
+            ],
+            other_findings: vec![issue(LintRule::UnusedVariable, None, "var.old is unused")],
+        };
+
+        let markdown = report.render(HealthFormat::Markdown);
+        assert!(markdown.starts_with("# Connector health: env-abc / lkc-xyz\n\nGenerated 2026-10-14 09:00 UTC\n\n2 connector(s): 1 managed, 0 unmanaged, 1 missing. 1 with config drift, 1 deprecated. 1 error(s), 0 warning(s).\n"));
+        assert!(markdown.contains("| ❌ | orders | confluent_connector.orders (main.tf) | RUNNING | 1 | 1 | 0 | deprecated, superseded by MySqlCdcSourceV2 |\n"));
+        assert!(markdown.contains("| ❌ | refunds | confluent_connector.refunds (main.tf) | not deployed | 0 | 0 | 0 | missing |\n"));
+        assert!(markdown.contains("## orders\n\n### Config drift\n\n| Key | Terraform | Confluent Cloud |\n|---|---|---|\n| tasks.max | 2 | 4 |\n"));
+        assert!(!markdown.contains("## refunds"));
+        assert!(markdown.contains("## Other findings\n\n| Severity | Rule | File | Line | Message |\n|---|---|---|---|---|\n| warning | connect_util_unused_variable | main.tf | 3 | var.old is unused |\n"));
+
+        let html = report.render(HealthFormat::Html);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h1>Connector health: env-abc / lkc-xyz</h1>"));
+        assert!(html.contains("<td>missing &lt;database.hostname&gt;</td>"));
+        assert!(html.trim_end().ends_with("</html>"));
+    }
+
+    #[test]
+    fn test_health_format_from_str() {
+        assert_eq!("html".parse(), Ok(HealthFormat::Html));
+        assert_eq!("Markdown".parse(), Ok(HealthFormat::Markdown));
+        assert!("pdf".parse::<HealthFormat>().is_err());
+    }
+}
@@ -0,0 +1,333 @@
+use crate::catalog::TranslationCoverage;
+use crate::types::{ConfigField, ConnectorDefinition};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+/// Environment variable that picks the language when `--lang` isn't given
+pub const LANG_ENV: &str = "CONNECT_UTIL_LANG";
+
+static LOCALE: AtomicU8 = AtomicU8::new(Locale::En as u8);
+
+/// Language connector and field descriptions are shown in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+}
+
+impl std::str::FromStr for Locale {
+    type Err = String;
+
+    /// Takes a language code, alone or with a region and encoding as in
+    /// `es_MX.UTF-8`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s.split(['_', '-', '.']).next().unwrap_or_default();
+        Locale::ALL
+            .into_iter()
+            .find(|locale| locale.code().eq_ignore_ascii_case(language))
+            .ok_or_else(|| format!("Invalid language '{}'. Use 'en' or 'es'", s))
+    }
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Es];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+        }
+    }
+
+    /// The bundled message table. English is the source text every other table
+    /// falls back to
+    fn source(self) -> &'static str {
+        match self {
+            Locale::En => include_str!("../locales/en.json"),
+            Locale::Es => include_str!("../locales/es.json"),
+        }
+    }
+
+    /// The locale's message table, parsed on first use
+    pub fn messages(self) -> &'static MessageTable {
+        static TABLES: OnceLock<Vec<MessageTable>> = OnceLock::new();
+        let tables = TABLES.get_or_init(|| {
+            Locale::ALL
+                .iter()
+                .map(|locale| {
+                    serde_json::from_str(locale.source()).expect("bundled message table is valid")
+                })
+                .collect()
+        });
+        &tables[self as usize]
+    }
+
+    /// The connector's description from this locale's table, else the English one.
+    /// Connectors neither table knows, such as ones loaded with `--definitions`,
+    /// keep their own description
+    pub fn connector_description(self, connector: &ConnectorDefinition) -> &str {
+        [self, Locale::En]
+            .into_iter()
+            .find_map(|locale| locale.messages().connector(&connector.name))
+            .unwrap_or(&connector.description)
+    }
+
+    /// The field's description, looked up as for `connector_description`
+    pub fn field_description<'a>(
+        self,
+        connector: &ConnectorDefinition,
+        field: &'a ConfigField,
+    ) -> &'a str {
+        [self, Locale::En]
+            .into_iter()
+            .find_map(|locale| locale.messages().field(&connector.name, &field.name))
+            .unwrap_or(&field.description)
+    }
+}
+
+/// Connector and field descriptions for one locale
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MessageTable {
+    /// Connector descriptions by connector name
+    #[serde(default)]
+    pub connectors: BTreeMap<String, String>,
+    /// Field descriptions by config key, for every connector with the key
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    /// Field descriptions for one connector, by connector name and then config
+    /// key, ahead of `fields`
+    #[serde(default)]
+    pub connector_fields: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl MessageTable {
+    pub fn connector(&self, connector: &str) -> Option<&str> {
+        self.connectors.get(connector).map(String::as_str)
+    }
+
+    pub fn field(&self, connector: &str, key: &str) -> Option<&str> {
+        self.connector_fields
+            .get(connector)
+            .and_then(|fields| fields.get(key))
+            .or_else(|| self.fields.get(key))
+            .map(String::as_str)
+    }
+
+    /// How many of the catalog's connector and field descriptions the table
+    /// covers
+    pub fn coverage(&self, connectors: &[ConnectorDefinition]) -> TranslationCoverage {
+        let mut coverage = TranslationCoverage::default();
+        for connector in connectors {
+            coverage.connectors.total += 1;
+            coverage.connectors.count += usize::from(self.connectors.contains_key(&connector.name));
+            let own = self.connector_fields.get(&connector.name);
+            for field in connector
+                .required_configs
+                .iter()
+                .chain(&connector.optional_configs)
+            {
+                coverage.fields.total += 1;
+                coverage.fields.count += usize::from(
+                    own.is_some_and(|fields| fields.contains_key(&field.name))
+                        || self.fields.contains_key(&field.name),
+                );
+            }
+        }
+        coverage
+    }
+
+    /// Entries naming a connector or field that isn't in the catalog, so stale
+    /// translations show up when the catalog changes
+    pub fn unknown_entries(&self, connectors: &[ConnectorDefinition]) -> Vec<String> {
+        let find = |name: &str| connectors.iter().find(|c| c.name == name);
+        let has_field = |connector: &ConnectorDefinition, key: &str| {
+            connector
+                .required_configs
+                .iter()
+                .chain(&connector.optional_configs)
+                .any(|field| field.name == key)
+        };
+        let mut unknown: Vec<String> = self
+            .connectors
+            .keys()
+            .filter(|name| find(name).is_none())
+            .map(|name| format!("connectors.{}", name))
+            .collect();
+        unknown.extend(
+            self.fields
+                .keys()
+                .filter(|key| !connectors.iter().any(|c| has_field(c, key)))
+                .map(|key| format!("fields.{}", key)),
+        );
+        for (name, fields) in &self.connector_fields {
+            unknown.extend(
+                fields
+                    .keys()
+                    .filter(|key| !find(name).is_some_and(|c| has_field(c, key)))
+                    .map(|key| format!("connector_fields.{}.{}", name, key)),
+            );
+        }
+        unknown
+    }
+}
+
+/// Sets the language from `--lang`, or else from `CONNECT_UTIL_LANG`
+pub fn configure(lang: Option<&str>) -> Result<(), String> {
+    let lang = match lang {
+        Some(lang) => Some(lang.to_string()),
+        None => std::env::var(LANG_ENV).ok().filter(|v| !v.is_empty()),
+    };
+    if let Some(lang) = lang {
+        LOCALE.store(lang.parse::<Locale>()? as u8, Ordering::Relaxed);
+    }
+    Ok(())
+}
+
+pub fn locale() -> Locale {
+    Locale::ALL[usize::from(LOCALE.load(Ordering::Relaxed))]
+}
+
+/// The connector's description in the configured language
+pub fn connector_description(connector: &ConnectorDefinition) -> &str {
+    locale().connector_description(connector)
+}
+
+/// The field's description in the configured language
+pub fn field_description<'a>(connector: &ConnectorDefinition, field: &'a ConfigField) -> &'a str {
+    locale().field_description(connector, field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Coverage;
+
+    fn field<'a>(connector: &'a ConnectorDefinition, key: &str) -> &'a ConfigField {
+        connector
+            .required_configs
+            .iter()
+            .chain(&connector.optional_configs)
+            .find(|field| field.name == key)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_locale_from_str() {
+        assert_eq!("en".parse::<Locale>(), Ok(Locale::En));
+        assert_eq!("ES".parse::<Locale>(), Ok(Locale::Es));
+        assert_eq!("es_MX.UTF-8".parse::<Locale>(), Ok(Locale::Es));
+        assert_eq!("es-AR".parse::<Locale>(), Ok(Locale::Es));
+        assert_eq!(
+            "fr".parse::<Locale>(),
+            Err("Invalid language 'fr'. Use 'en' or 'es'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_descriptions() {
+        let spanner = ConnectorDefinition::get_connector_by_name("SpannerSink").unwrap();
+        let datagen = ConnectorDefinition::get_connector_by_name("DatagenSource").unwrap();
+        assert_eq!(
+            Locale::En.connector_description(&datagen),
+            "Generate test data for development and testing"
+        );
+        assert_eq!(
+            Locale::En.field_description(&spanner, field(&spanner, "insert.mode")),
+            "Google Cloud Spanner insert mode"
+        );
+
+        assert_eq!(
+            Locale::Es.connector_description(&datagen),
+            "Genera datos de prueba para desarrollo y pruebas"
+        );
+        // A connector's own entry wins over the shared one
+        assert_eq!(
+            Locale::Es.field_description(&spanner, field(&spanner, "max.batch.size")),
+            "Tamaño máximo de lote de Google Cloud Spanner"
+        );
+        assert_eq!(
+            Locale::Es.field_description(&spanner, field(&spanner, "insert.mode")),
+            "Modo de inserción"
+        );
+        // Untranslated fields fall back to the English table
+        let quickstart = field(&datagen, "quickstart");
+        assert_eq!(
+            Locale::Es.field_description(&datagen, quickstart),
+            Locale::En
+                .messages()
+                .field("DatagenSource", "quickstart")
+                .unwrap()
+        );
+
+        // Connectors no table knows keep their own descriptions
+        let mut custom = datagen.clone();
+        custom.name = "CustomSource".to_string();
+        custom.description = "Our own source".to_string();
+        custom.optional_configs[0].name = "custom.key".to_string();
+        custom.optional_configs[0].description = "Our own key".to_string();
+        assert_eq!(Locale::Es.connector_description(&custom), "Our own source");
+        assert_eq!(
+            Locale::Es.field_description(&custom, &custom.optional_configs[0]),
+            "Our own key"
+        );
+    }
+
+    #[test]
+    fn test_coverage() {
+        let datagen = ConnectorDefinition::get_connector_by_name("DatagenSource").unwrap();
+        let fields = datagen.required_configs.len() + datagen.optional_configs.len();
+        let connectors = [datagen];
+        assert_eq!(
+            MessageTable::default().coverage(&connectors),
+            TranslationCoverage {
+                connectors: Coverage { count: 0, total: 1 },
+                fields: Coverage {
+                    count: 0,
+                    total: fields
+                },
+            }
+        );
+
+        let table = MessageTable {
+            connectors: BTreeMap::from([("DatagenSource".to_string(), "Datos".to_string())]),
+            fields: BTreeMap::from([("kafka.topic".to_string(), "Tema".to_string())]),
+            connector_fields: BTreeMap::from([(
+                "DatagenSource".to_string(),
+                BTreeMap::from([
+                    ("quickstart".to_string(), "Plantilla".to_string()),
+                    ("kafka.topic".to_string(), "Tema".to_string()),
+                ]),
+            )]),
+        };
+        let coverage = table.coverage(&connectors);
+        assert_eq!(coverage.connectors, Coverage { count: 1, total: 1 });
+        assert_eq!(coverage.fields.count, 2);
+    }
+
+    #[test]
+    fn test_bundled_tables_match_the_catalog() {
+        let connectors = ConnectorDefinition::get_all_connectors();
+        let english = Locale::En.messages().coverage(&connectors);
+        assert_eq!(english.connectors.count, english.connectors.total);
+        assert_eq!(english.fields.count, english.fields.total);
+        for locale in Locale::ALL {
+            assert_eq!(
+                locale.messages().unknown_entries(&connectors),
+                Vec::<String>::new(),
+                "{}",
+                locale.code()
+            );
+        }
+        let table = MessageTable {
+            fields: BTreeMap::from([("no.such.key".to_string(), String::new())]),
+            ..Default::default()
+        };
+        assert_eq!(
+            table.unknown_entries(&connectors),
+            vec!["fields.no.such.key"]
+        );
+    }
+}
@@ -0,0 +1,342 @@
+use crate::locals::{object_get, resolve, Locals};
+use hcl::{Attribute, Block, Body, Expression, Structure, TemplateExpr, TraversalOperator};
+use regex::Regex;
+
+/// Largest literal `count` expanded into instances; anything bigger is checked as
+/// a template, so a typo such as `count = 10000000` can't exhaust memory
+const MAX_COUNT: u64 = 1000;
+
+/// One statically known instance of a `for_each` or `count` resource
+#[derive(Debug, Clone)]
+pub struct Instance {
+    /// `each.key` for `for_each`, `count.index` for `count`
+    pub key: Expression,
+    /// `each.value`; unused for `count`
+    pub value: Expression,
+}
+
+/// How many copies of a block Terraform will create
+#[derive(Debug, Clone)]
+pub enum Instances {
+    /// No `for_each` or `count`
+    Single,
+    /// The collection resolved to literal values
+    Static(Vec<Instance>),
+    /// The collection depends on variables or other resources; `meta` is the
+    /// meta-argument as written, e.g. `for_each = var.connectors`
+    Dynamic { meta: String },
+}
+
+fn literal_text(expr: &Expression) -> Option<String> {
+    match expr {
+        Expression::String(s) => Some(s.clone()),
+        Expression::Number(n) => Some(n.to_string()),
+        Expression::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Elements of a literal list or `toset([...])`
+fn literal_elements(expr: &Expression) -> Option<&Vec<Expression>> {
+    match expr {
+        Expression::Array(elements) => Some(elements),
+        Expression::FuncCall(func) if func.name.as_str() == "toset" => match func.args.as_slice() {
+            [Expression::Array(elements)] => Some(elements),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Finds `for_each`/`count` on a block body and expands it when the collection
+/// is a literal, or a local that resolves to one
+pub fn instances(body: &Body, locals: &Locals) -> Instances {
+    for attribute in body.attributes() {
+        let meta = || {
+            format!(
+                "{} = {}",
+                attribute.key(),
+                hcl::format::to_string(attribute.expr())
+                    .unwrap_or_default()
+                    .trim()
+            )
+        };
+        let collection = resolve(attribute.expr(), locals);
+        match attribute.key() {
+            "for_each" => {
+                let instances = match &collection {
+                    Expression::Object(object) => Some(
+                        object
+                            .iter()
+                            .map(|(key, value)| Instance {
+                                key: Expression::String(key.to_string()),
+                                value: value.clone(),
+                            })
+                            .collect(),
+                    ),
+                    other => literal_elements(other).and_then(|elements| {
+                        elements
+                            .iter()
+                            .map(|element| {
+                                literal_text(element).map(|key| Instance {
+                                    key: Expression::String(key.clone()),
+                                    value: Expression::String(key),
+                                })
+                            })
+                            .collect::<Option<Vec<_>>>()
+                    }),
+                };
+                return match instances {
+                    Some(instances) => Instances::Static(instances),
+                    None => Instances::Dynamic { meta: meta() },
+                };
+            }
+            "count" => {
+                return match &collection {
+                    Expression::Number(n) => match n.as_u64().filter(|&n| n <= MAX_COUNT) {
+                        Some(count) => Instances::Static(
+                            (0..count)
+                                .map(|index| Instance {
+                                    key: Expression::Number(index.into()),
+                                    value: Expression::Null,
+                                })
+                                .collect(),
+                        ),
+                        None => Instances::Dynamic { meta: meta() },
+                    },
+                    _ => Instances::Dynamic { meta: meta() },
+                };
+            }
+            _ => {}
+        }
+    }
+    Instances::Single
+}
+
+impl Instance {
+    /// Terraform address of this instance, e.g. `orders["eu"]` or `orders[0]`
+    pub fn address(&self, label: &str) -> String {
+        match &self.key {
+            Expression::Number(n) => format!("{}[{}]", label, n),
+            key => format!("{}[\"{}\"]", label, literal_text(key).unwrap_or_default()),
+        }
+    }
+
+    /// Looks up `each.key`, `each.value[.attr...]`, or `count.index`
+    fn lookup(&self, root: &str, path: &[&str]) -> Option<Expression> {
+        match (root, path) {
+            ("each", ["key"]) => Some(self.key.clone()),
+            ("count", ["index"]) => Some(self.key.clone()),
+            ("each", ["value", rest @ ..]) => {
+                let mut value = self.value.clone();
+                for attr in rest {
+                    value = match &value {
+                        Expression::Object(object) => object_get(object, attr)?.clone(),
+                        _ => return None,
+                    };
+                }
+                Some(value)
+            }
+            _ => None,
+        }
+    }
+
+    /// Replaces `each.*` and `count.index` with this instance's values
+    pub fn substitute(&self, expr: &Expression) -> Expression {
+        match expr {
+            Expression::Traversal(traversal) => {
+                let Expression::Variable(root) = &traversal.expr else {
+                    return expr.clone();
+                };
+                let path: Option<Vec<&str>> = traversal
+                    .operators
+                    .iter()
+                    .map(|operator| match operator {
+                        TraversalOperator::GetAttr(attr) => Some(attr.as_str()),
+                        TraversalOperator::Index(Expression::String(key)) => Some(key.as_str()),
+                        _ => None,
+                    })
+                    .collect();
+                path.and_then(|path| self.lookup(root.as_str(), &path))
+                    .unwrap_or_else(|| expr.clone())
+            }
+            Expression::TemplateExpr(template) => {
+                let pattern = Regex::new(r"\$\{\s*((?:each|count)(?:\.[A-Za-z_][\w-]*)+)\s*\}")
+                    .expect("valid template reference pattern");
+                let raw = match template.as_ref() {
+                    TemplateExpr::QuotedString(raw) => raw,
+                    TemplateExpr::Heredoc(heredoc) => &heredoc.template,
+                };
+                let replaced = pattern.replace_all(raw, |captures: &regex::Captures| {
+                    let mut parts = captures[1].split('.');
+                    let root = parts.next().unwrap_or_default();
+                    let path: Vec<&str> = parts.collect();
+                    self.lookup(root, &path)
+                        .as_ref()
+                        .and_then(literal_text)
+                        .unwrap_or_else(|| captures[0].to_string())
+                });
+                let mut template = template.clone();
+                match template.as_mut() {
+                    TemplateExpr::QuotedString(raw) => *raw = replaced.into_owned(),
+                    TemplateExpr::Heredoc(heredoc) => heredoc.template = replaced.into_owned(),
+                }
+                Expression::TemplateExpr(template)
+            }
+            Expression::Object(object) => Expression::Object(
+                object
+                    .iter()
+                    .map(|(key, value)| (key.clone(), self.substitute(value)))
+                    .collect(),
+            ),
+            Expression::Array(elements) => {
+                Expression::Array(elements.iter().map(|e| self.substitute(e)).collect())
+            }
+            Expression::FuncCall(func) => {
+                let mut func = func.clone();
+                func.args = func.args.iter().map(|arg| self.substitute(arg)).collect();
+                Expression::FuncCall(func)
+            }
+            Expression::Parenthesis(inner) => {
+                Expression::Parenthesis(Box::new(self.substitute(inner)))
+            }
+            Expression::Conditional(conditional) => {
+                let mut conditional = conditional.clone();
+                conditional.cond_expr = self.substitute(&conditional.cond_expr);
+                conditional.true_expr = self.substitute(&conditional.true_expr);
+                conditional.false_expr = self.substitute(&conditional.false_expr);
+                Expression::Conditional(conditional)
+            }
+            _ => expr.clone(),
+        }
+    }
+
+    /// A copy of the block body with this instance's values substituted throughout
+    pub fn apply(&self, body: &Body) -> Body {
+        Body(
+            body.iter()
+                .map(|structure| match structure {
+                    Structure::Attribute(attribute) => Structure::Attribute(Attribute::new(
+                        attribute.key(),
+                        self.substitute(attribute.expr()),
+                    )),
+                    Structure::Block(block) => {
+                        let mut block: Block = block.clone();
+                        block.body = self.apply(&block.body);
+                        Structure::Block(block)
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body(content: &str) -> Body {
+        hcl::from_str(content).unwrap()
+    }
+
+    fn attribute(body: &Body, key: &str) -> Expression {
+        body.attributes()
+            .find(|a| a.key() == key)
+            .unwrap()
+            .expr()
+            .clone()
+    }
+
+    #[test]
+    fn test_instances_for_each_object() {
+        let resource = body(
+            r#"
+for_each = {
+  eu = { topic = "orders.eu" }
+  us = { topic = "orders.us" }
+}
+name  = "${each.key}-sink"
+topic = each.value.topic
+"#,
+        );
+        let Instances::Static(instances) = instances(&resource, &Locals::new()) else {
+            panic!("expected static instances");
+        };
+        assert_eq!(instances.len(), 2);
+        assert_eq!(instances[0].address("orders"), "orders[\"eu\"]");
+
+        let applied = instances[1].apply(&resource);
+        assert_eq!(
+            attribute(&applied, "topic"),
+            Expression::String("orders.us".to_string())
+        );
+        let Expression::TemplateExpr(name) = attribute(&applied, "name") else {
+            panic!("expected template");
+        };
+        assert_eq!(name.to_string(), "us-sink");
+    }
+
+    #[test]
+    fn test_instances_for_each_set_from_local() {
+        let locals = Locals::from([(
+            "regions".to_string(),
+            attribute(&body("x = toset([\"eu\", \"us\"])"), "x"),
+        )]);
+        let resource = body("for_each = local.regions\nregion = each.value\n");
+        let Instances::Static(instances) = instances(&resource, &locals) else {
+            panic!("expected static instances");
+        };
+        assert_eq!(instances.len(), 2);
+        assert_eq!(
+            attribute(&instances[0].apply(&resource), "region"),
+            Expression::String("eu".to_string())
+        );
+    }
+
+    #[test]
+    fn test_instances_count() {
+        let resource = body("count = 2\nname = \"sink-${count.index}\"\n");
+        let Instances::Static(instances) = instances(&resource, &Locals::new()) else {
+            panic!("expected static instances");
+        };
+        assert_eq!(instances.len(), 2);
+        assert_eq!(instances[1].address("sink"), "sink[1]");
+        let Expression::TemplateExpr(name) = attribute(&instances[1].apply(&resource), "name")
+        else {
+            panic!("expected template");
+        };
+        assert_eq!(name.to_string(), "sink-1");
+    }
+
+    #[test]
+    fn test_instances_dynamic() {
+        let resource = body("for_each = var.connectors\nname = each.key\n");
+        match instances(&resource, &Locals::new()) {
+            Instances::Dynamic { meta } => assert_eq!(meta, "for_each = var.connectors"),
+            other => panic!("expected dynamic instances, got {:?}", other),
+        }
+        let resource = body("count = var.enabled ? 1 : 0\n");
+        assert!(matches!(
+            instances(&resource, &Locals::new()),
+            Instances::Dynamic { .. }
+        ));
+        assert!(matches!(
+            instances(&body("count = 10000000000\n"), &Locals::new()),
+            Instances::Dynamic { .. }
+        ));
+        assert!(matches!(
+            instances(&body("name = \"x\"\n"), &Locals::new()),
+            Instances::Single
+        ));
+    }
+
+    #[test]
+    fn test_substitute_leaves_unknown_paths() {
+        let instance = Instance {
+            key: Expression::String("eu".to_string()),
+            value: Expression::String("eu".to_string()),
+        };
+        let expr = attribute(&body("x = each.value.missing\n"), "x");
+        assert_eq!(instance.substitute(&expr), expr);
+    }
+}
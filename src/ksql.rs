@@ -0,0 +1,109 @@
+use crate::error::ConnectUtilError;
+use crate::types::DataFormat;
+
+/// ksqlDB scaffolding over the change topics of a CDC source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamScaffold<'a> {
+    pub connector_name: &'a str,
+    /// `topic.prefix`; None leaves a placeholder
+    pub topic_prefix: Option<&'a str>,
+    /// Captured tables as `schema.table` (or `database.table`), one stream each
+    pub tables: &'a [String],
+    pub value_format: DataFormat,
+}
+
+/// Debezium-style sources write each table to `<topic.prefix>.<schema>.<table>`
+pub fn change_topic(prefix: &str, table: &str) -> String {
+    format!("{}.{}", prefix, table)
+}
+
+/// Stream name for a captured table, e.g. `public.orders` -> `public_orders`
+pub fn stream_name(table: &str) -> String {
+    table
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Renders a `CREATE STREAM` per table. Schema-backed formats let ksqlDB infer the
+/// columns; JSON gets a placeholder column list
+pub fn generate_streams(scaffold: &StreamScaffold) -> Result<String, ConnectUtilError> {
+    if scaffold.tables.is_empty() {
+        return Err(ConnectUtilError::Config(
+            "--with-ksql needs at least one table, e.g. public.orders".to_string(),
+        ));
+    }
+    if scaffold.value_format == DataFormat::Parquet {
+        return Err(ConnectUtilError::Config(
+            "ksqlDB can't read PARQUET topics; use AVRO, JSON, JSON_SR, or PROTOBUF".to_string(),
+        ));
+    }
+    let prefix = scaffold
+        .topic_prefix
+        .unwrap_or("<REPLACE_WITH_TOPIC_PREFIX>");
+    let format = scaffold.value_format.to_terraform_value();
+
+    let mut sql = format!(
+        "-- ksqlDB streams over the change topics of connector '{}'\n",
+        scaffold.connector_name
+    );
+    for table in scaffold.tables {
+        sql.push('\n');
+        sql.push_str(&format!(
+            "CREATE STREAM IF NOT EXISTS {}",
+            stream_name(table)
+        ));
+        if scaffold.value_format == DataFormat::Json {
+            sql.push_str(" (\n  -- JSON has no registered schema to infer columns from\n  <REPLACE_WITH_COLUMNS>\n)");
+        }
+        sql.push_str(&format!(
+            "\n  WITH (KAFKA_TOPIC = '{}', VALUE_FORMAT = '{}');\n",
+            change_topic(prefix, table),
+            format
+        ));
+    }
+    Ok(sql)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_streams() {
+        let tables = vec!["public.orders".to_string(), "public.Refunds".to_string()];
+        let mut scaffold = StreamScaffold {
+            connector_name: "orders-cdc",
+            topic_prefix: Some("shop"),
+            tables: &tables,
+            value_format: DataFormat::Avro,
+        };
+        assert_eq!(
+            generate_streams(&scaffold).unwrap(),
+            "-- ksqlDB streams over the change topics of connector 'orders-cdc'\n\
+             \n\
+             CREATE STREAM IF NOT EXISTS public_orders\n  \
+             WITH (KAFKA_TOPIC = 'shop.public.orders', VALUE_FORMAT = 'AVRO');\n\
+             \n\
+             CREATE STREAM IF NOT EXISTS public_refunds\n  \
+             WITH (KAFKA_TOPIC = 'shop.public.Refunds', VALUE_FORMAT = 'AVRO');\n"
+        );
+
+        scaffold.topic_prefix = None;
+        scaffold.value_format = DataFormat::Json;
+        let sql = generate_streams(&scaffold).unwrap();
+        assert!(sql.contains("'<REPLACE_WITH_TOPIC_PREFIX>.public.orders'"));
+        assert!(sql.contains("<REPLACE_WITH_COLUMNS>"));
+
+        scaffold.value_format = DataFormat::Parquet;
+        assert!(generate_streams(&scaffold).is_err());
+        scaffold.tables = &[];
+        assert!(generate_streams(&scaffold).is_err());
+    }
+}
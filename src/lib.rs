@@ -1,8 +1,66 @@
 pub mod app;
+pub mod audit;
+pub mod catalog;
+pub mod cloud;
+pub mod cluster;
+pub mod conflicts;
+pub mod connect;
 pub mod connectors;
+pub mod convert;
+pub mod coverage;
+pub mod debezium;
+pub mod definitions;
+pub mod docs;
+pub mod edit;
+pub mod enrich;
 pub mod error;
+pub mod expression;
+#[cfg(feature = "local-test")]
+pub mod harness;
+pub mod health;
+pub mod i18n;
+pub mod instances;
+pub mod ksql;
+pub mod lifecycle;
+pub mod lint;
+pub mod locals;
+pub mod merge;
+pub mod metadata;
+pub mod notify;
+pub mod order;
+pub mod parser;
+pub mod plan;
+pub mod policy;
+pub mod primary_key;
+pub mod profile;
+pub mod progress;
+pub mod provenance;
+pub mod recent;
+pub mod record_schema;
+pub mod remote;
+pub mod rename;
+pub mod reorganize;
+pub mod review;
+pub mod rotation;
+pub mod scaffold;
+pub mod scan;
+pub mod schema_registry;
+pub mod snapshot;
+pub mod sops;
+pub mod stamp;
+pub mod state;
+pub mod stats;
+pub mod stdio;
+pub mod storage;
+pub mod style;
+pub mod suppress;
+pub mod tables;
+pub mod template;
 pub mod terraform;
+pub mod timings;
 pub mod types;
+pub mod variables;
+pub mod where_used;
 
 pub use error::ConnectUtilError;
 
@@ -0,0 +1,164 @@
+use hcl::{Body, Expression};
+use std::collections::HashMap;
+
+/// Attributes Confluent Cloud manages on a connector, ignored unless
+/// `.connect-util.yaml` or `--ignore-changes` says otherwise
+pub const DEFAULT_IGNORE_CHANGES: &[&str] = &[
+    "config_nonsensitive[\"kafka.deployment.type\"]",
+    "config_nonsensitive[\"kafka.max.partition.validation.disable\"]",
+    "config_nonsensitive[\"kafka.max.partition.validation.enable\"]",
+    "config_nonsensitive[\"kafka.max.partition.validation\"]",
+];
+
+/// The resource's `lifecycle { ignore_changes = ... }` setting
+#[derive(Debug, Clone, PartialEq)]
+pub enum IgnoreChanges {
+    /// `ignore_changes = all`
+    All,
+    /// Normalized entries such as `config_nonsensitive["kafka.deployment.type"]`
+    Entries(Vec<String>),
+}
+
+impl IgnoreChanges {
+    pub fn covers(&self, entry: &str) -> bool {
+        match self {
+            IgnoreChanges::All => true,
+            IgnoreChanges::Entries(entries) => entries.contains(&normalize(entry)),
+        }
+    }
+}
+
+/// Removes whitespace so quoted legacy entries and bare references compare equal
+pub fn normalize(entry: &str) -> String {
+    entry.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+/// Reads `ignore_changes` from a resource body's literal `lifecycle` block; empty
+/// entries when the resource has no lifecycle block or doesn't set it
+pub fn ignore_changes(resource: &Body) -> IgnoreChanges {
+    let expression = resource
+        .blocks()
+        .filter(|block| block.identifier() == "lifecycle")
+        .flat_map(|block| block.body().attributes())
+        .find(|attribute| attribute.key() == "ignore_changes")
+        .map(|attribute| attribute.expr());
+    match expression {
+        Some(Expression::Variable(variable)) if variable.as_str() == "all" => IgnoreChanges::All,
+        Some(Expression::Array(items)) => IgnoreChanges::Entries(
+            items
+                .iter()
+                .map(|item| match item {
+                    Expression::String(entry) => normalize(entry),
+                    other => normalize(&other.to_string()),
+                })
+                .collect(),
+        ),
+        _ => IgnoreChanges::Entries(vec![]),
+    }
+}
+
+/// Warnings for `config_nonsensitive["..."]` entries naming keys that `config`
+/// doesn't set, so the entry silently ignores nothing. The defaults are skipped,
+/// since Confluent Cloud adds those keys itself
+pub fn unset_ignored_keys(
+    resource: &str,
+    ignored: &IgnoreChanges,
+    config: &HashMap<String, String>,
+) -> Vec<String> {
+    let IgnoreChanges::Entries(entries) = ignored else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter(|entry| {
+            !DEFAULT_IGNORE_CHANGES
+                .iter()
+                .any(|default| normalize(default) == **entry)
+        })
+        .filter_map(|entry| {
+            entry
+                .strip_prefix("config_nonsensitive[\"")?
+                .strip_suffix("\"]")
+        })
+        .filter(|key| !config.contains_key(*key))
+        .map(|key| {
+            format!(
+                "'{}' lifecycle.ignore_changes references config_nonsensitive[\"{}\"], which isn't set",
+                resource, key
+            )
+        })
+        .collect()
+}
+
+/// `ignore_changes` of the `confluent_connector` resource labelled `label` in a
+/// parsed file; `None` when the file doesn't declare it
+pub fn connector_ignore_changes(file: &Body, label: &str) -> Option<IgnoreChanges> {
+    file.blocks()
+        .find(|block| {
+            block.identifier() == "resource"
+                && block.labels().len() == 2
+                && block.labels()[0].as_str() == "confluent_connector"
+                && block.labels()[1].as_str() == label
+        })
+        .map(|block| ignore_changes(block.body()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resource(lifecycle: &str) -> Body {
+        let content = format!(
+            "resource \"confluent_connector\" \"orders\" {{\n{}\n}}\n",
+            lifecycle
+        );
+        let body: Body = hcl::from_str(&content).unwrap();
+        body.blocks().next().unwrap().body().clone()
+    }
+
+    #[test]
+    fn test_ignore_changes() {
+        let quoted = resource(
+            "lifecycle {\n  ignore_changes = [\"config_nonsensitive[\\\"kafka.deployment.type\\\"]\"]\n}",
+        );
+        let bare = resource(
+            "lifecycle {\n  ignore_changes = [config_nonsensitive[\"kafka.deployment.type\"], status]\n}",
+        );
+        for body in [&quoted, &bare] {
+            assert!(ignore_changes(body).covers(DEFAULT_IGNORE_CHANGES[0]));
+            assert!(!ignore_changes(body).covers(DEFAULT_IGNORE_CHANGES[1]));
+        }
+        assert!(ignore_changes(&bare).covers("status"));
+
+        let all = resource("lifecycle {\n  ignore_changes = all\n}");
+        assert_eq!(ignore_changes(&all), IgnoreChanges::All);
+        assert!(IgnoreChanges::All.covers(DEFAULT_IGNORE_CHANGES[1]));
+        assert_eq!(
+            ignore_changes(&resource("")),
+            IgnoreChanges::Entries(vec![])
+        );
+
+        let file: Body = hcl::from_str(
+            "resource \"confluent_connector\" \"orders\" {\n  lifecycle {\n    ignore_changes = all\n  }\n}\n",
+        )
+        .unwrap();
+        assert_eq!(
+            connector_ignore_changes(&file, "orders"),
+            Some(IgnoreChanges::All)
+        );
+        assert_eq!(connector_ignore_changes(&file, "payments"), None);
+    }
+
+    #[test]
+    fn test_unset_ignored_keys() {
+        let body = resource(
+            "lifecycle {\n  ignore_changes = [\n    config_nonsensitive[\"tasks.max\"],\n    config_nonsensitive[\"topic.prefix\"],\n    config_nonsensitive[\"kafka.deployment.type\"],\n    status,\n  ]\n}",
+        );
+        let config = HashMap::from([("tasks.max".to_string(), "1".to_string())]);
+        assert_eq!(
+            unset_ignored_keys("orders", &ignore_changes(&body), &config),
+            vec!["'orders' lifecycle.ignore_changes references config_nonsensitive[\"topic.prefix\"], which isn't set"]
+        );
+        assert!(unset_ignored_keys("orders", &IgnoreChanges::All, &config).is_empty());
+    }
+}
@@ -0,0 +1,444 @@
+use crate::scan::ScanRule;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// Prefix tflint expects on every rule name from a ruleset
+const RULESET: &str = "connect_util";
+
+/// The connector checks `validate --dir` and `scan` run, as lint rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum LintRule {
+    /// `connector.class` isn't in the catalog
+    UnknownConnector,
+    /// Missing required key, misplaced sensitive key, or invalid value
+    InvalidConfig,
+    /// Connector name or resource label breaks `--name-pattern`/`--label-pattern`
+    NamingConvention,
+    /// A rule from `.connect-util.yaml` is violated
+    Policy,
+    /// Duplicate names or labels, or overlapping topics within a module
+    Conflict,
+    UndeclaredVariable,
+    SensitiveVariableNotRouted,
+    UnusedVariable,
+    /// `lifecycle.ignore_changes` names a `config_nonsensitive` key the connector doesn't set
+    UnsetIgnoredKey,
+    /// A file given to `validate` has resources or data sources but no connectors
+    NoConnectors,
+    /// A secret scanner rule
+    Scan(ScanRule),
+}
+
+impl LintRule {
+    pub const ALL: [LintRule; 13] = [
+        LintRule::UnknownConnector,
+        LintRule::InvalidConfig,
+        LintRule::NamingConvention,
+        LintRule::Policy,
+        LintRule::Conflict,
+        LintRule::UndeclaredVariable,
+        LintRule::SensitiveVariableNotRouted,
+        LintRule::UnusedVariable,
+        LintRule::UnsetIgnoredKey,
+        LintRule::NoConnectors,
+        LintRule::Scan(ScanRule::HardcodedSecret),
+        LintRule::Scan(ScanRule::Placeholder),
+        LintRule::Scan(ScanRule::MisplacedSensitiveKey),
+    ];
+
+    /// Rule name in tflint's `<ruleset>_<rule>` form
+    pub fn name(&self) -> String {
+        let rule = match self {
+            LintRule::UnknownConnector => "unknown_connector".to_string(),
+            LintRule::InvalidConfig => "invalid_config".to_string(),
+            LintRule::NamingConvention => "naming_convention".to_string(),
+            LintRule::Policy => "policy".to_string(),
+            LintRule::Conflict => "conflict".to_string(),
+            LintRule::UndeclaredVariable => "undeclared_variable".to_string(),
+            LintRule::SensitiveVariableNotRouted => "sensitive_variable_not_routed".to_string(),
+            LintRule::UnusedVariable => "unused_variable".to_string(),
+            LintRule::UnsetIgnoredKey => "unset_ignored_key".to_string(),
+            LintRule::NoConnectors => "no_connectors".to_string(),
+            LintRule::Scan(rule) => rule.id().replace('-', "_"),
+        };
+        format!("{}_{}", RULESET, rule)
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            LintRule::UnknownConnector => "Connector class is not in the connector catalog",
+            LintRule::InvalidConfig => "Connector config fails the catalog's field checks",
+            LintRule::NamingConvention => "Connector name or resource label breaks naming rules",
+            LintRule::Policy => "Connector violates the repository policy file",
+            LintRule::Conflict => "Connectors in a module collide on names, labels, or topics",
+            LintRule::UndeclaredVariable => "Connector references a variable nobody declares",
+            LintRule::SensitiveVariableNotRouted => {
+                "Sensitive variable is used outside config_sensitive"
+            }
+            LintRule::UnusedVariable => "Declared variable is never referenced",
+            LintRule::UnsetIgnoredKey => "ignore_changes names a config key that isn't set",
+            LintRule::NoConnectors => "File has resources or data sources but no connectors",
+            LintRule::Scan(rule) => rule.description(),
+        }
+    }
+
+    /// tflint severity: `error` or `warning`
+    pub fn severity(&self) -> &'static str {
+        match self {
+            LintRule::UnusedVariable | LintRule::UnsetIgnoredKey | LintRule::NoConnectors => {
+                "warning"
+            }
+            LintRule::Scan(rule) => rule.level(),
+            _ => "error",
+        }
+    }
+
+    /// Looks a rule up by name, with or without the `connect_util_` prefix; dashes
+    /// and underscores are interchangeable, e.g. `hardcoded-secret`
+    pub fn from_id(id: &str) -> Option<LintRule> {
+        let id = id.trim().to_lowercase().replace('-', "_");
+        let prefix = format!("{}_", RULESET);
+        let id = id.strip_prefix(&prefix).unwrap_or(&id);
+        LintRule::ALL
+            .into_iter()
+            .find(|rule| rule.name() == format!("{}{}", prefix, id))
+    }
+}
+
+/// Severity the policy file's `severities` can give a rule; `off` drops its findings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Off,
+}
+
+impl Severity {
+    /// tflint severity, or `None` for `off`
+    pub fn level(&self) -> Option<&'static str> {
+        match self {
+            Severity::Error => Some("error"),
+            Severity::Warning => Some("warning"),
+            Severity::Off => None,
+        }
+    }
+}
+
+/// A single lint finding anchored to a file and line
+#[derive(Debug, Clone, Serialize)]
+pub struct LintIssue {
+    pub rule: LintRule,
+    /// The rule's severity, or the policy file's override for it
+    pub severity: &'static str,
+    pub file: PathBuf,
+    pub line: usize,
+    /// Label of the connector block the issue belongs to; `None` for file- or module-level issues
+    pub connector: Option<String>,
+    pub message: String,
+}
+
+/// First line opening the connector resource or module block labelled `label`,
+/// or the data source at a `data.confluent_connector.<label>` address, falling
+/// back to line 1; instance keys such as `orders["a"]` are ignored
+pub fn block_line(content: &str, label: &str) -> usize {
+    let label = label.split('[').next().unwrap_or(label);
+    let openings = match label.strip_prefix("data.confluent_connector.") {
+        Some(label) => vec![format!("data \"confluent_connector\" \"{}\"", label)],
+        None => vec![
+            format!("resource \"confluent_connector\" \"{}\"", label),
+            format!("module \"{}\"", label),
+        ],
+    };
+    content
+        .lines()
+        .position(|line| {
+            let line = line.trim_start();
+            openings.iter().any(|opening| line.starts_with(opening))
+        })
+        .map(|index| index + 1)
+        .unwrap_or(1)
+}
+
+/// Renders issues in the shape of `tflint --format json`, so the two reports can be merged
+pub fn to_tflint_json(issues: &[LintIssue]) -> serde_json::Value {
+    let issues: Vec<serde_json::Value> = issues
+        .iter()
+        .map(|issue| {
+            let position = json!({ "line": issue.line, "column": 1 });
+            json!({
+                "rule": {
+                    "name": issue.rule.name(),
+                    "severity": issue.severity,
+                    "link": "",
+                },
+                "message": issue.message,
+                "range": {
+                    "filename": issue.file.display().to_string(),
+                    "start": position,
+                    "end": position,
+                },
+                "callers": [],
+            })
+        })
+        .collect();
+    json!({ "issues": issues, "errors": [] })
+}
+
+pub(crate) fn base_label(label: &str) -> &str {
+    label.split('[').next().unwrap_or(label)
+}
+
+/// Keeps messages on one table row
+fn table_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Renders a PR-comment-ready summary: a table of connectors with their error and
+/// warning counts, then a collapsed findings table for each one that has findings.
+/// `connectors` lists every `(file, label)` checked so clean connectors are shown too
+pub fn to_markdown(title: &str, issues: &[LintIssue], connectors: &[(PathBuf, String)]) -> String {
+    let mut groups: Vec<(PathBuf, Option<String>)> = Vec::new();
+    let keys = connectors
+        .iter()
+        .map(|(file, label)| (file.clone(), Some(base_label(label).to_string())))
+        .chain(issues.iter().map(|issue| {
+            (
+                issue.file.clone(),
+                issue
+                    .connector
+                    .as_deref()
+                    .map(|l| base_label(l).to_string()),
+            )
+        }));
+    for key in keys {
+        if !groups.contains(&key) {
+            groups.push(key);
+        }
+    }
+    let in_group = |issue: &&LintIssue, (file, label): &(PathBuf, Option<String>)| {
+        issue.file == *file && issue.connector.as_deref().map(base_label) == label.as_deref()
+    };
+
+    let errors = issues.iter().filter(|i| i.severity == "error").count();
+    let warnings = issues.len() - errors;
+    let connector_count = groups.iter().filter(|(_, label)| label.is_some()).count();
+
+    let mut out = String::new();
+    let _ = writeln!(out, "## {}\n", title);
+    let _ = match (errors, warnings) {
+        (0, 0) => writeln!(
+            out,
+            "✅ No findings across {} connector(s)",
+            connector_count
+        ),
+        (0, _) => writeln!(
+            out,
+            "⚠️ **{} warning(s)** across {} connector(s)",
+            warnings, connector_count
+        ),
+        _ => writeln!(
+            out,
+            "❌ **{} error(s)**, {} warning(s) across {} connector(s)",
+            errors, warnings, connector_count
+        ),
+    };
+    if groups.is_empty() {
+        return out;
+    }
+
+    let _ = writeln!(out, "\n| | Connector | File | Errors | Warnings |");
+    let _ = writeln!(out, "|---|---|---|---:|---:|");
+    for group in &groups {
+        let found: Vec<&LintIssue> = issues.iter().filter(|i| in_group(i, group)).collect();
+        let group_errors = found.iter().filter(|i| i.severity == "error").count();
+        let status = match (group_errors, found.len()) {
+            (0, 0) => "✅",
+            (0, _) => "⚠️",
+            _ => "❌",
+        };
+        let label = match &group.1 {
+            Some(label) => format!("`{}`", label),
+            None => "_file-level_".to_string(),
+        };
+        let _ = writeln!(
+            out,
+            "| {} | {} | `{}` | {} | {} |",
+            status,
+            label,
+            group.0.display(),
+            group_errors,
+            found.len() - group_errors
+        );
+    }
+
+    for group in &groups {
+        let found: Vec<&LintIssue> = issues.iter().filter(|i| in_group(i, group)).collect();
+        if found.is_empty() {
+            continue;
+        }
+        let subject = match &group.1 {
+            Some(label) => format!("<code>{}</code>", label),
+            None => "File-level findings".to_string(),
+        };
+        let _ = writeln!(
+            out,
+            "\n<details>\n<summary>{} in <code>{}</code>: {} finding(s)</summary>\n",
+            subject,
+            group.0.display(),
+            found.len()
+        );
+        let _ = writeln!(out, "| Severity | Rule | Line | Message |");
+        let _ = writeln!(out, "|---|---|---:|---|");
+        for issue in found {
+            let _ = writeln!(
+                out,
+                "| {} | `{}` | {} | {} |",
+                issue.severity,
+                issue.rule.name(),
+                issue.line,
+                table_cell(&issue.message)
+            );
+        }
+        let _ = writeln!(out, "\n</details>");
+    }
+    out
+}
+
+/// Every rule with its default severity, for wiring into an existing tflint setup
+pub fn ruleset_json() -> serde_json::Value {
+    let rules: Vec<serde_json::Value> = LintRule::ALL
+        .iter()
+        .map(|rule| {
+            json!({
+                "name": rule.name(),
+                "enabled": true,
+                "severity": rule.severity(),
+                "description": rule.description(),
+            })
+        })
+        .collect();
+    json!({
+        "name": RULESET,
+        "version": env!("CARGO_PKG_VERSION"),
+        "rules": rules,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_names_and_severities() {
+        assert_eq!(
+            LintRule::InvalidConfig.name(),
+            "connect_util_invalid_config"
+        );
+        assert_eq!(
+            LintRule::Scan(ScanRule::MisplacedSensitiveKey).name(),
+            "connect_util_misplaced_sensitive_key"
+        );
+        assert_eq!(LintRule::UnusedVariable.severity(), "warning");
+        assert_eq!(LintRule::Scan(ScanRule::Placeholder).severity(), "warning");
+        assert_eq!(LintRule::Conflict.severity(), "error");
+
+        let names: std::collections::HashSet<String> =
+            LintRule::ALL.iter().map(|r| r.name()).collect();
+        assert_eq!(names.len(), LintRule::ALL.len());
+    }
+
+    #[test]
+    fn test_block_line() {
+        let content = "variable \"x\" {}\n\nresource \"confluent_connector\" \"orders\" {\n}\n  module \"sink\" {\n}\ndata \"confluent_connector\" \"orders\" {}\n";
+        assert_eq!(block_line(content, "orders"), 3);
+        assert_eq!(block_line(content, "data.confluent_connector.orders"), 7);
+        assert_eq!(block_line(content, "orders[\"eu\"]"), 3);
+        assert_eq!(block_line(content, "sink"), 5);
+        assert_eq!(block_line(content, "missing"), 1);
+    }
+
+    #[test]
+    fn test_to_tflint_json() {
+        let issues = vec![LintIssue {
+            rule: LintRule::UndeclaredVariable,
+            severity: LintRule::UndeclaredVariable.severity(),
+            file: PathBuf::from("infra/main.tf"),
+            line: 7,
+            connector: Some("orders".to_string()),
+            message: "var.x is never declared".to_string(),
+        }];
+        let report = to_tflint_json(&issues);
+        let issue = &report["issues"][0];
+        assert_eq!(issue["rule"]["name"], "connect_util_undeclared_variable");
+        assert_eq!(issue["rule"]["severity"], "error");
+        assert_eq!(issue["range"]["filename"], "infra/main.tf");
+        assert_eq!(issue["range"]["start"]["line"], 7);
+        assert_eq!(report["errors"], json!([]));
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let issues = vec![
+            LintIssue {
+                rule: LintRule::InvalidConfig,
+                severity: LintRule::InvalidConfig.severity(),
+                file: PathBuf::from("main.tf"),
+                line: 3,
+                connector: Some("orders[\"eu\"]".to_string()),
+                message: "Missing required configuration: a|b".to_string(),
+            },
+            LintIssue {
+                rule: LintRule::UnusedVariable,
+                severity: LintRule::UnusedVariable.severity(),
+                file: PathBuf::from("variables.tf"),
+                line: 1,
+                connector: None,
+                message: "var.legacy is never used".to_string(),
+            },
+        ];
+        let connectors = vec![
+            (PathBuf::from("main.tf"), "orders[\"eu\"]".to_string()),
+            (PathBuf::from("main.tf"), "orders[\"us\"]".to_string()),
+            (PathBuf::from("sinks.tf"), "s3".to_string()),
+        ];
+        let markdown = to_markdown("Connector validation", &issues, &connectors);
+        assert!(markdown.starts_with("## Connector validation\n"));
+        assert!(markdown.contains("❌ **1 error(s)**, 1 warning(s) across 2 connector(s)"));
+        assert!(markdown.contains("| ❌ | `orders` | `main.tf` | 1 | 0 |"));
+        assert!(markdown.contains("| ✅ | `s3` | `sinks.tf` | 0 | 0 |"));
+        assert!(markdown.contains("| ⚠️ | _file-level_ | `variables.tf` | 0 | 1 |"));
+        assert!(markdown.contains(
+            "<summary><code>orders</code> in <code>main.tf</code>: 1 finding(s)</summary>"
+        ));
+        assert!(markdown.contains(
+            "| error | `connect_util_invalid_config` | 3 | Missing required configuration: a\\|b |"
+        ));
+        assert!(!markdown.contains("<code>s3</code>"));
+        assert!(markdown.contains(
+            "<summary>File-level findings in <code>variables.tf</code>: 1 finding(s)</summary>"
+        ));
+        assert_eq!(markdown.matches("<details>").count(), 2);
+    }
+
+    #[test]
+    fn test_to_markdown_clean() {
+        let markdown = to_markdown("Checks", &[], &[]);
+        assert_eq!(
+            markdown,
+            "## Checks\n\n✅ No findings across 0 connector(s)\n"
+        );
+    }
+
+    #[test]
+    fn test_ruleset_json() {
+        let ruleset = ruleset_json();
+        assert_eq!(ruleset["name"], "connect_util");
+        assert_eq!(
+            ruleset["rules"].as_array().unwrap().len(),
+            LintRule::ALL.len()
+        );
+    }
+}
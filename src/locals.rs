@@ -0,0 +1,224 @@
+use crate::parser::parse_body;
+use hcl::{Body, Expression, Object, ObjectKey, TraversalOperator};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// `locals` entries by name
+pub type Locals = HashMap<String, Expression>;
+
+/// Deepest chain of locals referring to locals that gets followed
+const MAX_DEPTH: usize = 16;
+
+/// Collects every attribute of every `locals` block in a body
+pub fn collect_locals(body: &Body) -> Locals {
+    body.blocks()
+        .filter(|block| block.identifier() == "locals")
+        .flat_map(|block| block.body().attributes())
+        .map(|attribute| (attribute.key().to_string(), attribute.expr().clone()))
+        .collect()
+}
+
+/// Collects locals from every `.tf` file in a module directory; files that fail to
+/// parse are skipped since the file being validated reports its own parse errors
+pub fn module_locals(dir: &Path) -> Locals {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Locals::new();
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|e| e == "tf"))
+        .collect();
+    paths.sort();
+
+    let mut locals = Locals::new();
+    for path in paths {
+        let body = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| parse_body(&content).ok());
+        if let Some(body) = body {
+            locals.extend(collect_locals(&body));
+        }
+    }
+    locals
+}
+
+pub(crate) fn object_get<'a>(
+    object: &'a Object<ObjectKey, Expression>,
+    key: &str,
+) -> Option<&'a Expression> {
+    object
+        .iter()
+        .find(|(k, _)| k.to_string() == key)
+        .map(|(_, value)| value)
+}
+
+/// Substitutes `local.*` references and folds `merge()` over objects so callers see
+/// the effective value. Anything that can't be resolved statically is returned as-is
+pub fn resolve(expr: &Expression, locals: &Locals) -> Expression {
+    resolve_at(expr, locals, 0)
+}
+
+fn resolve_at(expr: &Expression, locals: &Locals, depth: usize) -> Expression {
+    if depth > MAX_DEPTH {
+        return expr.clone();
+    }
+    match expr {
+        Expression::Traversal(traversal) => {
+            let Expression::Variable(root) = &traversal.expr else {
+                return expr.clone();
+            };
+            let mut operators = traversal.operators.iter();
+            let name = match (root.as_str(), operators.next()) {
+                ("local", Some(TraversalOperator::GetAttr(name))) => name.as_str(),
+                _ => return expr.clone(),
+            };
+            let Some(local) = locals.get(name) else {
+                return expr.clone();
+            };
+            let mut value = resolve_at(local, locals, depth + 1);
+            for operator in operators {
+                let next = match (operator, &value) {
+                    (TraversalOperator::GetAttr(key), Expression::Object(object)) => {
+                        object_get(object, key.as_str())
+                    }
+                    (
+                        TraversalOperator::Index(Expression::String(key)),
+                        Expression::Object(object),
+                    ) => object_get(object, key),
+                    _ => None,
+                };
+                match next {
+                    Some(next) => value = resolve_at(next, locals, depth + 1),
+                    None => return expr.clone(),
+                }
+            }
+            value
+        }
+        Expression::FuncCall(func) if func.name.as_str() == "merge" => {
+            let mut merged = Object::new();
+            for arg in &func.args {
+                match resolve_at(arg, locals, depth + 1) {
+                    Expression::Object(object) => {
+                        for (key, value) in object {
+                            merged.insert(key, value);
+                        }
+                    }
+                    _ => return expr.clone(),
+                }
+            }
+            Expression::Object(merged)
+        }
+        Expression::Object(object) => Expression::Object(
+            object
+                .iter()
+                .map(|(key, value)| (key.clone(), resolve_at(value, locals, depth + 1)))
+                .collect(),
+        ),
+        Expression::Parenthesis(inner) => resolve_at(inner, locals, depth + 1),
+        _ => expr.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> Body {
+        hcl::from_str(content).unwrap()
+    }
+
+    fn attribute(body: &Body, key: &str) -> Expression {
+        body.attributes()
+            .find(|a| a.key() == key)
+            .unwrap()
+            .expr()
+            .clone()
+    }
+
+    const LOCALS: &str = r#"
+locals {
+  topic = "orders"
+  common_config = {
+    "kafka.auth.mode" = "SERVICE_ACCOUNT"
+    "tasks.max"       = "1"
+  }
+  settings = {
+    format = local.format
+  }
+  format = "AVRO"
+  loop   = local.loop
+}
+"#;
+
+    #[test]
+    fn test_collect_locals() {
+        let locals = collect_locals(&parse(LOCALS));
+        assert_eq!(locals.len(), 5);
+        assert_eq!(locals["topic"], Expression::String("orders".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_merge_with_locals() {
+        let locals = collect_locals(&parse(LOCALS));
+        let body = parse(
+            r#"
+config = merge(local.common_config, {
+  "tasks.max"   = "4"
+  "kafka.topic" = local.topic
+})
+"#,
+        );
+        let Expression::Object(object) = resolve(&attribute(&body, "config"), &locals) else {
+            panic!("expected merge() to resolve to an object");
+        };
+        let get = |key: &str| object_get(&object, key).cloned();
+        assert_eq!(
+            get("kafka.auth.mode"),
+            Some(Expression::String("SERVICE_ACCOUNT".to_string()))
+        );
+        assert_eq!(get("tasks.max"), Some(Expression::String("4".to_string())));
+        assert_eq!(
+            get("kafka.topic"),
+            Some(Expression::String("orders".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_resolve_nested_attribute() {
+        let locals = collect_locals(&parse(LOCALS));
+        let body = parse("a = local.settings.format\nb = local.settings[\"format\"]\n");
+        assert_eq!(
+            resolve(&attribute(&body, "a"), &locals),
+            Expression::String("AVRO".to_string())
+        );
+        assert_eq!(
+            resolve(&attribute(&body, "b"), &locals),
+            Expression::String("AVRO".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_leaves_unresolvable_expressions() {
+        let locals = collect_locals(&parse(LOCALS));
+        let body = parse(
+            "a = local.missing\nb = merge(var.extra, local.common_config)\nc = local.loop\nd = var.x\n",
+        );
+        for key in ["a", "b", "d"] {
+            let expr = attribute(&body, key);
+            assert_eq!(resolve(&expr, &locals), expr);
+        }
+        // Self-referencing locals stop at the depth limit instead of recursing forever
+        let _ = resolve(&attribute(&body, "c"), &locals);
+    }
+
+    #[test]
+    fn test_module_locals() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("locals.tf"), LOCALS).unwrap();
+        std::fs::write(dir.path().join("broken.tf"), "locals {").unwrap();
+        std::fs::write(dir.path().join("prod.tfvars"), "topic = \"x\"\n").unwrap();
+        let locals = module_locals(dir.path());
+        assert_eq!(locals.len(), 5);
+        assert!(module_locals(Path::new("/nonexistent")).is_empty());
+    }
+}
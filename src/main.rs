@@ -1,129 +1,3403 @@
-use clap::{Parser, Subcommand};
-use connect_util::{app::ConnectUtilApp, error::ConnectUtilError, types::ConnectorOptions};
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use connect_util::{
+    app::ConnectUtilApp,
+    cloud::{CloudClient, OrgApi},
+    connect::ConnectRestClient,
+    definitions, docs,
+    error::ConnectUtilError,
+    i18n,
+    metadata::ConnectorMetadata,
+    notify::{NotifyFormat, WebhookNotifier},
+    policy::NamingRules,
+    profile::ValidationProfile,
+    progress, record_schema,
+    remote::{self, SchemaCache},
+    review::TerminalReviewer,
+    say, stdio, style,
+    timings::{PhaseRecorder, TimingReport},
+    types::{
+        CompanionResources, ConnectorOptions, DebeziumOptions, SchemaRegistryOptions, SopsOptions,
+        StorageLayoutOptions,
+    },
+    where_used::UsageQuery,
+};
+use std::process::ExitCode;
 use tracing::info;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
+
+#[derive(Parser)]
+#[command(name = "connect-util")]
+#[command(about = "Interactive Kafka Connect Connector Terraform Generator")]
+#[command(version = "0.1.0")]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+
+    /// Regex connector names must match (e.g. '^(src|sink)-[a-z0-9-]+$')
+    #[arg(long, global = true)]
+    name_pattern: Option<String>,
+
+    /// Regex Terraform resource labels must match (e.g. '^[a-z][a-z0-9_]*$')
+    #[arg(long, global = true)]
+    label_pattern: Option<String>,
+
+    /// Replace emoji in output with ASCII tags such as [ok] and [error]
+    #[arg(long, global = true)]
+    no_emoji: bool,
+
+    /// Disable colored output (also honored via the NO_COLOR environment variable)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Don't draw progress bars (they are also hidden when stdout isn't a terminal)
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// JSON connector definition file, or a directory of them, to add to the built-in catalog
+    #[arg(long, global = true, value_name = "PATH")]
+    definitions: Option<String>,
+
+    /// Print how long catalog loading, parsing, validation, and generation took, on stderr
+    #[arg(long, global = true)]
+    timings: bool,
+
+    /// Write the phase timings as JSON to this file
+    #[arg(long, global = true, value_name = "FILE")]
+    profile_output: Option<String>,
+
+    /// File that `-` input stands in for: findings name it, policy overrides match it,
+    /// and validate checks it with the other files in its directory
+    #[arg(long, global = true, value_name = "PATH")]
+    stdin_filename: Option<String>,
+
+    /// Language for connector and field descriptions: en or es (also read from CONNECT_UTIL_LANG)
+    #[arg(long, global = true, value_name = "LANG")]
+    lang: Option<String>,
+}
+
+#[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)] // Parsed once per run, so boxing Generate's flags buys nothing
+enum Commands {
+    /// Generate Terraform configuration interactively
+    Generate {
+        /// Connector name (optional - will prompt if not provided)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Output file path, or - for stdout (optional - will prompt if not provided)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Connector plugin name (skips prompts when combined with --name)
+        #[arg(long)]
+        connector: Option<String>,
+
+        /// Print the rendered output and write plan without touching the filesystem
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Overwrite the output file if it already exists
+        #[arg(long, conflicts_with = "append")]
+        force: bool,
+
+        /// Add the generated resource to an existing output file
+        #[arg(long)]
+        append: bool,
+
+        /// Regenerate the connector into this existing file, keeping values already filled in
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["output", "append", "force", "environments"])]
+        merge_into: Option<String>,
+
+        /// Output layout: a single file, or a main.tf/variables.tf/outputs.tf project
+        #[arg(long, default_value = "single", value_parser = ["single", "project"])]
+        layout: String,
+
+        /// Directory for the project layout
+        #[arg(long, required_if_eq("layout", "project"))]
+        output_dir: Option<String>,
+
+        /// Line endings for written files; defaults to an existing file's, else the platform's
+        #[arg(long, value_parser = ["lf", "crlf"])]
+        newline: Option<String>,
+
+        /// Also emit connector_id/connector_status output blocks in single-file mode
+        #[arg(long)]
+        with_outputs: bool,
+
+        /// Generate confluent_kafka_topic resources for the connector's topics
+        #[arg(long)]
+        with_topics: bool,
+
+        /// Generate a confluent_service_account for the connector
+        #[arg(long)]
+        with_service_account: bool,
+
+        /// Generate a confluent_api_key (implies --with-service-account)
+        #[arg(long)]
+        with_api_key: bool,
+
+        /// For a CDC source, the schema.table patterns to capture as table.include.list
+        #[arg(long, value_name = "PATTERNS", value_delimiter = ',')]
+        tables: Vec<String>,
+
+        /// For a CDC source, the schema.table patterns to skip as table.exclude.list
+        #[arg(long, value_name = "PATTERNS", value_delimiter = ',')]
+        exclude_tables: Vec<String>,
+
+        /// For a Debezium CDC source, emit heartbeats every MS milliseconds (heartbeat.interval.ms)
+        #[arg(long, value_name = "MS")]
+        heartbeat_interval: Option<u64>,
+
+        /// For a Debezium CDC source, a schema.table the heartbeat.action.query upserts into
+        #[arg(long, value_name = "TABLE")]
+        heartbeat_table: Option<String>,
+
+        /// For a Debezium CDC source, the signal.data.collection for incremental snapshots
+        #[arg(long, value_name = "TABLE")]
+        signal_table: Option<String>,
+
+        /// Rows per chunk of an incremental snapshot (incremental.snapshot.chunk.size)
+        #[arg(long, value_name = "ROWS", requires = "signal_table")]
+        snapshot_chunk_size: Option<u32>,
+
+        /// For an object-store sink, how objects are partitioned
+        #[arg(long, value_parser = ["default", "field", "time", "daily", "hourly"])]
+        partitioner: Option<String>,
+
+        /// For an object-store sink, the record fields to partition by (implies --partitioner field)
+        #[arg(long, value_name = "FIELDS", value_delimiter = ',')]
+        partition_fields: Vec<String>,
+
+        /// For an object-store sink, the Joda-Time path.format, e.g. 'year'=YYYY/'month'=MM
+        #[arg(long, value_name = "PATTERN")]
+        path_format: Option<String>,
+
+        /// For a time-partitioned sink, the locale of the path (default: en-US)
+        #[arg(long)]
+        locale: Option<String>,
+
+        /// For a time-partitioned sink, the timezone of the path (default: UTC)
+        #[arg(long)]
+        timezone: Option<String>,
+
+        /// For a JDBC sink, an .avsc or .proto schema to suggest pk.fields and table.name.format from
+        #[arg(long, value_name = "FILE", conflicts_with = "schema_subject")]
+        schema_file: Option<String>,
+
+        /// For a JDBC sink, a Schema Registry subject whose latest schema suggests pk.fields and table.name.format
+        #[arg(long, value_name = "SUBJECT")]
+        schema_subject: Option<String>,
+
+        /// Schema Registry URL for --schema-subject (default: SCHEMA_REGISTRY_URL)
+        #[arg(long, value_name = "URL", requires = "schema_subject")]
+        schema_registry_url: Option<String>,
+
+        /// For a CDC source, also write ksqlDB streams over these tables' change topics (schema.table,...)
+        #[arg(
+            long = "with-ksql",
+            value_name = "TABLES",
+            value_delimiter = ',',
+            conflicts_with_all = ["append", "merge_into"]
+        )]
+        ksql_tables: Vec<String>,
+
+        /// Reference secrets as variables and write their values to a SOPS-encrypted secrets.auto.tfvars.enc
+        #[arg(long, conflicts_with_all = ["append", "merge_into"])]
+        sops: bool,
+
+        /// age recipients to encrypt the secrets file for (default: .sops.yaml creation rules)
+        #[arg(
+            long,
+            value_name = "RECIPIENTS",
+            value_delimiter = ',',
+            requires = "sops"
+        )]
+        sops_age: Vec<String>,
+
+        /// AWS KMS key ARNs to encrypt the secrets file with (default: .sops.yaml creation rules)
+        #[arg(long, value_name = "ARNS", value_delimiter = ',', requires = "sops")]
+        sops_kms: Vec<String>,
+
+        /// Read secrets from Azure Key Vault or GCP Secret Manager data sources instead of placeholders
+        #[arg(
+            long,
+            default_value = "inline",
+            value_parser = ["inline", "azure-key-vault", "gcp-secret-manager"],
+            conflicts_with_all = ["sops", "append", "merge_into"]
+        )]
+        secrets_backend: String,
+
+        /// lifecycle.ignore_changes entries, replacing the config file's list or the defaults
+        #[arg(long, value_name = "ENTRIES", value_delimiter = ',')]
+        ignore_changes: Vec<String>,
+
+        /// Value for a template token in .connect-util.yaml (key=value), overriding `tokens`
+        #[arg(long = "token", value_name = "KEY=VALUE")]
+        tokens: Vec<String>,
+
+        /// Emit an import block that adopts the existing connector with this name
+        #[arg(long, value_name = "CONNECTOR_NAME", conflicts_with = "environments")]
+        with_import_block: Option<String>,
+
+        /// Environment profile from .connect-util.yaml whose IDs replace the generic variables
+        #[arg(long, value_name = "NAME", conflicts_with = "environments")]
+        environment: Option<String>,
+
+        /// Generate the connector for several environment profiles at once, e.g. dev,prod
+        #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+        environments: Vec<String>,
+
+        /// Comma-separated topics, or the topic prefix for CDC sources (prompted for when generating interactively)
+        #[arg(long, value_delimiter = ',')]
+        topics: Vec<String>,
+
+        /// input.data.format for sink connectors (prompted for when generating interactively)
+        #[arg(long, value_parser = ["avro", "json", "json_sr", "protobuf", "parquet"], ignore_case = true)]
+        input_format: Option<String>,
+
+        /// output.data.format (prompted for when generating interactively)
+        #[arg(long, value_parser = ["avro", "json", "json_sr", "protobuf", "parquet"], ignore_case = true)]
+        output_format: Option<String>,
+
+        /// Set schema.context.name so subjects register under this Schema Registry context
+        #[arg(long, value_name = "NAME")]
+        schema_context: Option<String>,
+
+        /// Enable client-side field level encryption (csfle.*) with Schema Registry credentials
+        #[arg(long)]
+        csfle: bool,
+
+        /// Where the connector runs: Confluent Cloud, or a self-managed or MSK Connect cluster
+        /// (writes the REST payload as JSON)
+        #[arg(long, default_value = "cloud", value_parser = ["cloud", "self-managed"], conflicts_with = "provider")]
+        target: String,
+
+        /// Terraform provider to write the connector for, converting its config for providers
+        /// other than Confluent's
+        #[arg(long, value_parser = ["confluent", "kafka-connect", "aiven", "instaclustr"])]
+        provider: Option<String>,
+
+        /// Team or person that owns the connector, recorded as a comment on its resource
+        #[arg(long)]
+        owner: Option<String>,
+
+        /// Where to page about the connector, e.g. a Slack channel
+        #[arg(long, value_name = "CHANNEL")]
+        on_call: Option<String>,
+
+        /// When the connector may be paused for maintenance, e.g. "Sun 02:00-04:00 UTC"
+        #[arg(long, value_name = "WINDOW")]
+        maintenance_window: Option<String>,
+
+        /// Multi-environment output: a directory per environment, or one for_each resource
+        #[arg(long, default_value = "files", value_parser = ["files", "for-each"], requires = "environments")]
+        environment_layout: String,
+
+        /// Kafka cluster type, setting kafka.deployment.type and partition validation
+        /// (default: the environment profile's cluster_type, else dedicated)
+        #[arg(long, value_parser = ["basic", "standard", "dedicated"])]
+        cluster_type: Option<String>,
+
+        /// Print each generated config value with where it came from (flag, prompt, policy, ...)
+        #[arg(long)]
+        verbose: bool,
+
+        /// Write a JSON report of the generated config values and their sources to this file
+        #[arg(long, value_name = "FILE", conflicts_with = "environments")]
+        report: Option<String>,
+    },
+
+    /// Validate a connector configuration
+    Validate {
+        /// File or directory to validate, or - to read standard input
+        #[arg(conflicts_with_all = ["config_file", "dir"])]
+        path: Option<String>,
+
+        /// Connector configuration file, or - to read standard input
+        #[arg(short, long, required_unless_present_any = ["dir", "path"], conflicts_with = "dir")]
+        config_file: Option<String>,
+
+        /// Validate every connector file in a directory, including cross-file variable references
+        #[arg(short, long)]
+        dir: Option<String>,
+
+        /// Output format; markdown prints a PR-comment-ready summary of findings per connector,
+        /// json the findings with run statistics
+        #[arg(long, default_value = "text", value_parser = ["text", "markdown", "json"])]
+        format: String,
+
+        /// Post a pass/fail summary with the top findings to this webhook URL
+        #[arg(long, value_name = "URL")]
+        notify_webhook: Option<String>,
+
+        /// Webhook payload; defaults to slack for hooks.slack.com URLs and json otherwise
+        #[arg(long, requires = "notify_webhook", value_parser = ["json", "slack"])]
+        notify_format: Option<String>,
+
+        /// Also check each connector against its plugin's config definitions in Confluent Cloud
+        #[arg(long, requires_all = ["environment", "cluster"])]
+        remote: bool,
+
+        /// Environment ID (env-...) for --remote
+        #[arg(long, requires = "remote")]
+        environment: Option<String>,
+
+        /// Kafka cluster ID (lkc-...) for --remote
+        #[arg(long, requires = "remote")]
+        cluster: Option<String>,
+
+        /// Plugin schema requests --remote keeps in flight at once
+        #[arg(long, default_value_t = 4, requires = "remote", value_parser = clap::value_parser!(u16).range(1..))]
+        concurrency: u16,
+
+        /// Save the local and live configs, redacted, of each connector failing --remote here
+        #[arg(long, value_name = "DIR", requires = "remote")]
+        debug_dir: Option<String>,
+
+        /// Also run each connector, converted for a self-managed cluster, through this
+        /// Connect worker's plugin validation
+        #[arg(long, value_name = "URL")]
+        connect_url: Option<String>,
+
+        /// Step through the findings one at a time to fix, suppress, open, or skip each
+        #[arg(long, conflicts_with_all = ["notify_webhook", "remote", "connect_url"])]
+        interactive: bool,
+
+        /// Rule bundle to validate with, failing on any error finding: scaffold allows
+        /// placeholders, pre-deploy forbids them and requires --remote or --connect-url
+        #[arg(long, value_parser = ["scaffold", "pre-merge", "pre-deploy"])]
+        profile: Option<String>,
+
+        /// Warn about files generated from an older definition of their connector than
+        /// the current catalog's
+        #[arg(long, conflicts_with = "interactive")]
+        check_stale: bool,
+    },
+
+    /// List available connector plugins
+    ListPlugins {
+        /// Filter by connector type (source, sink)
+        #[arg(short, long)]
+        r#type: Option<String>,
+    },
+
+    /// Describe a connector plugin and its configuration fields
+    Describe {
+        /// Connector name (as shown by list-plugins)
+        connector: String,
+
+        /// Output format; json-schema prints a JSON Schema of the connector's config map
+        #[arg(long, default_value = "text", value_parser = ["text", "json-schema"])]
+        format: String,
+    },
+
+    /// Explain one of a connector's config keys: description, type, default, valid values, and related keys
+    Explain {
+        /// Connector name (as shown by list-plugins)
+        connector: String,
+
+        /// Config key, e.g. snapshot.mode
+        key: String,
+    },
+
+    /// Convert a self-managed connector definition to Terraform
+    #[command(group(clap::ArgGroup::new("input").required(true).args(["from_properties", "from_json"])))]
+    Convert {
+        /// Connect worker connector .properties file, or - to read standard input
+        #[arg(long)]
+        from_properties: Option<String>,
+
+        /// Connect REST payload, Confluent Cloud UI export, or config map JSON, or - for stdin
+        #[arg(long)]
+        from_json: Option<String>,
+
+        /// Output file path (defaults to stdout; - prints only the resource, with messages on stderr)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Print the rendered output and write plan without touching the filesystem
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Overwrite the output file if it already exists
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Smoke test connectors against local Kafka + Connect containers (requires Docker)
+    #[cfg(feature = "local-test")]
+    Test {
+        /// Connector configuration file
+        #[arg(short, long)]
+        config_file: String,
+
+        /// Override a config value for the local run (key=value), e.g. for secrets
+        #[arg(long = "set", value_name = "KEY=VALUE")]
+        overrides: Vec<String>,
+
+        /// Confluent Hub plugin to install into the Connect container
+        #[arg(long = "plugin")]
+        plugins: Vec<String>,
+
+        /// Image tag for the cp-kafka and cp-kafka-connect images
+        #[arg(long, default_value = "7.6.1")]
+        image_tag: String,
+
+        /// Seconds to wait for the connector to reach RUNNING
+        #[arg(long, default_value_t = 120)]
+        timeout: u64,
+    },
+
+    /// Validate planned connector changes from `terraform show -json` output
+    PlanCheck {
+        /// Plan JSON file (terraform show -json plan.out > plan.json), or - to read standard input
+        plan_file: String,
+    },
+
+    /// Append declarations for variables connectors reference but nobody declared
+    FixVariables {
+        /// Directory to fix; each subdirectory is treated as its own module
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// Print the declarations instead of writing variables.tf
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Rename a topic prefix in topic settings across every .tf and .tfvars file
+    RenamePrefix {
+        /// Current topic prefix
+        old_prefix: String,
+
+        /// Replacement topic prefix
+        new_prefix: String,
+
+        /// Directory to rewrite recursively
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// Print the changes without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Rename a connector resource and its references, adding a moved block so Terraform keeps the connector
+    RenameResource {
+        /// Current resource name (the label in `resource "confluent_connector" "<name>"`)
+        old_name: String,
+
+        /// New resource name
+        new_name: String,
+
+        /// Directory to search recursively for the resource
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// Print the changes without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Combine Terraform files into one, keeping comments and variable references as they are
+    Merge {
+        /// Files to combine, in order; blocks they share, such as variables, are kept once
+        #[arg(required = true, num_args = 2..)]
+        files: Vec<String>,
+
+        /// File to write (- prints it)
+        #[arg(short, long)]
+        output: String,
+
+        /// Overwrite the output file if it already exists
+        #[arg(long)]
+        force: bool,
+
+        /// Print the merged file and write plan without writing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Move the connectors of a Terraform file into files of their own
+    Split {
+        /// File to split
+        file: String,
+
+        /// Write each connector to `<resource name>.tf` next to the file
+        #[arg(long, required = true)]
+        per_connector: bool,
+
+        /// Overwrite connector files that already exist
+        #[arg(long)]
+        force: bool,
+
+        /// Print the files without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Report connectors in Confluent Cloud that Terraform doesn't manage
+    Orphans {
+        /// Environment ID (env-...)
+        #[arg(short, long)]
+        environment: String,
+
+        /// Kafka cluster ID (lkc-...)
+        #[arg(short, long)]
+        cluster: String,
+
+        /// Terraform state file
+        #[arg(short, long, default_value = "terraform.tfstate")]
+        state: String,
+
+        /// Directory of .tf files whose connectors count as managed
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+    },
+
+    /// List deployed connectors and whether Terraform declares them
+    List {
+        /// Environment ID (env-...)
+        #[arg(short, long)]
+        environment: String,
+
+        /// Kafka cluster ID (lkc-...)
+        #[arg(short, long)]
+        cluster: String,
+
+        /// Directory of .tf files to match against
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+    },
+
+    /// Write a health report for one environment: connector state, config drift, validation findings, and deprecations
+    Report {
+        /// Environment ID (env-...)
+        #[arg(short, long)]
+        environment: String,
+
+        /// Kafka cluster ID (lkc-...)
+        #[arg(short, long)]
+        cluster: String,
+
+        /// Directory of .tf files to report on
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// Report format
+        #[arg(long, default_value = "markdown", value_parser = ["markdown", "html"])]
+        format: String,
+
+        /// File to write the report to (prints to stdout if omitted or -)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Save the local and live configs, redacted, of each drifted connector here
+        #[arg(long, value_name = "DIR")]
+        debug_dir: Option<String>,
+    },
+
+    /// Show a deployed connector's source or sink offsets, or reset them
+    #[command(args_conflicts_with_subcommands = true, arg_required_else_help = true)]
+    Offsets {
+        #[command(subcommand)]
+        command: Option<OffsetsCommands>,
+
+        #[command(flatten)]
+        target: Option<OffsetsTarget>,
+    },
+
+    /// Print a CSV or JSON inventory of every connector for security and platform reviews
+    Audit {
+        /// Directory to inventory recursively
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// Report format
+        #[arg(long, default_value = "csv", value_parser = ["csv", "json"])]
+        format: String,
+
+        /// Directory names holding production connectors, which must have an owner
+        #[arg(
+            long,
+            value_name = "NAMES",
+            value_delimiter = ',',
+            default_value = "prod,production"
+        )]
+        production: Vec<String>,
+    },
+
+    /// Recommend a creation order for a directory's topics and connectors, with depends_on patches for sinks
+    Order {
+        /// Directory to order recursively, one module at a time
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// Output format
+        #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+        format: String,
+    },
+
+    /// List the connectors that reference a topic or a sensitive key, before a schema change or credential rotation
+    #[command(group(clap::ArgGroup::new("query").required(true).multiple(true).args(["topic", "secret_key"])))]
+    WhereUsed {
+        /// Topic to look for in topics, kafka.topic, topic.prefix, and topics.regex
+        #[arg(long)]
+        topic: Option<String>,
+
+        /// Config key to look for in config_sensitive and config_nonsensitive, e.g. database.password
+        #[arg(long)]
+        secret_key: Option<String>,
+
+        /// Directory to search recursively
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// Output format
+        #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+        format: String,
+    },
+
+    /// Point every connector that sets a sensitive key at a new reference and list the rotations to do outside Terraform
+    RotateSecret {
+        /// Config key holding the credential, e.g. kafka.api.key
+        #[arg(long)]
+        key: String,
+
+        /// New reference to set in config_sensitive, e.g. var.kafka_api_key_v2; omit to only print the checklist
+        #[arg(long)]
+        to: Option<String>,
+
+        /// Directory to search recursively
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// Print the changes without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Print connector checks as `tflint --format json` output, or the ruleset with --rules
+    Tflint {
+        /// Directory to check recursively
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// Print the rule names, severities, and descriptions instead of checking
+        #[arg(long)]
+        rules: bool,
+    },
+
+    /// Generate every catalog connector and check the validator accepts the result
+    Selftest {
+        /// Round-trip only this connector
+        #[arg(short, long)]
+        connector: Option<String>,
+    },
+
+    /// Export or compare connector catalog bundles
+    Catalog {
+        #[command(subcommand)]
+        command: CatalogCommands,
+    },
+
+    /// Scaffold a connectors repository: environments, modules, policy, CI, and pre-commit hooks
+    Init {
+        /// Directory to create the layout in
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// Environments to create directories and policy profiles for
+        #[arg(long, default_value = "dev,prod", value_delimiter = ',')]
+        environments: Vec<String>,
+
+        /// CI system to write a pipeline for
+        #[arg(long, default_value = "github", value_parser = ["github", "gitlab", "none"])]
+        ci: String,
+
+        /// Overwrite files that already exist
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Scan .tf/.tfvars files for hardcoded secrets, placeholders, and misplaced sensitive keys
+    Scan {
+        /// Directory to scan recursively, or - to scan standard input
+        #[arg(short, long, default_value = ".")]
+        dir: String,
+
+        /// Report format
+        #[arg(long, default_value = "text", value_parser = ["text", "json", "sarif"])]
+        format: String,
+    },
+
+    /// Generate man pages and a Markdown CLI reference from this CLI definition
+    #[command(hide = true)]
+    GenDocs {
+        /// Directory to write `man/*.1` and `cli-reference.md` into
+        #[arg(long, default_value = "docs")]
+        out_dir: String,
+    },
+}
+
+#[derive(Args)]
+struct OffsetsTarget {
+    /// Connector name as deployed in Confluent Cloud
+    connector: String,
+
+    /// Environment ID (env-...)
+    #[arg(short, long)]
+    environment: String,
+
+    /// Kafka cluster ID (lkc-...)
+    #[arg(short, long)]
+    cluster: String,
+}
+
+#[derive(Subcommand)]
+enum OffsetsCommands {
+    /// Reset a connector's offsets after showing them and asking for confirmation
+    Reset {
+        #[command(flatten)]
+        target: OffsetsTarget,
+
+        /// Where to move the offsets
+        #[arg(long, value_name = "earliest")]
+        to: String,
+
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum CatalogCommands {
+    /// Export the built-in connector catalog as a JSON bundle
+    Export {
+        /// Bundle file to write (prints to stdout if omitted or -)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Report connectors added, removed, and changed between two exported bundles
+    Diff {
+        /// Bundle before the update, or - to read standard input
+        old: String,
+
+        /// Bundle after the update, or - to read standard input
+        new: String,
+
+        /// Output format; markdown prints a PR-comment-ready summary
+        #[arg(long, default_value = "text", value_parser = ["text", "markdown"])]
+        format: String,
+    },
+
+    /// Count connectors by type and family, and how much field metadata they carry
+    Stats {
+        /// Exported bundle to report on instead of the built-in catalog, or - to read standard input
+        #[arg(long, value_name = "FILE")]
+        bundle: Option<String>,
+
+        /// Output format
+        #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+        format: String,
+    },
+
+    /// Merge published connector metadata into the catalog and report fields still missing it
+    Enrich {
+        /// JSON metadata file with connector descriptions, field defaults, and valid values, or - for stdin
+        metadata: String,
+
+        /// Exported bundle to enrich instead of the built-in catalog
+        #[arg(long, value_name = "FILE")]
+        bundle: Option<String>,
+
+        /// Write the enriched connectors as a definitions file for --definitions, or - for stdout
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Replace catalog values that differ from the metadata instead of reporting them
+        #[arg(long)]
+        overwrite: bool,
+
+        /// Report format
+        #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+        format: String,
+    },
+}
+
+#[cfg(not(tarpaulin_include))]
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    style::configure(cli.no_emoji, cli.no_color);
+    style::messages_to_stderr(writes_stdout(&cli.command));
+    progress::configure(cli.quiet);
+    // Logs go to stderr so JSON, SARIF, and Markdown output on stdout stays clean.
+    // Phase spans are debug level, so only the recorder sees them
+    let recorder = (cli.timings || cli.profile_output.is_some()).then(PhaseRecorder::new);
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_ansi(style::colors_enabled())
+                .with_filter(LevelFilter::INFO),
+        )
+        .with(recorder.clone())
+        .init();
+
+    let (timings, profile_output) = (cli.timings, cli.profile_output.clone());
+    let mut result = run(cli).await;
+    if let Some(recorder) = recorder {
+        let report = recorder.report();
+        if timings {
+            eprint!("{}", style::render(&report.render()));
+        }
+        if let Some(path) = profile_output {
+            result = result.and(write_profile(&path, &report));
+        }
+    }
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", style::render(&format!("Error: {}", e)));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Whether the command's `-o -` output goes to stdout, where messages would corrupt it
+fn writes_stdout(command: &Commands) -> bool {
+    let output = match command {
+        Commands::Generate { output, .. }
+        | Commands::Convert { output, .. }
+        | Commands::Report { output, .. }
+        | Commands::Catalog {
+            command: CatalogCommands::Export { output } | CatalogCommands::Enrich { output, .. },
+        } => output.as_deref(),
+        _ => None,
+    };
+    output.is_some_and(stdio::is_stdio)
+}
+
+/// Writes the `--profile-output` file
+fn write_profile(path: &str, report: &TimingReport) -> Result<(), ConnectUtilError> {
+    std::fs::write(path, format!("{}\n", serde_json::to_string_pretty(report)?))?;
+    Ok(())
+}
+
+#[cfg(not(tarpaulin_include))]
+async fn run(cli: Cli) -> Result<(), ConnectUtilError> {
+    i18n::configure(cli.lang.as_deref()).map_err(ConnectUtilError::Config)?;
+    let naming = NamingRules::new(cli.name_pattern.as_deref(), cli.label_pattern.as_deref())?;
+    let mut app = ConnectUtilApp::new()
+        .await?
+        .with_naming_rules(naming)
+        .with_stdin_filename(cli.stdin_filename.map(std::path::PathBuf::from));
+    if let Some(path) = &cli.definitions {
+        let loaded = definitions::load_definitions(std::path::Path::new(path))?;
+        info!(
+            "Loaded {} connector definition(s) from {}",
+            loaded.len(),
+            path
+        );
+        definitions::register(loaded);
+    }
+
+    match cli.command {
+        Commands::Generate {
+            name,
+            output,
+            connector,
+            dry_run,
+            force,
+            append,
+            merge_into,
+            layout,
+            output_dir,
+            newline,
+            with_outputs,
+            with_topics,
+            with_service_account,
+            with_api_key,
+            tables,
+            exclude_tables,
+            heartbeat_interval,
+            heartbeat_table,
+            signal_table,
+            snapshot_chunk_size,
+            partitioner,
+            partition_fields,
+            path_format,
+            locale,
+            timezone,
+            schema_file,
+            schema_subject,
+            schema_registry_url,
+            ksql_tables,
+            sops,
+            sops_age,
+            sops_kms,
+            secrets_backend,
+            with_import_block,
+            ignore_changes,
+            tokens,
+            environment,
+            environments,
+            environment_layout,
+            cluster_type,
+            target,
+            provider,
+            owner,
+            on_call,
+            maintenance_window,
+            schema_context,
+            csfle,
+            input_format,
+            output_format,
+            topics,
+            verbose,
+            report,
+        } => {
+            info!("Starting Terraform generation");
+            if output_dir.as_deref().is_some_and(stdio::is_stdio) {
+                return Err(ConnectUtilError::Config(
+                    "The project layout writes several files; --output-dir can't be standard output"
+                        .to_string(),
+                ));
+            }
+            if sops {
+                connect_util::sops::check_supported()?;
+            }
+            let tokens = tokens
+                .iter()
+                .map(|pair| {
+                    pair.split_once('=')
+                        .map(|(k, v)| (k.to_string(), v.to_string()))
+                        .ok_or_else(|| {
+                            ConnectUtilError::Config(format!(
+                                "Invalid --token '{}': expected key=value",
+                                pair
+                            ))
+                        })
+                })
+                .collect::<Result<_, _>>()?;
+            let options = ConnectorOptions {
+                name,
+                merge: merge_into.is_some(),
+                output: merge_into.or(output),
+                connector,
+                dry_run,
+                force,
+                append,
+                layout: layout.parse().map_err(ConnectUtilError::Config)?,
+                output_dir,
+                newline: newline
+                    .map(|n| n.parse())
+                    .transpose()
+                    .map_err(ConnectUtilError::Config)?,
+                environment,
+                environments,
+                environment_layout: environment_layout
+                    .parse()
+                    .map_err(ConnectUtilError::Config)?,
+                cluster_type: cluster_type
+                    .map(|c| c.parse())
+                    .transpose()
+                    .map_err(ConnectUtilError::Config)?,
+                with_outputs,
+                topics,
+                input_format: input_format
+                    .map(|f| f.parse())
+                    .transpose()
+                    .map_err(ConnectUtilError::Config)?,
+                output_format: output_format
+                    .map(|f| f.parse())
+                    .transpose()
+                    .map_err(ConnectUtilError::Config)?,
+                schema_registry: SchemaRegistryOptions {
+                    context: schema_context,
+                    csfle,
+                },
+                companions: CompanionResources {
+                    topics: with_topics,
+                    service_account: with_service_account || with_api_key,
+                    api_key: with_api_key,
+                },
+                import_name: with_import_block,
+                ignore_changes,
+                tokens,
+                tables,
+                exclude_tables,
+                debezium: DebeziumOptions {
+                    heartbeat_interval_ms: heartbeat_interval,
+                    heartbeat_table,
+                    signal_table,
+                    snapshot_chunk_size,
+                },
+                storage: StorageLayoutOptions {
+                    partitioner: partitioner
+                        .map(|p| p.parse())
+                        .transpose()
+                        .map_err(ConnectUtilError::Config)?,
+                    partition_fields,
+                    path_format,
+                    locale,
+                    timezone,
+                },
+                record_schema: record_schema::load(
+                    schema_file.as_deref(),
+                    schema_subject.as_deref(),
+                    schema_registry_url.as_deref(),
+                )
+                .await?,
+                ksql_tables,
+                sops: sops.then_some(SopsOptions {
+                    age: sops_age,
+                    kms: sops_kms,
+                }),
+                secrets_backend: secrets_backend.parse().map_err(ConnectUtilError::Config)?,
+                target: target.parse().map_err(ConnectUtilError::Config)?,
+                provider: provider
+                    .map(|p| p.parse())
+                    .transpose()
+                    .map_err(ConnectUtilError::Config)?
+                    .unwrap_or_default(),
+                metadata: ConnectorMetadata {
+                    owner,
+                    on_call,
+                    maintenance_window,
+                },
+                verbose,
+                report,
+            };
+            // Offer the organization's environments and clusters when the wizard runs
+            // with credentials and no environment profile
+            let discover = (options.name.is_none() || options.connector.is_none())
+                && options.environment.is_none()
+                && options.environments.is_empty();
+            let client = discover.then(CloudClient::from_env).and_then(Result::ok);
+            app.generate_terraform(options, client.as_ref().map(|c| c as &dyn OrgApi))
+                .await?;
+        }
+
+        Commands::Validate {
+            path,
+            config_file,
+            dir,
+            format,
+            notify_webhook,
+            notify_format,
+            remote,
+            environment,
+            cluster,
+            concurrency,
+            debug_dir,
+            connect_url,
+            interactive,
+            profile,
+            check_stale,
+        } => {
+            info!("Validating connector configuration");
+            let (config_file, dir) = match path {
+                Some(path) if std::path::Path::new(&path).is_dir() => (None, Some(path)),
+                Some(path) => (Some(path), None),
+                None => (config_file, dir),
+            };
+            if config_file.as_deref().is_some_and(stdio::is_stdio)
+                && (interactive || remote || connect_url.is_some())
+            {
+                return Err(ConnectUtilError::Config(
+                    "--interactive, --remote, and --connect-url work on files and can't read standard input"
+                        .to_string(),
+                ));
+            }
+            let profile: Option<ValidationProfile> = profile
+                .map(|p| p.parse())
+                .transpose()
+                .map_err(ConnectUtilError::Config)?;
+            if let Some(profile) = profile {
+                let remote_checks = remote || connect_url.is_some();
+                if profile.runs_remote_checks() && !remote_checks {
+                    return Err(ConnectUtilError::Config(format!(
+                        "The {} profile runs the remote checks; pass --remote with --environment and --cluster, or --connect-url",
+                        profile.name()
+                    )));
+                }
+                if !profile.runs_remote_checks() && remote_checks {
+                    return Err(ConnectUtilError::Config(format!(
+                        "--remote and --connect-url only run in the pre-deploy profile, not {}",
+                        profile.name()
+                    )));
+                }
+            }
+            app = app.with_validation_profile(profile);
+            if interactive {
+                if format != "text" {
+                    return Err(ConnectUtilError::Config(format!(
+                        "--interactive can't be combined with --format {}",
+                        format
+                    )));
+                }
+                let remaining = app
+                    .validate_interactive(
+                        &mut TerminalReviewer,
+                        config_file.as_deref(),
+                        dir.as_deref(),
+                    )
+                    .await?;
+                if remaining > 0 {
+                    return Err(ConnectUtilError::Validation(format!(
+                        "{} finding(s) left unresolved",
+                        remaining
+                    )));
+                }
+                return Ok(());
+            }
+            if (remote || connect_url.is_some()) && format != "text" {
+                return Err(ConnectUtilError::Config(format!(
+                    "--remote and --connect-url print their checks as text and can't be combined with --format {}",
+                    format
+                )));
+            }
+            if check_stale && format != "text" {
+                return Err(ConnectUtilError::Config(format!(
+                    "--check-stale prints its warnings as text and can't be combined with --format {}",
+                    format
+                )));
+            }
+            let result = match format.as_str() {
+                "markdown" => {
+                    app.validate_markdown(config_file.as_deref(), dir.as_deref())
+                        .await
+                }
+                "json" => {
+                    app.validate_json(config_file.as_deref(), dir.as_deref())
+                        .await
+                }
+                _ => match (&config_file, &dir) {
+                    (_, Some(dir)) => app.validate_directory(dir).await,
+                    (Some(config_file), None) => app.validate_connector(config_file).await,
+                    (None, None) => unreachable!("clap requires --config-file or --dir"),
+                },
+            };
+            if check_stale {
+                app.check_stale(config_file.as_deref(), dir.as_deref())?;
+            }
+            if remote {
+                app = app.with_debug_dir(debug_dir.map(std::path::PathBuf::from));
+            }
+            // Every check runs before the notification, so it reports the run's outcome
+            let outcome = async {
+                result?;
+                app.enforce_profile(config_file.as_deref(), dir.as_deref())?;
+                if let (true, Some(environment), Some(cluster)) = (remote, environment, cluster) {
+                    info!("Checking connectors against {}/{}", environment, cluster);
+                    let client = CloudClient::from_env()?;
+                    let cache = SchemaCache::new(remote::default_cache_dir());
+                    let path = dir
+                        .clone()
+                        .or(config_file.clone())
+                        .unwrap_or_else(|| ".".to_string());
+                    app.validate_remote(
+                        &client,
+                        &cache,
+                        &environment,
+                        &cluster,
+                        &path,
+                        concurrency.into(),
+                    )
+                    .await?;
+                }
+                if let Some(url) = connect_url {
+                    info!("Checking connectors against {}", url);
+                    let client = ConnectRestClient::from_env(url);
+                    let path = dir
+                        .clone()
+                        .or(config_file.clone())
+                        .unwrap_or_else(|| ".".to_string());
+                    let findings = app.validate_connect(&client, &path).await?;
+                    if findings > 0 {
+                        return Err(ConnectUtilError::Validation(format!(
+                            "Kafka Connect rejected {} setting(s)",
+                            findings
+                        )));
+                    }
+                }
+                Ok(())
+            }
+            .await;
+            // Notify even when validation fails, since that is what the channel needs to hear
+            if let Some(url) = notify_webhook {
+                let format = match notify_format {
+                    Some(format) => format.parse().map_err(ConnectUtilError::UserInput)?,
+                    None => NotifyFormat::for_url(&url),
+                };
+                let notified = app
+                    .notify_validation(
+                        &WebhookNotifier::new(url),
+                        format,
+                        config_file.as_deref(),
+                        dir.as_deref(),
+                        outcome.as_ref().err().map(ToString::to_string),
+                    )
+                    .await;
+                outcome?;
+                notified?;
+            } else {
+                outcome?;
+            }
+        }
+
+        Commands::ListPlugins { r#type } => {
+            info!("Listing available connector plugins");
+            app.list_plugins(r#type).await?;
+        }
+
+        Commands::Describe { connector, format } => match format.as_str() {
+            "json-schema" => app.describe_connector_schema(&connector).await?,
+            _ => app.describe_connector(&connector).await?,
+        },
+
+        Commands::Explain { connector, key } => app.explain(&connector, &key).await?,
+
+        Commands::Convert {
+            from_properties,
+            from_json,
+            output,
+            dry_run,
+            force,
+        } => {
+            let options = ConnectorOptions {
+                output,
+                dry_run,
+                force,
+                ..Default::default()
+            };
+            match (from_properties, from_json) {
+                (Some(properties), _) => {
+                    info!("Converting {}", properties);
+                    app.convert_properties_file(&properties, &options)?;
+                }
+                (None, Some(json)) => {
+                    info!("Converting {}", json);
+                    app.convert_json_file(&json, &options)?;
+                }
+                (None, None) => unreachable!("clap requires an input"),
+            }
+        }
+
+        #[cfg(feature = "local-test")]
+        Commands::Test {
+            config_file,
+            overrides,
+            plugins,
+            image_tag,
+            timeout,
+        } => {
+            info!("Smoke testing {} locally", config_file);
+            let overrides = overrides
+                .iter()
+                .map(|pair| {
+                    pair.split_once('=')
+                        .map(|(k, v)| (k.to_string(), v.to_string()))
+                        .ok_or_else(|| {
+                            ConnectUtilError::Config(format!(
+                                "Invalid --set '{}': expected key=value",
+                                pair
+                            ))
+                        })
+                })
+                .collect::<Result<_, _>>()?;
+            let options = connect_util::harness::LocalTestOptions {
+                image_tag,
+                plugins,
+                timeout: std::time::Duration::from_secs(timeout),
+            };
+            app.local_test(&config_file, &overrides, options).await?;
+        }
+
+        Commands::PlanCheck { plan_file } => {
+            info!("Checking planned connector changes in {}", plan_file);
+            app.plan_check(&plan_file).await?;
+        }
+
+        Commands::FixVariables { dir, dry_run } => {
+            info!("Declaring missing variables in {}", dir);
+            app.fix_variables(&dir, dry_run).await?;
+        }
+
+        Commands::RenamePrefix {
+            old_prefix,
+            new_prefix,
+            dir,
+            dry_run,
+        } => {
+            info!(
+                "Renaming topic prefix {} to {} in {}",
+                old_prefix, new_prefix, dir
+            );
+            app.rename_topic_prefix(&dir, &old_prefix, &new_prefix, dry_run)
+                .await?;
+        }
+
+        Commands::RenameResource {
+            old_name,
+            new_name,
+            dir,
+            dry_run,
+        } => {
+            info!(
+                "Renaming confluent_connector.{} to {} in {}",
+                old_name, new_name, dir
+            );
+            app.rename_connector_resource(&dir, &old_name, &new_name, dry_run)
+                .await?;
+        }
+        Commands::Merge {
+            files,
+            output,
+            force,
+            dry_run,
+        } => {
+            info!("Merging {} into {}", files.join(", "), output);
+            app.merge_terraform_files(&files, &output, force, dry_run)
+                .await?;
+        }
+        Commands::Split {
+            file,
+            per_connector: _,
+            force,
+            dry_run,
+        } => {
+            info!("Splitting {} per connector", file);
+            app.split_terraform_file(&file, force, dry_run).await?;
+        }
+
+        Commands::Orphans {
+            environment,
+            cluster,
+            state,
+            dir,
+        } => {
+            info!(
+                "Looking for unmanaged connectors in {}/{}",
+                environment, cluster
+            );
+            let client = CloudClient::from_env()?;
+            app.find_orphans(&client, &environment, &cluster, Some(&state), Some(&dir))
+                .await?;
+        }
+
+        Commands::List {
+            environment,
+            cluster,
+            dir,
+        } => {
+            info!(
+                "Listing connectors in {}/{} against {}",
+                environment, cluster, dir
+            );
+            let client = CloudClient::from_env()?;
+            app.list_deployed_connectors(&client, &environment, &cluster, &dir)
+                .await?;
+        }
+
+        Commands::Report {
+            environment,
+            cluster,
+            dir,
+            format,
+            output,
+            debug_dir,
+        } => {
+            info!(
+                "Reporting on connectors in {}/{} against {}",
+                environment, cluster, dir
+            );
+            let format = format.parse().map_err(ConnectUtilError::Config)?;
+            let client = CloudClient::from_env()?;
+            app = app.with_debug_dir(debug_dir.map(std::path::PathBuf::from));
+            app.health_report(
+                &client,
+                &environment,
+                &cluster,
+                &dir,
+                format,
+                output.as_deref(),
+            )
+            .await?;
+        }
+
+        Commands::Offsets { command, target } => {
+            let client = CloudClient::from_env()?;
+            match (command, target) {
+                (Some(OffsetsCommands::Reset { target, to, yes }), _) => {
+                    info!("Resetting offsets of {} to {}", target.connector, to);
+                    let to = to.parse().map_err(ConnectUtilError::Config)?;
+                    app.reset_offsets(
+                        &client,
+                        &target.environment,
+                        &target.cluster,
+                        &target.connector,
+                        to,
+                        yes,
+                    )
+                    .await?;
+                }
+                (None, Some(target)) => {
+                    info!("Reading offsets of {}", target.connector);
+                    app.show_offsets(
+                        &client,
+                        &target.environment,
+                        &target.cluster,
+                        &target.connector,
+                    )
+                    .await?;
+                }
+                (None, None) => {
+                    return Err(ConnectUtilError::Config(
+                        "Name the connector whose offsets to show, or use `offsets reset`"
+                            .to_string(),
+                    ));
+                }
+            }
+        }
+
+        Commands::Audit {
+            dir,
+            format,
+            production,
+        } => {
+            info!("Building connector inventory for {}", dir);
+            let format = format.parse().map_err(ConnectUtilError::Config)?;
+            app.audit(&dir, format, &production).await?;
+        }
+
+        Commands::Order { dir, format } => {
+            info!("Ordering connectors in {}", dir);
+            let format = format.parse().map_err(ConnectUtilError::Config)?;
+            app.order(&dir, format).await?;
+        }
+
+        Commands::WhereUsed {
+            topic,
+            secret_key,
+            dir,
+            format,
+        } => {
+            info!("Finding connectors that reference the query in {}", dir);
+            let format = format.parse().map_err(ConnectUtilError::Config)?;
+            app.where_used(&dir, &UsageQuery { topic, secret_key }, format)
+                .await?;
+        }
+
+        Commands::RotateSecret {
+            key,
+            to,
+            dir,
+            dry_run,
+        } => {
+            info!("Rotating {} in {}", key, dir);
+            app.rotate_secret(&dir, &key, to.as_deref(), dry_run)
+                .await?;
+        }
+
+        Commands::Tflint { dir, rules } => {
+            info!("Running connector lint checks in {}", dir);
+            app.tflint(&dir, rules).await?;
+        }
+
+        Commands::Selftest { connector } => {
+            info!("Round-tripping catalog connectors");
+            app.selftest(connector.as_deref()).await?;
+        }
+
+        Commands::Catalog { command } => match command {
+            CatalogCommands::Export { output } => {
+                info!("Exporting connector catalog");
+                app.export_catalog(output.as_deref()).await?;
+            }
+            CatalogCommands::Diff { old, new, format } => {
+                info!("Comparing catalog bundles {} and {}", old, new);
+                app.diff_catalog_bundles(&old, &new, format == "markdown")
+                    .await?;
+            }
+            CatalogCommands::Stats { bundle, format } => {
+                info!("Collecting catalog statistics");
+                app.catalog_stats(bundle.as_deref(), format == "json")
+                    .await?;
+            }
+            CatalogCommands::Enrich {
+                metadata,
+                bundle,
+                output,
+                overwrite,
+                format,
+            } => {
+                info!("Enriching the connector catalog from {}", metadata);
+                app.enrich_catalog(
+                    &metadata,
+                    bundle.as_deref(),
+                    output.as_deref(),
+                    overwrite,
+                    format == "json",
+                )
+                .await?;
+            }
+        },
+
+        Commands::Init {
+            dir,
+            environments,
+            ci,
+            force,
+        } => {
+            info!("Scaffolding a connectors repository in {}", dir);
+            let ci = ci.parse().map_err(ConnectUtilError::Config)?;
+            app.init(&dir, &environments, ci, force).await?;
+        }
+
+        Commands::Scan { dir, format } => {
+            info!("Scanning {} for secrets", dir);
+            let format = format.parse().map_err(ConnectUtilError::Config)?;
+            app.scan(&dir, format).await?;
+        }
+
+        Commands::GenDocs { out_dir } => {
+            info!("Generating CLI docs into {}", out_dir);
+            for path in docs::write_docs(&Cli::command(), std::path::Path::new(&out_dir))? {
+                say!("📝 Wrote {}", path.display());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_parsing_generate_command() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--name",
+            "test-connector",
+            "--output",
+            "test-output.tf",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Generate { name, output, .. } => {
+                assert_eq!(name, Some("test-connector".to_string()));
+                assert_eq!(output, Some("test-output.tf".to_string()));
+            }
+            _ => panic!("Expected Generate command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_environment() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--name",
+            "orders",
+            "--environment",
+            "prod",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Generate { environment, .. } => {
+                assert_eq!(environment.as_deref(), Some("prod"));
+            }
+            _ => panic!("Expected Generate command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_merge_into() {
+        let cli =
+            Cli::try_parse_from(["connect-util", "generate", "--merge-into", "main.tf"]).unwrap();
+        match cli.command {
+            Commands::Generate { merge_into, .. } => {
+                assert_eq!(merge_into.as_deref(), Some("main.tf"))
+            }
+            _ => panic!("Expected Generate command"),
+        }
+        assert!(Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--merge-into",
+            "main.tf",
+            "--append",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_with_import_block() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--with-import-block",
+            "orders-cdc",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Generate {
+                with_import_block, ..
+            } => assert_eq!(with_import_block.as_deref(), Some("orders-cdc")),
+            _ => panic!("Expected Generate command"),
+        }
+        assert!(Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--with-import-block",
+            "lcc-abc123",
+            "--environments",
+            "dev,prod",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_topics() {
+        let cli = Cli::try_parse_from(["connect-util", "generate", "--topics", "orders,refunds"])
+            .unwrap();
+
+        match cli.command {
+            Commands::Generate { topics, .. } => {
+                assert_eq!(topics, vec!["orders", "refunds"]);
+            }
+            _ => panic!("Expected Generate command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_ignore_changes() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--ignore-changes",
+            "status,config_nonsensitive[\"tasks.max\"]",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Generate { ignore_changes, .. } => {
+                assert_eq!(
+                    ignore_changes,
+                    vec!["status", "config_nonsensitive[\"tasks.max\"]"]
+                );
+            }
+            _ => panic!("Expected Generate command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_report() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--verbose",
+            "--report",
+            "generate.json",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Generate {
+                verbose, report, ..
+            } => {
+                assert!(verbose);
+                assert_eq!(report.as_deref(), Some("generate.json"));
+            }
+            _ => panic!("Expected Generate command"),
+        }
+        assert!(Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--report",
+            "generate.json",
+            "--environments",
+            "dev,prod",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_tables() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--tables",
+            "public.orders,public.refunds",
+            "--exclude-tables",
+            "public.audit",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Generate {
+                tables,
+                exclude_tables,
+                ..
+            } => {
+                assert_eq!(tables, vec!["public.orders", "public.refunds"]);
+                assert_eq!(exclude_tables, vec!["public.audit"]);
+            }
+            _ => panic!("Expected Generate command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_with_ksql() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--with-ksql",
+            "public.orders,public.refunds",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Generate { ksql_tables, .. } => {
+                assert_eq!(ksql_tables, vec!["public.orders", "public.refunds"]);
+            }
+            _ => panic!("Expected Generate command"),
+        }
+        assert!(Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--with-ksql",
+            "public.orders",
+            "--append",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_sops() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--sops",
+            "--sops-age",
+            "age1abc,age1def",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Generate {
+                sops,
+                sops_age,
+                sops_kms,
+                ..
+            } => {
+                assert!(sops);
+                assert_eq!(sops_age, vec!["age1abc", "age1def"]);
+                assert!(sops_kms.is_empty());
+            }
+            _ => panic!("Expected Generate command"),
+        }
+        assert!(
+            Cli::try_parse_from(["connect-util", "generate", "--sops-kms", "arn:aws:kms:k"])
+                .is_err()
+        );
+        assert!(Cli::try_parse_from(["connect-util", "generate", "--sops", "--append"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_secrets_backend() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--secrets-backend",
+            "azure-key-vault",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Generate {
+                secrets_backend, ..
+            } => assert_eq!(secrets_backend, "azure-key-vault"),
+            _ => panic!("Expected Generate command"),
+        }
+        assert!(Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--secrets-backend",
+            "gcp-secret-manager",
+            "--sops",
+        ])
+        .is_err());
+        assert!(
+            Cli::try_parse_from(["connect-util", "generate", "--secrets-backend", "vault",])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_tokens() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--token",
+            "team=payments",
+            "--token",
+            "env=prod",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Generate { tokens, .. } => {
+                assert_eq!(tokens, vec!["team=payments", "env=prod"]);
+            }
+            _ => panic!("Expected Generate command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_data_formats() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--input-format",
+            "JSON_SR",
+            "--output-format",
+            "parquet",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Generate {
+                input_format,
+                output_format,
+                ..
+            } => {
+                assert_eq!(input_format.as_deref(), Some("JSON_SR"));
+                assert_eq!(output_format.as_deref(), Some("parquet"));
+            }
+            _ => panic!("Expected Generate command"),
+        }
+        assert!(
+            Cli::try_parse_from(["connect-util", "generate", "--output-format", "xml"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_schema_registry() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--name",
+            "orders",
+            "--schema-context",
+            "orders",
+            "--csfle",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Generate {
+                schema_context,
+                csfle,
+                ..
+            } => {
+                assert_eq!(schema_context.as_deref(), Some("orders"));
+                assert!(csfle);
+            }
+            _ => panic!("Expected Generate command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_environments() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--name",
+            "orders",
+            "--environments",
+            "dev,prod",
+            "--environment-layout",
+            "for-each",
+            "--cluster-type",
+            "standard",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Generate {
+                environments,
+                environment_layout,
+                cluster_type,
+                ..
+            } => {
+                assert_eq!(environments, vec!["dev", "prod"]);
+                assert_eq!(environment_layout, "for-each");
+                assert_eq!(cluster_type.as_deref(), Some("standard"));
+            }
+            _ => panic!("Expected Generate command"),
+        }
+
+        assert!(Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--environment",
+            "prod",
+            "--environments",
+            "dev,prod",
+        ])
+        .is_err());
+        assert!(Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--environment-layout",
+            "for-each"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_target() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--name",
+            "orders",
+            "--target",
+            "self-managed",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Generate { target, .. } => assert_eq!(target, "self-managed"),
+            _ => panic!("Expected Generate command"),
+        }
+        assert!(Cli::try_parse_from(["connect-util", "generate", "--target", "strimzi"]).is_err());
+
+        let cli = Cli::try_parse_from(["connect-util", "generate", "--provider", "aiven"]).unwrap();
+        match cli.command {
+            Commands::Generate {
+                target, provider, ..
+            } => {
+                assert_eq!(target, "cloud");
+                assert_eq!(provider.as_deref(), Some("aiven"));
+            }
+            _ => panic!("Expected Generate command"),
+        }
+        assert!(Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--target",
+            "self-managed",
+            "--provider",
+            "aiven"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_command_minimal() {
+        let cli = Cli::try_parse_from(["connect-util", "generate"]).unwrap();
+
+        match cli.command {
+            Commands::Generate { name, output, .. } => {
+                assert_eq!(name, None);
+                assert_eq!(output, None);
+            }
+            _ => panic!("Expected Generate command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_dry_run() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--name",
+            "orders-sink",
+            "--connector",
+            "PostgresSink",
+            "--dry-run",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Generate {
+                connector, dry_run, ..
+            } => {
+                assert_eq!(connector, Some("PostgresSink".to_string()));
+                assert!(dry_run);
+            }
+            _ => panic!("Expected Generate command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_force_conflicts_with_append() {
+        let cli = Cli::try_parse_from(["connect-util", "generate", "--append"]).unwrap();
+        match cli.command {
+            Commands::Generate { append, force, .. } => {
+                assert!(append);
+                assert!(!force);
+            }
+            _ => panic!("Expected Generate command"),
+        }
+
+        let result = Cli::try_parse_from(["connect-util", "generate", "--force", "--append"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_project_layout() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--layout",
+            "project",
+            "--output-dir",
+            "./my-connector/",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Generate {
+                layout, output_dir, ..
+            } => {
+                assert_eq!(layout, "project");
+                assert_eq!(output_dir, Some("./my-connector/".to_string()));
+            }
+            _ => panic!("Expected Generate command"),
+        }
+
+        // The project layout needs somewhere to put its files
+        let result = Cli::try_parse_from(["connect-util", "generate", "--layout", "project"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_newline() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--output",
+            r"C:\infra\connectors\main.tf",
+            "--newline",
+            "crlf",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Generate {
+                output, newline, ..
+            } => {
+                assert_eq!(output, Some(r"C:\infra\connectors\main.tf".to_string()));
+                assert_eq!(newline, Some("crlf".to_string()));
+            }
+            _ => panic!("Expected Generate command"),
+        }
+
+        let result = Cli::try_parse_from(["connect-util", "generate", "--newline", "cr"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_with_outputs() {
+        let cli = Cli::try_parse_from(["connect-util", "generate", "--with-outputs"]).unwrap();
+
+        match cli.command {
+            Commands::Generate { with_outputs, .. } => assert!(with_outputs),
+            _ => panic!("Expected Generate command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_companions() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--with-topics",
+            "--with-api-key",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Generate {
+                with_topics,
+                with_service_account,
+                with_api_key,
+                ..
+            } => {
+                assert!(with_topics);
+                assert!(!with_service_account);
+                assert!(with_api_key);
+            }
+            _ => panic!("Expected Generate command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_naming_patterns() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "validate",
+            "--config-file",
+            "test.tf",
+            "--name-pattern",
+            "^src-.*$",
+            "--label-pattern",
+            "^[a-z_]+$",
+        ])
+        .unwrap();
+
+        assert_eq!(cli.name_pattern.as_deref(), Some("^src-.*$"));
+        assert_eq!(cli.label_pattern.as_deref(), Some("^[a-z_]+$"));
+    }
+
+    #[test]
+    fn test_cli_parsing_convert_command() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "convert",
+            "--from-properties",
+            "orders.properties",
+            "-o",
+            "orders.tf",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Convert {
+                from_properties,
+                from_json,
+                output,
+                dry_run,
+                force,
+            } => {
+                assert_eq!(from_properties, Some("orders.properties".to_string()));
+                assert_eq!(from_json, None);
+                assert_eq!(output, Some("orders.tf".to_string()));
+                assert!(!dry_run);
+                assert!(!force);
+            }
+            _ => panic!("Expected Convert command"),
+        }
+
+        let cli =
+            Cli::try_parse_from(["connect-util", "convert", "--from-json", "orders.json"]).unwrap();
+        match cli.command {
+            Commands::Convert { from_json, .. } => {
+                assert_eq!(from_json, Some("orders.json".to_string()))
+            }
+            _ => panic!("Expected Convert command"),
+        }
+        assert!(Cli::try_parse_from(["connect-util", "convert"]).is_err());
+        assert!(Cli::try_parse_from([
+            "connect-util",
+            "convert",
+            "--from-json",
+            "orders.json",
+            "--from-properties",
+            "orders.properties",
+        ])
+        .is_err());
+    }
+
+    #[cfg(feature = "local-test")]
+    #[test]
+    fn test_cli_parsing_test_command() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "test",
+            "--config-file",
+            "datagen.tf",
+            "--set",
+            "kafka.topic=orders",
+            "--plugin",
+            "confluentinc/kafka-connect-datagen:latest",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Test {
+                config_file,
+                overrides,
+                plugins,
+                timeout,
+                ..
+            } => {
+                assert_eq!(config_file, "datagen.tf");
+                assert_eq!(overrides, vec!["kafka.topic=orders"]);
+                assert_eq!(plugins.len(), 1);
+                assert_eq!(timeout, 120);
+            }
+            _ => panic!("Expected Test command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_plan_check_command() {
+        let cli = Cli::try_parse_from(["connect-util", "plan-check", "plan.json"]).unwrap();
+
+        match cli.command {
+            Commands::PlanCheck { plan_file } => assert_eq!(plan_file, "plan.json"),
+            _ => panic!("Expected PlanCheck command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_fix_variables_command() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "fix-variables",
+            "--dir",
+            "infra",
+            "--dry-run",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::FixVariables { dir, dry_run } => {
+                assert_eq!(dir, "infra");
+                assert!(dry_run);
+            }
+            _ => panic!("Expected FixVariables command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_rename_resource_command() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "rename-resource",
+            "orders",
+            "sales",
+            "--dry-run",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::RenameResource {
+                old_name,
+                new_name,
+                dir,
+                dry_run,
+            } => {
+                assert_eq!(old_name, "orders");
+                assert_eq!(new_name, "sales");
+                assert_eq!(dir, ".");
+                assert!(dry_run);
+            }
+            _ => panic!("Expected RenameResource command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_merge_and_split_commands() {
+        let cli =
+            Cli::try_parse_from(["connect-util", "merge", "a.tf", "b.tf", "-o", "combined.tf"])
+                .unwrap();
+        match cli.command {
+            Commands::Merge {
+                files,
+                output,
+                force,
+                dry_run,
+            } => {
+                assert_eq!(files, vec!["a.tf", "b.tf"]);
+                assert_eq!(output, "combined.tf");
+                assert!(!force);
+                assert!(!dry_run);
+            }
+            _ => panic!("Expected Merge command"),
+        }
+        assert!(Cli::try_parse_from(["connect-util", "merge", "a.tf", "-o", "c.tf"]).is_err());
+
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "split",
+            "big.tf",
+            "--per-connector",
+            "--dry-run",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Split { file, dry_run, .. } => {
+                assert_eq!(file, "big.tf");
+                assert!(dry_run);
+            }
+            _ => panic!("Expected Split command"),
+        }
+        assert!(Cli::try_parse_from(["connect-util", "split", "big.tf"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_rename_prefix_command() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "rename-prefix",
+            "--dir",
+            "infra",
+            "orders",
+            "sales",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::RenamePrefix {
+                old_prefix,
+                new_prefix,
+                dir,
+                dry_run,
+            } => {
+                assert_eq!(old_prefix, "orders");
+                assert_eq!(new_prefix, "sales");
+                assert_eq!(dir, "infra");
+                assert!(!dry_run);
+            }
+            _ => panic!("Expected RenamePrefix command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_list_command() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "list",
+            "--environment",
+            "env-123",
+            "--cluster",
+            "lkc-456",
+            "--dir",
+            "./terraform",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::List {
+                environment,
+                cluster,
+                dir,
+            } => {
+                assert_eq!(environment, "env-123");
+                assert_eq!(cluster, "lkc-456");
+                assert_eq!(dir, "./terraform");
+            }
+            _ => panic!("Expected List command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_report_command() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "report",
+            "-e",
+            "env-123",
+            "-c",
+            "lkc-456",
+            "--format",
+            "html",
+            "-o",
+            "health.html",
+            "--debug-dir",
+            "debug",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Report {
+                environment,
+                cluster,
+                dir,
+                format,
+                output,
+                debug_dir,
+            } => {
+                assert_eq!(environment, "env-123");
+                assert_eq!(cluster, "lkc-456");
+                assert_eq!(dir, ".");
+                assert_eq!(format, "html");
+                assert_eq!(output.as_deref(), Some("health.html"));
+                assert_eq!(debug_dir.as_deref(), Some("debug"));
+            }
+            _ => panic!("Expected Report command"),
+        }
+        assert!(Cli::try_parse_from([
+            "connect-util",
+            "report",
+            "-e",
+            "e",
+            "-c",
+            "c",
+            "--format",
+            "pdf"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_offsets_command() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "offsets",
+            "orders-sink",
+            "-e",
+            "env-123",
+            "-c",
+            "lkc-456",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Offsets {
+                command: None,
+                target: Some(target),
+            } => {
+                assert_eq!(target.connector, "orders-sink");
+                assert_eq!(target.environment, "env-123");
+                assert_eq!(target.cluster, "lkc-456");
+            }
+            _ => panic!("Expected Offsets command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "offsets",
+            "reset",
+            "orders-sink",
+            "-e",
+            "env-123",
+            "-c",
+            "lkc-456",
+            "--to",
+            "earliest",
+            "--yes",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Offsets {
+                command: Some(OffsetsCommands::Reset { target, to, yes }),
+                target: None,
+            } => {
+                assert_eq!(target.connector, "orders-sink");
+                assert_eq!(to, "earliest");
+                assert!(yes);
+            }
+            _ => panic!("Expected Offsets reset command"),
+        }
+
+        assert!(Cli::try_parse_from(["connect-util", "offsets", "orders-sink"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_orphans_command() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "orphans",
+            "--environment",
+            "env-123",
+            "--cluster",
+            "lkc-456",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Orphans {
+                environment,
+                cluster,
+                state,
+                dir,
+            } => {
+                assert_eq!(environment, "env-123");
+                assert_eq!(cluster, "lkc-456");
+                assert_eq!(state, "terraform.tfstate");
+                assert_eq!(dir, ".");
+            }
+            _ => panic!("Expected Orphans command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_audit_command() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "audit",
+            "--dir",
+            "infra",
+            "--format",
+            "json",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Audit {
+                dir,
+                format,
+                production,
+            } => {
+                assert_eq!(dir, "infra");
+                assert_eq!(format, "json");
+                assert_eq!(production, vec!["prod", "production"]);
+            }
+            _ => panic!("Expected Audit command"),
+        }
+
+        let cli = Cli::try_parse_from(["connect-util", "audit"]).unwrap();
+        match cli.command {
+            Commands::Audit { dir, format, .. } => {
+                assert_eq!(dir, ".");
+                assert_eq!(format, "csv");
+            }
+            _ => panic!("Expected Audit command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_tflint_command() {
+        let cli = Cli::try_parse_from(["connect-util", "tflint", "--dir", "infra"]).unwrap();
+        match cli.command {
+            Commands::Tflint { dir, rules } => {
+                assert_eq!(dir, "infra");
+                assert!(!rules);
+            }
+            _ => panic!("Expected Tflint command"),
+        }
+
+        let cli = Cli::try_parse_from(["connect-util", "tflint", "--rules"]).unwrap();
+        match cli.command {
+            Commands::Tflint { dir, rules } => {
+                assert_eq!(dir, ".");
+                assert!(rules);
+            }
+            _ => panic!("Expected Tflint command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_storage_layout() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--partitioner",
+            "hourly",
+            "--path-format",
+            "'dt'=YYYY-MM-dd/'hour'=HH",
+            "--timezone",
+            "Europe/Berlin",
+            "--locale",
+            "de-DE",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Generate {
+                partitioner,
+                partition_fields,
+                path_format,
+                locale,
+                timezone,
+                ..
+            } => {
+                assert_eq!(partitioner.as_deref(), Some("hourly"));
+                assert!(partition_fields.is_empty());
+                assert_eq!(path_format.as_deref(), Some("'dt'=YYYY-MM-dd/'hour'=HH"));
+                assert_eq!(locale.as_deref(), Some("de-DE"));
+                assert_eq!(timezone.as_deref(), Some("Europe/Berlin"));
+            }
+            _ => panic!("Expected Generate command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--partition-fields",
+            "region,country",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Generate {
+                partition_fields, ..
+            } => assert_eq!(partition_fields, vec!["region", "country"]),
+            _ => panic!("Expected Generate command"),
+        }
+        assert!(
+            Cli::try_parse_from(["connect-util", "generate", "--partitioner", "weekly"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_debezium_signals() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--heartbeat-interval",
+            "5000",
+            "--heartbeat-table",
+            "public.debezium_heartbeat",
+            "--signal-table",
+            "public.debezium_signal",
+            "--snapshot-chunk-size",
+            "2048",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Generate {
+                heartbeat_interval,
+                heartbeat_table,
+                signal_table,
+                snapshot_chunk_size,
+                ..
+            } => {
+                assert_eq!(heartbeat_interval, Some(5000));
+                assert_eq!(
+                    heartbeat_table.as_deref(),
+                    Some("public.debezium_heartbeat")
+                );
+                assert_eq!(signal_table.as_deref(), Some("public.debezium_signal"));
+                assert_eq!(snapshot_chunk_size, Some(2048));
+            }
+            _ => panic!("Expected Generate command"),
+        }
+
+        assert!(Cli::try_parse_from(
+            ["connect-util", "generate", "--snapshot-chunk-size", "2048",]
+        )
+        .is_err());
+        assert!(
+            Cli::try_parse_from(["connect-util", "generate", "--heartbeat-interval", "10s",])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_record_schema() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--schema-subject",
+            "refunds-value",
+            "--schema-registry-url",
+            "https://psrc-123.confluent.cloud",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Generate {
+                schema_file,
+                schema_subject,
+                schema_registry_url,
+                ..
+            } => {
+                assert_eq!(schema_file, None);
+                assert_eq!(schema_subject.as_deref(), Some("refunds-value"));
+                assert_eq!(
+                    schema_registry_url.as_deref(),
+                    Some("https://psrc-123.confluent.cloud")
+                );
+            }
+            _ => panic!("Expected Generate command"),
+        }
+
+        assert!(Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--schema-file",
+            "refund.avsc",
+            "--schema-subject",
+            "refunds-value",
+        ])
+        .is_err());
+        assert!(Cli::try_parse_from([
+            "connect-util",
+            "generate",
+            "--schema-registry-url",
+            "https://psrc-123.confluent.cloud",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_order_command() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "order",
+            "--dir",
+            "infra",
+            "--format",
+            "json",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Order { dir, format } => {
+                assert_eq!(dir, "infra");
+                assert_eq!(format, "json");
+            }
+            _ => panic!("Expected Order command"),
+        }
+        assert!(Cli::try_parse_from(["connect-util", "order", "--format", "sarif"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_where_used_command() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "where-used",
+            "--topic",
+            "orders.v1",
+            "--secret-key",
+            "database.password",
+            "--dir",
+            "infra",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::WhereUsed {
+                topic,
+                secret_key,
+                dir,
+                format,
+            } => {
+                assert_eq!(topic.as_deref(), Some("orders.v1"));
+                assert_eq!(secret_key.as_deref(), Some("database.password"));
+                assert_eq!(dir, "infra");
+                assert_eq!(format, "text");
+            }
+            _ => panic!("Expected WhereUsed command"),
+        }
+        assert!(Cli::try_parse_from(["connect-util", "where-used", "--dir", "infra"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_rotate_secret_command() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "rotate-secret",
+            "--key",
+            "kafka.api.key",
+            "--to",
+            "var.kafka_api_key_v2",
+            "--dry-run",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::RotateSecret {
+                key,
+                to,
+                dir,
+                dry_run,
+            } => {
+                assert_eq!(key, "kafka.api.key");
+                assert_eq!(to.as_deref(), Some("var.kafka_api_key_v2"));
+                assert_eq!(dir, ".");
+                assert!(dry_run);
+            }
+            _ => panic!("Expected RotateSecret command"),
+        }
+        assert!(Cli::try_parse_from(["connect-util", "rotate-secret"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_explain_command() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "explain",
+            "PostgresCdcSourceV2",
+            "snapshot.mode",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Explain { connector, key } => {
+                assert_eq!(connector, "PostgresCdcSourceV2");
+                assert_eq!(key, "snapshot.mode");
+            }
+            _ => panic!("Expected Explain command"),
+        }
+        assert!(Cli::try_parse_from(["connect-util", "explain", "PostgresCdcSourceV2"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_selftest_command() {
+        let cli = Cli::try_parse_from(["connect-util", "selftest"]).unwrap();
+        match cli.command {
+            Commands::Selftest { connector } => assert_eq!(connector, None),
+            _ => panic!("Expected Selftest command"),
+        }
+
+        let cli =
+            Cli::try_parse_from(["connect-util", "selftest", "--connector", "S3_SINK"]).unwrap();
+        match cli.command {
+            Commands::Selftest { connector } => assert_eq!(connector.as_deref(), Some("S3_SINK")),
+            _ => panic!("Expected Selftest command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_catalog_commands() {
+        let cli =
+            Cli::try_parse_from(["connect-util", "catalog", "diff", "v1.json", "v2.json"]).unwrap();
+        match cli.command {
+            Commands::Catalog {
+                command: CatalogCommands::Diff { old, new, format },
+            } => {
+                assert_eq!(old, "v1.json");
+                assert_eq!(new, "v2.json");
+                assert_eq!(format, "text");
+            }
+            _ => panic!("Expected catalog diff command"),
+        }
 
-#[derive(Parser)]
-#[command(name = "connect-util")]
-#[command(about = "Interactive Kafka Connect Connector Terraform Generator")]
-#[command(version = "0.1.0")]
-struct Cli {
-    #[command(subcommand)]
-    command: Commands,
-}
+        let cli = Cli::try_parse_from(["connect-util", "catalog", "export", "-o", "catalog.json"])
+            .unwrap();
+        match cli.command {
+            Commands::Catalog {
+                command: CatalogCommands::Export { output },
+            } => assert_eq!(output, Some("catalog.json".to_string())),
+            _ => panic!("Expected catalog export command"),
+        }
 
-#[derive(Subcommand)]
-enum Commands {
-    /// Generate Terraform configuration interactively
-    Generate {
-        /// Connector name (optional - will prompt if not provided)
-        #[arg(short, long)]
-        name: Option<String>,
+        assert!(Cli::try_parse_from(["connect-util", "catalog", "diff", "v1.json"]).is_err());
 
-        /// Output file path (optional - will prompt if not provided)
-        #[arg(short, long)]
-        output: Option<String>,
-    },
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "catalog",
+            "diff",
+            "--format",
+            "markdown",
+            "v1.json",
+            "v2.json",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Catalog {
+                command: CatalogCommands::Diff { format, .. },
+            } => assert_eq!(format, "markdown"),
+            _ => panic!("Expected catalog diff command"),
+        }
 
-    /// Validate a connector configuration
-    Validate {
-        /// Connector configuration file
-        #[arg(short, long)]
-        config_file: String,
-    },
+        let cli =
+            Cli::try_parse_from(["connect-util", "catalog", "stats", "--format", "json"]).unwrap();
+        match cli.command {
+            Commands::Catalog {
+                command: CatalogCommands::Stats { bundle, format },
+            } => {
+                assert_eq!(bundle, None);
+                assert_eq!(format, "json");
+            }
+            _ => panic!("Expected catalog stats command"),
+        }
+        assert!(
+            Cli::try_parse_from(["connect-util", "catalog", "stats", "--format", "csv"]).is_err()
+        );
 
-    /// List available connector plugins
-    ListPlugins {
-        /// Filter by connector type (source, sink)
-        #[arg(short, long)]
-        r#type: Option<String>,
-    },
-}
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "catalog",
+            "enrich",
+            "docs-metadata.json",
+            "-o",
+            "enriched.json",
+            "--overwrite",
+        ])
+        .unwrap();
+        assert!(!writes_stdout(&cli.command));
+        match cli.command {
+            Commands::Catalog {
+                command:
+                    CatalogCommands::Enrich {
+                        metadata,
+                        bundle,
+                        output,
+                        overwrite,
+                        format,
+                    },
+            } => {
+                assert_eq!(metadata, "docs-metadata.json");
+                assert_eq!(bundle, None);
+                assert_eq!(output, Some("enriched.json".to_string()));
+                assert!(overwrite);
+                assert_eq!(format, "text");
+            }
+            _ => panic!("Expected catalog enrich command"),
+        }
+        let cli = Cli::try_parse_from(["connect-util", "catalog", "enrich", "m.json", "-o", "-"])
+            .unwrap();
+        assert!(writes_stdout(&cli.command));
+    }
 
-#[cfg(not(tarpaulin_include))]
-#[tokio::main]
-async fn main() -> Result<(), ConnectUtilError> {
-    tracing_subscriber::fmt::init();
+    #[test]
+    fn test_cli_parsing_init_command() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "init",
+            "--dir",
+            "connectors",
+            "--environments",
+            "dev,staging,prod",
+            "--ci",
+            "gitlab",
+            "--force",
+        ])
+        .unwrap();
 
-    let cli = Cli::parse();
+        match cli.command {
+            Commands::Init {
+                dir,
+                environments,
+                ci,
+                force,
+            } => {
+                assert_eq!(dir, "connectors");
+                assert_eq!(environments, vec!["dev", "staging", "prod"]);
+                assert_eq!(ci, "gitlab");
+                assert!(force);
+            }
+            _ => panic!("Expected Init command"),
+        }
 
-    let mut app = ConnectUtilApp::new().await?;
+        let cli = Cli::try_parse_from(["connect-util", "init"]).unwrap();
+        match cli.command {
+            Commands::Init {
+                dir,
+                environments,
+                ci,
+                force,
+            } => {
+                assert_eq!(dir, ".");
+                assert_eq!(environments, vec!["dev", "prod"]);
+                assert_eq!(ci, "github");
+                assert!(!force);
+            }
+            _ => panic!("Expected Init command"),
+        }
+        assert!(Cli::try_parse_from(["connect-util", "init", "--ci", "jenkins"]).is_err());
+    }
 
-    match cli.command {
-        Commands::Generate { name, output } => {
-            info!("Starting interactive Terraform generation");
-            let options = ConnectorOptions { name, output };
-            app.generate_terraform_interactive(options).await?;
+    #[test]
+    fn test_cli_parsing_scan_command() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "scan",
+            "--dir",
+            "infra",
+            "--format",
+            "sarif",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Scan { dir, format } => {
+                assert_eq!(dir, "infra");
+                assert_eq!(format, "sarif");
+            }
+            _ => panic!("Expected Scan command"),
         }
 
-        Commands::Validate { config_file } => {
-            info!("Validating connector configuration");
-            app.validate_connector(&config_file).await?;
+        let cli = Cli::try_parse_from(["connect-util", "scan"]).unwrap();
+        match cli.command {
+            Commands::Scan { dir, format } => {
+                assert_eq!(dir, ".");
+                assert_eq!(format, "text");
+            }
+            _ => panic!("Expected Scan command"),
         }
+    }
 
-        Commands::ListPlugins { r#type } => {
-            info!("Listing available connector plugins");
-            app.list_plugins(r#type).await?;
+    #[test]
+    fn test_cli_parsing_gen_docs_command() {
+        let cli = Cli::try_parse_from(["connect-util", "gen-docs", "--out-dir", "site"]).unwrap();
+        match cli.command {
+            Commands::GenDocs { out_dir } => assert_eq!(out_dir, "site"),
+            _ => panic!("Expected GenDocs command"),
         }
     }
 
-    Ok(())
-}
+    #[test]
+    fn test_cli_reference_covers_every_command() {
+        let command = Cli::command();
+        let reference = docs::markdown_reference(&command);
+        for sub in command.get_subcommands() {
+            let heading = format!("## `connect-util {}`", sub.get_name());
+            assert_eq!(
+                reference.contains(&heading),
+                !sub.is_hide_set(),
+                "{}",
+                heading
+            );
+            if sub.is_hide_set() {
+                continue;
+            }
+            for arg in sub.get_arguments() {
+                if let Some(long) = arg.get_long() {
+                    assert!(reference.contains(&format!("--{}", long)), "--{}", long);
+                }
+            }
+        }
+        assert!(reference.contains("`--no-emoji`"));
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use clap::CommandFactory;
+    #[test]
+    fn test_cli_parsing_output_flags() {
+        let cli =
+            Cli::try_parse_from(["connect-util", "scan", "--no-emoji", "--no-color"]).unwrap();
+        assert!(cli.no_emoji);
+        assert!(cli.no_color);
+
+        let cli = Cli::try_parse_from(["connect-util", "--no-emoji", "list-plugins"]).unwrap();
+        assert!(cli.no_emoji);
+        assert!(!cli.no_color);
+        assert!(cli.definitions.is_none());
+
+        let cli = Cli::try_parse_from(["connect-util", "validate", "-d", "infra", "-q"]).unwrap();
+        assert!(cli.quiet);
+
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "describe",
+            "AcmeSink",
+            "--definitions",
+            "defs",
+        ])
+        .unwrap();
+        assert_eq!(cli.definitions, Some("defs".to_string()));
+        assert!(!cli.timings);
+        assert!(cli.lang.is_none());
+
+        let cli =
+            Cli::try_parse_from(["connect-util", "describe", "S3_SINK", "--lang", "es"]).unwrap();
+        assert_eq!(cli.lang, Some("es".to_string()));
+    }
 
     #[test]
-    fn test_cli_parsing_generate_command() {
+    fn test_cli_parsing_timings() {
         let cli = Cli::try_parse_from([
             "connect-util",
-            "generate",
-            "--name",
-            "test-connector",
-            "--output",
-            "test-output.tf",
+            "validate",
+            "-d",
+            "infra",
+            "--timings",
+            "--profile-output",
+            "profile.json",
+        ])
+        .unwrap();
+        assert!(cli.timings);
+        assert_eq!(cli.profile_output, Some("profile.json".to_string()));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("profile.json");
+        write_profile(path.to_str().unwrap(), &TimingReport::default()).unwrap();
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written["phases"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_cli_parsing_validate_command() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "validate",
+            "--config-file",
+            "test-config.tf",
         ])
         .unwrap();
 
         match cli.command {
-            Commands::Generate { name, output } => {
-                assert_eq!(name, Some("test-connector".to_string()));
-                assert_eq!(output, Some("test-output.tf".to_string()));
+            Commands::Validate {
+                config_file, dir, ..
+            } => {
+                assert_eq!(config_file.as_deref(), Some("test-config.tf"));
+                assert_eq!(dir, None);
             }
-            _ => panic!("Expected Generate command"),
+            _ => panic!("Expected Validate command"),
         }
     }
 
     #[test]
-    fn test_cli_parsing_generate_command_minimal() {
-        let cli = Cli::try_parse_from(["connect-util", "generate"]).unwrap();
+    fn test_cli_parsing_validate_remote() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "validate",
+            "--dir",
+            "infra",
+            "--remote",
+            "--environment",
+            "env-123",
+            "--cluster",
+            "lkc-456",
+            "--concurrency",
+            "8",
+            "--debug-dir",
+            "debug",
+        ])
+        .unwrap();
 
         match cli.command {
-            Commands::Generate { name, output } => {
-                assert_eq!(name, None);
-                assert_eq!(output, None);
+            Commands::Validate {
+                remote,
+                environment,
+                cluster,
+                concurrency,
+                debug_dir,
+                ..
+            } => {
+                assert!(remote);
+                assert_eq!(environment.as_deref(), Some("env-123"));
+                assert_eq!(cluster.as_deref(), Some("lkc-456"));
+                assert_eq!(concurrency, 8);
+                assert_eq!(debug_dir.as_deref(), Some("debug"));
             }
-            _ => panic!("Expected Generate command"),
+            _ => panic!("Expected Validate command"),
         }
+        assert!(Cli::try_parse_from([
+            "connect-util",
+            "validate",
+            "--dir",
+            "infra",
+            "--debug-dir",
+            "d"
+        ])
+        .is_err());
+
+        assert!(
+            Cli::try_parse_from(["connect-util", "validate", "--dir", "infra", "--remote"])
+                .is_err()
+        );
     }
 
     #[test]
-    fn test_cli_parsing_validate_command() {
+    fn test_cli_parsing_validate_profile() {
         let cli = Cli::try_parse_from([
             "connect-util",
             "validate",
-            "--config-file",
-            "test-config.tf",
+            "--dir",
+            "infra",
+            "--profile",
+            "pre-merge",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Validate { profile, .. } => {
+                assert_eq!(profile.as_deref(), Some("pre-merge"));
+            }
+            _ => panic!("Expected Validate command"),
+        }
+        assert!(Cli::try_parse_from([
+            "connect-util",
+            "validate",
+            "--dir",
+            "infra",
+            "--profile",
+            "release",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_validate_connect_url() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "validate",
+            "--dir",
+            "infra",
+            "--connect-url",
+            "http://connect:8083",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Validate {
+                connect_url,
+                remote,
+                ..
+            } => {
+                assert_eq!(connect_url.as_deref(), Some("http://connect:8083"));
+                assert!(!remote);
+            }
+            _ => panic!("Expected Validate command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_validate_interactive() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "validate",
+            "--dir",
+            "infra",
+            "--interactive",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Validate {
+                dir, interactive, ..
+            } => {
+                assert_eq!(dir.as_deref(), Some("infra"));
+                assert!(interactive);
+            }
+            _ => panic!("Expected Validate command"),
+        }
+        assert!(Cli::try_parse_from([
+            "connect-util",
+            "validate",
+            "--dir",
+            "infra",
+            "--interactive",
+            "--connect-url",
+            "http://connect:8083",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_validate_dir() {
+        let cli = Cli::try_parse_from(["connect-util", "validate", "--dir", "infra"]).unwrap();
+
+        match cli.command {
+            Commands::Validate {
+                config_file,
+                dir,
+                format,
+                notify_webhook,
+                ..
+            } => {
+                assert_eq!(config_file, None);
+                assert_eq!(dir.as_deref(), Some("infra"));
+                assert_eq!(format, "text");
+                assert_eq!(notify_webhook, None);
+            }
+            _ => panic!("Expected Validate command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "validate",
+            "--dir",
+            "infra",
+            "--format",
+            "markdown",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Validate { format, .. } => assert_eq!(format, "markdown"),
+            _ => panic!("Expected Validate command"),
+        }
+        assert!(Cli::try_parse_from([
+            "connect-util",
+            "validate",
+            "-d",
+            "infra",
+            "--format",
+            "json"
+        ])
+        .is_ok());
+
+        assert!(Cli::try_parse_from(["connect-util", "validate"]).is_err());
+        assert!(
+            Cli::try_parse_from(["connect-util", "validate", "-c", "main.tf", "-d", "infra"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_parsing_stdio() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "validate",
+            "-",
+            "--stdin-filename",
+            "infra/main.tf",
+        ])
+        .unwrap();
+        assert_eq!(cli.stdin_filename.as_deref(), Some("infra/main.tf"));
+        match cli.command {
+            Commands::Validate {
+                path, config_file, ..
+            } => {
+                assert_eq!(path.as_deref(), Some("-"));
+                assert_eq!(config_file, None);
+            }
+            _ => panic!("Expected Validate command"),
+        }
+        assert!(Cli::try_parse_from(["connect-util", "validate", "-", "-c", "main.tf"]).is_err());
+        assert!(Cli::try_parse_from(["connect-util", "validate", "-", "--check-stale"]).is_ok());
+        assert!(Cli::try_parse_from([
+            "connect-util",
+            "validate",
+            "main.tf",
+            "--check-stale",
+            "--interactive"
+        ])
+        .is_err());
+
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "convert",
+            "--from-properties",
+            "-",
+            "-o",
+            "-",
+        ])
+        .unwrap();
+        assert!(writes_stdout(&cli.command));
+        let cli = Cli::try_parse_from(["connect-util", "catalog", "export"]).unwrap();
+        assert!(!writes_stdout(&cli.command));
+        let cli = Cli::try_parse_from(["connect-util", "catalog", "export", "-o", "-"]).unwrap();
+        assert!(writes_stdout(&cli.command));
+        let cli = Cli::try_parse_from(["connect-util", "scan", "--dir", "-"]).unwrap();
+        assert!(!writes_stdout(&cli.command));
+    }
+
+    #[test]
+    fn test_cli_parsing_validate_notify_webhook() {
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "validate",
+            "--dir",
+            "infra",
+            "--notify-webhook",
+            "https://alerts.example.com/hook",
+            "--notify-format",
+            "slack",
         ])
         .unwrap();
 
         match cli.command {
-            Commands::Validate { config_file } => {
-                assert_eq!(config_file, "test-config.tf");
+            Commands::Validate {
+                notify_webhook,
+                notify_format,
+                ..
+            } => {
+                assert_eq!(
+                    notify_webhook.as_deref(),
+                    Some("https://alerts.example.com/hook")
+                );
+                assert_eq!(notify_format.as_deref(), Some("slack"));
             }
             _ => panic!("Expected Validate command"),
         }
+
+        // A payload format without a webhook is a mistake
+        assert!(Cli::try_parse_from([
+            "connect-util",
+            "validate",
+            "--dir",
+            "infra",
+            "--notify-format",
+            "json",
+        ])
+        .is_err());
     }
 
     #[test]
@@ -217,7 +3491,7 @@ mod tests {
         .unwrap();
 
         match cli.command {
-            Commands::Generate { name, output } => {
+            Commands::Generate { name, output, .. } => {
                 assert_eq!(name, Some("test-connector".to_string()));
                 assert_eq!(output, Some("test.tf".to_string()));
             }
@@ -231,8 +3505,11 @@ mod tests {
             Cli::try_parse_from(["connect-util", "validate", "-c", "test-config.tf"]).unwrap();
 
         match cli.command {
-            Commands::Validate { config_file } => {
-                assert_eq!(config_file, "test-config.tf");
+            Commands::Validate {
+                config_file, dir, ..
+            } => {
+                assert_eq!(config_file.as_deref(), Some("test-config.tf"));
+                assert_eq!(dir, None);
             }
             _ => panic!("Expected Validate command"),
         }
@@ -250,6 +3527,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parsing_describe_command() {
+        let cli = Cli::try_parse_from(["connect-util", "describe", "PostgresSink"]).unwrap();
+
+        match cli.command {
+            Commands::Describe { connector, format } => {
+                assert_eq!(connector, "PostgresSink");
+                assert_eq!(format, "text");
+            }
+            _ => panic!("Expected Describe command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "connect-util",
+            "describe",
+            "--format",
+            "json-schema",
+            "PostgresSink",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Describe { format, .. } => assert_eq!(format, "json-schema"),
+            _ => panic!("Expected Describe command"),
+        }
+        assert!(
+            Cli::try_parse_from(["connect-util", "describe", "--format", "yaml", "X"]).is_err()
+        );
+    }
+
     #[test]
     fn test_cli_version() {
         let cli = Cli::command();
@@ -0,0 +1,394 @@
+use crate::edit::TerraformFile;
+use crate::error::ConnectUtilError;
+use crate::parser::parse_body;
+use crate::schema_registry;
+use crate::terraform::TerraformGenerator;
+use crate::types::ConnectorDefinition;
+use hcl::{Block, Body, Expression, Object, ObjectKey, Structure};
+
+/// Config blocks whose values are merged key by key
+const CONFIG_ATTRIBUTES: &[&str] = &["config_nonsensitive", "config_sensitive"];
+
+/// Keys any connector may carry without its definition declaring them
+const PLATFORM_PREFIXES: &[&str] = &["kafka.", "transforms", "predicates", "errors."];
+
+/// A key in the existing config that the connector definition doesn't declare
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownKey {
+    pub key: String,
+    /// A newly generated key that looks like its replacement
+    pub renamed_to: Option<String>,
+}
+
+/// What merging a regenerated connector into an existing file changed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MergeReport {
+    /// Resource address that was merged, e.g. `confluent_connector.orders`
+    pub address: String,
+    /// Generated keys the existing config didn't have, such as newly required settings
+    pub added: Vec<String>,
+    /// Filled-in values kept instead of the generated ones
+    pub kept: usize,
+    /// Keys the connector definition no longer declares; they are kept as they are
+    pub unknown: Vec<UnknownKey>,
+    /// Other generated blocks, such as outputs, appended because the file lacked them
+    pub appended: Vec<String>,
+}
+
+/// Merges a freshly generated connector into the matching resource of `existing`.
+/// Values the user filled in win over generated ones; generated keys that are
+/// missing or still placeholders are taken from the new config. Only changed
+/// entries are rewritten, so comments and formatting elsewhere are kept
+pub fn merge_connector(
+    existing: &str,
+    generated: &str,
+) -> Result<(String, MergeReport), ConnectUtilError> {
+    let existing_body: Body = parse_body(existing).map_err(|e| {
+        ConnectUtilError::Terraform(format!("Failed to parse existing file: {}", e))
+    })?;
+    let generated_body: Body = parse_body(generated).map_err(|e| {
+        ConnectUtilError::Terraform(format!("Failed to parse generated config: {}", e))
+    })?;
+
+    let generated_resource = generated_body
+        .blocks()
+        .find(|block| is_connector_resource(block, None))
+        .ok_or_else(|| {
+            ConnectUtilError::Terraform("Generated config has no connector resource".to_string())
+        })?;
+    let label = generated_resource.labels()[1].as_str().to_string();
+    let address = format!("confluent_connector.{}", label);
+    let existing_resource = existing_body
+        .blocks()
+        .find(|block| is_connector_resource(block, Some(&label)))
+        .ok_or_else(|| {
+            ConnectUtilError::Config(format!(
+                "{} not found in the file to merge into (use --append to add it)",
+                address
+            ))
+        })?;
+
+    let mut report = MergeReport {
+        address: address.clone(),
+        ..Default::default()
+    };
+    let merged = merge_resource(existing_resource, generated_resource, &mut report)?;
+
+    // Edit the existing file in place so comments and alignment survive
+    let mut file = TerraformFile::parse(existing)?;
+    for name in CONFIG_ATTRIBUTES {
+        let before = config_object(existing_resource, name, &address)?;
+        for (key, value) in config_object(&merged, name, &address)? {
+            if before.get(&key) != Some(&value) {
+                file.set_config(&label, name, &key_name(&key), &render(&value)?)?;
+            }
+        }
+    }
+    // Settings merge_resource pushed, other than the config maps handled above
+    let added = merged
+        .body()
+        .iter()
+        .skip(existing_resource.body().iter().count())
+        .filter(|structure| {
+            structure
+                .as_attribute()
+                .is_none_or(|attribute| !CONFIG_ATTRIBUTES.contains(&attribute.key()))
+        });
+    for structure in added {
+        file.push_to_connector(&label, &render(&Body::from(vec![structure.clone()]))?)?;
+    }
+
+    // Companions, outputs, and import blocks the file doesn't have yet go at the end
+    for block in generated_body.blocks() {
+        if is_connector_resource(block, None) || has_block(&existing_body, block) {
+            continue;
+        }
+        file.append(&render(&Body::builder().add_block(block.clone()).build())?)?;
+        let labels: Vec<&str> = block.labels().iter().map(|l| l.as_str()).collect();
+        report.appended.push(
+            std::iter::once(block.identifier())
+                .chain(labels)
+                .collect::<Vec<_>>()
+                .join("."),
+        );
+    }
+    let mut content = file.to_string();
+    if !content.ends_with('\n') {
+        content.push('\n');
+    }
+    Ok((content, report))
+}
+
+fn render<T: hcl::format::Format>(value: &T) -> Result<String, ConnectUtilError> {
+    hcl::format::to_string(value)
+        .map_err(|e| ConnectUtilError::Terraform(format!("Failed to serialize HCL: {}", e)))
+}
+
+fn is_connector_resource(block: &Block, label: Option<&str>) -> bool {
+    let labels = block.labels();
+    block.identifier() == "resource"
+        && labels.len() >= 2
+        && labels[0].as_str() == "confluent_connector"
+        && label.is_none_or(|label| labels[1].as_str() == label)
+}
+
+fn has_block(body: &Body, wanted: &Block) -> bool {
+    body.blocks().any(|block| {
+        block.identifier() == wanted.identifier()
+            && (block.labels() == wanted.labels() || wanted.identifier() == "locals")
+    })
+}
+
+fn merge_resource(
+    existing: &Block,
+    generated: &Block,
+    report: &mut MergeReport,
+) -> Result<Block, ConnectUtilError> {
+    // Without a catalog entry there's nothing to tell removed keys from user additions
+    let connector = match config_object(generated, "config_nonsensitive", "generated")?
+        .iter()
+        .find(|(key, _)| key_name(key) == "connector.class")
+        .map(|(_, value)| value)
+    {
+        Some(Expression::String(class)) => ConnectorDefinition::get_connector_by_name(class),
+        _ => None,
+    };
+    let mut merged = existing.clone();
+    for name in CONFIG_ATTRIBUTES {
+        let generated_config = config_object(generated, name, "generated")?;
+        let existing_config = existing
+            .body()
+            .attributes()
+            .find(|attribute| attribute.key() == *name)
+            .map(|_| config_object(existing, name, &report.address))
+            .transpose()?;
+        let config = merge_config(
+            existing_config.unwrap_or_default(),
+            generated_config,
+            connector.as_ref(),
+            report,
+        );
+        set_attribute(&mut merged, name, Expression::Object(config));
+    }
+
+    // Settings added to the generator since the file was written, e.g. lifecycle
+    for structure in generated.body().iter() {
+        let present = match structure {
+            Structure::Attribute(attribute) => merged
+                .body()
+                .attributes()
+                .any(|a| a.key() == attribute.key()),
+            Structure::Block(block) => merged
+                .body()
+                .blocks()
+                .any(|b| b.identifier() == block.identifier()),
+        };
+        if !present {
+            merged.body.0.push(structure.clone());
+        }
+    }
+    Ok(merged)
+}
+
+fn config_object(
+    block: &Block,
+    name: &str,
+    owner: &str,
+) -> Result<Object<ObjectKey, Expression>, ConnectUtilError> {
+    match block
+        .body()
+        .attributes()
+        .find(|attribute| attribute.key() == name)
+        .map(|attribute| attribute.expr())
+    {
+        Some(Expression::Object(object)) => Ok(object.clone()),
+        None => Ok(Object::new()),
+        Some(_) => Err(ConnectUtilError::Terraform(format!(
+            "Can't merge {} of {}: it isn't a literal map",
+            name, owner
+        ))),
+    }
+}
+
+fn set_attribute(block: &mut Block, name: &str, expression: Expression) {
+    for structure in block.body.iter_mut() {
+        if let Structure::Attribute(attribute) = structure {
+            if attribute.key() == name {
+                attribute.expr = expression;
+                return;
+            }
+        }
+    }
+    block
+        .body
+        .0
+        .push(Structure::Attribute(hcl::Attribute::new(name, expression)));
+}
+
+fn key_name(key: &ObjectKey) -> String {
+    match key {
+        ObjectKey::Identifier(identifier) => identifier.as_str().to_string(),
+        ObjectKey::Expression(Expression::String(name)) => name.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn is_placeholder(expression: &Expression) -> bool {
+    matches!(expression, Expression::String(value) if value.contains("<REPLACE_WITH_"))
+}
+
+fn merge_config(
+    existing: Object<ObjectKey, Expression>,
+    generated: Object<ObjectKey, Expression>,
+    connector: Option<&ConnectorDefinition>,
+    report: &mut MergeReport,
+) -> Object<ObjectKey, Expression> {
+    let existing: Vec<(String, ObjectKey, Expression)> = existing
+        .into_iter()
+        .map(|(key, value)| (key_name(&key), key, value))
+        .collect();
+    let generated_names: Vec<String> = generated.keys().map(key_name).collect();
+
+    let mut merged = Object::new();
+    let mut added = Vec::new();
+    for (key, value) in generated {
+        let name = key_name(&key);
+        match existing
+            .iter()
+            .find(|(existing_name, _, _)| *existing_name == name)
+        {
+            Some((_, _, current)) if !is_placeholder(current) => {
+                if *current != value {
+                    report.kept += 1;
+                }
+                merged.insert(key, current.clone());
+            }
+            Some(_) => {
+                merged.insert(key, value);
+            }
+            None => {
+                added.push(name);
+                merged.insert(key, value);
+            }
+        }
+    }
+
+    for (name, key, value) in existing {
+        if generated_names.contains(&name) {
+            continue;
+        }
+        let declared = connector.is_none_or(|connector| {
+            connector.has_field(&name) || connector.sensitive_configs.contains(&name)
+        }) || schema_registry::CREDENTIAL_KEYS.contains(&name.as_str())
+            || PLATFORM_PREFIXES
+                .iter()
+                .any(|prefix| name.starts_with(prefix));
+        if !declared {
+            let last_segment = |key: &str| key.rsplit('.').next().unwrap_or(key).to_string();
+            let renamed_to = added
+                .iter()
+                .find(|candidate| last_segment(candidate) == last_segment(&name))
+                .cloned();
+            report.unknown.push(UnknownKey {
+                key: name,
+                renamed_to,
+            });
+        }
+        merged.insert(key, value);
+    }
+    report.added.extend(added);
+    TerraformGenerator::sorted_config(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXISTING: &str = r#"# Orders CDC
+variable "status" {}
+
+resource "confluent_connector" "orders" {
+  status = var.status
+
+  config_sensitive = {
+    "database.password" = var.orders_db_password
+  }
+  config_nonsensitive = {
+    "connector.class"      = "PostgresCdcSourceV2"
+    "name"                 = "orders"
+    "database.hostname"    = "db.internal" # primary
+    "database.user"        = "<REPLACE_WITH_DATABASE_USER>"
+    "database.server.name" = "orders"
+    "snapshot.mode"        = "never"
+  }
+}
+
+# trailing comment
+"#;
+
+    const GENERATED: &str = r#"resource "confluent_connector" "orders" {
+  status = var.status
+  config_sensitive = {
+    "database.password" = "<REPLACE_WITH_ACTUAL_VALUE>"
+  }
+  config_nonsensitive = {
+    "connector.class" = "PostgresCdcSourceV2"
+    "name" = "orders"
+    "database.hostname" = "<REPLACE_WITH_DATABASE_HOST>"
+    "database.user" = "<REPLACE_WITH_DATABASE_USER>"
+    "database.dbname" = "<REPLACE_WITH_DATABASE_NAME>"
+    "topic.prefix" = "<REPLACE_WITH_TOPIC_PREFIX>"
+    "snapshot.mode" = "initial"
+  }
+  lifecycle {
+    ignore_changes = ["config_nonsensitive[\"kafka.deployment.type\"]"]
+  }
+}
+
+output "connector_id" {
+  value = confluent_connector.orders.id
+}
+"#;
+
+    #[test]
+    fn test_merge_connector_keeps_filled_in_values() {
+        let (merged, report) = merge_connector(EXISTING, GENERATED).unwrap();
+
+        assert!(merged.starts_with("# Orders CDC\nvariable \"status\" {}\n\n"));
+        assert!(merged.contains("\"database.password\" = var.orders_db_password"));
+        assert!(merged.contains("\"database.hostname\"    = \"db.internal\" # primary"));
+        assert!(merged.contains("\"snapshot.mode\"        = \"never\""));
+        assert!(merged.contains("\"database.dbname\" = \"<REPLACE_WITH_DATABASE_NAME>\""));
+        assert!(merged.contains("lifecycle {"));
+        assert!(merged.contains("# trailing comment"));
+        assert!(merged
+            .trim_end()
+            .ends_with("value = confluent_connector.orders.id\n}"));
+
+        assert_eq!(report.address, "confluent_connector.orders");
+        assert_eq!(report.added, vec!["database.dbname", "topic.prefix"]);
+        assert_eq!(report.kept, 3);
+        assert_eq!(
+            report.unknown,
+            vec![UnknownKey {
+                key: "database.server.name".to_string(),
+                renamed_to: None,
+            }]
+        );
+        assert!(merged.contains("\"database.server.name\" = \"orders\""));
+        assert_eq!(report.appended, vec!["output.connector_id"]);
+
+        // Merging again changes nothing
+        let (again, report) = merge_connector(&merged, GENERATED).unwrap();
+        assert_eq!(again, merged);
+        assert!(report.added.is_empty());
+        assert!(report.appended.is_empty());
+    }
+
+    #[test]
+    fn test_merge_connector_requires_existing_resource() {
+        let other = EXISTING.replace("\"orders\" {", "\"refunds\" {");
+        let result = merge_connector(&other, GENERATED);
+        assert!(matches!(result, Err(ConnectUtilError::Config(_))));
+    }
+}
@@ -0,0 +1,152 @@
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Comment marker, as in `# connect-util:owner team-payments`
+const MARKER: &str = "connect-util:";
+
+/// Resource types that hold a connector, in any provider `generate` writes for
+const CONNECTOR_RESOURCES: &[&str] = &[
+    "confluent_connector",
+    "kafka_connect_connector",
+    "aiven_kafka_connector",
+];
+
+/// Operational metadata kept as structured comments above a connector resource
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ConnectorMetadata {
+    /// Team or person responsible for the connector
+    pub owner: Option<String>,
+    /// Where to page about it, e.g. a Slack channel
+    pub on_call: Option<String>,
+    /// When it may be paused for maintenance, e.g. `Sun 02:00-04:00 UTC`
+    pub maintenance_window: Option<String>,
+}
+
+impl ConnectorMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.fields().iter().all(|(_, value)| value.is_none())
+    }
+
+    fn fields(&self) -> [(&'static str, &Option<String>); 3] {
+        [
+            ("owner", &self.owner),
+            ("on-call", &self.on_call),
+            ("maintenance-window", &self.maintenance_window),
+        ]
+    }
+
+    fn set(&mut self, key: &str, value: String) -> bool {
+        let field = match key {
+            "owner" => &mut self.owner,
+            "on-call" => &mut self.on_call,
+            "maintenance-window" => &mut self.maintenance_window,
+            _ => return false,
+        };
+        *field = Some(value);
+        true
+    }
+
+    /// The comment lines, one per field set
+    pub fn comments(&self) -> String {
+        self.fields()
+            .iter()
+            .filter_map(|(key, value)| {
+                value
+                    .as_ref()
+                    .map(|value| format!("# {}{} {}\n", MARKER, key, value))
+            })
+            .collect()
+    }
+}
+
+/// The `type` and label of a connector resource opened on `line`
+fn connector_resource(line: &str) -> Option<(&str, &str)> {
+    let mut parts = line.trim_start().strip_prefix("resource ")?.split('"');
+    let (_, resource_type, _, label) = (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+    CONNECTOR_RESOURCES
+        .contains(&resource_type)
+        .then_some((resource_type, label))
+}
+
+/// Writes `metadata` as comments above the first connector resource in `content`
+pub fn annotate(content: &str, metadata: &ConnectorMetadata) -> String {
+    if metadata.is_empty() {
+        return content.to_string();
+    }
+    let mut out = String::new();
+    let mut annotated = false;
+    for line in content.split_inclusive('\n') {
+        if !annotated && connector_resource(line).is_some() {
+            out.push_str(&metadata.comments());
+            annotated = true;
+        }
+        out.push_str(line);
+    }
+    out
+}
+
+/// The metadata comments in a file, keyed by the label of the connector resource
+/// they sit above. Other comments may be mixed in; a blank line ends the run
+pub fn parse_metadata(content: &str) -> HashMap<String, ConnectorMetadata> {
+    let mut parsed = HashMap::new();
+    let mut pending = ConnectorMetadata::default();
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if let Some(comment) = trimmed
+            .strip_prefix('#')
+            .or_else(|| trimmed.strip_prefix("//"))
+        {
+            if let Some((key, value)) = comment
+                .trim()
+                .strip_prefix(MARKER)
+                .and_then(|rest| rest.split_once(char::is_whitespace))
+            {
+                pending.set(key, value.trim().to_string());
+            }
+            continue;
+        }
+        if let Some((_, label)) = connector_resource(line) {
+            if !pending.is_empty() {
+                parsed.insert(label.to_string(), std::mem::take(&mut pending));
+            }
+        }
+        pending = ConnectorMetadata::default();
+    }
+    parsed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotate_and_parse_metadata() {
+        let metadata = ConnectorMetadata {
+            owner: Some("team-payments".to_string()),
+            on_call: Some("#payments-oncall".to_string()),
+            maintenance_window: Some("Sun 02:00-04:00 UTC".to_string()),
+        };
+        let content = "resource \"confluent_service_account\" \"orders\" {\n}\n\nresource \"confluent_connector\" \"orders\" {\n}\n";
+        let annotated = annotate(content, &metadata);
+        assert!(annotated.contains(
+            "# connect-util:owner team-payments\n# connect-util:on-call #payments-oncall\n# connect-util:maintenance-window Sun 02:00-04:00 UTC\nresource \"confluent_connector\" \"orders\""
+        ));
+        assert_eq!(annotate(content, &ConnectorMetadata::default()), content);
+
+        let parsed = parse_metadata(&format!(
+            "{}\n# connect-util:owner data-platform\n\nresource \"confluent_connector\" \"refunds\" {{\n}}\n",
+            annotated
+        ));
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed["orders"], metadata);
+    }
+
+    #[test]
+    fn test_parse_metadata_with_other_comments() {
+        let parsed = parse_metadata(
+            "// connect-util:owner team-search\n# connect-util:ignore naming-convention legacy\n# connect-util:unknown x\nresource \"kafka_connect_connector\" \"search\" {\n}\n",
+        );
+        assert_eq!(parsed["search"].owner.as_deref(), Some("team-search"));
+        assert_eq!(parsed["search"].on_call, None);
+    }
+}
@@ -0,0 +1,366 @@
+use crate::error::ConnectUtilError;
+use crate::lint::{base_label, LintIssue};
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Findings included in a notification; the full list belongs in CI logs
+const TOP_FINDINGS: usize = 5;
+
+/// Payload shape for `--notify-webhook`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotifyFormat {
+    /// A `text` summary plus the structured counts and findings
+    Json,
+    /// Slack Block Kit message for incoming webhooks
+    Slack,
+}
+
+impl NotifyFormat {
+    /// Slack for Slack incoming-webhook URLs, generic JSON otherwise
+    pub fn for_url(url: &str) -> Self {
+        if url.contains("hooks.slack.com") {
+            NotifyFormat::Slack
+        } else {
+            NotifyFormat::Json
+        }
+    }
+}
+
+impl FromStr for NotifyFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(NotifyFormat::Json),
+            "slack" => Ok(NotifyFormat::Slack),
+            _ => Err(format!(
+                "Invalid notification format '{}'. Use 'json' or 'slack'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TopFinding {
+    pub severity: String,
+    pub rule: String,
+    pub connector: Option<String>,
+    pub file: PathBuf,
+    pub line: usize,
+    pub message: String,
+}
+
+/// Pass/fail counts and the most important findings from a validation run
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ValidationSummary {
+    pub target: String,
+    pub connectors: usize,
+    /// Connectors without error-severity findings
+    pub passed: usize,
+    pub failed: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    /// Errors first, then warnings, capped at a handful
+    pub top_findings: Vec<TopFinding>,
+    /// Why the run failed when the findings don't say, such as `--profile` or
+    /// `--remote` rejecting it
+    pub failed_check: Option<String>,
+}
+
+impl ValidationSummary {
+    pub fn from_issues(
+        target: &str,
+        issues: &[LintIssue],
+        connectors: &[(PathBuf, String)],
+    ) -> Self {
+        let all: BTreeSet<(PathBuf, String)> = connectors
+            .iter()
+            .map(|(file, label)| (file.clone(), base_label(label).to_string()))
+            .collect();
+        let failing: BTreeSet<(PathBuf, String)> = issues
+            .iter()
+            .filter(|issue| issue.severity == "error")
+            .filter_map(|issue| {
+                let label = issue.connector.as_deref()?;
+                Some((issue.file.clone(), base_label(label).to_string()))
+            })
+            .collect();
+        let failed = all.intersection(&failing).count();
+        let errors = issues
+            .iter()
+            .filter(|issue| issue.severity == "error")
+            .count();
+
+        let mut ranked: Vec<&LintIssue> = issues.iter().collect();
+        ranked.sort_by_key(|issue| issue.severity != "error");
+        let top_findings = ranked
+            .into_iter()
+            .take(TOP_FINDINGS)
+            .map(|issue| TopFinding {
+                severity: issue.severity.to_string(),
+                rule: issue.rule.name(),
+                connector: issue.connector.clone(),
+                file: issue.file.clone(),
+                line: issue.line,
+                message: issue.message.clone(),
+            })
+            .collect();
+
+        Self {
+            target: target.to_string(),
+            connectors: all.len(),
+            passed: all.len() - failed,
+            failed,
+            errors,
+            warnings: issues.len() - errors,
+            top_findings,
+            failed_check: None,
+        }
+    }
+
+    pub fn is_passing(&self) -> bool {
+        self.errors == 0 && self.failed_check.is_none()
+    }
+
+    /// One-line summary used as the message text and as Slack's notification fallback
+    pub fn headline(&self) -> String {
+        if self.is_passing() {
+            format!(
+                "✅ connect-util validation passed for {}: {} connector(s), {} warning(s)",
+                self.target, self.connectors, self.warnings
+            )
+        } else {
+            let headline = format!(
+                "❌ connect-util validation failed for {}: {} of {} connector(s) failed, {} error(s), {} warning(s)",
+                self.target, self.failed, self.connectors, self.errors, self.warnings
+            );
+            match &self.failed_check {
+                Some(check) => format!("{}; {}", headline, check),
+                None => headline,
+            }
+        }
+    }
+
+    pub fn payload(&self, format: NotifyFormat) -> serde_json::Value {
+        match format {
+            NotifyFormat::Json => json!({ "text": self.headline(), "summary": self }),
+            NotifyFormat::Slack => self.slack_blocks(),
+        }
+    }
+
+    fn slack_blocks(&self) -> serde_json::Value {
+        let mut blocks = vec![
+            json!({
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": format!("*{}*", self.headline()) },
+            }),
+            json!({
+                "type": "section",
+                "fields": [
+                    { "type": "mrkdwn", "text": format!("*Passed:* {}", self.passed) },
+                    { "type": "mrkdwn", "text": format!("*Failed:* {}", self.failed) },
+                    { "type": "mrkdwn", "text": format!("*Errors:* {}", self.errors) },
+                    { "type": "mrkdwn", "text": format!("*Warnings:* {}", self.warnings) },
+                ],
+            }),
+        ];
+        if !self.top_findings.is_empty() {
+            let lines: Vec<String> = self
+                .top_findings
+                .iter()
+                .map(|finding| {
+                    let connector = finding
+                        .connector
+                        .as_deref()
+                        .map(|c| format!("`{}` ", c))
+                        .unwrap_or_default();
+                    format!(
+                        "• *{}* {}({}:{}): {}",
+                        finding.severity,
+                        connector,
+                        finding.file.display(),
+                        finding.line,
+                        finding.message
+                    )
+                })
+                .collect();
+            blocks.push(json!({
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": lines.join("\n") },
+            }));
+        }
+        json!({ "text": self.headline(), "blocks": blocks })
+    }
+}
+
+/// Delivers a notification payload
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn send(&self, payload: &serde_json::Value) -> Result<(), ConnectUtilError>;
+}
+
+/// Posts payloads to an HTTP webhook such as a Slack incoming webhook
+pub struct WebhookNotifier {
+    http: reqwest::Client,
+    url: String,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            url: url.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn send(&self, payload: &serde_json::Value) -> Result<(), ConnectUtilError> {
+        let response = self
+            .http
+            .post(&self.url)
+            .json(payload)
+            .send()
+            .await
+            // The URL is a credential for Slack incoming webhooks, so keep it out of logs
+            .map_err(|e| {
+                ConnectUtilError::Unknown(format!("Webhook request failed: {}", e.without_url()))
+            })?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(ConnectUtilError::Config(format!(
+                "Webhook returned {}: {}",
+                status, body
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lint::LintRule;
+
+    fn issue(rule: LintRule, connector: Option<&str>, message: &str) -> LintIssue {
+        LintIssue {
+            rule,
+            severity: rule.severity(),
+            file: PathBuf::from("main.tf"),
+            line: 4,
+            connector: connector.map(str::to_string),
+            message: message.to_string(),
+        }
+    }
+
+    fn connectors() -> Vec<(PathBuf, String)> {
+        ["orders[\"eu\"]", "orders[\"us\"]", "refunds"]
+            .iter()
+            .map(|label| (PathBuf::from("main.tf"), label.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_summary_counts() {
+        let issues = vec![
+            issue(LintRule::UnusedVariable, None, "var.legacy is never used"),
+            issue(
+                LintRule::InvalidConfig,
+                Some("orders[\"eu\"]"),
+                "missing topic",
+            ),
+            issue(LintRule::Policy, Some("orders[\"us\"]"), "class denied"),
+        ];
+        let summary = ValidationSummary::from_issues("infra", &issues, &connectors());
+        assert_eq!(summary.connectors, 2);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(summary.passed, 1);
+        assert_eq!(summary.errors, 2);
+        assert_eq!(summary.warnings, 1);
+        assert_eq!(summary.top_findings[0].severity, "error");
+        assert_eq!(summary.top_findings[2].rule, "connect_util_unused_variable");
+        assert!(!summary.is_passing());
+        assert_eq!(
+            summary.headline(),
+            "❌ connect-util validation failed for infra: 1 of 2 connector(s) failed, 2 error(s), 1 warning(s)"
+        );
+    }
+
+    #[test]
+    fn test_summary_caps_top_findings() {
+        let issues: Vec<LintIssue> = (0..8)
+            .map(|i| {
+                issue(
+                    LintRule::Conflict,
+                    Some("refunds"),
+                    &format!("conflict {}", i),
+                )
+            })
+            .collect();
+        let summary = ValidationSummary::from_issues("infra", &issues, &connectors());
+        assert_eq!(summary.top_findings.len(), TOP_FINDINGS);
+        assert_eq!(summary.errors, 8);
+    }
+
+    #[test]
+    fn test_payloads() {
+        let issues = vec![issue(
+            LintRule::InvalidConfig,
+            Some("refunds"),
+            "missing topic",
+        )];
+        let summary = ValidationSummary::from_issues("infra", &issues, &connectors());
+
+        let generic = summary.payload(NotifyFormat::Json);
+        assert_eq!(generic["text"], summary.headline());
+        assert_eq!(generic["summary"]["failed"], 1);
+
+        let slack = summary.payload(NotifyFormat::Slack);
+        let blocks = slack["blocks"].as_array().unwrap();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[1]["fields"][1]["text"], "*Failed:* 1");
+        assert_eq!(
+            blocks[2]["text"]["text"],
+            "• *error* `refunds` (main.tf:4): missing topic"
+        );
+
+        let clean = ValidationSummary::from_issues("infra", &[], &connectors());
+        assert!(clean.is_passing());
+        let rejected = ValidationSummary {
+            failed_check: Some("1 finding(s) fail the strict profile".to_string()),
+            ..clean.clone()
+        };
+        assert!(!rejected.is_passing());
+        assert!(rejected
+            .headline()
+            .ends_with("0 error(s), 0 warning(s); 1 finding(s) fail the strict profile"));
+        assert_eq!(
+            clean.payload(NotifyFormat::Slack)["blocks"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_notify_format() {
+        assert_eq!(
+            NotifyFormat::for_url("https://hooks.slack.com/services/T/B/X"),
+            NotifyFormat::Slack
+        );
+        assert_eq!(
+            NotifyFormat::for_url("https://alerts.example.com/hook"),
+            NotifyFormat::Json
+        );
+        assert_eq!("Slack".parse(), Ok(NotifyFormat::Slack));
+        assert!("teams".parse::<NotifyFormat>().is_err());
+    }
+}
@@ -0,0 +1,647 @@
+use crate::conflicts::{covers, literal, ModuleConnector};
+use crate::error::ConnectUtilError;
+use crate::types::{ConnectorDefinition, ConnectorType};
+use hcl::{Body, Expression};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Output format for `order`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OrderFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for OrderFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(OrderFormat::Text),
+            "json" => Ok(OrderFormat::Json),
+            _ => Err(format!(
+                "Invalid order format '{}'. Use 'text' or 'json'",
+                s
+            )),
+        }
+    }
+}
+
+/// A `confluent_kafka_topic` and the name it creates, when that name is literal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopicResource {
+    pub address: String,
+    pub file: PathBuf,
+    pub topic_name: Option<String>,
+}
+
+/// One resource in the recommended creation order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OrderStep {
+    pub address: String,
+    pub file: PathBuf,
+    /// Resources that have to exist first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub after: Vec<String>,
+}
+
+/// A connector whose `depends_on` is missing some of the resources it needs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DependsOnPatch {
+    pub address: String,
+    pub file: PathBuf,
+    /// The full `depends_on` list to set, existing entries included
+    pub depends_on: Vec<String>,
+    /// The entries Terraform doesn't know about yet
+    pub missing: Vec<String>,
+}
+
+/// The creation order for the connectors and topics of one module
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModuleOrder {
+    pub dir: PathBuf,
+    pub steps: Vec<OrderStep>,
+    pub patches: Vec<DependsOnPatch>,
+    /// Resources on a dependency cycle, which no order can satisfy
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub cycle: Vec<String>,
+}
+
+/// References to topics and connectors, with the `data.` of a data source kept so
+/// it isn't mistaken for the resource with the same label
+fn reference_pattern() -> Regex {
+    Regex::new(
+        r"\b((?:data\.)?(?:confluent_kafka_topic|confluent_connector))\.([A-Za-z_][A-Za-z0-9_-]*)",
+    )
+    .expect("valid regex")
+}
+
+/// The `confluent_kafka_topic` resources a file declares
+pub fn topic_resources(file: &Path, body: &Body) -> Vec<TopicResource> {
+    body.blocks()
+        .filter(|block| block.identifier() == "resource")
+        .filter_map(|block| match block.labels() {
+            [kind, label, ..] if kind.as_str() == "confluent_kafka_topic" => Some(TopicResource {
+                address: format!("confluent_kafka_topic.{}", label.as_str()),
+                file: file.to_path_buf(),
+                topic_name: block
+                    .body()
+                    .attributes()
+                    .find_map(|attr| match attr.expr() {
+                        Expression::String(name) if attr.key() == "topic_name" => {
+                            Some(name.clone())
+                        }
+                        _ => None,
+                    }),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// What a connector resource already waits for
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeclaredDependencies {
+    /// Entries of its `depends_on`
+    pub depends_on: BTreeSet<String>,
+    /// Topics and connectors its config references, which Terraform orders implicitly
+    pub references: BTreeSet<String>,
+}
+
+impl DeclaredDependencies {
+    fn contains(&self, address: &str) -> bool {
+        self.depends_on.contains(address) || self.references.contains(address)
+    }
+}
+
+/// The dependencies of each connector resource in a file, keyed by its address
+pub fn declared_dependencies(body: &Body) -> BTreeMap<String, DeclaredDependencies> {
+    let pattern = reference_pattern();
+    body.blocks()
+        .filter(|block| block.identifier() == "resource")
+        .filter_map(|block| match block.labels() {
+            [kind, label, ..] if kind.as_str() == "confluent_connector" => {
+                let mut declared = DeclaredDependencies::default();
+                for attr in block.body().attributes() {
+                    match (attr.key(), attr.expr()) {
+                        ("depends_on", Expression::Array(entries)) => {
+                            declared.depends_on.extend(entries.iter().map(|entry| {
+                                hcl::format::to_string(entry)
+                                    .unwrap_or_default()
+                                    .trim()
+                                    .to_string()
+                            }));
+                        }
+                        (_, expr) => {
+                            let rendered = hcl::format::to_string(expr).unwrap_or_default();
+                            declared.references.extend(
+                                pattern
+                                    .captures_iter(&rendered)
+                                    .map(|captures| format!("{}.{}", &captures[1], &captures[2])),
+                            );
+                        }
+                    }
+                }
+                Some((format!("confluent_connector.{}", label.as_str()), declared))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// The address of a connector instance: `for_each` and `count` instances share
+/// their block's
+fn connector_address(connector: &ModuleConnector) -> String {
+    let label = connector.config.name.split('[').next().unwrap_or_default();
+    format!("confluent_connector.{}", label)
+}
+
+/// The topic names a config value lists: its literal entries, plus the names of
+/// the topic resources it references
+pub(crate) fn topic_names(value: &str, topics: &[TopicResource]) -> BTreeSet<String> {
+    let mut names: BTreeSet<String> = value
+        .split(',')
+        .filter_map(literal)
+        .map(str::to_string)
+        .collect();
+    for captures in reference_pattern().captures_iter(value) {
+        let address = format!("{}.{}", &captures[1], &captures[2]);
+        names.extend(
+            topics
+                .iter()
+                .filter(|topic| topic.address == address)
+                .filter_map(|topic| topic.topic_name.clone()),
+        );
+    }
+    names
+}
+
+fn config_topics(
+    connector: &ModuleConnector,
+    key: &str,
+    topics: &[TopicResource],
+) -> BTreeSet<String> {
+    connector
+        .config
+        .config
+        .get(key)
+        .map(|value| topic_names(value, topics))
+        .unwrap_or_default()
+}
+
+/// Whether a source's `kafka.topic` or `topic.prefix` covers `topic`
+fn produces(connector: &ModuleConnector, topic: &str, topics: &[TopicResource]) -> bool {
+    config_topics(connector, "kafka.topic", topics).contains(topic)
+        || connector
+            .config
+            .config
+            .get("topic.prefix")
+            .and_then(|p| literal(p))
+            .is_some_and(|prefix| covers(prefix, topic))
+}
+
+/// Works out the order the topics and connectors of a module should be created in:
+/// topics before the connectors that use them, and sources before the sinks that
+/// consume what they write. `declared` holds the dependencies Terraform already
+/// sees; the rest become `depends_on` suggestions. `existing` holds the addresses
+/// of connector data sources, with their files; they are read rather than
+/// created, so they come first
+pub fn plan_order(
+    dir: &Path,
+    connectors: &[ModuleConnector],
+    topics: &[TopicResource],
+    existing: &BTreeMap<String, PathBuf>,
+    declared: &BTreeMap<String, DeclaredDependencies>,
+) -> ModuleOrder {
+    // Every topic and connector in the module, with the file declaring it
+    let mut resources: BTreeMap<String, PathBuf> = existing.clone();
+    let mut needs: BTreeMap<String, BTreeSet<String>> = existing
+        .keys()
+        .map(|address| (address.clone(), BTreeSet::new()))
+        .collect();
+    for topic in topics {
+        resources.insert(topic.address.clone(), topic.file.clone());
+        needs.entry(topic.address.clone()).or_default();
+    }
+
+    let kind = |connector: &ModuleConnector| {
+        ConnectorDefinition::get_connector_by_name(&connector.config.connector_class)
+            .map(|definition| definition.connector_type)
+    };
+    let sources: Vec<&ModuleConnector> = connectors
+        .iter()
+        .filter(|c| kind(c) == Some(ConnectorType::Source))
+        .collect();
+    for connector in connectors {
+        let address = connector_address(connector);
+        resources
+            .entry(address.clone())
+            .or_insert(connector.file.clone());
+        let entry = needs.entry(address.clone()).or_default();
+        match kind(connector) {
+            Some(ConnectorType::Sink) => {
+                for topic in config_topics(connector, "topics", topics) {
+                    entry.extend(
+                        topics
+                            .iter()
+                            .filter(|t| t.topic_name.as_deref() == Some(topic.as_str()))
+                            .map(|t| t.address.clone()),
+                    );
+                    entry.extend(
+                        sources
+                            .iter()
+                            .filter(|source| produces(source, &topic, topics))
+                            .map(|source| connector_address(source)),
+                    );
+                }
+            }
+            Some(ConnectorType::Source) => {
+                entry.extend(
+                    topics
+                        .iter()
+                        .filter(|t| {
+                            t.topic_name
+                                .as_deref()
+                                .is_some_and(|name| produces(connector, name, topics))
+                        })
+                        .map(|t| t.address.clone()),
+                );
+            }
+            None => {}
+        }
+        if let Some(declared) = declared.get(&address) {
+            entry.extend(declared.depends_on.iter().cloned());
+            entry.extend(declared.references.iter().cloned());
+        }
+        entry.remove(&address);
+    }
+    // Dependencies on resources outside the module can't be ordered here
+    for dependencies in needs.values_mut() {
+        dependencies.retain(|dependency| resources.contains_key(dependency));
+    }
+
+    let mut patches = Vec::new();
+    for (address, dependencies) in &needs {
+        let Some(existing) = declared.get(address) else {
+            continue;
+        };
+        let missing: Vec<String> = dependencies
+            .iter()
+            .filter(|dependency| !existing.contains(dependency))
+            .cloned()
+            .collect();
+        if missing.is_empty() {
+            continue;
+        }
+        patches.push(DependsOnPatch {
+            address: address.clone(),
+            file: resources[address].clone(),
+            depends_on: existing
+                .depends_on
+                .iter()
+                .chain(&missing)
+                .cloned()
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+            missing,
+        });
+    }
+
+    // Kahn's algorithm, data sources and topics first and then by address, so the
+    // order is stable
+    let mut steps = Vec::new();
+    let mut remaining = needs.clone();
+    loop {
+        let mut ready: Vec<&String> = remaining
+            .iter()
+            .filter(|(_, dependencies)| dependencies.is_empty())
+            .map(|(address, _)| address)
+            .collect();
+        if ready.is_empty() {
+            break;
+        }
+        ready.sort_by_key(|address| {
+            (
+                !address.starts_with("data."),
+                !address.starts_with("confluent_kafka_topic."),
+                *address,
+            )
+        });
+        let ready: Vec<String> = ready.into_iter().cloned().collect();
+        for address in &ready {
+            remaining.remove(address);
+            steps.push(OrderStep {
+                address: address.clone(),
+                file: resources[address].clone(),
+                after: needs[address].iter().cloned().collect(),
+            });
+        }
+        for dependencies in remaining.values_mut() {
+            for address in &ready {
+                dependencies.remove(address);
+            }
+        }
+    }
+
+    ModuleOrder {
+        dir: dir.to_path_buf(),
+        steps,
+        patches,
+        cycle: remaining.into_keys().collect(),
+    }
+}
+
+/// Renders the order of each module, with the `depends_on` lines to add
+pub fn render_order(
+    orders: &[ModuleOrder],
+    format: OrderFormat,
+) -> Result<String, ConnectUtilError> {
+    if format == OrderFormat::Json {
+        return Ok(serde_json::to_string_pretty(orders)?);
+    }
+    let mut out = String::new();
+    for order in orders {
+        let _ = writeln!(out, "📦 {}", order.dir.display());
+        for (index, step) in order.steps.iter().enumerate() {
+            let file = step.file.file_name().unwrap_or_default().to_string_lossy();
+            let _ = write!(out, "  {}. {} ({})", index + 1, step.address, file);
+            if !step.after.is_empty() {
+                let _ = write!(out, ", after {}", step.after.join(", "));
+            }
+            out.push('\n');
+        }
+        if !order.cycle.is_empty() {
+            let _ = writeln!(
+                out,
+                "  ❌ Dependency cycle between {}",
+                order.cycle.join(", ")
+            );
+        }
+        for patch in &order.patches {
+            let _ = writeln!(
+                out,
+                "  🔧 {} ({}) should wait for {}:",
+                patch.address,
+                patch.file.display(),
+                patch.missing.join(", ")
+            );
+            let _ = writeln!(out, "     depends_on = [{}]", patch.depends_on.join(", "));
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_body;
+    use crate::types::ConnectorConfig;
+    use std::collections::HashMap;
+
+    fn connector(label: &str, class: &str, config: &[(&str, &str)]) -> ModuleConnector {
+        ModuleConnector {
+            file: PathBuf::from(format!("{}.tf", label)),
+            config: ConnectorConfig {
+                name: label.to_string(),
+                connector_class: class.to_string(),
+                config: config
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect::<HashMap<_, _>>(),
+                sensitive_config: HashMap::new(),
+            },
+        }
+    }
+
+    fn topic(label: &str, name: &str) -> TopicResource {
+        TopicResource {
+            address: format!("confluent_kafka_topic.{}", label),
+            file: PathBuf::from("topics.tf"),
+            topic_name: Some(name.to_string()),
+        }
+    }
+
+    fn references(addresses: &[&str]) -> DeclaredDependencies {
+        DeclaredDependencies {
+            references: addresses.iter().map(|a| a.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn addresses(order: &ModuleOrder) -> Vec<&str> {
+        order.steps.iter().map(|s| s.address.as_str()).collect()
+    }
+
+    #[test]
+    fn test_plan_order_puts_sinks_after_sources_and_topics() {
+        let connectors = vec![
+            connector(
+                "archive",
+                "S3_SINK",
+                &[("topics", "shop.public.orders,refunds")],
+            ),
+            connector("cdc", "PostgresCdcSourceV2", &[("topic.prefix", "shop")]),
+            connector("refunds", "DatagenSource", &[("kafka.topic", "refunds")]),
+        ];
+        let topics = vec![topic("refunds", "refunds")];
+        let declared = BTreeMap::from([
+            (
+                "confluent_connector.archive".to_string(),
+                DeclaredDependencies::default(),
+            ),
+            (
+                "confluent_connector.cdc".to_string(),
+                DeclaredDependencies::default(),
+            ),
+            (
+                "confluent_connector.refunds".to_string(),
+                references(&["confluent_kafka_topic.refunds"]),
+            ),
+        ]);
+
+        let order = plan_order(
+            Path::new("infra"),
+            &connectors,
+            &topics,
+            &BTreeMap::new(),
+            &declared,
+        );
+        assert_eq!(
+            addresses(&order),
+            vec![
+                "confluent_kafka_topic.refunds",
+                "confluent_connector.cdc",
+                "confluent_connector.refunds",
+                "confluent_connector.archive",
+            ]
+        );
+        assert_eq!(
+            order.steps[3].after,
+            vec![
+                "confluent_connector.cdc",
+                "confluent_connector.refunds",
+                "confluent_kafka_topic.refunds",
+            ]
+        );
+        assert!(order.cycle.is_empty());
+
+        // The datagen source already references its topic, so only the sink needs a patch
+        assert_eq!(order.patches.len(), 1);
+        assert_eq!(order.patches[0].address, "confluent_connector.archive");
+        assert_eq!(order.patches[0].missing.len(), 3);
+    }
+
+    #[test]
+    fn test_plan_order_resolves_topic_references() {
+        let connectors = vec![
+            connector(
+                "sink",
+                "ElasticsearchSink",
+                &[("topics", "${confluent_kafka_topic.orders.topic_name}")],
+            ),
+            connector("source", "DatagenSource", &[("kafka.topic", "orders")]),
+        ];
+        let topics = vec![topic("orders", "orders")];
+        let declared = BTreeMap::from([
+            (
+                "confluent_connector.sink".to_string(),
+                references(&["confluent_kafka_topic.orders"]),
+            ),
+            (
+                "confluent_connector.source".to_string(),
+                DeclaredDependencies::default(),
+            ),
+        ]);
+
+        let order = plan_order(
+            Path::new("."),
+            &connectors,
+            &topics,
+            &BTreeMap::new(),
+            &declared,
+        );
+        assert_eq!(addresses(&order).last(), Some(&"confluent_connector.sink"));
+        let patch = &order.patches[0];
+        assert_eq!(patch.address, "confluent_connector.sink");
+        assert_eq!(patch.missing, vec!["confluent_connector.source"]);
+        // The topic is referenced, so it doesn't need repeating in depends_on
+        assert_eq!(patch.depends_on, vec!["confluent_connector.source"]);
+    }
+
+    #[test]
+    fn test_plan_order_reports_cycles() {
+        let connectors = vec![
+            connector("a", "S3_SINK", &[("topics", "orders")]),
+            connector("b", "DatagenSource", &[("kafka.topic", "orders")]),
+        ];
+        let declared = BTreeMap::from([
+            (
+                "confluent_connector.a".to_string(),
+                DeclaredDependencies::default(),
+            ),
+            (
+                "confluent_connector.b".to_string(),
+                DeclaredDependencies {
+                    depends_on: BTreeSet::from(["confluent_connector.a".to_string()]),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let order = plan_order(
+            Path::new("."),
+            &connectors,
+            &[],
+            &BTreeMap::new(),
+            &declared,
+        );
+        assert!(order.steps.is_empty());
+        assert_eq!(
+            order.cycle,
+            vec!["confluent_connector.a", "confluent_connector.b"]
+        );
+        let text = render_order(&[order], OrderFormat::Text).unwrap();
+        assert!(text.contains("Dependency cycle between confluent_connector.a"));
+    }
+
+    #[test]
+    fn test_plan_order_reads_data_sources_first() {
+        let body = parse_body(
+            r#"
+resource "confluent_connector" "orders" {
+  config_nonsensitive = {
+    "connector.class" = "S3_SINK"
+    "topics"          = "orders"
+    "description"     = "Replaces ${data.confluent_connector.orders.id}"
+  }
+}
+"#,
+        )
+        .unwrap();
+        // The data source isn't mistaken for the resource with the same label
+        let declared = declared_dependencies(&body);
+        assert_eq!(
+            declared["confluent_connector.orders"].references,
+            BTreeSet::from(["data.confluent_connector.orders".to_string()])
+        );
+
+        let connectors = vec![connector("orders", "S3_SINK", &[("topics", "orders")])];
+        let existing = BTreeMap::from([(
+            "data.confluent_connector.orders".to_string(),
+            PathBuf::from("existing.tf"),
+        )]);
+        let order = plan_order(Path::new("."), &connectors, &[], &existing, &declared);
+        assert_eq!(
+            addresses(&order),
+            vec![
+                "data.confluent_connector.orders",
+                "confluent_connector.orders"
+            ]
+        );
+        assert_eq!(
+            order.steps[1].after,
+            vec!["data.confluent_connector.orders"]
+        );
+        assert!(order.patches.is_empty());
+        assert!(order.cycle.is_empty());
+    }
+
+    #[test]
+    fn test_topic_resources_and_declared_dependencies() {
+        let body = parse_body(
+            r#"
+resource "confluent_kafka_topic" "orders" {
+  topic_name = "orders"
+}
+resource "confluent_kafka_topic" "events" {
+  topic_name = var.events_topic
+}
+resource "confluent_connector" "sink" {
+  config_nonsensitive = {
+    "topics" = confluent_kafka_topic.orders.topic_name
+  }
+  depends_on = [confluent_connector.source]
+}
+"#,
+        )
+        .unwrap();
+        let topics = topic_resources(Path::new("main.tf"), &body);
+        assert_eq!(topics.len(), 2);
+        assert_eq!(topics[0].topic_name.as_deref(), Some("orders"));
+        assert_eq!(topics[1].topic_name, None);
+
+        let declared = declared_dependencies(&body);
+        assert_eq!(
+            declared["confluent_connector.sink"],
+            DeclaredDependencies {
+                depends_on: BTreeSet::from(["confluent_connector.source".to_string()]),
+                references: BTreeSet::from(["confluent_kafka_topic.orders".to_string()]),
+            }
+        );
+    }
+}
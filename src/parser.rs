@@ -0,0 +1,293 @@
+use crate::error::ConnectUtilError;
+use crate::locals::{collect_locals, Locals};
+use hcl::{Body, TemplateExpr};
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    /// Set while a guarded call runs, so its panic isn't printed
+    static GUARDED: Cell<bool> = const { Cell::new(false) };
+}
+
+static QUIET_HOOK: Once = Once::new();
+
+/// Runs `f`, returning `None` if it panics. hcl-rs 0.15 panics on some valid input:
+/// it slices strings by char index when unescaping, so a quoted string with a
+/// non-ASCII character before a backslash escape (`"café\n"`) can fail, and it
+/// unwraps integer literals larger than `i64::MAX`
+fn guarded<T>(f: impl FnOnce() -> T) -> Option<T> {
+    QUIET_HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !GUARDED.with(Cell::get) {
+                default(info);
+            }
+        }));
+    });
+    let was_guarded = GUARDED.with(|guarded| guarded.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    GUARDED.with(|guarded| guarded.set(was_guarded));
+    result.ok()
+}
+
+/// Rewrites non-ASCII characters in quoted strings as `\u` escapes, which decode to
+/// the same value but keep every backslash on a char boundary for hcl-rs. Comments
+/// and heredoc bodies are copied as they are
+fn escape_non_ascii_strings(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    // Brace depth of each open `${`/`%{` inside a string, innermost last
+    let mut interpolations: Vec<usize> = Vec::new();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            match c {
+                '\\' => {
+                    out.push(c);
+                    out.extend(chars.next());
+                }
+                '"' => {
+                    in_string = false;
+                    out.push(c);
+                }
+                '$' | '%' if chars.peek() == Some(&c) => {
+                    out.push(c);
+                    out.extend(chars.next());
+                }
+                '$' | '%' if chars.peek() == Some(&'{') => {
+                    out.push(c);
+                    out.extend(chars.next());
+                    interpolations.push(0);
+                    in_string = false;
+                }
+                // hcl-rs only reads four-digit escapes, so characters outside the
+                // Basic Multilingual Plane stay as they are
+                c if !c.is_ascii() && (c as u32) <= 0xffff => {
+                    out.push_str(&format!("\\u{:04x}", c as u32))
+                }
+                c => out.push(c),
+            }
+            continue;
+        }
+        out.push(c);
+        match c {
+            '"' => in_string = true,
+            '{' => {
+                if let Some(depth) = interpolations.last_mut() {
+                    *depth += 1;
+                }
+            }
+            '}' => match interpolations.last_mut() {
+                Some(0) => {
+                    interpolations.pop();
+                    in_string = true;
+                }
+                Some(depth) => *depth -= 1,
+                None => {}
+            },
+            '#' => copy_line(&mut chars, &mut out),
+            '/' if chars.peek() == Some(&'/') => copy_line(&mut chars, &mut out),
+            '/' if chars.peek() == Some(&'*') => {
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    out.push(c);
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            '<' if chars.peek() == Some(&'<') && interpolations.is_empty() => {
+                out.extend(chars.next());
+                let mut header = String::new();
+                while let Some(c) = chars.next_if(|c| *c != '\n') {
+                    header.push(c);
+                }
+                out.push_str(&header);
+                let marker = header.trim().trim_start_matches('-').to_string();
+                if marker.is_empty() {
+                    continue;
+                }
+                loop {
+                    let mut line = String::new();
+                    match chars.next() {
+                        Some(c) => line.push(c),
+                        None => break,
+                    }
+                    while let Some(c) = chars.next_if(|c| *c != '\n') {
+                        line.push(c);
+                    }
+                    out.push_str(&line);
+                    if line.trim() == marker {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Copies the rest of the line, for comments
+fn copy_line(chars: &mut std::iter::Peekable<std::str::Chars<'_>>, out: &mut String) {
+    while let Some(c) = chars.next_if(|c| *c != '\n') {
+        out.push(c);
+    }
+}
+
+/// Parses HCL like `hcl::from_str`. Where hcl-rs would panic on a string, the
+/// content is parsed again with its non-ASCII string characters escaped, and any
+/// further panic comes back as an error
+pub fn parse_body(content: &str) -> hcl::Result<Body> {
+    let _span = tracing::debug_span!("parse").entered();
+    if let Some(parsed) = guarded(|| hcl::from_str(content)) {
+        return parsed;
+    }
+    let escaped = escape_non_ascii_strings(content);
+    guarded(|| hcl::from_str(&escaped)).unwrap_or_else(|| {
+        Err(hcl::Error::Message {
+            msg: "unsupported literal: an integer larger than 9223372036854775807, or a \
+                  string with an emoji before an escape sequence"
+                .to_string(),
+            location: None,
+        })
+    })
+}
+
+/// A template's text with escapes resolved, or the raw text where hcl-rs can't
+/// resolve them
+pub fn template_text(template: &TemplateExpr) -> String {
+    guarded(|| template.to_string()).unwrap_or_else(|| match template {
+        TemplateExpr::QuotedString(raw) => raw.clone(),
+        TemplateExpr::Heredoc(heredoc) => heredoc.template.clone(),
+    })
+}
+
+/// A Terraform file read and parsed once, so the checks of a validation pass share
+/// one AST instead of each parsing the content again
+pub struct SourceFile {
+    pub path: PathBuf,
+    pub content: String,
+    pub body: hcl::Result<Body>,
+    /// Time spent reading and parsing the file
+    pub elapsed: Duration,
+}
+
+impl SourceFile {
+    pub fn read(path: &Path) -> Result<Self, ConnectUtilError> {
+        let started = Instant::now();
+        let content = {
+            let _span = tracing::debug_span!("read").entered();
+            std::fs::read_to_string(path)?
+        };
+        let mut source = Self::parse(path, content);
+        source.elapsed = started.elapsed();
+        Ok(source)
+    }
+
+    /// Parses content that didn't come from `path` on disk, such as standard input
+    pub fn parse(path: &Path, content: String) -> Self {
+        let started = Instant::now();
+        let body = parse_body(&content);
+        Self {
+            path: path.to_path_buf(),
+            content,
+            body,
+            elapsed: started.elapsed(),
+        }
+    }
+}
+
+/// The parsed files of one module and the locals declared across them. Modules
+/// are loaded one at a time, so memory is bounded by the largest module rather
+/// than the whole tree
+pub struct ModuleSources {
+    pub dir: PathBuf,
+    pub files: Vec<SourceFile>,
+    pub locals: Locals,
+}
+
+impl ModuleSources {
+    /// Reads and parses `files`; files that fail to parse contribute no locals
+    pub fn load(dir: &Path, files: &[PathBuf]) -> Result<Self, ConnectUtilError> {
+        let files = files
+            .iter()
+            .map(|file| SourceFile::read(file))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut locals = Locals::new();
+        for body in files.iter().filter_map(|file| file.body.as_ref().ok()) {
+            locals.extend(collect_locals(body));
+        }
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            files,
+            locals,
+        })
+    }
+
+    pub fn file(&self, path: &Path) -> Option<&SourceFile> {
+        self.files.iter().find(|file| file.path == path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_non_ascii_strings() {
+        assert_eq!(
+            escape_non_ascii_strings(
+                "# é \"\nx = \"é${lookup(m, \"ü\")}🚀\" // é\ny = <<-EOT\n  é\\n\n  EOT\nz = \"$${é}\"\n"
+            ),
+            "# é \"\nx = \"\\u00e9${lookup(m, \"\\u00fc\")}🚀\" // é\ny = <<-EOT\n  é\\n\n  EOT\nz = \"$${\\u00e9}\"\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_body_non_ascii_escapes() {
+        assert!(parse_body("name = \"orders\\n\"\n").is_ok());
+        let body =
+            parse_body("# café\\n\nname = \"café\\n${var.x}é\"\nlabel = \"日本\\t\"\n").unwrap();
+        let template = match body.attributes().next().unwrap().expr() {
+            hcl::Expression::TemplateExpr(template) => template_text(template),
+            other => panic!("expected a template, got {:?}", other),
+        };
+        assert_eq!(template, "café\n${var.x}é");
+        assert_eq!(
+            body.attributes().nth(1).unwrap().expr(),
+            &hcl::Expression::String("日本\t".to_string())
+        );
+        assert!(parse_body("name = \"café orders\"\n").is_ok());
+        assert!(parse_body("name = \"").is_err());
+        let error = parse_body("count = 18446744073709551615\n").unwrap_err();
+        assert!(error.to_string().starts_with("unsupported literal"));
+
+        let template = TemplateExpr::QuotedString("é\\n${var.x}".to_string());
+        assert_eq!(template_text(&template), "é\\n${var.x}");
+        let template = TemplateExpr::QuotedString("a\\n${var.x}".to_string());
+        assert_eq!(template_text(&template), "a\n${var.x}");
+    }
+
+    #[test]
+    fn test_module_sources_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let locals = dir.path().join("locals.tf");
+        let broken = dir.path().join("broken.tf");
+        std::fs::write(&locals, "locals {\n  topic = \"orders\"\n}\n").unwrap();
+        std::fs::write(&broken, "locals {").unwrap();
+        let module = ModuleSources::load(dir.path(), &[broken.clone(), locals.clone()]).unwrap();
+        assert_eq!(module.files.len(), 2);
+        assert!(module.file(&broken).unwrap().body.is_err());
+        assert!(module.file(&locals).unwrap().body.is_ok());
+        assert_eq!(
+            module.locals.get("topic"),
+            Some(&hcl::Expression::String("orders".to_string()))
+        );
+        assert!(ModuleSources::load(dir.path(), &[dir.path().join("missing.tf")]).is_err());
+    }
+}
@@ -0,0 +1,247 @@
+use crate::error::ConnectUtilError;
+use crate::types::ConnectorConfig;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// The subset of `terraform show -json` output needed to check connector changes
+#[derive(Debug, Deserialize)]
+struct Plan {
+    #[serde(default)]
+    resource_changes: Vec<ResourceChange>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResourceChange {
+    address: String,
+    #[serde(rename = "type")]
+    resource_type: String,
+    #[serde(default)]
+    name: String,
+    change: Change,
+}
+
+#[derive(Debug, Deserialize)]
+struct Change {
+    actions: Vec<String>,
+    #[serde(default)]
+    after: Option<serde_json::Value>,
+    #[serde(default)]
+    after_unknown: Option<serde_json::Value>,
+}
+
+/// The parts of one config map only known after apply
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnknownValues {
+    /// Keys whose values are only known after apply
+    pub keys: Vec<String>,
+    /// The whole map is only known after apply, so even its keys aren't known
+    pub entire_map: bool,
+}
+
+impl UnknownValues {
+    fn from_after_unknown(after_unknown: Option<&serde_json::Value>, attribute: &str) -> Self {
+        match after_unknown.and_then(|u| u.get(attribute)) {
+            Some(serde_json::Value::Object(object)) => UnknownValues {
+                keys: object
+                    .iter()
+                    .filter(|(_, unknown)| unknown.as_bool() == Some(true))
+                    .map(|(key, _)| key.clone())
+                    .collect(),
+                entire_map: false,
+            },
+            Some(serde_json::Value::Bool(true)) => UnknownValues {
+                keys: Vec::new(),
+                entire_map: true,
+            },
+            _ => UnknownValues::default(),
+        }
+    }
+}
+
+/// A planned `confluent_connector` create or update with its resolved values
+#[derive(Debug, Clone)]
+pub struct PlannedConnector {
+    pub address: String,
+    pub actions: Vec<String>,
+    pub config: ConnectorConfig,
+    /// What of `config_nonsensitive` is only known after apply
+    pub unknown_nonsensitive: UnknownValues,
+    /// What of `config_sensitive` is only known after apply
+    pub unknown_sensitive: UnknownValues,
+}
+
+impl PlannedConnector {
+    /// The keys and maps only known after apply, for display
+    pub fn unknown_values(&self) -> Vec<String> {
+        let mut values = Vec::new();
+        for (attribute, unknown) in [
+            ("config_nonsensitive", &self.unknown_nonsensitive),
+            ("config_sensitive", &self.unknown_sensitive),
+        ] {
+            if unknown.entire_map {
+                values.push(format!("{} (entire map)", attribute));
+            }
+            values.extend(unknown.keys.iter().cloned());
+        }
+        values.sort();
+        values
+    }
+}
+
+/// Renders planned values as the strings the validators compare against
+fn value_to_string(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn string_map(value: Option<&serde_json::Value>) -> HashMap<String, String> {
+    value
+        .and_then(|v| v.as_object())
+        .map(|object| {
+            object
+                .iter()
+                .filter_map(|(key, value)| value_to_string(value).map(|v| (key.clone(), v)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Extracts planned connector creates and updates from `terraform show -json` output;
+/// deletes and no-ops are skipped since there's nothing to validate
+pub fn parse_plan(content: &str) -> Result<Vec<PlannedConnector>, ConnectUtilError> {
+    let plan: Plan = serde_json::from_str(content)
+        .map_err(|e| ConnectUtilError::Config(format!("Invalid plan JSON: {}", e)))?;
+
+    let mut planned = Vec::new();
+    for change in plan.resource_changes {
+        if change.resource_type != "confluent_connector" {
+            continue;
+        }
+        let actions = change.change.actions;
+        if !actions.iter().any(|a| a == "create" || a == "update") {
+            continue;
+        }
+        let after = change.change.after.as_ref();
+        let after_unknown = change.change.after_unknown.as_ref();
+        let config_nonsensitive = string_map(after.and_then(|a| a.get("config_nonsensitive")));
+        let config_sensitive = string_map(after.and_then(|a| a.get("config_sensitive")));
+
+        planned.push(PlannedConnector {
+            address: change.address,
+            actions,
+            config: ConnectorConfig {
+                name: change.name,
+                connector_class: config_nonsensitive
+                    .get("connector.class")
+                    .cloned()
+                    .unwrap_or_default(),
+                config: config_nonsensitive,
+                sensitive_config: config_sensitive,
+            },
+            unknown_nonsensitive: UnknownValues::from_after_unknown(
+                after_unknown,
+                "config_nonsensitive",
+            ),
+            unknown_sensitive: UnknownValues::from_after_unknown(after_unknown, "config_sensitive"),
+        });
+    }
+    Ok(planned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAN: &str = r#"{
+  "format_version": "1.2",
+  "resource_changes": [
+    {
+      "address": "confluent_connector.orders",
+      "type": "confluent_connector",
+      "name": "orders",
+      "change": {
+        "actions": ["create"],
+        "after": {
+          "config_nonsensitive": {
+            "connector.class": "PostgresCdcSourceV2",
+            "name": "orders",
+            "tasks.max": 1
+          },
+          "config_sensitive": { "database.password": "resolved" }
+        },
+        "after_unknown": {
+          "config_nonsensitive": { "database.hostname": true },
+          "config_sensitive": { "database.password": false, "database.user": true },
+          "id": true
+        }
+      }
+    },
+    {
+      "address": "confluent_connector.legacy",
+      "type": "confluent_connector",
+      "name": "legacy",
+      "change": { "actions": ["delete"], "after": null }
+    },
+    {
+      "address": "confluent_kafka_topic.orders",
+      "type": "confluent_kafka_topic",
+      "name": "orders",
+      "change": { "actions": ["create"], "after": {} }
+    }
+  ]
+}"#;
+
+    #[test]
+    fn test_parse_plan() {
+        let planned = parse_plan(PLAN).unwrap();
+        assert_eq!(planned.len(), 1);
+        let orders = &planned[0];
+        assert_eq!(orders.address, "confluent_connector.orders");
+        assert_eq!(orders.actions, vec!["create"]);
+        assert_eq!(orders.config.connector_class, "PostgresCdcSourceV2");
+        assert_eq!(
+            orders.config.config.get("tasks.max"),
+            Some(&"1".to_string())
+        );
+        assert_eq!(
+            orders.config.sensitive_config.get("database.password"),
+            Some(&"resolved".to_string())
+        );
+        assert_eq!(orders.unknown_nonsensitive.keys, vec!["database.hostname"]);
+        assert_eq!(orders.unknown_sensitive.keys, vec!["database.user"]);
+        assert!(!orders.unknown_sensitive.entire_map);
+    }
+
+    #[test]
+    fn test_parse_plan_replace_counts_as_create() {
+        let plan = r#"{"resource_changes":[{"address":"confluent_connector.a","type":"confluent_connector",
+            "name":"a","change":{"actions":["delete","create"],"after":{"config_nonsensitive":{}},
+            "after_unknown":{"config_sensitive":true}}}]}"#;
+        let planned = parse_plan(plan).unwrap();
+        assert_eq!(planned.len(), 1);
+        assert!(planned[0].unknown_sensitive.entire_map);
+        assert_eq!(
+            planned[0].unknown_values(),
+            vec!["config_sensitive (entire map)"]
+        );
+    }
+
+    #[test]
+    fn test_parse_plan_invalid_json() {
+        assert!(matches!(
+            parse_plan("not json"),
+            Err(ConnectUtilError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_plan_without_changes() {
+        assert!(parse_plan(r#"{"format_version":"1.2"}"#)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,1046 @@
+use crate::error::ConnectUtilError;
+use crate::expression::Expr;
+use crate::lifecycle::{normalize, IgnoreChanges, DEFAULT_IGNORE_CHANGES};
+use crate::lint::{LintRule, Severity};
+use crate::profile::ValidationProfile;
+use crate::template;
+use crate::types::{ConnectorConfig, ConnectorDefinition, ConnectorType, Environment};
+use regex::Regex;
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Naming convention rules for connector names and Terraform resource labels
+#[derive(Debug, Clone, Default)]
+pub struct NamingRules {
+    pub connector_name: Option<Regex>,
+    pub resource_label: Option<Regex>,
+}
+
+impl NamingRules {
+    /// Compiles the optional connector name and resource label patterns
+    pub fn new(
+        connector_name: Option<&str>,
+        resource_label: Option<&str>,
+    ) -> Result<Self, ConnectUtilError> {
+        Ok(Self {
+            connector_name: connector_name.map(Self::compile).transpose()?,
+            resource_label: resource_label.map(Self::compile).transpose()?,
+        })
+    }
+
+    fn compile(pattern: &str) -> Result<Regex, ConnectUtilError> {
+        Regex::new(pattern).map_err(|e| {
+            ConnectUtilError::Config(format!("Invalid naming pattern '{}': {}", pattern, e))
+        })
+    }
+
+    /// Checks a connector name, returning an error message with the expected pattern
+    pub fn check_connector_name(&self, name: &str) -> Result<(), String> {
+        Self::check("Connector name", name, self.connector_name.as_ref())
+    }
+
+    /// Checks a Terraform resource label, returning an error message with the expected pattern
+    pub fn check_resource_label(&self, label: &str) -> Result<(), String> {
+        Self::check("Resource label", label, self.resource_label.as_ref())
+    }
+
+    fn check(kind: &str, value: &str, pattern: Option<&Regex>) -> Result<(), String> {
+        match pattern {
+            Some(pattern) if !pattern.is_match(value) => Err(format!(
+                "{} '{}' does not match required pattern '{}'",
+                kind,
+                value,
+                pattern.as_str()
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Suggests a compliant connector name by normalizing case and separators
+    /// and trying the conventional prefixes for the connector type
+    pub fn suggest_connector_name(
+        &self,
+        name: &str,
+        connector_type: &ConnectorType,
+    ) -> Option<String> {
+        let pattern = self.connector_name.as_ref()?;
+        let prefixes: &[&str] = match connector_type {
+            ConnectorType::Source => &["src", "source"],
+            ConnectorType::Sink => &["sink", "snk"],
+        };
+
+        for separator in ['-', '_'] {
+            let base = Self::normalize(name, separator);
+            if base.is_empty() {
+                continue;
+            }
+            let candidates = std::iter::once(base.clone()).chain(
+                prefixes
+                    .iter()
+                    .map(|prefix| format!("{}{}{}", prefix, separator, base)),
+            );
+            for candidate in candidates {
+                if pattern.is_match(&candidate) {
+                    return Some(candidate);
+                }
+            }
+        }
+        None
+    }
+
+    /// Suggests a compliant resource label, defaulting to snake_case
+    pub fn suggest_resource_label(&self, label: &str) -> Option<String> {
+        let pattern = self.resource_label.as_ref()?;
+        let candidate = Self::normalize(label, '_');
+        pattern.is_match(&candidate).then_some(candidate)
+    }
+
+    /// Lowercases and collapses runs of non-alphanumeric characters into one separator
+    fn normalize(value: &str, separator: char) -> String {
+        let mut normalized = String::new();
+        for c in value.chars() {
+            if c.is_ascii_alphanumeric() {
+                normalized.push(c.to_ascii_lowercase());
+            } else if !normalized.is_empty() && !normalized.ends_with(separator) {
+                normalized.push(separator);
+            }
+        }
+        normalized.trim_end_matches(separator).to_string()
+    }
+
+    /// Formats a naming violation with the suggested fix, if any
+    pub fn describe_violation(error: String, suggestion: Option<String>) -> String {
+        match suggestion {
+            Some(suggestion) => format!("{} (suggested: '{}')", error, suggestion),
+            None => error,
+        }
+    }
+}
+
+/// Organization policies loaded from a repo-level `.connect-util.yaml`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Policy {
+    /// Connector classes engineers may use; empty allows every class
+    pub allowed_connector_classes: Vec<String>,
+    /// Keys that must be present with exactly this value
+    #[serde(deserialize_with = "scalar_map")]
+    pub required_values: BTreeMap<String, String>,
+    /// Key/value pairs that must not appear
+    #[serde(deserialize_with = "scalar_map")]
+    pub forbidden_values: BTreeMap<String, String>,
+    /// Upper bound for `tasks.max`
+    pub max_tasks: Option<u32>,
+    /// Connector classes nobody may use
+    pub denied_connector_classes: Vec<String>,
+    /// Expression rules that must hold for every connector
+    pub rules: Vec<PolicyRule>,
+    /// Per-path connector class restrictions, applied in order
+    pub overrides: Vec<PathOverride>,
+    /// Named environment profiles selected with `generate --environment`
+    pub environments: BTreeMap<String, Environment>,
+    /// `ignore_changes` entries for generated connectors, replacing the defaults
+    pub ignore_changes: Option<Vec<String>>,
+    /// `ignore_changes` entries every connector resource must have; generated
+    /// connectors always include them
+    pub required_ignore_changes: Vec<String>,
+    /// Config values generated connectors get, as templates over `tokens` such as
+    /// `{{team}}-{{env}}-{{connector}}`
+    pub templates: BTreeMap<String, String>,
+    /// Metadata keys every connector must set in `config_nonsensitive`, such as
+    /// `confluent.custom.tags`, with the template generated connectors get for each
+    pub metadata: BTreeMap<String, String>,
+    /// Token values for `templates`; `--token` overrides them
+    #[serde(deserialize_with = "scalar_map")]
+    pub tokens: BTreeMap<String, String>,
+    /// Severity overrides by rule name, e.g. `unused_variable: error` or `placeholder: off`
+    pub severities: BTreeMap<String, Severity>,
+    /// Directory the policy file was loaded from; override paths are relative to it
+    #[serde(skip)]
+    pub root: Option<PathBuf>,
+    /// `validate --profile` bundle supplying the severities `severities` doesn't set
+    #[serde(skip)]
+    pub profile: Option<ValidationProfile>,
+}
+
+/// Connector class restrictions for files under a path, e.g. `prod/**`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PathOverride {
+    pub path: String,
+    /// Replaces the inherited allowlist for matching files
+    #[serde(default)]
+    pub allowed_connector_classes: Option<Vec<String>>,
+    /// Added to the inherited denylist for matching files
+    #[serde(default)]
+    pub denied_connector_classes: Vec<String>,
+}
+
+impl PathOverride {
+    /// Matches when the pattern components prefix the path; `*` matches one component
+    fn matches(&self, relative: &Path) -> bool {
+        let pattern = self.path.trim_end_matches("/**").trim_end_matches('/');
+        let components: Vec<String> = relative
+            .components()
+            .filter_map(|c| match c {
+                std::path::Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+                _ => None,
+            })
+            .collect();
+        let parts: Vec<&str> = pattern
+            .split('/')
+            .filter(|part| !part.is_empty() && *part != ".")
+            .collect();
+        parts.len() <= components.len()
+            && parts
+                .iter()
+                .zip(&components)
+                .all(|(part, component)| *part == "*" || part == component)
+    }
+}
+
+/// A named expression rule, e.g.
+/// `config["snapshot.mode"] == "initial" implies config["table.include.list"] != ""`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyRule {
+    pub name: String,
+    #[serde(rename = "expr")]
+    pub expression: String,
+    /// Shown instead of the expression when the rule fails
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(skip)]
+    compiled: Option<Expr>,
+}
+
+impl PolicyRule {
+    /// Evaluates the rule, returning a violation message if it doesn't hold
+    fn check(&self, config: &ConnectorConfig) -> Option<String> {
+        let result = match &self.compiled {
+            Some(expr) => expr.evaluate_bool(config),
+            None => Expr::parse(&self.expression)
+                .map_err(|e| e.to_string())
+                .and_then(|expr| expr.evaluate_bool(config)),
+        };
+        match result {
+            Ok(true) => None,
+            Ok(false) => Some(format!(
+                "Rule '{}' failed: {}",
+                self.name,
+                self.message.as_deref().unwrap_or(&self.expression)
+            )),
+            Err(e) => Some(format!(
+                "Rule '{}' could not be evaluated: {}",
+                self.name, e
+            )),
+        }
+    }
+}
+
+/// Accepts YAML scalars of any type so `auto.create: true` doesn't need quoting
+pub(crate) fn scalar_map<'de, D>(deserializer: D) -> Result<BTreeMap<String, String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw = BTreeMap::<String, serde_yaml::Value>::deserialize(deserializer)?;
+    raw.into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                serde_yaml::Value::String(s) => s,
+                serde_yaml::Value::Bool(b) => b.to_string(),
+                serde_yaml::Value::Number(n) => n.to_string(),
+                _ => {
+                    return Err(serde::de::Error::custom(format!(
+                        "value for '{}' must be a string, number, or boolean",
+                        key
+                    )))
+                }
+            };
+            Ok((key, value))
+        })
+        .collect()
+}
+
+impl Policy {
+    pub const FILE_NAME: &'static str = ".connect-util.yaml";
+
+    /// Parses a policy from YAML
+    pub fn from_yaml(content: &str) -> Result<Self, ConnectUtilError> {
+        let mut policy: Self = serde_yaml::from_str(content)
+            .map_err(|e| ConnectUtilError::Config(format!("Invalid policy file: {}", e)))?;
+        // Compile rules up front so syntax errors surface before any connector is checked
+        for rule in &mut policy.rules {
+            let expr = Expr::parse(&rule.expression).map_err(|e| {
+                ConnectUtilError::Config(format!(
+                    "Invalid policy file: rule '{}': {}",
+                    rule.name, e
+                ))
+            })?;
+            rule.compiled = Some(expr);
+        }
+        for (name, environment) in &mut policy.environments {
+            environment.name = name.clone();
+        }
+        if let Some(unknown) = policy
+            .severities
+            .keys()
+            .find(|id| LintRule::from_id(id).is_none())
+        {
+            return Err(ConnectUtilError::Config(format!(
+                "Invalid policy file: unknown rule '{}' under `severities`",
+                unknown
+            )));
+        }
+        Ok(policy)
+    }
+
+    /// The severity findings of `rule` are reported at; `None` when turned off
+    pub fn rule_severity(&self, rule: LintRule) -> Option<&'static str> {
+        match self
+            .severities
+            .iter()
+            .find(|(id, _)| LintRule::from_id(id) == Some(rule))
+        {
+            Some((_, severity)) => severity.level(),
+            None => match self.profile {
+                Some(profile) => profile.severity(rule),
+                None => Some(rule.severity()),
+            },
+        }
+    }
+
+    /// Looks up an environment profile by name
+    pub fn environment(&self, name: &str) -> Result<&Environment, String> {
+        self.environments.get(name).ok_or_else(|| {
+            if self.environments.is_empty() {
+                format!(
+                    "Unknown environment '{}': the config file defines no environments",
+                    name
+                )
+            } else {
+                let known: Vec<&str> = self.environments.keys().map(String::as_str).collect();
+                format!(
+                    "Unknown environment '{}' (defined: {})",
+                    name,
+                    known.join(", ")
+                )
+            }
+        })
+    }
+
+    /// Loads a policy file from disk
+    pub fn load(path: &Path) -> Result<Self, ConnectUtilError> {
+        let content = std::fs::read_to_string(path)?;
+        Self::from_yaml(&content)
+            .map_err(|e| ConnectUtilError::Config(format!("{} ({})", e, path.display())))
+    }
+
+    /// Finds the policy file for a path by walking up its directories,
+    /// stopping at the repository root (the directory containing `.git`)
+    pub fn discover(start: &Path) -> Result<Option<(PathBuf, Self)>, ConnectUtilError> {
+        let _span = tracing::debug_span!("policy_load").entered();
+        let start = if start.is_absolute() {
+            start.to_path_buf()
+        } else {
+            std::env::current_dir()?.join(start)
+        };
+        let mut dir = if start.is_dir() {
+            Some(start.as_path())
+        } else {
+            start.parent()
+        };
+
+        while let Some(current) = dir {
+            let candidate = current.join(Self::FILE_NAME);
+            if candidate.is_file() {
+                let mut policy = Self::load(&candidate)?;
+                policy.root = Some(current.to_path_buf());
+                return Ok(Some((candidate, policy)));
+            }
+            if current.join(".git").exists() {
+                break;
+            }
+            dir = current.parent();
+        }
+        Ok(None)
+    }
+
+    /// Checks a connector class against the allow/deny lists that apply to a file.
+    /// Classes are compared the way validation resolves them, so a listed name
+    /// also covers the connector's aliases and other spellings
+    pub fn check_connector_class(&self, connector_class: &str, file: &Path) -> Result<(), String> {
+        let definition = ConnectorDefinition::get_connector_by_name(connector_class);
+        let names = |class: &String| {
+            class.eq_ignore_ascii_case(connector_class)
+                || definition
+                    .as_ref()
+                    .is_some_and(|definition| definition.matches_class(class))
+        };
+        let relative = self.relative_path(file);
+        let mut allowed = &self.allowed_connector_classes;
+        let mut denied: Vec<&String> = self.denied_connector_classes.iter().collect();
+        for path_override in self.overrides.iter().filter(|o| o.matches(&relative)) {
+            if let Some(override_allowed) = &path_override.allowed_connector_classes {
+                allowed = override_allowed;
+            }
+            denied.extend(&path_override.denied_connector_classes);
+        }
+
+        if denied.iter().any(|class| names(class)) {
+            return Err(format!(
+                "Connector class '{}' is denied for {}",
+                connector_class,
+                relative.display()
+            ));
+        }
+        if !allowed.is_empty() && !allowed.iter().any(names) {
+            return Err(format!(
+                "Connector class '{}' is not allowed for {} (allowed: {})",
+                connector_class,
+                relative.display(),
+                allowed.join(", ")
+            ));
+        }
+        Ok(())
+    }
+
+    /// Resolves a file path relative to the policy root for override matching
+    fn relative_path(&self, file: &Path) -> PathBuf {
+        let absolute = if file.is_absolute() {
+            file.to_path_buf()
+        } else {
+            std::env::current_dir()
+                .map(|dir| dir.join(file))
+                .unwrap_or_else(|_| file.to_path_buf())
+        };
+        match &self.root {
+            Some(root) => absolute
+                .strip_prefix(root)
+                .map(Path::to_path_buf)
+                .unwrap_or(absolute),
+            None => file.to_path_buf(),
+        }
+    }
+
+    /// Entries for a generated connector's `ignore_changes`: `configured` from the
+    /// command line, else the policy's list, else the defaults, plus every required entry
+    pub fn ignore_changes(&self, configured: &[String]) -> Vec<String> {
+        let mut entries: Vec<String> = if !configured.is_empty() {
+            configured.to_vec()
+        } else if let Some(entries) = &self.ignore_changes {
+            entries.clone()
+        } else {
+            DEFAULT_IGNORE_CHANGES
+                .iter()
+                .map(|e| e.to_string())
+                .collect()
+        };
+        for required in &self.required_ignore_changes {
+            if !entries.iter().any(|e| normalize(e) == normalize(required)) {
+                entries.push(required.clone());
+            }
+        }
+        entries
+    }
+
+    /// Expands every template and metadata template with the policy's tokens
+    /// overlaid by `tokens`; a template for a metadata key replaces it. Each token
+    /// a template uses is required; `Err` names the ones without a value
+    pub fn expand_templates(
+        &self,
+        tokens: &BTreeMap<String, String>,
+    ) -> Result<BTreeMap<String, String>, String> {
+        let mut values = self.tokens.clone();
+        values.extend(tokens.iter().map(|(k, v)| (k.clone(), v.clone())));
+
+        let mut expanded = BTreeMap::new();
+        let mut missing: Vec<String> = Vec::new();
+        for (key, template) in self.metadata.iter().chain(&self.templates) {
+            match template::expand(template, &values) {
+                Ok(value) => {
+                    expanded.insert(key.clone(), value);
+                }
+                Err(tokens) => {
+                    for token in tokens {
+                        if !missing.contains(&token) {
+                            missing.push(token);
+                        }
+                    }
+                }
+            }
+        }
+        if !missing.is_empty() {
+            return Err(format!(
+                "Template token(s) without a value: {}. Set them under `tokens` in {} or with --token {}=...",
+                missing.join(", "),
+                Self::FILE_NAME,
+                missing[0]
+            ));
+        }
+        Ok(expanded)
+    }
+
+    /// Violations for required `ignore_changes` entries a resource's lifecycle lacks
+    pub fn check_ignore_changes(&self, resource: &str, ignored: &IgnoreChanges) -> Vec<String> {
+        self.required_ignore_changes
+            .iter()
+            .filter(|required| !ignored.covers(required))
+            .map(|required| {
+                format!(
+                    "'{}' lifecycle.ignore_changes is missing required entry {}",
+                    resource, required
+                )
+            })
+            .collect()
+    }
+
+    /// Returns every policy violation for a connector configuration parsed from `file`
+    pub fn check(&self, config: &ConnectorConfig, file: &Path) -> Vec<String> {
+        let mut violations = Vec::new();
+        let lookup = |key: &str| {
+            config
+                .config
+                .get(key)
+                .or_else(|| config.sensitive_config.get(key))
+        };
+
+        if let Err(violation) = self.check_connector_class(&config.connector_class, file) {
+            violations.push(violation);
+        }
+
+        for (key, expected) in &self.required_values {
+            match lookup(key) {
+                Some(value) if value == expected => {}
+                Some(value) => violations.push(format!(
+                    "'{}' must be '{}' (found: '{}')",
+                    key, expected, value
+                )),
+                None => violations.push(format!("Missing mandatory value: {} = {}", key, expected)),
+            }
+        }
+
+        for key in self.metadata.keys() {
+            match config.config.get(key) {
+                Some(value) if !value.trim().is_empty() => {}
+                Some(_) => violations.push(format!("Metadata key '{}' is empty", key)),
+                None if config.sensitive_config.contains_key(key) => violations.push(format!(
+                    "Metadata key '{}' belongs in config_nonsensitive",
+                    key
+                )),
+                None => violations.push(format!("Missing metadata key: {}", key)),
+            }
+        }
+
+        for (key, forbidden) in &self.forbidden_values {
+            if lookup(key) == Some(forbidden) {
+                violations.push(format!("Forbidden value: {} = {}", key, forbidden));
+            }
+        }
+
+        if let (Some(max_tasks), Some(value)) = (self.max_tasks, lookup("tasks.max")) {
+            match value.parse::<u32>() {
+                Ok(tasks) if tasks > max_tasks => violations.push(format!(
+                    "tasks.max = {} exceeds the maximum of {}",
+                    tasks, max_tasks
+                )),
+                Ok(_) => {}
+                Err(_) => violations.push(format!(
+                    "tasks.max '{}' is not a number (maximum: {})",
+                    value, max_tasks
+                )),
+            }
+        }
+
+        violations.extend(self.rules.iter().filter_map(|rule| rule.check(config)));
+
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> NamingRules {
+        NamingRules::new(Some("^(src|sink)-[a-z0-9-]+$"), Some("^[a-z][a-z0-9_]*$")).unwrap()
+    }
+
+    #[test]
+    fn test_default_rules_accept_anything() {
+        let rules = NamingRules::default();
+        assert!(rules.check_connector_name("Anything Goes").is_ok());
+        assert!(rules.check_resource_label("Anything-Goes").is_ok());
+        assert!(rules
+            .suggest_connector_name("x", &ConnectorType::Source)
+            .is_none());
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let result = NamingRules::new(Some("(unclosed"), None);
+        assert!(matches!(result, Err(ConnectUtilError::Config(_))));
+    }
+
+    #[test]
+    fn test_check_connector_name() {
+        let rules = rules();
+        assert!(rules.check_connector_name("src-orders").is_ok());
+        let error = rules.check_connector_name("Orders_CDC").unwrap_err();
+        assert!(error.contains("'Orders_CDC'"));
+        assert!(error.contains("^(src|sink)-[a-z0-9-]+$"));
+    }
+
+    #[test]
+    fn test_check_resource_label() {
+        let rules = rules();
+        assert!(rules.check_resource_label("src_orders").is_ok());
+        assert!(rules.check_resource_label("SrcOrders").is_err());
+    }
+
+    #[test]
+    fn test_suggest_connector_name() {
+        let rules = rules();
+        assert_eq!(
+            rules.suggest_connector_name("Orders_CDC", &ConnectorType::Source),
+            Some("src-orders-cdc".to_string())
+        );
+        assert_eq!(
+            rules.suggest_connector_name("orders warehouse", &ConnectorType::Sink),
+            Some("sink-orders-warehouse".to_string())
+        );
+        assert_eq!(
+            rules.suggest_connector_name("SRC-Orders", &ConnectorType::Source),
+            Some("src-orders".to_string())
+        );
+    }
+
+    #[test]
+    fn test_suggest_resource_label() {
+        let rules = rules();
+        assert_eq!(
+            rules.suggest_resource_label("Src-Orders"),
+            Some("src_orders".to_string())
+        );
+    }
+
+    fn connector_config(entries: &[(&str, &str)]) -> ConnectorConfig {
+        ConnectorConfig {
+            name: "orders".to_string(),
+            connector_class: "PostgresCdcSourceV2".to_string(),
+            config: entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            sensitive_config: Default::default(),
+        }
+    }
+
+    const POLICY: &str = r#"
+allowed_connector_classes: [PostgresCdcSourceV2, S3_SINK]
+required_values:
+  kafka.auth.mode: SERVICE_ACCOUNT
+forbidden_values:
+  auto.create: true
+max_tasks: 4
+"#;
+
+    #[test]
+    fn test_policy_from_yaml() {
+        let policy = Policy::from_yaml(POLICY).unwrap();
+        assert_eq!(policy.allowed_connector_classes.len(), 2);
+        assert_eq!(
+            policy.forbidden_values.get("auto.create"),
+            Some(&"true".to_string())
+        );
+        assert_eq!(policy.max_tasks, Some(4));
+    }
+
+    #[test]
+    fn test_policy_from_yaml_rejects_unknown_keys() {
+        let result = Policy::from_yaml("max_task: 4");
+        assert!(matches!(result, Err(ConnectUtilError::Config(_))));
+    }
+
+    #[test]
+    fn test_policy_check_compliant() {
+        let policy = Policy::from_yaml(POLICY).unwrap();
+        let config = connector_config(&[
+            ("kafka.auth.mode", "SERVICE_ACCOUNT"),
+            ("auto.create", "false"),
+            ("tasks.max", "2"),
+        ]);
+        assert!(policy.check(&config, Path::new("orders.tf")).is_empty());
+    }
+
+    #[test]
+    fn test_policy_check_violations() {
+        let policy = Policy::from_yaml(POLICY).unwrap();
+        let mut config = connector_config(&[("auto.create", "true"), ("tasks.max", "8")]);
+        config.connector_class = "DatagenSource".to_string();
+
+        let violations = policy.check(&config, Path::new("orders.tf"));
+        assert_eq!(violations.len(), 4);
+        assert!(violations[0].contains("'DatagenSource' is not allowed for orders.tf"));
+        assert!(violations[1].contains("Missing mandatory value: kafka.auth.mode"));
+        assert!(violations[2].contains("Forbidden value: auto.create = true"));
+        assert!(violations[3].contains("exceeds the maximum of 4"));
+    }
+
+    #[test]
+    fn test_policy_check_wrong_required_value() {
+        let policy = Policy::from_yaml(POLICY).unwrap();
+        let config = connector_config(&[("kafka.auth.mode", "KAFKA_API_KEY")]);
+        let violations = policy.check(&config, Path::new("orders.tf"));
+        assert_eq!(
+            violations,
+            vec!["'kafka.auth.mode' must be 'SERVICE_ACCOUNT' (found: 'KAFKA_API_KEY')"]
+        );
+    }
+
+    #[test]
+    fn test_policy_expression_rules() {
+        let policy = Policy::from_yaml(
+            r#"
+rules:
+  - name: snapshot-needs-tables
+    expr: 'config["snapshot.mode"] == "initial" implies config["table.include.list"] != ""'
+    message: initial snapshots must list their tables
+  - name: bounded-tasks
+    expr: 'int(config["tasks.max"]) <= 2'
+"#,
+        )
+        .unwrap();
+
+        let violations = policy.check(
+            &connector_config(&[("snapshot.mode", "initial"), ("tasks.max", "1")]),
+            Path::new("orders.tf"),
+        );
+        assert_eq!(
+            violations,
+            vec!["Rule 'snapshot-needs-tables' failed: initial snapshots must list their tables"]
+        );
+
+        // Missing tasks.max can't be converted, which is reported rather than ignored
+        let violations = policy.check(
+            &connector_config(&[("snapshot.mode", "never")]),
+            Path::new("orders.tf"),
+        );
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].contains("'bounded-tasks' could not be evaluated"));
+    }
+
+    #[test]
+    fn test_policy_invalid_expression_rule() {
+        let result = Policy::from_yaml(
+            r#"
+rules:
+  - name: broken
+    expr: 'config["a"] =='
+"#,
+        );
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("rule 'broken'"));
+    }
+
+    const CLASS_POLICY: &str = r#"
+denied_connector_classes: [LegacySource]
+overrides:
+  - path: prod/**
+    denied_connector_classes: [DatagenSource]
+  - path: "*/payments"
+    allowed_connector_classes: [PostgresCdcSourceV2]
+"#;
+
+    #[test]
+    fn test_check_connector_class_denylist() {
+        let policy = Policy::from_yaml(CLASS_POLICY).unwrap();
+        assert!(policy
+            .check_connector_class("LegacySource", Path::new("dev/a.tf"))
+            .unwrap_err()
+            .contains("denied for dev/a.tf"));
+        assert!(policy
+            .check_connector_class("DatagenSource", Path::new("dev/a.tf"))
+            .is_ok());
+        assert!(policy
+            .check_connector_class("DatagenSource", Path::new("prod/orders/a.tf"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_check_connector_class_resolves_aliases() {
+        let policy = Policy::from_yaml(CLASS_POLICY).unwrap();
+        // A denied short name also covers its Java class and other spellings
+        for class in [
+            "io.confluent.kafka.connect.datagen.DatagenConnector",
+            "datagensource",
+        ] {
+            assert!(policy
+                .check_connector_class(class, Path::new("prod/orders/a.tf"))
+                .unwrap_err()
+                .contains("is denied"));
+        }
+        assert!(policy
+            .check_connector_class("postgrescdcsourcev2", Path::new("prod/payments/a.tf"))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_check_connector_class_override_allowlist() {
+        let policy = Policy::from_yaml(CLASS_POLICY).unwrap();
+        assert!(policy
+            .check_connector_class("PostgresCdcSourceV2", Path::new("prod/payments/a.tf"))
+            .is_ok());
+        let error = policy
+            .check_connector_class("S3_SINK", Path::new("dev/payments/a.tf"))
+            .unwrap_err();
+        assert!(error.contains("allowed: PostgresCdcSourceV2"));
+        assert!(policy
+            .check_connector_class("S3_SINK", Path::new("dev/orders/a.tf"))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_check_connector_class_relative_to_policy_root() {
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::create_dir(repo.path().join(".git")).unwrap();
+        std::fs::write(repo.path().join(Policy::FILE_NAME), CLASS_POLICY).unwrap();
+        let file = repo.path().join("prod/orders.tf");
+
+        let (_, policy) = Policy::discover(&file).unwrap().unwrap();
+        assert_eq!(policy.root.as_deref(), Some(repo.path()));
+        assert!(policy
+            .check_connector_class("DatagenSource", &file)
+            .is_err());
+    }
+
+    #[test]
+    fn test_policy_discover_walks_up_to_repo_root() {
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::create_dir(repo.path().join(".git")).unwrap();
+        std::fs::write(repo.path().join(Policy::FILE_NAME), "max_tasks: 1").unwrap();
+        let nested = repo.path().join("connectors/prod");
+        std::fs::create_dir_all(&nested).unwrap();
+        let file = nested.join("orders.tf");
+        std::fs::write(&file, "").unwrap();
+
+        let (path, policy) = Policy::discover(&file).unwrap().unwrap();
+        assert_eq!(path, repo.path().join(Policy::FILE_NAME));
+        assert_eq!(policy.max_tasks, Some(1));
+    }
+
+    #[test]
+    fn test_policy_discover_stops_at_repo_root() {
+        let outer = tempfile::tempdir().unwrap();
+        std::fs::write(outer.path().join(Policy::FILE_NAME), "max_tasks: 1").unwrap();
+        let repo = outer.path().join("repo");
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+
+        assert!(Policy::discover(&repo).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_policy_environments() {
+        let policy = Policy::from_yaml(
+            r#"
+environments:
+  prod:
+    id: env-prod1
+    cluster_id: lkc-prod1
+    schema_registry_cluster_id: lsrc-prod1
+    overrides:
+      tasks.max: 4
+  dev:
+    id: var.dev_environment_id
+    cluster_id: var.dev_cluster_id
+"#,
+        )
+        .unwrap();
+        let prod = policy.environment("prod").unwrap();
+        assert_eq!(prod.name, "prod");
+        assert_eq!(prod.cluster_id, "lkc-prod1");
+        assert_eq!(prod.schema_registry_cluster_id, "lsrc-prod1");
+        assert_eq!(
+            prod.overrides.get("tasks.max").map(String::as_str),
+            Some("4")
+        );
+        assert_eq!(
+            policy
+                .environment("dev")
+                .unwrap()
+                .schema_registry_cluster_id,
+            ""
+        );
+        assert_eq!(
+            policy.environment("qa").unwrap_err(),
+            "Unknown environment 'qa' (defined: dev, prod)"
+        );
+        assert!(Policy::default().environment("prod").is_err());
+
+        let result = Policy::from_yaml(
+            "environments:
+  prod:
+    id: env-1
+",
+        );
+        assert!(matches!(result, Err(ConnectUtilError::Config(_))));
+    }
+
+    #[test]
+    fn test_policy_ignore_changes() {
+        let policy = Policy::from_yaml(
+            r#"
+ignore_changes:
+  - status
+required_ignore_changes:
+  - config_nonsensitive["kafka.deployment.type"]
+"#,
+        )
+        .unwrap();
+        let required = "config_nonsensitive[\"kafka.deployment.type\"]";
+        assert_eq!(policy.ignore_changes(&[]), vec!["status", required]);
+        assert_eq!(
+            policy.ignore_changes(&[required.replace('[', " [")]),
+            vec![required.replace('[', " [")]
+        );
+        assert_eq!(
+            Policy::default().ignore_changes(&[]),
+            DEFAULT_IGNORE_CHANGES.to_vec()
+        );
+
+        assert!(policy
+            .check_ignore_changes("orders", &IgnoreChanges::All)
+            .is_empty());
+        assert_eq!(
+            policy.check_ignore_changes(
+                "orders",
+                &IgnoreChanges::Entries(vec!["status".to_string()])
+            ),
+            vec!["'orders' lifecycle.ignore_changes is missing required entry config_nonsensitive[\"kafka.deployment.type\"]"]
+        );
+    }
+
+    #[test]
+    fn test_policy_metadata() {
+        let policy = Policy::from_yaml(
+            r#"
+tokens:
+  team: payments
+metadata:
+  confluent.custom.tags: "team={{team}},connector={{connector}}"
+  owner: "{{team}}"
+templates:
+  owner: "{{team}}-oncall"
+"#,
+        )
+        .unwrap();
+        let expanded = policy
+            .expand_templates(&BTreeMap::from([(
+                "connector".to_string(),
+                "orders".to_string(),
+            )]))
+            .unwrap();
+        assert_eq!(
+            expanded["confluent.custom.tags"],
+            "team=payments,connector=orders"
+        );
+        assert_eq!(expanded["owner"], "payments-oncall");
+
+        let config = connector_config(&[("confluent.custom.tags", "team=payments")]);
+        assert_eq!(
+            policy.check(&config, Path::new("orders.tf")),
+            vec!["Missing metadata key: owner"]
+        );
+        let mut config = connector_config(&[("confluent.custom.tags", " ")]);
+        config
+            .sensitive_config
+            .insert("owner".to_string(), "payments".to_string());
+        assert_eq!(
+            policy.check(&config, Path::new("orders.tf")),
+            vec![
+                "Metadata key 'confluent.custom.tags' is empty",
+                "Metadata key 'owner' belongs in config_nonsensitive"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_policy_expand_templates() {
+        let policy = Policy::from_yaml(
+            r#"
+tokens:
+  team: payments
+  domain: finance
+templates:
+  name: "{{team}}-{{env}}-{{connector}}"
+  topics.dir: "{{domain}}/{{connector}}"
+"#,
+        )
+        .unwrap();
+        let tokens = |pairs: &[(&str, &str)]| -> BTreeMap<String, String> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+
+        let expanded = policy
+            .expand_templates(&tokens(&[("connector", "orders"), ("env", "prod")]))
+            .unwrap();
+        assert_eq!(expanded["name"], "payments-prod-orders");
+        assert_eq!(expanded["topics.dir"], "finance/orders");
+
+        let expanded = policy
+            .expand_templates(&tokens(&[
+                ("connector", "orders"),
+                ("env", "dev"),
+                ("team", "risk"),
+            ]))
+            .unwrap();
+        assert_eq!(expanded["name"], "risk-dev-orders");
+
+        let error = policy
+            .expand_templates(&tokens(&[("connector", "orders")]))
+            .unwrap_err();
+        assert!(error.contains("without a value: env"));
+        assert!(Policy::default()
+            .expand_templates(&BTreeMap::new())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_policy_rule_severities() {
+        let policy = Policy::from_yaml(
+            "severities:\n  unused-variable: error\n  connect_util_placeholder: off\n  policy: warning\n",
+        )
+        .unwrap();
+        assert_eq!(
+            policy.rule_severity(LintRule::UnusedVariable),
+            Some("error")
+        );
+        assert_eq!(
+            policy.rule_severity(LintRule::Scan(crate::scan::ScanRule::Placeholder)),
+            None
+        );
+        assert_eq!(policy.rule_severity(LintRule::Policy), Some("warning"));
+        assert_eq!(policy.rule_severity(LintRule::Conflict), Some("error"));
+
+        let error = Policy::from_yaml("severities:\n  no_such_rule: off\n").unwrap_err();
+        assert!(error.to_string().contains("unknown rule 'no_such_rule'"));
+        assert!(Policy::from_yaml("severities:\n  policy: info\n").is_err());
+    }
+
+    #[test]
+    fn test_describe_violation() {
+        assert_eq!(
+            NamingRules::describe_violation("bad".to_string(), Some("good".to_string())),
+            "bad (suggested: 'good')"
+        );
+        assert_eq!(
+            NamingRules::describe_violation("bad".to_string(), None),
+            "bad"
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use crate::types::{is_dynamic_value, ConnectorDefinition};
+use std::collections::HashMap;
+
+pub const INSERT_MODE: &str = "insert.mode";
+/// Where the sink takes each row's primary key from
+pub const PK_MODE: &str = "pk.mode";
+pub const PK_FIELDS: &str = "pk.fields";
+/// Format of the record key, which decides whether `record_key` reads a struct
+pub const INPUT_KEY_FORMAT: &str = "input.key.format";
+
+/// Key formats whose records carry a single primitive rather than a struct
+const PRIMITIVE_KEY_FORMATS: &[&str] = &["STRING", "BYTES"];
+
+/// The prefix a JDBC-style sink puts before its `pk.mode` and related keys, e.g.
+/// `alloydb.`; None for connectors without a primary key mode
+pub fn key_prefix(connector: &ConnectorDefinition) -> Option<&str> {
+    connector
+        .required_configs
+        .iter()
+        .chain(connector.optional_configs.iter())
+        .find_map(|field| field.name.strip_suffix(PK_MODE))
+        .filter(|prefix| prefix.is_empty() || prefix.ends_with('.'))
+}
+
+/// Checks a JDBC-style sink's insert and primary key settings agree: upserts and
+/// updates need a primary key, `pk.fields` only applies when there is one, the
+/// `kafka` mode names its three coordinate columns, and a primitive record key
+/// is written to a single column. Keys carry the connector's `prefix`
+pub fn check(prefix: &str, config_nonsensitive: &HashMap<String, String>) -> Result<(), String> {
+    let key = |name: &str| format!("{}{}", prefix, name);
+    let known = |name: &str| {
+        config_nonsensitive
+            .get(&key(name))
+            .filter(|value| !is_dynamic_value(value) && !value.contains("<REPLACE_WITH_"))
+    };
+    let dynamic = |name: &str| {
+        config_nonsensitive
+            .get(&key(name))
+            .is_some_and(|value| is_dynamic_value(value))
+    };
+    let insert_mode = known(INSERT_MODE).map(|mode| mode.to_lowercase());
+    let pk_mode = known(PK_MODE).map(|mode| mode.to_lowercase());
+    let pk_fields: Option<Vec<&str>> = known(PK_FIELDS).map(|fields| {
+        fields
+            .split(',')
+            .map(str::trim)
+            .filter(|field| !field.is_empty())
+            .collect()
+    });
+
+    if let Some(mode @ ("upsert" | "update")) = insert_mode.as_deref() {
+        if pk_mode.as_deref().unwrap_or("none") == "none" && !dynamic(PK_MODE) {
+            return Err(format!(
+                "'{}' {} needs a primary key; set '{}' to record_key, record_value, or kafka",
+                key(INSERT_MODE),
+                mode.to_uppercase(),
+                key(PK_MODE)
+            ));
+        }
+    }
+    let Some(fields) = pk_fields.filter(|fields| !fields.is_empty()) else {
+        return Ok(());
+    };
+    match pk_mode.as_deref() {
+        None if !dynamic(PK_MODE) => Err(format!(
+            "'{}' is ignored without '{}'; set it to record_key or record_value",
+            key(PK_FIELDS),
+            key(PK_MODE)
+        )),
+        Some("none") => Err(format!(
+            "'{}' is ignored when '{}' is none",
+            key(PK_FIELDS),
+            key(PK_MODE)
+        )),
+        Some("kafka") if fields.len() != 3 => Err(format!(
+            "With '{}' kafka, '{}' names the topic, partition, and offset columns, so it needs 3 names, not {}",
+            key(PK_MODE),
+            key(PK_FIELDS),
+            fields.len()
+        )),
+        Some("record_key") if fields.len() > 1 => {
+            match config_nonsensitive
+                .get(INPUT_KEY_FORMAT)
+                .filter(|format| PRIMITIVE_KEY_FORMATS.contains(&format.to_uppercase().as_str()))
+            {
+                Some(format) => Err(format!(
+                    "A {} record key is one value, so '{}' with '{}' record_key names a single column, not {}; use record_value to key on value fields",
+                    format,
+                    key(PK_FIELDS),
+                    key(PK_MODE),
+                    fields.join(", ")
+                )),
+                None => Ok(()),
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ConnectorDefinition;
+
+    fn config(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_key_prefix() {
+        let prefix = |name: &str| {
+            let connector = ConnectorDefinition::get_connector_by_name(name).unwrap();
+            key_prefix(&connector).map(str::to_string)
+        };
+        assert_eq!(prefix("JdbcSinkConnector").as_deref(), Some(""));
+        assert_eq!(prefix("AlloyDBSink").as_deref(), Some("alloydb."));
+        assert_eq!(prefix("S3_SINK"), None);
+    }
+
+    #[test]
+    fn test_check() {
+        assert!(check(
+            "",
+            &config(&[
+                (INSERT_MODE, "UPSERT"),
+                (PK_MODE, "record_value"),
+                (PK_FIELDS, "<REPLACE_WITH_PK_FIELDS>"),
+            ])
+        )
+        .is_ok());
+        assert!(check("", &config(&[(INSERT_MODE, "UPSERT")]))
+            .unwrap_err()
+            .contains("'insert.mode' UPSERT needs a primary key"));
+        assert!(check(
+            "alloydb.",
+            &config(&[
+                ("alloydb.insert.mode", "update"),
+                ("alloydb.pk.mode", "none")
+            ])
+        )
+        .unwrap_err()
+        .contains("set 'alloydb.pk.mode'"));
+        assert!(check(
+            "",
+            &config(&[(INSERT_MODE, "UPSERT"), (PK_MODE, "${var.pk_mode}")])
+        )
+        .is_ok());
+        assert!(check("", &config(&[(PK_MODE, "none"), (PK_FIELDS, "id")]))
+            .unwrap_err()
+            .contains("ignored when 'pk.mode' is none"));
+        assert!(check("", &config(&[(PK_FIELDS, "id")]))
+            .unwrap_err()
+            .contains("ignored without 'pk.mode'"));
+        assert!(
+            check("", &config(&[(PK_MODE, "kafka"), (PK_FIELDS, "topic")]))
+                .unwrap_err()
+                .contains("needs 3 names, not 1")
+        );
+        assert!(check(
+            "",
+            &config(&[
+                (PK_MODE, "kafka"),
+                (PK_FIELDS, "__topic, __partition, __offset")
+            ])
+        )
+        .is_ok());
+        assert!(check(
+            "",
+            &config(&[
+                (PK_MODE, "record_key"),
+                (PK_FIELDS, "order_id,line_no"),
+                (INPUT_KEY_FORMAT, "STRING"),
+            ])
+        )
+        .unwrap_err()
+        .contains("use record_value to key on value fields"));
+        assert!(check(
+            "",
+            &config(&[
+                (PK_MODE, "record_key"),
+                (PK_FIELDS, "order_id,line_no"),
+                (INPUT_KEY_FORMAT, "AVRO"),
+            ])
+        )
+        .is_ok());
+    }
+}
@@ -0,0 +1,104 @@
+use crate::lint::LintRule;
+use crate::scan::ScanRule;
+
+/// A named rule bundle for `validate --profile`, from the loosest checks while a
+/// connector is scaffolded to the release gate before it's applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationProfile {
+    /// Freshly generated connectors: placeholders and unfinished variables are expected
+    Scaffold,
+    /// Code review: placeholders are flagged but don't block the merge
+    PreMerge,
+    /// Release gate: no placeholders or unused declarations, plus the remote checks
+    PreDeploy,
+}
+
+impl std::str::FromStr for ValidationProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "scaffold" => Ok(ValidationProfile::Scaffold),
+            "pre-merge" => Ok(ValidationProfile::PreMerge),
+            "pre-deploy" => Ok(ValidationProfile::PreDeploy),
+            _ => Err(format!(
+                "Invalid profile '{}'. Use 'scaffold', 'pre-merge', or 'pre-deploy'",
+                s
+            )),
+        }
+    }
+}
+
+impl ValidationProfile {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ValidationProfile::Scaffold => "scaffold",
+            ValidationProfile::PreMerge => "pre-merge",
+            ValidationProfile::PreDeploy => "pre-deploy",
+        }
+    }
+
+    /// The severity the profile gives a rule before the policy file's `severities`
+    /// apply; `None` drops its findings
+    pub fn severity(&self, rule: LintRule) -> Option<&'static str> {
+        match (self, rule) {
+            (
+                ValidationProfile::Scaffold,
+                LintRule::Scan(ScanRule::Placeholder)
+                | LintRule::UnusedVariable
+                | LintRule::UnsetIgnoredKey
+                | LintRule::NoConnectors,
+            ) => None,
+            (ValidationProfile::Scaffold, LintRule::UndeclaredVariable) => Some("warning"),
+            (
+                ValidationProfile::PreDeploy,
+                LintRule::Scan(ScanRule::Placeholder)
+                | LintRule::UnusedVariable
+                | LintRule::UnsetIgnoredKey,
+            ) => Some("error"),
+            _ => Some(rule.severity()),
+        }
+    }
+
+    /// Whether the profile runs `--remote` or `--connect-url`; only the release
+    /// gate talks to a live cluster
+    pub fn runs_remote_checks(&self) -> bool {
+        *self == ValidationProfile::PreDeploy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles_tighten_placeholders() {
+        let placeholder = LintRule::Scan(ScanRule::Placeholder);
+        assert_eq!(ValidationProfile::Scaffold.severity(placeholder), None);
+        assert_eq!(
+            ValidationProfile::PreMerge.severity(placeholder),
+            Some("warning")
+        );
+        assert_eq!(
+            ValidationProfile::PreDeploy.severity(placeholder),
+            Some("error")
+        );
+        assert_eq!(
+            ValidationProfile::Scaffold.severity(LintRule::UndeclaredVariable),
+            Some("warning")
+        );
+        // Rules the profiles don't mention keep their own severity
+        for profile in ["scaffold", "pre-merge", "pre-deploy"] {
+            let profile: ValidationProfile = profile.parse().unwrap();
+            assert_eq!(profile.severity(LintRule::InvalidConfig), Some("error"));
+        }
+        assert!("release".parse::<ValidationProfile>().is_err());
+    }
+
+    #[test]
+    fn test_only_pre_deploy_runs_remote_checks() {
+        assert!(!ValidationProfile::Scaffold.runs_remote_checks());
+        assert!(!ValidationProfile::PreMerge.runs_remote_checks());
+        assert!(ValidationProfile::PreDeploy.runs_remote_checks());
+    }
+}
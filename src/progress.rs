@@ -0,0 +1,95 @@
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Bars currently on screen, cleared around each line of output
+static ACTIVE: Mutex<Option<MultiProgress>> = Mutex::new(None);
+
+/// Applies `--quiet`
+pub fn configure(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Whether progress is drawn: not `--quiet`, and stdout is a terminal
+pub fn enabled() -> bool {
+    !QUIET.load(Ordering::Relaxed) && console::Term::stdout().is_term()
+}
+
+/// Runs `print` with any bars cleared, so output lines don't tear them
+pub fn suspend<R>(print: impl FnOnce() -> R) -> R {
+    let active = ACTIVE.lock().unwrap().clone();
+    match active {
+        Some(multi) => multi.suspend(print),
+        None => print(),
+    }
+}
+
+/// An overall bar with a spinner naming the item in progress, drawn on stderr.
+/// Hidden when progress is disabled and removed when dropped
+pub struct Progress {
+    overall: ProgressBar,
+    current: ProgressBar,
+}
+
+impl Progress {
+    /// `unit` names what is counted, e.g. `files`
+    pub fn new(total: usize, unit: &str) -> Self {
+        if !enabled() {
+            return Self {
+                overall: ProgressBar::hidden(),
+                current: ProgressBar::hidden(),
+            };
+        }
+        let multi = MultiProgress::with_draw_target(ProgressDrawTarget::stderr());
+        let overall = multi.add(ProgressBar::new(total as u64));
+        overall.set_style(
+            ProgressStyle::with_template(&format!("{{bar:30}} {{pos}}/{{len}} {}", unit))
+                .expect("progress template is valid"),
+        );
+        let current = multi.add(ProgressBar::new_spinner());
+        current.enable_steady_tick(Duration::from_millis(100));
+        *ACTIVE.lock().unwrap() = Some(multi);
+        Self { overall, current }
+    }
+
+    /// Shows `item` next to the spinner
+    pub fn start(&self, item: impl Into<String>) {
+        self.current.set_message(item.into());
+    }
+
+    /// Counts one item as done
+    pub fn advance(&self) {
+        self.overall.inc(1);
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.current.finish_and_clear();
+        self.overall.finish_and_clear();
+        if !self.overall.is_hidden() {
+            *ACTIVE.lock().unwrap() = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quiet_hides_progress() {
+        configure(true);
+        assert!(!enabled());
+        let progress = Progress::new(2, "files");
+        progress.start("main.tf");
+        progress.advance();
+        assert!(progress.overall.is_hidden());
+        assert_eq!(progress.overall.position(), 1);
+        assert_eq!(suspend(|| 42), 42);
+        configure(false);
+    }
+}
@@ -0,0 +1,298 @@
+use crate::debezium;
+use crate::schema_registry;
+use crate::storage::{self, Partitioner};
+use crate::tables::{TABLE_EXCLUDE_LIST, TABLE_INCLUDE_LIST};
+use crate::terraform::TerraformGenerator;
+use crate::types::{ConnectorConfig, SecretsBackend, TerraformConfigOptions};
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// Where a generated config value came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueSource {
+    /// A `generate` flag such as `--topics`
+    Flag,
+    /// An answer in the interactive wizard
+    Prompt,
+    /// A template in `.connect-util.yaml`, filled in with the policy's or `--token` values
+    Policy,
+    /// The `overrides` of an environment profile
+    Environment,
+    /// A suggestion from the record schema of `--schema-file` or `--schema-subject`
+    Schema,
+    /// A value the catalog sets for one of the connector's fields
+    Catalog,
+    /// A generator default or placeholder
+    Default,
+}
+
+impl ValueSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ValueSource::Flag => "flag",
+            ValueSource::Prompt => "prompt",
+            ValueSource::Policy => "policy",
+            ValueSource::Environment => "environment",
+            ValueSource::Schema => "schema",
+            ValueSource::Catalog => "catalog",
+            ValueSource::Default => "default",
+        }
+    }
+}
+
+/// A generated config key, its value, and where the value came from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValueProvenance {
+    pub key: String,
+    /// None for `config_sensitive` keys, whose values aren't reported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    pub sensitive: bool,
+    pub source: ValueSource,
+}
+
+/// JSON summary of a `generate` run for `--report`
+#[derive(Debug, Clone, Serialize)]
+pub struct GenerationReport {
+    pub connector_name: String,
+    pub connector: String,
+    /// Files written, or empty when the output went to stdout
+    pub files: Vec<String>,
+    pub values: Vec<ValueProvenance>,
+}
+
+/// Traces every key of a generated connector config back to the input that set it,
+/// checked in the order the generator applies them: policy templates win over
+/// environment overrides, which win over schema suggestions, then requested and
+/// catalog values. `prompted`
+/// holds the keys answered in the wizard rather than passed as flags
+pub fn trace(
+    options: &TerraformConfigOptions,
+    config: &ConnectorConfig,
+    prompted: &BTreeSet<String>,
+) -> Vec<ValueProvenance> {
+    let requested = requested_keys(options);
+    let overridden: BTreeSet<&str> = options
+        .environment
+        .iter()
+        .chain(options.for_each_environments.iter())
+        .flat_map(|environment| environment.overrides.keys())
+        .map(String::as_str)
+        .collect();
+    let source = |key: &str| {
+        if options.template_values.contains_key(key) {
+            ValueSource::Policy
+        } else if overridden.contains(key) {
+            ValueSource::Environment
+        } else if options.schema_values.contains_key(key) {
+            ValueSource::Schema
+        } else if requested.contains(key) {
+            if prompted.contains(key) {
+                ValueSource::Prompt
+            } else {
+                ValueSource::Flag
+            }
+        } else if key == "connector.class" || options.connector.has_field(key) {
+            ValueSource::Catalog
+        } else {
+            ValueSource::Default
+        }
+    };
+
+    let mut values: Vec<ValueProvenance> = config
+        .config
+        .iter()
+        .map(|(key, value)| (key, Some(value.clone()), false))
+        .chain(config.sensitive_config.keys().map(|key| (key, None, true)))
+        .map(|(key, value, sensitive)| ValueProvenance {
+            key: key.clone(),
+            value,
+            sensitive,
+            source: source(key),
+        })
+        .collect();
+    values.sort_by(|a, b| a.key.cmp(&b.key));
+    values
+}
+
+/// Keys whose values come from the options the user supplied
+fn requested_keys(options: &TerraformConfigOptions) -> BTreeSet<String> {
+    let mut keys = BTreeSet::from(["name".to_string()]);
+    let mut add = |set: bool, key: &str| {
+        if set {
+            keys.insert(key.to_string());
+        }
+    };
+    add(
+        !options.topics.is_empty() || options.topic_prefix.is_some(),
+        options.connector.topic_strategy.key(),
+    );
+    add(!options.tables.is_empty(), TABLE_INCLUDE_LIST);
+    add(!options.exclude_tables.is_empty(), TABLE_EXCLUDE_LIST);
+    if let Some(partitioner) = options.storage.partitioner {
+        add(true, storage::PARTITIONER_CLASS);
+        add(
+            partitioner == Partitioner::Field,
+            storage::PARTITION_FIELD_NAME,
+        );
+        add(
+            partitioner == Partitioner::Time || options.storage.path_format.is_some(),
+            storage::PATH_FORMAT,
+        );
+        add(options.storage.locale.is_some(), storage::LOCALE);
+        add(options.storage.timezone.is_some(), storage::TIMEZONE);
+    }
+    let heartbeats = &options.debezium;
+    add(
+        heartbeats.heartbeat_interval_ms.is_some() || heartbeats.heartbeat_table.is_some(),
+        debezium::HEARTBEAT_INTERVAL_MS,
+    );
+    add(
+        heartbeats.heartbeat_table.is_some(),
+        debezium::HEARTBEAT_ACTION_QUERY,
+    );
+    add(
+        heartbeats.signal_table.is_some(),
+        debezium::SIGNAL_DATA_COLLECTION,
+    );
+    add(
+        heartbeats.snapshot_chunk_size.is_some(),
+        debezium::INCREMENTAL_SNAPSHOT_CHUNK_SIZE,
+    );
+    add(options.input_data_format.is_some(), "input.data.format");
+    add(options.output_data_format.is_some(), "output.data.format");
+    add(
+        options.schema_registry.context.is_some(),
+        schema_registry::SCHEMA_CONTEXT,
+    );
+    for key in [
+        schema_registry::CSFLE_ENABLED,
+        schema_registry::CSFLE_ON_FAILURE,
+    ] {
+        add(options.schema_registry.csfle, key);
+    }
+    for key in ["kafka.auth.mode", "kafka.api.key", "kafka.api.secret"] {
+        add(options.companions.api_key, key);
+    }
+    add(
+        options.companions.service_account && !options.companions.api_key,
+        "kafka.service.account.id",
+    );
+    if options.secrets_backend != SecretsBackend::Inline {
+        keys.extend(TerraformGenerator::secret_keys(options));
+    }
+    keys
+}
+
+/// `--verbose` lines, one per key, e.g. `  tasks.max = "1" (default)`
+pub fn render_text(values: &[ValueProvenance]) -> String {
+    values
+        .iter()
+        .map(|entry| match &entry.value {
+            Some(value) => format!("  {} = {:?} ({})", entry.key, value, entry.source.label()),
+            None => format!("  {} = (sensitive) ({})", entry.key, entry.source.label()),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ConnectorDefinition, Environment};
+    use std::collections::{BTreeMap, HashMap};
+
+    fn config(values: &[(&str, &str)], sensitive: &[&str]) -> ConnectorConfig {
+        ConnectorConfig {
+            name: "orders-cdc".to_string(),
+            connector_class: "PostgresCdcSourceV2".to_string(),
+            config: values
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            sensitive_config: sensitive
+                .iter()
+                .map(|k| (k.to_string(), "<REPLACE_WITH_ACTUAL_VALUE>".to_string()))
+                .collect::<HashMap<_, _>>(),
+        }
+    }
+
+    #[test]
+    fn test_trace() {
+        let options = TerraformConfigOptions {
+            connector_name: "orders-cdc".to_string(),
+            connector: ConnectorDefinition::get_connector_by_name("PostgresCdcSourceV2").unwrap(),
+            topic_prefix: Some("orders".to_string()),
+            tables: vec!["public.orders".to_string()],
+            environment: Some(Environment {
+                name: "prod".to_string(),
+                id: "env-abc123".to_string(),
+                cluster_id: "lkc-abc123".to_string(),
+                schema_registry_cluster_id: String::new(),
+                cluster_type: None,
+                overrides: BTreeMap::from([("tasks.max".to_string(), "4".to_string())]),
+            }),
+            template_values: BTreeMap::from([(
+                "database.hostname".to_string(),
+                "db.prod.internal".to_string(),
+            )]),
+            ..Default::default()
+        };
+        let config = config(
+            &[
+                ("connector.class", "PostgresCdcSourceV2"),
+                ("name", "orders-cdc"),
+                ("topic.prefix", "orders"),
+                ("table.include.list", "public.orders"),
+                ("database.hostname", "db.prod.internal"),
+                ("database.port", "5432"),
+                ("tasks.max", "4"),
+                ("kafka.deployment.type", "DEDICATED"),
+            ],
+            &["database.password"],
+        );
+        let prompted = BTreeSet::from(["topic.prefix".to_string()]);
+
+        let values = trace(&options, &config, &prompted);
+        let sources: Vec<(&str, ValueSource)> = values
+            .iter()
+            .map(|entry| (entry.key.as_str(), entry.source))
+            .collect();
+        assert_eq!(
+            sources,
+            vec![
+                ("connector.class", ValueSource::Catalog),
+                ("database.hostname", ValueSource::Policy),
+                ("database.password", ValueSource::Default),
+                ("database.port", ValueSource::Catalog),
+                ("kafka.deployment.type", ValueSource::Default),
+                ("name", ValueSource::Flag),
+                ("table.include.list", ValueSource::Flag),
+                ("tasks.max", ValueSource::Environment),
+                ("topic.prefix", ValueSource::Prompt),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_text_hides_sensitive_values() {
+        let values = trace(
+            &TerraformConfigOptions {
+                connector: ConnectorDefinition::get_connector_by_name("PostgresCdcSourceV2")
+                    .unwrap(),
+                ..Default::default()
+            },
+            &config(&[("tasks.max", "1")], &["database.password"]),
+            &BTreeSet::new(),
+        );
+        let text = render_text(&values);
+        assert!(text.contains("  database.password = (sensitive) (default)"));
+        assert!(text.contains("  tasks.max = \"1\" (default)"));
+        assert!(!text.contains("REPLACE_WITH_ACTUAL_VALUE"));
+
+        let json = serde_json::to_value(&values).unwrap();
+        assert_eq!(json[0]["source"], "default");
+        assert!(json[0].get("value").is_none());
+    }
+}
@@ -0,0 +1,66 @@
+use crate::error::ConnectUtilError;
+use std::path::{Path, PathBuf};
+
+/// Connectors remembered for the top of the interactive picker
+pub const LIMIT: usize = 5;
+
+/// `$XDG_STATE_HOME/connect-util/recent-connectors`, falling back to
+/// `~/.local/state`. `None` when neither variable is set
+pub fn default_path() -> Option<PathBuf> {
+    let state_home = std::env::var_os("XDG_STATE_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME")
+                .filter(|v| !v.is_empty())
+                .map(|home| PathBuf::from(home).join(".local").join("state"))
+        })?;
+    Some(state_home.join("connect-util").join("recent-connectors"))
+}
+
+/// Connector names, most recently used first. A missing or unreadable file is
+/// treated as no history
+pub fn load(path: &Path) -> Vec<String> {
+    std::fs::read_to_string(path)
+        .map(|content| {
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .take(LIMIT)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Moves `name` to the front of the history, dropping the oldest entries past `LIMIT`
+pub fn record(path: &Path, name: &str) -> Result<(), ConnectUtilError> {
+    let mut names = load(path);
+    names.retain(|existing| existing != name);
+    names.insert(0, name.to_string());
+    names.truncate(LIMIT);
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, names.join("\n") + "\n")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_orders_and_caps_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state").join("recent-connectors");
+        assert!(load(&path).is_empty());
+
+        for name in ["a", "b", "c", "d", "e", "f", "c"] {
+            record(&path, name).unwrap();
+        }
+        assert_eq!(load(&path), vec!["c", "f", "e", "d", "b"]);
+    }
+}
@@ -0,0 +1,758 @@
+use crate::error::ConnectUtilError;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// A field type as far as a JDBC sink's column mapping is concerned
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldType {
+    Boolean,
+    Int,
+    Long,
+    /// Protobuf `uint64`/`fixed64`, wider than a signed BIGINT
+    UnsignedLong,
+    Float,
+    Double,
+    String,
+    Bytes,
+    Decimal {
+        precision: Option<u32>,
+        scale: u32,
+    },
+    Date,
+    Time,
+    Timestamp,
+    /// Avro `timestamp-micros`, which the converter writes as a plain long
+    TimestampMicros,
+    Enum,
+    Array,
+    Map,
+    Record,
+    /// A union of more than one non-null type
+    Union,
+}
+
+/// A top-level field of the record a sink writes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaField {
+    pub name: String,
+    pub field_type: FieldType,
+    pub optional: bool,
+}
+
+/// The record schema behind a sink's topic, read from an `.avsc` or `.proto` file
+/// or a Schema Registry subject
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordSchema {
+    pub name: String,
+    pub fields: Vec<SchemaField>,
+}
+
+impl RecordSchema {
+    /// Parses a schema by the Schema Registry type: `AVRO` or `PROTOBUF`
+    pub fn parse(schema: &str, schema_type: &str) -> Result<Self, String> {
+        match schema_type.to_uppercase().as_str() {
+            "AVRO" => parse_avro(schema),
+            "PROTOBUF" => parse_protobuf(schema),
+            other => Err(format!(
+                "Unsupported schema type '{}'; use an Avro or Protobuf schema",
+                other
+            )),
+        }
+    }
+
+    /// Reads a schema file, picking the format by extension: `.proto` is Protobuf,
+    /// anything else (`.avsc`, `.json`) Avro
+    pub fn from_file(path: &str) -> Result<Self, ConnectUtilError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            ConnectUtilError::Config(format!("Failed to read schema file {}: {}", path, e))
+        })?;
+        let schema_type = match Path::new(path).extension().and_then(|e| e.to_str()) {
+            Some("proto") => "PROTOBUF",
+            _ => "AVRO",
+        };
+        Self::parse(&content, schema_type)
+            .map_err(|e| ConnectUtilError::Config(format!("{}: {}", path, e)))
+    }
+
+    /// The field a sink should key rows on: `id`, or the record's own `<name>_id`
+    /// or `<name>Id`
+    pub fn primary_key(&self) -> Option<&SchemaField> {
+        let snake = snake_case(&self.name);
+        let camel = format!("{}Id", lower_first(&self.name));
+        ["id".to_string(), format!("{}_id", snake), camel]
+            .iter()
+            .find_map(|candidate| self.fields.iter().find(|field| &field.name == candidate))
+            .filter(|field| is_scalar(&field.field_type))
+    }
+
+    /// `table.name.format` for the record, its name in snake case
+    pub fn table_name(&self) -> String {
+        snake_case(&self.name)
+    }
+}
+
+/// Suggested sink values and the type-mapping issues found for a record schema
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaSuggestions {
+    pub values: BTreeMap<String, String>,
+    pub issues: Vec<String>,
+}
+
+/// Suggests `pk.fields`, `pk.mode`, and `table.name.format` under the given keys
+/// (which carry the connector's prefix, e.g. `alloydb.pk.fields`), and flags
+/// fields the sink's database can't hold as one column
+pub fn suggest(
+    schema: &RecordSchema,
+    pk_fields_key: &str,
+    pk_mode_key: Option<&str>,
+    table_name_key: Option<&str>,
+    connector_name: &str,
+) -> SchemaSuggestions {
+    let mut suggestions = SchemaSuggestions::default();
+    match schema.primary_key() {
+        Some(field) => {
+            suggestions
+                .values
+                .insert(pk_fields_key.to_string(), field.name.clone());
+            if let Some(key) = pk_mode_key {
+                suggestions
+                    .values
+                    .insert(key.to_string(), "record_value".to_string());
+            }
+            if field.optional {
+                suggestions.issues.push(format!(
+                    "primary key field '{}' is optional, but key columns must be NOT NULL",
+                    field.name
+                ));
+            }
+        }
+        None => suggestions.issues.push(format!(
+            "{} has no 'id' field to key rows on; fill in {} by hand",
+            schema.name, pk_fields_key
+        )),
+    }
+    if let Some(key) = table_name_key {
+        let table = schema.table_name();
+        if RESERVED_WORDS.contains(&table.as_str()) {
+            suggestions.issues.push(format!(
+                "table name '{}' is an SQL reserved word; quote it or choose another in {}",
+                table, key
+            ));
+        }
+        suggestions.values.insert(key.to_string(), table);
+    }
+
+    let max_precision = max_decimal_precision(connector_name);
+    for field in &schema.fields {
+        let issue = match &field.field_type {
+            FieldType::Record | FieldType::Array | FieldType::Map => Some(format!(
+                "field '{}' is a nested {}; JDBC sinks write flat rows, so flatten it (e.g. with a Flatten transform) or drop it",
+                field.name,
+                match field.field_type {
+                    FieldType::Record => "record",
+                    FieldType::Array => "array",
+                    _ => "map",
+                }
+            )),
+            FieldType::Union => Some(format!(
+                "field '{}' is a union of several types, which no single column can hold",
+                field.name
+            )),
+            FieldType::Decimal {
+                precision: Some(precision),
+                ..
+            } if *precision > max_precision => Some(format!(
+                "decimal field '{}' has precision {}, more than the {} digits a {} numeric column holds",
+                field.name, precision, max_precision, connector_name
+            )),
+            FieldType::TimestampMicros => Some(format!(
+                "field '{}' is timestamp-micros, which is written as a BIGINT rather than a timestamp column",
+                field.name
+            )),
+            FieldType::UnsignedLong => Some(format!(
+                "field '{}' is an unsigned 64-bit integer, which can overflow a signed BIGINT column",
+                field.name
+            )),
+            _ => None,
+        };
+        suggestions.issues.extend(issue);
+    }
+    suggestions
+}
+
+/// Reserved words that commonly collide with table names
+const RESERVED_WORDS: &[&str] = &[
+    "order",
+    "user",
+    "group",
+    "table",
+    "select",
+    "where",
+    "from",
+    "index",
+    "key",
+    "check",
+    "column",
+    "default",
+    "grant",
+    "limit",
+    "offset",
+    "references",
+    "session",
+    "transaction",
+];
+
+/// The widest decimal a sink's database stores as a numeric column
+fn max_decimal_precision(connector_name: &str) -> u32 {
+    let name = connector_name.to_lowercase();
+    if name.contains("postgres") || name.contains("alloydb") {
+        1000
+    } else if name.contains("mysql") {
+        65
+    } else {
+        38
+    }
+}
+
+fn is_scalar(field_type: &FieldType) -> bool {
+    !matches!(
+        field_type,
+        FieldType::Record | FieldType::Array | FieldType::Map | FieldType::Union
+    )
+}
+
+/// Loads the schema from `--schema-file`, or the latest version of
+/// `--schema-subject` from the registry at `registry_url` or `SCHEMA_REGISTRY_URL`
+pub async fn load(
+    file: Option<&str>,
+    subject: Option<&str>,
+    registry_url: Option<&str>,
+) -> Result<Option<RecordSchema>, ConnectUtilError> {
+    if let Some(file) = file {
+        return RecordSchema::from_file(file).map(Some);
+    }
+    let Some(subject) = subject else {
+        return Ok(None);
+    };
+    let url = match registry_url {
+        Some(url) => url.to_string(),
+        None => std::env::var("SCHEMA_REGISTRY_URL").map_err(|_| {
+            ConnectUtilError::Config(
+                "--schema-subject needs --schema-registry-url or SCHEMA_REGISTRY_URL".to_string(),
+            )
+        })?,
+    };
+    fetch_subject(&url, subject).await.map(Some)
+}
+
+/// The latest version of a Schema Registry subject
+#[derive(Debug, Deserialize)]
+struct SubjectVersion {
+    schema: String,
+    /// Absent for Avro, which is the registry's default
+    #[serde(rename = "schemaType", default = "default_schema_type")]
+    schema_type: String,
+}
+
+fn default_schema_type() -> String {
+    "AVRO".to_string()
+}
+
+/// Parses a `GET /subjects/{subject}/versions/latest` response
+pub fn parse_subject_version(json: &str) -> Result<RecordSchema, ConnectUtilError> {
+    let version: SubjectVersion = serde_json::from_str(json)?;
+    RecordSchema::parse(&version.schema, &version.schema_type).map_err(ConnectUtilError::Config)
+}
+
+/// Fetches the latest schema of `subject`, authenticating with
+/// `SCHEMA_REGISTRY_API_KEY` and `SCHEMA_REGISTRY_API_SECRET` when both are set
+pub async fn fetch_subject(url: &str, subject: &str) -> Result<RecordSchema, ConnectUtilError> {
+    let path = format!("/subjects/{}/versions/latest", subject);
+    let url = format!("{}{}", url.trim_end_matches('/'), path);
+    let mut request = reqwest::Client::new().get(&url);
+    let var = |name| std::env::var(name).ok().filter(|v: &String| !v.is_empty());
+    if let (Some(key), Some(secret)) = (
+        var("SCHEMA_REGISTRY_API_KEY"),
+        var("SCHEMA_REGISTRY_API_SECRET"),
+    ) {
+        request = request.basic_auth(key, Some(secret));
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| ConnectUtilError::Unknown(format!("Request to {} failed: {}", url, e)))?;
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    if !status.is_success() {
+        return Err(ConnectUtilError::Config(format!(
+            "Schema Registry returned {} for {}: {}",
+            status, path, body
+        )));
+    }
+    parse_subject_version(&body)
+}
+
+fn parse_avro(schema: &str) -> Result<RecordSchema, String> {
+    let value: Value =
+        serde_json::from_str(schema).map_err(|e| format!("Invalid Avro schema: {}", e))?;
+    if value.get("type").and_then(Value::as_str) != Some("record") {
+        return Err("The Avro schema must be a record".to_string());
+    }
+    let name = value
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or("The Avro record has no name")?;
+    let mut enums = BTreeSet::new();
+    let fields = value
+        .get("fields")
+        .and_then(Value::as_array)
+        .ok_or("The Avro record has no fields")?
+        .iter()
+        .map(|field| {
+            let name = field
+                .get("name")
+                .and_then(Value::as_str)
+                .ok_or("An Avro field has no name")?;
+            let (field_type, optional) =
+                avro_type(field.get("type").unwrap_or(&Value::Null), &mut enums);
+            Ok(SchemaField {
+                name: name.to_string(),
+                field_type,
+                optional,
+            })
+        })
+        .collect::<Result<_, String>>()?;
+    Ok(RecordSchema {
+        name: name.to_string(),
+        fields,
+    })
+}
+
+/// An Avro type and whether a union makes it nullable. Enums named earlier in the
+/// schema are remembered so later references to them resolve
+fn avro_type(value: &Value, enums: &mut BTreeSet<String>) -> (FieldType, bool) {
+    match value {
+        Value::String(name) => (avro_primitive(name, enums), false),
+        Value::Array(branches) => {
+            let types: Vec<&Value> = branches
+                .iter()
+                .filter(|b| b.as_str() != Some("null"))
+                .collect();
+            let optional = types.len() < branches.len();
+            match types.as_slice() {
+                [single] => (avro_type(single, enums).0, optional),
+                _ => (FieldType::Union, optional),
+            }
+        }
+        Value::Object(object) => {
+            let get = |key: &str| object.get(key).and_then(Value::as_str);
+            let field_type = match (get("logicalType"), get("type")) {
+                (Some("decimal"), _) => FieldType::Decimal {
+                    precision: object
+                        .get("precision")
+                        .and_then(Value::as_u64)
+                        .map(|p| p as u32),
+                    scale: object.get("scale").and_then(Value::as_u64).unwrap_or(0) as u32,
+                },
+                (Some("date"), _) => FieldType::Date,
+                (Some("time-millis" | "time-micros"), _) => FieldType::Time,
+                (Some("timestamp-millis" | "local-timestamp-millis"), _) => FieldType::Timestamp,
+                (Some("timestamp-micros" | "local-timestamp-micros"), _) => {
+                    FieldType::TimestampMicros
+                }
+                (Some("uuid"), _) => FieldType::String,
+                (_, Some("enum")) => {
+                    if let Some(name) = get("name") {
+                        enums.insert(name.to_string());
+                    }
+                    FieldType::Enum
+                }
+                (_, Some("fixed")) => FieldType::Bytes,
+                (_, Some(name)) => avro_primitive(name, enums),
+                (_, None) => FieldType::Record,
+            };
+            (field_type, false)
+        }
+        _ => (FieldType::Record, false),
+    }
+}
+
+fn avro_primitive(name: &str, enums: &BTreeSet<String>) -> FieldType {
+    match name {
+        "boolean" => FieldType::Boolean,
+        "int" => FieldType::Int,
+        "long" => FieldType::Long,
+        "float" => FieldType::Float,
+        "double" => FieldType::Double,
+        "string" => FieldType::String,
+        "bytes" => FieldType::Bytes,
+        "array" => FieldType::Array,
+        "map" => FieldType::Map,
+        other if enums.contains(other) => FieldType::Enum,
+        _ => FieldType::Record,
+    }
+}
+
+/// Reads the first message of a `.proto` file, declared enums resolving to `Enum`
+/// and other message types to `Record`
+fn parse_protobuf(schema: &str) -> Result<RecordSchema, String> {
+    let source = strip_proto_comments(schema);
+    let enums: BTreeSet<String> = declared_names(&source, "enum");
+    let start = find_keyword(&source, "message").ok_or("The .proto file has no message")?;
+    let header = &source[start + "message".len()..];
+    let open = header.find('{').ok_or("The message has no body")?;
+    let name = header[..open].trim().to_string();
+    let body = &header[open + 1..];
+
+    let mut fields = Vec::new();
+    let mut depth = 0usize;
+    let mut statement = String::new();
+    let mut oneof = false;
+    for c in body.chars() {
+        match c {
+            '{' => {
+                if depth == 0 && statement.trim_start().starts_with("oneof") {
+                    oneof = true;
+                    statement.clear();
+                } else {
+                    depth += 1;
+                }
+            }
+            '}' if depth == 0 && oneof => oneof = false,
+            '}' if depth == 0 => break,
+            '}' => {
+                depth -= 1;
+                statement.clear();
+            }
+            ';' if depth == 0 => {
+                fields.extend(proto_field(&statement, oneof, &enums));
+                statement.clear();
+            }
+            _ if depth == 0 => statement.push(c),
+            _ => {}
+        }
+    }
+    Ok(RecordSchema { name, fields })
+}
+
+/// A `[label] type name = N [options]` field statement; `None` for options,
+/// reserved ranges, and anything else that isn't a field
+fn proto_field(statement: &str, oneof: bool, enums: &BTreeSet<String>) -> Option<SchemaField> {
+    let (declaration, _) = statement.split_once('=')?;
+    let declaration = declaration.trim();
+    if declaration.starts_with("map<") {
+        let name = declaration
+            .rsplit(|c: char| c.is_whitespace() || c == '>')
+            .next()?;
+        return Some(SchemaField {
+            name: name.to_string(),
+            field_type: FieldType::Map,
+            optional: true,
+        });
+    }
+    let words: Vec<&str> = declaration.split_whitespace().collect();
+    let (label, type_name, name) = match words.as_slice() {
+        [label, type_name, name] => (Some(*label), *type_name, *name),
+        [type_name, name] => (None, *type_name, *name),
+        _ => return None,
+    };
+    if matches!(type_name, "option" | "reserved" | "extensions") {
+        return None;
+    }
+    let (field_type, wrapper) = proto_type(type_name, enums);
+    let field_type = if label == Some("repeated") {
+        FieldType::Array
+    } else {
+        field_type
+    };
+    Some(SchemaField {
+        name: name.to_string(),
+        optional: oneof
+            || wrapper
+            || label == Some("optional")
+            || matches!(field_type, FieldType::Record | FieldType::Timestamp),
+        field_type,
+    })
+}
+
+/// A Protobuf type, and whether it's a nullable `google.protobuf` wrapper
+fn proto_type(type_name: &str, enums: &BTreeSet<String>) -> (FieldType, bool) {
+    let short = type_name.rsplit('.').next().unwrap_or(type_name);
+    let field_type = match type_name {
+        "int32" | "sint32" | "sfixed32" => FieldType::Int,
+        "uint32" | "fixed32" | "int64" | "sint64" | "sfixed64" => FieldType::Long,
+        "uint64" | "fixed64" => FieldType::UnsignedLong,
+        "float" => FieldType::Float,
+        "double" => FieldType::Double,
+        "bool" => FieldType::Boolean,
+        "string" => FieldType::String,
+        "bytes" => FieldType::Bytes,
+        "google.protobuf.Timestamp" => FieldType::Timestamp,
+        "google.type.Date" => FieldType::Date,
+        "google.type.TimeOfDay" => FieldType::Time,
+        "confluent.type.Decimal" => FieldType::Decimal {
+            precision: None,
+            scale: 0,
+        },
+        _ if type_name.starts_with("google.protobuf.") && short.ends_with("Value") => {
+            let field_type = match short {
+                "BoolValue" => FieldType::Boolean,
+                "Int32Value" => FieldType::Int,
+                "Int64Value" | "UInt32Value" => FieldType::Long,
+                "UInt64Value" => FieldType::UnsignedLong,
+                "FloatValue" => FieldType::Float,
+                "DoubleValue" => FieldType::Double,
+                "BytesValue" => FieldType::Bytes,
+                _ => FieldType::String,
+            };
+            return (field_type, true);
+        }
+        _ if enums.contains(short) => FieldType::Enum,
+        _ => FieldType::Record,
+    };
+    (field_type, false)
+}
+
+fn strip_proto_comments(source: &str) -> String {
+    let mut stripped = String::with_capacity(source.len());
+    let mut rest = source;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("//") {
+            rest = after.find('\n').map_or("", |end| &after[end..]);
+        } else if let Some(after) = rest.strip_prefix("/*") {
+            rest = after.find("*/").map_or("", |end| &after[end + 2..]);
+        } else {
+            let c = rest.chars().next().unwrap_or_default();
+            stripped.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    stripped
+}
+
+/// Where `keyword` starts as a whole word
+fn find_keyword(source: &str, keyword: &str) -> Option<usize> {
+    source.match_indices(keyword).map(|(i, _)| i).find(|&i| {
+        let before = source[..i].chars().next_back();
+        let after = source[i + keyword.len()..].chars().next();
+        !before.is_some_and(|c| c.is_alphanumeric() || c == '_')
+            && after.is_some_and(char::is_whitespace)
+    })
+}
+
+/// Names declared with `keyword`, at any nesting level
+fn declared_names(source: &str, keyword: &str) -> BTreeSet<String> {
+    let words: Vec<&str> = source
+        .split(|c: char| c.is_whitespace() || c == '{')
+        .filter(|w| !w.is_empty())
+        .collect();
+    words
+        .windows(2)
+        .filter(|pair| pair[0] == keyword)
+        .map(|pair| pair[1].to_string())
+        .collect()
+}
+
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else if c.is_alphanumeric() {
+            snake.push(c);
+        } else if !snake.ends_with('_') {
+            snake.push('_');
+        }
+    }
+    snake
+}
+
+fn lower_first(name: &str) -> String {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|first| first.to_lowercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORDER_AVRO: &str = r#"{
+        "type": "record",
+        "name": "OrderLine",
+        "namespace": "com.example",
+        "fields": [
+            {"name": "order_line_id", "type": "long"},
+            {"name": "status", "type": {"type": "enum", "name": "Status", "symbols": ["NEW", "PAID"]}},
+            {"name": "previous_status", "type": ["null", "Status"]},
+            {"name": "amount", "type": {"type": "bytes", "logicalType": "decimal", "precision": 40, "scale": 2}},
+            {"name": "created_at", "type": {"type": "long", "logicalType": "timestamp-micros"}},
+            {"name": "tags", "type": {"type": "array", "items": "string"}},
+            {"name": "note", "type": ["null", "string", "int"]}
+        ]
+    }"#;
+
+    #[test]
+    fn test_parse_avro() {
+        let schema = RecordSchema::parse(ORDER_AVRO, "AVRO").unwrap();
+        assert_eq!(schema.name, "OrderLine");
+        let types: Vec<(&str, &FieldType, bool)> = schema
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), &f.field_type, f.optional))
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                ("order_line_id", &FieldType::Long, false),
+                ("status", &FieldType::Enum, false),
+                ("previous_status", &FieldType::Enum, true),
+                (
+                    "amount",
+                    &FieldType::Decimal {
+                        precision: Some(40),
+                        scale: 2
+                    },
+                    false
+                ),
+                ("created_at", &FieldType::TimestampMicros, false),
+                ("tags", &FieldType::Array, false),
+                ("note", &FieldType::Union, true),
+            ]
+        );
+        assert_eq!(schema.primary_key().unwrap().name, "order_line_id");
+        assert_eq!(schema.table_name(), "order_line");
+
+        assert!(RecordSchema::parse(r#"{"type": "string"}"#, "AVRO").is_err());
+        assert!(RecordSchema::parse("{", "AVRO").is_err());
+        assert!(RecordSchema::parse("{}", "JSON").is_err());
+    }
+
+    #[test]
+    fn test_parse_protobuf() {
+        let proto = r#"
+            syntax = "proto3";
+            package com.example;
+            import "google/protobuf/timestamp.proto";
+
+            // An order as the checkout service publishes it
+            message Customer {
+                string id = 1;
+                Address address = 2; /* nested message */
+                message Address {
+                    string city = 1;
+                }
+                map<string, string> labels = 3;
+                repeated string emails = 4;
+                google.protobuf.Timestamp created_at = 5;
+                google.protobuf.StringValue nickname = 6;
+                uint64 lifetime_cents = 7;
+                Tier tier = 8;
+                oneof contact {
+                    string phone = 9;
+                    string fax = 10;
+                }
+                reserved 11;
+            }
+            enum Tier { FREE = 0; PAID = 1; }
+        "#;
+        let schema = RecordSchema::parse(proto, "PROTOBUF").unwrap();
+        assert_eq!(schema.name, "Customer");
+        let types: Vec<(&str, &FieldType, bool)> = schema
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), &f.field_type, f.optional))
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                ("id", &FieldType::String, false),
+                ("address", &FieldType::Record, true),
+                ("labels", &FieldType::Map, true),
+                ("emails", &FieldType::Array, false),
+                ("created_at", &FieldType::Timestamp, true),
+                ("nickname", &FieldType::String, true),
+                ("lifetime_cents", &FieldType::UnsignedLong, false),
+                ("tier", &FieldType::Enum, false),
+                ("phone", &FieldType::String, true),
+                ("fax", &FieldType::String, true),
+            ]
+        );
+        assert_eq!(schema.primary_key().unwrap().name, "id");
+    }
+
+    #[test]
+    fn test_suggest() {
+        let schema = RecordSchema::parse(ORDER_AVRO, "AVRO").unwrap();
+        let suggestions = suggest(
+            &schema,
+            "pk.fields",
+            Some("pk.mode"),
+            Some("table.name.format"),
+            "MicrosoftSqlServerSink",
+        );
+        assert_eq!(
+            suggestions.values,
+            BTreeMap::from([
+                ("pk.fields".to_string(), "order_line_id".to_string()),
+                ("pk.mode".to_string(), "record_value".to_string()),
+                ("table.name.format".to_string(), "order_line".to_string()),
+            ])
+        );
+        assert_eq!(suggestions.issues.len(), 4);
+        assert!(suggestions.issues[0].contains("'amount' has precision 40"));
+        assert!(suggestions.issues[1].contains("'created_at' is timestamp-micros"));
+        assert!(suggestions.issues[2].contains("'tags' is a nested array"));
+        assert!(suggestions.issues[3].contains("'note' is a union"));
+        // PostgreSQL numerics hold far more digits
+        let issues = suggest(&schema, "pk.fields", None, None, "PostgresSink").issues;
+        assert_eq!(issues.len(), 3);
+        assert!(!issues.iter().any(|issue| issue.contains("'amount'")));
+
+        let order = RecordSchema {
+            name: "Order".to_string(),
+            fields: vec![SchemaField {
+                name: "total".to_string(),
+                field_type: FieldType::Double,
+                optional: false,
+            }],
+        };
+        let suggestions = suggest(
+            &order,
+            "pk.fields",
+            None,
+            Some("table.name.format"),
+            "PostgresSink",
+        );
+        assert!(!suggestions.values.contains_key("pk.fields"));
+        assert!(suggestions.issues[0].contains("no 'id' field"));
+        assert!(suggestions.issues[1].contains("'order' is an SQL reserved word"));
+    }
+
+    #[test]
+    fn test_parse_subject_version() {
+        let json = serde_json::json!({
+            "subject": "orders-value",
+            "version": 3,
+            "id": 100042,
+            "schema": ORDER_AVRO,
+        })
+        .to_string();
+        assert_eq!(parse_subject_version(&json).unwrap().name, "OrderLine");
+
+        let json = serde_json::json!({
+            "schema": "syntax = \"proto3\"; message Refund { string refund_id = 1; }",
+            "schemaType": "PROTOBUF",
+        })
+        .to_string();
+        let schema = parse_subject_version(&json).unwrap();
+        assert_eq!(schema.primary_key().unwrap().name, "refund_id");
+    }
+}
@@ -0,0 +1,322 @@
+use crate::cloud::{ConnectApi, PluginSchema, SchemaFetch};
+use crate::error::ConnectUtilError;
+use crate::types::ConnectorConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// `$XDG_CACHE_HOME/connect-util/schemas`, falling back to `~/.cache`. `None` when
+/// neither variable is set
+pub fn default_cache_dir() -> Option<PathBuf> {
+    let cache_home = std::env::var_os("XDG_CACHE_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME")
+                .filter(|v| !v.is_empty())
+                .map(|home| PathBuf::from(home).join(".cache"))
+        })?;
+    Some(cache_home.join("connect-util").join("schemas"))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    #[serde(default)]
+    etag: Option<String>,
+    schema: PluginSchema,
+}
+
+/// Plugin schemas fetched during a run, backed by an on-disk cache keyed by
+/// connector class and plugin version. Shared by concurrent lookups
+pub struct SchemaCache {
+    dir: Option<PathBuf>,
+    fetched: Mutex<HashMap<String, PluginSchema>>,
+    downloads: AtomicUsize,
+}
+
+impl SchemaCache {
+    /// `dir` of `None` keeps schemas in memory only
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        Self {
+            dir,
+            fetched: Mutex::new(HashMap::new()),
+            downloads: AtomicUsize::new(0),
+        }
+    }
+
+    /// Schemas downloaded from the API, as opposed to served from the cache
+    pub fn downloads(&self) -> usize {
+        self.downloads.load(Ordering::Relaxed)
+    }
+
+    fn key(connector_class: &str, version: Option<&str>) -> String {
+        let key = match version {
+            Some(version) => format!("{}@{}", connector_class, version),
+            None => connector_class.to_string(),
+        };
+        key.chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || "._-@".contains(c) {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    }
+
+    fn read_entry(path: &Path) -> Option<CacheEntry> {
+        serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+    }
+
+    /// The schema for `connector_class`. A cached copy of a known plugin version is
+    /// used as is, since a version's configs don't change; without a version the
+    /// cached copy is revalidated with its ETag
+    pub async fn get(
+        &self,
+        api: &dyn ConnectApi,
+        environment_id: &str,
+        cluster_id: &str,
+        connector_class: &str,
+        version: Option<&str>,
+    ) -> Result<PluginSchema, ConnectUtilError> {
+        let key = Self::key(connector_class, version);
+        if let Some(schema) = self.fetched.lock().unwrap().get(&key) {
+            return Ok(schema.clone());
+        }
+
+        let path = self
+            .dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{}.json", key)));
+        let cached = path.as_deref().and_then(Self::read_entry);
+        let schema = match cached {
+            Some(entry) if version.is_some() => entry.schema,
+            cached => {
+                let etag = cached.as_ref().and_then(|entry| entry.etag.as_deref());
+                match api
+                    .plugin_schema(environment_id, cluster_id, connector_class, etag)
+                    .await?
+                {
+                    SchemaFetch::NotModified => {
+                        cached.map(|entry| entry.schema).ok_or_else(|| {
+                            ConnectUtilError::Unknown(format!(
+                                "Schema for {} was reported unchanged, but none is cached",
+                                connector_class
+                            ))
+                        })?
+                    }
+                    SchemaFetch::Fetched { schema, etag } => {
+                        self.downloads.fetch_add(1, Ordering::Relaxed);
+                        if let Some(path) = &path {
+                            let entry = CacheEntry {
+                                etag,
+                                schema: schema.clone(),
+                            };
+                            if let Err(e) = write_entry(path, &entry) {
+                                tracing::debug!(
+                                    "Could not cache schema at {}: {}",
+                                    path.display(),
+                                    e
+                                );
+                            }
+                        }
+                        schema
+                    }
+                }
+            }
+        };
+        self.fetched.lock().unwrap().insert(key, schema.clone());
+        Ok(schema)
+    }
+}
+
+fn write_entry(path: &Path, entry: &CacheEntry) -> Result<(), ConnectUtilError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
+/// Keys the plugin doesn't declare, and required configs without a default that
+/// aren't set
+pub fn schema_findings(config: &ConnectorConfig, schema: &PluginSchema) -> Vec<String> {
+    if schema.configs.is_empty() {
+        return Vec::new();
+    }
+    let mut keys: Vec<&String> = config
+        .config
+        .keys()
+        .chain(config.sensitive_config.keys())
+        .collect();
+    keys.sort();
+
+    let mut findings: Vec<String> = keys
+        .into_iter()
+        .filter(|key| !schema.configs.iter().any(|def| &def.name == *key))
+        .map(|key| {
+            format!(
+                "'{}': {} doesn't declare '{}'",
+                config.name, config.connector_class, key
+            )
+        })
+        .collect();
+    findings.extend(
+        schema
+            .configs
+            .iter()
+            .filter(|def| def.required && def.default_value.as_deref().unwrap_or("").is_empty())
+            .filter(|def| {
+                !config.config.contains_key(&def.name)
+                    && !config.sensitive_config.contains_key(&def.name)
+            })
+            .map(|def| format!("'{}': missing required config '{}'", config.name, def.name)),
+    );
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cloud::{ConnectorOffsets, LiveConnector, OffsetReset, PluginConfigDef};
+
+    /// Serves one schema under a fixed ETag and records each request's If-None-Match
+    struct SchemaApi {
+        requests: Mutex<Vec<Option<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl ConnectApi for SchemaApi {
+        async fn list_connectors(
+            &self,
+            _environment_id: &str,
+            _cluster_id: &str,
+        ) -> Result<Vec<LiveConnector>, ConnectUtilError> {
+            Ok(Vec::new())
+        }
+
+        async fn get_offsets(
+            &self,
+            _environment_id: &str,
+            _cluster_id: &str,
+            _connector: &str,
+        ) -> Result<ConnectorOffsets, ConnectUtilError> {
+            Ok(ConnectorOffsets::default())
+        }
+
+        async fn plugin_versions(
+            &self,
+            _environment_id: &str,
+            _cluster_id: &str,
+        ) -> Result<HashMap<String, String>, ConnectUtilError> {
+            Ok(HashMap::new())
+        }
+
+        async fn plugin_schema(
+            &self,
+            _environment_id: &str,
+            _cluster_id: &str,
+            _connector_class: &str,
+            etag: Option<&str>,
+        ) -> Result<SchemaFetch, ConnectUtilError> {
+            self.requests.lock().unwrap().push(etag.map(str::to_string));
+            if etag == Some("\"v1\"") {
+                return Ok(SchemaFetch::NotModified);
+            }
+            Ok(SchemaFetch::Fetched {
+                schema: schema(),
+                etag: Some("\"v1\"".to_string()),
+            })
+        }
+
+        async fn reset_offsets(
+            &self,
+            _environment_id: &str,
+            _cluster_id: &str,
+            _connector: &str,
+            _to: OffsetReset,
+        ) -> Result<(), ConnectUtilError> {
+            Ok(())
+        }
+    }
+
+    fn schema() -> PluginSchema {
+        let def = |name: &str, required: bool, default_value: Option<&str>| PluginConfigDef {
+            name: name.to_string(),
+            required,
+            default_value: default_value.map(str::to_string),
+        };
+        PluginSchema {
+            configs: vec![
+                def("connector.class", true, None),
+                def("kafka.topic", true, Some("")),
+                def("tasks.max", true, Some("1")),
+            ],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_schema_cache_uses_etags_and_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let api = SchemaApi {
+            requests: Mutex::new(Vec::new()),
+        };
+
+        let cache = SchemaCache::new(Some(dir.path().to_path_buf()));
+        for _ in 0..3 {
+            let fetched = cache
+                .get(&api, "env-1", "lkc-1", "DatagenSource", None)
+                .await
+                .unwrap();
+            assert_eq!(fetched, schema());
+        }
+        assert_eq!(cache.downloads(), 1);
+
+        // A new run revalidates the unversioned copy, and trusts a versioned one
+        let cache = SchemaCache::new(Some(dir.path().to_path_buf()));
+        cache
+            .get(&api, "env-1", "lkc-1", "DatagenSource", None)
+            .await
+            .unwrap();
+        cache
+            .get(&api, "env-1", "lkc-1", "DatagenSource", Some("1.0"))
+            .await
+            .unwrap();
+        assert_eq!(cache.downloads(), 1);
+        let cache = SchemaCache::new(Some(dir.path().to_path_buf()));
+        cache
+            .get(&api, "env-1", "lkc-1", "DatagenSource", Some("1.0"))
+            .await
+            .unwrap();
+        assert_eq!(cache.downloads(), 0);
+
+        assert_eq!(
+            *api.requests.lock().unwrap(),
+            vec![None, Some("\"v1\"".to_string()), None]
+        );
+    }
+
+    #[test]
+    fn test_schema_findings() {
+        let config = ConnectorConfig {
+            name: "orders".to_string(),
+            connector_class: "DatagenSource".to_string(),
+            config: HashMap::from([
+                ("connector.class".to_string(), "DatagenSource".to_string()),
+                ("kafka.topics".to_string(), "orders".to_string()),
+            ]),
+            sensitive_config: HashMap::new(),
+        };
+        assert_eq!(
+            schema_findings(&config, &schema()),
+            vec![
+                "'orders': DatagenSource doesn't declare 'kafka.topics'",
+                "'orders': missing required config 'kafka.topic'",
+            ]
+        );
+        assert!(schema_findings(&config, &PluginSchema::default()).is_empty());
+    }
+}
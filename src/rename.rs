@@ -0,0 +1,255 @@
+use crate::edit::TerraformFile;
+use crate::error::ConnectUtilError;
+use regex::Regex;
+
+/// One rewritten line, 1-based
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineChange {
+    pub line: usize,
+    pub before: String,
+    pub after: String,
+}
+
+/// Whether a config key holds topic names: `topics`, `kafka.topic`, `topic.prefix`,
+/// `errors.deadletterqueue.topic.name`, and SMT `regex`/`replacement` settings.
+/// Variable names in `.tfvars` files use `_` in place of `.`
+pub fn is_topic_key(key: &str) -> bool {
+    let key = key.replace('_', ".");
+    let segments: Vec<&str> = key.split('.').collect();
+    let smt_setting = segments.first() == Some(&"transforms")
+        && matches!(segments.last(), Some(&"regex") | Some(&"replacement"));
+    smt_setting || segments.iter().any(|s| *s == "topic" || *s == "topics")
+}
+
+/// Characters that may separate a topic prefix from the rest of a name or list
+fn is_boundary(c: char) -> bool {
+    matches!(
+        c,
+        '.' | '_' | '-' | ',' | ' ' | '\\' | '|' | '(' | ')' | '^' | '$' | '['
+    )
+}
+
+/// Replaces `old` with `new` wherever it starts a topic name inside `value`
+pub fn rename_prefix_in_value(value: &str, old: &str, new: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    let mut previous: Option<char> = None;
+    while !rest.is_empty() {
+        let starts_topic = previous.is_none_or(is_boundary);
+        if starts_topic && rest.starts_with(old) {
+            let after = &rest[old.len()..];
+            if after.chars().next().is_none_or(is_boundary) {
+                result.push_str(new);
+                previous = old.chars().last();
+                rest = after;
+                continue;
+            }
+        }
+        let c = rest.chars().next().expect("rest is not empty");
+        result.push(c);
+        previous = Some(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    result
+}
+
+/// Rewrites string literals after `=` on lines that assign a topic key. Multi-line
+/// arrays (`"topics" = join(",", [` ... `])`) are followed to their closing bracket
+pub fn rename_prefix(content: &str, old: &str, new: &str) -> (String, Vec<LineChange>) {
+    let assignment =
+        Regex::new(r#"^\s*"?([A-Za-z0-9_.\-]+)"?\s*=(.*)$"#).expect("valid assignment pattern");
+    let literal = Regex::new(r#""((?:[^"\\]|\\.)*)""#).expect("valid string literal pattern");
+
+    let rewrite_literals = |text: &str| -> String {
+        literal
+            .replace_all(text, |captures: &regex::Captures| {
+                format!("\"{}\"", rename_prefix_in_value(&captures[1], old, new))
+            })
+            .into_owned()
+    };
+
+    let mut changes = Vec::new();
+    let mut output = Vec::new();
+    let mut depth = 0i32;
+    for (index, line) in content.split('\n').enumerate() {
+        let rewritten = if depth > 0 {
+            depth += line.matches('[').count() as i32 - line.matches(']').count() as i32;
+            rewrite_literals(line)
+        } else if let Some(captures) = assignment.captures(line) {
+            if is_topic_key(&captures[1]) {
+                let value = captures.get(2).expect("assignment has a value");
+                depth = value.as_str().matches('[').count() as i32
+                    - value.as_str().matches(']').count() as i32;
+                format!(
+                    "{}{}",
+                    &line[..value.start()],
+                    rewrite_literals(value.as_str())
+                )
+            } else {
+                line.to_string()
+            }
+        } else {
+            line.to_string()
+        };
+
+        if rewritten != line {
+            changes.push(LineChange {
+                line: index + 1,
+                before: line.to_string(),
+                after: rewritten.clone(),
+            });
+        }
+        output.push(rewritten);
+    }
+    (output.join("\n"), changes)
+}
+
+/// Renames the `confluent_connector` resource labelled `old` to `new`, along with
+/// every `confluent_connector.<old>` reference. Existing `moved` blocks are left
+/// alone so earlier moves still chain onto the new address
+pub fn rename_resource(
+    content: &str,
+    old: &str,
+    new: &str,
+) -> Result<(String, Vec<LineChange>), ConnectUtilError> {
+    let mut file = TerraformFile::parse(content)?;
+    if !file.rename_connector(old, new) {
+        return Ok((content.to_string(), Vec::new()));
+    }
+    let rewritten = file.to_string();
+    // Renames only touch identifiers, so lines stay where they were
+    let changes = content
+        .split('\n')
+        .zip(rewritten.split('\n'))
+        .enumerate()
+        .filter(|(_, (before, after))| before != after)
+        .map(|(index, (before, after))| LineChange {
+            line: index + 1,
+            before: before.to_string(),
+            after: after.to_string(),
+        })
+        .collect();
+    Ok((rewritten, changes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_topic_key() {
+        assert!(is_topic_key("topics"));
+        assert!(is_topic_key("topic.prefix"));
+        assert!(is_topic_key("kafka.topic"));
+        assert!(is_topic_key("topics.regex"));
+        assert!(is_topic_key("errors.deadletterqueue.topic.name"));
+        assert!(is_topic_key("transforms.route.regex"));
+        assert!(is_topic_key("transforms.route.replacement"));
+        assert!(is_topic_key("topic_prefix"));
+        assert!(!is_topic_key("salesforce.pushtopic.name"));
+        assert!(!is_topic_key("database.hostname"));
+        assert!(!is_topic_key("transforms.mask.fields"));
+    }
+
+    #[test]
+    fn test_rename_prefix_in_value() {
+        assert_eq!(rename_prefix_in_value("orders", "orders", "sales"), "sales");
+        assert_eq!(
+            rename_prefix_in_value("orders.public.items,orders_dlq", "orders", "sales"),
+            "sales.public.items,sales_dlq"
+        );
+        assert_eq!(
+            rename_prefix_in_value("^orders\\\\.(.*)$", "orders", "sales"),
+            "^sales\\\\.(.*)$"
+        );
+        // Only whole topic-name prefixes are renamed
+        assert_eq!(
+            rename_prefix_in_value("ordersx,preorders.v1", "orders", "sales"),
+            "ordersx,preorders.v1"
+        );
+    }
+
+    #[test]
+    fn test_rename_prefix() {
+        let content = r#"resource "confluent_connector" "orders" {
+  config_nonsensitive = {
+    "name"                   = "orders-cdc"
+    "topic.prefix"           = "orders"
+    "transforms.route.regex" = "orders\\.(.*)"
+    "topics" = join(",", [
+      "orders.v1",
+      "orders.v2",
+    ])
+    "database.dbname" = "orders"
+  }
+}
+"#;
+        let (rewritten, changes) = rename_prefix(content, "orders", "sales");
+        let changed: Vec<usize> = changes.iter().map(|c| c.line).collect();
+        assert_eq!(changed, vec![4, 5, 7, 8]);
+        assert!(rewritten.contains("\"name\"                   = \"orders-cdc\""));
+        assert!(rewritten.contains("\"topic.prefix\"           = \"sales\""));
+        assert!(rewritten.contains("\"transforms.route.regex\" = \"sales\\\\.(.*)\""));
+        assert!(rewritten.contains("\"sales.v2\","));
+        assert!(rewritten.contains("\"database.dbname\" = \"orders\""));
+        assert!(rewritten.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_rename_prefix_tfvars() {
+        let (rewritten, changes) = rename_prefix(
+            "topic_prefix = \"orders\"\ndb_name = \"orders\"\n",
+            "orders",
+            "sales",
+        );
+        assert_eq!(changes.len(), 1);
+        assert_eq!(
+            rewritten,
+            "topic_prefix = \"sales\"\ndb_name = \"orders\"\n"
+        );
+    }
+
+    #[test]
+    fn test_rename_resource() {
+        let content = r#"resource "confluent_connector" "orders" {
+  config_nonsensitive = {
+    "name" = "orders"
+  }
+}
+
+resource "confluent_connector" "orders_v2" {
+  depends_on = [confluent_connector.orders]
+}
+
+output "orders_id" {
+  value = confluent_connector.orders.id
+}
+
+moved {
+  from = confluent_connector.legacy
+  to   = confluent_connector.orders
+}
+
+import {
+  to = confluent_connector.orders
+  id = "env-abc/lkc-abc/lcc-abc"
+}
+
+data "confluent_connector" "orders" {}
+locals {
+  id = data.confluent_connector.orders.id
+}
+"#;
+        let (rewritten, changes) = rename_resource(content, "orders", "sales").unwrap();
+        let changed: Vec<usize> = changes.iter().map(|c| c.line).collect();
+        assert_eq!(changed, vec![1, 8, 12, 21]);
+        assert!(rewritten.contains("resource \"confluent_connector\" \"sales\" {"));
+        assert!(rewritten.contains("resource \"confluent_connector\" \"orders_v2\" {"));
+        assert!(rewritten.contains("\"name\" = \"orders\""));
+        assert!(rewritten.contains("depends_on = [confluent_connector.sales]"));
+        assert!(rewritten.contains("value = confluent_connector.sales.id"));
+        assert!(rewritten.contains("  to   = confluent_connector.orders\n"));
+        assert!(rewritten.contains("  to = confluent_connector.sales\n"));
+        assert!(rewritten.contains("id = data.confluent_connector.orders.id"));
+    }
+}
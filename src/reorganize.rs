@@ -0,0 +1,242 @@
+use crate::edit::TerraformFile;
+use crate::error::ConnectUtilError;
+use hcl_edit::structure::{Block, Body, Structure};
+use hcl_edit::Decorate;
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// A connector resource moved into a file of its own, with the blocks that belong to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectorFile {
+    /// Terraform label of the connector
+    pub label: String,
+    pub content: String,
+}
+
+/// A file split into one file per connector and whatever the connectors share
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitFile {
+    pub connectors: Vec<ConnectorFile>,
+    /// Variables, locals, providers, and other blocks left in the original file;
+    /// None when nothing is left
+    pub rest: Option<String>,
+}
+
+/// Adds the file to a Terraform error, so a clash names the input it came from
+fn in_file(path: &Path, error: ConnectUtilError) -> ConnectUtilError {
+    match error {
+        ConnectUtilError::Terraform(message) => {
+            ConnectUtilError::Terraform(format!("{}: {}", path.display(), message))
+        }
+        other => other,
+    }
+}
+
+/// Combines Terraform files into one, in the order given. Each file's text, comments
+/// included, is kept as it is, so variable and local references carry over
+/// unchanged. Blocks several files declare the same way, such as a shared
+/// `variable`, are kept once; a connector declared twice is an error
+pub fn merge_files(sources: &[(PathBuf, String)]) -> Result<String, ConnectUtilError> {
+    let Some(((first_path, first), rest)) = sources.split_first() else {
+        return Err(ConnectUtilError::UserInput("No files to merge".to_string()));
+    };
+    let mut merged = TerraformFile::parse(first).map_err(|e| in_file(first_path, e))?;
+    for (path, content) in rest {
+        merged.append_new(content).map_err(|e| in_file(path, e))?;
+    }
+    let mut content = merged.to_string();
+    if !content.ends_with('\n') {
+        content.push('\n');
+    }
+    Ok(content)
+}
+
+fn connector_label(block: &Block) -> Option<&str> {
+    match block.labels.as_slice() {
+        [kind, label]
+            if block.ident.as_str() == "resource" && kind.as_str() == "confluent_connector" =>
+        {
+            Some(label.as_str())
+        }
+        _ => None,
+    }
+}
+
+/// Renders structures as a file of their own, without blank lines at the top
+fn render(structures: Vec<Structure>, suffix: Option<&str>) -> String {
+    let mut body = Body::new();
+    for structure in structures {
+        body.push(structure);
+    }
+    if let Some(suffix) = suffix {
+        body.decor_mut().set_suffix(suffix.to_string());
+    }
+    let mut content = body
+        .to_string()
+        .trim_start_matches(['\r', '\n'])
+        .to_string();
+    if !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content
+}
+
+/// Splits a file into one file per `confluent_connector` resource. Outputs,
+/// `import`, and `moved` blocks that refer to a single connector go with it;
+/// everything else stays behind. The pieces belong in the same directory, since
+/// the connectors keep referring to the module's variables and locals
+pub fn split_connectors(content: &str) -> Result<SplitFile, ConnectUtilError> {
+    let body: Body = content.parse().map_err(|e| {
+        ConnectUtilError::Terraform(format!("Failed to parse Terraform file: {}", e))
+    })?;
+    let labels: BTreeSet<String> = body
+        .blocks()
+        .filter_map(connector_label)
+        .map(str::to_string)
+        .collect();
+    let reference = Regex::new(r"confluent_connector\.([A-Za-z_][\w-]*)")
+        .expect("valid connector reference pattern");
+    let suffix = body.decor().suffix().map(|s| s.to_string());
+
+    let mut connectors: Vec<(String, Vec<Structure>)> = Vec::new();
+    let mut rest = Vec::new();
+    for structure in body {
+        let owner = structure.as_block().and_then(|block| {
+            if let Some(label) = connector_label(block) {
+                return Some(label.to_string());
+            }
+            if !matches!(block.ident.as_str(), "output" | "import" | "moved") {
+                return None;
+            }
+            let rendered = Body::builder().block(block.clone()).build().to_string();
+            let referenced: BTreeSet<&str> = reference
+                .captures_iter(&rendered)
+                .filter_map(|captures| captures.get(1))
+                .map(|label| label.as_str())
+                .filter(|label| labels.contains(*label))
+                .collect();
+            match referenced.into_iter().collect::<Vec<_>>().as_slice() {
+                [label] => Some(label.to_string()),
+                _ => None,
+            }
+        });
+        match owner {
+            Some(label) => match connectors.iter_mut().find(|(l, _)| *l == label) {
+                Some((_, structures)) => structures.push(structure),
+                None => connectors.push((label, vec![structure])),
+            },
+            None => rest.push(structure),
+        }
+    }
+
+    let has_rest = !rest.is_empty();
+    Ok(SplitFile {
+        connectors: connectors
+            .into_iter()
+            .map(|(label, structures)| ConnectorFile {
+                label,
+                content: render(structures, None),
+            })
+            .collect(),
+        rest: has_rest.then(|| render(rest, suffix.as_deref())),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = r#"# Shared settings
+variable "db_password" {
+  sensitive = true
+}
+
+# Orders CDC
+resource "confluent_connector" "orders" {
+  config_sensitive = {
+    "database.password" = var.db_password # rotated quarterly
+  }
+}
+
+output "orders_id" {
+  value = confluent_connector.orders.id
+}
+
+resource "confluent_connector" "refunds" {
+  config_nonsensitive = {
+    "topics" = local.refund_topics
+  }
+}
+
+moved {
+  from = confluent_connector.legacy_refunds
+  to   = confluent_connector.refunds
+}
+
+output "all_ids" {
+  value = [confluent_connector.orders.id, confluent_connector.refunds.id]
+}
+# end of connectors
+"#;
+
+    #[test]
+    fn test_split_connectors() {
+        let split = split_connectors(CONTENT).unwrap();
+        let labels: Vec<&str> = split.connectors.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(labels, vec!["orders", "refunds"]);
+        assert_eq!(
+            split.connectors[0].content,
+            r#"# Orders CDC
+resource "confluent_connector" "orders" {
+  config_sensitive = {
+    "database.password" = var.db_password # rotated quarterly
+  }
+}
+
+output "orders_id" {
+  value = confluent_connector.orders.id
+}
+"#
+        );
+        assert!(split.connectors[1].content.contains("moved {"));
+        assert!(split.connectors[1]
+            .content
+            .contains("\"topics\" = local.refund_topics"));
+        let rest = split.rest.unwrap();
+        assert!(rest.starts_with("# Shared settings\nvariable \"db_password\""));
+        assert!(rest.contains("output \"all_ids\""));
+        assert!(rest.ends_with("# end of connectors\n"));
+
+        let split = split_connectors("resource \"confluent_connector\" \"orders\" {}\n").unwrap();
+        assert_eq!(split.rest, None);
+    }
+
+    #[test]
+    fn test_merge_files_round_trips_a_split() {
+        let split = split_connectors(CONTENT).unwrap();
+        let mut sources = vec![(PathBuf::from("shared.tf"), split.rest.unwrap())];
+        sources.extend(
+            split
+                .connectors
+                .into_iter()
+                .map(|c| (PathBuf::from(format!("{}.tf", c.label)), c.content)),
+        );
+        let merged = merge_files(&sources).unwrap();
+        assert!(merged.contains("\"database.password\" = var.db_password # rotated quarterly"));
+        assert_eq!(
+            merged.matches("resource \"confluent_connector\"").count(),
+            2
+        );
+
+        sources.push((
+            PathBuf::from("copy.tf"),
+            "resource \"confluent_connector\" \"orders\" {}\n".to_string(),
+        ));
+        let error = merge_files(&sources).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("copy.tf: Resource 'confluent_connector.orders' already exists"));
+        assert!(merge_files(&[]).is_err());
+    }
+}
@@ -0,0 +1,308 @@
+use crate::error::ConnectUtilError;
+use crate::lint::{LintIssue, LintRule};
+use crate::types::Newline;
+use dialoguer::{theme::ColorfulTheme, Input, Select};
+use std::path::Path;
+
+/// What `validate --interactive` can do with a finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewAction {
+    /// Apply the finding's suggested fix
+    Fix,
+    /// Write a `connect-util:ignore` comment above the finding
+    Suppress,
+    /// Open the file at the finding's line in `$VISUAL`/`$EDITOR`
+    Open,
+    Skip,
+    /// Stop reviewing, leaving the remaining findings alone
+    Quit,
+}
+
+impl ReviewAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReviewAction::Fix => "Apply suggested fix",
+            ReviewAction::Suppress => "Suppress with a comment",
+            ReviewAction::Open => "Open file at location",
+            ReviewAction::Skip => "Skip",
+            ReviewAction::Quit => "Quit",
+        }
+    }
+}
+
+/// Asks what to do with each finding; the terminal prompts in normal use
+pub trait Reviewer {
+    fn choose(
+        &mut self,
+        issue: &LintIssue,
+        actions: &[ReviewAction],
+    ) -> Result<ReviewAction, ConnectUtilError>;
+
+    /// Why the finding is suppressed, for the comment
+    fn reason(&mut self, issue: &LintIssue) -> Result<String, ConnectUtilError>;
+}
+
+/// Prompts on the terminal with dialoguer
+pub struct TerminalReviewer;
+
+impl Reviewer for TerminalReviewer {
+    fn choose(
+        &mut self,
+        _issue: &LintIssue,
+        actions: &[ReviewAction],
+    ) -> Result<ReviewAction, ConnectUtilError> {
+        let labels: Vec<&str> = actions.iter().map(ReviewAction::label).collect();
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Action")
+            .items(&labels)
+            .default(0)
+            .interact()
+            .map_err(|e| ConnectUtilError::UserInput(format!("Failed to select action: {}", e)))?;
+        Ok(actions[selection])
+    }
+
+    fn reason(&mut self, _issue: &LintIssue) -> Result<String, ConnectUtilError> {
+        Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("Reason")
+            .allow_empty(true)
+            .interact_text()
+            .map_err(|e| ConnectUtilError::UserInput(format!("Failed to read reason: {}", e)))
+    }
+}
+
+/// A change `validate --interactive` knows how to make for a finding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SuggestedFix {
+    /// Move the connector resource to a compliant label, as `rename-resource` does
+    RenameResource { old: String, new: String },
+    /// Replace the connector's `name` setting with a compliant one
+    RenameConnector { old: String, new: String },
+    /// Declare the module's missing variables, as `fix-variables` does
+    DeclareVariables,
+}
+
+impl SuggestedFix {
+    pub fn describe(&self) -> String {
+        match self {
+            SuggestedFix::RenameResource { old, new } => {
+                format!("rename resource '{}' to '{}'", old, new)
+            }
+            SuggestedFix::RenameConnector { old, new } => {
+                format!("rename connector '{}' to '{}'", old, new)
+            }
+            SuggestedFix::DeclareVariables => {
+                "declare the module's missing variables in variables.tf".to_string()
+            }
+        }
+    }
+}
+
+/// The two quoted values of a naming violation: the offending value and the
+/// suggestion, as in `Resource label 'Orders' ... (suggested: 'orders')`
+fn naming_suggestion<'a>(message: &'a str, kind: &str) -> Option<(&'a str, &'a str)> {
+    let rest = message.strip_prefix(kind)?.strip_prefix(" '")?;
+    let (old, rest) = rest.split_once('\'')?;
+    let new = rest.rsplit_once("(suggested: '")?.1.strip_suffix("')")?;
+    Some((old, new))
+}
+
+/// The fix for a finding, when there is a mechanical one
+pub fn suggested_fix(issue: &LintIssue) -> Option<SuggestedFix> {
+    match issue.rule {
+        LintRule::NamingConvention => {
+            if let Some((old, new)) = naming_suggestion(&issue.message, "Resource label") {
+                return Some(SuggestedFix::RenameResource {
+                    old: old.to_string(),
+                    new: new.to_string(),
+                });
+            }
+            naming_suggestion(&issue.message, "Connector name").map(|(old, new)| {
+                SuggestedFix::RenameConnector {
+                    old: old.to_string(),
+                    new: new.to_string(),
+                }
+            })
+        }
+        LintRule::UndeclaredVariable => Some(SuggestedFix::DeclareVariables),
+        _ => None,
+    }
+}
+
+/// The actions offered for `issue`; `Fix` only when it has a suggested fix
+pub fn actions(issue: &LintIssue) -> Vec<ReviewAction> {
+    let mut actions = Vec::new();
+    if suggested_fix(issue).is_some() {
+        actions.push(ReviewAction::Fix);
+    }
+    actions.extend([
+        ReviewAction::Suppress,
+        ReviewAction::Open,
+        ReviewAction::Skip,
+        ReviewAction::Quit,
+    ]);
+    actions
+}
+
+/// Inserts a `connect-util:ignore` comment above the 1-based `line`, indented to match it
+pub fn insert_suppression(content: &str, line: usize, rule: LintRule, reason: &str) -> String {
+    let mut lines: Vec<&str> = content.split('\n').collect();
+    let index = line.saturating_sub(1).min(lines.len());
+    let target = lines.get(index).copied().unwrap_or_default();
+    let indent = &target[..target.len() - target.trim_start().len()];
+    let id = rule.name();
+    let id = id.strip_prefix("connect_util_").unwrap_or(&id);
+    let mut comment = format!("{}# connect-util:ignore {}", indent, id);
+    if !reason.trim().is_empty() {
+        comment.push(' ');
+        comment.push_str(reason.trim());
+    }
+    // Split on LF, the lines of a CRLF file keep their `\r`
+    if Newline::detect(content) == Some(Newline::Crlf) {
+        comment.push('\r');
+    }
+    lines.insert(index, &comment);
+    lines.join("\n")
+}
+
+/// Replaces the quoted connector name `old` with `new` in the first assignment of it
+/// at or after the 1-based `line` where the connector's block opens
+pub fn rename_connector_name(content: &str, line: usize, old: &str, new: &str) -> Option<String> {
+    let quoted = format!("\"{}\"", old);
+    let mut lines: Vec<String> = content.split('\n').map(str::to_string).collect();
+    let target = lines.iter_mut().skip(line.saturating_sub(1)).find(|l| {
+        l.split_once('=').is_some_and(|(key, value)| {
+            key.trim().trim_matches('"') == "name" && value.trim() == quoted
+        })
+    })?;
+    *target = target.replace(&quoted, &format!("\"{}\"", new));
+    Some(lines.join("\n"))
+}
+
+/// Program and arguments opening `file` at `line` in `editor`, in the form the
+/// common editors accept
+pub fn editor_command(editor: &str, file: &Path, line: usize) -> (String, Vec<String>) {
+    let mut words = editor.split_whitespace().map(str::to_string);
+    let program = words.next().unwrap_or_else(|| "vi".to_string());
+    let mut args: Vec<String> = words.collect();
+    let name = Path::new(&program)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    match name {
+        "code" | "code-insiders" | "codium" => {
+            args.push("--goto".to_string());
+            args.push(format!("{}:{}", file.display(), line));
+        }
+        "subl" | "zed" => args.push(format!("{}:{}", file.display(), line)),
+        _ => {
+            args.push(format!("+{}", line));
+            args.push(file.display().to_string());
+        }
+    }
+    (program, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn issue(rule: LintRule, message: &str) -> LintIssue {
+        LintIssue {
+            rule,
+            severity: rule.severity(),
+            file: PathBuf::from("main.tf"),
+            line: 2,
+            connector: Some("Orders".to_string()),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_suggested_fix() {
+        let label = issue(
+            LintRule::NamingConvention,
+            "Resource label 'Orders' does not match required pattern '^[a-z_]+$' (suggested: 'orders')",
+        );
+        assert_eq!(
+            suggested_fix(&label),
+            Some(SuggestedFix::RenameResource {
+                old: "Orders".to_string(),
+                new: "orders".to_string()
+            })
+        );
+        assert_eq!(actions(&label)[0], ReviewAction::Fix);
+
+        let name = issue(
+            LintRule::NamingConvention,
+            "Connector name 'Orders CDC' does not match required pattern '^src-' (suggested: 'src-orders-cdc')",
+        );
+        assert_eq!(
+            suggested_fix(&name),
+            Some(SuggestedFix::RenameConnector {
+                old: "Orders CDC".to_string(),
+                new: "src-orders-cdc".to_string()
+            })
+        );
+
+        // No suggestion could be found for the pattern
+        let unfixable = issue(
+            LintRule::NamingConvention,
+            "Resource label 'Orders' does not match required pattern '^x$'",
+        );
+        assert_eq!(suggested_fix(&unfixable), None);
+        assert_eq!(actions(&unfixable)[0], ReviewAction::Suppress);
+        assert_eq!(
+            suggested_fix(&issue(LintRule::UndeclaredVariable, "")),
+            Some(SuggestedFix::DeclareVariables)
+        );
+        assert_eq!(suggested_fix(&issue(LintRule::Policy, "")), None);
+    }
+
+    #[test]
+    fn test_insert_suppression() {
+        let content = "resource \"confluent_connector\" \"Orders\" {\n  config_nonsensitive = {\n    \"tasks.max\" = \"9\"\n  }\n}\n";
+        let suppressed = insert_suppression(content, 3, LintRule::Policy, " legacy load ");
+        assert_eq!(
+            suppressed.lines().nth(2),
+            Some("    # connect-util:ignore policy legacy load")
+        );
+        assert_eq!(suppressed.lines().nth(3), Some("    \"tasks.max\" = \"9\""));
+
+        let suppressed = insert_suppression(content, 1, LintRule::NamingConvention, "");
+        assert!(suppressed.starts_with("# connect-util:ignore naming_convention\nresource"));
+        assert!(suppressed.ends_with("}\n"));
+
+        // A CRLF file keeps CRLF line endings throughout
+        let crlf = content.replace('\n', "\r\n");
+        let suppressed = insert_suppression(&crlf, 3, LintRule::Policy, "");
+        assert!(suppressed.contains("\r\n    # connect-util:ignore policy\r\n    \"tasks.max\""));
+        assert!(!suppressed.replace("\r\n", "").contains('\n'));
+    }
+
+    #[test]
+    fn test_rename_connector_name() {
+        let content = "resource \"confluent_connector\" \"a\" {\n  config_nonsensitive = {\n    \"name\" = \"Orders\"\n  }\n}\nresource \"confluent_connector\" \"b\" {\n  config_nonsensitive = {\n    \"name\"  = \"Orders\"\n  }\n}";
+        let renamed = rename_connector_name(content, 6, "Orders", "src-orders").unwrap();
+        assert!(renamed.contains("    \"name\" = \"Orders\"\n"));
+        assert!(renamed.contains("    \"name\"  = \"src-orders\"\n"));
+        assert_eq!(rename_connector_name(content, 1, "Refunds", "x"), None);
+    }
+
+    #[test]
+    fn test_editor_command() {
+        let file = Path::new("env/main.tf");
+        assert_eq!(
+            editor_command("nvim", file, 12),
+            (
+                "nvim".to_string(),
+                vec!["+12".to_string(), "env/main.tf".to_string()]
+            )
+        );
+        assert_eq!(
+            editor_command("/usr/bin/code --wait", file, 3).1,
+            vec!["--wait", "--goto", "env/main.tf:3"]
+        );
+        assert_eq!(editor_command("", file, 1).0, "vi");
+    }
+}
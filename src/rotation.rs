@@ -0,0 +1,263 @@
+use crate::edit::TerraformFile;
+use crate::error::ConnectUtilError;
+use crate::where_used::Usage;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::Path;
+
+/// A connector entry pointed at the new credential
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RotationEdit {
+    /// Terraform label of the connector
+    pub resource: String,
+    /// The map the key was in: `config_sensitive` or `config_nonsensitive`
+    pub from: String,
+    pub before: String,
+}
+
+/// The Terraform label of a connector address, without its `for_each`/`count` key
+pub fn resource_label(resource: &str) -> &str {
+    resource.split('[').next().unwrap_or(resource)
+}
+
+/// Sets `key` to the expression `to` in `config_sensitive` for each connector in
+/// `usages`, all of which must come from `content`. Keys found in
+/// `config_nonsensitive` move to `config_sensitive`, so the new credential stays
+/// out of plan output
+pub fn rotate_references(
+    content: &str,
+    key: &str,
+    to: &str,
+    usages: &[Usage],
+) -> Result<(String, Vec<RotationEdit>), ConnectUtilError> {
+    let mut file = TerraformFile::parse(content)?;
+    let mut seen = BTreeSet::new();
+    let mut edits = Vec::new();
+    for usage in usages {
+        let label = resource_label(&usage.resource);
+        let from = usage
+            .key
+            .strip_suffix(&format!(".{}", key))
+            .unwrap_or("config_sensitive");
+        // `for_each` instances share one block
+        if !seen.insert((label.to_string(), from.to_string())) {
+            continue;
+        }
+        if from == "config_nonsensitive" {
+            file.remove_config(label, from, key)?;
+        }
+        if file.set_config(label, "config_sensitive", key, to)? || from == "config_nonsensitive" {
+            edits.push(RotationEdit {
+                resource: label.to_string(),
+                from: from.to_string(),
+                before: usage.value.clone(),
+            });
+        }
+    }
+    Ok((file.to_string(), edits))
+}
+
+fn joined<'a>(items: impl IntoIterator<Item = &'a str>) -> String {
+    items
+        .into_iter()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The steps outside these files that a rotation of `key` needs, in order
+pub fn rotation_checklist(key: &str, to: Option<&str>, usages: &[Usage]) -> Vec<String> {
+    let names = joined(usages.iter().map(|usage| usage.name.as_str()));
+    let modules = joined(
+        usages
+            .iter()
+            .map(|usage| usage.file.parent().unwrap_or(Path::new(".")))
+            .map(|dir| dir.to_str().filter(|dir| !dir.is_empty()).unwrap_or(".")),
+    );
+    let previous: BTreeSet<&str> = usages
+        .iter()
+        .map(|usage| usage.value.as_str())
+        .filter(|value| !value.starts_with('('))
+        .filter(|value| Some(value.trim_start_matches("${").trim_end_matches('}')) != to)
+        .collect();
+
+    let mut steps = vec![format!(
+        "Issue a new credential for {} in the system it authenticates to",
+        key
+    )];
+    match to {
+        Some(to) => {
+            if let Some(variable) = to.strip_prefix("var.") {
+                steps.push(format!(
+                    "Supply the new value for {}, e.g. TF_VAR_{} or a .tfvars file kept out of git",
+                    to, variable
+                ));
+                steps.push(format!(
+                    "Declare variable \"{}\" with sensitive = true if it's new; `connect-util fix-variables` adds missing declarations",
+                    variable
+                ));
+            } else {
+                steps.push(format!("Store the new value where {} reads it from", to));
+            }
+        }
+        None => {
+            let hardcoded = joined(
+                usages
+                    .iter()
+                    .filter(|usage| usage.value.starts_with('('))
+                    .map(|usage| usage.resource.as_str()),
+            );
+            if !hardcoded.is_empty() {
+                steps.push(format!(
+                    "Replace the literal values in {} with a variable (`--to var.<name>`)",
+                    hardcoded
+                ));
+            }
+            if !previous.is_empty() {
+                steps.push(format!(
+                    "Update the value behind {}",
+                    joined(previous.iter().copied())
+                ));
+            }
+        }
+    }
+    steps.push(format!("Run terraform plan and apply in {}", modules));
+    steps.push(format!(
+        "Confirm each connector is RUNNING with the new credential: {}",
+        names
+    ));
+    steps.push("Revoke the old credential".to_string());
+    if to.is_some() && !previous.is_empty() {
+        steps.push(format!(
+            "Remove {} once nothing else references it",
+            joined(previous.iter().copied())
+        ));
+    }
+    steps
+}
+
+/// Renders the checklist as unchecked Markdown task items
+pub fn render_checklist(key: &str, steps: &[String]) -> String {
+    let mut out = format!("📋 Rotation checklist for {}:\n", key);
+    for step in steps {
+        let _ = writeln!(out, "  - [ ] {}", step);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn usage(resource: &str, key: &str, value: &str) -> Usage {
+        Usage {
+            file: PathBuf::from("infra/prod/main.tf"),
+            resource: resource.to_string(),
+            name: format!("{}-sink", resource_label(resource)),
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_rotate_references() {
+        let content = r#"resource "confluent_connector" "orders" {
+  for_each = toset(["eu", "us"])
+  config_sensitive = {
+    "database.password" = var.db_password # rotated quarterly
+  }
+}
+
+resource "confluent_connector" "legacy" {
+  config_nonsensitive = {
+    "connector.class"   = "PostgresSink"
+    "database.password" = "hunter2"
+  }
+}
+"#;
+        let usages = vec![
+            usage(
+                "orders[\"eu\"]",
+                "config_sensitive.database.password",
+                "${var.db_password}",
+            ),
+            usage(
+                "orders[\"us\"]",
+                "config_sensitive.database.password",
+                "${var.db_password}",
+            ),
+            usage(
+                "legacy",
+                "config_nonsensitive.database.password",
+                "(hardcoded)",
+            ),
+        ];
+        let (rewritten, edits) =
+            rotate_references(content, "database.password", "var.db_password_v2", &usages).unwrap();
+        let moved: Vec<(&str, &str)> = edits
+            .iter()
+            .map(|edit| (edit.resource.as_str(), edit.from.as_str()))
+            .collect();
+        assert_eq!(
+            moved,
+            vec![
+                ("orders", "config_sensitive"),
+                ("legacy", "config_nonsensitive")
+            ]
+        );
+        assert!(
+            rewritten.contains("\"database.password\" = var.db_password_v2 # rotated quarterly\n")
+        );
+        assert!(!rewritten.contains("hunter2"));
+        assert!(rewritten.contains(
+            "  config_sensitive = {\n    \"database.password\" = var.db_password_v2\n  }\n"
+        ));
+
+        let (_, edits) = rotate_references(
+            &rewritten,
+            "database.password",
+            "var.db_password_v2",
+            &usages[..1],
+        )
+        .unwrap();
+        assert!(edits.is_empty());
+    }
+
+    #[test]
+    fn test_rotation_checklist() {
+        let usages = vec![
+            usage(
+                "orders",
+                "config_sensitive.database.password",
+                "${var.db_password}",
+            ),
+            usage(
+                "legacy",
+                "config_nonsensitive.database.password",
+                "(hardcoded)",
+            ),
+        ];
+        let steps = rotation_checklist("database.password", Some("var.db_password_v2"), &usages);
+        assert!(steps[0].contains("new credential for database.password"));
+        assert!(steps[1].contains("TF_VAR_db_password_v2"));
+        assert!(steps.contains(&"Run terraform plan and apply in infra/prod".to_string()));
+        assert!(steps.contains(
+            &"Confirm each connector is RUNNING with the new credential: legacy-sink, orders-sink"
+                .to_string()
+        ));
+        assert_eq!(
+            steps.last().unwrap(),
+            "Remove ${var.db_password} once nothing else references it"
+        );
+
+        let steps = rotation_checklist("database.password", None, &usages);
+        assert!(steps[1].contains("literal values in legacy"));
+        assert_eq!(steps[2], "Update the value behind ${var.db_password}");
+        assert_eq!(steps.last().unwrap(), "Revoke the old credential");
+
+        let rendered = render_checklist("database.password", &steps);
+        assert!(rendered.starts_with("📋 Rotation checklist for database.password:\n  - [ ] Issue"));
+    }
+}
@@ -0,0 +1,223 @@
+use crate::policy::Policy;
+use crate::types::GeneratedFile;
+use std::fmt::Write;
+
+/// CI system `init` writes a pipeline for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CiProvider {
+    #[default]
+    Github,
+    Gitlab,
+    /// No pipeline
+    None,
+}
+
+impl std::str::FromStr for CiProvider {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "github" => Ok(CiProvider::Github),
+            "gitlab" => Ok(CiProvider::Gitlab),
+            "none" => Ok(CiProvider::None),
+            _ => Err(format!(
+                "Invalid CI provider '{}'. Use 'github', 'gitlab', or 'none'",
+                s
+            )),
+        }
+    }
+}
+
+/// The checks every pipeline and the pre-commit hooks run
+const CHECKS: &[(&str, &str)] = &[
+    ("validate", "connect-util validate --dir environments"),
+    ("scan", "connect-util scan --dir ."),
+    ("audit", "connect-util audit --dir environments"),
+];
+
+const GITIGNORE: &str = "\
+.terraform/
+*.tfstate
+*.tfstate.backup
+*.tfplan
+# Decrypted SOPS secrets; commit the .enc file only
+secrets.auto.tfvars
+";
+
+fn policy(environments: &[String]) -> String {
+    let mut out = String::from(
+        "\
+# Org policy for every connector in this repository; see `connect-util validate`
+# Connector classes that may be used (empty allows all)
+allowed_connector_classes: []
+denied_connector_classes: []
+",
+    );
+    if let Some(production) = environments
+        .iter()
+        .find(|name| ["prod", "production"].contains(&name.as_str()))
+    {
+        let _ = write!(
+            out,
+            "\
+# Stricter rules for production connectors
+overrides:
+  - path: environments/{}/**
+    denied_connector_classes: [DatagenSource]
+",
+            production
+        );
+    }
+    out.push_str(
+        "\
+required_values:
+  kafka.auth.mode: SERVICE_ACCOUNT
+max_tasks: 4
+# Per-rule severity overrides: error, warning, or off
+severities: {}
+# Profiles for `connect-util generate --environment <name>`
+environments:
+",
+    );
+    for name in environments {
+        let _ = write!(
+            out,
+            "  {}:\n    id: env-REPLACE_ME\n    cluster_id: lkc-REPLACE_ME\n",
+            name
+        );
+    }
+    out
+}
+
+fn github_workflow() -> String {
+    let mut out = String::from(
+        "\
+name: Connectors
+
+on:
+  pull_request:
+  push:
+    branches: [main]
+
+jobs:
+  connect-util:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - name: Install connect-util
+        run: cargo install --git https://github.com/yourusername/confluent-connect-util --locked
+",
+    );
+    for (name, command) in CHECKS {
+        let _ = write!(out, "      - name: {}\n        run: {}\n", name, command);
+    }
+    out
+}
+
+fn gitlab_pipeline() -> String {
+    let mut out = String::from(
+        "\
+connect-util:
+  image: rust:latest
+  before_script:
+    - cargo install --git https://github.com/yourusername/confluent-connect-util --locked
+  script:
+",
+    );
+    for (_, command) in CHECKS {
+        let _ = writeln!(out, "    - {}", command);
+    }
+    out
+}
+
+fn pre_commit() -> String {
+    let mut out = String::from("repos:\n  - repo: local\n    hooks:\n");
+    for (name, command) in CHECKS.iter().take(2) {
+        let _ = write!(
+            out,
+            "      - id: connect-util-{0}\n        name: connect-util {0}\n        entry: {1}\n        language: system\n        files: \\.(tf|tfvars)$\n        pass_filenames: false\n",
+            name, command
+        );
+    }
+    out
+}
+
+/// The files of a new connectors repository, relative to its root: a directory per
+/// environment, `modules/`, the policy file, a CI pipeline, and pre-commit hooks
+pub fn scaffold(environments: &[String], ci: CiProvider) -> Vec<GeneratedFile> {
+    let file = |path: &str, content: String| GeneratedFile {
+        path: Some(path.to_string()),
+        content,
+    };
+    let mut files = vec![file(Policy::FILE_NAME, policy(environments))];
+    for name in environments {
+        files.push(file(
+            &format!("environments/{}/.gitkeep", name),
+            String::new(),
+        ));
+    }
+    files.push(file("modules/.gitkeep", String::new()));
+    match ci {
+        CiProvider::Github => {
+            files.push(file(".github/workflows/connectors.yml", github_workflow()))
+        }
+        CiProvider::Gitlab => files.push(file(".gitlab-ci.yml", gitlab_pipeline())),
+        CiProvider::None => {}
+    }
+    files.push(file(".pre-commit-config.yaml", pre_commit()));
+    files.push(file(".gitignore", GITIGNORE.to_string()));
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scaffold() {
+        let environments = vec!["dev".to_string(), "prod".to_string()];
+        let files = scaffold(&environments, CiProvider::Github);
+        let paths: Vec<&str> = files.iter().filter_map(|f| f.path.as_deref()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                ".connect-util.yaml",
+                "environments/dev/.gitkeep",
+                "environments/prod/.gitkeep",
+                "modules/.gitkeep",
+                ".github/workflows/connectors.yml",
+                ".pre-commit-config.yaml",
+                ".gitignore",
+            ]
+        );
+
+        // The policy file loads, with a profile per environment
+        let policy = Policy::from_yaml(&files[0].content).unwrap();
+        assert_eq!(
+            policy.environments.keys().collect::<Vec<_>>(),
+            vec!["dev", "prod"]
+        );
+        assert_eq!(policy.overrides[0].path, "environments/prod/**");
+        assert_eq!(policy.max_tasks, Some(4));
+
+        let workflow = &files[4].content;
+        assert!(workflow.contains("run: connect-util validate --dir environments\n"));
+        assert!(files[5].content.contains("id: connect-util-scan"));
+        assert!(!files[5].content.contains("audit"));
+
+        let files = scaffold(&["staging".to_string()], CiProvider::Gitlab);
+        assert!(Policy::from_yaml(&files[0].content)
+            .unwrap()
+            .overrides
+            .is_empty());
+        assert_eq!(files[3].path.as_deref(), Some(".gitlab-ci.yml"));
+        assert!(files[3]
+            .content
+            .contains("    - connect-util scan --dir .\n"));
+        assert!(scaffold(&[], CiProvider::None).iter().all(|f| !f
+            .path
+            .as_deref()
+            .unwrap()
+            .contains("ci")));
+    }
+}
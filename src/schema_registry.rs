@@ -0,0 +1,206 @@
+use crate::types::is_dynamic_value;
+use std::collections::HashMap;
+
+/// Schema context the connector's subjects are registered under
+pub const SCHEMA_CONTEXT: &str = "schema.context.name";
+/// Enables client-side field level encryption
+pub const CSFLE_ENABLED: &str = "csfle.enabled";
+/// What the connector does with records it can't encrypt or decrypt
+pub const CSFLE_ON_FAILURE: &str = "csfle.onFailure";
+/// Service account used for Schema Registry and key access when CSFLE runs
+/// with `SERVICE_ACCOUNT` auth
+pub const SR_SERVICE_ACCOUNT: &str = "sr.service.account.id";
+pub const SR_API_KEY: &str = "schema.registry.api.key";
+pub const SR_API_SECRET: &str = "schema.registry.api.secret";
+
+/// Schema Registry credentials; these belong in `config_sensitive`
+pub const CREDENTIAL_KEYS: &[&str] = &[
+    SR_API_KEY,
+    SR_API_SECRET,
+    "schema.registry.basic.auth.user.info",
+];
+
+/// Settings that are not secret and belong in `config_nonsensitive`
+const NONSENSITIVE_KEYS: &[&str] = &[
+    SCHEMA_CONTEXT,
+    CSFLE_ENABLED,
+    CSFLE_ON_FAILURE,
+    SR_SERVICE_ACCOUNT,
+];
+
+pub const CSFLE_ON_FAILURE_VALUES: &[&str] = &["ERROR", "NONE"];
+
+/// Record formats backed by Schema Registry
+const SCHEMA_FORMATS: &[&str] = &["AVRO", "PROTOBUF", "JSON_SR"];
+
+const FORMAT_KEYS: &[&str] = &[
+    "input.data.format",
+    "input.key.format",
+    "output.data.format",
+    "output.key.format",
+];
+
+/// Checks Schema Registry and CSFLE settings: that each key sits in the right
+/// config block and that CSFLE has the credentials its auth mode needs
+pub fn check(
+    config_nonsensitive: &HashMap<String, String>,
+    config_sensitive: &HashMap<String, String>,
+) -> Result<(), String> {
+    for key in CREDENTIAL_KEYS {
+        if config_nonsensitive
+            .get(*key)
+            .is_some_and(|value| !value.is_empty())
+        {
+            return Err(format!(
+                "Schema Registry credential '{}' should be in config_sensitive block",
+                key
+            ));
+        }
+    }
+    for key in NONSENSITIVE_KEYS {
+        if config_sensitive.contains_key(*key) {
+            return Err(format!(
+                "'{}' is not secret and should be in config_nonsensitive block",
+                key
+            ));
+        }
+    }
+
+    let known = |key: &str| {
+        config_nonsensitive
+            .get(key)
+            .filter(|value| !is_dynamic_value(value))
+    };
+    let csfle = match known(CSFLE_ENABLED).map(String::as_str) {
+        Some("true") => true,
+        Some("false") | None => false,
+        Some(other) => {
+            return Err(format!(
+                "Invalid value '{}' for field '{}'. Valid values: [\"true\", \"false\"]",
+                other, CSFLE_ENABLED
+            ))
+        }
+    };
+    if let Some(on_failure) = known(CSFLE_ON_FAILURE) {
+        if !CSFLE_ON_FAILURE_VALUES.contains(&on_failure.as_str()) {
+            return Err(format!(
+                "Invalid value '{}' for field '{}'. Valid values: {:?}",
+                on_failure, CSFLE_ON_FAILURE, CSFLE_ON_FAILURE_VALUES
+            ));
+        }
+    }
+
+    // Both features need schemas, so a connector that sets only non-SR formats can't use them
+    let formats: Vec<&String> = FORMAT_KEYS.iter().filter_map(|key| known(key)).collect();
+    let uses_schemas = formats
+        .iter()
+        .any(|format| SCHEMA_FORMATS.contains(&format.as_str()));
+    if !formats.is_empty() && !uses_schemas {
+        for (key, enabled) in [
+            (
+                SCHEMA_CONTEXT,
+                config_nonsensitive.contains_key(SCHEMA_CONTEXT),
+            ),
+            (CSFLE_ENABLED, csfle),
+        ] {
+            if enabled {
+                return Err(format!(
+                    "'{}' requires a Schema Registry data format ({})",
+                    key,
+                    SCHEMA_FORMATS.join(", ")
+                ));
+            }
+        }
+    }
+
+    if csfle {
+        match known("kafka.auth.mode").map(String::as_str) {
+            Some("SERVICE_ACCOUNT") if !config_nonsensitive.contains_key(SR_SERVICE_ACCOUNT) => {
+                return Err(format!(
+                    "CSFLE with SERVICE_ACCOUNT auth requires '{}'",
+                    SR_SERVICE_ACCOUNT
+                ));
+            }
+            Some("KAFKA_API_KEY") => {
+                for key in [SR_API_KEY, SR_API_SECRET] {
+                    if !config_sensitive.contains_key(key) {
+                        return Err(format!(
+                            "CSFLE with KAFKA_API_KEY auth requires '{}' in config_sensitive",
+                            key
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_check_placement() {
+        let result = check(&map(&[(SR_API_SECRET, "hunter2")]), &map(&[]));
+        assert_eq!(
+            result.unwrap_err(),
+            "Schema Registry credential 'schema.registry.api.secret' should be in config_sensitive block"
+        );
+        let result = check(&map(&[]), &map(&[(CSFLE_ENABLED, "true")]));
+        assert!(result.unwrap_err().contains("config_nonsensitive"));
+        assert!(check(&map(&[]), &map(&[(SR_API_KEY, "key")])).is_ok());
+    }
+
+    #[test]
+    fn test_check_csfle_values_and_formats() {
+        assert!(check(&map(&[(CSFLE_ENABLED, "yes")]), &map(&[])).is_err());
+        assert!(check(&map(&[(CSFLE_ON_FAILURE, "SKIP")]), &map(&[])).is_err());
+        assert!(check(&map(&[(CSFLE_ENABLED, "${var.csfle}")]), &map(&[])).is_ok());
+
+        let result = check(
+            &map(&[("output.data.format", "JSON"), (SCHEMA_CONTEXT, "orders")]),
+            &map(&[]),
+        );
+        assert!(result
+            .unwrap_err()
+            .contains("requires a Schema Registry data format"));
+        assert!(check(
+            &map(&[("output.data.format", "AVRO"), (SCHEMA_CONTEXT, "orders")]),
+            &map(&[]),
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_check_csfle_credentials() {
+        let service_account = map(&[
+            ("kafka.auth.mode", "SERVICE_ACCOUNT"),
+            (CSFLE_ENABLED, "true"),
+        ]);
+        assert!(check(&service_account, &map(&[]))
+            .unwrap_err()
+            .contains(SR_SERVICE_ACCOUNT));
+        let mut wired = service_account.clone();
+        wired.insert(SR_SERVICE_ACCOUNT.to_string(), "sa-123".to_string());
+        assert!(check(&wired, &map(&[])).is_ok());
+
+        let api_key = map(&[
+            ("kafka.auth.mode", "KAFKA_API_KEY"),
+            (CSFLE_ENABLED, "true"),
+        ]);
+        assert!(check(&api_key, &map(&[(SR_API_KEY, "k")]))
+            .unwrap_err()
+            .contains(SR_API_SECRET));
+        assert!(check(&api_key, &map(&[(SR_API_KEY, "k"), (SR_API_SECRET, "s")])).is_ok());
+    }
+}
@@ -0,0 +1,221 @@
+use crate::cloud::LiveConnector;
+use crate::conflicts::ModuleConnector;
+use crate::error::ConnectUtilError;
+use crate::scan::SecretScanner;
+use crate::types::is_dynamic_value;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Written in place of credential values
+const REDACTED: &str = "<redacted>";
+
+/// One side of a snapshot. The local and live files share this shape, with keys
+/// sorted, so `diff` lines them up
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigSnapshot {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connector_class: Option<String>,
+    /// Where the config came from: the `.tf` file, or the connector ID
+    pub source: String,
+    pub config: BTreeMap<String, String>,
+}
+
+/// The parsed local config, with both maps merged as Confluent Cloud reports
+/// them. Literal credentials are redacted; references such as `${var.db_password}`
+/// are kept, since they show where the value comes from
+pub fn local_snapshot(connector: &ModuleConnector, scanner: &SecretScanner) -> ConfigSnapshot {
+    let config = &connector.config;
+    let redact = |sensitive: bool, key: &String, value: &String| {
+        let value = if (sensitive || scanner.is_sensitive_key(key)) && !is_dynamic_value(value) {
+            REDACTED.to_string()
+        } else {
+            value.clone()
+        };
+        (key.clone(), value)
+    };
+    ConfigSnapshot {
+        name: config.name.clone(),
+        connector_class: Some(config.connector_class.clone()),
+        source: connector.file.display().to_string(),
+        config: config
+            .config
+            .iter()
+            .map(|(key, value)| redact(false, key, value))
+            .chain(
+                config
+                    .sensitive_config
+                    .iter()
+                    .map(|(key, value)| redact(true, key, value)),
+            )
+            .collect(),
+    }
+}
+
+/// The running config as fetched. Confluent Cloud masks most credentials already;
+/// keys the local config keeps in `config_sensitive`, or that look like
+/// credentials, are redacted too
+pub fn live_snapshot(
+    live: &LiveConnector,
+    local: &ModuleConnector,
+    scanner: &SecretScanner,
+) -> ConfigSnapshot {
+    ConfigSnapshot {
+        name: live.name.clone(),
+        connector_class: live.connector_class.clone(),
+        source: live.id.clone().unwrap_or_else(|| live.name.clone()),
+        config: live
+            .config
+            .iter()
+            .map(|(key, value)| {
+                let sensitive = local.config.sensitive_config.contains_key(key)
+                    || scanner.is_sensitive_key(key);
+                let value = if sensitive { REDACTED } else { value };
+                (key.clone(), value.to_string())
+            })
+            .collect(),
+    }
+}
+
+/// A file name for the connector that stays inside the debug directory
+fn file_stem(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+        .trim_start_matches('.')
+        .to_string()
+}
+
+/// Writes `<name>.local.json`, `<name>.live.json` when the connector is deployed,
+/// and `<name>.findings.txt` with the failed checks into `dir`, creating it if
+/// needed. Returns the files written
+pub fn write_snapshot(
+    dir: &Path,
+    local: &ModuleConnector,
+    live: Option<&LiveConnector>,
+    findings: &[String],
+) -> Result<Vec<PathBuf>, ConnectUtilError> {
+    std::fs::create_dir_all(dir)?;
+    let scanner = SecretScanner::new();
+    let stem = file_stem(&local.config.name);
+    let mut written = Vec::new();
+    let mut write = |suffix: &str, content: String| -> Result<(), ConnectUtilError> {
+        let path = dir.join(format!("{}.{}", stem, suffix));
+        std::fs::write(&path, content)?;
+        written.push(path);
+        Ok(())
+    };
+    write(
+        "local.json",
+        format!(
+            "{}\n",
+            serde_json::to_string_pretty(&local_snapshot(local, &scanner))?
+        ),
+    )?;
+    if let Some(live) = live {
+        write(
+            "live.json",
+            format!(
+                "{}\n",
+                serde_json::to_string_pretty(&live_snapshot(live, local, &scanner))?
+            ),
+        )?;
+    }
+    write(
+        "findings.txt",
+        findings
+            .iter()
+            .map(|finding| format!("{}\n", finding))
+            .collect(),
+    )?;
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ConnectorConfig;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_write_snapshot_redacts_credentials() {
+        let local = ModuleConnector {
+            file: PathBuf::from("prod/main.tf"),
+            config: ConnectorConfig {
+                name: "orders/cdc".to_string(),
+                connector_class: "PostgresCdcSourceV2".to_string(),
+                config: HashMap::from([
+                    ("tasks.max".to_string(), "1".to_string()),
+                    ("database.password".to_string(), "hunter2".to_string()),
+                ]),
+                sensitive_config: HashMap::from([
+                    ("kafka.api.key".to_string(), "ABCDEF".to_string()),
+                    (
+                        "kafka.api.secret".to_string(),
+                        "${var.api_secret}".to_string(),
+                    ),
+                ]),
+            },
+        };
+        let live = LiveConnector {
+            name: "orders/cdc".to_string(),
+            id: Some("lcc-1".to_string()),
+            connector_class: Some("PostgresCdcSourceV2".to_string()),
+            state: Some("RUNNING".to_string()),
+            config: HashMap::from([
+                ("tasks.max".to_string(), "2".to_string()),
+                ("kafka.api.key".to_string(), "ABCDEF".to_string()),
+            ]),
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let written = write_snapshot(
+            dir.path(),
+            &local,
+            Some(&live),
+            &["tasks.max: Terraform '1', Confluent Cloud '2'".to_string()],
+        )
+        .unwrap();
+        let names: Vec<String> = written
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "orders_cdc.local.json",
+                "orders_cdc.live.json",
+                "orders_cdc.findings.txt"
+            ]
+        );
+
+        let read = |name: &str| std::fs::read_to_string(dir.path().join(name)).unwrap();
+        let local_json: serde_json::Value = serde_json::from_str(&read(&names[0])).unwrap();
+        assert_eq!(local_json["source"], "prod/main.tf");
+        assert_eq!(local_json["config"]["tasks.max"], "1");
+        assert_eq!(local_json["config"]["database.password"], REDACTED);
+        assert_eq!(local_json["config"]["kafka.api.key"], REDACTED);
+        assert_eq!(
+            local_json["config"]["kafka.api.secret"],
+            "${var.api_secret}"
+        );
+        let live_json: serde_json::Value = serde_json::from_str(&read(&names[1])).unwrap();
+        assert_eq!(live_json["source"], "lcc-1");
+        assert_eq!(live_json["config"]["tasks.max"], "2");
+        assert_eq!(live_json["config"]["kafka.api.key"], REDACTED);
+        assert!(!read(&names[0]).contains("hunter2"));
+        assert_eq!(
+            read(&names[2]),
+            "tasks.max: Terraform '1', Confluent Cloud '2'\n"
+        );
+
+        let written = write_snapshot(dir.path(), &local, None, &[]).unwrap();
+        assert_eq!(written.len(), 2);
+    }
+}
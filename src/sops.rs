@@ -0,0 +1,178 @@
+use crate::error::ConnectUtilError;
+use crate::types::SopsOptions;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Encrypted variable file `--sops` writes next to the connector
+pub const SECRETS_FILE: &str = "secrets.auto.tfvars.enc";
+
+/// Value each secret variable starts with, until it's edited with `sops`
+const PLACEHOLDER: &str = "<REPLACE_WITH_ACTUAL_VALUE>";
+
+/// One `name="value"` line per variable. The dotenv store encrypts each value and
+/// leaves the names readable, and decrypts back to valid tfvars
+pub fn secrets_tfvars(variables: &[String]) -> String {
+    variables
+        .iter()
+        .map(|name| format!("{}=\"{}\"\n", name, PLACEHOLDER))
+        .collect()
+}
+
+/// Fails unless sops can read the plaintext from `/dev/stdin`, which only exists
+/// on Unix. Writing it to a temporary file instead would leave secrets on disk
+pub fn check_supported() -> Result<(), ConnectUtilError> {
+    if cfg!(unix) {
+        Ok(())
+    } else {
+        Err(ConnectUtilError::Config(
+            "--sops is only supported on Linux and macOS".to_string(),
+        ))
+    }
+}
+
+/// Arguments for encrypting stdin as `file_name`, so `.sops.yaml` path rules apply
+fn encrypt_args(file_name: &str, options: &SopsOptions) -> Vec<String> {
+    let mut args: Vec<String> = [
+        "--encrypt",
+        "--input-type",
+        "dotenv",
+        "--output-type",
+        "dotenv",
+        "--filename-override",
+        file_name,
+    ]
+    .map(str::to_string)
+    .to_vec();
+    if !options.age.is_empty() {
+        args.extend(["--age".to_string(), options.age.join(",")]);
+    }
+    if !options.kms.is_empty() {
+        args.extend(["--kms".to_string(), options.kms.join(",")]);
+    }
+    args.push("/dev/stdin".to_string());
+    args
+}
+
+/// Encrypts `plaintext` with the `sops` binary on PATH
+pub fn encrypt(
+    plaintext: &str,
+    file_name: &str,
+    options: &SopsOptions,
+) -> Result<String, ConnectUtilError> {
+    encrypt_with(Command::new("sops"), plaintext, file_name, options)
+}
+
+fn encrypt_with(
+    mut sops: Command,
+    plaintext: &str,
+    file_name: &str,
+    options: &SopsOptions,
+) -> Result<String, ConnectUtilError> {
+    check_supported()?;
+    let mut child = sops
+        .args(encrypt_args(file_name, options))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => ConnectUtilError::Config(
+                "--sops needs the `sops` binary on PATH (https://github.com/getsops/sops)"
+                    .to_string(),
+            ),
+            _ => ConnectUtilError::Io(e),
+        })?;
+    // sops may exit before reading everything; its status explains why
+    let written = match child.stdin.take() {
+        Some(mut stdin) => stdin.write_all(plaintext.as_bytes()),
+        None => Ok(()),
+    };
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(ConnectUtilError::Config(format!(
+            "sops failed to encrypt {}: {}",
+            file_name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    written?;
+    String::from_utf8(output.stdout)
+        .map_err(|e| ConnectUtilError::Unknown(format!("sops wrote non-UTF-8 output: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secrets_tfvars() {
+        assert_eq!(
+            secrets_tfvars(&[
+                "orders_database_password".to_string(),
+                "orders_api_key".to_string()
+            ]),
+            "orders_database_password=\"<REPLACE_WITH_ACTUAL_VALUE>\"\norders_api_key=\"<REPLACE_WITH_ACTUAL_VALUE>\"\n"
+        );
+    }
+
+    /// Stands in for sops with a shell `script` that receives sops' arguments
+    #[cfg(unix)]
+    fn shell(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.args(["-c", script, "sops"]);
+        command
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_encrypt_runs_sops() {
+        let options = SopsOptions {
+            age: vec!["age1abc".to_string(), "age1def".to_string()],
+            kms: vec![],
+        };
+        let output = encrypt_with(
+            shell("echo \"$@\"; cat"),
+            "a=\"b\"\n",
+            "prod/secrets.auto.tfvars.enc",
+            &options,
+        )
+        .unwrap();
+        assert_eq!(
+            output,
+            "--encrypt --input-type dotenv --output-type dotenv --filename-override prod/secrets.auto.tfvars.enc --age age1abc,age1def /dev/stdin\na=\"b\"\n"
+        );
+
+        let error = encrypt_with(
+            shell("cat >/dev/null; echo 'no matching creation rules' >&2; exit 1"),
+            "",
+            SECRETS_FILE,
+            &SopsOptions::default(),
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("no matching creation rules"));
+
+        let error = encrypt_with(
+            Command::new("/nonexistent/sops"),
+            "",
+            SECRETS_FILE,
+            &SopsOptions::default(),
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("needs the `sops` binary"));
+    }
+
+    #[cfg(not(unix))]
+    #[test]
+    fn test_encrypt_unsupported() {
+        let error = encrypt_with(
+            Command::new("sops"),
+            "",
+            SECRETS_FILE,
+            &SopsOptions::default(),
+        )
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("only supported on Linux and macOS"));
+    }
+}
@@ -0,0 +1,190 @@
+use crate::catalog::{fingerprint, CatalogBundle};
+use crate::types::ConnectorDefinition;
+
+/// Start of the comment a generated file opens with, as in
+/// `# Generated by connect-util 0.1.0 from catalog 3f9c... (DatagenSource definition 81ab...)`
+const PREFIX: &str = "# Generated by connect-util ";
+
+/// What a generated file was generated with: the tool and catalog, and for a
+/// connector file the definition of its connector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stamp {
+    pub tool_version: String,
+    /// Fingerprint of the whole catalog
+    pub catalog: String,
+    /// Connector name and the fingerprint of its definition
+    pub definition: Option<(String, String)>,
+}
+
+impl Stamp {
+    /// This build's stamp, for a file configuring `connector` if given
+    pub fn current(connector: Option<&ConnectorDefinition>) -> Self {
+        Self {
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            catalog: CatalogBundle::builtin().fingerprint(),
+            definition: connector.map(|connector| (connector.name.clone(), fingerprint(connector))),
+        }
+    }
+
+    /// The header comment line
+    pub fn comment(&self) -> String {
+        let mut comment = format!(
+            "{}{} from catalog {}",
+            PREFIX, self.tool_version, self.catalog
+        );
+        if let Some((name, definition)) = &self.definition {
+            comment.push_str(&format!(" ({} definition {})", name, definition));
+        }
+        comment.push('\n');
+        comment
+    }
+
+    pub fn parse(line: &str) -> Option<Self> {
+        let rest = line.trim().strip_prefix(PREFIX)?;
+        let (tool_version, rest) = rest.split_once(" from catalog ")?;
+        let (catalog, definition) = match rest.split_once(" (") {
+            Some((catalog, definition)) => {
+                let (name, hash) = definition.strip_suffix(')')?.split_once(" definition ")?;
+                (catalog, Some((name.to_string(), hash.to_string())))
+            }
+            None => (rest, None),
+        };
+        Some(Self {
+            tool_version: tool_version.to_string(),
+            catalog: catalog.to_string(),
+            definition,
+        })
+    }
+
+    /// Why the file is out of date with `catalog`, if it is. Only a change to the
+    /// file's own connector counts; the rest of the catalog moving on doesn't
+    pub fn staleness(&self, catalog: &CatalogBundle) -> Option<String> {
+        let (name, definition) = self.definition.as_ref()?;
+        let Some(current) = catalog
+            .connectors
+            .iter()
+            .find(|connector| connector.name == *name)
+        else {
+            return Some(format!(
+                "generated by connect-util {} for {}, which is no longer in the catalog",
+                self.tool_version, name
+            ));
+        };
+        (fingerprint(current) != *definition).then(|| {
+            format!(
+                "generated by connect-util {} from an older {} definition; regenerate it or run generate --merge-into to pick up the catalog's changes",
+                self.tool_version, name
+            )
+        })
+    }
+}
+
+/// `content` with this build's stamp for `connector` above it
+pub fn stamp(content: &str, connector: Option<&ConnectorDefinition>) -> String {
+    format!("{}{}", Stamp::current(connector).comment(), content)
+}
+
+/// Every stamp in a file. Appending to a file leaves a stamp above each addition
+pub fn stamps(content: &str) -> Vec<Stamp> {
+    content.lines().filter_map(Stamp::parse).collect()
+}
+
+/// `merged` with the stamp for the connector in `generated` brought up to date,
+/// the stamp added at the top if the file had none for it
+pub fn restamp(merged: &str, generated: &str) -> String {
+    let Some(fresh) = stamps(generated)
+        .into_iter()
+        .find(|s| s.definition.is_some())
+    else {
+        return merged.to_string();
+    };
+    let connector = |stamp: &Stamp| stamp.definition.as_ref().map(|(name, _)| name.clone());
+    let mut replaced = false;
+    let mut out = String::new();
+    for line in merged.split_inclusive('\n') {
+        match Stamp::parse(line) {
+            Some(old) if !replaced && connector(&old) == connector(&fresh) => {
+                out.push_str(&fresh.comment());
+                replaced = true;
+            }
+            _ => out.push_str(line),
+        }
+    }
+    if replaced {
+        out
+    } else {
+        format!("{}{}", fresh.comment(), out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stamp_round_trip_and_staleness() {
+        let catalog = CatalogBundle::builtin();
+        let datagen = ConnectorDefinition::get_connector_by_name("DatagenSource").unwrap();
+        let content = stamp(
+            "resource \"confluent_connector\" \"orders\" {\n}\n",
+            Some(&datagen),
+        );
+        assert!(content.starts_with(&format!(
+            "# Generated by connect-util {} from catalog {} (DatagenSource definition ",
+            env!("CARGO_PKG_VERSION"),
+            catalog.fingerprint()
+        )));
+        let parsed = stamps(&content);
+        assert_eq!(parsed, vec![Stamp::current(Some(&datagen))]);
+        assert_eq!(parsed[0].staleness(&catalog), None);
+
+        // Another connector changing doesn't make the file stale
+        let mut moved_on = catalog.clone();
+        moved_on.connectors.retain(|c| c.name != "ActiveMQSource");
+        assert_ne!(moved_on.fingerprint(), catalog.fingerprint());
+        assert_eq!(parsed[0].staleness(&moved_on), None);
+
+        let mut changed = catalog.clone();
+        for connector in &mut changed.connectors {
+            if connector.name == "DatagenSource" {
+                connector.required_configs.pop();
+            }
+        }
+        assert!(parsed[0]
+            .staleness(&changed)
+            .unwrap()
+            .contains("older DatagenSource definition"));
+        changed.connectors.retain(|c| c.name != "DatagenSource");
+        assert!(parsed[0]
+            .staleness(&changed)
+            .unwrap()
+            .contains("no longer in the catalog"));
+
+        let variables = Stamp::current(None);
+        assert_eq!(Stamp::parse(&variables.comment()), Some(variables.clone()));
+        assert_eq!(variables.staleness(&changed), None);
+        assert_eq!(Stamp::parse("# Generated by hand"), None);
+    }
+
+    #[test]
+    fn test_restamp() {
+        let datagen = ConnectorDefinition::get_connector_by_name("DatagenSource").unwrap();
+        let fresh = Stamp::current(Some(&datagen));
+        let old = Stamp {
+            tool_version: "0.0.1".to_string(),
+            catalog: "0000000000000000".to_string(),
+            definition: Some(("DatagenSource".to_string(), "0000000000000000".to_string())),
+        };
+        let generated = stamp("resource {}\n", Some(&datagen));
+        let merged = format!("{}resource {{}}\n", old.comment());
+        assert_eq!(
+            restamp(&merged, &generated),
+            format!("{}resource {{}}\n", fresh.comment())
+        );
+        assert_eq!(
+            restamp("resource {}\n", &generated),
+            format!("{}resource {{}}\n", fresh.comment())
+        );
+        assert_eq!(restamp("resource {}\n", "resource {}\n"), "resource {}\n");
+    }
+}
@@ -0,0 +1,113 @@
+use crate::error::ConnectUtilError;
+use serde::Deserialize;
+use std::collections::BTreeSet;
+
+/// Connectors Terraform already knows about, from state and/or configuration
+#[derive(Debug, Default)]
+pub struct ManagedConnectors {
+    /// Connector names (`config_nonsensitive.name`)
+    pub names: BTreeSet<String>,
+    /// Connector IDs recorded in state (`lcc-...`)
+    pub ids: BTreeSet<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct State {
+    #[serde(default)]
+    resources: Vec<StateResource>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StateResource {
+    #[serde(default)]
+    mode: String,
+    #[serde(rename = "type")]
+    resource_type: String,
+    #[serde(default)]
+    instances: Vec<StateInstance>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StateInstance {
+    #[serde(default)]
+    attributes: serde_json::Value,
+}
+
+impl ManagedConnectors {
+    /// Collects every `confluent_connector` recorded in a `terraform.tfstate` file
+    pub fn from_state(content: &str) -> Result<Self, ConnectUtilError> {
+        let state: State = serde_json::from_str(content)
+            .map_err(|e| ConnectUtilError::Config(format!("Invalid state file: {}", e)))?;
+
+        let mut managed = Self::default();
+        for resource in state.resources {
+            if resource.mode != "managed" || resource.resource_type != "confluent_connector" {
+                continue;
+            }
+            for instance in resource.instances {
+                let attributes = &instance.attributes;
+                if let Some(id) = attributes.get("id").and_then(|v| v.as_str()) {
+                    managed.ids.insert(id.to_string());
+                }
+                if let Some(name) = attributes
+                    .get("config_nonsensitive")
+                    .and_then(|config| config.get("name"))
+                    .and_then(|v| v.as_str())
+                {
+                    managed.names.insert(name.to_string());
+                }
+            }
+        }
+        Ok(managed)
+    }
+
+    /// Whether a live connector is tracked by name or ID
+    pub fn contains(&self, name: &str, id: Option<&str>) -> bool {
+        self.names.contains(name) || id.is_some_and(|id| self.ids.contains(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STATE: &str = r#"{
+      "version": 4,
+      "resources": [
+        {
+          "mode": "managed",
+          "type": "confluent_connector",
+          "name": "orders",
+          "instances": [
+            {"attributes": {"id": "lcc-111", "config_nonsensitive": {"name": "orders-cdc"}}}
+          ]
+        },
+        {
+          "mode": "data",
+          "type": "confluent_connector",
+          "name": "lookup",
+          "instances": [{"attributes": {"id": "lcc-999"}}]
+        },
+        {
+          "mode": "managed",
+          "type": "confluent_kafka_topic",
+          "name": "orders",
+          "instances": [{"attributes": {"id": "lkc/orders"}}]
+        }
+      ]
+    }"#;
+
+    #[test]
+    fn test_from_state() {
+        let managed = ManagedConnectors::from_state(STATE).unwrap();
+        assert_eq!(managed.ids.len(), 1);
+        assert!(managed.contains("orders-cdc", None));
+        assert!(managed.contains("renamed", Some("lcc-111")));
+        assert!(!managed.contains("lookup", Some("lcc-999")));
+    }
+
+    #[test]
+    fn test_from_state_invalid() {
+        assert!(ManagedConnectors::from_state("{").is_err());
+    }
+}
@@ -0,0 +1,210 @@
+use crate::audit::InventoryEntry;
+use crate::lint::LintIssue;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// How many of the slowest files a summary lists
+const SLOWEST: usize = 5;
+
+/// Time spent checking one file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileTiming {
+    pub file: PathBuf,
+    #[serde(rename = "millis", serialize_with = "millis")]
+    pub elapsed: Duration,
+}
+
+pub(crate) fn millis<S: serde::Serializer>(
+    elapsed: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64((elapsed.as_secs_f64() * 1000.0 * 10.0).round() / 10.0)
+}
+
+/// Totals for a validation run, printed after `validate --dir` and included in the
+/// JSON report
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ValidationStats {
+    pub files_scanned: usize,
+    pub connectors: usize,
+    pub connectors_by_type: BTreeMap<String, usize>,
+    pub connectors_by_class: BTreeMap<String, usize>,
+    pub findings_by_severity: BTreeMap<String, usize>,
+    pub findings_by_rule: BTreeMap<String, usize>,
+    pub slowest_files: Vec<FileTiming>,
+}
+
+impl ValidationStats {
+    /// `timings` has an entry for every file checked
+    pub fn collect(
+        timings: &[FileTiming],
+        entries: &[InventoryEntry],
+        issues: &[LintIssue],
+    ) -> Self {
+        let mut stats = ValidationStats {
+            files_scanned: timings.len(),
+            connectors: entries.len(),
+            ..Default::default()
+        };
+        for entry in entries {
+            *stats
+                .connectors_by_type
+                .entry(entry.connector_type.clone())
+                .or_default() += 1;
+            // A data source's class isn't known locally
+            if !entry.is_data_source() {
+                *stats
+                    .connectors_by_class
+                    .entry(entry.connector_class.clone())
+                    .or_default() += 1;
+            }
+        }
+        for issue in issues {
+            *stats
+                .findings_by_severity
+                .entry(issue.severity.to_string())
+                .or_default() += 1;
+            *stats.findings_by_rule.entry(issue.rule.name()).or_default() += 1;
+        }
+        let mut slowest = timings.to_vec();
+        slowest.sort_by(|a, b| b.elapsed.cmp(&a.elapsed).then_with(|| a.file.cmp(&b.file)));
+        slowest.truncate(SLOWEST);
+        stats.slowest_files = slowest;
+        stats
+    }
+
+    /// Multi-line summary for the end of a text run
+    pub fn render(&self) -> String {
+        let counts = |map: &BTreeMap<String, usize>| {
+            map.iter()
+                .map(|(key, count)| format!("{} {}", count, key))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut out = String::new();
+        let _ = writeln!(out, "📊 Validation summary");
+        let _ = writeln!(out, "  Files scanned: {}", self.files_scanned);
+        let _ = write!(out, "  Connectors: {}", self.connectors);
+        if !self.connectors_by_type.is_empty() {
+            let _ = write!(out, " ({})", counts(&self.connectors_by_type));
+        }
+        let _ = writeln!(out);
+        for (class, count) in &self.connectors_by_class {
+            let _ = writeln!(out, "    {}: {}", class, count);
+        }
+        let findings: usize = self.findings_by_severity.values().sum();
+        if findings == 0 {
+            let _ = writeln!(out, "  Findings: none");
+        } else {
+            let _ = writeln!(
+                out,
+                "  Findings: {} ({})",
+                findings,
+                counts(&self.findings_by_severity)
+            );
+            let mut by_rule: Vec<(&String, &usize)> = self.findings_by_rule.iter().collect();
+            by_rule.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+            for (rule, count) in by_rule {
+                let _ = writeln!(out, "    {}: {}", rule, count);
+            }
+        }
+        if !self.slowest_files.is_empty() {
+            let _ = writeln!(out, "  Slowest files:");
+            for timing in &self.slowest_files {
+                let _ = writeln!(
+                    out,
+                    "    {} ({:.1} ms)",
+                    timing.file.display(),
+                    timing.elapsed.as_secs_f64() * 1000.0
+                );
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lint::LintRule;
+
+    fn entry(class: &str, connector_type: &str) -> InventoryEntry {
+        InventoryEntry {
+            file: PathBuf::from("main.tf"),
+            resource: class.to_lowercase(),
+            name: class.to_lowercase(),
+            connector_class: class.to_string(),
+            connector_type: connector_type.to_string(),
+            topics: vec![],
+            data_formats: vec![],
+            auth_mode: None,
+            secrets_backend: vec![],
+            deprecated: None,
+            metadata: Default::default(),
+        }
+    }
+
+    fn issue(rule: LintRule) -> LintIssue {
+        LintIssue {
+            rule,
+            severity: rule.severity(),
+            file: PathBuf::from("main.tf"),
+            line: 1,
+            connector: None,
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_validation_stats() {
+        let timings: Vec<FileTiming> = (1..=7)
+            .map(|i| FileTiming {
+                file: PathBuf::from(format!("{}.tf", i)),
+                elapsed: Duration::from_millis(i),
+            })
+            .collect();
+        let stats = ValidationStats::collect(
+            &timings,
+            &[
+                entry("DatagenSource", "source"),
+                entry("DatagenSource", "source"),
+                entry("S3_SINK", "sink"),
+                entry("", "existing"),
+            ],
+            &[
+                issue(LintRule::InvalidConfig),
+                issue(LintRule::UnusedVariable),
+                issue(LintRule::UnusedVariable),
+            ],
+        );
+        assert_eq!(stats.files_scanned, 7);
+        assert_eq!(stats.connectors_by_type["source"], 2);
+        assert_eq!(stats.connectors_by_class["S3_SINK"], 1);
+        assert!(!stats.connectors_by_class.contains_key(""));
+        assert_eq!(stats.findings_by_severity["warning"], 2);
+        assert_eq!(stats.findings_by_rule["connect_util_invalid_config"], 1);
+        let slowest: Vec<String> = stats
+            .slowest_files
+            .iter()
+            .map(|t| t.file.display().to_string())
+            .collect();
+        assert_eq!(slowest, vec!["7.tf", "6.tf", "5.tf", "4.tf", "3.tf"]);
+
+        let summary = stats.render();
+        assert!(summary
+            .contains("  Connectors: 4 (1 existing, 1 sink, 2 source)\n    DatagenSource: 2\n"));
+        assert!(summary.contains(
+            "  Findings: 3 (1 error, 2 warning)\n    connect_util_unused_variable: 2\n    connect_util_invalid_config: 1\n"
+        ));
+        assert!(summary.contains("    7.tf (7.0 ms)\n"));
+
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["slowest_files"][0]["millis"], 7.0);
+        assert!(ValidationStats::default()
+            .render()
+            .contains("Findings: none"));
+    }
+}
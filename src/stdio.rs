@@ -0,0 +1,66 @@
+use crate::error::ConnectUtilError;
+use std::io::Read;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// The path that stands for standard input, or standard output where a command writes
+pub const STDIO: &str = "-";
+
+/// The file name findings in standard input are reported under without `--stdin-filename`
+pub const STDIN_NAME: &str = "<stdin>";
+
+static STDIN: OnceLock<String> = OnceLock::new();
+
+pub fn is_stdio(path: &str) -> bool {
+    path == STDIO
+}
+
+/// Standard input, read on first use. Later calls get the same content, so one run
+/// can check it more than once, e.g. for findings and again for `--profile`
+pub fn read_stdin() -> Result<String, ConnectUtilError> {
+    if let Some(content) = STDIN.get() {
+        return Ok(content.clone());
+    }
+    let mut content = String::new();
+    std::io::stdin().read_to_string(&mut content)?;
+    Ok(STDIN.get_or_init(|| content).clone())
+}
+
+/// The contents of `path`, or standard input for `-`. `what` names the input in the
+/// error for a missing file, e.g. `Plan file`
+pub fn read_input(path: &str, what: &str) -> Result<String, ConnectUtilError> {
+    if is_stdio(path) {
+        return read_stdin();
+    }
+    if !Path::new(path).exists() {
+        return Err(ConnectUtilError::Config(format!(
+            "{} not found: {}",
+            what, path
+        )));
+    }
+    Ok(std::fs::read_to_string(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_input_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plan.json");
+        std::fs::write(&path, "{}").unwrap();
+        assert_eq!(
+            read_input(path.to_str().unwrap(), "Plan file").unwrap(),
+            "{}"
+        );
+
+        let missing = dir.path().join("missing.json");
+        let error = read_input(missing.to_str().unwrap(), "Plan file")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("Plan file not found"));
+        assert!(is_stdio("-"));
+        assert!(!is_stdio("./-"));
+    }
+}
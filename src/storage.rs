@@ -0,0 +1,293 @@
+use crate::types::is_dynamic_value;
+use regex::Regex;
+use std::collections::HashMap;
+
+pub const PARTITIONER_CLASS: &str = "partitioner.class";
+/// Record fields the field partitioner splits objects by
+pub const PARTITION_FIELD_NAME: &str = "partition.field.name";
+/// Datetime pattern for the object path under `topics.dir`
+pub const PATH_FORMAT: &str = "path.format";
+/// Whether time-based partitions cover an hour or a day
+pub const TIME_INTERVAL: &str = "time.interval";
+pub const LOCALE: &str = "locale";
+pub const TIMEZONE: &str = "timezone";
+
+pub const PARTITIONER_CLASSES: &[&str] = &[
+    "DefaultPartitioner",
+    "FieldPartitioner",
+    "TimeBasedPartitioner",
+];
+
+/// Letters Joda-Time reads as datetime fields in `path.format`
+const PATTERN_LETTERS: &str = "GCYxweEyDMdaKhHkmsSzZ";
+
+/// How an object-store sink lays out the objects it writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Partitioner {
+    /// One directory per Kafka partition
+    Default,
+    /// One directory per value of `partition.field.name`
+    Field,
+    /// Time-based, with a `path.format` of the user's choosing
+    Time,
+    /// Time-based, one directory per day
+    Daily,
+    /// Time-based, one directory per hour
+    Hourly,
+}
+
+impl std::str::FromStr for Partitioner {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "default" => Ok(Partitioner::Default),
+            "field" => Ok(Partitioner::Field),
+            "time" => Ok(Partitioner::Time),
+            "daily" => Ok(Partitioner::Daily),
+            "hourly" => Ok(Partitioner::Hourly),
+            _ => Err(format!(
+                "Invalid partitioner '{}'. Use 'default', 'field', 'time', 'daily', or 'hourly'",
+                s
+            )),
+        }
+    }
+}
+
+impl Partitioner {
+    pub fn class(&self) -> &'static str {
+        match self {
+            Partitioner::Default => "DefaultPartitioner",
+            Partitioner::Field => "FieldPartitioner",
+            Partitioner::Time | Partitioner::Daily | Partitioner::Hourly => "TimeBasedPartitioner",
+        }
+    }
+
+    pub fn is_time_based(&self) -> bool {
+        self.class() == "TimeBasedPartitioner"
+    }
+}
+
+/// Hive-style `path.format` for a time interval, e.g.
+/// `'year'=YYYY/'month'=MM/'day'=dd/'hour'=HH` for `HOURLY`
+pub fn build_path_format(hourly: bool) -> String {
+    let mut parts = vec!["'year'=YYYY", "'month'=MM", "'day'=dd"];
+    if hourly {
+        parts.push("'hour'=HH");
+    }
+    parts.join("/")
+}
+
+/// The `time.interval` a path format needs: `HOURLY` when it has an hour field
+pub fn time_interval(path_format: &str) -> &'static str {
+    if unquoted(path_format).any(|c| matches!(c, 'H' | 'h' | 'k' | 'K')) {
+        "HOURLY"
+    } else {
+        "DAILY"
+    }
+}
+
+/// Checks a `path.format` is a Joda-Time pattern the connector can expand: literal
+/// text is quoted, every unquoted letter is a date field, there's a year, and the
+/// path doesn't start or end with `/`
+pub fn check_path_format(pattern: &str) -> Result<(), String> {
+    let invalid = |reason: String| format!("Invalid path.format '{}': {}", pattern, reason);
+    if pattern.trim().is_empty() {
+        return Err(invalid("the pattern is empty".to_string()));
+    }
+    if pattern.starts_with('/') || pattern.ends_with('/') {
+        return Err(invalid(
+            "drop the leading or trailing '/'; the connector adds them".to_string(),
+        ));
+    }
+    if !pattern.matches('\'').count().is_multiple_of(2) {
+        return Err(invalid("a quoted literal isn't closed".to_string()));
+    }
+
+    let text: String = unquoted(pattern).collect();
+    for word in text.split(|c: char| !c.is_ascii_alphabetic()) {
+        if word.chars().any(|c| !PATTERN_LETTERS.contains(c)) {
+            return Err(invalid(format!(
+                "unquoted text '{}'; wrap literals in single quotes, e.g. 'year'=YYYY",
+                word
+            )));
+        }
+    }
+    if !text.chars().any(|c| matches!(c, 'Y' | 'y' | 'x')) {
+        return Err(invalid("it has no year field (YYYY)".to_string()));
+    }
+    Ok(())
+}
+
+/// Checks a locale is a language with an optional country, e.g. `en` or `en-US`
+pub fn check_locale(locale: &str) -> Result<(), String> {
+    let pattern = Regex::new(r"^[a-z]{2,3}([_-][A-Z]{2})?$").expect("valid regex");
+    if pattern.is_match(locale) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid locale '{}': expected a language and optional country, e.g. en-US",
+            locale
+        ))
+    }
+}
+
+/// Checks a timezone is `UTC` or an IANA `Area/Location` name, e.g. `America/New_York`
+pub fn check_timezone(timezone: &str) -> Result<(), String> {
+    let pattern = Regex::new(r"^(UTC|GMT|[A-Z][A-Za-z_]+(/[A-Z][A-Za-z0-9_+-]+){1,2})$")
+        .expect("valid regex");
+    if pattern.is_match(timezone) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid timezone '{}': expected UTC or an IANA name, e.g. America/New_York",
+            timezone
+        ))
+    }
+}
+
+/// Checks an object-store sink's layout settings agree: the path format, locale, and
+/// timezone are well formed, the field partitioner names its fields, and the
+/// time-based settings only appear with a time-based partitioner
+pub fn check(config_nonsensitive: &HashMap<String, String>) -> Result<(), String> {
+    let known = |key: &str| {
+        config_nonsensitive
+            .get(key)
+            .filter(|value| !is_dynamic_value(value))
+    };
+    if let Some(path_format) = known(PATH_FORMAT) {
+        check_path_format(path_format)?;
+    }
+    if let Some(locale) = known(LOCALE) {
+        check_locale(locale)?;
+    }
+    if let Some(timezone) = known(TIMEZONE) {
+        check_timezone(timezone)?;
+    }
+
+    match known(PARTITIONER_CLASS).map(String::as_str) {
+        Some("FieldPartitioner") if !config_nonsensitive.contains_key(PARTITION_FIELD_NAME) => Err(
+            format!("FieldPartitioner requires '{}'", PARTITION_FIELD_NAME),
+        ),
+        Some(class @ ("DefaultPartitioner" | "FieldPartitioner")) => {
+            match [PATH_FORMAT, TIMEZONE, LOCALE]
+                .into_iter()
+                .find(|key| config_nonsensitive.contains_key(*key))
+            {
+                Some(key) => Err(format!(
+                    "'{}' only applies to TimeBasedPartitioner, not {}",
+                    key, class
+                )),
+                None => Ok(()),
+            }
+        }
+        Some("TimeBasedPartitioner") => {
+            match [PATH_FORMAT, TIMEZONE]
+                .into_iter()
+                .find(|key| !config_nonsensitive.contains_key(*key))
+            {
+                Some(key) => Err(format!("TimeBasedPartitioner requires '{}'", key)),
+                None => Ok(()),
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The pattern's characters outside quoted literals
+fn unquoted(pattern: &str) -> impl Iterator<Item = char> + '_ {
+    let mut quoted = false;
+    pattern.chars().filter(move |c| {
+        if *c == '\'' {
+            quoted = !quoted;
+            false
+        } else {
+            !quoted
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_check_path_format() {
+        for pattern in [
+            "'effective_date'=YYYY-MM-dd",
+            "'year'=YYYY/'month'=MM/'day'=dd/'hour'=HH",
+            "YYYY/MM/dd",
+            &build_path_format(true),
+        ] {
+            assert!(check_path_format(pattern).is_ok(), "{}", pattern);
+        }
+
+        let error = check_path_format("year=YYYY/month=MM").unwrap_err();
+        assert!(error.contains("unquoted text 'year'"));
+        assert!(check_path_format("'year=YYYY")
+            .unwrap_err()
+            .contains("isn't closed"));
+        assert!(check_path_format("/YYYY/MM").is_err());
+        assert!(check_path_format("'month'=MM")
+            .unwrap_err()
+            .contains("no year"));
+        assert!(check_path_format("").is_err());
+    }
+
+    #[test]
+    fn test_time_interval() {
+        assert_eq!(time_interval(&build_path_format(true)), "HOURLY");
+        assert_eq!(time_interval(&build_path_format(false)), "DAILY");
+        // An 'h' inside a quoted literal isn't an hour field
+        assert_eq!(time_interval("'hour_bucket'=YYYY-MM-dd"), "DAILY");
+    }
+
+    #[test]
+    fn test_check_locale_and_timezone() {
+        assert!(check_locale("en-US").is_ok());
+        assert!(check_locale("en_US").is_ok());
+        assert!(check_locale("en").is_ok());
+        assert!(check_locale("english").is_err());
+        assert!(check_timezone("UTC").is_ok());
+        assert!(check_timezone("America/New_York").is_ok());
+        assert!(check_timezone("America/Argentina/Buenos_Aires").is_ok());
+        assert!(check_timezone("Eastern").is_err());
+        assert!(check_timezone("utc").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        assert!(check(&config(&[
+            (PARTITIONER_CLASS, "TimeBasedPartitioner"),
+            (PATH_FORMAT, "'year'=YYYY"),
+            (TIMEZONE, "UTC"),
+        ]))
+        .is_ok());
+        assert!(check(&config(&[
+            (PARTITIONER_CLASS, "TimeBasedPartitioner"),
+            (PATH_FORMAT, "'year'=YYYY"),
+        ]))
+        .unwrap_err()
+        .contains("requires 'timezone'"));
+        assert!(check(&config(&[(PARTITIONER_CLASS, "FieldPartitioner")]))
+            .unwrap_err()
+            .contains("partition.field.name"));
+        assert!(check(&config(&[
+            (PARTITIONER_CLASS, "DefaultPartitioner"),
+            (PATH_FORMAT, "YYYY"),
+        ]))
+        .unwrap_err()
+        .contains("only applies to TimeBasedPartitioner"));
+        assert!(check(&config(&[(TIMEZONE, "Eastern")])).is_err());
+        // Without a partitioner, the existing default layout stays valid
+        assert!(check(&config(&[(PATH_FORMAT, "'effective_date'=YYYY-MM-dd")])).is_ok());
+        assert!(check(&config(&[(PATH_FORMAT, "${var.path_format}")])).is_ok());
+    }
+}
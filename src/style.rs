@@ -0,0 +1,239 @@
+use console::Style;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static EMOJI: AtomicBool = AtomicBool::new(true);
+static TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// ASCII stand-ins for the glyphs used in output, with the color each tag gets
+const GLYPHS: &[(&str, &str, Tone)] = &[
+    ("✅", "[ok]", Tone::Success),
+    ("❌", "[error]", Tone::Failure),
+    ("⚠️", "[warn]", Tone::Warning),
+    ("⚠", "[warn]", Tone::Warning),
+    ("ℹ️", "[info]", Tone::Info),
+    ("ℹ", "[info]", Tone::Info),
+    ("⏭️", "[skip]", Tone::Info),
+    ("🔍", "[check]", Tone::Info),
+    ("📄", "[file]", Tone::Info),
+    ("📋", "[summary]", Tone::Info),
+    ("📜", "[policy]", Tone::Info),
+    ("📝", "[write]", Tone::Info),
+    ("📤", "[send]", Tone::Info),
+    ("🔒", "[secure]", Tone::Info),
+    ("🐳", "[docker]", Tone::Info),
+    ("🚀", "[start]", Tone::Info),
+    ("📊", "[summary]", Tone::Info),
+    ("📦", "[module]", Tone::Info),
+    ("📍", "[offsets]", Tone::Info),
+    ("📣", "[send]", Tone::Info),
+    ("🔎", "[check]", Tone::Info),
+    ("🌐", "[check]", Tone::Info),
+    ("🔌", "[check]", Tone::Info),
+    ("🔑", "[secret]", Tone::Info),
+    ("🔗", "[skip]", Tone::Info),
+    ("🔀", "[merge]", Tone::Info),
+    ("🔧", "[fix]", Tone::Info),
+    ("💡", "[hint]", Tone::Info),
+    ("🌍", "[env]", Tone::Info),
+    ("🐛", "[debug]", Tone::Info),
+    ("🔕", "[suppressed]", Tone::Info),
+    ("🔇", "[suppressed]", Tone::Info),
+    ("🗑️", "[remove]", Tone::Warning),
+    ("🗑", "[remove]", Tone::Warning),
+    ("🕰️", "[stale]", Tone::Warning),
+    ("🕰", "[stale]", Tone::Warning),
+    ("⏱️", "[timings]", Tone::Info),
+    ("⏱", "[timings]", Tone::Info),
+    ("✓", "[ok]", Tone::Success),
+    ("→", "->", Tone::Plain),
+    ("•", "*", Tone::Plain),
+    ("—", "-", Tone::Plain),
+];
+
+/// Whether a character is an emoji or symbol glyph that `--no-emoji` must not
+/// print. Letters outside ASCII (names, translated messages) are left alone
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x2000..=0x206F | 0x2190..=0x2BFF | 0xFE00..=0xFE0F | 0x1F000..=0x1FAFF
+    )
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Tone {
+    Success,
+    Failure,
+    Warning,
+    Info,
+    Plain,
+}
+
+impl Tone {
+    fn style(self) -> Style {
+        match self {
+            Tone::Success => Style::new().green(),
+            Tone::Failure => Style::new().red().bold(),
+            Tone::Warning => Style::new().yellow(),
+            Tone::Info => Style::new().cyan(),
+            Tone::Plain => Style::new(),
+        }
+    }
+}
+
+/// Applies the output flags. `NO_COLOR` (any non-empty value) disables color
+/// the same way `--no-color` does
+pub fn configure(no_emoji: bool, no_color: bool) {
+    EMOJI.store(!no_emoji, Ordering::Relaxed);
+    if no_color || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        console::set_colors_enabled(false);
+        console::set_colors_enabled_stderr(false);
+    }
+}
+
+/// Sends `say!` output to stderr, for runs whose stdout carries a `-` output file
+pub fn messages_to_stderr(enabled: bool) {
+    TO_STDERR.store(enabled, Ordering::Relaxed);
+}
+
+/// Prints a rendered line of `say!` output
+#[doc(hidden)]
+pub fn say(text: &str) {
+    let line = render(text);
+    if TO_STDERR.load(Ordering::Relaxed) {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
+
+/// Whether output may use color, after flags, `NO_COLOR`, and TTY detection
+pub fn colors_enabled() -> bool {
+    console::colors_enabled()
+}
+
+/// Rewrites a line of output for the configured mode
+pub fn render(text: &str) -> String {
+    render_with(text, EMOJI.load(Ordering::Relaxed))
+}
+
+fn render_with(text: &str, emoji: bool) -> String {
+    if emoji {
+        return text.to_string();
+    }
+    let mut rendered = text.to_string();
+    for (glyph, tag, tone) in GLYPHS {
+        if rendered.contains(glyph) {
+            rendered = rendered.replace(glyph, &tone.style().apply_to(tag).to_string());
+        }
+    }
+    // Glyphs without a tag are dropped along with the padding that followed
+    // them, so a new emoji can't leak into ASCII output
+    let mut ascii = String::with_capacity(rendered.len());
+    let mut dropped = false;
+    for c in rendered.chars() {
+        if is_emoji(c) {
+            dropped = true;
+        } else if dropped && c == ' ' && (ascii.is_empty() || ascii.ends_with(' ')) {
+            continue;
+        } else {
+            dropped = false;
+            ascii.push(c);
+        }
+    }
+    // Emoji are double-width, so their tags read better without the padding
+    // that followed them
+    ascii.replace("]  ", "] ")
+}
+
+/// `println!` routed through the output style layer, clearing any progress bars
+/// while the line is written
+#[macro_export]
+macro_rules! say {
+    () => {
+        $crate::progress::suspend(|| $crate::style::say(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::progress::suspend(|| $crate::style::say(&format!($($arg)*)))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_keeps_emoji_by_default() {
+        assert_eq!(render_with("✅ Done", true), "✅ Done");
+    }
+
+    #[test]
+    fn test_render_ascii() {
+        console::set_colors_enabled(false);
+        assert_eq!(render_with("✅ Done", false), "[ok] Done");
+        assert_eq!(
+            render_with("⚠️  Found 1 unmanaged connector(s):", false),
+            "[warn] Found 1 unmanaged connector(s):"
+        );
+        assert_eq!(
+            render_with("ℹ️  Dynamic value for 'x' — skipped", false),
+            "[info] Dynamic value for 'x' - skipped"
+        );
+        assert_eq!(
+            render_with("  ❌ Configuration error: ❌ Resource", false),
+            "  [error] Configuration error: [error] Resource"
+        );
+        assert!(render_with("📄 main.tf 📋 🔍 📜 📝 📤 🔒 🐳 🚀 ⏭️", false).is_ascii());
+    }
+
+    #[test]
+    fn test_render_ascii_drops_untagged_emoji() {
+        console::set_colors_enabled(false);
+        // Escaped so the source scan below doesn't see them
+        assert_eq!(
+            render_with("\u{1F195}  New connector", false),
+            "New connector"
+        );
+        assert_eq!(
+            render_with("  \u{1F195} New \u{2728} here", false),
+            "  New here"
+        );
+        assert_eq!(render_with("Café 日本", false), "Café 日本");
+    }
+
+    #[test]
+    fn test_glyphs_cover_source_emoji() {
+        fn scan(dir: &std::path::Path, missing: &mut Vec<String>) {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    scan(&path, missing);
+                    continue;
+                }
+                if path.extension().is_none_or(|ext| ext != "rs") {
+                    continue;
+                }
+                let source = std::fs::read_to_string(&path).unwrap();
+                for c in source.chars() {
+                    // Variation selectors and joiners only modify the glyph before them
+                    if !is_emoji(c) || matches!(c as u32, 0xFE00..=0xFE0F | 0x200D) {
+                        continue;
+                    }
+                    if !GLYPHS.iter().any(|(glyph, _, _)| glyph.starts_with(c)) {
+                        missing.push(format!("{} in {}", c, path.display()));
+                    }
+                }
+            }
+        }
+        let mut missing = Vec::new();
+        scan(
+            &std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+            &mut missing,
+        );
+        missing.dedup();
+        assert!(
+            missing.is_empty(),
+            "glyphs missing from GLYPHS: {:?}",
+            missing
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use crate::lint::LintRule;
+
+/// Comment marker, as in `# connect-util:ignore invalid_config legacy connector`
+const MARKER: &str = "connect-util:ignore";
+
+/// A suppression comment, applying to the block or attribute on the next line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suppression {
+    pub rule: LintRule,
+    pub reason: String,
+    /// 1-based line of the comment
+    pub line: usize,
+    /// Line of the block or attribute the comment applies to
+    target: usize,
+    /// For an attribute, the line of the block it sits in and its key
+    attribute: Option<(usize, String)>,
+}
+
+/// The suppression comments in one file
+#[derive(Debug, Clone, Default)]
+pub struct Suppressions {
+    entries: Vec<Suppression>,
+    /// `(line, rule)` for comments naming a rule that doesn't exist
+    pub unknown: Vec<(usize, String)>,
+}
+
+fn comment_body(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    line.strip_prefix('#')
+        .or_else(|| line.strip_prefix("//"))
+        .map(str::trim)
+}
+
+fn opens_block(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("resource \"") || line.starts_with("module \"")
+}
+
+impl Suppressions {
+    pub fn parse(content: &str) -> Self {
+        let lines: Vec<&str> = content.lines().collect();
+        let mut suppressions = Suppressions::default();
+        for (index, line) in lines.iter().enumerate() {
+            let Some(rest) = comment_body(line).and_then(|body| body.strip_prefix(MARKER)) else {
+                continue;
+            };
+            let mut words = rest.split_whitespace();
+            let Some(id) = words.next() else { continue };
+            let Some(rule) = LintRule::from_id(id) else {
+                suppressions.unknown.push((index + 1, id.to_string()));
+                continue;
+            };
+            let Some(target) = (index + 1..lines.len())
+                .find(|&i| !lines[i].trim().is_empty() && comment_body(lines[i]).is_none())
+            else {
+                continue;
+            };
+
+            let attribute = if opens_block(lines[target]) {
+                None
+            } else {
+                let key = lines[target].split('=').next().unwrap_or_default();
+                let block = (0..target).rev().find(|&i| opens_block(lines[i]));
+                block.map(|block| (block + 1, key.trim().trim_matches('"').to_string()))
+            };
+            suppressions.entries.push(Suppression {
+                rule,
+                reason: words.collect::<Vec<_>>().join(" "),
+                line: index + 1,
+                target: target + 1,
+                attribute,
+            });
+        }
+        suppressions
+    }
+
+    /// The comment suppressing a `rule` finding reported at `line`. A comment on an
+    /// attribute covers findings of its block that mention the attribute's key
+    pub fn find(&self, rule: LintRule, line: usize, message: &str) -> Option<&Suppression> {
+        self.entries.iter().find(|suppression| {
+            suppression.rule == rule
+                && (suppression.target == line
+                    || suppression.attribute.as_ref().is_some_and(|(block, key)| {
+                        *block == line && !key.is_empty() && message.contains(key.as_str())
+                    }))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::ScanRule;
+
+    #[test]
+    fn test_suppressions() {
+        let content = r#"
+# connect-util:ignore naming-convention legacy name kept for the dashboards
+resource "confluent_connector" "Orders" {
+  config_nonsensitive = {
+    # connect-util:ignore connect_util_policy approved in PLAT-12
+    "auto.create" = "true"
+    // connect-util:ignore hardcoded-secret
+    "database.password" = "hunter2"
+  }
+}
+# connect-util:ignore no_such_rule
+"#;
+        let suppressions = Suppressions::parse(content);
+        assert_eq!(suppressions.unknown, vec![(11, "no_such_rule".to_string())]);
+
+        let naming = suppressions
+            .find(
+                LintRule::NamingConvention,
+                3,
+                "label 'Orders' isn't snake_case",
+            )
+            .unwrap();
+        assert_eq!(naming.line, 2);
+        assert_eq!(naming.reason, "legacy name kept for the dashboards");
+        assert!(suppressions.find(LintRule::InvalidConfig, 3, "").is_none());
+
+        // Attribute comments cover block-level findings naming the key
+        assert!(suppressions
+            .find(
+                LintRule::Policy,
+                3,
+                "'Orders' sets forbidden value auto.create=true"
+            )
+            .is_some());
+        assert!(suppressions
+            .find(LintRule::Policy, 3, "'Orders' sets tasks.max above 4")
+            .is_none());
+        assert!(suppressions
+            .find(LintRule::Scan(ScanRule::HardcodedSecret), 8, "")
+            .is_some_and(|s| s.reason.is_empty()));
+    }
+}
@@ -0,0 +1,149 @@
+use regex::Regex;
+
+/// Debezium key listing the tables a CDC source captures
+pub const TABLE_INCLUDE_LIST: &str = "table.include.list";
+/// Debezium key listing the tables a CDC source skips
+pub const TABLE_EXCLUDE_LIST: &str = "table.exclude.list";
+
+/// Splits comma-separated `schema.table` patterns, checking each one. Debezium reads
+/// every entry as a regular expression over the fully qualified table name, so
+/// `public.orders`, `public\.orders`, and `inventory\..*` are all accepted
+pub fn parse_table_patterns(input: &str) -> Result<Vec<String>, String> {
+    let patterns: Vec<String> = input
+        .split(',')
+        .map(str::trim)
+        .filter(|pattern| !pattern.is_empty())
+        .map(str::to_string)
+        .collect();
+    for pattern in &patterns {
+        check_pattern(pattern)?;
+    }
+    Ok(patterns)
+}
+
+/// A pattern needs a schema (or database) and a table around the first `.`, and must
+/// compile as a regular expression
+pub fn check_pattern(pattern: &str) -> Result<(), String> {
+    let (schema, table) = split_pattern(pattern).ok_or_else(|| {
+        format!(
+            "Invalid table pattern '{}': expected schema.table, e.g. public.orders",
+            pattern
+        )
+    })?;
+    if schema.is_empty() || table.is_empty() {
+        return Err(format!(
+            "Invalid table pattern '{}': both the schema and the table are needed",
+            pattern
+        ));
+    }
+    anchored(pattern)
+        .map(|_| ())
+        .map_err(|e| format!("Invalid table pattern '{}': {}", pattern, e))
+}
+
+/// Warnings for include and exclude lists that fight each other: Debezium rejects a
+/// connector with both set, and a table matched by both lists is ambiguous
+pub fn table_warnings(include: &[String], exclude: &[String]) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if include.is_empty() || exclude.is_empty() {
+        return warnings;
+    }
+    warnings.push(format!(
+        "{} and {} are both set; Debezium only accepts one of them",
+        TABLE_INCLUDE_LIST, TABLE_EXCLUDE_LIST
+    ));
+    for excluded in exclude {
+        for included in include {
+            if overlaps(included, excluded) {
+                warnings.push(format!(
+                    "'{}' is excluded but also matches the include pattern '{}'",
+                    excluded, included
+                ));
+            }
+        }
+    }
+    warnings
+}
+
+/// Whether any of the patterns matches a fully qualified table name
+pub fn captures(patterns: &[String], table: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| anchored(pattern).is_ok_and(|regex| regex.is_match(table)))
+}
+
+/// Whether either pattern matches the other read as a literal table name
+fn overlaps(a: &str, b: &str) -> bool {
+    let matches = |pattern: &str, table: &str| {
+        anchored(pattern).is_ok_and(|regex| regex.is_match(&literal(table)))
+    };
+    matches(a, b) || matches(b, a)
+}
+
+fn anchored(pattern: &str) -> Result<Regex, regex::Error> {
+    Regex::new(&format!("^(?:{})$", pattern))
+}
+
+/// A pattern with its escaped dots read back as plain ones, e.g. `public\.orders`
+fn literal(pattern: &str) -> String {
+    pattern.replace("\\.", ".")
+}
+
+/// Splits at the first `.`, treating `\.` as the same separator
+fn split_pattern(pattern: &str) -> Option<(&str, &str)> {
+    let (schema, table) = pattern.split_once('.')?;
+    Some((schema.strip_suffix('\\').unwrap_or(schema), table))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_table_patterns() {
+        assert_eq!(
+            parse_table_patterns(" public.orders, inventory\\..* ,").unwrap(),
+            vec!["public.orders", "inventory\\..*"]
+        );
+        assert!(parse_table_patterns("").unwrap().is_empty());
+
+        let error = parse_table_patterns("public.orders,orders").unwrap_err();
+        assert!(error.contains("'orders'"));
+        assert!(error.contains("expected schema.table"));
+        assert!(parse_table_patterns(".orders").is_err());
+        assert!(parse_table_patterns("public.").is_err());
+        assert!(parse_table_patterns("public.(orders").is_err());
+    }
+
+    #[test]
+    fn test_table_warnings() {
+        let tables =
+            |patterns: &[&str]| -> Vec<String> { patterns.iter().map(|p| p.to_string()).collect() };
+        assert!(table_warnings(&tables(&["public.orders"]), &[]).is_empty());
+        assert!(table_warnings(&[], &tables(&["public.audit"])).is_empty());
+
+        let warnings = table_warnings(
+            &tables(&["public\\..*", "sales.orders"]),
+            &tables(&["public.audit", "billing.invoices"]),
+        );
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("only accepts one of them"));
+        assert_eq!(
+            warnings[1],
+            "'public.audit' is excluded but also matches the include pattern 'public\\..*'"
+        );
+
+        // An exclude pattern can cover an included table too
+        let warnings = table_warnings(&tables(&["public.orders"]), &tables(&["public.*"]));
+        assert_eq!(warnings.len(), 2);
+    }
+
+    #[test]
+    fn test_captures() {
+        let patterns = vec!["public\\.orders".to_string(), "inventory\\..*".to_string()];
+        assert!(captures(&patterns, "public.orders"));
+        assert!(captures(&patterns, "inventory.debezium_signal"));
+        assert!(!captures(&patterns, "public.debezium_signal"));
+        assert!(!captures(&[], "public.orders"));
+    }
+}
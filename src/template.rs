@@ -0,0 +1,72 @@
+use std::collections::BTreeMap;
+
+/// Tokens a template references, in order of first use. `{{ team }}` and
+/// `{{team}}` name the same token
+pub fn tokens(template: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let token = rest[start + 2..start + 2 + end].trim().to_string();
+        if !tokens.contains(&token) {
+            tokens.push(token);
+        }
+        rest = &rest[start + 2 + end + 2..];
+    }
+    tokens
+}
+
+/// Replaces every `{{token}}` with its value; `Err` lists the tokens without one
+pub fn expand(template: &str, values: &BTreeMap<String, String>) -> Result<String, Vec<String>> {
+    let missing: Vec<String> = tokens(template)
+        .into_iter()
+        .filter(|token| !values.contains_key(token))
+        .collect();
+    if !missing.is_empty() {
+        return Err(missing);
+    }
+
+    let mut expanded = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&values[rest[start + 2..start + 2 + end].trim()]);
+        rest = &rest[start + 2 + end + 2..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand() {
+        let values = BTreeMap::from([
+            ("team".to_string(), "payments".to_string()),
+            ("connector".to_string(), "orders".to_string()),
+        ]);
+        assert_eq!(
+            tokens("{{team}}-{{ env }}-{{connector}}-{{team}}"),
+            vec!["team", "env", "connector"]
+        );
+        assert_eq!(
+            expand("{{team}}-{{ connector }}", &values),
+            Ok("payments-orders".to_string())
+        );
+        assert_eq!(
+            expand("{{team}}-{{env}}-{{domain}}", &values),
+            Err(vec!["env".to_string(), "domain".to_string()])
+        );
+        assert_eq!(
+            expand("plain {{unclosed", &values),
+            Ok("plain {{unclosed".to_string())
+        );
+    }
+}
@@ -1,6 +1,22 @@
+use crate::connectors::infer_config_group;
+use crate::convert::SelfManagedConnector;
+use crate::debezium::{self, Dialect};
 use crate::error::ConnectUtilError;
-use crate::types::{ConnectorDefinition, ConnectorType, DataFormat, TerraformConfigOptions};
-use hcl::{Block, Body, Expression, Identifier, Object, ObjectKey, Traversal, Variable};
+use crate::lifecycle::DEFAULT_IGNORE_CHANGES;
+use crate::parser::parse_body;
+use crate::schema_registry;
+use crate::storage::{self, Partitioner};
+use crate::tables;
+use crate::types::{
+    CompanionResources, ConfigGroup, ConnectorDefinition, ConnectorFamily, ConnectorType,
+    DataFormat, DebeziumOptions, Environment, SecretsBackend, StorageLayoutOptions,
+    TerraformConfigOptions, TerraformProvider, TopicStrategy,
+};
+use crate::variables::MissingVariable;
+use hcl::{
+    Block, Body, Expression, Identifier, Object, ObjectKey, TemplateExpr, Traversal, Variable,
+};
+use std::collections::{BTreeMap, HashSet};
 
 /// Terraform generator for creating connector configurations
 pub struct TerraformGenerator;
@@ -11,24 +27,15 @@ impl TerraformGenerator {
         &self,
         options: TerraformConfigOptions,
     ) -> Result<String, ConnectUtilError> {
-        let resource_name = options.connector_name.replace('-', "_");
+        let _span = tracing::debug_span!("generate").entered();
+        let resource_name = Self::resource_name(&options.connector_name);
+        Self::check_config_keys(&options)?;
 
         // Build config_sensitive map as Expression::Object
         let mut config_sensitive_obj = Object::new();
         for sensitive_config in &options.connector.sensitive_configs {
-            // Use Expression::String for keys with dots or special characters
-            let key = if sensitive_config.contains('.') {
-                ObjectKey::Expression(Expression::String(sensitive_config.clone()))
-            } else {
-                ObjectKey::Identifier(Identifier::new(sensitive_config.clone()).map_err(|e| {
-                    ConnectUtilError::Terraform(format!(
-                        "Invalid identifier '{}': {}",
-                        sensitive_config, e
-                    ))
-                })?)
-            };
             config_sensitive_obj.insert(
-                key,
+                Self::make_object_key(sensitive_config),
                 Expression::String("<REPLACE_WITH_ACTUAL_VALUE>".to_string()),
             );
         }
@@ -43,59 +50,104 @@ impl TerraformGenerator {
             Self::make_object_key("name"),
             Expression::String(options.connector_name.clone()),
         );
-        config_nonsensitive_obj.insert(
-            Self::make_object_key("kafka.auth.mode"),
-            Expression::String("SERVICE_ACCOUNT".to_string()),
-        );
-        config_nonsensitive_obj.insert(
-            Self::make_object_key("kafka.deployment.type"),
-            Expression::String("DEDICATED".to_string()),
-        );
-
-        // Add topics configuration - handle connector-specific patterns
-        if options.topics.is_empty() {
-            if options.connector.connector_type == ConnectorType::Sink {
-                config_nonsensitive_obj.insert(
-                    Self::make_object_key("topics"),
-                    Expression::String("<REPLACE_WITH_TOPIC_NAME>".to_string()),
-                );
+        if options.companions.api_key {
+            // The generated API key wires the dependency on itself and its service account
+            config_nonsensitive_obj.insert(
+                Self::make_object_key("kafka.auth.mode"),
+                Expression::String("KAFKA_API_KEY".to_string()),
+            );
+            config_sensitive_obj.insert(
+                Self::make_object_key("kafka.api.key"),
+                Self::traversal("confluent_api_key", &[&resource_name, "id"])?,
+            );
+            config_sensitive_obj.insert(
+                Self::make_object_key("kafka.api.secret"),
+                Self::traversal("confluent_api_key", &[&resource_name, "secret"])?,
+            );
+        } else {
+            config_nonsensitive_obj.insert(
+                Self::make_object_key("kafka.auth.mode"),
+                Expression::String("SERVICE_ACCOUNT".to_string()),
+            );
+            let service_account = if options.companions.service_account {
+                Self::traversal("confluent_service_account", &[&resource_name, "id"])?
             } else {
+                Expression::String("<REPLACE_WITH_SERVICE_ACCOUNT_ID>".to_string())
+            };
+            config_nonsensitive_obj.insert(
+                Self::make_object_key("kafka.service.account.id"),
+                service_account,
+            );
+        }
+        for (key, value) in options.cluster_type.unwrap_or_default().config_values() {
+            config_nonsensitive_obj.insert(
+                Self::make_object_key(key),
+                Expression::String(value.to_string()),
+            );
+        }
+
+        // Topics go under the key the connector's topic strategy names
+        let strategy = options.connector.topic_strategy;
+        let single = |placeholder: &str| -> Result<Expression, ConnectUtilError> {
+            match options.topics.as_slice() {
+                [] => Ok(Expression::String(placeholder.to_string())),
+                [topic] => Ok(Expression::String(topic.clone())),
+                _ => Err(ConnectUtilError::Terraform(format!(
+                    "{} takes a single {}, not a list of topics",
+                    options.connector.name,
+                    strategy.key()
+                ))),
+            }
+        };
+        let topics = match strategy {
+            TopicStrategy::TopicPrefix => match &options.topic_prefix {
+                Some(prefix) => Expression::String(prefix.clone()),
+                None => single("<REPLACE_WITH_TOPIC_PREFIX>")?,
+            },
+            TopicStrategy::KafkaTopicSingle => single("<REPLACE_WITH_TOPIC_NAME>")?,
+            TopicStrategy::TopicsList if options.topics.is_empty() => {
+                Expression::String("<REPLACE_WITH_TOPIC_NAME>".to_string())
+            }
+            TopicStrategy::TopicsList => {
+                // join() flattens the list into the comma-separated string Connect expects
+                let topic_values: Vec<Expression> = options
+                    .topics
+                    .iter()
+                    .map(|t| Expression::String(t.clone()))
+                    .collect();
+                Expression::FuncCall(Box::new(hcl::FuncCall {
+                    name: Identifier::new("join").map_err(|e| {
+                        ConnectUtilError::Terraform(format!("Invalid function name 'join': {}", e))
+                    })?,
+                    args: vec![
+                        Expression::String(",".to_string()),
+                        Expression::Array(topic_values),
+                    ],
+                    expand_final: false,
+                }))
+            }
+        };
+        config_nonsensitive_obj.insert(Self::make_object_key(strategy.key()), topics);
+
+        for (key, patterns) in [
+            (tables::TABLE_INCLUDE_LIST, &options.tables),
+            (tables::TABLE_EXCLUDE_LIST, &options.exclude_tables),
+        ] {
+            if !patterns.is_empty() {
                 config_nonsensitive_obj.insert(
-                    Self::make_object_key("topic.prefix"),
-                    Expression::String("<REPLACE_WITH_TOPIC_PREFIX>".to_string()),
+                    Self::make_object_key(key),
+                    Expression::String(patterns.join(",")),
                 );
             }
-        } else {
-            // Check if this connector uses topic.prefix (like PostgreSQL CDC Source V2)
-            if options
-                .connector
-                .required_configs
-                .iter()
-                .any(|config| config.name == "topic.prefix")
-            {
+        }
+
+        if options.connector.connector_type == ConnectorType::Sink {
+            if let Some(input_format) = &options.input_data_format {
                 config_nonsensitive_obj.insert(
-                    Self::make_object_key("topic.prefix"),
-                    Expression::String("<REPLACE_WITH_TOPIC_PREFIX>".to_string()),
+                    Self::make_object_key("input.data.format"),
+                    Self::data_format_to_expression(input_format),
                 );
             }
-
-            // Add topics field with join() to flatten array as comma-separated string
-            let topic_values: Vec<Expression> = options
-                .topics
-                .iter()
-                .map(|t| Expression::String(t.clone()))
-                .collect();
-            let join_expr = Expression::FuncCall(Box::new(hcl::FuncCall {
-                name: Identifier::new("join").map_err(|e| {
-                    ConnectUtilError::Terraform(format!("Invalid function name 'join': {}", e))
-                })?,
-                args: vec![
-                    Expression::String(",".to_string()),
-                    Expression::Array(topic_values),
-                ],
-                expand_final: false,
-            }));
-            config_nonsensitive_obj.insert(Self::make_object_key("topics"), join_expr);
         }
 
         // Add connector-specific configurations
@@ -104,6 +156,21 @@ impl TerraformGenerator {
             &options.connector,
             &options,
         )?;
+        if options.connector.family == ConnectorFamily::ObjectStorage
+            && options.connector.connector_type == ConnectorType::Sink
+        {
+            Self::add_storage_layout(&mut config_nonsensitive_obj, &options.storage);
+        }
+        // Debezium sources default heartbeats off, so explicit settings go on top
+        let dialect = Dialect::for_connector(&options.connector);
+        if let Some(dialect) = dialect {
+            Self::add_debezium_config(&mut config_nonsensitive_obj, dialect, &options.debezium);
+        }
+        Self::add_required_placeholders(
+            &mut config_nonsensitive_obj,
+            &config_sensitive_obj,
+            &options.connector,
+        );
 
         // Add output data format
         let output_format = options
@@ -119,11 +186,98 @@ impl TerraformGenerator {
             Self::make_object_key("tasks.max"),
             Expression::String("1".to_string()),
         );
+        for (key, value) in &options.schema_values {
+            config_nonsensitive_obj.insert(
+                Self::make_object_key(key),
+                Expression::String(value.clone()),
+            );
+        }
+        Self::add_schema_registry_config(
+            &mut config_nonsensitive_obj,
+            &mut config_sensitive_obj,
+            &options,
+            &resource_name,
+        )?;
+        for (key, value) in &options.template_values {
+            let config = if options.connector.sensitive_configs.contains(key) {
+                &mut config_sensitive_obj
+            } else {
+                &mut config_nonsensitive_obj
+            };
+            config.insert(
+                Self::make_object_key(key),
+                Expression::String(value.clone()),
+            );
+        }
+        if let (Some(environment), true) = (
+            &options.environment,
+            options.for_each_environments.is_empty(),
+        ) {
+            for (key, value) in &environment.overrides {
+                config_nonsensitive_obj.insert(
+                    Self::make_object_key(key),
+                    Expression::String(value.clone()),
+                );
+            }
+        }
+
+        for key in Self::secret_keys(&options) {
+            if let Some(reference) =
+                Self::secret_reference(options.secrets_backend, &options.connector_name, &key)?
+            {
+                config_sensitive_obj.insert(Self::make_object_key(&key), reference);
+            }
+        }
+
+        // Topics aren't referenced by the connector config, so order them explicitly
+        let topic_dependencies: Vec<Expression> = if options.companions.topics {
+            Self::topic_resource_names(&options.topics)
+                .iter()
+                .map(|label| Self::traversal("confluent_kafka_topic", &[label]))
+                .collect::<Result<_, _>>()?
+        } else {
+            vec![]
+        };
+
+        let for_each = !options.for_each_environments.is_empty();
+        if for_each && options.companions != CompanionResources::default() {
+            return Err(ConnectUtilError::Terraform(
+                "Companion resources can't be generated for a for_each over environments"
+                    .to_string(),
+            ));
+        }
+        let environments_local = format!("{}_environments", resource_name);
+        let config_sensitive_obj = Self::sorted_config(config_sensitive_obj);
+        let config_nonsensitive_obj = Self::sorted_config(config_nonsensitive_obj);
+        let config_nonsensitive = if for_each {
+            // Per-environment overrides win over the shared config
+            Expression::FuncCall(Box::new(hcl::FuncCall {
+                name: Identifier::new("merge").map_err(|e| {
+                    ConnectUtilError::Terraform(format!("Invalid function name 'merge': {}", e))
+                })?,
+                args: vec![
+                    Expression::Object(config_nonsensitive_obj),
+                    Self::traversal("each", &["value", "config"])?,
+                ],
+                expand_final: false,
+            }))
+        } else {
+            Expression::Object(config_nonsensitive_obj)
+        };
 
         // Build the resource block
-        let resource_block = Block::builder("resource")
+        let resource_builder = Block::builder("resource")
             .add_label("confluent_connector")
-            .add_label(resource_name)
+            .add_label(resource_name.as_str());
+        let resource_builder = if for_each {
+            resource_builder.add_attribute((
+                "for_each",
+                Self::traversal("local", &[&environments_local])?,
+            ))
+        } else {
+            resource_builder
+        };
+        let resource_builder = resource_builder
             .add_attribute((
                 "status",
                 Traversal::builder(Variable::new("var").map_err(|e| {
@@ -134,74 +288,962 @@ impl TerraformGenerator {
             ))
             .add_block(
                 Block::builder("environment")
-                    .add_attribute((
-                        "id",
-                        Traversal::builder(Variable::new("var").map_err(|e| {
-                            ConnectUtilError::Terraform(format!(
-                                "Invalid variable name 'var': {}",
-                                e
-                            ))
-                        })?)
-                        .attr("environment_id")
-                        .build(),
-                    ))
+                    .add_attribute(("id", Self::environment_id(&options)?))
+                    .build(),
+            )
+            .add_block(
+                Block::builder("kafka_cluster")
+                    .add_attribute(("id", Self::kafka_cluster_attr(&options, "id")?))
+                    .build(),
+            )
+            .add_attribute(("config_sensitive", Expression::Object(config_sensitive_obj)))
+            .add_attribute(("config_nonsensitive", config_nonsensitive));
+        let resource_builder = if topic_dependencies.is_empty() {
+            resource_builder
+        } else {
+            resource_builder.add_attribute(("depends_on", Expression::Array(topic_dependencies)))
+        };
+        let lifecycle = match &options.ignore_changes {
+            Some(entries) => Self::lifecycle_block(entries),
+            None => Self::lifecycle_block(DEFAULT_IGNORE_CHANGES),
+        };
+        let resource_block = resource_builder.add_block(lifecycle).build();
+
+        // Build the main body with companion resources ahead of the connector
+        let body = Body::builder();
+        let body = if for_each {
+            body.add_block(Self::environments_locals(
+                &environments_local,
+                &options.for_each_environments,
+            )?)
+        } else {
+            body
+        };
+        let body = body
+            .add_blocks(Self::companion_blocks(&options, &resource_name)?)
+            .add_block(resource_block);
+        let body = match &options.import_name {
+            Some(connector_name) => body.add_block(Self::import_block(
+                &options,
+                &resource_name,
+                connector_name,
+            )?),
+            None => body,
+        };
+        let body = body.build();
+
+        // Serialize to HCL string
+        let hcl_string = hcl::to_string(&body)
+            .map_err(|e| ConnectUtilError::Terraform(format!("Failed to serialize HCL: {}", e)))?;
+
+        // The database-side setup for the signal and heartbeat tables goes above
+        // the connector resource
+        let setup = dialect
+            .map(|dialect| debezium::setup_comments(dialect, &options.debezium))
+            .unwrap_or_default();
+        let connector_resource = format!("resource \"confluent_connector\" \"{}\"", resource_name);
+        Ok(match hcl_string.find(&connector_resource) {
+            Some(index) if !setup.is_empty() => {
+                format!("{}{}{}", &hcl_string[..index], setup, &hcl_string[index..])
+            }
+            _ => hcl_string,
+        })
+    }
+
+    /// Orders config keys so regenerating a connector gives minimal diffs: the
+    /// plumbing keys every connector has first, then connection, authentication,
+    /// behavior, and advanced settings, alphabetical within each group
+    pub(crate) fn sorted_config(
+        config: Object<ObjectKey, Expression>,
+    ) -> Object<ObjectKey, Expression> {
+        const PLUMBING: &[&str] = &[
+            "connector.class",
+            "name",
+            "kafka.auth.mode",
+            "kafka.service.account.id",
+            "kafka.api.key",
+            "kafka.api.secret",
+            "kafka.deployment.type",
+            "tasks.max",
+        ];
+        let key_name = |key: &ObjectKey| match key {
+            ObjectKey::Identifier(identifier) => identifier.as_str().to_string(),
+            ObjectKey::Expression(Expression::String(name)) => name.clone(),
+            other => other.to_string(),
+        };
+        let mut entries: Vec<(ObjectKey, Expression)> = config.into_iter().collect();
+        entries.sort_by_cached_key(|(key, _)| {
+            let name = key_name(key);
+            let rank = match PLUMBING.iter().position(|plumbing| *plumbing == name) {
+                Some(position) => (0, position),
+                None => {
+                    let group = infer_config_group(&name);
+                    let position = ConfigGroup::ALL.iter().position(|g| *g == group);
+                    (1, position.unwrap_or(ConfigGroup::ALL.len()))
+                }
+            };
+            (rank, name)
+        });
+        entries.into_iter().collect()
+    }
+
+    /// Adds `schema.context.name` and CSFLE settings, with the Schema Registry
+    /// credentials the connector's auth mode needs
+    fn add_schema_registry_config(
+        config_nonsensitive: &mut Object<ObjectKey, Expression>,
+        config_sensitive: &mut Object<ObjectKey, Expression>,
+        options: &TerraformConfigOptions,
+        resource_name: &str,
+    ) -> Result<(), ConnectUtilError> {
+        let schema_registry = &options.schema_registry;
+        if let Some(context) = &schema_registry.context {
+            config_nonsensitive.insert(
+                Self::make_object_key(schema_registry::SCHEMA_CONTEXT),
+                Expression::String(context.clone()),
+            );
+        }
+        if !schema_registry.csfle {
+            return Ok(());
+        }
+        config_nonsensitive.insert(
+            Self::make_object_key(schema_registry::CSFLE_ENABLED),
+            Expression::String("true".to_string()),
+        );
+        config_nonsensitive.insert(
+            Self::make_object_key(schema_registry::CSFLE_ON_FAILURE),
+            Expression::String("ERROR".to_string()),
+        );
+        if options.companions.api_key {
+            for key in [schema_registry::SR_API_KEY, schema_registry::SR_API_SECRET] {
+                config_sensitive.insert(
+                    Self::make_object_key(key),
+                    Expression::String("<REPLACE_WITH_ACTUAL_VALUE>".to_string()),
+                );
+            }
+        } else {
+            let service_account = if options.companions.service_account {
+                Self::traversal("confluent_service_account", &[resource_name, "id"])?
+            } else {
+                Expression::String("<REPLACE_WITH_SR_SERVICE_ACCOUNT_ID>".to_string())
+            };
+            config_nonsensitive.insert(
+                Self::make_object_key(schema_registry::SR_SERVICE_ACCOUNT),
+                service_account,
+            );
+        }
+        Ok(())
+    }
+
+    /// Lifecycle block ignoring keys Confluent Cloud manages on the connector
+    fn lifecycle_block<S: AsRef<str>>(ignore_changes: &[S]) -> Block {
+        Block::builder("lifecycle")
+            .add_attribute((
+                "ignore_changes",
+                Expression::Array(
+                    ignore_changes
+                        .iter()
+                        .map(|entry| Expression::String(entry.as_ref().to_string()))
+                        .collect(),
+                ),
+            ))
+            .build()
+    }
+
+    /// Generate a connector resource from an explicit, already-resolved configuration,
+    /// e.g. one converted from a self-managed worker's properties file
+    pub fn generate_resource_from_config(
+        &self,
+        connector_name: &str,
+        config_nonsensitive: &BTreeMap<String, String>,
+        config_sensitive: &BTreeMap<String, String>,
+    ) -> Result<String, ConnectUtilError> {
+        let resource_block = Block::builder("resource")
+            .add_label("confluent_connector")
+            .add_label(Self::resource_name(connector_name))
+            .add_attribute(("status", Self::traversal("var", &["status"])?))
+            .add_block(
+                Block::builder("environment")
+                    .add_attribute(("id", Self::traversal("var", &["environment_id"])?))
                     .build(),
             )
             .add_block(
                 Block::builder("kafka_cluster")
+                    .add_attribute(("id", Self::traversal("var", &["kafka_cluster", "id"])?))
+                    .build(),
+            )
+            .add_attribute(("config_sensitive", Self::string_object(config_sensitive)?))
+            .add_attribute((
+                "config_nonsensitive",
+                Self::string_object(config_nonsensitive)?,
+            ))
+            .add_block(Self::lifecycle_block(DEFAULT_IGNORE_CHANGES))
+            .build();
+
+        let body = Body::builder().add_block(resource_block).build();
+        hcl::to_string(&body)
+            .map_err(|e| ConnectUtilError::Terraform(format!("Failed to serialize HCL: {}", e)))
+    }
+
+    /// Generate a connector converted for a self-managed cluster as the given
+    /// provider's resource. For Instaclustr, `with_provider_block` adds the Kafka
+    /// Connect provider configured for the cluster's REST endpoint
+    pub fn generate_provider_resource(
+        &self,
+        provider: TerraformProvider,
+        connector: &SelfManagedConnector,
+        with_provider_block: bool,
+    ) -> Result<String, ConnectUtilError> {
+        let label = Self::resource_name(&connector.name);
+        let mut body = Body::builder();
+        let resource = match provider {
+            TerraformProvider::Confluent => {
+                return Err(ConnectUtilError::Terraform(
+                    "confluent_connector resources are generated from the catalog, not from a self-managed config".to_string(),
+                ))
+            }
+            // Aiven keeps the whole config in one map, marked sensitive by the provider
+            TerraformProvider::Aiven => {
+                let mut config = connector.config.clone();
+                config.extend(connector.config_sensitive.clone());
+                Block::builder("resource")
+                    .add_label("aiven_kafka_connector")
+                    .add_label(label)
+                    .add_attribute(("project", Self::traversal("var", &["aiven_project"])?))
                     .add_attribute((
-                        "id",
-                        Traversal::builder(Variable::new("var").map_err(|e| {
-                            ConnectUtilError::Terraform(format!(
-                                "Invalid variable name 'var': {}",
-                                e
+                        "service_name",
+                        Self::traversal("var", &["aiven_service_name"])?,
+                    ))
+                    .add_attribute(("connector_name", connector.name.as_str()))
+                    .add_attribute(("config", Self::string_object(&config)?))
+                    .build()
+            }
+            TerraformProvider::KafkaConnect | TerraformProvider::Instaclustr => {
+                if provider == TerraformProvider::Instaclustr && with_provider_block {
+                    body = body.add_block(
+                        Block::builder("provider")
+                            .add_label("kafka-connect")
+                            .add_attribute((
+                                "url",
+                                Self::traversal("var", &["instaclustr_connect_url"])?,
                             ))
-                        })?)
-                        .attr("kafka_cluster")
-                        .attr("id")
+                            .add_attribute((
+                                "basic_auth_username",
+                                Self::traversal("var", &["instaclustr_connect_username"])?,
+                            ))
+                            .add_attribute((
+                                "basic_auth_password",
+                                Self::traversal("var", &["instaclustr_connect_password"])?,
+                            ))
+                            .build(),
+                    );
+                }
+                let mut resource = Block::builder("resource")
+                    .add_label("kafka_connect_connector")
+                    .add_label(label)
+                    .add_attribute(("name", connector.name.as_str()))
+                    .add_attribute(("config", Self::string_object(&connector.config)?));
+                if !connector.config_sensitive.is_empty() {
+                    resource = resource.add_attribute((
+                        "config_sensitive",
+                        Self::string_object(&connector.config_sensitive)?,
+                    ));
+                }
+                resource.build()
+            }
+        };
+        hcl::to_string(&body.add_block(resource).build())
+            .map_err(|e| ConnectUtilError::Terraform(format!("Failed to serialize HCL: {}", e)))
+    }
+
+    /// An object of quoted string values, in key order
+    fn string_object(config: &BTreeMap<String, String>) -> Result<Expression, ConnectUtilError> {
+        let mut object = Object::new();
+        for (key, value) in config {
+            Self::check_config_key(key)?;
+            object.insert(
+                Self::make_object_key(key),
+                Expression::String(value.clone()),
+            );
+        }
+        Ok(Expression::Object(object))
+    }
+
+    /// Builds the supporting topic, service account, and API key resources
+    fn companion_blocks(
+        options: &TerraformConfigOptions,
+        resource_name: &str,
+    ) -> Result<Vec<Block>, ConnectUtilError> {
+        let companions = &options.companions;
+        let mut blocks = Vec::new();
+
+        if companions.topics {
+            let labels = Self::topic_resource_names(&options.topics);
+            for (topic, label) in options.topics.iter().zip(labels) {
+                blocks.push(
+                    Block::builder("resource")
+                        .add_label("confluent_kafka_topic")
+                        .add_label(label)
+                        .add_block(
+                            Block::builder("kafka_cluster")
+                                .add_attribute(("id", Self::kafka_cluster_attr(options, "id")?))
+                                .build(),
+                        )
+                        .add_attribute(("topic_name", topic.as_str()))
                         .build(),
+                );
+            }
+        }
+
+        if companions.service_account || companions.api_key {
+            blocks.push(
+                Block::builder("resource")
+                    .add_label("confluent_service_account")
+                    .add_label(resource_name)
+                    .add_attribute((
+                        "display_name",
+                        format!("{}-connector", options.connector_name),
+                    ))
+                    .add_attribute((
+                        "description",
+                        format!(
+                            "Service account for the {} connector",
+                            options.connector_name
+                        ),
                     ))
                     .build(),
-            )
-            .add_attribute(("config_sensitive", Expression::Object(config_sensitive_obj)))
+            );
+        }
+
+        if companions.api_key {
+            let owner_attr =
+                |attr: &str| Self::traversal("confluent_service_account", &[resource_name, attr]);
+            let cluster_attr = |attr: &str| Self::kafka_cluster_attr(options, attr);
+            blocks.push(
+                Block::builder("resource")
+                    .add_label("confluent_api_key")
+                    .add_label(resource_name)
+                    .add_attribute((
+                        "display_name",
+                        format!("{}-connector-key", options.connector_name),
+                    ))
+                    .add_block(
+                        Block::builder("owner")
+                            .add_attribute(("id", owner_attr("id")?))
+                            .add_attribute(("api_version", owner_attr("api_version")?))
+                            .add_attribute(("kind", owner_attr("kind")?))
+                            .build(),
+                    )
+                    .add_block(
+                        Block::builder("managed_resource")
+                            .add_attribute(("id", cluster_attr("id")?))
+                            .add_attribute(("api_version", cluster_attr("api_version")?))
+                            .add_attribute(("kind", cluster_attr("kind")?))
+                            .add_block(
+                                Block::builder("environment")
+                                    .add_attribute(("id", Self::environment_id(options)?))
+                                    .build(),
+                            )
+                            .build(),
+                    )
+                    .build(),
+            );
+        }
+
+        Ok(blocks)
+    }
+
+    /// A `locals` block mapping each environment to its IDs and config overrides
+    fn environments_locals(
+        local_name: &str,
+        environments: &[Environment],
+    ) -> Result<Block, ConnectUtilError> {
+        let mut map = Object::new();
+        for environment in environments {
+            let mut overrides = Object::new();
+            for (key, value) in &environment.overrides {
+                overrides.insert(
+                    Self::make_object_key(key),
+                    Expression::String(value.clone()),
+                );
+            }
+            let mut entry = Object::new();
+            entry.insert(
+                Self::make_object_key("environment_id"),
+                Self::profile_value(&environment.id)?,
+            );
+            entry.insert(
+                Self::make_object_key("kafka_cluster_id"),
+                Self::profile_value(&environment.cluster_id)?,
+            );
+            entry.insert(
+                Self::make_object_key("config"),
+                Expression::Object(Self::sorted_config(overrides)),
+            );
+            map.insert(
+                Self::make_object_key(&environment.name),
+                Expression::Object(entry),
+            );
+        }
+        Ok(Block::builder("locals")
+            .add_attribute((local_name, Expression::Object(map)))
+            .build())
+    }
+
+    /// The provider's import ID for a connector: `<environment>/<cluster>/<connector name>`
+    pub fn connector_import_id(environment_id: &str, cluster_id: &str, name: &str) -> String {
+        format!("{}/{}/{}", environment_id, cluster_id, name)
+    }
+
+    /// An `import` block adopting the live connector, addressed by the
+    /// provider's import ID
+    fn import_block(
+        options: &TerraformConfigOptions,
+        resource_name: &str,
+        connector_name: &str,
+    ) -> Result<Block, ConnectUtilError> {
+        if !options.for_each_environments.is_empty() {
+            return Err(ConnectUtilError::Terraform(
+                "Import blocks need one connector per environment and can't be generated for a for_each connector".to_string(),
+            ));
+        }
+        let (environment_id, cluster_id) = match &options.environment {
+            Some(environment) => (environment.id.as_str(), environment.cluster_id.as_str()),
+            None => ("var.environment_id", "var.kafka_cluster.id"),
+        };
+        let part = |value: &str| match Self::profile_reference(value) {
+            Some(_) => format!("${{{}}}", value),
+            None => value.to_string(),
+        };
+        let id =
+            Self::connector_import_id(&part(environment_id), &part(cluster_id), connector_name);
+        let id = if id.contains("${") {
+            Expression::TemplateExpr(Box::new(TemplateExpr::QuotedString(id)))
+        } else {
+            Expression::String(id)
+        };
+        Ok(Block::builder("import")
             .add_attribute((
-                "config_nonsensitive",
-                Expression::Object(config_nonsensitive_obj),
+                "to",
+                Self::traversal("confluent_connector", &[resource_name])?,
             ))
+            .add_attribute(("id", id))
+            .build())
+    }
+
+    /// The connector's environment ID: the profile's, or `var.environment_id`
+    fn environment_id(options: &TerraformConfigOptions) -> Result<Expression, ConnectUtilError> {
+        if !options.for_each_environments.is_empty() {
+            return Self::traversal("each", &["value", "environment_id"]);
+        }
+        match &options.environment {
+            Some(environment) => Self::profile_value(&environment.id),
+            None => Self::traversal("var", &["environment_id"]),
+        }
+    }
+
+    /// An attribute of the connector's Kafka cluster reference. With a profile the
+    /// `api_version` and `kind` are the fixed values for a Kafka cluster
+    fn kafka_cluster_attr(
+        options: &TerraformConfigOptions,
+        attr: &str,
+    ) -> Result<Expression, ConnectUtilError> {
+        if !options.for_each_environments.is_empty() && attr == "id" {
+            return Self::traversal("each", &["value", "kafka_cluster_id"]);
+        }
+        match (&options.environment, attr) {
+            (None, _) => Self::traversal("var", &["kafka_cluster", attr]),
+            (Some(environment), "id") => Self::profile_value(&environment.cluster_id),
+            (Some(_), "api_version") => Ok(Expression::String("cmk/v2".to_string())),
+            (Some(_), "kind") => Ok(Expression::String("Cluster".to_string())),
+            (Some(_), _) => Err(ConnectUtilError::Terraform(format!(
+                "Unknown kafka_cluster attribute '{}'",
+                attr
+            ))),
+        }
+    }
+
+    /// Splits a profile value such as `var.prod_cluster_id` into its root and
+    /// attributes; None means the value is a literal ID
+    fn profile_reference(value: &str) -> Option<(&str, Vec<&str>)> {
+        let mut parts = value.split('.');
+        let root = parts.next()?;
+        let attrs: Vec<&str> = parts.collect();
+        let is_identifier = |part: &str| {
+            part.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        };
+        (["var", "local", "data", "module"].contains(&root)
+            && !attrs.is_empty()
+            && attrs.iter().all(|attr| is_identifier(attr)))
+        .then_some((root, attrs))
+    }
+
+    /// Renders a profile value as a reference expression or a quoted literal
+    fn profile_value(value: &str) -> Result<Expression, ConnectUtilError> {
+        match Self::profile_reference(value) {
+            Some((root, attrs)) => Self::traversal(root, &attrs),
+            None => Ok(Expression::String(value.to_string())),
+        }
+    }
+
+    /// Builds a reference expression such as `var.kafka_cluster.id`
+    pub(crate) fn traversal(root: &str, attrs: &[&str]) -> Result<Expression, ConnectUtilError> {
+        let mut builder = Traversal::builder(Variable::new(root).map_err(|e| {
+            ConnectUtilError::Terraform(format!("Invalid variable name '{}': {}", root, e))
+        })?);
+        for attr in attrs {
+            builder = builder.attr(*attr);
+        }
+        Ok(Expression::from(builder.build()))
+    }
+
+    /// Terraform resource label for a topic name
+    pub fn topic_resource_name(topic: &str) -> String {
+        let label: String = topic
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        if label.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("topic_{}", label)
+        } else {
+            label
+        }
+    }
+
+    /// Terraform resource labels for a connector's topics, in order. Topics that map
+    /// to the same label, such as `orders.v1` and `orders_v1`, get `_2`, `_3`, ...
+    pub fn topic_resource_names(topics: &[String]) -> Vec<String> {
+        let mut used = HashSet::new();
+        topics
+            .iter()
+            .map(|topic| {
+                let base = Self::topic_resource_name(topic);
+                let mut label = base.clone();
+                let mut suffix = 2;
+                while !used.insert(label.clone()) {
+                    label = format!("{}_{}", base, suffix);
+                    suffix += 1;
+                }
+                label
+            })
+            .collect()
+    }
+
+    /// Terraform resource label for a connector name
+    pub fn resource_name(connector_name: &str) -> String {
+        connector_name.replace('-', "_")
+    }
+
+    /// Sensitive keys the generator leaves a placeholder for: the connector's secrets
+    /// and, with CSFLE over an API key, the Schema Registry credentials
+    pub fn secret_keys(options: &TerraformConfigOptions) -> Vec<String> {
+        let mut keys = options.connector.sensitive_configs.clone();
+        if options.schema_registry.csfle && options.companions.api_key {
+            keys.extend(
+                [schema_registry::SR_API_KEY, schema_registry::SR_API_SECRET].map(str::to_string),
+            );
+        }
+        keys.retain(|key| !options.template_values.contains_key(key));
+        keys
+    }
+
+    /// Variable holding one of a connector's secrets, e.g. `orders_database_password`
+    pub fn secret_variable_name(connector_name: &str, key: &str) -> String {
+        format!(
+            "{}_{}",
+            Self::resource_name(connector_name),
+            Self::topic_resource_name(key)
+        )
+    }
+
+    /// Where the backend reads a secret from, or None for an inline placeholder
+    pub fn secret_reference(
+        backend: SecretsBackend,
+        connector_name: &str,
+        key: &str,
+    ) -> Result<Option<Expression>, ConnectUtilError> {
+        let label = Self::secret_variable_name(connector_name, key);
+        let reference = match backend {
+            SecretsBackend::Inline => return Ok(None),
+            SecretsBackend::Variables => Self::traversal("var", &[&label])?,
+            SecretsBackend::AzureKeyVault => {
+                Self::traversal("data", &["azurerm_key_vault_secret", &label, "value"])?
+            }
+            SecretsBackend::GcpSecretManager => Self::traversal(
+                "data",
+                &[
+                    "google_secret_manager_secret_version",
+                    &label,
+                    "secret_data",
+                ],
+            )?,
+        };
+        Ok(Some(reference))
+    }
+
+    /// Generate the data sources a secret manager backend looks a connector's secrets
+    /// up with. Key Vault secret names only allow dashes, so `orders_database_password`
+    /// is read from `orders-database-password`
+    pub fn generate_secret_data_sources(
+        &self,
+        connector_name: &str,
+        keys: &[String],
+        backend: SecretsBackend,
+    ) -> Result<Option<String>, ConnectUtilError> {
+        let data_type = match backend {
+            SecretsBackend::Inline | SecretsBackend::Variables => return Ok(None),
+            SecretsBackend::AzureKeyVault => "azurerm_key_vault_secret",
+            SecretsBackend::GcpSecretManager => "google_secret_manager_secret_version",
+        };
+        if keys.is_empty() {
+            return Ok(None);
+        }
+        let mut body = Body::builder();
+        for key in keys {
+            let label = Self::secret_variable_name(connector_name, key);
+            let block = Block::builder("data")
+                .add_label(data_type)
+                .add_label(label.as_str());
+            let block = if backend == SecretsBackend::AzureKeyVault {
+                block
+                    .add_attribute(("name", label.replace('_', "-")))
+                    .add_attribute(("key_vault_id", Self::traversal("var", &["key_vault_id"])?))
+            } else {
+                block.add_attribute(("secret", label.as_str()))
+            };
+            body = body.add_block(block.build());
+        }
+        hcl::to_string(&body.build())
+            .map(Some)
+            .map_err(|e| ConnectUtilError::Terraform(format!("Failed to serialize HCL: {}", e)))
+    }
+
+    /// Generate the `variable` declarations a backend's secret references need: one
+    /// per secret for SOPS, or the vault ID the Key Vault data sources share
+    pub fn generate_secret_backend_variables(
+        &self,
+        connector_name: &str,
+        keys: &[String],
+        backend: SecretsBackend,
+    ) -> Result<Option<String>, ConnectUtilError> {
+        if keys.is_empty() {
+            return Ok(None);
+        }
+        match backend {
+            SecretsBackend::Inline | SecretsBackend::GcpSecretManager => Ok(None),
+            SecretsBackend::Variables => self
+                .generate_secret_variables(connector_name, keys)
+                .map(Some),
+            SecretsBackend::AzureKeyVault => {
+                let variable = MissingVariable {
+                    name: "key_vault_id".to_string(),
+                    sensitive: false,
+                    attributes: Default::default(),
+                    addresses: keys
+                        .iter()
+                        .map(|key| {
+                            format!(
+                                "data.azurerm_key_vault_secret.{}",
+                                Self::secret_variable_name(connector_name, key)
+                            )
+                        })
+                        .collect(),
+                };
+                self.generate_missing_variables(&[variable]).map(Some)
+            }
+        }
+    }
+
+    /// Generate sensitive `variable` declarations for a connector's secrets
+    pub fn generate_secret_variables(
+        &self,
+        connector_name: &str,
+        keys: &[String],
+    ) -> Result<String, ConnectUtilError> {
+        let address = format!(
+            "confluent_connector.{}",
+            Self::resource_name(connector_name)
+        );
+        let variables: Vec<MissingVariable> = keys
+            .iter()
+            .map(|key| MissingVariable {
+                name: Self::secret_variable_name(connector_name, key),
+                sensitive: true,
+                attributes: Default::default(),
+                addresses: [address.clone()].into(),
+            })
+            .collect();
+        self.generate_missing_variables(&variables)
+    }
+
+    /// Generate `variable` declarations for every variable the connector resource references.
+    /// With environment profiles only the `var.*` IDs they name are declared
+    pub fn generate_variables_config(
+        &self,
+        companions: &CompanionResources,
+        environments: &[Environment],
+    ) -> Result<String, ConnectUtilError> {
+        let string_type = || Self::type_expression("string");
+
+        let mut body = Body::builder().add_block(
+            Block::builder("variable")
+                .add_label("status")
+                .add_attribute(("description", "Desired connector status"))
+                .add_attribute(("type", string_type()?))
+                .add_attribute(("default", "RUNNING"))
+                .build(),
+        );
+
+        if !environments.is_empty() {
+            let profile_variables = environments.iter().flat_map(|environment| {
+                [
+                    (
+                        &environment.id,
+                        format!("Confluent Cloud environment ID for {}", environment.name),
+                    ),
+                    (
+                        &environment.cluster_id,
+                        format!("Kafka cluster ID for {}", environment.name),
+                    ),
+                ]
+            });
+            let mut declared = Vec::new();
+            for (value, description) in profile_variables {
+                // Nested references like var.prod.id point at variables the user already manages
+                let name = match Self::profile_reference(value) {
+                    Some(("var", attrs)) if attrs.len() == 1 => attrs[0],
+                    _ => continue,
+                };
+                if declared.contains(&name) {
+                    continue;
+                }
+                declared.push(name);
+                body = body.add_block(
+                    Block::builder("variable")
+                        .add_label(name)
+                        .add_attribute(("description", description))
+                        .add_attribute(("type", string_type()?))
+                        .build(),
+                );
+            }
+        } else {
+            let mut kafka_cluster_type = Object::new();
+            kafka_cluster_type.insert(Self::make_object_key("id"), string_type()?);
+            if companions.api_key {
+                // API key managed_resource blocks need the full cluster reference
+                kafka_cluster_type.insert(Self::make_object_key("api_version"), string_type()?);
+                kafka_cluster_type.insert(Self::make_object_key("kind"), string_type()?);
+            }
+
+            body = body
+                .add_block(
+                    Block::builder("variable")
+                        .add_label("environment_id")
+                        .add_attribute(("description", "Confluent Cloud environment ID"))
+                        .add_attribute(("type", string_type()?))
+                        .build(),
+                )
+                .add_block(
+                    Block::builder("variable")
+                        .add_label("kafka_cluster")
+                        .add_attribute(("description", "Kafka cluster the connector runs on"))
+                        .add_attribute((
+                            "type",
+                            Expression::FuncCall(Box::new(hcl::FuncCall {
+                                name: Identifier::new("object").map_err(|e| {
+                                    ConnectUtilError::Terraform(format!(
+                                        "Invalid function name 'object': {}",
+                                        e
+                                    ))
+                                })?,
+                                args: vec![Expression::Object(kafka_cluster_type)],
+                                expand_final: false,
+                            })),
+                        ))
+                        .build(),
+                );
+        }
+        let body = body.build();
+
+        hcl::to_string(&body)
+            .map_err(|e| ConnectUtilError::Terraform(format!("Failed to serialize HCL: {}", e)))
+    }
+
+    /// Generate `variable` declarations for variables connectors reference but
+    /// nobody declared; attribute reads become object types, everything else a string
+    pub fn generate_missing_variables(
+        &self,
+        missing: &[MissingVariable],
+    ) -> Result<String, ConnectUtilError> {
+        let mut body = Body::builder();
+        for variable in missing {
+            let addresses: Vec<&str> = variable.addresses.iter().map(String::as_str).collect();
+            let variable_type = if variable.attributes.is_empty() {
+                Self::type_expression("string")?
+            } else {
+                let mut object_type = Object::new();
+                for attribute in &variable.attributes {
+                    object_type.insert(
+                        Self::make_object_key(attribute),
+                        Self::type_expression("string")?,
+                    );
+                }
+                Expression::FuncCall(Box::new(hcl::FuncCall {
+                    name: Identifier::new("object").map_err(|e| {
+                        ConnectUtilError::Terraform(format!(
+                            "Invalid function name 'object': {}",
+                            e
+                        ))
+                    })?,
+                    args: vec![Expression::Object(object_type)],
+                    expand_final: false,
+                }))
+            };
+
+            let mut block = Block::builder("variable")
+                .add_label(variable.name.as_str())
+                .add_attribute(("description", format!("Used by {}", addresses.join(", "))))
+                .add_attribute(("type", variable_type));
+            if variable.sensitive {
+                block = block.add_attribute(("sensitive", true));
+            }
+            body = body.add_block(block.build());
+        }
+
+        hcl::to_string(&body.build())
+            .map_err(|e| ConnectUtilError::Terraform(format!("Failed to serialize HCL: {}", e)))
+    }
+
+    /// Generate `output` blocks exposing the connector's ID and status
+    pub fn generate_outputs_config(
+        &self,
+        connector_name: &str,
+    ) -> Result<String, ConnectUtilError> {
+        let resource_name = Self::resource_name(connector_name);
+        let attribute_ref = |attr: &str| -> Result<Expression, ConnectUtilError> {
+            Ok(Expression::from(
+                Traversal::builder(Variable::new("confluent_connector").map_err(|e| {
+                    ConnectUtilError::Terraform(format!(
+                        "Invalid variable name 'confluent_connector': {}",
+                        e
+                    ))
+                })?)
+                .attr(resource_name.as_str())
+                .attr(attr)
+                .build(),
+            ))
+        };
+
+        let body = Body::builder()
+            .add_block(
+                Block::builder("output")
+                    .add_label("connector_id")
+                    .add_attribute(("description", "ID of the connector"))
+                    .add_attribute(("value", attribute_ref("id")?))
+                    .build(),
+            )
             .add_block(
-                Block::builder("lifecycle")
-                    .add_attribute((
-                        "ignore_changes",
-                        Expression::Array(vec![
-                            Expression::String(
-                                "config_nonsensitive[\"kafka.deployment.type\"]".to_string(),
-                            ),
-                            Expression::String(
-                                "config_nonsensitive[\"kafka.max.partition.validation.disable\"]"
-                                    .to_string(),
-                            ),
-                            Expression::String(
-                                "config_nonsensitive[\"kafka.max.partition.validation.enable\"]"
-                                    .to_string(),
-                            ),
-                            Expression::String(
-                                "config_nonsensitive[\"kafka.max.partition.validation\"]"
-                                    .to_string(),
-                            ),
-                        ]),
-                    ))
+                Block::builder("output")
+                    .add_label("connector_status")
+                    .add_attribute(("description", "Current status of the connector"))
+                    .add_attribute(("value", attribute_ref("status")?))
                     .build(),
             )
             .build();
 
-        // Build the main body
-        let body = Body::builder().add_block(resource_block).build();
+        hcl::to_string(&body)
+            .map_err(|e| ConnectUtilError::Terraform(format!("Failed to serialize HCL: {}", e)))
+    }
 
-        // Serialize to HCL string
-        let hcl_string = hcl::to_string(&body)
-            .map_err(|e| ConnectUtilError::Terraform(format!("Failed to serialize HCL: {}", e)))?;
+    /// A `moved` block recording that a connector resource was relabelled, so
+    /// Terraform updates its state instead of destroying and recreating the connector
+    pub fn generate_moved_config(&self, from: &str, to: &str) -> Result<String, ConnectUtilError> {
+        let body = Body::builder()
+            .add_block(
+                Block::builder("moved")
+                    .add_attribute(("from", Self::traversal("confluent_connector", &[from])?))
+                    .add_attribute(("to", Self::traversal("confluent_connector", &[to])?))
+                    .build(),
+            )
+            .build();
+        hcl::to_string(&body)
+            .map_err(|e| ConnectUtilError::Terraform(format!("Failed to serialize HCL: {}", e)))
+    }
+
+    /// Bare type keyword (e.g. `string`) for variable declarations
+    fn type_expression(name: &str) -> Result<Expression, ConnectUtilError> {
+        Ok(Expression::Variable(Variable::new(name).map_err(|e| {
+            ConnectUtilError::Terraform(format!("Invalid type name '{}': {}", name, e))
+        })?))
+    }
+
+    /// Appends newly generated configuration to existing file content.
+    /// The existing text is kept verbatim; resources and outputs whose
+    /// address already exists in the file are rejected.
+    pub fn append_to_existing(existing: &str, generated: &str) -> Result<String, ConnectUtilError> {
+        let existing_body: Body = parse_body(existing).map_err(|e| {
+            ConnectUtilError::Terraform(format!("Failed to parse existing file: {}", e))
+        })?;
+        let generated_body: Body = parse_body(generated).map_err(|e| {
+            ConnectUtilError::Terraform(format!("Failed to parse generated config: {}", e))
+        })?;
+
+        let existing_addresses = Self::resource_addresses(&existing_body);
+        for address in Self::resource_addresses(&generated_body) {
+            if existing_addresses.contains(&address) {
+                return Err(ConnectUtilError::Terraform(format!(
+                    "Resource '{}' already exists in the output file",
+                    address
+                )));
+            }
+        }
+        let existing_outputs = Self::labels_of(&existing_body, "output");
+        for output in Self::labels_of(&generated_body, "output") {
+            if existing_outputs.contains(&output) {
+                return Err(ConnectUtilError::Terraform(format!(
+                    "Output '{}' already exists in the output file",
+                    output
+                )));
+            }
+        }
+
+        let mut combined = existing.trim_end().to_string();
+        if !combined.is_empty() {
+            combined.push_str("\n\n");
+        }
+        combined.push_str(generated);
+        Ok(combined)
+    }
+
+    /// Returns the first label of every block with the given identifier
+    fn labels_of(body: &Body, identifier: &str) -> Vec<String> {
+        body.blocks()
+            .filter(|block| block.identifier() == identifier)
+            .filter_map(|block| block.labels().first().map(|l| l.as_str().to_string()))
+            .collect()
+    }
 
-        Ok(hcl_string)
+    /// Returns `type.name` addresses for every resource block in a body
+    pub(crate) fn resource_addresses(body: &Body) -> Vec<String> {
+        body.blocks()
+            .filter(|block| block.identifier() == "resource")
+            .filter_map(|block| {
+                let labels = block.labels();
+                if labels.len() >= 2 {
+                    Some(format!("{}.{}", labels[0].as_str(), labels[1].as_str()))
+                } else {
+                    None
+                }
+            })
+            .collect()
     }
 
     /// Convert DataFormat to Expression for use in HCL
@@ -229,16 +1271,50 @@ impl TerraformGenerator {
 
     /// Helper to create ObjectKey from string
     pub(crate) fn make_object_key(s: &str) -> ObjectKey {
-        if s.contains('.') {
-            ObjectKey::Expression(Expression::String(s.to_string()))
-        } else {
-            ObjectKey::Identifier(Identifier::new(s).unwrap_or_else(|_| {
-                // Fallback to Expression if identifier creation fails
-                Identifier::sanitized(s)
-            }))
+        // Only plain ASCII names go bare; dots, dashes, non-ASCII letters, and
+        // literal names such as `true` are quoted, so the key reads back unchanged
+        let plain = s.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && !["true", "false", "null"].contains(&s);
+        match Identifier::new(s) {
+            Ok(identifier) if plain => ObjectKey::Identifier(identifier),
+            _ => ObjectKey::Expression(Expression::String(s.to_string())),
         }
     }
 
+    /// Rejects config keys Confluent Cloud can't hold: empty keys, keys with
+    /// surrounding whitespace, and keys with control characters such as newlines
+    pub(crate) fn check_config_key(key: &str) -> Result<(), ConnectUtilError> {
+        let problem = if key.is_empty() {
+            "is empty"
+        } else if key.trim() != key {
+            "has leading or trailing whitespace"
+        } else if key.chars().any(char::is_control) {
+            "contains a control character"
+        } else {
+            return Ok(());
+        };
+        Err(ConnectUtilError::Config(format!(
+            "Config key {:?} {}",
+            key, problem
+        )))
+    }
+
+    /// Checks the keys that come from policy templates, schema settings, and
+    /// environment overrides before they're written
+    fn check_config_keys(options: &TerraformConfigOptions) -> Result<(), ConnectUtilError> {
+        let environments = options
+            .environment
+            .iter()
+            .chain(&options.for_each_environments);
+        options
+            .template_values
+            .keys()
+            .chain(options.schema_values.keys())
+            .chain(environments.flat_map(|environment| environment.overrides.keys()))
+            .try_for_each(|key| Self::check_config_key(key))
+    }
+
     /// Add connector-specific configuration to the config object
     pub(crate) fn add_connector_specific_config_to_object(
         config_obj: &mut Object<ObjectKey, Expression>,
@@ -455,14 +1531,6 @@ impl TerraformGenerator {
                     Self::make_object_key("topics.dir"),
                     Expression::String("<REPLACE_WITH_TOPICS_DIR>".to_string()),
                 );
-                config_obj.insert(
-                    Self::make_object_key("path.format"),
-                    Expression::String("'effective_date'=YYYY-MM-dd".to_string()),
-                );
-                config_obj.insert(
-                    Self::make_object_key("time.interval"),
-                    Expression::String("HOURLY".to_string()),
-                );
                 config_obj.insert(
                     Self::make_object_key("rotate.schedule.interval.ms"),
                     Expression::String("3600000".to_string()),
@@ -706,12 +1774,123 @@ impl TerraformGenerator {
         }
         Ok(())
     }
+
+    /// `partitioner.class` and the keys it needs for an object-store sink. Without a
+    /// partitioner the sink keeps the dated `effective_date` layout with hourly rotation
+    fn add_storage_layout(
+        config_obj: &mut Object<ObjectKey, Expression>,
+        layout: &StorageLayoutOptions,
+    ) {
+        let mut insert = |key: &str, value: String| {
+            config_obj.insert(Self::make_object_key(key), Expression::String(value));
+        };
+        let Some(partitioner) = layout.partitioner else {
+            insert(
+                storage::PATH_FORMAT,
+                "'effective_date'=YYYY-MM-dd".to_string(),
+            );
+            insert(storage::TIME_INTERVAL, "HOURLY".to_string());
+            return;
+        };
+        insert(storage::PARTITIONER_CLASS, partitioner.class().to_string());
+        match partitioner {
+            Partitioner::Default => {}
+            Partitioner::Field => {
+                insert(
+                    storage::PARTITION_FIELD_NAME,
+                    layout.partition_fields.join(","),
+                );
+            }
+            Partitioner::Time | Partitioner::Daily | Partitioner::Hourly => {
+                let path_format = layout.path_format.clone().unwrap_or_else(|| {
+                    storage::build_path_format(partitioner == Partitioner::Hourly)
+                });
+                let interval = match partitioner {
+                    Partitioner::Daily => "DAILY",
+                    Partitioner::Hourly => "HOURLY",
+                    _ => storage::time_interval(&path_format),
+                };
+                insert(storage::TIME_INTERVAL, interval.to_string());
+                insert(storage::PATH_FORMAT, path_format);
+                insert(
+                    storage::LOCALE,
+                    layout.locale.clone().unwrap_or_else(|| "en-US".to_string()),
+                );
+                insert(
+                    storage::TIMEZONE,
+                    layout.timezone.clone().unwrap_or_else(|| "UTC".to_string()),
+                );
+            }
+        }
+    }
+
+    /// Heartbeat and signal table keys for a Debezium-based source. A heartbeat
+    /// table turns heartbeats on at the default interval when none is given
+    fn add_debezium_config(
+        config_obj: &mut Object<ObjectKey, Expression>,
+        dialect: Dialect,
+        options: &DebeziumOptions,
+    ) {
+        let mut insert = |key: &str, value: String| {
+            config_obj.insert(Self::make_object_key(key), Expression::String(value));
+        };
+        let interval = options.heartbeat_interval_ms.or(options
+            .heartbeat_table
+            .as_ref()
+            .map(|_| debezium::DEFAULT_HEARTBEAT_INTERVAL_MS));
+        if let Some(interval) = interval {
+            insert(debezium::HEARTBEAT_INTERVAL_MS, interval.to_string());
+        }
+        if let Some(query) = options
+            .heartbeat_table
+            .as_ref()
+            .and_then(|table| dialect.heartbeat_query(table))
+        {
+            insert(debezium::HEARTBEAT_ACTION_QUERY, query);
+        }
+        if let Some(table) = &options.signal_table {
+            insert(debezium::SIGNAL_DATA_COLLECTION, table.clone());
+        }
+        if let Some(size) = options.snapshot_chunk_size {
+            insert(debezium::INCREMENTAL_SNAPSHOT_CHUNK_SIZE, size.to_string());
+        }
+    }
+
+    /// Fills every required field still missing with its default, its first valid
+    /// value, or a `<REPLACE_WITH_...>` placeholder, so a generated connector always
+    /// carries the keys `validate` requires
+    fn add_required_placeholders(
+        config_obj: &mut Object<ObjectKey, Expression>,
+        sensitive_obj: &Object<ObjectKey, Expression>,
+        connector_def: &ConnectorDefinition,
+    ) {
+        for field in &connector_def.required_configs {
+            let key = Self::make_object_key(&field.name);
+            if config_obj.contains_key(&key) || sensitive_obj.contains_key(&key) {
+                continue;
+            }
+            let value = field
+                .default_value
+                .clone()
+                .or_else(|| field.valid_values.as_ref()?.first().cloned())
+                .unwrap_or_else(|| {
+                    format!(
+                        "<REPLACE_WITH_{}>",
+                        field.name.to_uppercase().replace(['.', '-'], "_")
+                    )
+                });
+            config_obj.insert(key, Expression::String(value));
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{ConnectorDefinition, ConnectorType};
+    use crate::types::{
+        ConfigField, ConnectorDefinition, ConnectorFamily, ConnectorType, SchemaRegistryOptions,
+    };
+    use std::collections::BTreeSet;
 
     fn create_test_connector() -> ConnectorDefinition {
         ConnectorDefinition {
@@ -719,13 +1898,54 @@ mod tests {
             display_name: "PostgreSQL Sink".to_string(),
             description: "PostgreSQL Sink Connector".to_string(),
             connector_class: "io.confluent.connect.jdbc.JdbcSinkConnector".to_string(),
+            aliases: vec![],
             connector_type: ConnectorType::Sink,
+            family: ConnectorFamily::Other,
+            topic_strategy: TopicStrategy::TopicsList,
             required_configs: vec![],
             optional_configs: vec![],
             sensitive_configs: vec!["password".to_string()],
         }
     }
 
+    #[test]
+    fn test_generate_connector_config_fills_required_fields() {
+        let field = |name: &str, default: Option<&str>, valid: Option<&[&str]>| ConfigField {
+            name: name.to_string(),
+            display_name: name.to_string(),
+            description: String::new(),
+            field_type: "string".to_string(),
+            required: true,
+            default_value: default.map(str::to_string),
+            valid_values: valid.map(|values| values.iter().map(|v| v.to_string()).collect()),
+            group: Default::default(),
+        };
+        let connector = ConnectorDefinition {
+            name: "ExampleSource".to_string(),
+            connector_type: ConnectorType::Source,
+            required_configs: vec![
+                field("example.url", None, None),
+                field("example.poll.ms", Some("5000"), None),
+                field("example.mode", None, Some(&["bulk", "incremental"])),
+                field("password", None, None),
+            ],
+            sensitive_configs: vec!["password".to_string()],
+            ..create_test_connector()
+        };
+
+        let terraform = TerraformGenerator
+            .generate_connector_config(TerraformConfigOptions {
+                connector_name: "example".to_string(),
+                connector,
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(terraform.contains("\"example.url\" = \"<REPLACE_WITH_EXAMPLE_URL>\""));
+        assert!(terraform.contains("\"example.poll.ms\" = \"5000\""));
+        assert!(terraform.contains("\"example.mode\" = \"bulk\""));
+        assert_eq!(terraform.matches("password =").count(), 1);
+    }
+
     #[test]
     fn test_generate_connector_config() {
         let generator = TerraformGenerator;
@@ -737,6 +1957,7 @@ mod tests {
             topics: vec!["test-topic".to_string()],
             input_data_format: None,
             output_data_format: None,
+            ..Default::default()
         };
 
         let result = generator.generate_connector_config(options);
@@ -768,6 +1989,7 @@ mod tests {
             topics: vec![],
             input_data_format: None,
             output_data_format: None,
+            ..Default::default()
         };
 
         let result = generator.generate_connector_config(options);
@@ -781,6 +2003,730 @@ mod tests {
         assert!(terraform.contains("lifecycle {"));
     }
 
+    #[test]
+    fn test_generate_variables_config() {
+        let generator = TerraformGenerator;
+        let variables = generator
+            .generate_variables_config(&CompanionResources::default(), &[])
+            .unwrap();
+
+        assert!(variables.contains("variable \"status\""));
+        assert!(variables.contains("default = \"RUNNING\""));
+        assert!(variables.contains("variable \"environment_id\""));
+        assert!(variables.contains("type = string"));
+        assert!(variables.contains("variable \"kafka_cluster\""));
+        assert!(variables.contains("object({"));
+        assert!(hcl::from_str::<Body>(&variables).is_ok());
+    }
+
+    #[test]
+    fn test_generate_variables_config_with_api_key() {
+        let generator = TerraformGenerator;
+        let companions = CompanionResources {
+            api_key: true,
+            ..Default::default()
+        };
+        let variables = generator
+            .generate_variables_config(&companions, &[])
+            .unwrap();
+
+        assert!(variables.contains("api_version = string"));
+        assert!(variables.contains("kind = string"));
+    }
+
+    fn environment(id: &str, cluster_id: &str) -> Environment {
+        Environment {
+            name: "prod".to_string(),
+            id: id.to_string(),
+            cluster_id: cluster_id.to_string(),
+            schema_registry_cluster_id: String::new(),
+            cluster_type: None,
+            overrides: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_generate_connector_config_with_environment_profile() {
+        let generator = TerraformGenerator;
+        let options = TerraformConfigOptions {
+            connector_name: "test-connector".to_string(),
+            connector: create_test_connector(),
+            topics: vec!["orders".to_string()],
+            companions: CompanionResources {
+                topics: true,
+                service_account: true,
+                api_key: true,
+            },
+            environment: Some(environment("env-prod1", "lkc-prod1")),
+            ..Default::default()
+        };
+        let terraform = generator.generate_connector_config(options).unwrap();
+
+        assert!(terraform.contains("id = \"env-prod1\""));
+        assert!(terraform.contains("id = \"lkc-prod1\""));
+        assert!(terraform.contains("api_version = \"cmk/v2\""));
+        assert!(terraform.contains("kind = \"Cluster\""));
+        assert!(!terraform.contains("var.environment_id"));
+        assert!(!terraform.contains("var.kafka_cluster"));
+    }
+
+    #[test]
+    fn test_generate_connector_config_with_import_block() {
+        let generator = TerraformGenerator;
+        let options = |environment: Option<Environment>| TerraformConfigOptions {
+            connector_name: "test-connector".to_string(),
+            connector: create_test_connector(),
+            environment,
+            import_name: Some("orders-cdc".to_string()),
+            ..Default::default()
+        };
+
+        let terraform = generator.generate_connector_config(options(None)).unwrap();
+        let import = &terraform[terraform.find("import {").unwrap()..];
+        assert!(import.contains("to = confluent_connector.test_connector"));
+        assert!(
+            import.contains("id = \"${var.environment_id}/${var.kafka_cluster.id}/orders-cdc\"")
+        );
+
+        let terraform = generator
+            .generate_connector_config(options(Some(environment(
+                "env-prod1",
+                "local.clusters.prod",
+            ))))
+            .unwrap();
+        assert!(terraform.contains("id = \"env-prod1/${local.clusters.prod}/orders-cdc\""));
+
+        let for_each = TerraformConfigOptions {
+            for_each_environments: vec![environment("env-dev1", "lkc-dev1")],
+            ..options(None)
+        };
+        assert!(matches!(
+            generator.generate_connector_config(for_each),
+            Err(ConnectUtilError::Terraform(_))
+        ));
+    }
+
+    #[test]
+    fn test_generate_variables_config_with_environment_profile() {
+        let generator = TerraformGenerator;
+        let options = TerraformConfigOptions {
+            connector_name: "test-connector".to_string(),
+            connector: create_test_connector(),
+            environment: Some(environment(
+                "var.prod_environment_id",
+                "local.clusters.prod",
+            )),
+            ..Default::default()
+        };
+        let terraform = generator.generate_connector_config(options).unwrap();
+        assert!(terraform.contains("id = var.prod_environment_id"));
+        assert!(terraform.contains("id = local.clusters.prod"));
+
+        let profile = environment("var.prod_environment_id", "local.clusters.prod");
+        let variables = generator
+            .generate_variables_config(&CompanionResources::default(), &[profile])
+            .unwrap();
+        assert!(variables.contains("variable \"status\""));
+        assert!(variables.contains("variable \"prod_environment_id\""));
+        assert!(variables.contains("Confluent Cloud environment ID for prod"));
+        assert!(!variables.contains("variable \"kafka_cluster\""));
+        assert!(!variables.contains("variable \"environment_id\""));
+    }
+
+    #[test]
+    fn test_generate_connector_config_for_each_environment() {
+        let generator = TerraformGenerator;
+        let mut prod = environment("env-prod1", "lkc-prod1");
+        prod.overrides
+            .insert("tasks.max".to_string(), "4".to_string());
+        let mut dev = environment("env-dev1", "var.dev_cluster_id");
+        dev.name = "dev".to_string();
+        let options = TerraformConfigOptions {
+            connector_name: "test-connector".to_string(),
+            connector: create_test_connector(),
+            for_each_environments: vec![dev.clone(), prod.clone()],
+            ..Default::default()
+        };
+        let terraform = generator.generate_connector_config(options).unwrap();
+
+        assert!(terraform.contains("test_connector_environments = {"));
+        assert!(terraform.contains("kafka_cluster_id = var.dev_cluster_id"));
+        assert!(terraform.contains("\"tasks.max\" = \"4\""));
+        assert!(terraform.contains("for_each = local.test_connector_environments"));
+        assert!(terraform.contains("id = each.value.environment_id"));
+        assert!(terraform.contains("id = each.value.kafka_cluster_id"));
+        assert!(terraform.contains("each.value.config)"));
+
+        let variables = generator
+            .generate_variables_config(&CompanionResources::default(), &[dev, prod.clone()])
+            .unwrap();
+        assert!(variables.contains("variable \"dev_cluster_id\""));
+
+        let result = generator.generate_connector_config(TerraformConfigOptions {
+            connector_name: "test-connector".to_string(),
+            connector: create_test_connector(),
+            companions: CompanionResources {
+                topics: true,
+                ..Default::default()
+            },
+            for_each_environments: vec![prod],
+            ..Default::default()
+        });
+        assert!(matches!(result, Err(ConnectUtilError::Terraform(_))));
+    }
+
+    #[test]
+    fn test_environment_overrides_replace_defaults() {
+        let generator = TerraformGenerator;
+        let mut prod = environment("env-prod1", "lkc-prod1");
+        prod.overrides
+            .insert("tasks.max".to_string(), "4".to_string());
+        let terraform = generator
+            .generate_connector_config(TerraformConfigOptions {
+                connector_name: "test-connector".to_string(),
+                connector: create_test_connector(),
+                environment: Some(prod),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(terraform.contains("\"tasks.max\" = \"4\""));
+        assert!(!terraform.contains("\"tasks.max\" = \"1\""));
+    }
+
+    #[test]
+    fn test_generate_schema_registry_config() {
+        let generator = TerraformGenerator;
+        let connector = ConnectorDefinition::get_connector_by_name("DatagenSource").unwrap();
+        let options = |companions: CompanionResources| TerraformConfigOptions {
+            connector_name: "orders".to_string(),
+            connector: connector.clone(),
+            companions,
+            schema_registry: SchemaRegistryOptions {
+                context: Some("orders".to_string()),
+                csfle: true,
+            },
+            ..Default::default()
+        };
+
+        let service_account = generator
+            .generate_connector_config(options(CompanionResources {
+                service_account: true,
+                ..Default::default()
+            }))
+            .unwrap();
+        assert!(service_account.contains("\"schema.context.name\" = \"orders\""));
+        assert!(service_account.contains("\"csfle.enabled\" = \"true\""));
+        assert!(service_account.contains("\"csfle.onFailure\" = \"ERROR\""));
+        assert!(service_account
+            .contains("\"sr.service.account.id\" = confluent_service_account.orders.id"));
+        assert!(!service_account.contains("schema.registry.api.key"));
+
+        let api_key = generator
+            .generate_connector_config(options(CompanionResources {
+                service_account: true,
+                api_key: true,
+                ..Default::default()
+            }))
+            .unwrap();
+        assert!(api_key.contains("\"schema.registry.api.key\" = \"<REPLACE_WITH_ACTUAL_VALUE>\""));
+        assert!(api_key.contains("\"schema.registry.api.secret\""));
+        assert!(!api_key.contains("sr.service.account.id"));
+    }
+
+    #[test]
+    fn test_generate_connector_config_with_secret_variables() {
+        let generator = TerraformGenerator;
+        let options = TerraformConfigOptions {
+            connector_name: "orders-cdc".to_string(),
+            connector: ConnectorDefinition::get_connector_by_name("PostgresCdcSourceV2").unwrap(),
+            companions: CompanionResources {
+                service_account: true,
+                api_key: true,
+                ..Default::default()
+            },
+            schema_registry: SchemaRegistryOptions {
+                csfle: true,
+                ..Default::default()
+            },
+            secrets_backend: SecretsBackend::Variables,
+            ..Default::default()
+        };
+        let keys = TerraformGenerator::secret_keys(&options);
+        assert_eq!(
+            keys,
+            vec![
+                "database.password",
+                "schema.registry.api.key",
+                "schema.registry.api.secret"
+            ]
+        );
+
+        let terraform = generator.generate_connector_config(options).unwrap();
+        assert!(terraform.contains("\"database.password\" = var.orders_cdc_database_password"));
+        assert!(terraform
+            .contains("\"schema.registry.api.key\" = var.orders_cdc_schema_registry_api_key"));
+        assert!(terraform.contains("\"kafka.api.key\" = confluent_api_key.orders_cdc.id"));
+        assert!(!terraform.contains("<REPLACE_WITH_ACTUAL_VALUE>"));
+
+        let variables = generator
+            .generate_secret_variables("orders-cdc", &keys[..1])
+            .unwrap();
+        assert!(variables.contains("variable \"orders_cdc_database_password\""));
+        assert!(variables.contains("Used by confluent_connector.orders_cdc"));
+        assert!(variables.contains("sensitive = true"));
+    }
+
+    #[test]
+    fn test_generate_connector_config_with_secret_managers() {
+        let generator = TerraformGenerator;
+        let options = |secrets_backend| TerraformConfigOptions {
+            connector_name: "orders-cdc".to_string(),
+            connector: ConnectorDefinition::get_connector_by_name("PostgresCdcSourceV2").unwrap(),
+            secrets_backend,
+            ..Default::default()
+        };
+        let keys = vec!["database.password".to_string()];
+
+        let terraform = generator
+            .generate_connector_config(options(SecretsBackend::AzureKeyVault))
+            .unwrap();
+        assert!(terraform.contains(
+            "\"database.password\" = data.azurerm_key_vault_secret.orders_cdc_database_password.value"
+        ));
+        assert!(!terraform.contains("<REPLACE_WITH_ACTUAL_VALUE>"));
+        let data_sources = generator
+            .generate_secret_data_sources("orders-cdc", &keys, SecretsBackend::AzureKeyVault)
+            .unwrap()
+            .unwrap();
+        assert!(data_sources
+            .contains("data \"azurerm_key_vault_secret\" \"orders_cdc_database_password\""));
+        assert!(data_sources.contains("name = \"orders-cdc-database-password\""));
+        assert!(data_sources.contains("key_vault_id = var.key_vault_id"));
+        let variables = generator
+            .generate_secret_backend_variables("orders-cdc", &keys, SecretsBackend::AzureKeyVault)
+            .unwrap()
+            .unwrap();
+        assert!(variables.contains("variable \"key_vault_id\""));
+        assert!(!variables.contains("sensitive"));
+
+        let terraform = generator
+            .generate_connector_config(options(SecretsBackend::GcpSecretManager))
+            .unwrap();
+        assert!(terraform.contains(
+            "\"database.password\" = data.google_secret_manager_secret_version.orders_cdc_database_password.secret_data"
+        ));
+        let data_sources = generator
+            .generate_secret_data_sources("orders-cdc", &keys, SecretsBackend::GcpSecretManager)
+            .unwrap()
+            .unwrap();
+        assert!(data_sources.contains(
+            "data \"google_secret_manager_secret_version\" \"orders_cdc_database_password\""
+        ));
+        assert!(data_sources.contains("secret = \"orders_cdc_database_password\""));
+        assert!(generator
+            .generate_secret_backend_variables(
+                "orders-cdc",
+                &keys,
+                SecretsBackend::GcpSecretManager
+            )
+            .unwrap()
+            .is_none());
+
+        let terraform = generator
+            .generate_connector_config(options(SecretsBackend::Inline))
+            .unwrap();
+        assert!(terraform.contains("<REPLACE_WITH_ACTUAL_VALUE>"));
+        assert!(generator
+            .generate_secret_data_sources("orders-cdc", &keys, SecretsBackend::Inline)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_config_keys_round_trip() {
+        let keys = [
+            "tasks.max",
+            "_plain",
+            "dash-key",
+            "naïve.clé",
+            "日本語",
+            "true",
+            "a${b}",
+            "x%{y}",
+            "q\"uote",
+            "back\\slash",
+        ];
+        let config: BTreeMap<String, String> = keys
+            .iter()
+            .map(|key| (key.to_string(), "v".to_string()))
+            .collect();
+        let content = TerraformGenerator
+            .generate_resource_from_config("orders", &config, &BTreeMap::new())
+            .unwrap();
+        assert!(content.contains("_plain = \"v\""));
+        assert!(content.contains("\"dash-key\" = \"v\""));
+        assert!(content.contains("\"日本語\" = \"v\""));
+        assert!(content.contains("\"true\" = \"v\""));
+
+        let body = parse_body(&content).unwrap();
+        let block = body.blocks().next().unwrap();
+        let attribute = block
+            .body
+            .attributes()
+            .find(|attribute| attribute.key() == "config_nonsensitive")
+            .unwrap();
+        let Expression::Object(object) = attribute.expr() else {
+            panic!("config_nonsensitive should be an object");
+        };
+        let parsed: BTreeSet<String> = object.keys().map(|key| key.to_string()).collect();
+        assert_eq!(parsed, config.keys().cloned().collect());
+    }
+
+    #[test]
+    fn test_check_config_key() {
+        assert!(TerraformGenerator::check_config_key("naïve-key.name").is_ok());
+        for (key, problem) in [
+            ("", "is empty"),
+            (" tasks.max", "has leading or trailing whitespace"),
+            ("tasks\nmax", "contains a control character"),
+        ] {
+            let error = TerraformGenerator::check_config_key(key).unwrap_err();
+            assert!(error.to_string().contains(problem), "{}", error);
+        }
+
+        let options = TerraformConfigOptions {
+            connector_name: "orders".to_string(),
+            connector: create_test_connector(),
+            template_values: BTreeMap::from([("tasks.max\t".to_string(), "2".to_string())]),
+            ..Default::default()
+        };
+        assert!(TerraformGenerator
+            .generate_connector_config(options)
+            .is_err());
+        let options = TerraformConfigOptions {
+            connector_name: "orders".to_string(),
+            connector: create_test_connector(),
+            environment: Some(Environment {
+                overrides: BTreeMap::from([(String::new(), "4".to_string())]),
+                ..environment("env-123", "lkc-456")
+            }),
+            ..Default::default()
+        };
+        assert!(TerraformGenerator
+            .generate_connector_config(options)
+            .is_err());
+    }
+
+    #[test]
+    fn test_sorted_config() {
+        let config = |keys: &[&str]| -> Object<ObjectKey, Expression> {
+            keys.iter()
+                .map(|key| {
+                    (
+                        TerraformGenerator::make_object_key(key),
+                        Expression::String(String::new()),
+                    )
+                })
+                .collect()
+        };
+        let keys = [
+            "tombstones.on.delete",
+            "snapshot.mode",
+            "database.password",
+            "tasks.max",
+            "database.hostname",
+            "name",
+            "connector.class",
+            "database.port",
+        ];
+        let mut reversed = keys;
+        reversed.reverse();
+
+        let sorted = TerraformGenerator::sorted_config(config(&keys));
+        assert_eq!(sorted, TerraformGenerator::sorted_config(config(&reversed)));
+        let order: Vec<String> = sorted
+            .keys()
+            .map(|key| key.to_string().trim_matches('"').to_string())
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                "connector.class",
+                "name",
+                "tasks.max",
+                "database.hostname",
+                "database.port",
+                "database.password",
+                "snapshot.mode",
+                "tombstones.on.delete",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_profile_reference() {
+        assert_eq!(
+            TerraformGenerator::profile_reference("var.prod_cluster_id"),
+            Some(("var", vec!["prod_cluster_id"]))
+        );
+        assert_eq!(TerraformGenerator::profile_reference("lkc-abc123"), None);
+        assert_eq!(TerraformGenerator::profile_reference("var."), None);
+        assert_eq!(TerraformGenerator::profile_reference("env.1"), None);
+    }
+
+    #[test]
+    fn test_generate_missing_variables() {
+        let generator = TerraformGenerator;
+        let missing = vec![
+            MissingVariable {
+                name: "database_password".to_string(),
+                sensitive: true,
+                attributes: Default::default(),
+                addresses: ["confluent_connector.orders".to_string()].into(),
+            },
+            MissingVariable {
+                name: "kafka_cluster".to_string(),
+                sensitive: false,
+                attributes: ["id".to_string()].into(),
+                addresses: [
+                    "confluent_connector.orders".to_string(),
+                    "confluent_connector.payments".to_string(),
+                ]
+                .into(),
+            },
+        ];
+        let variables = generator.generate_missing_variables(&missing).unwrap();
+
+        assert!(variables.contains("variable \"database_password\""));
+        assert!(variables.contains("sensitive = true"));
+        assert!(variables.contains("type = string"));
+        assert!(variables.contains("object({"));
+        assert!(
+            variables.contains("Used by confluent_connector.orders, confluent_connector.payments")
+        );
+        assert_eq!(variables.matches("sensitive").count(), 1);
+        assert!(hcl::from_str::<Body>(&variables).is_ok());
+    }
+
+    #[test]
+    fn test_generate_connector_config_with_topic_companions() {
+        let generator = TerraformGenerator;
+        let options = TerraformConfigOptions {
+            connector_name: "orders-sink".to_string(),
+            connector: create_test_connector(),
+            topics: vec!["orders.v1".to_string(), "1-refunds".to_string()],
+            companions: CompanionResources {
+                topics: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let terraform = generator.generate_connector_config(options).unwrap();
+        assert!(terraform.contains("resource \"confluent_kafka_topic\" \"orders_v1\""));
+        assert!(terraform.contains("resource \"confluent_kafka_topic\" \"topic_1_refunds\""));
+        assert!(terraform.contains("topic_name = \"orders.v1\""));
+        assert!(terraform.contains("depends_on = ["));
+        assert!(terraform.contains("confluent_kafka_topic.orders_v1"));
+
+        // Topics come before the connector that depends on them
+        let topic_pos = terraform
+            .find("confluent_kafka_topic\" \"orders_v1")
+            .unwrap();
+        let connector_pos = terraform.find("\"confluent_connector\"").unwrap();
+        assert!(topic_pos < connector_pos);
+        assert!(hcl::from_str::<Body>(&terraform).is_ok());
+    }
+
+    #[test]
+    fn test_topic_companions_with_colliding_labels() {
+        let generator = TerraformGenerator;
+        let options = TerraformConfigOptions {
+            connector_name: "orders-sink".to_string(),
+            connector: create_test_connector(),
+            topics: vec![
+                "orders.v1".to_string(),
+                "orders_v1".to_string(),
+                "orders-v1".to_string(),
+            ],
+            companions: CompanionResources {
+                topics: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let terraform = generator.generate_connector_config(options).unwrap();
+        let body: Body = hcl::from_str(&terraform).unwrap();
+        let labels: Vec<String> = body
+            .blocks()
+            .filter(|block| {
+                block.labels.first().map(|l| l.as_str()) == Some("confluent_kafka_topic")
+            })
+            .map(|block| block.labels[1].as_str().to_string())
+            .collect();
+        assert_eq!(labels, ["orders_v1", "orders_v1_2", "orders_v1_3"]);
+        assert!(terraform.contains("confluent_kafka_topic.orders_v1_2"));
+        assert!(terraform.contains("confluent_kafka_topic.orders_v1_3"));
+    }
+
+    #[test]
+    fn test_generate_connector_config_with_api_key_companions() {
+        let generator = TerraformGenerator;
+        let options = TerraformConfigOptions {
+            connector_name: "orders-sink".to_string(),
+            connector: create_test_connector(),
+            companions: CompanionResources {
+                service_account: true,
+                api_key: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let terraform = generator.generate_connector_config(options).unwrap();
+        assert!(terraform.contains("resource \"confluent_service_account\" \"orders_sink\""));
+        assert!(terraform.contains("resource \"confluent_api_key\" \"orders_sink\""));
+        assert!(terraform.contains("id = confluent_service_account.orders_sink.id"));
+        assert!(terraform.contains("\"kafka.auth.mode\" = \"KAFKA_API_KEY\""));
+        assert!(terraform.contains("\"kafka.api.key\" = confluent_api_key.orders_sink.id"));
+        assert!(terraform.contains("\"kafka.api.secret\" = confluent_api_key.orders_sink.secret"));
+        assert!(!terraform.contains("depends_on"));
+    }
+
+    #[test]
+    fn test_generate_connector_config_without_companions() {
+        let generator = TerraformGenerator;
+        let options = TerraformConfigOptions {
+            connector_name: "orders-sink".to_string(),
+            connector: create_test_connector(),
+            topics: vec!["orders".to_string()],
+            ..Default::default()
+        };
+
+        let terraform = generator.generate_connector_config(options).unwrap();
+        assert!(!terraform.contains("confluent_kafka_topic"));
+        assert!(!terraform.contains("depends_on"));
+        assert!(terraform.contains("\"kafka.auth.mode\" = \"SERVICE_ACCOUNT\""));
+    }
+
+    #[test]
+    fn test_generate_resource_from_config() {
+        let generator = TerraformGenerator;
+        let nonsensitive = BTreeMap::from([
+            ("connector.class".to_string(), "S3_SINK".to_string()),
+            ("topics".to_string(), "orders".to_string()),
+        ]);
+        let sensitive = BTreeMap::from([(
+            "aws.secret.access.key".to_string(),
+            "<REPLACE_WITH_ACTUAL_VALUE>".to_string(),
+        )]);
+
+        let terraform = generator
+            .generate_resource_from_config("orders-archive", &nonsensitive, &sensitive)
+            .unwrap();
+        assert!(terraform.contains("resource \"confluent_connector\" \"orders_archive\""));
+        assert!(terraform.contains("\"connector.class\" = \"S3_SINK\""));
+        assert!(terraform.contains("\"aws.secret.access.key\" = \"<REPLACE_WITH_ACTUAL_VALUE>\""));
+        assert!(terraform.contains("id = var.kafka_cluster.id"));
+        assert!(terraform.contains("ignore_changes"));
+    }
+
+    #[test]
+    fn test_generate_provider_resource() {
+        let connector = SelfManagedConnector {
+            name: "orders-archive".to_string(),
+            connector_class: "io.confluent.connect.s3.S3SinkConnector".to_string(),
+            config: BTreeMap::from([
+                (
+                    "connector.class".to_string(),
+                    "io.confluent.connect.s3.S3SinkConnector".to_string(),
+                ),
+                ("name".to_string(), "orders-archive".to_string()),
+            ]),
+            config_sensitive: BTreeMap::from([(
+                "aws.secret.access.key".to_string(),
+                "<REPLACE_WITH_AWS_SECRET_ACCESS_KEY>".to_string(),
+            )]),
+            notes: vec![],
+        };
+        let generate = |provider, with_provider_block| {
+            TerraformGenerator
+                .generate_provider_resource(provider, &connector, with_provider_block)
+                .unwrap()
+        };
+
+        let terraform = generate(TerraformProvider::KafkaConnect, true);
+        assert!(terraform.contains("resource \"kafka_connect_connector\" \"orders_archive\""));
+        assert!(terraform.contains("name = \"orders-archive\""));
+        assert!(
+            terraform.contains("\"connector.class\" = \"io.confluent.connect.s3.S3SinkConnector\"")
+        );
+        assert!(terraform.contains("config_sensitive = {"));
+        assert!(!terraform.contains("provider \""));
+
+        let terraform = generate(TerraformProvider::Aiven, true);
+        assert!(terraform.contains("resource \"aiven_kafka_connector\" \"orders_archive\""));
+        assert!(terraform.contains("project = var.aiven_project"));
+        assert!(terraform.contains("connector_name = \"orders-archive\""));
+        assert!(terraform
+            .contains("\"aws.secret.access.key\" = \"<REPLACE_WITH_AWS_SECRET_ACCESS_KEY>\""));
+        assert!(!terraform.contains("config_sensitive"));
+
+        let terraform = generate(TerraformProvider::Instaclustr, true);
+        assert!(terraform.contains("provider \"kafka-connect\""));
+        assert!(terraform.contains("url = var.instaclustr_connect_url"));
+        assert!(terraform.contains("resource \"kafka_connect_connector\""));
+        assert!(!generate(TerraformProvider::Instaclustr, false).contains("provider \""));
+    }
+
+    #[test]
+    fn test_generate_outputs_config() {
+        let generator = TerraformGenerator;
+        let outputs = generator.generate_outputs_config("my-connector").unwrap();
+
+        assert!(outputs.contains("output \"connector_id\""));
+        assert!(outputs.contains("value = confluent_connector.my_connector.id"));
+        assert!(outputs.contains("output \"connector_status\""));
+        assert!(outputs.contains("value = confluent_connector.my_connector.status"));
+    }
+
+    #[test]
+    fn test_append_to_existing() {
+        let existing =
+            "# keep me\nresource \"confluent_connector\" \"a\" {\n  status = \"RUNNING\"\n}\n";
+        let generated = "resource \"confluent_connector\" \"b\" {\n  status = \"RUNNING\"\n}\n";
+
+        let combined = TerraformGenerator::append_to_existing(existing, generated).unwrap();
+        assert!(combined.starts_with("# keep me"));
+        assert!(combined.contains("\"a\""));
+        assert!(combined.contains("\"b\""));
+    }
+
+    #[test]
+    fn test_append_to_existing_rejects_duplicate() {
+        let existing = "resource \"confluent_connector\" \"a\" {\n  status = \"RUNNING\"\n}\n";
+
+        let result = TerraformGenerator::append_to_existing(existing, existing);
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("confluent_connector.a"));
+    }
+
+    #[test]
+    fn test_append_to_existing_rejects_duplicate_output() {
+        let existing = "output \"connector_id\" {\n  value = \"a\"\n}\n";
+
+        let result = TerraformGenerator::append_to_existing(existing, existing);
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Output 'connector_id'"));
+    }
+
     #[test]
     fn test_add_connector_specific_config_postgres() {
         let mut config_obj = Object::new();
@@ -789,7 +2735,10 @@ mod tests {
             display_name: "PostgreSQL CDC Source V2".to_string(),
             description: "PostgreSQL CDC Source V2 Connector".to_string(),
             connector_class: "io.debezium.connector.postgresql.PostgresConnector".to_string(),
+            aliases: vec![],
             connector_type: ConnectorType::Source,
+            family: ConnectorFamily::Other,
+            topic_strategy: TopicStrategy::TopicPrefix,
             required_configs: vec![],
             optional_configs: vec![],
             sensitive_configs: vec![],
@@ -801,6 +2750,7 @@ mod tests {
             topics: vec![],
             input_data_format: None,
             output_data_format: None,
+            ..Default::default()
         };
         let result = TerraformGenerator::add_connector_specific_config_to_object(
             &mut config_obj,
@@ -821,7 +2771,10 @@ mod tests {
             display_name: "MySQL CDC Source V2".to_string(),
             description: "MySQL CDC Source V2 Connector".to_string(),
             connector_class: "io.debezium.connector.mysql.MySqlConnector".to_string(),
+            aliases: vec![],
             connector_type: ConnectorType::Source,
+            family: ConnectorFamily::Other,
+            topic_strategy: TopicStrategy::TopicPrefix,
             required_configs: vec![],
             optional_configs: vec![],
             sensitive_configs: vec![],
@@ -833,6 +2786,7 @@ mod tests {
             topics: vec![],
             input_data_format: None,
             output_data_format: None,
+            ..Default::default()
         };
         let result = TerraformGenerator::add_connector_specific_config_to_object(
             &mut config_obj,
@@ -855,7 +2809,10 @@ mod tests {
             display_name: "S3 Sink".to_string(),
             description: "S3 Sink Connector".to_string(),
             connector_class: "io.confluent.connect.s3.S3SinkConnector".to_string(),
+            aliases: vec![],
             connector_type: ConnectorType::Sink,
+            family: ConnectorFamily::Other,
+            topic_strategy: TopicStrategy::TopicsList,
             required_configs: vec![],
             optional_configs: vec![],
             sensitive_configs: vec![],
@@ -867,6 +2824,7 @@ mod tests {
             topics: vec![],
             input_data_format: None,
             output_data_format: None,
+            ..Default::default()
         };
         let result = TerraformGenerator::add_connector_specific_config_to_object(
             &mut config_obj,
@@ -887,7 +2845,10 @@ mod tests {
             display_name: "Unknown Connector".to_string(),
             description: "Unknown Connector".to_string(),
             connector_class: "com.example.UnknownConnector".to_string(),
+            aliases: vec![],
             connector_type: ConnectorType::Source,
+            family: ConnectorFamily::Other,
+            topic_strategy: TopicStrategy::KafkaTopicSingle,
             required_configs: vec![],
             optional_configs: vec![],
             sensitive_configs: vec![],
@@ -899,6 +2860,7 @@ mod tests {
             topics: vec![],
             input_data_format: None,
             output_data_format: None,
+            ..Default::default()
         };
         let result = TerraformGenerator::add_connector_specific_config_to_object(
             &mut config_obj,
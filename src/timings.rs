@@ -0,0 +1,203 @@
+use crate::stats::millis;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::span;
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Time spent in one instrumented phase, such as `parse`, over a run
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PhaseTiming {
+    pub name: String,
+    pub calls: usize,
+    /// Time in the phase itself, leaving out the phases nested inside it
+    #[serde(rename = "millis", serialize_with = "millis")]
+    pub elapsed: Duration,
+    /// Time from entering the phase to leaving it, nested phases included
+    #[serde(rename = "total_millis", serialize_with = "millis")]
+    pub total: Duration,
+}
+
+/// Phase breakdown for `--timings` and `--profile-output`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TimingReport {
+    #[serde(rename = "total_millis", serialize_with = "millis")]
+    pub total: Duration,
+    /// Slowest first, by the time spent in the phase itself
+    pub phases: Vec<PhaseTiming>,
+}
+
+impl TimingReport {
+    /// Time outside every instrumented phase, e.g. prompts and terminal output
+    pub fn other(&self) -> Duration {
+        let phases: Duration = self.phases.iter().map(|phase| phase.elapsed).sum();
+        self.total.saturating_sub(phases)
+    }
+
+    /// Table of phases with their call counts and share of the run
+    pub fn render(&self) -> String {
+        let ms = |elapsed: Duration| elapsed.as_secs_f64() * 1000.0;
+        let share = |elapsed: Duration| {
+            if self.total.is_zero() {
+                0.0
+            } else {
+                elapsed.as_secs_f64() / self.total.as_secs_f64() * 100.0
+            }
+        };
+        let mut out = String::new();
+        let _ = writeln!(out, "⏱️  Timings ({:.1} ms total)", ms(self.total));
+        for phase in &self.phases {
+            let _ = writeln!(
+                out,
+                "  {:<14} {:>6} call(s) {:>10.1} ms {:>5.1}%",
+                phase.name,
+                phase.calls,
+                ms(phase.elapsed),
+                share(phase.elapsed)
+            );
+        }
+        let other = self.other();
+        let _ = writeln!(
+            out,
+            "  {:<14} {:>14} {:>10.1} ms {:>5.1}%",
+            "other",
+            "",
+            ms(other),
+            share(other)
+        );
+        out
+    }
+}
+
+/// When a span opened, and how long the phases nested in it took
+struct Open {
+    at: Instant,
+    nested: Duration,
+}
+
+/// Tracing layer that totals the time spent in this crate's spans by name.
+/// Clones share their totals, so one can be installed while another reports
+#[derive(Clone)]
+pub struct PhaseRecorder {
+    started: Instant,
+    phases: Arc<Mutex<BTreeMap<&'static str, PhaseTiming>>>,
+}
+
+impl Default for PhaseRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PhaseRecorder {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            phases: Arc::default(),
+        }
+    }
+
+    fn record(&self, name: &'static str, elapsed: Duration, total: Duration) {
+        let mut phases = self.phases.lock().unwrap();
+        let phase = phases.entry(name).or_insert_with(|| PhaseTiming {
+            name: name.to_string(),
+            ..Default::default()
+        });
+        phase.calls += 1;
+        phase.elapsed += elapsed;
+        phase.total += total;
+    }
+
+    /// The phases recorded so far, against the time since the recorder was created
+    pub fn report(&self) -> TimingReport {
+        let mut phases: Vec<PhaseTiming> = self.phases.lock().unwrap().values().cloned().collect();
+        phases.sort_by(|a, b| b.elapsed.cmp(&a.elapsed).then_with(|| a.name.cmp(&b.name)));
+        TimingReport {
+            total: self.started.elapsed(),
+            phases,
+        }
+    }
+}
+
+impl<S> Layer<S> for PhaseRecorder
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, _attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if span.metadata().target().starts_with("connect_util") {
+                span.extensions_mut().insert(Open {
+                    at: Instant::now(),
+                    nested: Duration::ZERO,
+                });
+            }
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(open) = span.extensions_mut().remove::<Open>() else {
+            return;
+        };
+        let total = open.at.elapsed();
+        if let Some(parent) = span.parent() {
+            if let Some(parent) = parent.extensions_mut().get_mut::<Open>() {
+                parent.nested += total;
+            }
+        }
+        self.record(span.name(), total.saturating_sub(open.nested), total);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_phase_recorder() {
+        let recorder = PhaseRecorder::new();
+        let subscriber = tracing_subscriber::registry().with(recorder.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            let _validate = tracing::debug_span!("validate").entered();
+            for _ in 0..3 {
+                let _parse = tracing::debug_span!("parse").entered();
+                std::thread::sleep(Duration::from_millis(2));
+            }
+        });
+
+        let report = recorder.report();
+        let names: Vec<(&str, usize)> = report
+            .phases
+            .iter()
+            .map(|phase| (phase.name.as_str(), phase.calls))
+            .collect();
+        assert_eq!(names, vec![("parse", 3), ("validate", 1)]);
+        // The nested parses count toward validate's total but not its own time
+        let (parse, validate) = (&report.phases[0], &report.phases[1]);
+        assert!(parse.elapsed >= Duration::from_millis(6));
+        assert!(validate.total >= parse.total);
+        assert!(validate.elapsed < parse.elapsed);
+        assert!(report.total >= validate.total);
+
+        let text = report.render();
+        assert!(text.contains("Timings"));
+        assert!(text
+            .lines()
+            .nth(1)
+            .unwrap()
+            .trim_start()
+            .starts_with("parse"));
+        assert!(text.contains("other"));
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["phases"][0]["name"], "parse");
+        assert_eq!(json["phases"][0]["calls"], 3);
+        assert!(json["phases"][1]["total_millis"].as_f64().unwrap() >= 6.0);
+    }
+}
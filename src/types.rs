@@ -1,5 +1,9 @@
+use crate::cluster::ClusterType;
+use crate::metadata::ConnectorMetadata;
+use crate::record_schema::RecordSchema;
+use crate::storage::Partitioner;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConnectorConfig {
@@ -9,51 +13,571 @@ pub struct ConnectorConfig {
     pub sensitive_config: HashMap<String, String>,
 }
 
-#[derive(Debug, Clone)]
+/// Whether a parsed config value is a Terraform expression (`${...}` or a `%{...}`
+/// directive) whose real value is only known at plan time
+pub fn is_dynamic_value(value: &str) -> bool {
+    value.contains("${") || value.contains("%{")
+}
+
+impl ConnectorConfig {
+    /// Keys from both config maps whose values are dynamic, sorted
+    pub fn dynamic_keys(&self) -> Vec<&str> {
+        let mut keys: Vec<&str> = self
+            .config
+            .iter()
+            .chain(self.sensitive_config.iter())
+            .filter(|(_, value)| is_dynamic_value(value))
+            .map(|(key, _)| key.as_str())
+            .collect();
+        keys.sort();
+        keys
+    }
+}
+
+/// A named Confluent Cloud environment profile from the `environments` section of
+/// `.connect-util.yaml`. Each ID is either a literal such as `env-abc123` or a
+/// Terraform reference such as `var.prod_environment_id`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Environment {
+    /// Key of the profile in the config file
+    #[serde(skip)]
     pub name: String,
     pub id: String,
     pub cluster_id: String,
+    /// Empty when the profile doesn't name a Schema Registry cluster
+    #[serde(default)]
     pub schema_registry_cluster_id: String,
+    /// Type of the profile's Kafka cluster; sets `kafka.deployment.type` and the
+    /// partition validation keys unless `--cluster-type` is given
+    #[serde(default)]
+    pub cluster_type: Option<ClusterType>,
+    /// `config_nonsensitive` values that differ in this environment, e.g. `tasks.max`
+    #[serde(default, deserialize_with = "crate::policy::scalar_map")]
+    pub overrides: BTreeMap<String, String>,
 }
 
 // CLI and Application Types
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct ConnectorOptions {
     pub name: Option<String>,
     pub output: Option<String>,
+    /// Connector plugin name; when set together with `name` generation runs without prompts
+    pub connector: Option<String>,
+    pub dry_run: bool,
+    /// Overwrite an existing output file without asking
+    pub force: bool,
+    /// Add the generated resource to an existing output file
+    pub append: bool,
+    /// Merge into the existing connector resource in `output`, keeping filled-in values
+    pub merge: bool,
+    pub layout: OutputLayout,
+    /// Target directory for the project layout
+    pub output_dir: Option<String>,
+    /// Line endings for written files; None keeps an existing file's, else the platform's
+    pub newline: Option<Newline>,
+    /// Environment profile whose IDs replace the generic environment/cluster variables
+    pub environment: Option<String>,
+    /// Environment profiles to generate the same connector for in one run
+    pub environments: Vec<String>,
+    pub environment_layout: EnvironmentLayout,
+    /// Kafka cluster type; None takes it from the environment profile, else Dedicated
+    pub cluster_type: Option<ClusterType>,
+    pub schema_registry: SchemaRegistryOptions,
+    /// Emit connector output blocks in single-file mode
+    pub with_outputs: bool,
+    /// Topics, or the single topic prefix for connectors with `topic.prefix`;
+    /// prompted for when generating interactively
+    pub topics: Vec<String>,
+    /// `input.data.format` for sinks; prompted for when generating interactively
+    pub input_format: Option<DataFormat>,
+    /// `output.data.format`; prompted for when generating interactively
+    pub output_format: Option<DataFormat>,
+    pub companions: CompanionResources,
+    /// Name of the existing connector for an `import` block to adopt
+    pub import_name: Option<String>,
+    /// `lifecycle.ignore_changes` entries replacing the policy's or the default list
+    pub ignore_changes: Vec<String>,
+    /// Template token values from `--token`, overriding the policy file's `tokens`
+    pub tokens: Vec<(String, String)>,
+    /// `table.include.list` patterns for CDC sources; prompted for when generating interactively
+    pub tables: Vec<String>,
+    /// `table.exclude.list` patterns for CDC sources
+    pub exclude_tables: Vec<String>,
+    /// Partitioner and object layout for object-store sinks; prompted for when
+    /// generating interactively
+    pub storage: StorageLayoutOptions,
+    /// Heartbeat and signal table settings for Debezium-based CDC sources
+    pub debezium: DebeziumOptions,
+    /// Record schema from `--schema-file` or `--schema-subject`, for JDBC sink suggestions
+    pub record_schema: Option<RecordSchema>,
+    /// Captured tables to scaffold ksqlDB streams for, written next to the Terraform
+    pub ksql_tables: Vec<String>,
+    /// Move placeholder secrets into variables set by a SOPS-encrypted tfvars file
+    pub sops: Option<SopsOptions>,
+    /// Where `config_sensitive` values come from; `sops` implies `Variables`
+    pub secrets_backend: SecretsBackend,
+    /// Where the connector runs
+    pub target: GenerateTarget,
+    /// Provider whose resource the connector is written as
+    pub provider: TerraformProvider,
+    /// Owner, on-call, and maintenance window comments for the connector resource
+    pub metadata: ConnectorMetadata,
+    /// Print where each generated config value came from
+    pub verbose: bool,
+    /// JSON report of the generated values and their sources
+    pub report: Option<String>,
+}
+
+/// Where a generated connector runs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GenerateTarget {
+    /// Confluent Cloud, or the cluster behind `provider`
+    #[default]
+    Cloud,
+    /// A Kafka Connect REST payload for a self-managed or Amazon MSK Connect cluster
+    SelfManaged,
+}
+
+impl std::str::FromStr for GenerateTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "cloud" => Ok(GenerateTarget::Cloud),
+            "self-managed" => Ok(GenerateTarget::SelfManaged),
+            _ => Err(format!(
+                "Invalid target '{}'. Use 'cloud' or 'self-managed'",
+                s
+            )),
+        }
+    }
+}
+
+/// The Terraform provider whose connector resource `generate` writes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TerraformProvider {
+    /// `confluent_connector`
+    #[default]
+    Confluent,
+    /// `kafka_connect_connector` from the community Kafka Connect provider
+    KafkaConnect,
+    /// `aiven_kafka_connector`
+    Aiven,
+    /// `kafka_connect_connector` against an Instaclustr Kafka Connect cluster's REST API
+    Instaclustr,
+}
+
+impl std::str::FromStr for TerraformProvider {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "confluent" => Ok(TerraformProvider::Confluent),
+            "kafka-connect" => Ok(TerraformProvider::KafkaConnect),
+            "aiven" => Ok(TerraformProvider::Aiven),
+            "instaclustr" => Ok(TerraformProvider::Instaclustr),
+            _ => Err(format!(
+                "Invalid provider '{}'. Use 'confluent', 'kafka-connect', 'aiven', or 'instaclustr'",
+                s
+            )),
+        }
+    }
+}
+
+/// How generated Terraform is split across files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputLayout {
+    /// One file containing the connector resource
+    #[default]
+    Single,
+    /// Conventional main.tf / variables.tf / outputs.tf split
+    Project,
+}
+
+impl std::str::FromStr for OutputLayout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "single" => Ok(OutputLayout::Single),
+            "project" => Ok(OutputLayout::Project),
+            _ => Err(format!("Invalid layout '{}'. Use 'single' or 'project'", s)),
+        }
+    }
+}
+
+/// How `--environments` output is split
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EnvironmentLayout {
+    /// The usual output once per environment, under a directory named after it
+    #[default]
+    Files,
+    /// One connector resource with `for_each` over a map of the environments
+    ForEach,
+}
+
+impl std::str::FromStr for EnvironmentLayout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "files" => Ok(EnvironmentLayout::Files),
+            "for-each" => Ok(EnvironmentLayout::ForEach),
+            _ => Err(format!(
+                "Invalid environment layout '{}'. Use 'files' or 'for-each'",
+                s
+            )),
+        }
+    }
+}
+
+/// Line endings written to generated files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Newline {
+    Lf,
+    Crlf,
+}
+
+impl std::str::FromStr for Newline {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "lf" => Ok(Newline::Lf),
+            "crlf" => Ok(Newline::Crlf),
+            _ => Err(format!("Invalid newline '{}'. Use 'lf' or 'crlf'", s)),
+        }
+    }
+}
+
+impl Newline {
+    /// CRLF on Windows, LF elsewhere
+    pub fn platform() -> Self {
+        if cfg!(windows) {
+            Newline::Crlf
+        } else {
+            Newline::Lf
+        }
+    }
+
+    /// The line ending `content` uses, going by its first line break
+    pub fn detect(content: &str) -> Option<Self> {
+        let end = content.find('\n')?;
+        if content[..end].ends_with('\r') {
+            Some(Newline::Crlf)
+        } else {
+            Some(Newline::Lf)
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Newline::Lf => "\n",
+            Newline::Crlf => "\r\n",
+        }
+    }
+
+    /// `content` with every line break, LF or CRLF, written as this newline
+    pub fn apply(&self, content: &str) -> String {
+        let normalized = content.replace("\r\n", "\n");
+        match self {
+            Newline::Lf => normalized,
+            Newline::Crlf => normalized.replace('\n', "\r\n"),
+        }
+    }
+}
+
+/// A rendered output file; a `path` of None means stdout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedFile {
+    pub path: Option<String>,
+    pub content: String,
+}
+
+/// Describes what writing generated output would do, reported by `--dry-run`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WritePlan {
+    /// Destination path, or None when output goes to stdout
+    pub path: Option<String>,
+    pub bytes: usize,
+    pub overwrite: bool,
+    pub append: bool,
+}
+
+impl WritePlan {
+    pub fn new(path: Option<&str>, content: &str) -> Self {
+        let overwrite = path
+            .map(|p| std::path::Path::new(p).exists())
+            .unwrap_or(false);
+        Self {
+            path: path.map(|p| p.to_string()),
+            bytes: content.len(),
+            overwrite,
+            append: false,
+        }
+    }
+}
+
+impl std::fmt::Display for WritePlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.path {
+            Some(path) if self.append => {
+                write!(f, "would write: {} ({} bytes, append)", path, self.bytes)
+            }
+            Some(path) => write!(
+                f,
+                "would write: {} ({} bytes, overwrite: {})",
+                path,
+                self.bytes,
+                if self.overwrite { "yes" } else { "no" }
+            ),
+            None => write!(f, "would write: stdout ({} bytes)", self.bytes),
+        }
+    }
 }
 
 // Terraform Types
-#[derive(Debug)]
+#[derive(Debug, Clone, Default)]
 pub struct TerraformConfigOptions {
     pub connector_name: String,
     pub connector: ConnectorDefinition,
     pub topics: Vec<String>,
+    /// `topic.prefix` for CDC-style sources, replacing the placeholder
+    pub topic_prefix: Option<String>,
+    /// `table.include.list` patterns; empty leaves the key out
+    pub tables: Vec<String>,
+    /// `table.exclude.list` patterns; empty leaves the key out
+    pub exclude_tables: Vec<String>,
+    pub storage: StorageLayoutOptions,
+    pub debezium: DebeziumOptions,
+    /// `pk.fields` and similar values suggested by the record schema
+    pub schema_values: BTreeMap<String, String>,
     pub input_data_format: Option<DataFormat>,
     pub output_data_format: Option<DataFormat>,
+    pub companions: CompanionResources,
+    /// Environment profile to target; None references `var.environment_id` and `var.kafka_cluster`
+    pub environment: Option<Environment>,
+    /// Environments for a single `for_each` resource; takes precedence over `environment`
+    pub for_each_environments: Vec<Environment>,
+    /// `--cluster-type`; None writes the Dedicated defaults
+    pub cluster_type: Option<ClusterType>,
+    pub schema_registry: SchemaRegistryOptions,
+    /// Name of a live connector to adopt with an `import` block
+    pub import_name: Option<String>,
+    /// `lifecycle.ignore_changes` entries; None uses the defaults
+    pub ignore_changes: Option<Vec<String>>,
+    /// Expanded policy templates, replacing the generated value of each key
+    pub template_values: BTreeMap<String, String>,
+    /// Where placeholder secrets are read from instead of being inlined
+    pub secrets_backend: SecretsBackend,
+}
+
+/// Where a generated connector's `config_sensitive` values come from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SecretsBackend {
+    /// Placeholder strings in the connector resource
+    #[default]
+    Inline,
+    /// `var.<connector>_<key>` variables, set by the SOPS secrets file
+    Variables,
+    /// `azurerm_key_vault_secret` data sources in the vault at `var.key_vault_id`
+    AzureKeyVault,
+    /// `google_secret_manager_secret_version` data sources for the latest version
+    GcpSecretManager,
+}
+
+impl std::str::FromStr for SecretsBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "inline" => Ok(SecretsBackend::Inline),
+            "azure-key-vault" => Ok(SecretsBackend::AzureKeyVault),
+            "gcp-secret-manager" => Ok(SecretsBackend::GcpSecretManager),
+            _ => Err(format!(
+                "Invalid secrets backend '{}'. Use 'inline', 'azure-key-vault', or 'gcp-secret-manager'",
+                s
+            )),
+        }
+    }
+}
+
+/// Optional Schema Registry settings for generated connectors
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaRegistryOptions {
+    /// Value for `schema.context.name`
+    pub context: Option<String>,
+    /// Enable client-side field level encryption with the credentials the auth mode needs
+    pub csfle: bool,
+}
+
+/// Partitioner and object layout for object-store sinks
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StorageLayoutOptions {
+    /// None keeps the connector's default layout
+    pub partitioner: Option<Partitioner>,
+    /// `partition.field.name` entries for the field partitioner
+    pub partition_fields: Vec<String>,
+    /// `path.format` for time-based partitioners; None builds one from the interval
+    pub path_format: Option<String>,
+    /// `locale` for time-based partitioners; None uses `en-US`
+    pub locale: Option<String>,
+    /// `timezone` for time-based partitioners; None uses `UTC`
+    pub timezone: Option<String>,
+}
+
+impl StorageLayoutOptions {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Heartbeats and the signal table for Debezium-based CDC sources
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DebeziumOptions {
+    /// `heartbeat.interval.ms`; None leaves heartbeats off unless a heartbeat table is set
+    pub heartbeat_interval_ms: Option<u64>,
+    /// Table `heartbeat.action.query` updates on every heartbeat
+    pub heartbeat_table: Option<String>,
+    /// `signal.data.collection`, for triggering incremental snapshots
+    pub signal_table: Option<String>,
+    /// `incremental.snapshot.chunk.size`; needs a signal table
+    pub snapshot_chunk_size: Option<u32>,
+}
+
+impl DebeziumOptions {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Keys for `sops --encrypt`. With neither set, the creation rules in `.sops.yaml`
+/// pick the keys
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SopsOptions {
+    /// age recipients (`age1...`)
+    pub age: Vec<String>,
+    /// AWS KMS key ARNs
+    pub kms: Vec<String>,
+}
+
+/// Supporting resources generated alongside the connector in the same body
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompanionResources {
+    /// A `confluent_kafka_topic` per configured topic
+    pub topics: bool,
+    /// A `confluent_service_account` the connector runs as
+    pub service_account: bool,
+    /// A `confluent_api_key` owned by the service account, switching auth to KAFKA_API_KEY
+    pub api_key: bool,
 }
 
 // Connector Definition Types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ConnectorDefinition {
     pub name: String,
     pub display_name: String,
     pub connector_class: String,
+    /// Other `connector.class` values that resolve to this connector, such as the
+    /// self-managed Java class
+    #[serde(default)]
+    pub aliases: Vec<String>,
     pub connector_type: ConnectorType,
+    #[serde(default)]
+    pub family: ConnectorFamily,
+    /// Which key names the topics the connector reads or writes
+    #[serde(default)]
+    pub topic_strategy: TopicStrategy,
     pub description: String,
     pub required_configs: Vec<ConfigField>,
     pub optional_configs: Vec<ConfigField>,
     pub sensitive_configs: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub enum ConnectorType {
+    #[default]
     Source,
     Sink,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+/// What a connector connects to, used to group the connector picker
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum ConnectorFamily {
+    Databases,
+    ObjectStorage,
+    Saas,
+    Messaging,
+    #[default]
+    Other,
+}
+
+impl ConnectorFamily {
+    /// All families in display order
+    pub const ALL: [ConnectorFamily; 5] = [
+        ConnectorFamily::Databases,
+        ConnectorFamily::ObjectStorage,
+        ConnectorFamily::Saas,
+        ConnectorFamily::Messaging,
+        ConnectorFamily::Other,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ConnectorFamily::Databases => "Databases",
+            ConnectorFamily::ObjectStorage => "Object storage",
+            ConnectorFamily::Saas => "SaaS",
+            ConnectorFamily::Messaging => "Messaging",
+            ConnectorFamily::Other => "Other",
+        }
+    }
+}
+
+/// How a connector's config names its topics
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum TopicStrategy {
+    /// `topics`: the comma-separated topics a sink consumes
+    #[default]
+    TopicsList,
+    /// `topic.prefix`: a source writing one topic per table or collection under the prefix
+    TopicPrefix,
+    /// `kafka.topic`: a source writing every record to one topic
+    KafkaTopicSingle,
+}
+
+impl TopicStrategy {
+    pub const ALL: [TopicStrategy; 3] = [
+        TopicStrategy::TopicsList,
+        TopicStrategy::TopicPrefix,
+        TopicStrategy::KafkaTopicSingle,
+    ];
+
+    /// The `config_nonsensitive` key holding the topics
+    pub fn key(&self) -> &'static str {
+        match self {
+            TopicStrategy::TopicsList => "topics",
+            TopicStrategy::TopicPrefix => "topic.prefix",
+            TopicStrategy::KafkaTopicSingle => "kafka.topic",
+        }
+    }
+
+    /// The strategy a definition without one most likely uses: the topic key it
+    /// declares, otherwise `topics` for sinks and `kafka.topic` for sources
+    pub fn infer(definition: &ConnectorDefinition) -> Self {
+        if definition.has_field("topic.prefix") {
+            TopicStrategy::TopicPrefix
+        } else if definition.has_field("kafka.topic")
+            || definition.connector_type == ConnectorType::Source
+        {
+            TopicStrategy::KafkaTopicSingle
+        } else {
+            TopicStrategy::TopicsList
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum DataFormat {
     Avro,
     Json,
@@ -63,6 +587,14 @@ pub enum DataFormat {
 }
 
 impl DataFormat {
+    pub const ALL: [DataFormat; 5] = [
+        DataFormat::Avro,
+        DataFormat::Json,
+        DataFormat::JsonSr,
+        DataFormat::Protobuf,
+        DataFormat::Parquet,
+    ];
+
     pub fn to_terraform_value(&self) -> &'static str {
         match self {
             DataFormat::Avro => "AVRO",
@@ -74,6 +606,23 @@ impl DataFormat {
     }
 }
 
+impl std::str::FromStr for DataFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.to_uppercase().replace('-', "_");
+        DataFormat::ALL
+            .into_iter()
+            .find(|format| format.to_terraform_value() == normalized)
+            .ok_or_else(|| {
+                format!(
+                    "Invalid data format '{}'. Use one of: AVRO, JSON, JSON_SR, PROTOBUF, PARQUET",
+                    s
+                )
+            })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConfigField {
     pub name: String,
@@ -83,6 +632,38 @@ pub struct ConfigField {
     pub required: bool,
     pub default_value: Option<String>,
     pub valid_values: Option<Vec<String>>,
+    #[serde(default)]
+    pub group: ConfigGroup,
+}
+
+/// Logical grouping of configuration fields, used to organize prompts and
+/// `describe` output into sections instead of one long list
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum ConfigGroup {
+    Connection,
+    Authentication,
+    Behavior,
+    #[default]
+    Advanced,
+}
+
+impl ConfigGroup {
+    /// All groups in display order
+    pub const ALL: [ConfigGroup; 4] = [
+        ConfigGroup::Connection,
+        ConfigGroup::Authentication,
+        ConfigGroup::Behavior,
+        ConfigGroup::Advanced,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ConfigGroup::Connection => "Connection",
+            ConfigGroup::Authentication => "Authentication",
+            ConfigGroup::Behavior => "Behavior",
+            ConfigGroup::Advanced => "Advanced",
+        }
+    }
 }
 
 #[cfg(test)]
@@ -125,6 +706,8 @@ mod tests {
             id: "env-123".to_string(),
             cluster_id: "cluster-456".to_string(),
             schema_registry_cluster_id: "sr-789".to_string(),
+            cluster_type: None,
+            overrides: Default::default(),
         };
 
         assert_eq!(environment.name, "test-env");
@@ -170,6 +753,7 @@ mod tests {
         let options = ConnectorOptions {
             name: Some("test-connector".to_string()),
             output: Some("output.tf".to_string()),
+            ..Default::default()
         };
 
         assert_eq!(options.name, Some("test-connector".to_string()));
@@ -181,6 +765,75 @@ mod tests {
         let options = ConnectorOptions::default();
         assert_eq!(options.name, None);
         assert_eq!(options.output, None);
+        assert_eq!(options.connector, None);
+        assert!(!options.dry_run);
+    }
+
+    #[test]
+    fn test_output_layout_from_str() {
+        assert_eq!("single".parse::<OutputLayout>(), Ok(OutputLayout::Single));
+        assert_eq!("Project".parse::<OutputLayout>(), Ok(OutputLayout::Project));
+        assert!("modules".parse::<OutputLayout>().is_err());
+        assert_eq!(OutputLayout::default(), OutputLayout::Single);
+    }
+
+    #[test]
+    fn test_newline() {
+        assert_eq!("CRLF".parse::<Newline>(), Ok(Newline::Crlf));
+        assert!("cr".parse::<Newline>().is_err());
+        assert_eq!(Newline::detect("a = 1\r\nb = 2\n"), Some(Newline::Crlf));
+        assert_eq!(Newline::detect("a = 1\n"), Some(Newline::Lf));
+        assert_eq!(Newline::detect("a = 1"), None);
+
+        // Mixed endings come out uniform, and applying twice changes nothing
+        let crlf = Newline::Crlf.apply("a = 1\nb = 2\r\n");
+        assert_eq!(crlf, "a = 1\r\nb = 2\r\n");
+        assert_eq!(Newline::Crlf.apply(&crlf), crlf);
+        assert_eq!(Newline::Lf.apply(&crlf), "a = 1\nb = 2\n");
+        assert_eq!(
+            Newline::platform(),
+            if cfg!(windows) {
+                Newline::Crlf
+            } else {
+                Newline::Lf
+            }
+        );
+    }
+
+    #[test]
+    fn test_write_plan_new_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("new.tf");
+        let plan = WritePlan::new(Some(path.to_str().unwrap()), "abc");
+
+        assert_eq!(plan.bytes, 3);
+        assert!(!plan.overwrite);
+        assert!(plan.to_string().contains("(3 bytes, overwrite: no)"));
+    }
+
+    #[test]
+    fn test_write_plan_existing_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("existing.tf");
+        std::fs::write(&path, "old").unwrap();
+        let plan = WritePlan::new(Some(path.to_str().unwrap()), "abcdef");
+
+        assert!(plan.overwrite);
+        assert!(plan.to_string().contains("(6 bytes, overwrite: yes)"));
+    }
+
+    #[test]
+    fn test_write_plan_append() {
+        let mut plan = WritePlan::new(Some("main.tf"), "abc");
+        plan.append = true;
+        assert_eq!(plan.to_string(), "would write: main.tf (3 bytes, append)");
+    }
+
+    #[test]
+    fn test_write_plan_stdout() {
+        let plan = WritePlan::new(None, "abc");
+        assert_eq!(plan.path, None);
+        assert_eq!(plan.to_string(), "would write: stdout (3 bytes)");
     }
 
     #[test]
@@ -218,6 +871,7 @@ mod tests {
             required: true,
             default_value: None,
             valid_values: None,
+            group: ConfigGroup::Connection,
         };
 
         assert_eq!(config_field.name, "database.url");
@@ -227,6 +881,32 @@ mod tests {
         assert!(config_field.required);
         assert_eq!(config_field.default_value, None);
         assert_eq!(config_field.valid_values, None);
+        assert_eq!(config_field.group, ConfigGroup::Connection);
+    }
+
+    #[test]
+    fn test_config_group_order_and_names() {
+        let names: Vec<&str> = ConfigGroup::ALL.iter().map(|g| g.display_name()).collect();
+        assert_eq!(
+            names,
+            vec!["Connection", "Authentication", "Behavior", "Advanced"]
+        );
+        assert_eq!(ConfigGroup::default(), ConfigGroup::Advanced);
+    }
+
+    #[test]
+    fn test_config_field_deserialize_without_group() {
+        let json = r#"{
+            "name": "database.url",
+            "display_name": "Database URL",
+            "description": "JDBC URL for the database",
+            "field_type": "STRING",
+            "required": true,
+            "default_value": null,
+            "valid_values": null
+        }"#;
+        let field: ConfigField = serde_json::from_str(json).unwrap();
+        assert_eq!(field.group, ConfigGroup::Advanced);
     }
 
     #[test]
@@ -239,13 +919,17 @@ mod tests {
             required: true,
             default_value: None,
             valid_values: None,
+            group: ConfigGroup::Connection,
         };
 
         let connector_def = ConnectorDefinition {
             name: "PostgresSink".to_string(),
             display_name: "PostgreSQL Sink".to_string(),
             connector_class: "io.confluent.connect.jdbc.JdbcSinkConnector".to_string(),
+            aliases: vec![],
             connector_type: ConnectorType::Sink,
+            family: ConnectorFamily::Other,
+            topic_strategy: TopicStrategy::TopicsList,
             description: "PostgreSQL Sink Connector".to_string(),
             required_configs: vec![config_field.clone()],
             optional_configs: vec![],
@@ -275,13 +959,17 @@ mod tests {
             required: true,
             default_value: None,
             valid_values: None,
+            group: ConfigGroup::Connection,
         };
 
         let connector_def = ConnectorDefinition {
             name: "PostgresSink".to_string(),
             display_name: "PostgreSQL Sink".to_string(),
             connector_class: "io.confluent.connect.jdbc.JdbcSinkConnector".to_string(),
+            aliases: vec![],
             connector_type: ConnectorType::Sink,
+            family: ConnectorFamily::Other,
+            topic_strategy: TopicStrategy::TopicsList,
             description: "PostgreSQL Sink Connector".to_string(),
             required_configs: vec![config_field],
             optional_configs: vec![],
@@ -294,9 +982,35 @@ mod tests {
             topics: vec!["test-topic".to_string()],
             input_data_format: None,
             output_data_format: None,
+            ..Default::default()
         };
 
         assert_eq!(terraform_options.connector_name, "test-connector");
         assert_eq!(terraform_options.topics.len(), 1);
     }
+
+    #[test]
+    fn test_dynamic_keys() {
+        let config = ConnectorConfig {
+            name: "orders".to_string(),
+            connector_class: "DatagenSource".to_string(),
+            config: HashMap::from([
+                ("kafka.topic".to_string(), "orders".to_string()),
+                ("tasks.max".to_string(), "${var.tasks}".to_string()),
+                (
+                    "output.data.format".to_string(),
+                    "%{ if var.avro }AVRO%{ else }JSON%{ endif }".to_string(),
+                ),
+            ]),
+            sensitive_config: HashMap::from([(
+                "kafka.api.secret".to_string(),
+                "${var.kafka_api_secret}".to_string(),
+            )]),
+        };
+        assert_eq!(
+            config.dynamic_keys(),
+            vec!["kafka.api.secret", "output.data.format", "tasks.max"]
+        );
+        assert!(!is_dynamic_value("orders"));
+    }
 }
@@ -0,0 +1,491 @@
+use crate::error::ConnectUtilError;
+use crate::parser::{parse_body, SourceFile};
+use hcl::{Block, Body, Expression};
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Where a `var.*` reference appears inside a connector block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceLocation {
+    ConfigSensitive,
+    ConfigNonsensitive,
+    Other,
+}
+
+/// A `variable` block declared in the module
+#[derive(Debug, Clone)]
+pub struct VariableDeclaration {
+    pub name: String,
+    pub file: PathBuf,
+    pub sensitive: bool,
+}
+
+/// A `var.*` reference made by a connector resource or module block
+#[derive(Debug, Clone)]
+pub struct VariableReference {
+    pub name: String,
+    /// Address of the referencing block, e.g. `confluent_connector.orders`
+    pub address: String,
+    pub file: PathBuf,
+    pub location: ReferenceLocation,
+    /// First attribute read from the variable, e.g. `id` in `var.kafka_cluster.id`
+    pub attribute: Option<String>,
+}
+
+/// An undeclared variable with the shape inferred from how connectors use it
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingVariable {
+    pub name: String,
+    /// Set when any connector routes the variable into `config_sensitive`
+    pub sensitive: bool,
+    /// Attributes read from the variable; empty means a plain string
+    pub attributes: BTreeSet<String>,
+    /// Connector blocks that reference it
+    pub addresses: BTreeSet<String>,
+}
+
+/// A problem with how connector blocks use the module's variables
+#[derive(Debug, Clone, PartialEq)]
+pub enum VariableFinding {
+    Undeclared {
+        name: String,
+        address: String,
+        file: PathBuf,
+    },
+    SensitiveNotRouted {
+        name: String,
+        address: String,
+        file: PathBuf,
+    },
+    Unused {
+        name: String,
+        file: PathBuf,
+    },
+}
+
+impl VariableFinding {
+    /// Unused declarations are warnings; everything else fails at plan or leaks secrets
+    pub fn is_error(&self) -> bool {
+        !matches!(self, VariableFinding::Unused { .. })
+    }
+}
+
+impl fmt::Display for VariableFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VariableFinding::Undeclared {
+                name,
+                address,
+                file,
+            } => write!(
+                f,
+                "var.{} is referenced by {} ({}) but never declared",
+                name,
+                address,
+                file.display()
+            ),
+            VariableFinding::SensitiveNotRouted {
+                name,
+                address,
+                file,
+            } => write!(
+                f,
+                "var.{} is declared sensitive but {} ({}) uses it outside config_sensitive",
+                name,
+                address,
+                file.display()
+            ),
+            VariableFinding::Unused { name, file } => write!(
+                f,
+                "var.{} is declared in {} but never referenced",
+                name,
+                file.display()
+            ),
+        }
+    }
+}
+
+/// Variable declarations and references collected from one Terraform module
+#[derive(Debug, Default)]
+pub struct ModuleVariables {
+    pub declarations: BTreeMap<String, VariableDeclaration>,
+    pub references: Vec<VariableReference>,
+    /// Every variable referenced anywhere in the module, connector blocks or not
+    pub referenced: BTreeSet<String>,
+}
+
+fn reference_pattern() -> Regex {
+    Regex::new(r"(?:^|[^\w.])var\.([A-Za-z_][\w-]*)(?:\.([A-Za-z_][\w-]*))?")
+        .expect("valid variable reference pattern")
+}
+
+/// Variable names and the first attribute read from each, in order of appearance
+fn references_in(pattern: &Regex, rendered: &str) -> Vec<(String, Option<String>)> {
+    pattern
+        .captures_iter(rendered)
+        .map(|captures| {
+            (
+                captures[1].to_string(),
+                captures.get(2).map(|m| m.as_str().to_string()),
+            )
+        })
+        .collect()
+}
+
+fn referenced_names(pattern: &Regex, rendered: &str) -> BTreeSet<String> {
+    references_in(pattern, rendered)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect()
+}
+
+fn render<T: hcl::format::Format>(value: &T) -> String {
+    hcl::format::to_string(value).unwrap_or_default()
+}
+
+/// Returns the address of blocks that carry connector configuration
+fn connector_address(block: &Block) -> Option<String> {
+    let labels = block.labels();
+    match block.identifier() {
+        "resource" if labels.len() >= 2 && labels[0].as_str() == "confluent_connector" => {
+            Some(format!("confluent_connector.{}", labels[1].as_str()))
+        }
+        "module"
+            if !labels.is_empty()
+                && block
+                    .body()
+                    .attributes()
+                    .any(|a| a.key() == "config_nonsensitive") =>
+        {
+            Some(format!("module.{}", labels[0].as_str()))
+        }
+        _ => None,
+    }
+}
+
+impl ModuleVariables {
+    /// Parses every `.tf` file of a module and records its variables
+    pub fn analyze(files: &[(PathBuf, String)]) -> Result<Self, ConnectUtilError> {
+        let pattern = reference_pattern();
+        let mut module = Self::default();
+        for (file, content) in files {
+            module.record(&pattern, file, &parse_body(content))?;
+        }
+        Ok(module)
+    }
+
+    /// `analyze` over files that have already been parsed
+    pub fn analyze_parsed(files: &[SourceFile]) -> Result<Self, ConnectUtilError> {
+        let _span = tracing::debug_span!("variables").entered();
+        let pattern = reference_pattern();
+        let mut module = Self::default();
+        for file in files {
+            module.record(&pattern, &file.path, &file.body)?;
+        }
+        Ok(module)
+    }
+
+    /// Records the declarations and references of one file
+    fn record(
+        &mut self,
+        pattern: &Regex,
+        file: &Path,
+        body: &hcl::Result<Body>,
+    ) -> Result<(), ConnectUtilError> {
+        let body = body.as_ref().map_err(|e| {
+            ConnectUtilError::Config(format!("Failed to parse {}: {}", file.display(), e))
+        })?;
+
+        for attribute in body.attributes() {
+            self.referenced
+                .extend(referenced_names(pattern, &render(attribute.expr())));
+        }
+
+        for block in body.blocks() {
+            if block.identifier() == "variable" {
+                if let Some(label) = block.labels().first() {
+                    let sensitive = block.body().attributes().any(|a| {
+                        a.key() == "sensitive" && matches!(a.expr(), Expression::Bool(true))
+                    });
+                    self.declarations.insert(
+                        label.as_str().to_string(),
+                        VariableDeclaration {
+                            name: label.as_str().to_string(),
+                            file: file.to_path_buf(),
+                            sensitive,
+                        },
+                    );
+                }
+                continue;
+            }
+
+            self.referenced
+                .extend(referenced_names(pattern, &render(block.body())));
+
+            let Some(address) = connector_address(block) else {
+                continue;
+            };
+            let mut record = |rendered: &str, location: ReferenceLocation| {
+                for (name, attribute) in references_in(pattern, rendered) {
+                    self.references.push(VariableReference {
+                        name,
+                        address: address.clone(),
+                        file: file.to_path_buf(),
+                        location,
+                        attribute,
+                    });
+                }
+            };
+            for attribute in block.body().attributes() {
+                let location = match attribute.key() {
+                    "config_sensitive" => ReferenceLocation::ConfigSensitive,
+                    "config_nonsensitive" => ReferenceLocation::ConfigNonsensitive,
+                    _ => ReferenceLocation::Other,
+                };
+                record(&render(attribute.expr()), location);
+            }
+            for nested in block.body().blocks() {
+                record(&render(nested), ReferenceLocation::Other);
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks references against declarations
+    pub fn findings(&self) -> Vec<VariableFinding> {
+        let mut findings = Vec::new();
+        let mut reported = BTreeSet::new();
+
+        for reference in &self.references {
+            let key = (reference.name.clone(), reference.address.clone());
+            match self.declarations.get(&reference.name) {
+                None => {
+                    if reported.insert(key) {
+                        findings.push(VariableFinding::Undeclared {
+                            name: reference.name.clone(),
+                            address: reference.address.clone(),
+                            file: reference.file.clone(),
+                        });
+                    }
+                }
+                Some(declaration)
+                    if declaration.sensitive
+                        && reference.location == ReferenceLocation::ConfigNonsensitive =>
+                {
+                    if reported.insert(key) {
+                        findings.push(VariableFinding::SensitiveNotRouted {
+                            name: reference.name.clone(),
+                            address: reference.address.clone(),
+                            file: reference.file.clone(),
+                        });
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+
+        for declaration in self.declarations.values() {
+            if !self.referenced.contains(&declaration.name) {
+                findings.push(VariableFinding::Unused {
+                    name: declaration.name.clone(),
+                    file: declaration.file.clone(),
+                });
+            }
+        }
+        findings
+    }
+}
+
+impl ModuleVariables {
+    /// Undeclared variables referenced by connector blocks, sorted by name
+    pub fn missing_declarations(&self) -> Vec<MissingVariable> {
+        let mut missing: BTreeMap<String, MissingVariable> = BTreeMap::new();
+        for reference in &self.references {
+            if self.declarations.contains_key(&reference.name) {
+                continue;
+            }
+            let variable =
+                missing
+                    .entry(reference.name.clone())
+                    .or_insert_with(|| MissingVariable {
+                        name: reference.name.clone(),
+                        sensitive: false,
+                        attributes: BTreeSet::new(),
+                        addresses: BTreeSet::new(),
+                    });
+            variable.sensitive |= reference.location == ReferenceLocation::ConfigSensitive;
+            variable.attributes.extend(reference.attribute.clone());
+            variable.addresses.insert(reference.address.clone());
+        }
+        missing.into_values().collect()
+    }
+}
+
+/// Groups `.tf` files by directory, since each directory is its own Terraform module
+pub fn group_by_module(files: &[PathBuf]) -> BTreeMap<PathBuf, Vec<PathBuf>> {
+    let mut modules: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+    for file in files {
+        if file.extension().is_some_and(|e| e == "tf") {
+            let dir = file.parent().unwrap_or(Path::new(".")).to_path_buf();
+            modules.entry(dir).or_default().push(file.clone());
+        }
+    }
+    modules
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONNECTORS: &str = r#"
+resource "confluent_connector" "orders" {
+  environment {
+    id = var.environment_id
+  }
+  config_sensitive = {
+    "database.password" = var.database_password
+  }
+  config_nonsensitive = {
+    "connector.class"   = "PostgresCdcSourceV2"
+    "database.hostname" = var.database_hostname
+    "database.user"     = "${var.database_user}-svc"
+    "kafka.api.key"     = var.kafka_api_key
+  }
+}
+"#;
+
+    const VARIABLES: &str = r#"
+variable "environment_id" {}
+variable "database_password" {
+  sensitive = true
+}
+variable "database_hostname" {}
+variable "kafka_api_key" {
+  sensitive = true
+}
+variable "legacy_setting" {}
+variable "region" {}
+
+locals {
+  azure_region = var.region
+}
+"#;
+
+    fn analyze() -> ModuleVariables {
+        ModuleVariables::analyze(&[
+            (PathBuf::from("main.tf"), CONNECTORS.to_string()),
+            (PathBuf::from("variables.tf"), VARIABLES.to_string()),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_analyze_collects_declarations_and_references() {
+        let module = analyze();
+        assert_eq!(module.declarations.len(), 6);
+        assert!(module.declarations["database_password"].sensitive);
+        assert!(!module.declarations["database_hostname"].sensitive);
+
+        let password = module
+            .references
+            .iter()
+            .find(|r| r.name == "database_password")
+            .unwrap();
+        assert_eq!(password.location, ReferenceLocation::ConfigSensitive);
+        assert_eq!(password.address, "confluent_connector.orders");
+        let environment = module
+            .references
+            .iter()
+            .find(|r| r.name == "environment_id")
+            .unwrap();
+        assert_eq!(environment.location, ReferenceLocation::Other);
+        assert!(module.references.iter().any(|r| r.name == "database_user"));
+    }
+
+    #[test]
+    fn test_findings() {
+        let findings = analyze().findings();
+        assert_eq!(
+            findings,
+            vec![
+                VariableFinding::Undeclared {
+                    name: "database_user".to_string(),
+                    address: "confluent_connector.orders".to_string(),
+                    file: PathBuf::from("main.tf"),
+                },
+                VariableFinding::SensitiveNotRouted {
+                    name: "kafka_api_key".to_string(),
+                    address: "confluent_connector.orders".to_string(),
+                    file: PathBuf::from("main.tf"),
+                },
+                VariableFinding::Unused {
+                    name: "legacy_setting".to_string(),
+                    file: PathBuf::from("variables.tf"),
+                },
+            ]
+        );
+        assert!(findings[0].is_error());
+        assert!(!findings[2].is_error());
+    }
+
+    #[test]
+    fn test_missing_declarations() {
+        let module = ModuleVariables::analyze(&[(
+            PathBuf::from("main.tf"),
+            r#"
+resource "confluent_connector" "orders" {
+  kafka_cluster {
+    id = var.kafka_cluster.id
+  }
+  config_sensitive = {
+    "database.password" = var.database_password
+  }
+  config_nonsensitive = {
+    "database.hostname" = var.database_hostname
+  }
+}
+resource "confluent_connector" "payments" {
+  config_nonsensitive = {
+    "database.hostname" = var.database_hostname
+  }
+}
+variable "declared" {}
+"#
+            .to_string(),
+        )])
+        .unwrap();
+
+        let missing = module.missing_declarations();
+        let names: Vec<&str> = missing.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["database_hostname", "database_password", "kafka_cluster"]
+        );
+        assert!(!missing[0].sensitive);
+        assert_eq!(missing[0].addresses.len(), 2);
+        assert!(missing[1].sensitive);
+        assert!(missing[1].attributes.is_empty());
+        assert_eq!(missing[2].attributes, BTreeSet::from(["id".to_string()]));
+    }
+
+    #[test]
+    fn test_reference_pattern_ignores_nested_traversals() {
+        let pattern = reference_pattern();
+        let names = referenced_names(&pattern, "local.var.x + my_var.y + var.z");
+        assert_eq!(names, BTreeSet::from(["z".to_string()]));
+    }
+
+    #[test]
+    fn test_group_by_module() {
+        let modules = group_by_module(&[
+            PathBuf::from("infra/main.tf"),
+            PathBuf::from("infra/variables.tf"),
+            PathBuf::from("infra/prod.tfvars"),
+            PathBuf::from("infra/staging/main.tf"),
+        ]);
+        assert_eq!(modules.len(), 2);
+        assert_eq!(modules[&PathBuf::from("infra")].len(), 2);
+    }
+}
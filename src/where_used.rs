@@ -0,0 +1,360 @@
+use crate::conflicts::{covers, literal, ModuleConnector};
+use crate::error::ConnectUtilError;
+use crate::order::{topic_names, TopicResource};
+use crate::types::is_dynamic_value;
+use regex::Regex;
+use serde::Serialize;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Keys naming the topics a connector reads or writes
+const TOPIC_KEYS: &[&str] = &["topics", "kafka.topic", "topic.prefix", "topics.regex"];
+
+/// Output format for `where-used`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UsageFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for UsageFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(UsageFormat::Text),
+            "json" => Ok(UsageFormat::Json),
+            _ => Err(format!(
+                "Invalid where-used format '{}'. Use 'text' or 'json'",
+                s
+            )),
+        }
+    }
+}
+
+/// What `where-used` looks for; either or both may be set
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageQuery {
+    pub topic: Option<String>,
+    /// A config key such as `database.password`
+    pub secret_key: Option<String>,
+}
+
+/// A connector that references the topic or secret key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Usage {
+    pub file: PathBuf,
+    /// Terraform label, including the instance key for `for_each`/`count` blocks
+    pub resource: String,
+    pub name: String,
+    /// The config key it's referenced under, e.g. `topic.prefix` or
+    /// `config_sensitive.database.password`
+    pub key: String,
+    /// The configured value; literal secrets show as `(hardcoded)` or `(placeholder)`
+    pub value: String,
+}
+
+/// The connectors referencing each part of a query
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct UsageReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret_key: Option<String>,
+    pub topic_usages: Vec<Usage>,
+    pub secret_usages: Vec<Usage>,
+    /// Connectors whose topics come from variables or expressions, so whether they
+    /// use the topic can't be told from the files
+    pub unresolved: Vec<Usage>,
+}
+
+fn usage(connector: &ModuleConnector, key: &str, value: &str) -> Usage {
+    Usage {
+        file: connector.file.clone(),
+        resource: connector.config.name.clone(),
+        name: connector
+            .config
+            .config
+            .get("name")
+            .cloned()
+            .unwrap_or_else(|| connector.config.name.clone()),
+        key: key.to_string(),
+        value: value.to_string(),
+    }
+}
+
+/// Whether one entry of a topic key's value names `topic`, or None when it comes
+/// from a variable or expression
+fn entry_names(key: &str, entry: &str, topic: &str, topics: &[TopicResource]) -> Option<bool> {
+    if let Some(entry) = literal(entry) {
+        return match key {
+            "topic.prefix" => Some(covers(entry, topic)),
+            "topics.regex" => Regex::new(&format!("^(?:{})$", entry))
+                .ok()
+                .map(|pattern| pattern.is_match(topic)),
+            _ => Some(entry == topic),
+        };
+    }
+    let names = topic_names(entry, topics);
+    (!names.is_empty()).then(|| names.contains(topic))
+}
+
+/// Finds the connectors that read or write `query.topic` and those that set
+/// `query.secret_key`, in either config map
+pub fn find_usages(
+    connectors: &[ModuleConnector],
+    topics: &[TopicResource],
+    query: &UsageQuery,
+) -> UsageReport {
+    let mut report = UsageReport {
+        topic: query.topic.clone(),
+        secret_key: query.secret_key.clone(),
+        ..Default::default()
+    };
+    for connector in connectors {
+        if let Some(topic) = &query.topic {
+            let mut unresolved = None;
+            let mut used = false;
+            for key in TOPIC_KEYS {
+                let Some(value) = connector.config.config.get(*key) else {
+                    continue;
+                };
+                let entries: Vec<&str> = match *key {
+                    "topics" => value.split(',').collect(),
+                    _ => vec![value.as_str()],
+                };
+                let names: Vec<Option<bool>> = entries
+                    .iter()
+                    .map(|entry| entry_names(key, entry, topic, topics))
+                    .collect();
+                if names.contains(&Some(true)) {
+                    report.topic_usages.push(usage(connector, key, value));
+                    used = true;
+                } else if names.contains(&None) && unresolved.is_none() {
+                    unresolved = Some(usage(connector, key, value));
+                }
+            }
+            if let (false, Some(unresolved)) = (used, unresolved) {
+                report.unresolved.push(unresolved);
+            }
+        }
+        if let Some(secret_key) = &query.secret_key {
+            let maps = [
+                ("config_sensitive", &connector.config.sensitive_config),
+                ("config_nonsensitive", &connector.config.config),
+            ];
+            for (block, map) in maps {
+                if let Some(value) = map.get(secret_key) {
+                    let value = if is_dynamic_value(value) || value.starts_with("var.") {
+                        value.as_str()
+                    } else if value.contains("<REPLACE_WITH_") {
+                        "(placeholder)"
+                    } else {
+                        "(hardcoded)"
+                    };
+                    report.secret_usages.push(usage(
+                        connector,
+                        &format!("{}.{}", block, secret_key),
+                        value,
+                    ));
+                }
+            }
+        }
+    }
+    report
+}
+
+fn render_usages_text(out: &mut String, usages: &[Usage], joiner: &str) {
+    for usage in usages {
+        let _ = writeln!(
+            out,
+            "  {}: {} ({}) {} {} = {}",
+            usage.file.display(),
+            usage.resource,
+            usage.name,
+            joiner,
+            usage.key,
+            usage.value
+        );
+    }
+}
+
+/// Renders the connectors found for each part of the query
+pub fn render_usages(
+    report: &UsageReport,
+    format: UsageFormat,
+) -> Result<String, ConnectUtilError> {
+    if format == UsageFormat::Json {
+        return Ok(serde_json::to_string_pretty(report)?);
+    }
+    let mut out = String::new();
+    if let Some(topic) = &report.topic {
+        if report.topic_usages.is_empty() {
+            let _ = writeln!(out, "ℹ️  No connectors use topic {}", topic);
+        } else {
+            let _ = writeln!(out, "🔎 Topic {} is used by:", topic);
+            render_usages_text(&mut out, &report.topic_usages, "via");
+        }
+        if !report.unresolved.is_empty() {
+            let _ = writeln!(
+                out,
+                "⚠️  These set their topics from variables or expressions, so they may use {} too:",
+                topic
+            );
+            render_usages_text(&mut out, &report.unresolved, "via");
+        }
+    }
+    if let Some(secret_key) = &report.secret_key {
+        if report.secret_usages.is_empty() {
+            let _ = writeln!(out, "ℹ️  No connectors set {}", secret_key);
+        } else {
+            let _ = writeln!(out, "🔑 {} is set by:", secret_key);
+            render_usages_text(&mut out, &report.secret_usages, "in");
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ConnectorConfig;
+    use std::collections::HashMap;
+
+    fn connector(
+        label: &str,
+        config: &[(&str, &str)],
+        sensitive: &[(&str, &str)],
+    ) -> ModuleConnector {
+        let to_map = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        ModuleConnector {
+            file: PathBuf::from(format!("{}.tf", label)),
+            config: ConnectorConfig {
+                name: label.to_string(),
+                connector_class: "PostgresSink".to_string(),
+                config: to_map(config),
+                sensitive_config: to_map(sensitive),
+            },
+        }
+    }
+
+    #[test]
+    fn test_find_topic_usages() {
+        let connectors = vec![
+            connector(
+                "sink",
+                &[("name", "orders-sink"), ("topics", "refunds, orders.v1")],
+                &[],
+            ),
+            connector("cdc", &[("topic.prefix", "orders")], &[]),
+            connector("regex", &[("topics.regex", "orders\\..*")], &[]),
+            connector(
+                "by_ref",
+                &[("topics", "${confluent_kafka_topic.orders.topic_name}")],
+                &[],
+            ),
+            connector("other", &[("kafka.topic", "payments")], &[]),
+            connector("dynamic", &[("topics", "${var.topics}")], &[]),
+        ];
+        let topics = vec![TopicResource {
+            address: "confluent_kafka_topic.orders".to_string(),
+            file: PathBuf::from("topics.tf"),
+            topic_name: Some("orders.v1".to_string()),
+        }];
+        let report = find_usages(
+            &connectors,
+            &topics,
+            &UsageQuery {
+                topic: Some("orders.v1".to_string()),
+                secret_key: None,
+            },
+        );
+        let found: Vec<(&str, &str)> = report
+            .topic_usages
+            .iter()
+            .map(|usage| (usage.resource.as_str(), usage.key.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("sink", "topics"),
+                ("cdc", "topic.prefix"),
+                ("regex", "topics.regex"),
+                ("by_ref", "topics"),
+            ]
+        );
+        assert_eq!(report.topic_usages[0].name, "orders-sink");
+        assert_eq!(report.unresolved.len(), 1);
+        assert_eq!(report.unresolved[0].resource, "dynamic");
+
+        let text = render_usages(&report, UsageFormat::Text).unwrap();
+        assert!(text.contains("🔎 Topic orders.v1 is used by:"));
+        assert!(text.contains("  sink.tf: sink (orders-sink) via topics = refunds, orders.v1"));
+        assert!(text.contains("may use orders.v1 too"));
+    }
+
+    #[test]
+    fn test_find_secret_usages() {
+        let connectors = vec![
+            connector("cdc", &[], &[("database.password", "${var.db_password}")]),
+            connector("legacy", &[("database.password", "hunter2")], &[]),
+            connector("other", &[], &[("aws.secret.access.key", "${var.aws}")]),
+        ];
+        let report = find_usages(
+            &connectors,
+            &[],
+            &UsageQuery {
+                topic: None,
+                secret_key: Some("database.password".to_string()),
+            },
+        );
+        let found: Vec<(&str, &str, &str)> = report
+            .secret_usages
+            .iter()
+            .map(|usage| {
+                (
+                    usage.resource.as_str(),
+                    usage.key.as_str(),
+                    usage.value.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    "cdc",
+                    "config_sensitive.database.password",
+                    "${var.db_password}"
+                ),
+                (
+                    "legacy",
+                    "config_nonsensitive.database.password",
+                    "(hardcoded)"
+                ),
+            ]
+        );
+        let text = render_usages(&report, UsageFormat::Text).unwrap();
+        assert!(!text.contains("hunter2"));
+        assert!(text.contains("🔑 database.password is set by:"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render_usages(&report, UsageFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["secret_key"], "database.password");
+        assert!(json.get("topic").is_none());
+        assert_eq!(json["secret_usages"][0]["resource"], "cdc");
+    }
+
+    #[test]
+    fn test_usage_format_from_str() {
+        assert_eq!("JSON".parse(), Ok(UsageFormat::Json));
+        assert!("csv".parse::<UsageFormat>().is_err());
+    }
+}